@@ -0,0 +1,116 @@
+#![expect(clippy::unwrap_used, reason = "Ignoring clippy in benchmarks")]
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use temp_dir::TempDir;
+use xdmf::{
+    CellType, DataAttribute, DataMap, DataStorage, FlushPolicy, TimeSeriesDataWriter,
+    TimeSeriesWriter, Values,
+};
+
+const NUM_POINTS: usize = 100_000;
+const NUM_STEPS: usize = 100;
+
+// the inline storages are not used, their XDMF file grows with each iteration of the benchmarks
+fn data_storages() -> Vec<DataStorage> {
+    let mut data_storages = vec![DataStorage::Ascii];
+
+    if xdmf::is_hdf5_enabled() {
+        data_storages.push(DataStorage::Hdf5SingleFile);
+    }
+
+    data_storages
+}
+
+// a strip of triangles, each connecting three consecutive points
+fn create_writer(
+    tmp_dir: &TempDir,
+    data_storage: DataStorage,
+    flush_policy: FlushPolicy,
+) -> TimeSeriesDataWriter {
+    let coords: Vec<f64> = (0..NUM_POINTS * 3).map(|i| i as f64).collect();
+    let connectivity: Vec<u64> = (0..NUM_POINTS as u64 - 2)
+        .flat_map(|i| [i, i + 1, i + 2])
+        .collect();
+    let cell_types = vec![CellType::Triangle; NUM_POINTS - 2];
+
+    TimeSeriesWriter::new(
+        tmp_dir.path().join(format!("{data_storage:?}")),
+        data_storage,
+    )
+    .unwrap()
+    .with_flush_policy(flush_policy)
+    .write_mesh(&coords, (&connectivity, &cell_types))
+    .unwrap()
+}
+
+fn point_data(values: Values<'_>) -> DataMap<'_> {
+    [("pressure".to_string(), (DataAttribute::Scalar, values))]
+        .into_iter()
+        .collect()
+}
+
+// writing the heavy data of a time step, with the fields either copied into the DataMap or borrowed
+fn write_data(c: &mut Criterion) {
+    let mut group = c.benchmark_group("write_data");
+    group.sample_size(10);
+
+    let pressure: Vec<f64> = (0..NUM_POINTS).map(|i| i as f64).collect();
+
+    for data_storage in data_storages() {
+        let tmp_dir = TempDir::new().unwrap();
+        let mut writer = create_writer(&tmp_dir, data_storage, FlushPolicy::OnFinalize);
+        let mut step = 0_usize;
+
+        group.bench_function(
+            BenchmarkId::new("owned", format!("{data_storage:?}")),
+            |b| {
+                b.iter(|| {
+                    step += 1;
+                    let data = point_data(pressure.clone().into());
+                    writer.write_data(&step, Some(&data), None).unwrap();
+                });
+            },
+        );
+
+        group.bench_function(
+            BenchmarkId::new("borrowed", format!("{data_storage:?}")),
+            |b| {
+                b.iter(|| {
+                    step += 1;
+                    let data = point_data(pressure.as_slice().into());
+                    writer.write_data(&step, Some(&data), None).unwrap();
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+// rewriting the XDMF file of a time series with many time steps
+fn flush_xml(c: &mut Criterion) {
+    let mut group = c.benchmark_group("flush_xml");
+
+    let pressure: Vec<f64> = (0..NUM_POINTS).map(|i| i as f64).collect();
+
+    for data_storage in data_storages() {
+        let tmp_dir = TempDir::new().unwrap();
+        let mut writer = create_writer(&tmp_dir, data_storage, FlushPolicy::OnFinalize);
+
+        for step in 0..NUM_STEPS {
+            let data = point_data(pressure.as_slice().into());
+            writer.write_data(&step, Some(&data), None).unwrap();
+        }
+
+        group.bench_function(format!("{data_storage:?}"), |b| {
+            b.iter(|| black_box(&mut writer).flush_xml().unwrap());
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, write_data, flush_xml);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+/* C interface of the xdmf crate, built with `cargo rustc --release --features ffi --crate-type cdylib`.
+ *
+ * All functions return an XdmfStatus, the message of the last error of the calling thread
+ * is available with xdmf_last_error_message. See src/ffi.rs for details.
+ */
+#ifndef XDMF_H
+#define XDMF_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum XdmfStatus {
+    XDMF_OK = 0,
+    XDMF_ERROR_INVALID_ARGUMENT = 1,
+    XDMF_ERROR_IO = 2,
+    XDMF_ERROR_VALIDATION = 3,
+    XDMF_ERROR_UNSUPPORTED = 4,
+    XDMF_ERROR_HDF5 = 5,
+    XDMF_ERROR_SERIALIZATION = 6,
+    XDMF_ERROR_PANIC = 7
+} XdmfStatus;
+
+typedef struct XdmfWriter XdmfWriter;
+
+/* Create a writer of the XDMF file with the given name, the storage is given by its name, e.g. "hdf5-single-file". */
+XdmfStatus xdmf_writer_create(const char* file_name, const char* storage, XdmfWriter** writer);
+
+/* Write the mesh, given by the interleaved coordinates of num_points points and the cells.
+ * The connectivity contains the zero-based indices of the points of the cells. The type of each cell is given
+ * by its id in the mixed topology of XDMF (e.g. 4 for triangles) and its number of points in the connectivity. */
+XdmfStatus xdmf_writer_write_mesh(XdmfWriter* writer, const double* points, size_t num_points,
+                                  const uint64_t* connectivity, size_t connectivity_len,
+                                  const uint64_t* cell_types, const uint64_t* cell_sizes, size_t num_cells);
+
+/* Add a field at the points to the next time step, its len values are copied.
+ * The field has num_components values per point, 1 for scalars, 3 for vectors,
+ * 6 for symmetric tensors (XX, XY, XZ, YY, YZ, ZZ) and 9 for tensors. */
+XdmfStatus xdmf_writer_add_point_data(XdmfWriter* writer, const char* name, const double* values, size_t len,
+                                      size_t num_components);
+
+/* Add a field at the cells to the next time step, see xdmf_writer_add_point_data. */
+XdmfStatus xdmf_writer_add_cell_data(XdmfWriter* writer, const char* name, const double* values, size_t len,
+                                     size_t num_components);
+
+/* Write the added fields as the time step at the given time, they are removed afterwards. */
+XdmfStatus xdmf_writer_write_step(XdmfWriter* writer, double time);
+
+/* Write the XDMF file now. */
+XdmfStatus xdmf_writer_flush(XdmfWriter* writer);
+
+/* Close the writer, which writes the XDMF file a last time, and free it. A null pointer is ignored. */
+XdmfStatus xdmf_writer_finalize(XdmfWriter* writer);
+
+/* Copy the message of the last error of the calling thread into buffer, returning the length of the message.
+ * The message is truncated to size - 1 bytes and nul-terminated. With a null buffer only the length is returned. */
+size_t xdmf_last_error_message(char* buffer, size_t size);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* XDMF_H */
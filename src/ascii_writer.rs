@@ -1,13 +1,22 @@
 //! Implementations of writers for ASCII data storage (inline and in separate files).
 
 use std::{
-    fs::File,
-    io::{BufWriter, Error as IoError, ErrorKind::InvalidFilename, Result as IoResult, Write},
+    collections::{HashMap, HashSet},
+    io::{BufWriter, IntoInnerError, Result as IoResult, Write},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
+use flate2::{Compression, write::GzEncoder};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "parallel")]
+use crate::FieldData;
 use crate::{
-    DataStorage, DataWriter,
+    DataStorage, DataWriter, FileSystem, IndexPrecision, PathPolicy, XdmfError, XdmfResult,
+    check_stream_exhausted,
+    number_format::{FormatNumber, array_to_string_fmt, array_to_writer_fmt, iter_to_writer_fmt},
     values::Values,
     xdmf_elements::{
         attribute,
@@ -15,14 +24,43 @@ use crate::{
     },
 };
 
-pub(crate) struct AsciiInlineWriter {}
+// the text of the indices is the same for all index precisions, only the Precision of their DataItems differs
+pub(crate) struct AsciiInlineWriter {
+    size_limit: Option<usize>,
+    index_precision: IndexPrecision,
+}
 
 impl AsciiInlineWriter {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            size_limit: None,
+            index_precision: IndexPrecision::default(),
+        }
+    }
+
+    fn check_size_limit(&self, name: &str, data: String) -> XdmfResult<DataContent> {
+        check_inline_size_limit(self.size_limit, name, data)
     }
 }
 
+// the inline data is written to the XML file, hence large data would result in huge files
+pub(crate) fn check_inline_size_limit(
+    size_limit: Option<usize>,
+    name: &str,
+    data: String,
+) -> XdmfResult<DataContent> {
+    if let Some(size_limit) = size_limit
+        && data.len() > size_limit
+    {
+        return Err(XdmfError::Validation(format!(
+            "Inline data '{name}' has {} bytes, which exceeds the limit of {size_limit} bytes. Consider using a different DataStorage",
+            data.len()
+        )));
+    }
+
+    Ok(data.into())
+}
+
 impl DataWriter for AsciiInlineWriter {
     fn format(&self) -> Format {
         Format::XML
@@ -36,50 +74,224 @@ impl DataWriter for AsciiInlineWriter {
         &mut self,
         points: &[f64],
         cells: &[u64],
-    ) -> IoResult<(DataContent, DataContent)> {
+    ) -> XdmfResult<(DataContent, DataContent)> {
         Ok((
-            array_to_string_fmt(points).into(),
-            array_to_string_fmt(cells).into(),
+            self.check_size_limit("points", array_to_string_fmt(points))?,
+            self.check_size_limit("cells", array_to_string_fmt(cells))?,
         ))
     }
 
-    fn write_data(
+    fn write_named_mesh(
         &mut self,
         _name: &str,
+        points: &[f64],
+        cells: &[u64],
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        self.write_mesh(points, cells)
+    }
+
+    fn write_mesh_soa(
+        &mut self,
+        _name: Option<&str>,
+        [x, y, z]: [&[f64]; 3],
+        cells: &[u64],
+    ) -> XdmfResult<([DataContent; 3], DataContent)> {
+        Ok((
+            [
+                self.check_size_limit("points_x", array_to_string_fmt(x))?,
+                self.check_size_limit("points_y", array_to_string_fmt(y))?,
+                self.check_size_limit("points_z", array_to_string_fmt(z))?,
+            ],
+            self.check_size_limit("cells", array_to_string_fmt(cells))?,
+        ))
+    }
+
+    fn write_set(&mut self, name: &str, indices: &[u64]) -> XdmfResult<DataContent> {
+        self.check_size_limit(name, array_to_string_fmt(indices))
+    }
+
+    fn write_data(
+        &mut self,
+        name: &str,
         _center: attribute::Center,
         data: &Values,
-    ) -> IoResult<DataContent> {
-        Ok(values_to_string(data).into())
+    ) -> XdmfResult<DataContent> {
+        self.check_size_limit(name, values_to_string(data))
+    }
+
+    #[cfg(feature = "parallel")]
+    fn write_fields(&mut self, fields: &[FieldData<'_>]) -> XdmfResult<Vec<DataContent>> {
+        fields
+            .par_iter()
+            .map(|&(name, _, data, _)| self.check_size_limit(name, values_to_string(data)))
+            .collect()
+    }
+
+    fn write_data_streamed(
+        &mut self,
+        name: &str,
+        _center: attribute::Center,
+        values: &mut dyn Iterator<Item = f64>,
+        len: usize,
+    ) -> XdmfResult<DataContent> {
+        // the inline data is part of the XML file, hence it has to be collected anyways
+        let collected: Vec<f64> = (&mut *values).take(len).collect();
+        check_stream_exhausted(name, values, collected.len(), len)?;
+
+        self.check_size_limit(name, array_to_string_fmt(&collected))
+    }
+
+    fn set_inline_size_limit(&mut self, size_limit: usize) {
+        self.size_limit = Some(size_limit);
+    }
+
+    fn index_precision(&self) -> IndexPrecision {
+        self.index_precision
+    }
+
+    fn set_index_precision(&mut self, index_precision: IndexPrecision) {
+        self.index_precision = index_precision;
     }
 }
 
 /// This writer uses the XML format, but instead of writing the data directly into the xdmf file,
 /// it writes it to a separate file and includes it in the xdmf file using an `xi:include` tag.
+/// Optionally the files are compressed with gzip.
 pub(crate) struct AsciiWriter {
     txt_files_dir: PathBuf,
     folder_name: PathBuf,
     write_time: Option<String>,
+    // names of the data files written for each time step, such that they can be removed
+    step_files: HashMap<String, HashSet<String>>,
+    file_system: Arc<dyn FileSystem>,
+    path_policy: PathPolicy,
+    index_precision: IndexPrecision,
+    gzip: bool,
 }
 
 impl AsciiWriter {
-    pub fn new(file_name: impl AsRef<Path>) -> IoResult<Self> {
+    pub fn new(file_name: impl AsRef<Path>, file_system: Arc<dyn FileSystem>) -> XdmfResult<Self> {
         let txt_files_dir = file_name.as_ref().to_path_buf().with_extension("txt");
 
         let folder_name = txt_files_dir.file_name().ok_or_else(|| {
-            IoError::new(
-                InvalidFilename,
-                "Input file name must have a valid file name",
-            )
+            XdmfError::Validation("Input file name must have a valid file name".into())
         })?;
 
-        crate::mpi_safe_create_dir_all(&txt_files_dir)?;
+        file_system.create_dir_all(&txt_files_dir)?;
 
         Ok(Self {
             folder_name: folder_name.into(),
             txt_files_dir,
             write_time: None,
+            step_files: HashMap::new(),
+            file_system,
+            path_policy: PathPolicy::default(),
+            index_precision: IndexPrecision::default(),
+            gzip: false,
         })
     }
+
+    /// Compress the files with gzip, they are written as `.txt.gz`
+    pub fn with_gzip(mut self) -> Self {
+        self.gzip = true;
+        self
+    }
+
+    fn write_mesh_files(
+        &self,
+        prefix: &str,
+        points: &[f64],
+        cells: &[u64],
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        Ok((
+            self.write_mesh_file(&format!("{prefix}points.txt"), points)?,
+            self.write_mesh_file(&format!("{prefix}cells.txt"), cells)?,
+        ))
+    }
+
+    // create the file for an array of the mesh, e.g. the points or the cells
+    fn write_mesh_file<T: FormatNumber + Copy>(
+        &self,
+        file_name: &str,
+        values: &[T],
+    ) -> XdmfResult<DataContent> {
+        self.write_file(file_name, |file| Ok(array_to_writer_fmt(values, file)?))
+    }
+
+    // create a file in the folder of the txt files and include it, the content is written by the given function
+    fn write_file(
+        &self,
+        file_name: &str,
+        write_content: impl FnOnce(&mut dyn Write) -> XdmfResult<()>,
+    ) -> XdmfResult<DataContent> {
+        let file_name = self.stored_file_name(file_name);
+
+        let file_path = self.txt_files_dir.join(&file_name);
+        let file = self.file_system.create_file(&file_path)?;
+
+        // explicitly flush the buffers to ensure all data is written and errors are caught
+        if self.gzip {
+            let mut encoder = BufWriter::new(GzEncoder::new(file, Compression::default()));
+            write_content(&mut encoder)?;
+            encoder
+                .into_inner()
+                .map_err(IntoInnerError::into_error)?
+                .finish()?
+                .flush()?;
+        } else {
+            let mut file = BufWriter::new(file);
+            write_content(&mut file)?;
+            file.flush()?;
+        }
+
+        let reference_path = self
+            .path_policy
+            .reference_path(&file_path, &self.folder_name.join(file_name))?;
+
+        Ok(XInclude::new(reference_path, true).into())
+    }
+
+    // name of a file as it is stored, i.e. with the extension of compressed files
+    fn stored_file_name(&self, file_name: &str) -> String {
+        if self.gzip {
+            format!("{file_name}.gz")
+        } else {
+            file_name.to_string()
+        }
+    }
+
+    // create the file for the data of the current time step, the content is written by the given function
+    fn write_data_file(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        write_content: impl FnOnce(&mut dyn Write) -> XdmfResult<()>,
+    ) -> XdmfResult<DataContent> {
+        let data_file_name = self.data_file_name(name, center)?;
+
+        self.write_file(&data_file_name, write_content)
+    }
+
+    // name of the file for the data of the current time step, which is recorded such that it can be removed
+    fn data_file_name(&mut self, name: &str, center: attribute::Center) -> XdmfResult<String> {
+        let time = self
+            .write_time
+            .as_ref()
+            .ok_or_else(|| XdmfError::Validation("Writing data was not initialized".into()))?;
+
+        let data_file_name = format!(
+            "data_t_{time}_{}_{name}.txt",
+            attribute::center_to_data_tag(center)
+        );
+
+        let stored_file_name = self.stored_file_name(&data_file_name);
+        self.step_files
+            .entry(time.clone())
+            .or_default()
+            .insert(stored_file_name);
+
+        Ok(data_file_name)
+    }
 }
 
 impl DataWriter for AsciiWriter {
@@ -88,251 +300,192 @@ impl DataWriter for AsciiWriter {
     }
 
     fn data_storage(&self) -> DataStorage {
-        DataStorage::Ascii
+        if self.gzip {
+            DataStorage::AsciiGz
+        } else {
+            DataStorage::Ascii
+        }
+    }
+
+    fn set_path_policy(&mut self, path_policy: PathPolicy) {
+        self.path_policy = path_policy;
+    }
+
+    fn heavy_data_path(&self) -> Option<&Path> {
+        Some(&self.txt_files_dir)
+    }
+
+    fn index_precision(&self) -> IndexPrecision {
+        self.index_precision
+    }
+
+    fn set_index_precision(&mut self, index_precision: IndexPrecision) {
+        self.index_precision = index_precision;
     }
 
     fn write_mesh(
         &mut self,
         points: &[f64],
         cells: &[u64],
-    ) -> IoResult<(DataContent, DataContent)> {
-        // create files for points and cells
-        let points_file_name = "points.txt";
-        let cells_file_name = "cells.txt";
-
-        let mut file_points =
-            BufWriter::new(File::create(self.txt_files_dir.join(points_file_name))?);
-        let mut file_cells =
-            BufWriter::new(File::create(self.txt_files_dir.join(cells_file_name))?);
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        self.write_mesh_files("", points, cells)
+    }
 
-        array_to_writer_fmt(points, &mut file_points)?;
-        array_to_writer_fmt(cells, &mut file_cells)?;
+    fn write_named_mesh(
+        &mut self,
+        name: &str,
+        points: &[f64],
+        cells: &[u64],
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        self.write_mesh_files(&format!("mesh_{name}_"), points, cells)
+    }
 
-        // explicitly flush the buffers to ensure all data is written and errors are caught
-        file_points.flush()?;
-        file_cells.flush()?;
+    fn write_mesh_soa(
+        &mut self,
+        name: Option<&str>,
+        [x, y, z]: [&[f64]; 3],
+        cells: &[u64],
+    ) -> XdmfResult<([DataContent; 3], DataContent)> {
+        let prefix = name.map_or_else(String::new, |name| format!("mesh_{name}_"));
 
         Ok((
-            XInclude::new(
-                self.folder_name.join(points_file_name).to_string_lossy(),
-                true,
-            )
-            .into(),
-            XInclude::new(
-                self.folder_name.join(cells_file_name).to_string_lossy(),
-                true,
-            )
-            .into(),
+            [
+                self.write_mesh_file(&format!("{prefix}points_x.txt"), x)?,
+                self.write_mesh_file(&format!("{prefix}points_y.txt"), y)?,
+                self.write_mesh_file(&format!("{prefix}points_z.txt"), z)?,
+            ],
+            self.write_mesh_file(&format!("{prefix}cells.txt"), cells)?,
         ))
     }
 
+    fn write_set(&mut self, name: &str, indices: &[u64]) -> XdmfResult<DataContent> {
+        self.write_mesh_file(&format!("set_{name}.txt"), indices)
+    }
+
     fn write_data(
         &mut self,
         name: &str,
         center: attribute::Center,
         data: &Values,
-    ) -> IoResult<DataContent> {
-        let time = self
-            .write_time
-            .as_ref()
-            .ok_or_else(|| IoError::other("Writing data was not initialized"))?;
-
-        let data_file_name = format!(
-            "data_t_{time}_{}_{name}.txt",
-            attribute::center_to_data_tag(center)
-        );
-
-        let mut data_file = BufWriter::new(File::create(self.txt_files_dir.join(&data_file_name))?);
-
-        values_to_writer(data, &mut data_file)?;
+    ) -> XdmfResult<DataContent> {
+        self.write_data_file(name, center, |data_file| {
+            Ok(values_to_writer(data, data_file)?)
+        })
+    }
 
-        // explicitly flush the buffers to ensure all data is written and errors are caught
-        data_file.flush()?;
+    #[cfg(feature = "parallel")]
+    fn write_fields(&mut self, fields: &[FieldData<'_>]) -> XdmfResult<Vec<DataContent>> {
+        // the file names are recorded upfront, afterwards the files are written concurrently
+        let data_file_names = fields
+            .iter()
+            .map(|&(name, center, _, _)| self.data_file_name(name, center))
+            .collect::<XdmfResult<Vec<_>>>()?;
+
+        fields
+            .par_iter()
+            .zip(&data_file_names)
+            .map(|(&(_, _, data, _), data_file_name)| {
+                self.write_file(data_file_name, |data_file| {
+                    Ok(values_to_writer(data, data_file)?)
+                })
+            })
+            .collect()
+    }
 
-        Ok(XInclude::new(
-            self.folder_name.join(data_file_name).to_string_lossy(),
-            true,
-        )
-        .into())
+    fn write_data_streamed(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        values: &mut dyn Iterator<Item = f64>,
+        len: usize,
+    ) -> XdmfResult<DataContent> {
+        self.write_data_file(name, center, |data_file| {
+            // the values are written one by one, the BufWriter takes care of writing in chunks
+            let num_written = iter_to_writer_fmt((&mut *values).take(len), data_file)?;
+            check_stream_exhausted(name, values, num_written, len)
+        })
     }
 
-    fn write_data_initialize(&mut self, time: &str) -> IoResult<()> {
+    fn write_data_initialize(&mut self, time: &str) -> XdmfResult<()> {
         if self.write_time.is_some() {
-            return Err(IoError::other("Writing data was already initialized"));
+            return Err(XdmfError::Validation(
+                "Writing data was already initialized".into(),
+            ));
         }
 
         self.write_time = Some(time.to_string());
         Ok(())
     }
 
-    fn write_data_finalize(&mut self) -> IoResult<()> {
+    fn write_data_finalize(&mut self) -> XdmfResult<()> {
         if self.write_time.is_none() {
-            return Err(IoError::other("Writing data was not initialized"));
+            return Err(XdmfError::Validation(
+                "Writing data was not initialized".into(),
+            ));
         }
 
         self.write_time = None;
         Ok(())
     }
-}
 
-pub trait FormatNumber {
-    fn format_number(&self) -> String;
-}
-
-macro_rules! impl_format_number {
-    ($t:ty, $format:expr) => {
-        impl FormatNumber for $t {
-            fn format_number(&self) -> String {
-                format!($format, self)
-            }
+    fn remove_step(&mut self, time: &str) -> XdmfResult<()> {
+        for file_name in self.step_files.remove(time).into_iter().flatten() {
+            self.file_system
+                .remove_file(&self.txt_files_dir.join(file_name))?;
         }
-    };
-}
 
-// Implement FormatNumber for various types
-// taken from meshio
-impl_format_number!(f32, "{:.7e}");
-impl_format_number!(f64, "{:.16e}");
-impl_format_number!(i8, "{}");
-impl_format_number!(i16, "{}");
-impl_format_number!(i32, "{}");
-impl_format_number!(i64, "{}");
-impl_format_number!(isize, "{}");
-impl_format_number!(u8, "{}");
-impl_format_number!(u16, "{}");
-impl_format_number!(u32, "{}");
-impl_format_number!(u64, "{}");
-impl_format_number!(usize, "{}");
-
-/// Generic formatter for arrays of scalar numeric types
-pub fn array_to_string_fmt<T>(vec: &[T]) -> String
-where
-    T: FormatNumber,
-{
-    vec.iter()
-        .map(|elem| elem.format_number())
-        .collect::<Vec<_>>()
-        .join(" ")
-}
-
-/// Generic formatter for arrays of either f64 or i32
-pub fn array_to_writer_fmt<T, W>(vec: &[T], writer: &mut W) -> IoResult<()>
-where
-    T: FormatNumber,
-    W: Write,
-{
-    let mut iter = vec.iter().peekable();
-
-    while let Some(elem) = iter.next() {
-        write!(writer, "{}", elem.format_number())?;
-        if iter.peek().is_some() {
-            write!(writer, " ")?;
-        }
+        Ok(())
     }
-
-    // final newline
-    writeln!(writer)
 }
 
-fn values_to_string(data: &Values) -> String {
+pub(crate) fn values_to_string(data: &Values) -> String {
     match data {
         Values::F64(v) => array_to_string_fmt(v),
+        Values::F32(v) => array_to_string_fmt(v),
         Values::U64(v) => array_to_string_fmt(v),
+        Values::I64(v) => array_to_string_fmt(v),
+        Values::U8(v) => array_to_string_fmt(v),
     }
 }
 
-fn values_to_writer(data: &Values, writer: &mut impl Write) -> IoResult<()> {
+fn values_to_writer(data: &Values, writer: &mut (impl Write + ?Sized)) -> IoResult<()> {
     match data {
         Values::F64(v) => array_to_writer_fmt(v, writer),
+        Values::F32(v) => array_to_writer_fmt(v, writer),
         Values::U64(v) => array_to_writer_fmt(v, writer),
+        Values::I64(v) => array_to_writer_fmt(v, writer),
+        Values::U8(v) => array_to_writer_fmt(v, writer),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::xdmf_elements::data_item::XInclude;
-
-    #[test]
-    fn format_number_all_types() {
-        // floating point numbers
-        let num: f32 = 3.141_590_4;
-        assert_eq!(num.format_number(), "3.1415904e0");
-        let num: f64 = 1.234_567_89;
-        assert_eq!(num.format_number(), "1.2345678899999999e0");
-
-        // signed integer types
-        let num: i8 = -5;
-        assert_eq!(num.format_number(), "-5");
-        let num: i16 = -32768;
-        assert_eq!(num.format_number(), "-32768");
-        let num: i32 = 42;
-        assert_eq!(num.format_number(), "42");
-        let num: i64 = -1_234_567_890_123_456_789;
-        assert_eq!(num.format_number(), "-1234567890123456789");
-        let num: isize = -987_654_321;
-        assert_eq!(num.format_number(), "-987654321");
-
-        // unsigned integer types
-        let num: u8 = 255;
-        assert_eq!(num.format_number(), "255");
-        let num: u16 = 65535;
-        assert_eq!(num.format_number(), "65535");
-        let num: u32 = 4_294_967_295;
-        assert_eq!(num.format_number(), "4294967295");
-        let num: u64 = 1000;
-        assert_eq!(num.format_number(), "1000");
-        let num: usize = 123_456_789;
-        assert_eq!(num.format_number(), "123456789");
-    }
-
-    #[test]
-    fn array_to_string_fmt_multiple_types() {
-        let vec_f64 = vec![1.0, 2.0, 3.0];
-        let result_f64 = array_to_string_fmt(&vec_f64);
-        assert_eq!(
-            result_f64,
-            "1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0"
-        );
-
-        let vec_u64 = vec![1_u64, 2, 3];
-        let result_u64 = array_to_string_fmt(&vec_u64);
-        assert_eq!(result_u64, "1 2 3");
-    }
-
-    #[test]
-    fn array_to_writer_fmt_multiple_types() {
-        let vec_f64 = vec![1.0, 2.0, 3.0];
-        let mut buffer = Vec::new();
-        array_to_writer_fmt(&vec_f64, &mut buffer).unwrap();
-        assert_eq!(
-            String::from_utf8(buffer).unwrap(),
-            "1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0\n"
-        );
-
-        let vec_u64 = vec![1_u64, 2, 3];
-        let mut buffer = Vec::new();
-        array_to_writer_fmt(&vec_u64, &mut buffer).unwrap();
-        assert_eq!(String::from_utf8(buffer).unwrap(), "1 2 3\n");
-    }
+    use crate::{LocalFileSystem, xdmf_elements::data_item::XInclude};
 
     #[test]
     fn values_to_string_multiple_types() {
-        let data_f64 = Values::F64(vec![1.0, 2.0, 3.0]);
+        let data_f64 = Values::from(vec![1.0, 2.0, 3.0]);
         let result_f64 = values_to_string(&data_f64);
         assert_eq!(
             result_f64,
             "1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0"
         );
 
-        let data_u64 = Values::U64(vec![1_u64, 2, 3]);
+        let data_u64 = Values::from(vec![1_u64, 2, 3]);
         let result_u64 = values_to_string(&data_u64);
         assert_eq!(result_u64, "1 2 3");
+
+        let data_i64 = Values::from(vec![-1_i64, 2, 3]);
+        assert_eq!(values_to_string(&data_i64), "-1 2 3");
+
+        let data_u8 = Values::from(vec![0_u8, 1]);
+        assert_eq!(values_to_string(&data_u8), "0 1");
     }
 
     #[test]
     fn values_to_writer_multiple_types() {
-        let data_f64 = Values::F64(vec![1.0, 2.0, 3.0]);
+        let data_f64 = Values::from(vec![1.0, 2.0, 3.0]);
         let mut buffer = Vec::new();
         values_to_writer(&data_f64, &mut buffer).unwrap();
         assert_eq!(
@@ -340,7 +493,7 @@ mod tests {
             "1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0\n"
         );
 
-        let data_u64 = Values::U64(vec![1_u64, 2, 3]);
+        let data_u64 = Values::from(vec![1_u64, 2, 3]);
         let mut buffer = Vec::new();
         values_to_writer(&data_u64, &mut buffer).unwrap();
         assert_eq!(String::from_utf8(buffer).unwrap(), "1 2 3\n");
@@ -377,11 +530,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ascii_inline_writer_size_limit() {
+        let mut writer = AsciiInlineWriter::new();
+        writer.set_inline_size_limit(20);
+
+        let data = vec![1_u64, 2, 3].into();
+        let result = writer
+            .write_data("small", attribute::Center::Node, &data)
+            .unwrap();
+        assert_eq!(result, "1 2 3".into());
+
+        let data = vec![1.0, 2.0, 3.0].into();
+        let err = writer
+            .write_data("large", attribute::Center::Node, &data)
+            .unwrap_err();
+        assert!(matches!(err, XdmfError::Validation(_)));
+        assert_eq!(
+            err.to_string(),
+            "Inline data 'large' has 62 bytes, which exceeds the limit of 20 bytes. Consider using a different DataStorage"
+        );
+
+        let err = writer.write_mesh(&[0.0; 3], &[0]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Inline data 'points' has 62 bytes, which exceeds the limit of 20 bytes. Consider using a different DataStorage"
+        );
+    }
+
     #[test]
     fn ascii_writer_write_data_init_fin() {
         let tmp_dir = temp_dir::TempDir::new().unwrap();
         let file_name = tmp_dir.path().join("sub/folder/test.xdmf");
-        let mut writer = AsciiWriter::new(file_name).unwrap();
+        let mut writer = AsciiWriter::new(file_name, Arc::new(LocalFileSystem)).unwrap();
 
         assert!(writer.write_time.is_none());
 
@@ -394,7 +575,7 @@ mod tests {
         let res_write = writer.write_data(
             "test_data",
             attribute::Center::Node,
-            &Values::F64(vec![1.0, 2.0]),
+            &Values::from(vec![1.0, 2.0]),
         );
         assert_eq!(
             res_write.unwrap_err().to_string(),
@@ -418,7 +599,7 @@ mod tests {
     fn ascii_writer_new() {
         let tmp_dir = temp_dir::TempDir::new().unwrap();
         let file_name = tmp_dir.path().join("sub/folder/test.xdmf");
-        let writer = AsciiWriter::new(&file_name).unwrap();
+        let writer = AsciiWriter::new(&file_name, Arc::new(LocalFileSystem)).unwrap();
         let exp_dir_name = file_name.with_extension("txt");
         assert_eq!(writer.txt_files_dir, exp_dir_name);
         assert!(writer.txt_files_dir.exists());
@@ -431,7 +612,7 @@ mod tests {
     fn ascii_writer_write_mesh() {
         let tmp_dir = temp_dir::TempDir::new().unwrap();
         let file_name = tmp_dir.path().join("sub/folder/test.xdmf");
-        let mut writer = AsciiWriter::new(file_name).unwrap();
+        let mut writer = AsciiWriter::new(file_name, Arc::new(LocalFileSystem)).unwrap();
         let points_file = writer.txt_files_dir.join("points.txt");
         let cells_file = writer.txt_files_dir.join("cells.txt");
         assert!(!points_file.exists());
@@ -464,7 +645,7 @@ mod tests {
     fn ascii_writer_write_data() {
         let tmp_dir = temp_dir::TempDir::new().unwrap();
         let file_name = tmp_dir.path().join("sub/folder/test.xdmf");
-        let mut writer = AsciiWriter::new(file_name).unwrap();
+        let mut writer = AsciiWriter::new(file_name, Arc::new(LocalFileSystem)).unwrap();
         let write_time = "12.258";
         let point_data_name = "dummy_point_data";
         let cell_data_name = "some_cell_data";
@@ -487,7 +668,7 @@ mod tests {
             .write_data(
                 point_data_name,
                 attribute::Center::Node,
-                &Values::F64(data_points),
+                &Values::from(data_points),
             )
             .unwrap();
 
@@ -500,7 +681,7 @@ mod tests {
             .write_data(
                 "some_cell_data",
                 attribute::Center::Cell,
-                &Values::F64(data_cells),
+                &Values::from(data_cells),
             )
             .unwrap();
         assert!(data_file_points.exists());
@@ -533,5 +714,143 @@ mod tests {
             cells_data,
             "-9.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 5.5869999999999997e1\n"
         );
+
+        writer.remove_step(write_time).unwrap();
+        assert!(!data_file_points.exists());
+        assert!(!data_file_cells.exists());
+    }
+    #[test]
+    fn ascii_writer_write_data_streamed() {
+        let tmp_dir = temp_dir::TempDir::new().unwrap();
+        let file_name = tmp_dir.path().join("test.xdmf");
+        let mut writer = AsciiWriter::new(file_name, Arc::new(LocalFileSystem)).unwrap();
+
+        writer.write_data_initialize("1.5").unwrap();
+
+        let data_path = writer
+            .write_data_streamed(
+                "streamed",
+                attribute::Center::Node,
+                &mut (0..3).map(f64::from),
+                3,
+            )
+            .unwrap();
+
+        assert_eq!(
+            data_path,
+            XInclude::new("test.txt/data_t_1.5_point_data_streamed.txt", true).into()
+        );
+        assert_eq!(
+            std::fs::read_to_string(
+                writer
+                    .txt_files_dir
+                    .join("data_t_1.5_point_data_streamed.txt")
+            )
+            .unwrap(),
+            "0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0\n"
+        );
+
+        let err = writer
+            .write_data_streamed("short", attribute::Center::Node, &mut [1.0].into_iter(), 3)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Streamed data 'short' ended after 1 values, but 3 values are expected"
+        );
+
+        let err = writer
+            .write_data_streamed(
+                "long",
+                attribute::Center::Cell,
+                &mut [1.0; 4].into_iter(),
+                3,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Streamed data 'long' has more than the expected 3 values"
+        );
+    }
+
+    #[test]
+    fn ascii_writer_gzip() {
+        let tmp_dir = temp_dir::TempDir::new().unwrap();
+        let file_name = tmp_dir.path().join("test.xdmf");
+        let mut writer = AsciiWriter::new(file_name, Arc::new(LocalFileSystem))
+            .unwrap()
+            .with_gzip();
+
+        assert_eq!(writer.data_storage(), DataStorage::AsciiGz);
+
+        let (points_path, cells_path) = writer.write_mesh(&[0.0, 1.0], &[0, 1]).unwrap();
+        assert_eq!(
+            points_path,
+            XInclude::new("test.txt/points.txt.gz", true).into()
+        );
+        assert_eq!(
+            cells_path,
+            XInclude::new("test.txt/cells.txt.gz", true).into()
+        );
+
+        writer.write_data_initialize("1.5").unwrap();
+        let data_path = writer
+            .write_data(
+                "dummy",
+                attribute::Center::Cell,
+                &Values::from(vec![1.0, 2.0]),
+            )
+            .unwrap();
+        writer.write_data_finalize().unwrap();
+
+        assert_eq!(
+            data_path,
+            XInclude::new("test.txt/data_t_1.5_cell_data_dummy.txt.gz", true).into()
+        );
+
+        // read back the data to verify
+        let read_gz = |file_name: &str| {
+            let mut text = String::new();
+            std::io::Read::read_to_string(
+                &mut flate2::read::GzDecoder::new(
+                    std::fs::File::open(writer.txt_files_dir.join(file_name)).unwrap(),
+                ),
+                &mut text,
+            )
+            .unwrap();
+            text
+        };
+
+        assert_eq!(
+            read_gz("points.txt.gz"),
+            "0.0000000000000000e0 1.0000000000000000e0\n"
+        );
+        assert_eq!(read_gz("cells.txt.gz"), "0 1\n");
+        assert_eq!(
+            read_gz("data_t_1.5_cell_data_dummy.txt.gz"),
+            "1.0000000000000000e0 2.0000000000000000e0\n"
+        );
+    }
+
+    #[test]
+    fn ascii_inline_writer_write_data_streamed() {
+        let mut writer = AsciiInlineWriter::new();
+
+        let result = writer
+            .write_data_streamed(
+                "dummy",
+                attribute::Center::Node,
+                &mut [1.0, 2.0].into_iter(),
+                2,
+            )
+            .unwrap();
+        assert_eq!(result, "1.0000000000000000e0 2.0000000000000000e0".into());
+
+        let err = writer
+            .write_data_streamed("dummy", attribute::Center::Node, &mut [1.0].into_iter(), 2)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Streamed data 'dummy' ended after 1 values, but 2 values are expected"
+        );
     }
 }
@@ -0,0 +1,390 @@
+//! This module contains an async variant of the [`TimeSeriesWriter`], for use in async applications.
+//!
+//! Writing files is blocking, which would stall the async runtime if done on its worker threads.
+//! Hence the writing is done on the blocking thread pool of tokio, which is also what `tokio::fs` does internally.
+//! The writer is configured with a [`TimeSeriesWriterBuilder`], the API of writing mirrors the sync writers,
+//! but takes ownership of the data, since it is moved to the blocking thread.
+
+use std::io::Error as IoError;
+
+use crate::{
+    CellType, DataAttribute, DataMap, StepKey, TimeSeriesDataWriter, TimeSeriesWriter,
+    TimeSeriesWriterBuilder, XdmfError, XdmfResult,
+    xdmf_elements::{attribute, set::SetType},
+};
+
+/// Async writer for time series data in XDMF format.
+pub struct AsyncTimeSeriesWriter {
+    writer: TimeSeriesWriter,
+}
+
+impl AsyncTimeSeriesWriter {
+    /// Create a new `AsyncTimeSeriesWriter` with the options of the given builder.
+    ///
+    /// The options are set with the [`TimeSeriesWriterBuilder`], the files are created on the blocking thread pool.
+    /// See [`TimeSeriesWriterBuilder::build`] for details.
+    /// ```rust
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// use xdmf::{AsyncTimeSeriesWriter, TimeSeriesWriter};
+    /// let xdmf_writer = AsyncTimeSeriesWriter::new(
+    ///     TimeSeriesWriter::builder()
+    ///         .basename("name_async_xdmf_file")
+    ///         .storage(xdmf::DataStorage::Ascii)
+    ///         .statistics(true),
+    /// )
+    /// .await
+    /// .expect("failed to create XDMF writer");
+    ///
+    /// let mut ts_writer = xdmf_writer
+    ///     .write_mesh(vec![0.0; 3], (vec![], vec![]))
+    ///     .await
+    ///     .expect("failed to write mesh");
+    ///
+    /// let point_data = vec![(
+    ///     "point_data".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![1.0].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// ts_writer
+    ///     .write_data("0.0", Some(point_data), None)
+    ///     .await
+    ///     .expect("failed to write time step data");
+    /// # });
+    /// ```
+    pub async fn new(builder: TimeSeriesWriterBuilder) -> XdmfResult<Self> {
+        let writer = run_blocking(move || builder.build()).await?;
+
+        Ok(Self { writer })
+    }
+
+    /// Writes the mesh, returning an `AsyncTimeSeriesDataWriter` for writing time steps.
+    ///
+    /// See [`TimeSeriesWriter::write_mesh`] for details.
+    pub async fn write_mesh(
+        self,
+        points: Vec<f64>,
+        cells: (Vec<u64>, Vec<CellType>),
+    ) -> XdmfResult<AsyncTimeSeriesDataWriter> {
+        let writer = self.writer;
+
+        let ts_writer =
+            run_blocking(move || writer.write_mesh(&points, (&cells.0, &cells.1))).await?;
+
+        Ok(AsyncTimeSeriesDataWriter {
+            writer: Some(ts_writer),
+        })
+    }
+
+    /// Writes the mesh with the coordinates given as separate arrays, returning an `AsyncTimeSeriesDataWriter` for writing time steps.
+    ///
+    /// See [`TimeSeriesWriter::write_mesh_soa`] for details.
+    pub async fn write_mesh_soa(
+        self,
+        coordinates: [Vec<f64>; 3],
+        cells: (Vec<u64>, Vec<CellType>),
+    ) -> XdmfResult<AsyncTimeSeriesDataWriter> {
+        let writer = self.writer;
+
+        let ts_writer = run_blocking(move || {
+            let [x, y, z] = coordinates;
+            writer.write_mesh_soa(&x, &y, &z, (&cells.0, &cells.1))
+        })
+        .await?;
+
+        Ok(AsyncTimeSeriesDataWriter {
+            writer: Some(ts_writer),
+        })
+    }
+
+    /// Writes a point cloud without cells, returning an `AsyncTimeSeriesDataWriter` for writing time steps.
+    ///
+    /// See [`TimeSeriesWriter::write_points`] for details.
+    pub async fn write_points(self, points: Vec<f64>) -> XdmfResult<AsyncTimeSeriesDataWriter> {
+        self.write_mesh(points, (vec![], vec![])).await
+    }
+}
+
+/// Use an already configured writer, e.g. one writing to a [`crate::FileSystem`] or with a progress callback.
+///
+/// Note that the writer was created on the calling thread.
+impl From<TimeSeriesWriter> for AsyncTimeSeriesWriter {
+    fn from(writer: TimeSeriesWriter) -> Self {
+        Self { writer }
+    }
+}
+
+/// Async writer for time series data. Can be used after writing the mesh with `AsyncTimeSeriesWriter::write_mesh`.
+pub struct AsyncTimeSeriesDataWriter {
+    // taken while a write is in progress on the blocking thread
+    writer: Option<TimeSeriesDataWriter>,
+}
+
+impl AsyncTimeSeriesDataWriter {
+    /// Write point and cell data for a specific time step.
+    ///
+    /// See [`TimeSeriesDataWriter::write_data`] for details.
+    /// The data is moved to a blocking thread, hence it must own its values, see [`crate::Values::into_owned`].
+    pub async fn write_data(
+        &mut self,
+        step: impl StepKey + Send + 'static,
+        point_data: Option<DataMap<'static>>,
+        cell_data: Option<DataMap<'static>>,
+    ) -> XdmfResult<()> {
+        self.with_writer(move |writer| {
+            writer.write_data(&step, point_data.as_ref(), cell_data.as_ref())
+        })
+        .await
+    }
+
+    /// Write point and cell data for a time step, writing the heavy data of the fields concurrently.
+    ///
+    /// See [`TimeSeriesDataWriter::write_data_parallel`] for details.
+    #[cfg(feature = "parallel")]
+    pub async fn write_data_parallel(
+        &mut self,
+        step: impl StepKey + Send + 'static,
+        point_data: Option<DataMap<'static>>,
+        cell_data: Option<DataMap<'static>>,
+    ) -> XdmfResult<()> {
+        self.with_writer(move |writer| {
+            writer.write_data_parallel(&step, point_data.as_ref(), cell_data.as_ref())
+        })
+        .await
+    }
+
+    /// Write point and cell data for a time step, replacing the data if the time step has already been written.
+    ///
+    /// See [`TimeSeriesDataWriter::write_data_overwrite`] for details.
+    pub async fn write_data_overwrite(
+        &mut self,
+        step: impl StepKey + Send + 'static,
+        point_data: Option<DataMap<'static>>,
+        cell_data: Option<DataMap<'static>>,
+    ) -> XdmfResult<()> {
+        self.with_writer(move |writer| {
+            writer.write_data_overwrite(&step, point_data.as_ref(), cell_data.as_ref())
+        })
+        .await
+    }
+
+    /// Write global (grid-centered) data for a time step.
+    ///
+    /// See [`TimeSeriesDataWriter::write_global_data`] for details.
+    pub async fn write_global_data(
+        &mut self,
+        step: impl StepKey + Send + 'static,
+        global_data: DataMap<'static>,
+    ) -> XdmfResult<()> {
+        self.with_writer(move |writer| writer.write_global_data(&step, &global_data))
+            .await
+    }
+
+    /// Write point and cell data that is constant in time, e.g. material properties.
+    ///
+    /// See [`TimeSeriesDataWriter::write_static_data`] for details.
+    pub async fn write_static_data(
+        &mut self,
+        point_data: Option<DataMap<'static>>,
+        cell_data: Option<DataMap<'static>>,
+    ) -> XdmfResult<()> {
+        self.with_writer(move |writer| {
+            writer.write_static_data(point_data.as_ref(), cell_data.as_ref())
+        })
+        .await
+    }
+
+    /// Declare the number of edges of the mesh, which is required for writing edge-data.
+    ///
+    /// See [`TimeSeriesDataWriter::set_num_edges`] for details.
+    pub fn set_num_edges(&mut self, num_edges: usize) -> XdmfResult<()> {
+        self.writer
+            .as_mut()
+            .ok_or_else(cancelled_error)?
+            .set_num_edges(num_edges);
+        Ok(())
+    }
+
+    /// Declare the number of faces of the mesh, which is required for writing face-data.
+    ///
+    /// See [`TimeSeriesDataWriter::set_num_faces`] for details.
+    pub fn set_num_faces(&mut self, num_faces: usize) -> XdmfResult<()> {
+        self.writer
+            .as_mut()
+            .ok_or_else(cancelled_error)?
+            .set_num_faces(num_faces);
+        Ok(())
+    }
+
+    /// Write edge- and face-centered data for a time step.
+    ///
+    /// See [`TimeSeriesDataWriter::write_edge_and_face_data`] for details.
+    pub async fn write_edge_and_face_data(
+        &mut self,
+        step: impl StepKey + Send + 'static,
+        edge_data: Option<DataMap<'static>>,
+        face_data: Option<DataMap<'static>>,
+    ) -> XdmfResult<()> {
+        self.with_writer(move |writer| {
+            writer.write_edge_and_face_data(&step, edge_data.as_ref(), face_data.as_ref())
+        })
+        .await
+    }
+
+    /// Register the fields that are written in each time step.
+    ///
+    /// See [`TimeSeriesDataWriter::register_fields`] for details.
+    pub fn register_fields(
+        &mut self,
+        fields: &[(&str, DataAttribute, attribute::Center)],
+    ) -> XdmfResult<()> {
+        self.writer
+            .as_mut()
+            .ok_or_else(cancelled_error)?
+            .register_fields(fields)
+    }
+
+    /// Register a field whose values are computed by the reader from other fields.
+    ///
+    /// See [`TimeSeriesDataWriter::register_derived_field`] for details.
+    pub fn register_derived_field(
+        &mut self,
+        name: &str,
+        data_attribute: DataAttribute,
+        center: attribute::Center,
+        function: &str,
+        fields: &[&str],
+    ) -> XdmfResult<()> {
+        self.writer
+            .as_mut()
+            .ok_or_else(cancelled_error)?
+            .register_derived_field(name, data_attribute, center, function, fields)
+    }
+
+    /// Write a set of points or cells, e.g. the nodes of a boundary condition.
+    ///
+    /// See [`TimeSeriesDataWriter::write_set`] for details.
+    pub async fn write_set(
+        &mut self,
+        name: String,
+        set_type: SetType,
+        indices: Vec<u64>,
+    ) -> XdmfResult<()> {
+        self.with_writer(move |writer| writer.write_set(&name, set_type, &indices))
+            .await
+    }
+
+    /// Write a subset of the cells of the mesh, e.g. a region of interest.
+    ///
+    /// See [`TimeSeriesDataWriter::write_subset`] for details.
+    pub async fn write_subset(&mut self, name: String, cell_indices: Vec<u64>) -> XdmfResult<()> {
+        self.with_writer(move |writer| writer.write_subset(&name, &cell_indices))
+            .await
+    }
+
+    /// Write a submesh of the cells of the mesh, selecting its connectivity and cell data from the mesh.
+    ///
+    /// See [`TimeSeriesDataWriter::write_submesh`] for details.
+    pub async fn write_submesh(&mut self, name: String, cell_indices: Vec<u64>) -> XdmfResult<()> {
+        self.with_writer(move |writer| writer.write_submesh(&name, &cell_indices))
+            .await
+    }
+
+    /// Write data of a time step that is defined only on the entities of a set.
+    ///
+    /// See [`TimeSeriesDataWriter::write_set_data`] for details.
+    pub async fn write_set_data(
+        &mut self,
+        step: impl StepKey + Send + 'static,
+        set_name: String,
+        set_data: DataMap<'static>,
+    ) -> XdmfResult<()> {
+        self.with_writer(move |writer| writer.write_set_data(&step, &set_name, &set_data))
+            .await
+    }
+
+    /// Update the mesh, e.g. after remeshing or adaptive refinement.
+    ///
+    /// See [`TimeSeriesDataWriter::write_mesh_update`] for details.
+    pub async fn write_mesh_update(
+        &mut self,
+        step: impl StepKey + Send + 'static,
+        points: Vec<f64>,
+        cells: (Vec<u64>, Vec<CellType>),
+    ) -> XdmfResult<()> {
+        self.with_writer(move |writer| {
+            writer.write_mesh_update(&step, &points, (&cells.0, &cells.1))
+        })
+        .await
+    }
+
+    /// Write a time step of a point cloud whose number of points changes over time.
+    ///
+    /// See [`TimeSeriesDataWriter::write_particles`] for details.
+    pub async fn write_particles(
+        &mut self,
+        step: impl StepKey + Send + 'static,
+        points: Vec<f64>,
+        point_data: Option<DataMap<'static>>,
+    ) -> XdmfResult<()> {
+        self.with_writer(move |writer| writer.write_particles(&step, &points, point_data.as_ref()))
+            .await
+    }
+
+    /// Write the XDMF file now, independent of the [`FlushPolicy`](crate::FlushPolicy).
+    ///
+    /// See [`TimeSeriesDataWriter::flush_xml`] for details.
+    pub async fn flush_xml(&mut self) -> XdmfResult<()> {
+        self.with_writer(TimeSeriesDataWriter::flush_xml).await
+    }
+
+    /// Finish writing, equivalent to [`AsyncTimeSeriesDataWriter::close`].
+    pub async fn finalize(self) -> XdmfResult<()> {
+        self.close().await
+    }
+
+    /// Close the writer, which finishes writing the heavy data and writes the XDMF file a last time.
+    ///
+    /// See [`TimeSeriesDataWriter::close`] for details. Note that dropping the writer without closing it
+    /// closes it on the current thread, i.e. blocking the async runtime.
+    pub async fn close(mut self) -> XdmfResult<()> {
+        let writer = self.writer.take().ok_or_else(cancelled_error)?;
+
+        run_blocking(move || writer.close()).await
+    }
+
+    // run the given function with the writer on the blocking thread pool
+    async fn with_writer(
+        &mut self,
+        func: impl FnOnce(&mut TimeSeriesDataWriter) -> XdmfResult<()> + Send + 'static,
+    ) -> XdmfResult<()> {
+        // the writer is lost if a previous write was cancelled, as its state is unknown
+        let mut writer = self.writer.take().ok_or_else(cancelled_error)?;
+
+        let (writer, result) = run_blocking(move || {
+            let result = func(&mut writer);
+            Ok((writer, result))
+        })
+        .await?;
+
+        self.writer = Some(writer);
+
+        result
+    }
+}
+
+// error of a writer that was lost, because a previous write was cancelled
+fn cancelled_error() -> XdmfError {
+    XdmfError::Validation(
+        "A previous write was cancelled, the writer can not be used anymore".into(),
+    )
+}
+
+async fn run_blocking<T: Send + 'static>(
+    func: impl FnOnce() -> XdmfResult<T> + Send + 'static,
+) -> XdmfResult<T> {
+    tokio::task::spawn_blocking(func)
+        .await
+        .map_err(|err| XdmfError::Io(IoError::other(err)))?
+}
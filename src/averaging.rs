@@ -0,0 +1,171 @@
+//! This module contains the averaging of point data to the cells and of cell data to the points of a mesh.
+//!
+//! It is used for the fields requested with [`TimeSeriesWriter::with_cell_average`](crate::TimeSeriesWriter::with_cell_average)
+//! and [`TimeSeriesWriter::with_point_average`](crate::TimeSeriesWriter::with_point_average), e.g. for threshold filters
+//! that only work on cell data. The points of the cells are kept for this, in a single contiguous array.
+
+use crate::{
+    CellType, Values, XdmfResult, time_series_writer::face_stream_points,
+    xdmf_elements::attribute::Center,
+};
+
+/// Field that is computed by averaging a field of the other center.
+#[derive(Clone, Debug)]
+pub(crate) struct AveragedField {
+    pub source: String,
+    pub name: String,
+    // center of the averaged field, the source field has the other center
+    pub center: Center,
+}
+
+/// Points of the cells of a mesh, the points of cell `i` are `points[offsets[i]..offsets[i + 1]]`.
+pub(crate) struct CellPoints {
+    num_points: usize,
+    offsets: Vec<usize>,
+    points: Vec<usize>,
+}
+
+impl CellPoints {
+    // the cells must be validated before, a mesh without cells consists of a vertex per point
+    pub(crate) fn new(
+        num_points: usize,
+        (connectivity, cell_types): (&[u64], &[CellType]),
+    ) -> XdmfResult<Self> {
+        if cell_types.is_empty() {
+            return Ok(Self {
+                num_points,
+                offsets: (0..=num_points).collect(),
+                points: (0..num_points).collect(),
+            });
+        }
+
+        let mut offsets = Vec::with_capacity(cell_types.len() + 1);
+        let mut points = Vec::with_capacity(connectivity.len());
+        offsets.push(0);
+
+        let mut index = 0;
+        for cell_type in cell_types {
+            let cell = &connectivity[index..index + cell_type.num_points()];
+
+            if let CellType::Polyhedron(_) = cell_type {
+                // the points of a polyhedron are part of multiple faces, each is counted once
+                let mut cell_points = face_stream_points(cell)?;
+                cell_points.sort_unstable();
+                cell_points.dedup();
+                points.extend(cell_points.iter().map(|&point| point as usize));
+            } else {
+                points.extend(cell.iter().map(|&point| point as usize));
+            }
+
+            offsets.push(points.len());
+            index += cell.len();
+        }
+
+        Ok(Self {
+            num_points,
+            offsets,
+            points,
+        })
+    }
+
+    // the average of the values of the points of each cell, with `num_components` values per point
+    pub(crate) fn cell_averages(&self, values: &Values, num_components: usize) -> Vec<f64> {
+        let values = values.to_f64();
+        let mut averages = Vec::with_capacity((self.offsets.len() - 1) * num_components);
+
+        for range in self.offsets.windows(2) {
+            let cell_points = &self.points[range[0]..range[1]];
+            let start = averages.len();
+            averages.resize(start + num_components, 0.0);
+
+            for &point in cell_points {
+                let point_values = &values[point * num_components..(point + 1) * num_components];
+                for (average, value) in averages[start..].iter_mut().zip(point_values) {
+                    *average += value;
+                }
+            }
+
+            if !cell_points.is_empty() {
+                for average in &mut averages[start..] {
+                    *average /= cell_points.len() as f64;
+                }
+            }
+        }
+
+        averages
+    }
+
+    // the average of the values of the cells of each point in a single pass over the cells,
+    // with `num_components` values per cell, points without cells are zero
+    pub(crate) fn point_averages(&self, values: &Values, num_components: usize) -> Vec<f64> {
+        let values = values.to_f64();
+        let mut sums = vec![0.0; self.num_points * num_components];
+        let mut counts = vec![0_usize; self.num_points];
+
+        for (cell, range) in self.offsets.windows(2).enumerate() {
+            let cell_values = &values[cell * num_components..(cell + 1) * num_components];
+
+            for &point in &self.points[range[0]..range[1]] {
+                counts[point] += 1;
+                let point_sums = &mut sums[point * num_components..(point + 1) * num_components];
+                for (sum, value) in point_sums.iter_mut().zip(cell_values) {
+                    *sum += value;
+                }
+            }
+        }
+
+        for (point_sums, &count) in sums.chunks_exact_mut(num_components).zip(&counts) {
+            if count > 0 {
+                for sum in point_sums {
+                    *sum /= count as f64;
+                }
+            }
+        }
+
+        sums
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn average_between_points_and_cells() {
+        // a triangle and an edge sharing a point, and an unused point
+        let cell_points =
+            CellPoints::new(5, (&[0, 1, 2, 2, 3], &[CellType::Triangle, CellType::Edge])).unwrap();
+
+        let point_values = Values::from(vec![0_u64, 3, 6, 10, 100]);
+        assert_eq!(cell_points.cell_averages(&point_values, 1), [3.0, 8.0]);
+
+        let cell_values = Values::from(vec![1.0, -1.0, 3.0, 1.0]);
+        assert_eq!(
+            cell_points.point_averages(&cell_values, 2),
+            [1.0, -1.0, 1.0, -1.0, 2.0, 0.0, 3.0, 1.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn average_polyhedron_and_vertices() {
+        let (polyhedron, face_stream) = CellType::polyhedron(&[
+            &[0, 1, 2, 3],
+            &[0, 1, 4],
+            &[1, 2, 4],
+            &[2, 3, 4],
+            &[3, 0, 4],
+        ]);
+        let cell_points = CellPoints::new(5, (&face_stream, &[polyhedron])).unwrap();
+
+        // each point is counted once, although it is part of multiple faces
+        let point_values = Values::from(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(cell_points.cell_averages(&point_values, 1), [3.0]);
+
+        // without cells each point is a vertex
+        let cell_points = CellPoints::new(2, (&[], &[])).unwrap();
+        assert_eq!(
+            cell_points.point_averages(&Values::from(vec![4.0_f32, 5.0]), 1),
+            [4.0, 5.0]
+        );
+    }
+}
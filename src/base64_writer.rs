@@ -0,0 +1,236 @@
+//! Implementation of the writer for base64 encoded data, stored inline in the XDMF file.
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "parallel")]
+use crate::FieldData;
+use crate::{
+    DataStorage, DataWriter, IndexPrecision, XdmfResult,
+    ascii_writer::check_inline_size_limit,
+    check_stream_exhausted,
+    values::Values,
+    xdmf_elements::{
+        attribute,
+        data_item::{DataContent, Encoding, Format},
+    },
+};
+
+/// This writer stores the values as little-endian bytes, encoded as base64 inline in the XDMF file.
+/// This results in a single self-contained file, which is more compact than ASCII text.
+pub(crate) struct Base64InlineWriter {
+    size_limit: Option<usize>,
+    index_precision: IndexPrecision,
+}
+
+impl Base64InlineWriter {
+    pub fn new() -> Self {
+        Self {
+            size_limit: None,
+            index_precision: IndexPrecision::default(),
+        }
+    }
+
+    fn check_size_limit(&self, name: &str, data: String) -> XdmfResult<DataContent> {
+        check_inline_size_limit(self.size_limit, name, data)
+    }
+
+    // encode the indices with the bytes of their resolved precision
+    fn check_size_limit_indices(&self, name: &str, indices: &[u64]) -> XdmfResult<DataContent> {
+        let data = if self.index_precision.resolve(indices)? == 4 {
+            // the indices were checked to fit, hence the truncation is lossless
+            encode(indices.iter().map(|&v| (v as u32).to_le_bytes()))
+        } else {
+            encode(indices.iter().map(|v| v.to_le_bytes()))
+        };
+
+        self.check_size_limit(name, data)
+    }
+}
+
+impl DataWriter for Base64InlineWriter {
+    fn format(&self) -> Format {
+        Format::XML
+    }
+
+    fn encoding(&self) -> Option<Encoding> {
+        Some(Encoding::Base64)
+    }
+
+    fn data_storage(&self) -> DataStorage {
+        DataStorage::Base64Inline
+    }
+
+    fn write_mesh(
+        &mut self,
+        points: &[f64],
+        cells: &[u64],
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        Ok((
+            self.check_size_limit("points", encode(points.iter().map(|v| v.to_le_bytes())))?,
+            self.check_size_limit_indices("cells", cells)?,
+        ))
+    }
+
+    fn write_named_mesh(
+        &mut self,
+        _name: &str,
+        points: &[f64],
+        cells: &[u64],
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        self.write_mesh(points, cells)
+    }
+
+    fn write_mesh_soa(
+        &mut self,
+        _name: Option<&str>,
+        [x, y, z]: [&[f64]; 3],
+        cells: &[u64],
+    ) -> XdmfResult<([DataContent; 3], DataContent)> {
+        Ok((
+            [
+                self.check_size_limit("points_x", encode(x.iter().map(|v| v.to_le_bytes())))?,
+                self.check_size_limit("points_y", encode(y.iter().map(|v| v.to_le_bytes())))?,
+                self.check_size_limit("points_z", encode(z.iter().map(|v| v.to_le_bytes())))?,
+            ],
+            self.check_size_limit_indices("cells", cells)?,
+        ))
+    }
+
+    fn write_set(&mut self, name: &str, indices: &[u64]) -> XdmfResult<DataContent> {
+        self.check_size_limit_indices(name, indices)
+    }
+
+    fn write_data(
+        &mut self,
+        name: &str,
+        _center: attribute::Center,
+        data: &Values,
+    ) -> XdmfResult<DataContent> {
+        self.check_size_limit(name, values_to_base64(data))
+    }
+
+    #[cfg(feature = "parallel")]
+    fn write_fields(&mut self, fields: &[FieldData<'_>]) -> XdmfResult<Vec<DataContent>> {
+        fields
+            .par_iter()
+            .map(|&(name, _, data, _)| self.check_size_limit(name, values_to_base64(data)))
+            .collect()
+    }
+
+    fn write_data_streamed(
+        &mut self,
+        name: &str,
+        _center: attribute::Center,
+        values: &mut dyn Iterator<Item = f64>,
+        len: usize,
+    ) -> XdmfResult<DataContent> {
+        // the inline data is part of the XML file, hence it has to be collected anyways
+        let collected: Vec<f64> = (&mut *values).take(len).collect();
+        check_stream_exhausted(name, values, collected.len(), len)?;
+
+        self.check_size_limit(name, encode(collected.iter().map(|v| v.to_le_bytes())))
+    }
+
+    fn set_inline_size_limit(&mut self, size_limit: usize) {
+        self.size_limit = Some(size_limit);
+    }
+
+    fn index_precision(&self) -> IndexPrecision {
+        self.index_precision
+    }
+
+    fn set_index_precision(&mut self, index_precision: IndexPrecision) {
+        self.index_precision = index_precision;
+    }
+}
+
+fn values_to_base64(data: &Values) -> String {
+    match data {
+        Values::F64(v) => encode(v.iter().map(|v| v.to_le_bytes())),
+        Values::F32(v) => encode(v.iter().map(|v| v.to_le_bytes())),
+        Values::U64(v) => encode(v.iter().map(|v| v.to_le_bytes())),
+        Values::I64(v) => encode(v.iter().map(|v| v.to_le_bytes())),
+        Values::U8(v) => encode(v.iter().map(|v| v.to_le_bytes())),
+    }
+}
+
+// encode the bytes of all values at once, as base64 can not be concatenated
+fn encode<const N: usize>(bytes: impl Iterator<Item = [u8; N]>) -> String {
+    STANDARD.encode(bytes.flatten().collect::<Vec<u8>>())
+}
+
+/// Decode base64 encoded data into its bytes, e.g. for validating the number of values.
+pub(crate) fn decode(data: &str) -> Option<Vec<u8>> {
+    STANDARD.decode(data.trim()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_inline_writer_write_mesh() {
+        let mut writer = Base64InlineWriter::new();
+
+        let (points, cells) = writer.write_mesh(&[1.0, 2.0], &[0, 1]).unwrap();
+        pretty_assertions::assert_eq!(points, "AAAAAAAA8D8AAAAAAAAAQA==".into());
+        pretty_assertions::assert_eq!(cells, "AAAAAAEAAAA=".into());
+
+        writer.set_index_precision(IndexPrecision::U64);
+        let (_, cells) = writer.write_mesh(&[1.0, 2.0], &[0, 1]).unwrap();
+        pretty_assertions::assert_eq!(cells, "AAAAAAAAAAABAAAAAAAAAA==".into());
+
+        writer.set_index_precision(IndexPrecision::U32);
+        assert_eq!(
+            writer
+                .write_set("large", &[u64::from(u32::MAX) + 1])
+                .unwrap_err()
+                .to_string(),
+            "Indices can not be written as u32, the largest index 4294967296 exceeds 4294967295"
+        );
+    }
+
+    #[test]
+    fn base64_inline_writer_write_data_roundtrip() {
+        let mut writer = Base64InlineWriter::new();
+        let raw_data = vec![-1.5, 0.0, 3.25e10];
+
+        let result = writer
+            .write_data("dummy", attribute::Center::Node, &raw_data.clone().into())
+            .unwrap();
+
+        let DataContent::Raw(text) = result else {
+            panic!("data must be written inline");
+        };
+
+        let decoded: Vec<f64> = decode(&text)
+            .unwrap()
+            .chunks_exact(8)
+            .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        assert_eq!(decoded, raw_data);
+    }
+
+    #[test]
+    fn base64_inline_writer_size_limit() {
+        let mut writer = Base64InlineWriter::new();
+        writer.set_inline_size_limit(20);
+
+        let data = vec![1_u64].into();
+        writer
+            .write_data("small", attribute::Center::Node, &data)
+            .unwrap();
+
+        let data = vec![1.0, 2.0, 3.0].into();
+        assert_eq!(
+            writer
+                .write_data("large", attribute::Center::Node, &data)
+                .unwrap_err()
+                .to_string(),
+            "Inline data 'large' has 32 bytes, which exceeds the limit of 20 bytes. Consider using a different DataStorage"
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! Command line tool for inspecting XDMF files, e.g. for debugging broken outputs on clusters.
+//!
+//! Prints a summary of the grids, time steps, attributes and referenced heavy data files of a file.
+//! With `--validate`, the consistency checks of [`xdmf::validate`] are run as well.
+#![expect(
+    clippy::use_debug,
+    reason = "The types of the XDMF elements are printed with their Debug names, as in the validation messages"
+)]
+
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::{BufReader, Write},
+    path::Path,
+    process::ExitCode,
+};
+
+use xdmf::{
+    XdmfResult,
+    validate::validate,
+    xdmf_elements::{
+        Xdmf,
+        attribute::Attribute,
+        data_item::{DataContent, DataItem, Format},
+        grid::{Grid, GridType, Time},
+    },
+};
+
+const USAGE: &str = "Usage: xdmf-tool <file> [--validate]
+
+Prints a summary of an XDMF file: grids, time steps, attributes and referenced heavy data files.
+
+Options:
+    --validate  Check the consistency of the file and its heavy data
+    --help      Print this help";
+
+fn main() -> ExitCode {
+    let mut file_name = None;
+    let mut run_validation = false;
+
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--validate" => run_validation = true,
+            "--help" | "-h" => return report(writeln!(std::io::stdout(), "{USAGE}")),
+            _ if arg.starts_with('-') || file_name.is_some() => {
+                return usage_error(&format!("Unexpected argument '{arg}'"));
+            }
+            _ => file_name = Some(arg),
+        }
+    }
+
+    let Some(file_name) = file_name else {
+        return usage_error("The XDMF file must be given");
+    };
+
+    match run(
+        Path::new(&file_name),
+        run_validation,
+        &mut std::io::stdout().lock(),
+    ) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            let _ = writeln!(std::io::stderr(), "Error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage_error(message: &str) -> ExitCode {
+    let _ = writeln!(std::io::stderr(), "{message}\n\n{USAGE}");
+    ExitCode::FAILURE
+}
+
+fn report(result: std::io::Result<()>) -> ExitCode {
+    if result.is_ok() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+// print the summary (and the validation report), returns whether the file is valid
+fn run(file_name: &Path, run_validation: bool, out: &mut impl Write) -> XdmfResult<bool> {
+    let xdmf = Xdmf::read_from(BufReader::new(File::open(file_name)?))?;
+    let base_dir = file_name.parent().unwrap_or_else(|| Path::new(""));
+
+    writeln!(out, "File: {}", file_name.display())?;
+
+    if !xdmf.information.is_empty() {
+        writeln!(out, "Information:")?;
+        for information in &xdmf.information {
+            writeln!(out, "    {}: {}", information.name, information.value)?;
+        }
+    }
+
+    let mut heavy_data_files = BTreeSet::new();
+
+    for domain in &xdmf.domains {
+        if let Some(name) = &domain.name {
+            writeln!(out, "Domain '{name}':")?;
+        }
+        writeln!(out, "Grids:")?;
+        for grid in &domain.grids {
+            write_grid(out, grid, 1, &mut heavy_data_files)?;
+        }
+
+        for data_item in &domain.data_items {
+            collect_heavy_data_files(data_item, &mut heavy_data_files);
+        }
+
+        for include in &domain.includes {
+            heavy_data_files.insert(include.file_path().to_string());
+        }
+    }
+
+    if !heavy_data_files.is_empty() {
+        writeln!(out, "Heavy data files:")?;
+        for heavy_data_file in &heavy_data_files {
+            let state = if base_dir.join(heavy_data_file).is_file() {
+                ""
+            } else {
+                " (missing)"
+            };
+            writeln!(out, "    {heavy_data_file}{state}")?;
+        }
+    }
+
+    if !run_validation {
+        return Ok(true);
+    }
+
+    let validation_report = validate(&xdmf, base_dir);
+
+    writeln!(out, "Validation:")?;
+    for issue in validation_report.issues() {
+        writeln!(out, "    {issue}")?;
+    }
+    writeln!(
+        out,
+        "    {} errors, {} warnings",
+        validation_report.errors().count(),
+        validation_report.warnings().count()
+    )?;
+
+    Ok(validation_report.is_valid())
+}
+
+fn write_grid(
+    out: &mut impl Write,
+    grid: &Grid,
+    level: usize,
+    heavy_data_files: &mut BTreeSet<String>,
+) -> XdmfResult<()> {
+    let indent = "    ".repeat(level);
+
+    let mut description = format!("{:?}", grid.grid_type);
+    if let Some(collection_type) = grid.collection_type {
+        description.push_str(&format!(" {collection_type:?}"));
+    }
+    if let Some(section) = grid.section {
+        description.push_str(&format!(" {section:?}"));
+    }
+    if let Some(reference_path) = &grid.reference_path {
+        description.push_str(&format!(", reference {}", reference_path.trim()));
+    }
+
+    let children = grid.grids.as_deref().unwrap_or_default();
+    if grid.grid_type == GridType::Collection {
+        // the times are given either as list by the collection, or by each of its grids
+        let times = grid.time.as_ref().map_or_else(
+            || {
+                children
+                    .iter()
+                    .filter_map(|child| child.time.as_ref())
+                    .flat_map(time_values)
+                    .collect()
+            },
+            time_values,
+        );
+
+        if let (Some(first), Some(last)) = (times.first(), times.last()) {
+            description.push_str(&format!(", {} time steps: {first} .. {last}", times.len()));
+        }
+    } else if let Some(time) = &grid.time {
+        description.push_str(&format!(", time {}", time_values(time).join(" ")));
+    }
+
+    if let Some(cycle) = grid.cycle() {
+        description.push_str(&format!(", cycle {cycle}"));
+    }
+
+    writeln!(out, "{indent}{} ({description})", grid.name)?;
+
+    if let Some(topology) = &grid.topology {
+        let size = topology.dimensions.as_ref().map_or_else(
+            || format!("{} elements", topology.number_of_elements),
+            |dimensions| format!("dimensions {}", format_dimensions(&dimensions.0)),
+        );
+        writeln!(
+            out,
+            "{indent}    Topology: {:?}, {size}",
+            topology.topology_type
+        )?;
+
+        if let Some(data_item) = &topology.data_item {
+            collect_heavy_data_files(data_item, heavy_data_files);
+        }
+    }
+
+    if let Some(geometry) = &grid.geometry {
+        writeln!(out, "{indent}    Geometry: {:?}", geometry.geometry_type)?;
+
+        for data_item in &geometry.data_items {
+            collect_heavy_data_files(data_item, heavy_data_files);
+        }
+    }
+
+    for attribute in grid.attributes.iter().flatten() {
+        write_attribute(out, attribute, &format!("{indent}    "), heavy_data_files)?;
+    }
+
+    for set in grid.sets.iter().flatten() {
+        writeln!(out, "{indent}    Set '{}' ({:?})", set.name, set.set_type)?;
+
+        for data_item in &set.data_items {
+            collect_heavy_data_files(data_item, heavy_data_files);
+        }
+
+        for attribute in &set.attributes {
+            write_attribute(
+                out,
+                attribute,
+                &format!("{indent}        "),
+                heavy_data_files,
+            )?;
+        }
+    }
+
+    for include in grid.includes.iter().flatten() {
+        writeln!(out, "{indent}    Included: {}", include.file_path())?;
+        heavy_data_files.insert(include.file_path().to_string());
+    }
+
+    for child in children {
+        write_grid(out, child, level + 1, heavy_data_files)?;
+    }
+
+    Ok(())
+}
+
+fn write_attribute(
+    out: &mut impl Write,
+    attribute: &Attribute,
+    indent: &str,
+    heavy_data_files: &mut BTreeSet<String>,
+) -> XdmfResult<()> {
+    let dimensions = attribute
+        .data_items
+        .iter()
+        .map(describe_dimensions)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    writeln!(
+        out,
+        "{indent}Attribute '{}' ({:?}, {:?}): {dimensions}",
+        attribute.name, attribute.attribute_type, attribute.center
+    )?;
+
+    for data_item in &attribute.data_items {
+        collect_heavy_data_files(data_item, heavy_data_files);
+    }
+
+    Ok(())
+}
+
+// the values of a time, either a single value or a list
+fn time_values(time: &Time) -> Vec<String> {
+    match (&time.value, &time.data_item) {
+        (Some(value), _) => vec![value.clone()],
+        (
+            None,
+            Some(DataItem {
+                data: DataContent::Raw(text),
+                ..
+            }),
+        ) => text.split_whitespace().map(str::to_string).collect(),
+        (None, _) => vec![],
+    }
+}
+
+fn describe_dimensions(data_item: &DataItem) -> String {
+    match (&data_item.dimensions, &data_item.reference) {
+        (Some(dimensions), _) => format_dimensions(&dimensions.0),
+        (None, Some(reference)) => format!("reference to {reference}"),
+        (None, None) => "unknown dimensions".to_string(),
+    }
+}
+
+fn format_dimensions(dimensions: &[usize]) -> String {
+    dimensions
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" x ")
+}
+
+// files of the heavy data, i.e. included files and HDF5 files
+fn collect_heavy_data_files(data_item: &DataItem, heavy_data_files: &mut BTreeSet<String>) {
+    match (data_item.format.unwrap_or_default(), &data_item.data) {
+        (_, DataContent::Include(include)) => {
+            heavy_data_files.insert(include.file_path().to_string());
+        }
+        (Format::HDF, DataContent::Raw(text)) => {
+            if let Some((file_name, _)) = text.trim().split_once(':') {
+                heavy_data_files.insert(file_name.to_string());
+            }
+        }
+        (Format::XML | Format::Binary, DataContent::Raw(_)) => {}
+    }
+
+    for child in &data_item.data_items {
+        collect_heavy_data_files(child, heavy_data_files);
+    }
+}
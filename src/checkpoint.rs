@@ -0,0 +1,348 @@
+//! Checkpoints for restarting simulations, storing the mesh and selected fields of a time step losslessly in HDF5.
+//!
+//! Other than the files for visualization, the checkpoint contains everything to restore the state of a simulation:
+//! the points, the cells with their types, and the fields with their number types and attributes.
+//! Multiple time steps can be stored in the same file, each in its own group:
+//! ```text
+//! checkpoint.h5
+//! └── t_<time>
+//!     ├── mesh
+//!     │   ├── points
+//!     │   ├── connectivity
+//!     │   ├── cell_types
+//!     │   └── cell_sizes
+//!     ├── point_data
+//!     │   └── <field>
+//!     └── cell_data
+//!         └── <field>
+//! ```
+//! ```rust
+//! use xdmf::checkpoint::Checkpoint;
+//!
+//! let point_data = vec![(
+//!     "temperature".to_string(),
+//!     (xdmf::DataAttribute::Scalar, vec![20.0, 21.5].into()),
+//! )]
+//! .into_iter()
+//! .collect();
+//!
+//! Checkpoint::write(
+//!     "restart",
+//!     0.5,
+//!     &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+//!     (&[0, 1], &[xdmf::CellType::Edge]),
+//!     Some(&point_data),
+//!     None,
+//! )
+//! .expect("failed to write checkpoint");
+//!
+//! let checkpoint = Checkpoint::load("restart", 0.5).expect("failed to load checkpoint");
+//! assert_eq!(checkpoint.point_data, point_data);
+//! # std::fs::remove_file("restart.h5").expect("failed to remove checkpoint");
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use hdf5::{File as H5File, Group as H5Group, H5Type};
+
+use crate::{
+    CellType, DataAttribute, DataMap, Values, XdmfError, XdmfResult,
+    time_series_writer::{validate_data, validate_points_and_cells},
+    xdmf_elements::attribute,
+};
+
+const MESH: &str = "mesh";
+const POINTS: &str = "points";
+const CONNECTIVITY: &str = "connectivity";
+const CELL_TYPES: &str = "cell_types";
+const CELL_SIZES: &str = "cell_sizes";
+const DATA_ATTRIBUTE: &str = "data_attribute";
+const SHAPE: &str = "shape";
+
+/// State of a simulation at a time step, as written with [`Checkpoint::write`] and read with [`Checkpoint::load`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Checkpoint {
+    /// Time of the checkpoint.
+    pub time: f64,
+    /// Coordinates of the points, three per point.
+    pub points: Vec<f64>,
+    /// Connectivity of the cells.
+    pub connectivity: Vec<u64>,
+    /// Types of the cells.
+    pub cell_types: Vec<CellType>,
+    /// Fields defined on the points.
+    pub point_data: DataMap<'static>,
+    /// Fields defined on the cells.
+    pub cell_data: DataMap<'static>,
+}
+
+impl Checkpoint {
+    /// Write the mesh and the given fields at a time step into the checkpoint file (with extension `.h5`).
+    ///
+    /// The file is created if it does not exist, otherwise the time step is added.
+    /// An existing checkpoint of the same time is replaced.
+    pub fn write(
+        file_name: impl AsRef<Path>,
+        time: f64,
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+        point_data: Option<&DataMap>,
+        cell_data: Option<&DataMap>,
+    ) -> XdmfResult<()> {
+        if !time.is_finite() {
+            return Err(XdmfError::Validation(format!(
+                "Time must be a valid float, and not '{time}'"
+            )));
+        }
+
+        validate_points_and_cells(points, cells)?;
+        validate_data((points.len() / 3, cells.1.len()), point_data, cell_data)?;
+
+        let h5_file_name = checkpoint_file_name(file_name.as_ref());
+        if let Some(parent) = h5_file_name.parent() {
+            crate::mpi_safe_create_dir_all(parent)?;
+        }
+
+        let h5_file = H5File::append(&h5_file_name)?;
+
+        let group_name = time_group_name(time);
+        if h5_file.link_exists(&group_name) {
+            h5_file.unlink(&group_name)?;
+        }
+        let time_group = h5_file.create_group(&group_name)?;
+
+        let (connectivity, cell_types) = cells;
+        let mesh_group = time_group.create_group(MESH)?;
+        write_dataset(&mesh_group, POINTS, points)?;
+        write_dataset(&mesh_group, CONNECTIVITY, connectivity)?;
+        write_dataset(
+            &mesh_group,
+            CELL_TYPES,
+            &cell_types
+                .iter()
+                .map(|cell_type| cell_type.xdmf_id())
+                .collect::<Vec<_>>(),
+        )?;
+        write_dataset(
+            &mesh_group,
+            CELL_SIZES,
+            &cell_types
+                .iter()
+                .map(|cell_type| cell_type.num_points() as u64)
+                .collect::<Vec<_>>(),
+        )?;
+
+        for (center, data) in [
+            (attribute::Center::Node, point_data),
+            (attribute::Center::Cell, cell_data),
+        ] {
+            let data_group = time_group.create_group(attribute::center_to_data_tag(center))?;
+
+            for (name, (data_attribute, values)) in data.into_iter().flatten() {
+                write_field(&data_group, name, data_attribute, values)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Load the checkpoint of the given time from the checkpoint file.
+    ///
+    /// The time must match exactly the time the checkpoint was written with, see [`Checkpoint::times`].
+    pub fn load(file_name: impl AsRef<Path>, time: f64) -> XdmfResult<Self> {
+        let h5_file_name = checkpoint_file_name(file_name.as_ref());
+        let h5_file = H5File::open(&h5_file_name)?;
+
+        let group_name = time_group_name(time);
+        if !h5_file.link_exists(&group_name) {
+            return Err(XdmfError::Validation(format!(
+                "Checkpoint file '{}' does not contain time {time}, available are: [{}]",
+                h5_file_name.display(),
+                checkpoint_times(&h5_file)?
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        let time_group = h5_file.group(&group_name)?;
+
+        let mesh_group = time_group.group(MESH)?;
+        let points = mesh_group.dataset(POINTS)?.read_raw::<f64>()?;
+        let connectivity = mesh_group.dataset(CONNECTIVITY)?.read_raw::<u64>()?;
+        let cell_types = read_cell_types(&mesh_group, connectivity.len())?;
+
+        let point_data = read_fields(&time_group, attribute::Center::Node)?;
+        let cell_data = read_fields(&time_group, attribute::Center::Cell)?;
+
+        Ok(Self {
+            time,
+            points,
+            connectivity,
+            cell_types,
+            point_data,
+            cell_data,
+        })
+    }
+
+    /// Times of the checkpoints in the checkpoint file, in increasing order.
+    pub fn times(file_name: impl AsRef<Path>) -> XdmfResult<Vec<f64>> {
+        checkpoint_times(&H5File::open(checkpoint_file_name(file_name.as_ref()))?)
+    }
+}
+
+fn checkpoint_file_name(file_name: &Path) -> PathBuf {
+    file_name.with_extension("h5")
+}
+
+// the time is formatted such that it is parsed to the same float, hence it can be found again
+fn time_group_name(time: f64) -> String {
+    format!("t_{time}")
+}
+
+fn checkpoint_times(h5_file: &H5File) -> XdmfResult<Vec<f64>> {
+    let mut times: Vec<f64> = h5_file
+        .member_names()?
+        .iter()
+        .filter_map(|name| name.strip_prefix("t_")?.parse().ok())
+        .collect();
+
+    times.sort_by(f64::total_cmp);
+    Ok(times)
+}
+
+fn write_dataset<T: H5Type>(group: &H5Group, dataset_name: &str, values: &[T]) -> XdmfResult<()> {
+    group
+        .new_dataset::<T>()
+        .shape(values.len())
+        .create(dataset_name)?
+        .write(values)?;
+
+    Ok(())
+}
+
+// the values are written with their number type, which is stored along with the data attribute
+fn write_field(
+    group: &H5Group,
+    name: &str,
+    data_attribute: &DataAttribute,
+    values: &Values,
+) -> XdmfResult<()> {
+    let number_type = match values {
+        Values::F64(v) => write_dataset(group, name, v).map(|()| 0),
+        Values::F32(v) => write_dataset(group, name, v).map(|()| 1),
+        Values::U64(v) => write_dataset(group, name, v).map(|()| 2),
+        Values::I64(v) => write_dataset(group, name, v).map(|()| 3),
+        Values::U8(v) => write_dataset(group, name, v).map(|()| 4),
+    }?;
+
+    let (kind, rows, columns) = match data_attribute {
+        DataAttribute::Scalar => (0, 0, 0),
+        DataAttribute::Vector => (1, 0, 0),
+        DataAttribute::Tensor => (2, 0, 0),
+        DataAttribute::Tensor6 => (3, 0, 0),
+        DataAttribute::Matrix(rows, columns) => (4, *rows as u64, *columns as u64),
+        DataAttribute::Generic(_) => (5, data_attribute.size() as u64, 0),
+    };
+
+    let dataset = group.dataset(name)?;
+    dataset
+        .new_attr::<u64>()
+        .shape(4)
+        .create(DATA_ATTRIBUTE)?
+        .write([kind, rows, columns, number_type].as_slice())?;
+
+    // the full shape of generic data is stored separately, checkpoints without it have a one-dimensional shape
+    if let DataAttribute::Generic(shape) = data_attribute {
+        let shape: Vec<u64> = shape.iter().map(|&size| size as u64).collect();
+        dataset
+            .new_attr::<u64>()
+            .shape(shape.len())
+            .create(SHAPE)?
+            .write(shape.as_slice())?;
+    }
+
+    Ok(())
+}
+
+fn read_fields(time_group: &H5Group, center: attribute::Center) -> XdmfResult<DataMap<'static>> {
+    let data_group = time_group.group(attribute::center_to_data_tag(center))?;
+
+    data_group
+        .member_names()?
+        .into_iter()
+        .map(|name| {
+            let dataset = data_group.dataset(&name)?;
+
+            let [kind, rows, columns, number_type]: [u64; 4] = dataset
+                .attr(DATA_ATTRIBUTE)?
+                .read_raw::<u64>()?
+                .try_into()
+                .map_err(|_err| invalid_field(&name))?;
+
+            let data_attribute = match (kind, rows as usize, columns as usize) {
+                (0, _, _) => DataAttribute::Scalar,
+                (1, _, _) => DataAttribute::Vector,
+                (2, _, _) => DataAttribute::Tensor,
+                (3, _, _) => DataAttribute::Tensor6,
+                (4, rows, columns) => DataAttribute::Matrix(rows, columns),
+                (5, _, _) if dataset.attr_names()?.iter().any(|attr| attr == SHAPE) => {
+                    DataAttribute::Generic(
+                        dataset
+                            .attr(SHAPE)?
+                            .read_raw::<u64>()?
+                            .into_iter()
+                            .map(|size| size as usize)
+                            .collect(),
+                    )
+                }
+                (5, size, _) => DataAttribute::Generic(vec![size]),
+                _ => return Err(invalid_field(&name)),
+            };
+
+            let values: Values = match number_type {
+                0 => dataset.read_raw::<f64>()?.into(),
+                1 => dataset.read_raw::<f32>()?.into(),
+                2 => dataset.read_raw::<u64>()?.into(),
+                3 => dataset.read_raw::<i64>()?.into(),
+                4 => dataset.read_raw::<u8>()?.into(),
+                _ => return Err(invalid_field(&name)),
+            };
+
+            Ok((name, (data_attribute, values)))
+        })
+        .collect()
+}
+
+fn invalid_field(name: &str) -> XdmfError {
+    XdmfError::Validation(format!(
+        "Field '{name}' of checkpoint has an invalid data attribute"
+    ))
+}
+
+fn read_cell_types(mesh_group: &H5Group, connectivity_size: usize) -> XdmfResult<Vec<CellType>> {
+    let cell_type_ids = mesh_group.dataset(CELL_TYPES)?.read_raw::<u64>()?;
+    let cell_sizes = mesh_group.dataset(CELL_SIZES)?.read_raw::<u64>()?;
+
+    let cell_types = cell_type_ids
+        .iter()
+        .zip(&cell_sizes)
+        .map(|(&id, &size)| {
+            CellType::from_xdmf_id(id, size as usize).ok_or_else(|| {
+                XdmfError::Validation(format!(
+                    "Cell type {id} with {size} points of checkpoint is not valid"
+                ))
+            })
+        })
+        .collect::<XdmfResult<Vec<_>>>()?;
+
+    if cell_type_ids.len() != cell_sizes.len()
+        || cell_types.iter().map(CellType::num_points).sum::<usize>() != connectivity_size
+    {
+        return Err(XdmfError::Validation(
+            "Cells of checkpoint do not match its connectivity".into(),
+        ));
+    }
+
+    Ok(cell_types)
+}
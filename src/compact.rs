@@ -0,0 +1,320 @@
+//! Compaction of meshes before writing them, i.e. removing the points that no cell uses and merging duplicated points.
+//!
+//! Meshes exported by mesh generators often contain unused points, or the same point multiple times, e.g. once per
+//! block of the mesh. Compacting such a mesh reduces the size of the written files, the returned mapping is used to
+//! select the values of the fields of the remaining points.
+//! ```rust
+//! use xdmf::{CellType, compact::compact_mesh};
+//!
+//! // two edges that share a point, which is given twice, and an unused point
+//! let points = [
+//!     0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 2.0, 0.0, 0.0, 5.0, 5.0, 5.0,
+//! ];
+//! let connectivity = [0, 1, 2, 3];
+//! let cell_types = [CellType::Edge, CellType::Edge];
+//!
+//! let mesh = compact_mesh(&points, (&connectivity, &cell_types), Some(0.0)).unwrap();
+//!
+//! assert_eq!(mesh.points, [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 2.0, 0.0, 0.0]);
+//! assert_eq!(mesh.connectivity, [0, 1, 1, 2]);
+//! assert_eq!(mesh.point_map, [Some(0), Some(1), Some(1), Some(2), None]);
+//!
+//! // the values of a field of the given points, for the remaining points
+//! let temperature = [20.0, 21.0, 21.0, 22.0, 0.0];
+//! assert_eq!(
+//!     mesh.remap_point_data(&temperature, 1).unwrap(),
+//!     [20.0, 21.0, 22.0]
+//! );
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{CellType, XdmfError, XdmfResult, time_series_writer::validate_points_and_cells};
+
+/// Mesh whose unused points are removed and whose duplicated points are merged, see [`compact_mesh`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompactMesh {
+    /// Coordinates of the remaining points, interleaved as x, y, z
+    pub points: Vec<f64>,
+    /// Connectivity of the cells with the indices of the remaining points, for the same cell types
+    pub connectivity: Vec<u64>,
+    /// Index of the remaining point for each given point, `None` for the points that no cell uses
+    pub point_map: Vec<Option<usize>>,
+    /// Index of the given point for each remaining point, the first one for merged points
+    pub source_points: Vec<usize>,
+}
+
+impl CompactMesh {
+    /// Select the values of the remaining points from the values of the given points, with `num_components` values per point.
+    ///
+    /// The values of merged points are taken from the first of them.
+    pub fn remap_point_data<T: Copy>(
+        &self,
+        values: &[T],
+        num_components: usize,
+    ) -> XdmfResult<Vec<T>> {
+        if values.len() != self.point_map.len() * num_components {
+            return Err(XdmfError::Validation(format!(
+                "Point data has {} values, but {} are expected for {} points with {num_components} components",
+                values.len(),
+                self.point_map.len() * num_components,
+                self.point_map.len()
+            )));
+        }
+
+        Ok(self
+            .source_points
+            .iter()
+            .flat_map(|&point| &values[point * num_components..(point + 1) * num_components])
+            .copied()
+            .collect())
+    }
+}
+
+/// Remove the points that are not used by any cell, and remap the connectivity of the cells accordingly.
+///
+/// With a `merge_tolerance` the points whose distance is at most the tolerance are merged as well, `Some(0.0)` merges
+/// only identical points. The remaining points keep their order, the cells are not changed otherwise.
+/// Note that merging points with a tolerance depends on the order of the points, if more than two points are close to each other.
+pub fn compact_mesh(
+    points: &[f64],
+    cells: (&[u64], &[CellType]),
+    merge_tolerance: Option<f64>,
+) -> XdmfResult<CompactMesh> {
+    validate_points_and_cells(points, cells)?;
+
+    if let Some(tolerance) = merge_tolerance
+        && !(tolerance.is_finite() && tolerance >= 0.0)
+    {
+        return Err(XdmfError::Validation(format!(
+            "Tolerance for merging points must be finite and not negative, but is {tolerance}"
+        )));
+    }
+
+    let num_points = points.len() / 3;
+    let positions = point_positions(cells);
+
+    let mut is_used = vec![false; num_points];
+    for &position in &positions {
+        is_used[cells.0[position] as usize] = true;
+    }
+
+    let mut merger = merge_tolerance.map(PointMerger::new);
+    let mut point_map = vec![None; num_points];
+    let mut source_points = Vec::new();
+
+    for (index, point) in points.chunks_exact(3).enumerate() {
+        if !is_used[index] {
+            continue;
+        }
+
+        let merged = merger
+            .as_ref()
+            .and_then(|merger| merger.find(point, points, &source_points));
+
+        point_map[index] = Some(merged.unwrap_or_else(|| {
+            if let Some(merger) = &mut merger {
+                merger.insert(point, source_points.len());
+            }
+            source_points.push(index);
+            source_points.len() - 1
+        }));
+    }
+
+    let mut connectivity = cells.0.to_vec();
+    for position in positions {
+        // all used points are mapped
+        connectivity[position] = point_map[cells.0[position] as usize].unwrap_or_default() as u64;
+    }
+
+    Ok(CompactMesh {
+        points: source_points
+            .iter()
+            .flat_map(|&point| &points[3 * point..3 * point + 3])
+            .copied()
+            .collect(),
+        connectivity,
+        point_map,
+        source_points,
+    })
+}
+
+// positions of the point indices in the connectivity, i.e. without the numbers of faces and points of the face streams of polyhedra
+fn point_positions((connectivity, cell_types): (&[u64], &[CellType])) -> Vec<usize> {
+    let mut positions = Vec::with_capacity(connectivity.len());
+    let mut index = 0;
+
+    for cell_type in cell_types {
+        let num_points = cell_type.num_points();
+
+        if let CellType::Polyhedron(_) = cell_type {
+            // the face stream is the number of faces, followed by the number of points and the points of each face
+            let mut position = index + 1;
+            while position < index + num_points {
+                let num_face_points = connectivity[position] as usize;
+                positions.extend(position + 1..position + 1 + num_face_points);
+                position += 1 + num_face_points;
+            }
+        } else {
+            positions.extend(index..index + num_points);
+        }
+
+        index += num_points;
+    }
+
+    positions
+}
+
+// finds already inserted points within the tolerance, by sorting the points into buckets of the size of the tolerance
+struct PointMerger {
+    tolerance: f64,
+    buckets: HashMap<[i64; 3], Vec<usize>>,
+}
+
+impl PointMerger {
+    fn new(tolerance: f64) -> Self {
+        Self {
+            tolerance,
+            buckets: HashMap::new(),
+        }
+    }
+
+    // for a tolerance of zero, identical points are in the same bucket of their coordinates
+    fn bucket(&self, point: &[f64]) -> [i64; 3] {
+        let coordinate = |value: f64| {
+            if self.tolerance > 0.0 {
+                (value / self.tolerance).floor() as i64
+            } else {
+                // -0.0 and 0.0 are identical
+                (value + 0.0).to_bits() as i64
+            }
+        };
+
+        [
+            coordinate(point[0]),
+            coordinate(point[1]),
+            coordinate(point[2]),
+        ]
+    }
+
+    fn insert(&mut self, point: &[f64], merged_index: usize) {
+        self.buckets
+            .entry(self.bucket(point))
+            .or_default()
+            .push(merged_index);
+    }
+
+    // index of an inserted point within the tolerance of the given point
+    fn find(&self, point: &[f64], points: &[f64], source_points: &[usize]) -> Option<usize> {
+        let bucket = self.bucket(point);
+        // close points can be in the neighboring buckets
+        let offsets: &[i64] = if self.tolerance > 0.0 {
+            &[-1, 0, 1]
+        } else {
+            &[0]
+        };
+
+        let is_close = |merged_index: usize| {
+            let source = source_points[merged_index];
+            let distance_squared: f64 = point
+                .iter()
+                .zip(&points[3 * source..3 * source + 3])
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum();
+            distance_squared <= self.tolerance * self.tolerance
+        };
+
+        offsets
+            .iter()
+            .flat_map(|&dx| offsets.iter().map(move |&dy| (dx, dy)))
+            .flat_map(|(dx, dy)| offsets.iter().map(move |&dz| [dx, dy, dz]))
+            .filter_map(|offset| {
+                self.buckets.get(&[
+                    bucket[0].saturating_add(offset[0]),
+                    bucket[1].saturating_add(offset[1]),
+                    bucket[2].saturating_add(offset[2]),
+                ])
+            })
+            .flatten()
+            .copied()
+            .filter(|&merged_index| is_close(merged_index))
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_unused_points() {
+        let points = [0.0, 0.0, 0.0, 9.0, 9.0, 9.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+        let mesh = compact_mesh(&points, (&[0, 2, 3], &[CellType::Triangle]), None).unwrap();
+
+        assert_eq!(mesh.connectivity, [0, 1, 2]);
+        assert_eq!(mesh.point_map, [Some(0), None, Some(1), Some(2)]);
+        assert_eq!(mesh.source_points, [0, 2, 3]);
+        assert_eq!(
+            mesh.remap_point_data(&[1, 2, 3, 4, 5, 6, 7, 8], 2).unwrap(),
+            [1, 2, 5, 6, 7, 8]
+        );
+        assert_eq!(
+            mesh.remap_point_data(&[1, 2, 3], 1)
+                .unwrap_err()
+                .to_string(),
+            "Point data has 3 values, but 4 are expected for 4 points with 1 components"
+        );
+    }
+
+    #[test]
+    fn compact_merge_points() {
+        // the second point is within the tolerance of the first, the third only of the second
+        let points = [
+            0.0, 0.0, 0.0, 0.0, 0.0, 0.09, 0.0, 0.0, 0.18, -0.0, 1.0, 0.0, 0.0, 1.0, 0.0,
+        ];
+        let cells = (&[0, 1, 2, 3, 4][..], &[CellType::Polygon(5)][..]);
+
+        let mesh = compact_mesh(&points, cells, Some(0.1)).unwrap();
+        assert_eq!(
+            mesh.point_map,
+            [Some(0), Some(0), Some(1), Some(2), Some(2)]
+        );
+        assert_eq!(mesh.connectivity, [0, 0, 1, 2, 2]);
+
+        // -0.0 and 0.0 are identical
+        let mesh = compact_mesh(&points, cells, Some(0.0)).unwrap();
+        assert_eq!(mesh.connectivity, [0, 1, 2, 3, 3]);
+
+        assert_eq!(
+            compact_mesh(&points, cells, Some(f64::NAN))
+                .unwrap_err()
+                .to_string(),
+            "Tolerance for merging points must be finite and not negative, but is NaN"
+        );
+    }
+
+    #[test]
+    fn compact_polyhedron() {
+        let (polyhedron, face_stream) = CellType::polyhedron(&[
+            &[1, 2, 3, 4],
+            &[1, 2, 5],
+            &[2, 3, 5],
+            &[3, 4, 5],
+            &[4, 1, 5],
+        ]);
+        let points = [0.0; 18];
+
+        let mesh = compact_mesh(&points, (&face_stream, &[polyhedron]), None).unwrap();
+
+        // the numbers of faces and points are not remapped
+        let (_, expected) = CellType::polyhedron(&[
+            &[0, 1, 2, 3],
+            &[0, 1, 4],
+            &[1, 2, 4],
+            &[2, 3, 4],
+            &[3, 0, 4],
+        ]);
+        assert_eq!(mesh.connectivity, expected);
+        assert_eq!(mesh.source_points, [1, 2, 3, 4, 5]);
+    }
+}
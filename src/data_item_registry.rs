@@ -0,0 +1,168 @@
+//! Registry of the named `DataItems` of a domain, which are shared by referencing them from the grids.
+
+use crate::{
+    XdmfError, XdmfResult,
+    xdmf_elements::data_item::{DataItem, Reference},
+};
+
+/// Keeps track of the named `DataItems` and hands out references to them.
+///
+/// The number of references to each `DataItem` is counted, such that only referenced `DataItems` are written.
+/// The `DataItems` are kept in the order of registration.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DataItemRegistry {
+    entries: Vec<Entry>,
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    data_item: DataItem,
+    num_references: usize,
+}
+
+impl DataItemRegistry {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a `DataItem`, which can then be referenced by its name.
+    ///
+    /// The `DataItem` must have a name, which is unique within the registry.
+    pub(crate) fn register(&mut self, data_item: DataItem) -> XdmfResult<()> {
+        let name = data_item.name.clone().ok_or_else(|| {
+            XdmfError::Validation("DataItem must have a name to be registered".into())
+        })?;
+
+        if self.contains(&name) {
+            return Err(XdmfError::Validation(format!(
+                "DataItem with name '{name}' is already registered"
+            )));
+        }
+
+        self.entries.push(Entry {
+            data_item,
+            num_references: 0,
+        });
+
+        Ok(())
+    }
+
+    /// Get a new reference to a registered `DataItem`.
+    pub(crate) fn reference(&mut self, name: &str) -> XdmfResult<DataItem> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.data_item.name.as_deref() == Some(name))
+            .ok_or_else(|| {
+                XdmfError::Validation(format!("DataItem with name '{name}' is not registered"))
+            })?;
+
+        entry.num_references += 1;
+
+        Ok(Reference::builder()
+            .source(&entry.data_item)
+            .build()?
+            .into())
+    }
+
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Get a registered `DataItem` by its name, without referencing it.
+    pub(crate) fn get(&self, name: &str) -> Option<&DataItem> {
+        self.entries
+            .iter()
+            .map(|entry| &entry.data_item)
+            .find(|data_item| data_item.name.as_deref() == Some(name))
+    }
+
+    /// The registered `DataItems` that are referenced, in the order of registration.
+    pub(crate) fn data_items(&self) -> impl Iterator<Item = &DataItem> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.num_references > 0)
+            .map(|entry| &entry.data_item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdmf_elements::data_item::DataContent;
+
+    fn named_item(name: &str) -> DataItem {
+        DataItem {
+            name: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn register_and_reference() {
+        let mut registry = DataItemRegistry::new();
+
+        registry.register(named_item("coords")).unwrap();
+        registry.register(named_item("unused")).unwrap();
+        registry.register(named_item("connectivity")).unwrap();
+
+        let coords_ref = registry.reference("coords").unwrap();
+        assert_eq!(coords_ref.reference, Some("XML".to_string()));
+        assert_eq!(
+            coords_ref.data,
+            DataContent::from("/Xdmf/Domain/DataItem[@Name=\"coords\"]")
+        );
+
+        registry.reference("coords").unwrap();
+        registry.reference("connectivity").unwrap();
+
+        let num_references: Vec<_> = registry
+            .entries
+            .iter()
+            .map(|entry| entry.num_references)
+            .collect();
+        assert_eq!(num_references, vec![2, 0, 1]);
+
+        // only referenced DataItems are written
+        let names: Vec<_> = registry
+            .data_items()
+            .map(|item| item.name.clone().unwrap())
+            .collect();
+        assert_eq!(names, vec!["coords", "connectivity"]);
+
+        // getting a DataItem does not reference it
+        assert_eq!(
+            registry.get("unused").unwrap().name.as_deref(),
+            Some("unused")
+        );
+        assert!(registry.get("other").is_none());
+        assert_eq!(registry.data_items().count(), 2);
+    }
+
+    #[test]
+    fn register_invalid() {
+        let mut registry = DataItemRegistry::new();
+
+        assert_eq!(
+            registry
+                .register(DataItem::default())
+                .unwrap_err()
+                .to_string(),
+            "DataItem must have a name to be registered"
+        );
+
+        registry.register(named_item("coords")).unwrap();
+        assert_eq!(
+            registry
+                .register(named_item("coords"))
+                .unwrap_err()
+                .to_string(),
+            "DataItem with name 'coords' is already registered"
+        );
+
+        assert_eq!(
+            registry.reference("other").unwrap_err().to_string(),
+            "DataItem with name 'other' is not registered"
+        );
+    }
+}
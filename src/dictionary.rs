@@ -0,0 +1,218 @@
+//! Standardized names, units and descriptions of fields, to make the written files self-describing.
+//!
+//! A [`FieldDescriptor`] is written as `Information` of each attribute of its field, see
+//! [`TimeSeriesWriter::with_field_descriptor`](crate::TimeSeriesWriter::with_field_descriptor).
+//! The descriptors of common quantities of CFD and FE simulations are provided by [`Quantity`] for the common [`UnitSystem`]s.
+//! ```rust
+//! use xdmf::{
+//!     TimeSeriesWriter,
+//!     dictionary::{FieldDescriptor, Quantity, UnitSystem},
+//! };
+//!
+//! let pressure = Quantity::Pressure.descriptor(UnitSystem::Si);
+//! assert_eq!(pressure.name(), "pressure");
+//! assert_eq!(pressure.units(), "Pa");
+//!
+//! let xdmf_writer = TimeSeriesWriter::new("xdmf_dictionary", xdmf::DataStorage::AsciiInline)
+//!     .expect("failed to create XDMF writer")
+//!     .with_field_descriptor(&pressure)
+//!     // the field of the velocity is named "u" by the solver
+//!     .with_field_descriptor(&Quantity::Velocity.descriptor(UnitSystem::Si).with_name("u"))
+//!     .with_field_descriptor(&FieldDescriptor::new(
+//!         "phi",
+//!         "1",
+//!         "Volume fraction of the liquid phase",
+//!     ));
+//! ```
+
+use crate::xdmf_elements::Information;
+
+/// Name of the `Information` with the units of a field
+pub const UNITS_NAME: &str = "units";
+
+/// Name of the `Information` with the description of a field
+pub const DESCRIPTION_NAME: &str = "description";
+
+/// Name of the `Information` with the names of the components of a field, separated by spaces
+pub const COMPONENT_NAMES_NAME: &str = "component_names";
+
+/// Name, units and description of a field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldDescriptor {
+    name: String,
+    units: String,
+    description: String,
+}
+
+impl FieldDescriptor {
+    /// Create a descriptor of the field with the given name, e.g. for quantities that are not covered by [`Quantity`].
+    ///
+    /// Dimensionless fields have the units `"1"`.
+    pub fn new(name: impl ToString, units: impl ToString, description: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            units: units.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    /// Use the descriptor for a field with a different name, e.g. the name used by the solver.
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Name of the field the descriptor belongs to
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Units of the field, e.g. `"m/s"`
+    pub fn units(&self) -> &str {
+        &self.units
+    }
+
+    /// Description of the field
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The `Information` elements written into each attribute of the field, named "units" and "description".
+    pub fn information(&self) -> Vec<Information> {
+        vec![
+            Information::new(UNITS_NAME, &self.units),
+            Information::new(DESCRIPTION_NAME, &self.description),
+        ]
+    }
+}
+
+/// System of units of the descriptors of the [`Quantity`]s.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnitSystem {
+    /// Units of the International System of Units (m, kg, s, K)
+    #[default]
+    Si,
+    /// Centimetre, gram and second (cm, g, s, K)
+    Cgs,
+    /// Millimetre, tonne and second (mm, t, s, K), common for structural FE simulations
+    MmTonneSecond,
+}
+
+/// Common quantities of CFD and FE simulations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quantity {
+    /// Static pressure
+    Pressure,
+    /// Velocity of the flow
+    Velocity,
+    /// Temperature
+    Temperature,
+    /// Density
+    Density,
+    /// Dynamic viscosity
+    DynamicViscosity,
+    /// Turbulent kinetic energy per unit mass
+    TurbulentKineticEnergy,
+    /// Vorticity, i.e. the curl of the velocity
+    Vorticity,
+    /// Displacement of the structure
+    Displacement,
+    /// Cauchy stress
+    Stress,
+    /// Strain
+    Strain,
+    /// Force
+    Force,
+}
+
+impl Quantity {
+    /// The descriptor of the quantity in the given unit system.
+    pub fn descriptor(self, unit_system: UnitSystem) -> FieldDescriptor {
+        let (name, description) = self.name_and_description();
+        FieldDescriptor::new(name, self.units(unit_system), description)
+    }
+
+    fn name_and_description(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Pressure => ("pressure", "Static pressure"),
+            Self::Velocity => ("velocity", "Velocity of the flow"),
+            Self::Temperature => ("temperature", "Temperature"),
+            Self::Density => ("density", "Density"),
+            Self::DynamicViscosity => ("dynamic_viscosity", "Dynamic viscosity"),
+            Self::TurbulentKineticEnergy => (
+                "turbulent_kinetic_energy",
+                "Turbulent kinetic energy per unit mass",
+            ),
+            Self::Vorticity => ("vorticity", "Vorticity, the curl of the velocity"),
+            Self::Displacement => ("displacement", "Displacement of the structure"),
+            Self::Stress => ("stress", "Cauchy stress"),
+            Self::Strain => ("strain", "Strain"),
+            Self::Force => ("force", "Force"),
+        }
+    }
+
+    fn units(self, unit_system: UnitSystem) -> &'static str {
+        use UnitSystem::{Cgs, MmTonneSecond, Si};
+
+        match (self, unit_system) {
+            (Self::Pressure | Self::Stress, Si) => "Pa",
+            (Self::Pressure | Self::Stress, Cgs) => "Ba",
+            (Self::Pressure | Self::Stress, MmTonneSecond) => "MPa",
+            (Self::Velocity, Si) => "m/s",
+            (Self::Velocity, Cgs) => "cm/s",
+            (Self::Velocity, MmTonneSecond) => "mm/s",
+            (Self::Temperature, _) => "K",
+            (Self::Density, Si) => "kg/m^3",
+            (Self::Density, Cgs) => "g/cm^3",
+            (Self::Density, MmTonneSecond) => "t/mm^3",
+            (Self::DynamicViscosity, Si) => "Pa*s",
+            (Self::DynamicViscosity, Cgs) => "P",
+            (Self::DynamicViscosity, MmTonneSecond) => "MPa*s",
+            (Self::TurbulentKineticEnergy, Si) => "m^2/s^2",
+            (Self::TurbulentKineticEnergy, Cgs) => "cm^2/s^2",
+            (Self::TurbulentKineticEnergy, MmTonneSecond) => "mm^2/s^2",
+            (Self::Vorticity, _) => "1/s",
+            (Self::Displacement, Si) => "m",
+            (Self::Displacement, Cgs) => "cm",
+            (Self::Displacement, MmTonneSecond) => "mm",
+            (Self::Strain, _) => "1",
+            (Self::Force, Si | MmTonneSecond) => "N",
+            (Self::Force, Cgs) => "dyn",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantity_descriptors() {
+        assert_eq!(
+            Quantity::Stress.descriptor(UnitSystem::MmTonneSecond),
+            FieldDescriptor::new("stress", "MPa", "Cauchy stress")
+        );
+        assert_eq!(
+            Quantity::Force
+                .descriptor(UnitSystem::MmTonneSecond)
+                .units(),
+            "N"
+        );
+        assert_eq!(
+            Quantity::Density.descriptor(UnitSystem::Cgs).units(),
+            "g/cm^3"
+        );
+
+        let velocity = Quantity::Velocity
+            .descriptor(UnitSystem::default())
+            .with_name("u");
+        assert_eq!(velocity.name(), "u");
+        assert_eq!(
+            velocity.information(),
+            [
+                Information::new("units", "m/s"),
+                Information::new("description", "Velocity of the flow")
+            ]
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! This module contains the low-level builders for custom XDMF documents, for when the `TimeSeriesWriter` is not flexible enough.
+//!
+//! The [`DomainBuilder`] writes the heavy data with the chosen [`DataStorage`] and creates the `DataItems` describing it,
+//! while the grids are composed freely with the [`GridBuilder`], e.g. nesting spatial collections in temporal collections.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    DataStorage, DataWriter, FileSystem, LocalFileSystem, Values, XdmfError, XdmfResult, XmlHeader,
+    create_writer,
+    data_item_registry::DataItemRegistry,
+    time_series_writer::{create_xdmf, is_valid_data_name, validate_file_name, write_xdmf_file},
+    xdmf_elements::{
+        Information,
+        attribute::{Attribute, AttributeType, Center},
+        data_item::DataItem,
+        dimensions::Dimensions,
+        geometry::Geometry,
+        grid::{CollectionType, Grid, GridType, Time},
+        topology::Topology,
+    },
+};
+
+/// Label under which the heavy data of the domain is written
+const DOMAIN_DATA_LABEL: &str = "domain";
+
+/// Builder for an XDMF file with custom grids, which manages the heavy data of the grids.
+///
+/// The values are written with the [`DataStorage`] of the builder, the returned `DataItems` are used to compose the grids with the [`GridBuilder`].
+/// Values that are used by multiple grids are written once as named `DataItem` of the domain, and referenced by the grids.
+/// ```rust
+/// use xdmf::{
+///     DomainBuilder, GridBuilder,
+///     xdmf_elements::{
+///         attribute::{AttributeType, Center},
+///         dimensions::Dimensions,
+///         geometry::{Geometry, GeometryType},
+///         grid::CollectionType,
+///         topology::{Topology, TopologyType},
+///     },
+/// };
+///
+/// let mut domain_builder =
+///     DomainBuilder::new("xdmf_domain_builder", xdmf::DataStorage::AsciiInline)
+///         .expect("failed to create domain builder");
+///
+/// // the mesh is shared by the grids of all time steps
+/// domain_builder
+///     .write_shared_values(
+///         "coords",
+///         &vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0].into(),
+///         Dimensions(vec![3, 3]),
+///     )
+///     .expect("failed to write coordinates");
+/// domain_builder
+///     .write_shared_values(
+///         "connectivity",
+///         &vec![0_u64, 1, 2].into(),
+///         Dimensions(vec![1, 3]),
+///     )
+///     .expect("failed to write connectivity");
+///
+/// let mut time_steps = GridBuilder::collection("time_series", CollectionType::Temporal);
+/// for i in 0..3 {
+///     let temperature = domain_builder
+///         .write_values(
+///             &format!("temperature_{i}"),
+///             &vec![f64::from(i); 3].into(),
+///             Dimensions(vec![3]),
+///         )
+///         .expect("failed to write temperature");
+///
+///     let grid = GridBuilder::uniform(
+///         format!("mesh_{i}"),
+///         Geometry {
+///             geometry_type: GeometryType::XYZ,
+///             data_items: vec![
+///                 domain_builder
+///                     .reference("coords")
+///                     .expect("coordinates not written"),
+///             ],
+///         },
+///         Topology {
+///             topology_type: TopologyType::Triangle,
+///             number_of_elements: "1".into(),
+///             dimensions: None,
+///             data_item: Some(
+///                 domain_builder
+///                     .reference("connectivity")
+///                     .expect("connectivity not written"),
+///             ),
+///         },
+///     )
+///     .time(i.to_string())
+///     .attribute(
+///         "temperature",
+///         AttributeType::Scalar,
+///         Center::Node,
+///         temperature,
+///     )
+///     .build()
+///     .expect("failed to build grid");
+///
+///     time_steps = time_steps.grid(grid);
+/// }
+///
+/// domain_builder.add_grid(time_steps.build().expect("failed to build collection"));
+/// domain_builder.write().expect("failed to write XDMF file");
+/// ```
+pub struct DomainBuilder {
+    xdmf_file_name: PathBuf,
+    writer: Box<dyn DataWriter>,
+    information: Vec<Information>,
+    xml_header: XmlHeader,
+    data_items: DataItemRegistry,
+    written_names: HashSet<String>,
+    grids: Vec<Grid>,
+}
+
+impl DomainBuilder {
+    /// Create a new `DomainBuilder`, writing the heavy data with the given `DataStorage`.
+    pub fn new(file_name: impl AsRef<Path>, data_storage: DataStorage) -> XdmfResult<Self> {
+        let xdmf_file_name = file_name.as_ref().with_extension("xdmf2");
+
+        validate_file_name(&xdmf_file_name)?;
+
+        let file_system: Arc<dyn FileSystem> = Arc::new(LocalFileSystem);
+
+        // create the parent directory if it does not exist
+        if let Some(parent) = xdmf_file_name.parent() {
+            file_system.create_dir_all(parent)?;
+        }
+
+        Ok(Self {
+            xdmf_file_name,
+            writer: create_writer(file_name.as_ref(), data_storage, &file_system)?,
+            information: Vec::new(),
+            xml_header: XmlHeader::default(),
+            data_items: DataItemRegistry::new(),
+            written_names: HashSet::new(),
+            grids: Vec::new(),
+        })
+    }
+
+    /// Add custom information to the XDMF file, see [`TimeSeriesWriter::with_information`](crate::TimeSeriesWriter::with_information).
+    pub fn with_information(mut self, name: impl ToString, value: impl ToString) -> Self {
+        self.information.push(Information::new(name, value));
+        self
+    }
+
+    /// Write the XML declaration and/or the `DOCTYPE` at the beginning of the XDMF file, see [`TimeSeriesWriter::with_xml_header`](crate::TimeSeriesWriter::with_xml_header).
+    pub fn with_xml_header(mut self, xml_header: XmlHeader) -> Self {
+        self.xml_header = xml_header;
+        self
+    }
+
+    /// Write values as heavy data, returning the `DataItem` describing them, e.g. for the attribute of a single grid.
+    ///
+    /// The name identifies the heavy data, it must be unique within the domain.
+    /// The number of values must match the given dimensions.
+    pub fn write_values(
+        &mut self,
+        name: &str,
+        values: &Values,
+        dimensions: Dimensions,
+    ) -> XdmfResult<DataItem> {
+        if !is_valid_data_name(name) {
+            return Err(XdmfError::Validation(format!(
+                "Data name '{name}' is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
+            )));
+        }
+
+        if self.written_names.contains(name) {
+            return Err(XdmfError::Validation(format!(
+                "Data '{name}' has already been written"
+            )));
+        }
+
+        let num_values: usize = dimensions.0.iter().product();
+        if num_values != values.len() {
+            return Err(XdmfError::Validation(format!(
+                "Size of data '{name}' must be {num_values} according to its dimensions, but is {}",
+                values.len()
+            )));
+        }
+
+        self.writer.write_data_initialize(DOMAIN_DATA_LABEL)?;
+        let data = self
+            .writer
+            .write_shaped_data(name, Center::Other, values, &dimensions)?;
+        self.writer.write_data_finalize()?;
+
+        self.written_names.insert(name.to_string());
+
+        Ok(self.writer.wrap_data_item(DataItem {
+            name: None,
+            dimensions: Some(dimensions),
+            number_type: Some(values.number_type()),
+            format: Some(self.writer.format()),
+            precision: Some(values.precision()),
+            data,
+            reference: None,
+            item_type: None,
+            encoding: self.writer.encoding(),
+            endian: None,
+            function: None,
+            data_items: vec![],
+        }))
+    }
+
+    /// Write values as heavy data, stored once as named `DataItem` of the domain, e.g. the coordinates shared by multiple grids.
+    ///
+    /// The grids refer to the values with [`DomainBuilder::reference`]. See [`DomainBuilder::write_values`] for details.
+    pub fn write_shared_values(
+        &mut self,
+        name: &str,
+        values: &Values,
+        dimensions: Dimensions,
+    ) -> XdmfResult<()> {
+        let mut data_item = self.write_values(name, values, dimensions)?;
+        data_item.name = Some(name.to_string());
+
+        self.data_items.register(data_item)
+    }
+
+    /// Get a `DataItem` referencing values written with [`DomainBuilder::write_shared_values`].
+    pub fn reference(&mut self, name: &str) -> XdmfResult<DataItem> {
+        self.data_items.reference(name)
+    }
+
+    /// Add a grid to the domain, e.g. created with the [`GridBuilder`].
+    pub fn add_grid(&mut self, grid: Grid) {
+        self.grids.push(grid);
+    }
+
+    /// Write the XDMF file and finish writing the heavy data.
+    ///
+    /// Only the shared values that are referenced by the grids are part of the XDMF file.
+    pub fn write(mut self) -> XdmfResult<()> {
+        self.writer.flush()?;
+
+        let mut grids = std::mem::take(&mut self.grids).into_iter();
+        let first_grid = grids.next().ok_or_else(|| {
+            XdmfError::Validation("The domain must contain at least one grid".into())
+        })?;
+
+        let mut xdmf = create_xdmf(
+            self.writer.as_ref(),
+            &self.information,
+            first_grid,
+            &self.data_items,
+        );
+        xdmf.domains[0].grids.extend(grids);
+
+        write_xdmf_file(
+            &LocalFileSystem,
+            &xdmf,
+            &self.xml_header,
+            None,
+            &self.xdmf_file_name,
+        )?;
+
+        self.writer.close()
+    }
+}
+
+/// Builder for a grid, either a uniform grid with its mesh and attributes or a collection of other grids.
+///
+/// The grids can be nested arbitrarily, e.g. spatial collections of the partitions in a temporal collection.
+/// See [`DomainBuilder`] for an example.
+#[derive(Clone, Debug)]
+pub struct GridBuilder {
+    grid: Grid,
+}
+
+impl GridBuilder {
+    /// Create a builder for a uniform grid with the given geometry and topology.
+    pub fn uniform(name: impl ToString, geometry: Geometry, topology: Topology) -> Self {
+        Self {
+            grid: Grid::new_uniform(name, geometry, topology),
+        }
+    }
+
+    /// Create a builder for a collection of grids, which are added with [`GridBuilder::grid`].
+    pub fn collection(name: impl ToString, collection_type: CollectionType) -> Self {
+        Self {
+            grid: Grid::new_collection(name, collection_type, None),
+        }
+    }
+
+    /// Set the time of the grid, e.g. of the grids in a temporal collection.
+    pub fn time(mut self, time: impl ToString) -> Self {
+        self.grid.time = Some(Time::new(time));
+        self
+    }
+
+    /// Add an attribute with the given values to a uniform grid.
+    pub fn attribute(
+        mut self,
+        name: impl ToString,
+        attribute_type: AttributeType,
+        center: Center,
+        data_item: DataItem,
+    ) -> Self {
+        self.grid
+            .attributes
+            .get_or_insert_default()
+            .push(std::sync::Arc::new(Attribute {
+                name: name.to_string(),
+                attribute_type,
+                center,
+                information: vec![],
+                data_items: vec![data_item],
+            }));
+        self
+    }
+
+    /// Add an attribute with metadata to a uniform grid, e.g. built with [`AttributeBuilder`](crate::xdmf_elements::attribute::AttributeBuilder).
+    pub fn custom_attribute(mut self, attribute: Attribute) -> Self {
+        self.grid
+            .attributes
+            .get_or_insert_default()
+            .push(std::sync::Arc::new(attribute));
+        self
+    }
+
+    /// Add a grid to a collection.
+    pub fn grid(mut self, grid: Grid) -> Self {
+        self.grid.grids.get_or_insert_default().push(grid);
+        self
+    }
+
+    /// Create the grid, which fails if grids are added to a uniform grid or attributes to a collection.
+    pub fn build(self) -> XdmfResult<Grid> {
+        let grid = self.grid;
+
+        match grid.grid_type {
+            GridType::Uniform if grid.grids.is_some() => Err(XdmfError::Validation(format!(
+                "Uniform grid '{}' can not contain other grids",
+                grid.name
+            ))),
+            GridType::Collection if grid.attributes.is_some() => {
+                Err(XdmfError::Validation(format!(
+                    "Attributes can only be added to uniform grids, but grid '{}' is a collection",
+                    grid.name
+                )))
+            }
+            _ => Ok(grid),
+        }
+    }
+}
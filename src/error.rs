@@ -0,0 +1,52 @@
+//! This module contains the error type that is returned by all fallible operations of this crate.
+
+/// Result type with [`XdmfError`] as error.
+pub type XdmfResult<T> = Result<T, XdmfError>;
+
+/// Errors that can occur when writing (or reading) XDMF files.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum XdmfError {
+    /// Reading or writing a file failed
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// An operation of the HDF5 library failed
+    #[cfg(feature = "hdf5")]
+    #[error(transparent)]
+    Hdf5(#[from] hdf5::Error),
+
+    /// The input is invalid, e.g. the size of the data does not match the mesh
+    #[error("{0}")]
+    Validation(String),
+
+    /// The operation is not supported, e.g. because a required feature is not enabled
+    #[error("{0}")]
+    Unsupported(String),
+
+    /// Serializing the XDMF file failed
+    #[error(transparent)]
+    Serialization(#[from] quick_xml::SeError),
+
+    /// Deserializing an XDMF file failed
+    #[error(transparent)]
+    Deserialization(#[from] quick_xml::DeError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xdmf_error_display() {
+        let error = XdmfError::Validation("Size of point-data 'data' must be 3, but is 2".into());
+        assert_eq!(
+            error.to_string(),
+            "Size of point-data 'data' must be 3, but is 2"
+        );
+
+        let error = XdmfError::from(std::io::Error::other("failed to write"));
+        assert!(matches!(error, XdmfError::Io(_)));
+        assert_eq!(error.to_string(), "failed to write");
+    }
+}
@@ -0,0 +1,529 @@
+//! C interface of the [`TimeSeriesWriter`], enabled with the `ffi` feature, e.g. to write the output of C or Fortran solvers.
+//!
+//! The shared library is built with `cargo rustc --release --features ffi --crate-type cdylib`, the functions are declared in `include/xdmf.h`.
+//! All functions return an [`XdmfStatus`], the message of the last error of the calling thread is available with [`xdmf_last_error_message`].
+//! The fields of a time step are added one after another and written together with [`xdmf_writer_write_step`]:
+//! ```c
+//! #include "xdmf.h"
+//!
+//! double points[] = {0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0};
+//! uint64_t connectivity[] = {0, 1, 2};
+//! uint64_t cell_types[] = {4}; // triangle
+//! uint64_t cell_sizes[] = {3};
+//! double temperature[] = {20.0, 21.0, 22.0};
+//!
+//! XdmfWriter* writer = NULL;
+//! xdmf_writer_create("result", "hdf5-single-file", &writer);
+//! xdmf_writer_write_mesh(writer, points, 3, connectivity, 3, cell_types, cell_sizes, 1);
+//!
+//! for (int step = 0; step < 10; ++step) {
+//!     xdmf_writer_add_point_data(writer, "temperature", temperature, 3, 1);
+//!     if (xdmf_writer_write_step(writer, 0.1 * step) != XDMF_OK) {
+//!         char message[256];
+//!         xdmf_last_error_message(message, sizeof(message));
+//!     }
+//! }
+//!
+//! xdmf_writer_finalize(writer);
+//! ```
+
+use std::{
+    cell::RefCell,
+    ffi::{CStr, c_char},
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+use crate::{
+    CellType, DataAttribute, DataMap, DataStorage, TimeSeriesDataWriter, TimeSeriesWriter, Values,
+    XdmfError,
+};
+
+/// Status returned by the functions of the C interface.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum XdmfStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// An argument is invalid, e.g. a null pointer, or the call is not valid in the state of the writer
+    InvalidArgument = 1,
+    /// Reading or writing a file failed, see [`XdmfError::Io`]
+    Io = 2,
+    /// The input is invalid, see [`XdmfError::Validation`]
+    Validation = 3,
+    /// The operation is not supported, see [`XdmfError::Unsupported`]
+    Unsupported = 4,
+    /// An operation of the HDF5 library failed
+    #[cfg(feature = "hdf5")]
+    Hdf5 = 5,
+    /// Serializing the XDMF file failed
+    Serialization = 6,
+    /// The writer panicked, it should not be used anymore
+    Panic = 7,
+}
+
+/// Opaque handle of a writer, created with [`xdmf_writer_create`] and freed with [`xdmf_writer_finalize`].
+pub struct XdmfWriter {
+    // taken when the mesh is written, hence none after writing the mesh failed
+    mesh_writer: Option<TimeSeriesWriter>,
+    data_writer: Option<TimeSeriesDataWriter>,
+    // fields of the next time step, copied when they are added
+    point_data: DataMap<'static>,
+    cell_data: DataMap<'static>,
+}
+
+// errors of the calls, which are turned into the status and the last error message
+enum FfiError {
+    InvalidArgument(String),
+    Xdmf(XdmfError),
+}
+
+impl From<XdmfError> for FfiError {
+    fn from(error: XdmfError) -> Self {
+        Self::Xdmf(error)
+    }
+}
+
+type FfiResult<T> = Result<T, FfiError>;
+
+thread_local! {
+    static LAST_ERROR: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+// runs a call, storing the message of its error and catching panics, which must not unwind into C
+fn ffi_call(call: impl FnOnce() -> FfiResult<()>) -> XdmfStatus {
+    let (status, message) = match panic::catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(())) => return XdmfStatus::Ok,
+        Ok(Err(FfiError::InvalidArgument(message))) => (XdmfStatus::InvalidArgument, message),
+        Ok(Err(FfiError::Xdmf(error))) => (status_of(&error), error.to_string()),
+        Err(_) => (
+            XdmfStatus::Panic,
+            "The writer panicked, it should not be used anymore".to_string(),
+        ),
+    };
+
+    LAST_ERROR.with_borrow_mut(|last_error| *last_error = message);
+    status
+}
+
+fn status_of(error: &XdmfError) -> XdmfStatus {
+    match error {
+        XdmfError::Io(_) => XdmfStatus::Io,
+        #[cfg(feature = "hdf5")]
+        XdmfError::Hdf5(_) => XdmfStatus::Hdf5,
+        XdmfError::Validation(_) => XdmfStatus::Validation,
+        XdmfError::Unsupported(_) => XdmfStatus::Unsupported,
+        XdmfError::Serialization(_) | XdmfError::Deserialization(_) => XdmfStatus::Serialization,
+    }
+}
+
+fn invalid_argument(message: impl ToString) -> FfiError {
+    FfiError::InvalidArgument(message.to_string())
+}
+
+// a null pointer is only valid for an empty slice
+unsafe fn slice<'a, T>(data: *const T, len: usize, name: &str) -> FfiResult<&'a [T]> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if data.is_null() {
+        return Err(invalid_argument(format!("'{name}' must not be null")));
+    }
+
+    // SAFETY: the caller guarantees that `data` points to `len` values
+    Ok(unsafe { std::slice::from_raw_parts(data, len) })
+}
+
+unsafe fn string<'a>(string: *const c_char, name: &str) -> FfiResult<&'a str> {
+    if string.is_null() {
+        return Err(invalid_argument(format!("'{name}' must not be null")));
+    }
+
+    // SAFETY: the caller guarantees that `string` is nul-terminated
+    unsafe { CStr::from_ptr(string) }
+        .to_str()
+        .map_err(|_err| invalid_argument(format!("'{name}' must be valid UTF-8")))
+}
+
+unsafe fn writer_mut<'a>(writer: *mut XdmfWriter) -> FfiResult<&'a mut XdmfWriter> {
+    // SAFETY: the caller guarantees that `writer` was created by `xdmf_writer_create`
+    unsafe { writer.as_mut() }.ok_or_else(|| invalid_argument("'writer' must not be null"))
+}
+
+fn data_writer(writer: &mut XdmfWriter) -> FfiResult<&mut TimeSeriesDataWriter> {
+    writer
+        .data_writer
+        .as_mut()
+        .ok_or_else(|| invalid_argument("The mesh must be written before the time steps"))
+}
+
+// the cell types are given by their XDMF ids and number of points, as in checkpoints
+fn cell_types(ids: &[u64], sizes: &[u64], connectivity_len: usize) -> FfiResult<Vec<CellType>> {
+    let cell_types = ids
+        .iter()
+        .zip(sizes)
+        .map(|(&id, &size)| {
+            let num_points = usize::try_from(size).map_err(|_err| {
+                invalid_argument(format!("Cell type {id} with {size} points is not valid"))
+            })?;
+
+            CellType::from_xdmf_id(id, num_points)
+                .filter(|cell_type| cell_type.num_points() == num_points)
+                .ok_or_else(|| {
+                    invalid_argument(format!("Cell type {id} with {size} points is not valid"))
+                })
+        })
+        .collect::<FfiResult<Vec<_>>>()?;
+
+    if cell_types.iter().map(CellType::num_points).sum::<usize>() != connectivity_len {
+        return Err(invalid_argument(
+            "The sizes of the cells do not match the connectivity",
+        ));
+    }
+
+    Ok(cell_types)
+}
+
+// the data attribute follows from the number of components, see `Values` for their order
+unsafe fn add_data(
+    data: &mut DataMap<'static>,
+    name: *const c_char,
+    values: *const f64,
+    len: usize,
+    num_components: usize,
+) -> FfiResult<()> {
+    // SAFETY: guaranteed by the callers
+    let name = unsafe { string(name, "name") }?;
+    let values = unsafe { slice(values, len, "values") }?;
+
+    let data_attribute = match num_components {
+        0 => return Err(invalid_argument("'num_components' must be at least 1")),
+        1 => DataAttribute::Scalar,
+        3 => DataAttribute::Vector,
+        6 => DataAttribute::Tensor6,
+        9 => DataAttribute::Tensor,
+        _ => DataAttribute::Generic(vec![num_components]),
+    };
+
+    data.insert(
+        name.to_string(),
+        (data_attribute, Values::from(values.to_vec())),
+    );
+    Ok(())
+}
+
+/// Create a writer of the XDMF file with the given name, the storage is given by its name, e.g. `"hdf5-single-file"`.
+///
+/// # Safety
+/// `file_name` and `storage` must be nul-terminated strings, `writer` must point to a writable pointer,
+/// which is set to the created writer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn xdmf_writer_create(
+    file_name: *const c_char,
+    storage: *const c_char,
+    writer: *mut *mut XdmfWriter,
+) -> XdmfStatus {
+    ffi_call(|| {
+        if writer.is_null() {
+            return Err(invalid_argument("'writer' must not be null"));
+        }
+        // SAFETY: guaranteed by the caller
+        let file_name = unsafe { string(file_name, "file_name") }?;
+        let storage: DataStorage = unsafe { string(storage, "storage") }?
+            .parse()
+            .map_err(invalid_argument)?;
+
+        let created = Box::new(XdmfWriter {
+            mesh_writer: Some(TimeSeriesWriter::new(file_name, storage)?),
+            data_writer: None,
+            point_data: DataMap::new(),
+            cell_data: DataMap::new(),
+        });
+        // SAFETY: checked for null above
+        unsafe { *writer = Box::into_raw(created) };
+        Ok(())
+    })
+}
+
+/// Write the mesh, given by the interleaved coordinates of `num_points` points and the cells.
+///
+/// The connectivity contains the zero-based indices of the points of the cells. The type of each cell is given
+/// by its id in the mixed topology of XDMF (e.g. 4 for triangles) and its number of points in the connectivity.
+///
+/// # Safety
+/// `writer` must be created by [`xdmf_writer_create`], `points` must point to `3 * num_points` values,
+/// `connectivity` to `connectivity_len` values, and `cell_types` and `cell_sizes` to `num_cells` values each.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn xdmf_writer_write_mesh(
+    writer: *mut XdmfWriter,
+    points: *const f64,
+    num_points: usize,
+    connectivity: *const u64,
+    connectivity_len: usize,
+    cell_types: *const u64,
+    cell_sizes: *const u64,
+    num_cells: usize,
+) -> XdmfStatus {
+    ffi_call(|| {
+        // SAFETY: guaranteed by the caller
+        let writer = unsafe { writer_mut(writer) }?;
+        let num_coordinates = num_points
+            .checked_mul(3)
+            .ok_or_else(|| invalid_argument(format!("{num_points} points are too many")))?;
+        let points = unsafe { slice(points, num_coordinates, "points") }?;
+        let connectivity = unsafe { slice(connectivity, connectivity_len, "connectivity") }?;
+        let cell_type_ids = unsafe { slice(cell_types, num_cells, "cell_types") }?;
+        let cell_sizes = unsafe { slice(cell_sizes, num_cells, "cell_sizes") }?;
+
+        let cell_types = self::cell_types(cell_type_ids, cell_sizes, connectivity.len())?;
+        let mesh_writer = writer
+            .mesh_writer
+            .take()
+            .ok_or_else(|| invalid_argument("The mesh has already been written"))?;
+
+        writer.data_writer = Some(mesh_writer.write_mesh(points, (connectivity, &cell_types))?);
+        Ok(())
+    })
+}
+
+/// Add a field at the points to the next time step, its `len` values are copied.
+///
+/// The field has `num_components` values per point, 1 for scalars, 3 for vectors,
+/// 6 for symmetric tensors (XX, XY, XZ, YY, YZ, ZZ) and 9 for tensors.
+///
+/// # Safety
+/// `writer` must be created by [`xdmf_writer_create`], `name` must be a nul-terminated string
+/// and `values` must point to `len` values.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn xdmf_writer_add_point_data(
+    writer: *mut XdmfWriter,
+    name: *const c_char,
+    values: *const f64,
+    len: usize,
+    num_components: usize,
+) -> XdmfStatus {
+    ffi_call(|| {
+        // SAFETY: guaranteed by the caller
+        let writer = unsafe { writer_mut(writer) }?;
+        unsafe { add_data(&mut writer.point_data, name, values, len, num_components) }
+    })
+}
+
+/// Add a field at the cells to the next time step, see [`xdmf_writer_add_point_data`] for details.
+///
+/// # Safety
+/// See [`xdmf_writer_add_point_data`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn xdmf_writer_add_cell_data(
+    writer: *mut XdmfWriter,
+    name: *const c_char,
+    values: *const f64,
+    len: usize,
+    num_components: usize,
+) -> XdmfStatus {
+    ffi_call(|| {
+        // SAFETY: guaranteed by the caller
+        let writer = unsafe { writer_mut(writer) }?;
+        unsafe { add_data(&mut writer.cell_data, name, values, len, num_components) }
+    })
+}
+
+/// Write the added fields as the time step at the given time, see [`TimeSeriesDataWriter::write_data`].
+///
+/// The added fields are removed afterwards, also if writing them failed.
+///
+/// # Safety
+/// `writer` must be created by [`xdmf_writer_create`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn xdmf_writer_write_step(writer: *mut XdmfWriter, time: f64) -> XdmfStatus {
+    ffi_call(|| {
+        // SAFETY: guaranteed by the caller
+        let writer = unsafe { writer_mut(writer) }?;
+        let point_data = std::mem::take(&mut writer.point_data);
+        let cell_data = std::mem::take(&mut writer.cell_data);
+
+        data_writer(writer)?.write_data(
+            &time,
+            (!point_data.is_empty()).then_some(&point_data),
+            (!cell_data.is_empty()).then_some(&cell_data),
+        )?;
+        Ok(())
+    })
+}
+
+/// Write the XDMF file now, see [`TimeSeriesDataWriter::flush_xml`].
+///
+/// # Safety
+/// `writer` must be created by [`xdmf_writer_create`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn xdmf_writer_flush(writer: *mut XdmfWriter) -> XdmfStatus {
+    ffi_call(|| {
+        // SAFETY: guaranteed by the caller
+        let writer = unsafe { writer_mut(writer) }?;
+        data_writer(writer)?.flush_xml()?;
+        Ok(())
+    })
+}
+
+/// Close the writer, which writes the XDMF file a last time, and free it. A null pointer is ignored.
+///
+/// # Safety
+/// `writer` must be created by [`xdmf_writer_create`] and must not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn xdmf_writer_finalize(writer: *mut XdmfWriter) -> XdmfStatus {
+    ffi_call(|| {
+        if writer.is_null() {
+            return Ok(());
+        }
+
+        // SAFETY: guaranteed by the caller
+        let writer = unsafe { Box::from_raw(writer) };
+        if let Some(data_writer) = writer.data_writer {
+            data_writer.close()?;
+        }
+        Ok(())
+    })
+}
+
+/// Copy the message of the last error of the calling thread into `buffer`, returning the length of the message.
+///
+/// The message is truncated to `size - 1` bytes and nul-terminated. With a null `buffer` only the length is returned.
+///
+/// # Safety
+/// `buffer` must be null or point to `size` writable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn xdmf_last_error_message(buffer: *mut c_char, size: usize) -> usize {
+    LAST_ERROR.with_borrow(|message| {
+        if !buffer.is_null() && size > 0 {
+            let len = message.len().min(size - 1);
+            // SAFETY: guaranteed by the caller
+            unsafe {
+                ptr::copy_nonoverlapping(message.as_ptr().cast::<c_char>(), buffer, len);
+                *buffer.add(len) = 0;
+            }
+        }
+
+        message.len()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error_message() -> String {
+        let mut buffer = [0 as c_char; 256];
+        let len = unsafe { xdmf_last_error_message(buffer.as_mut_ptr(), buffer.len()) };
+        let message = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+        assert_eq!(message.to_bytes().len(), len);
+        message.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn write_with_ffi() {
+        let tmp_dir = temp_dir::TempDir::new().unwrap();
+        let file_name =
+            std::ffi::CString::new(tmp_dir.path().join("ffi").to_str().unwrap()).unwrap();
+
+        let mut writer = ptr::null_mut();
+        let status = unsafe {
+            xdmf_writer_create(file_name.as_ptr(), c"ascii-inline".as_ptr(), &mut writer)
+        };
+        assert_eq!(status, XdmfStatus::Ok);
+
+        // the time steps require the mesh
+        let status = unsafe { xdmf_writer_write_step(writer, 0.0) };
+        assert_eq!(status, XdmfStatus::InvalidArgument);
+        assert_eq!(
+            last_error_message(),
+            "The mesh must be written before the time steps"
+        );
+
+        let points = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0];
+        let connectivity = [0, 1, 3, 2, 1, 3, 2];
+        // a triangle has 3 points
+        let (cell_types, cell_sizes) = ([3, 4], [4, 4]);
+        let status = unsafe {
+            xdmf_writer_write_mesh(
+                writer,
+                points.as_ptr(),
+                4,
+                connectivity.as_ptr(),
+                connectivity.len(),
+                cell_types.as_ptr(),
+                cell_sizes.as_ptr(),
+                2,
+            )
+        };
+        assert_eq!(status, XdmfStatus::InvalidArgument);
+        assert_eq!(
+            last_error_message(),
+            "Cell type 4 with 4 points is not valid"
+        );
+
+        // the number of coordinates must not overflow
+        let status = unsafe {
+            xdmf_writer_write_mesh(
+                writer,
+                points.as_ptr(),
+                usize::MAX,
+                connectivity.as_ptr(),
+                connectivity.len(),
+                cell_types.as_ptr(),
+                cell_sizes.as_ptr(),
+                2,
+            )
+        };
+        assert_eq!(status, XdmfStatus::InvalidArgument);
+        assert_eq!(
+            last_error_message(),
+            format!("{} points are too many", usize::MAX)
+        );
+
+        // a polygon and a triangle
+        let (cell_types, cell_sizes) = ([3, 4], [4, 3]);
+        let status = unsafe {
+            xdmf_writer_write_mesh(
+                writer,
+                points.as_ptr(),
+                4,
+                connectivity.as_ptr(),
+                connectivity.len(),
+                cell_types.as_ptr(),
+                cell_sizes.as_ptr(),
+                2,
+            )
+        };
+        assert_eq!(status, XdmfStatus::Ok);
+
+        let temperature = [1.0, 2.0, 3.0, 4.0];
+        let velocity = [0.5; 6];
+        unsafe {
+            assert_eq!(
+                xdmf_writer_add_point_data(writer, c"T".as_ptr(), temperature.as_ptr(), 4, 1),
+                XdmfStatus::Ok
+            );
+            assert_eq!(
+                xdmf_writer_add_cell_data(writer, c"v".as_ptr(), velocity.as_ptr(), 6, 3),
+                XdmfStatus::Ok
+            );
+            assert_eq!(xdmf_writer_write_step(writer, 0.5), XdmfStatus::Ok);
+
+            // the fields are validated by the writer
+            xdmf_writer_add_point_data(writer, c"T".as_ptr(), temperature.as_ptr(), 3, 1);
+            assert_eq!(xdmf_writer_write_step(writer, 1.0), XdmfStatus::Validation);
+        }
+        assert_eq!(
+            last_error_message(),
+            "Size of point-data 'T' must be 4, but is 3"
+        );
+
+        assert_eq!(unsafe { xdmf_writer_finalize(writer) }, XdmfStatus::Ok);
+
+        let xdmf = std::fs::read_to_string(tmp_dir.path().join("ffi.xdmf2")).unwrap();
+        assert!(xdmf.contains(r#"<Attribute Name="T" AttributeType="Scalar" Center="Node">"#));
+        assert!(xdmf.contains(r#"<Attribute Name="v" AttributeType="Vector" Center="Cell">"#));
+        assert!(xdmf.contains(r#"<Time Value="0.5"/>"#));
+        assert!(!xdmf.contains(r#"<Time Value="1"/>"#));
+    }
+}
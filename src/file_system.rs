@@ -0,0 +1,209 @@
+//! This module contains the abstraction of the file system the files are written to, such that they can also be kept in memory.
+
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{Error as IoError, ErrorKind, Result as IoResult, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::mpi_safe_create_dir_all;
+
+/// File system the XDMF file and the files of the heavy data are written to.
+///
+/// By default the files are written to disk with the [`LocalFileSystem`], the [`MemoryFileSystem`] keeps them in memory instead,
+/// e.g. to send them to an in-situ visualization service. Other targets, e.g. an archive, can be supported by implementing this trait.
+pub trait FileSystem: Send + Sync {
+    /// Create a file for writing, replacing it if it exists.
+    fn create_file(&self, path: &Path) -> IoResult<Box<dyn Write + Send>>;
+
+    /// Create a directory and all of its parents, if they do not exist.
+    fn create_dir_all(&self, path: &Path) -> IoResult<()>;
+
+    /// Remove a file.
+    fn remove_file(&self, path: &Path) -> IoResult<()>;
+
+    /// Rename a file, replacing the target if it exists.
+    fn rename(&self, from: &Path, to: &Path) -> IoResult<()>;
+}
+
+/// Writes the files to disk.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LocalFileSystem;
+
+impl FileSystem for LocalFileSystem {
+    fn create_file(&self, path: &Path) -> IoResult<Box<dyn Write + Send>> {
+        Ok(Box::new(File::create(path)?))
+    }
+
+    fn create_dir_all(&self, path: &Path) -> IoResult<()> {
+        mpi_safe_create_dir_all(path)
+    }
+
+    fn remove_file(&self, path: &Path) -> IoResult<()> {
+        std::fs::remove_file(path)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> IoResult<()> {
+        std::fs::rename(from, to)
+    }
+}
+
+/// Keeps the files in memory, such that nothing is written to disk.
+///
+/// Clones share the files, hence a clone can be given to the writer while the written files are retrieved from the original.
+/// ```rust
+/// use xdmf::{MemoryFileSystem, TimeSeriesWriter};
+/// let memory = MemoryFileSystem::default();
+///
+/// let xdmf_writer = TimeSeriesWriter::new_with_file_system(
+///     "in_memory",
+///     xdmf::DataStorage::Ascii,
+///     memory.clone(),
+/// )
+/// .expect("failed to create XDMF writer");
+///
+/// xdmf_writer
+///     .write_mesh(&[0.0, 0.0, 0.0], (&[], &[]))
+///     .expect("failed to write mesh");
+///
+/// // the XDMF file and the files of the heavy data
+/// let xdmf_file = memory
+///     .file("in_memory.xdmf2")
+///     .expect("XDMF file was not written");
+/// assert!(String::from_utf8_lossy(&xdmf_file).contains("in_memory.txt/points.txt"));
+///
+/// let points_file = memory
+///     .file("in_memory.txt/points.txt")
+///     .expect("points were not written");
+/// assert_eq!(
+///     points_file,
+///     b"0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0\n"
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MemoryFileSystem {
+    files: Arc<Mutex<BTreeMap<PathBuf, Vec<u8>>>>,
+}
+
+impl MemoryFileSystem {
+    /// Content of a written file, if it exists.
+    pub fn file(&self, path: impl AsRef<Path>) -> Option<Vec<u8>> {
+        self.lock_files().get(path.as_ref()).cloned()
+    }
+
+    /// All written files with their content, by their path.
+    pub fn files(&self) -> BTreeMap<PathBuf, Vec<u8>> {
+        self.lock_files().clone()
+    }
+
+    // the files are only modified by appending or replacing them, hence they are consistent even if a writer panicked
+    fn lock_files(&self) -> std::sync::MutexGuard<'_, BTreeMap<PathBuf, Vec<u8>>> {
+        self.files.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn create_file(&self, path: &Path) -> IoResult<Box<dyn Write + Send>> {
+        self.lock_files().insert(path.to_path_buf(), Vec::new());
+
+        Ok(Box::new(MemoryFile {
+            file_system: self.clone(),
+            path: path.to_path_buf(),
+        }))
+    }
+
+    fn create_dir_all(&self, _path: &Path) -> IoResult<()> {
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &Path) -> IoResult<()> {
+        self.lock_files()
+            .remove(path)
+            .map(|_| ())
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> IoResult<()> {
+        let mut files = self.lock_files();
+        let content = files.remove(from).ok_or_else(|| not_found(from))?;
+        files.insert(to.to_path_buf(), content);
+
+        Ok(())
+    }
+}
+
+fn not_found(path: &Path) -> IoError {
+    IoError::new(
+        ErrorKind::NotFound,
+        format!("File '{}' does not exist", path.display()),
+    )
+}
+
+/// File of a [`MemoryFileSystem`], the written bytes are appended to its content.
+struct MemoryFile {
+    file_system: MemoryFileSystem,
+    path: PathBuf,
+}
+
+impl Write for MemoryFile {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.file_system
+            .lock_files()
+            .get_mut(&self.path)
+            .ok_or_else(|| not_found(&self.path))?
+            .extend_from_slice(buf);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_file_system() {
+        let memory = MemoryFileSystem::default();
+        let file_system: &dyn FileSystem = &memory.clone();
+
+        let mut file = file_system.create_file(Path::new("out/file.txt")).unwrap();
+        file.write_all(b"hello ").unwrap();
+        file.write_all(b"world").unwrap();
+        assert_eq!(memory.file("out/file.txt").unwrap(), b"hello world");
+
+        file_system
+            .rename(Path::new("out/file.txt"), Path::new("out/renamed.txt"))
+            .unwrap();
+        assert_eq!(
+            memory.files(),
+            BTreeMap::from([(PathBuf::from("out/renamed.txt"), b"hello world".to_vec())])
+        );
+
+        // creating a file again replaces its content
+        file_system
+            .create_file(Path::new("out/renamed.txt"))
+            .unwrap()
+            .write_all(b"new")
+            .unwrap();
+        assert_eq!(memory.file("out/renamed.txt").unwrap(), b"new");
+
+        file_system
+            .remove_file(Path::new("out/renamed.txt"))
+            .unwrap();
+        assert!(memory.files().is_empty());
+
+        assert_eq!(
+            file_system
+                .remove_file(Path::new("out/renamed.txt"))
+                .unwrap_err()
+                .to_string(),
+            "File 'out/renamed.txt' does not exist"
+        );
+    }
+}
@@ -1,55 +1,375 @@
 //! Implementations of writers for HDF5 data storage (single and multiple files).
 
 use std::{
-    io::{Error as IoError, ErrorKind::InvalidFilename, Result as IoResult},
+    collections::HashMap,
     path::{Path, PathBuf},
 };
 
-use hdf5::{File as H5File, Group as H5Group};
+use hdf5::{Dataset, File as H5File, FileBuilder, Group as H5Group, H5Type, filters::Filter};
+#[cfg(feature = "hdf5-mpio")]
+use mpi::{
+    raw::AsRaw,
+    topology::{Communicator, SimpleCommunicator},
+    traits::CommunicatorCollectives,
+};
 
 use crate::{
-    DataStorage, DataWriter, Values,
+    DataStorage, DataWriter, Hdf5FileNaming, Hdf5Layout, Hdf5Options, IndexPrecision, PathPolicy,
+    Values, XdmfError, XdmfResult, check_stream_exhausted,
     xdmf_elements::{
         attribute,
-        data_item::{DataContent, Format},
+        data_item::{DataContent, DataItem, Format},
+        dimensions::Dimensions,
     },
 };
 
 const MESH: &str = "mesh";
 const DATA: &str = "data";
 const POINTS: &str = "points";
+const POINTS_XYZ: [&str; 3] = ["points_x", "points_y", "points_z"];
 const CELLS: &str = "cells";
+const SETS: &str = "sets";
+const INDICES: &str = "indices";
+
+/// Number of values that are buffered at once when writing streamed data.
+const STREAM_CHUNK_SIZE: usize = 1 << 16;
+
+/// Maximum size in bytes of the chunks of a row of a time-major dataset, well below the limit of HDF5 of 4 GiB.
+const MAX_CHUNK_BYTES: usize = 1 << 30;
 
 pub(crate) struct SingleFileHdf5Writer {
     h5_file: H5File,
+    // group containing all groups and datasets of this writer, the root group of the file if no group is given
+    root: H5Group,
+    h5_file_path: PathBuf,
+    // path of the file relative to the XDMF file
     h5_file_name: PathBuf,
+    path_policy: PathPolicy,
     write_time: Option<String>,
+    filters: Vec<Filter>,
+    layout: Hdf5Layout,
+    index_precision: IndexPrecision,
+    // rows of the datasets of the time-major layout, by their path as written to the XDMF file
+    time_rows: HashMap<String, TimeRows>,
+    #[cfg(feature = "hdf5-mpio")]
+    collective: Option<Collective>,
+}
+
+// time steps of the rows of a dataset with one row per time step
+#[derive(Default)]
+struct TimeRows {
+    // label of the time step of each row
+    labels: Vec<String>,
+    // row that was written last, which is selected by the wrapped DataItem
+    last_written: usize,
 }
 
 /// TODO show file hierarchy, and how data is structured
 impl SingleFileHdf5Writer {
-    pub(crate) fn new(file_name: impl AsRef<Path>) -> IoResult<Self> {
-        let h5_file_name_full = file_name.as_ref().to_path_buf().with_extension("h5");
+    pub(crate) fn new(file_name: impl AsRef<Path>) -> XdmfResult<Self> {
+        Self::create(file_name.as_ref(), &H5File::with_options(), None)
+    }
+
+    /// Create a writer that writes into the given group of the file, such that multiple writers can share a file.
+    ///
+    /// An existing file is opened and kept, only the group must not exist yet.
+    pub(crate) fn new_in_group(file_name: impl AsRef<Path>, group: &str) -> XdmfResult<Self> {
+        Self::create(file_name.as_ref(), &H5File::with_options(), Some(group))
+    }
+
+    /// Create a writer for all ranks of the communicator, which write collectively into one file using parallel HDF5.
+    ///
+    /// Each dataset contains the values of all ranks, ordered by rank.
+    #[cfg(feature = "hdf5-mpio")]
+    pub(crate) fn new_collective(
+        file_name: impl AsRef<Path>,
+        comm: &impl Communicator,
+    ) -> XdmfResult<Self> {
+        let comm = comm.duplicate();
+        let raw_comm = comm.as_raw();
+
+        let mut file_builder = H5File::with_options();
+        file_builder.with_fapl(|fapl| fapl.mpio(raw_comm, None));
+
+        let mut writer = Self::create(file_name.as_ref(), &file_builder, None)?;
+        writer.collective = Some(Collective {
+            comm,
+            selections: HashMap::new(),
+        });
+
+        Ok(writer)
+    }
+
+    fn create(
+        file_name: &Path,
+        file_builder: &FileBuilder,
+        root_group: Option<&str>,
+    ) -> XdmfResult<Self> {
+        let h5_file_name_full = file_name.to_path_buf().with_extension("h5");
 
         if let Some(parent) = h5_file_name_full.parent() {
             crate::mpi_safe_create_dir_all(parent)?;
         }
 
         let h5_file_name = h5_file_name_full.file_name().ok_or_else(|| {
-            IoError::new(
-                InvalidFilename,
-                "Input file name must have a valid file name",
-            )
+            XdmfError::Validation("Input file name must have a valid file name".into())
         })?;
 
-        let h5_file = H5File::create(&h5_file_name_full).map_err(IoError::other)?;
+        let (h5_file, root) = match root_group {
+            Some(root_group) => {
+                let h5_file = file_builder.append(&h5_file_name_full)?;
+
+                // the group is owned by this writer, writing into the group of another writer would mix their data
+                if h5_file.link_exists(root_group) {
+                    return Err(XdmfError::Validation(format!(
+                        "Group '{root_group}' already exists in HDF5 file '{}'",
+                        h5_file_name_full.display()
+                    )));
+                }
+
+                let root = h5_file.create_group(root_group)?;
+                (h5_file, root)
+            }
+            None => {
+                let h5_file = file_builder.create(&h5_file_name_full)?;
+                let root = h5_file.group("/")?;
+                (h5_file, root)
+            }
+        };
 
         Ok(Self {
             h5_file,
+            root,
             h5_file_name: h5_file_name.into(),
+            h5_file_path: h5_file_name_full,
+            path_policy: PathPolicy::default(),
             write_time: None,
+            filters: Vec::new(),
+            layout: Hdf5Layout::default(),
+            index_precision: IndexPrecision::default(),
+            time_rows: HashMap::new(),
+            #[cfg(feature = "hdf5-mpio")]
+            collective: None,
         })
     }
+
+    // path of the file as written to the XDMF file
+    fn reference_path(&self) -> XdmfResult<String> {
+        self.path_policy
+            .reference_path(&self.h5_file_path, &self.h5_file_name)
+    }
+
+    fn write_dataset<T: H5Type>(
+        &mut self,
+        group: &H5Group,
+        dataset_name: &str,
+        values: &[T],
+    ) -> XdmfResult<DataContent> {
+        self.write_shaped_dataset(group, dataset_name, values, &[values.len()])
+    }
+
+    // write the indices into a new dataset, as u32 if they are written with this precision
+    fn write_indices(
+        &mut self,
+        group: &H5Group,
+        dataset_name: &str,
+        indices: &[u64],
+    ) -> XdmfResult<DataContent> {
+        if self.index_precision().resolve(indices)? == 4 {
+            let indices = indices_u32(indices);
+            self.write_dataset(group, dataset_name, &indices)
+        } else {
+            self.write_dataset(group, dataset_name, indices)
+        }
+    }
+
+    // write the values into a new dataset of the given shape, which contains the values of all ranks if written collectively
+    fn write_shaped_dataset<T: H5Type>(
+        &mut self,
+        group: &H5Group,
+        dataset_name: &str,
+        values: &[T],
+        shape: &[usize],
+    ) -> XdmfResult<DataContent> {
+        // the data of an overwritten time step replaces the existing dataset
+        // note that HDF5 does not reclaim the space of the unlinked dataset
+        if group.link_exists(dataset_name) {
+            group.unlink(dataset_name)?;
+        }
+
+        let reference_path = self.reference_path()?;
+
+        // the values of all ranks are concatenated, hence a collectively written dataset is one-dimensional
+        #[cfg(feature = "hdf5-mpio")]
+        if let Some(collective) = &mut self.collective {
+            let selection = collective.selection(values.len());
+
+            // the dataset is created collectively, afterwards each rank writes its values
+            let dataset = group
+                .new_dataset::<T>()
+                .shape(selection.len)
+                .create(dataset_name)?;
+            dataset.write_slice(values, selection.start..selection.start + selection.count)?;
+
+            return Ok(collective.register(&reference_path, &dataset.name(), selection));
+        }
+
+        let dataset = group
+            .new_dataset::<T>()
+            .set_filters(&self.filters)
+            .shape(shape.to_vec())
+            .create(dataset_name)?;
+        dataset.write_raw(values)?;
+
+        Ok(full_path(&reference_path, &dataset.name()).into())
+    }
+
+    fn write_values(
+        &mut self,
+        group: &H5Group,
+        dataset_name: &str,
+        vals: &Values,
+        shape: &[usize],
+    ) -> XdmfResult<DataContent> {
+        match vals {
+            Values::F64(v) => self.write_shaped_dataset(group, dataset_name, v, shape),
+            Values::F32(v) => self.write_shaped_dataset(group, dataset_name, v, shape),
+            Values::U64(v) => self.write_shaped_dataset(group, dataset_name, v, shape),
+            Values::I64(v) => self.write_shaped_dataset(group, dataset_name, v, shape),
+            Values::U8(v) => self.write_shaped_dataset(group, dataset_name, v, shape),
+        }
+    }
+
+    // write the values as the row of the current time step into the dataset of the field, see `Hdf5Layout::TimeMajorDatasets`
+    fn write_time_row(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        vals: &Values,
+    ) -> XdmfResult<DataContent> {
+        match vals {
+            Values::F64(v) => self.write_time_row_typed(name, center, v),
+            Values::F32(v) => self.write_time_row_typed(name, center, v),
+            Values::U64(v) => self.write_time_row_typed(name, center, v),
+            Values::I64(v) => self.write_time_row_typed(name, center, v),
+            Values::U8(v) => self.write_time_row_typed(name, center, v),
+        }
+    }
+
+    fn write_time_row_typed<T: H5Type>(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        values: &[T],
+    ) -> XdmfResult<DataContent> {
+        let (dataset, data_path, row) = self.time_row_dataset::<T>(name, center, values.len())?;
+        dataset.write_slice(values, (row, ..))?;
+
+        Ok(data_path.into())
+    }
+
+    // the dataset of a field with one row per time step, which is created or extended by the row of the current time step if needed,
+    // along with its path as written to the XDMF file and the row of the current time step
+    fn time_row_dataset<T: H5Type>(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        len: usize,
+    ) -> XdmfResult<(Dataset, String, usize)> {
+        let time = self
+            .write_time
+            .clone()
+            .ok_or_else(|| XdmfError::Validation("Writing data was not initialized".into()))?;
+
+        let group_name = format!("{DATA}/{}", attribute::center_to_data_tag(center));
+
+        // Create the group if it does not exist
+        if !self.root.link_exists(&group_name) {
+            self.root.create_group(&group_name)?;
+        }
+
+        let group = self.root.group(&group_name)?;
+
+        let dataset = if group.link_exists(name) {
+            let dataset = group.dataset(name)?;
+            let row_len = dataset.shape().get(1).copied().unwrap_or_default();
+
+            if row_len != len {
+                return Err(XdmfError::Validation(format!(
+                    "Data '{name}' must have the same size at all time steps with the TimeMajorDatasets Hdf5Layout, but has {len} values instead of {row_len}"
+                )));
+            }
+
+            if !dataset.dtype()?.is::<T>() {
+                return Err(XdmfError::Validation(format!(
+                    "Data '{name}' must have the same type at all time steps with the TimeMajorDatasets Hdf5Layout"
+                )));
+            }
+
+            dataset
+        } else {
+            // the rows are chunked, such that the dataset can grow along the time axis
+            group
+                .new_dataset::<T>()
+                .set_filters(&self.filters)
+                .chunk((1, row_chunk_len::<T>(len)))
+                .shape((0.., 0..))
+                .create(name)?
+        };
+
+        let data_path = full_path(&self.reference_path()?, &dataset.name());
+
+        // the data of an overwritten time step replaces its row
+        let rows = self.time_rows.entry(data_path.clone()).or_default();
+        let row = rows
+            .labels
+            .iter()
+            .position(|label| *label == time)
+            .unwrap_or_else(|| {
+                rows.labels.push(time);
+                rows.labels.len() - 1
+            });
+        rows.last_written = row;
+
+        dataset.resize((rows.labels.len(), len))?;
+
+        Ok((dataset, data_path, row))
+    }
+
+    fn write_mesh_group(
+        &mut self,
+        group_name: &str,
+        points: &[f64],
+        cells: &[u64],
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        let mesh_group = self.root.create_group(group_name)?;
+
+        Ok((
+            self.write_dataset(&mesh_group, POINTS, points)?,
+            self.write_indices(&mesh_group, CELLS, cells)?,
+        ))
+    }
+
+    // group of the data of the current time step, created if it does not exist
+    fn data_group(&self, center: attribute::Center) -> XdmfResult<H5Group> {
+        let time = self
+            .write_time
+            .as_ref()
+            .ok_or_else(|| XdmfError::Validation("Writing data was not initialized".into()))?;
+
+        let group_name = &format!(
+            "{}/t_{time}/{}",
+            DATA,
+            attribute::center_to_data_tag(center)
+        );
+
+        // Create the group if it does not exist
+        if !self.root.link_exists(group_name) {
+            self.root.create_group(group_name)?;
+        }
+
+        Ok(self.root.group(group_name)?)
+    }
 }
 
 impl DataWriter for SingleFileHdf5Writer {
@@ -61,105 +381,377 @@ impl DataWriter for SingleFileHdf5Writer {
         DataStorage::Hdf5SingleFile
     }
 
+    fn set_hdf5_options(&mut self, options: &Hdf5Options) -> XdmfResult<()> {
+        #[cfg(feature = "hdf5-mpio")]
+        if self.collective.is_some() && options.layout() == Hdf5Layout::TimeMajorDatasets {
+            return Err(XdmfError::Unsupported(
+                "TimeMajorDatasets Hdf5Layout is not supported when writing collectively".into(),
+            ));
+        }
+
+        if options.atomic_steps() {
+            return Err(XdmfError::Unsupported(
+                "Atomic steps are only supported by the Hdf5MultipleFiles DataStorage".into(),
+            ));
+        }
+
+        self.filters = filters(options)?;
+        self.layout = options.layout();
+        Ok(())
+    }
+
+    fn set_path_policy(&mut self, path_policy: PathPolicy) {
+        self.path_policy = path_policy;
+    }
+
+    fn heavy_data_path(&self) -> Option<&Path> {
+        Some(&self.h5_file_path)
+    }
+
+    fn index_precision(&self) -> IndexPrecision {
+        // the ranks could resolve the automatic precision differently, but a collectively written dataset has one type
+        #[cfg(feature = "hdf5-mpio")]
+        if self.collective.is_some() && self.index_precision == IndexPrecision::Auto {
+            return IndexPrecision::U64;
+        }
+
+        self.index_precision
+    }
+
+    fn set_index_precision(&mut self, index_precision: IndexPrecision) {
+        self.index_precision = index_precision;
+    }
+
     fn write_mesh(
         &mut self,
         points: &[f64],
         cells: &[u64],
-    ) -> IoResult<(DataContent, DataContent)> {
-        if self.h5_file.link_exists(MESH) {
-            return Err(IoError::other("Mesh was already written"));
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        if self.root.link_exists(MESH) {
+            return Err(XdmfError::Validation("Mesh was already written".into()));
+        }
+
+        self.write_mesh_group(MESH, points, cells)
+    }
+
+    fn write_named_mesh(
+        &mut self,
+        name: &str,
+        points: &[f64],
+        cells: &[u64],
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        let group_name = format!("{MESH}/{name}");
+
+        if self.root.link_exists(&group_name) {
+            return Err(XdmfError::Validation(format!(
+                "Mesh '{name}' was already written"
+            )));
+        }
+
+        self.write_mesh_group(&group_name, points, cells)
+    }
+
+    fn write_mesh_soa(
+        &mut self,
+        name: Option<&str>,
+        coordinates: [&[f64]; 3],
+        cells: &[u64],
+    ) -> XdmfResult<([DataContent; 3], DataContent)> {
+        let group_name = name.map_or_else(|| MESH.to_string(), |name| format!("{MESH}/{name}"));
+
+        if self.root.link_exists(&group_name) {
+            return Err(XdmfError::Validation(format!(
+                "Mesh '{group_name}' was already written"
+            )));
         }
 
-        let mesh_group = self.h5_file.create_group(MESH).map_err(IoError::other)?;
+        let mesh_group = self.root.create_group(&group_name)?;
 
-        let (data_name_points, data_name_cells) = write_mesh(&mesh_group, points, cells)?;
+        let [x, y, z] = coordinates;
+        let [x_name, y_name, z_name] = POINTS_XYZ;
 
         Ok((
-            full_path(&self.h5_file_name, &data_name_points).into(),
-            full_path(&self.h5_file_name, &data_name_cells).into(),
+            [
+                self.write_dataset(&mesh_group, x_name, x)?,
+                self.write_dataset(&mesh_group, y_name, y)?,
+                self.write_dataset(&mesh_group, z_name, z)?,
+            ],
+            self.write_indices(&mesh_group, CELLS, cells)?,
         ))
     }
 
+    fn write_set(&mut self, name: &str, indices: &[u64]) -> XdmfResult<DataContent> {
+        // Create the group if it does not exist
+        if !self.root.link_exists(SETS) {
+            self.root.create_group(SETS)?;
+        }
+
+        let sets_group = self.root.group(SETS)?;
+
+        self.write_indices(&sets_group, name, indices)
+    }
+
     fn write_data(
         &mut self,
         name: &str,
         center: attribute::Center,
         data: &Values,
-    ) -> IoResult<DataContent> {
-        let time = self
-            .write_time
-            .as_ref()
-            .ok_or_else(|| IoError::other("Writing data was not initialized"))?;
+    ) -> XdmfResult<DataContent> {
+        if self.layout == Hdf5Layout::TimeMajorDatasets {
+            return self.write_time_row(name, center, data);
+        }
 
-        let group_name = &format!(
-            "{}/t_{time}/{}",
-            DATA,
-            attribute::center_to_data_tag(center)
-        );
+        let data_group = self.data_group(center)?;
 
-        // Create the group if it does not exist
-        if !self.h5_file.link_exists(group_name) {
-            self.h5_file
-                .create_group(group_name)
-                .map_err(IoError::other)?;
+        self.write_values(&data_group, name, data, &[data.len()])
+    }
+
+    // `write_fields` is not overridden by the HDF5 writers, the fields are written one after another,
+    // as the HDF5 library is not thread-safe and serializes all calls with a global lock anyways
+    fn write_shaped_data(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        data: &Values,
+        dimensions: &Dimensions,
+    ) -> XdmfResult<DataContent> {
+        // the rows of the time-major layout contain the flattened values, their shape is given by the DataItem
+        if self.layout == Hdf5Layout::TimeMajorDatasets {
+            return self.write_time_row(name, center, data);
         }
 
-        let data_path = write_values(
-            &self.h5_file.group(group_name).map_err(IoError::other)?,
-            name,
-            data,
-        )?;
+        let data_group = self.data_group(center)?;
+
+        self.write_values(&data_group, name, data, &dimensions.0)
+    }
+
+    fn write_data_streamed(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        values: &mut dyn Iterator<Item = f64>,
+        len: usize,
+    ) -> XdmfResult<DataContent> {
+        if self.layout == Hdf5Layout::TimeMajorDatasets {
+            let (dataset, data_path, row) = self.time_row_dataset::<f64>(name, center, len)?;
+            write_chunks(&dataset, name, values, Some(row), 0, len)?;
+
+            return Ok(data_path.into());
+        }
+
+        let data_group = self.data_group(center)?;
+        let reference_path = self.reference_path()?;
+
+        #[cfg(feature = "hdf5-mpio")]
+        if let Some(collective) = &mut self.collective {
+            let selection = collective.selection(len);
+
+            let dataset = data_group
+                .new_dataset::<f64>()
+                .shape(selection.len)
+                .create(name)?;
+            write_chunks(&dataset, name, values, None, selection.start, len)?;
+
+            return Ok(collective.register(&reference_path, &dataset.name(), selection));
+        }
+
+        let data_path = write_values_streamed(&data_group, name, values, len, &self.filters)?;
+
+        Ok(full_path(&reference_path, &data_path).into())
+    }
+
+    // the data of this rank is only a part of the collectively written dataset,
+    // and with the time-major layout the data of a time step is a row of the dataset
+    fn wrap_data_item(&self, data_item: DataItem) -> DataItem {
+        let DataContent::Raw(data_path) = &data_item.data else {
+            return data_item;
+        };
 
-        Ok(full_path(&self.h5_file_name, &data_path).into())
+        #[cfg(feature = "hdf5-mpio")]
+        if let Some(selection) = self
+            .collective
+            .as_ref()
+            .and_then(|collective| collective.selections.get(data_path).copied())
+        {
+            return DataItem::new_hyperslab(
+                data_item,
+                selection.start,
+                selection.count,
+                selection.len,
+            );
+        }
+
+        match self.time_rows.get(data_path) {
+            Some(rows) => {
+                DataItem::new_hyperslab_row(data_item, rows.last_written, rows.labels.len())
+            }
+            None => data_item,
+        }
     }
 
-    fn write_data_initialize(&mut self, time: &str) -> IoResult<()> {
+    fn write_data_initialize(&mut self, time: &str) -> XdmfResult<()> {
         if self.write_time.is_some() {
-            return Err(IoError::other("Writing data was already initialized"));
+            return Err(XdmfError::Validation(
+                "Writing data was already initialized".into(),
+            ));
         }
 
         self.write_time = Some(time.to_string());
         Ok(())
     }
-    fn write_data_finalize(&mut self) -> IoResult<()> {
+    fn write_data_finalize(&mut self) -> XdmfResult<()> {
         if self.write_time.is_none() {
-            return Err(IoError::other("Writing data was not initialized"));
+            return Err(XdmfError::Validation(
+                "Writing data was not initialized".into(),
+            ));
         }
 
         self.write_time = None;
         Ok(())
     }
 
-    fn flush(&mut self) -> IoResult<()> {
+    fn flush(&mut self) -> XdmfResult<()> {
         // Flush the HDF5 file
-        self.h5_file.flush().map_err(IoError::other)
+        Ok(self.h5_file.flush()?)
     }
 }
 
-/// TODO show file hierarchy, and how data is structured
+// state of the ranks that write collectively into one file
+#[cfg(feature = "hdf5-mpio")]
+struct Collective {
+    comm: SimpleCommunicator,
+    // selection of the values of this rank, by the path of the dataset as written to the XDMF file
+    selections: HashMap<String, Selection>,
+}
+
+// values `start..start + count` of a dataset with `len` values
+#[cfg(feature = "hdf5-mpio")]
+#[derive(Clone, Copy)]
+struct Selection {
+    start: usize,
+    count: usize,
+    len: usize,
+}
+
+#[cfg(feature = "hdf5-mpio")]
+impl Collective {
+    // selection of the `count` values of this rank, the values of all ranks are ordered by rank
+    // this is a collective operation, i.e. it must be called by all ranks
+    fn selection(&self, count: usize) -> Selection {
+        let mut counts = vec![0_usize; self.comm.size() as usize];
+        self.comm.all_gather_into(&count, &mut counts[..]);
+
+        Selection {
+            start: counts.iter().take(self.comm.rank() as usize).sum(),
+            count,
+            len: counts.iter().sum(),
+        }
+    }
+
+    fn register(
+        &mut self,
+        reference_path: &str,
+        data_name: &str,
+        selection: Selection,
+    ) -> DataContent {
+        let data_path = full_path(reference_path, data_name);
+        self.selections.insert(data_path.clone(), selection);
+        data_path.into()
+    }
+}
+
+/// Writes the mesh, the sets and the data of each time step into separate files in the directory `<file_name>.h5`.
+///
+/// The names of the files are given by the [`Hdf5FileNaming`].
 pub(crate) struct MultipleFilesHdf5Writer {
     h5_files_dir: PathBuf,
-    h5_data_file: Option<H5File>,
+    file_naming: Hdf5FileNaming,
+    // the file of the current time step, and its path as written to the XDMF file
+    h5_data_file: Option<(H5File, String)>,
+    // temporary file of the current time step and the file it replaces once complete, with atomic steps
+    pending_rename: Option<(PathBuf, PathBuf)>,
+    atomic_steps: bool,
+    // index of each written time step, in the order of writing
+    step_indices: HashMap<String, usize>,
+    filters: Vec<Filter>,
+    path_policy: PathPolicy,
+    index_precision: IndexPrecision,
 }
 
 impl MultipleFilesHdf5Writer {
-    pub(crate) fn new(file_name: impl AsRef<Path>) -> IoResult<Self> {
+    pub(crate) fn new(
+        file_name: impl AsRef<Path>,
+        file_naming: &Hdf5FileNaming,
+    ) -> XdmfResult<Self> {
         let h5_files_dir = file_name.as_ref().to_path_buf().with_extension("h5");
 
         h5_files_dir.file_name().ok_or_else(|| {
-            IoError::new(
-                InvalidFilename,
-                "Input file name must have a valid file name",
-            )
+            XdmfError::Validation("Input file name must have a valid file name".into())
         })?;
 
         crate::mpi_safe_create_dir_all(&h5_files_dir)?;
 
         Ok(Self {
             h5_files_dir,
+            file_naming: file_naming.clone(),
             h5_data_file: None,
+            pending_rename: None,
+            atomic_steps: false,
+            step_indices: HashMap::new(),
+            filters: Vec::new(),
+            path_policy: PathPolicy::default(),
+            index_precision: IndexPrecision::default(),
         })
     }
+
+    // path of a file within the directory of the files, and the path as written to the XDMF file
+    fn file_paths(&self, file_name: &Path) -> XdmfResult<(PathBuf, String)> {
+        let dir_name = self.h5_files_dir.file_name().ok_or_else(|| {
+            XdmfError::Validation("Input file name must have a valid file name".into())
+        })?;
+
+        let file_path = self.h5_files_dir.join(file_name);
+        let reference_path = self
+            .path_policy
+            .reference_path(&file_path, &Path::new(dir_name).join(file_name))?;
+
+        Ok((file_path, reference_path))
+    }
+
+    fn write_mesh_file(
+        &self,
+        file_name: &Path,
+        points: &[f64],
+        cells: &[u64],
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        let (file_name, reference_path) = self.file_paths(file_name)?;
+        let h5_file = H5File::create(&file_name)?;
+
+        let (data_name_points, data_name_cells) =
+            write_mesh(&h5_file, points, cells, self.index_precision, &self.filters)?;
+
+        Ok((
+            full_path(&reference_path, &data_name_points).into(),
+            full_path(&reference_path, &data_name_cells).into(),
+        ))
+    }
+
+    // group of the data in the file of the current time step, created if it does not exist
+    fn data_group(&self, center: attribute::Center) -> XdmfResult<(H5Group, String)> {
+        let (data_file, reference_path) = self
+            .h5_data_file
+            .as_ref()
+            .ok_or_else(|| XdmfError::Validation("Writing data was not initialized".into()))?;
+
+        let group_name = attribute::center_to_data_tag(center);
+
+        // Create the group if it does not exist
+        if !data_file.link_exists(group_name) {
+            data_file.create_group(group_name)?;
+        }
+
+        Ok((data_file.group(group_name)?, reference_path.clone()))
+    }
 }
 
 impl DataWriter for MultipleFilesHdf5Writer {
@@ -171,134 +763,445 @@ impl DataWriter for MultipleFilesHdf5Writer {
         DataStorage::Hdf5MultipleFiles
     }
 
+    fn set_hdf5_options(&mut self, options: &Hdf5Options) -> XdmfResult<()> {
+        if options.layout() != Hdf5Layout::GroupPerStep {
+            return Err(XdmfError::Unsupported(format!(
+                "{:?} Hdf5Layout is only supported by the Hdf5SingleFile DataStorage",
+                options.layout()
+            )));
+        }
+
+        self.filters = filters(options)?;
+        self.file_naming = options.file_naming().clone();
+        self.atomic_steps = options.atomic_steps();
+        Ok(())
+    }
+
+    fn set_path_policy(&mut self, path_policy: PathPolicy) {
+        self.path_policy = path_policy;
+    }
+
+    fn heavy_data_path(&self) -> Option<&Path> {
+        Some(&self.h5_files_dir)
+    }
+
+    fn index_precision(&self) -> IndexPrecision {
+        self.index_precision
+    }
+
+    fn set_index_precision(&mut self, index_precision: IndexPrecision) {
+        self.index_precision = index_precision;
+    }
+
     fn write_mesh(
         &mut self,
         points: &[f64],
         cells: &[u64],
-    ) -> IoResult<(DataContent, DataContent)> {
-        let file_name = self.h5_files_dir.join(format!("{MESH}.h5"));
-        let h5_file = H5File::create(&file_name).map_err(IoError::other)?;
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        self.write_mesh_file(&self.file_naming.mesh_file(None), points, cells)
+    }
 
-        let (data_name_points, data_name_cells) = write_mesh(&h5_file, points, cells)?;
+    fn write_named_mesh(
+        &mut self,
+        name: &str,
+        points: &[f64],
+        cells: &[u64],
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        self.write_mesh_file(&self.file_naming.mesh_file(Some(name)), points, cells)
+    }
 
-        let rel_file_name = parent_and_filename(&file_name)
-            .ok_or_else(|| IoError::other("Could not get parent and file name"))?;
+    fn write_mesh_soa(
+        &mut self,
+        name: Option<&str>,
+        coordinates: [&[f64]; 3],
+        cells: &[u64],
+    ) -> XdmfResult<([DataContent; 3], DataContent)> {
+        let (file_name, reference_path) = self.file_paths(&self.file_naming.mesh_file(name))?;
+        let h5_file = H5File::create(&file_name)?;
+
+        let (data_names_points, data_name_cells) = write_mesh_soa(
+            &h5_file,
+            coordinates,
+            cells,
+            self.index_precision,
+            &self.filters,
+        )?;
 
         Ok((
-            full_path(&rel_file_name, &data_name_points).into(),
-            full_path(&rel_file_name, &data_name_cells).into(),
+            data_names_points.map(|data_name| full_path(&reference_path, &data_name).into()),
+            full_path(&reference_path, &data_name_cells).into(),
         ))
     }
 
+    fn write_set(&mut self, name: &str, indices: &[u64]) -> XdmfResult<DataContent> {
+        let (file_name, reference_path) = self.file_paths(&self.file_naming.set_file(name))?;
+        let h5_file = H5File::create(&file_name)?;
+
+        let data_name = write_indices(
+            &h5_file,
+            INDICES,
+            indices,
+            self.index_precision,
+            &self.filters,
+        )?;
+
+        Ok(full_path(&reference_path, &data_name).into())
+    }
+
     fn write_data(
         &mut self,
         name: &str,
         center: attribute::Center,
         data: &Values,
-    ) -> IoResult<DataContent> {
-        // also double check that the name does not already exist
-
-        let data_file = self
-            .h5_data_file
-            .as_ref()
-            .ok_or_else(|| IoError::other("Writing data was not initialized"))?;
-
-        let group_name = attribute::center_to_data_tag(center);
+    ) -> XdmfResult<DataContent> {
+        self.write_shaped_data(name, center, data, &Dimensions(vec![data.len()]))
+    }
 
-        // Create the group if it does not exist
-        if !data_file.link_exists(group_name) {
-            data_file.create_group(group_name).map_err(IoError::other)?;
-        }
+    fn write_shaped_data(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        data: &Values,
+        dimensions: &Dimensions,
+    ) -> XdmfResult<DataContent> {
+        let (data_group, reference_path) = self.data_group(center)?;
+        let data_path = write_values(&data_group, name, data, &dimensions.0, &self.filters)?;
 
-        let data_path = write_values(
-            &data_file.group(group_name).map_err(IoError::other)?,
-            name,
-            data,
-        )?;
+        Ok(full_path(&reference_path, &data_path).into())
+    }
 
-        let rel_file_name = parent_and_filename(data_file.filename())
-            .ok_or_else(|| IoError::other("Could not get parent and file name"))?;
+    fn write_data_streamed(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        values: &mut dyn Iterator<Item = f64>,
+        len: usize,
+    ) -> XdmfResult<DataContent> {
+        let (data_group, reference_path) = self.data_group(center)?;
+        let data_path = write_values_streamed(&data_group, name, values, len, &self.filters)?;
 
-        Ok(full_path(&rel_file_name, &data_path).into())
+        Ok(full_path(&reference_path, &data_path).into())
     }
 
-    fn write_data_initialize(&mut self, time: &str) -> IoResult<()> {
+    fn write_data_initialize(&mut self, time: &str) -> XdmfResult<()> {
         if self.h5_data_file.is_some() {
-            return Err(IoError::other("Writing data was already initialized"));
+            return Err(XdmfError::Validation(
+                "Writing data was already initialized".into(),
+            ));
         }
 
-        let file_name = self.h5_files_dir.join(format!("data_t_{time}.h5"));
-        self.h5_data_file = Some(H5File::create(&file_name).map_err(IoError::other)?);
+        // data can be added to a time step that was written before, in which case its file is reopened
+        let num_steps = self.step_indices.len();
+        let (step_index, is_new_step) = match self.step_indices.get(time) {
+            Some(&step_index) => (step_index, false),
+            None => (num_steps, true),
+        };
+
+        let (file_name, reference_path) =
+            self.file_paths(&self.file_naming.data_file(time, step_index))?;
+
+        // with atomic steps the file is written under a temporary name, and renamed when the time step is complete
+        let write_file_name = if self.atomic_steps {
+            let temp_file_name = temp_file_name(&file_name);
+            self.pending_rename = Some((temp_file_name.clone(), file_name.clone()));
+            temp_file_name
+        } else {
+            file_name.clone()
+        };
+
+        let h5_data_file = if is_new_step {
+            if let Some(step_dir) = file_name.parent() {
+                crate::mpi_safe_create_dir_all(step_dir)?;
+            }
+            H5File::create(&write_file_name)?
+        } else {
+            if self.atomic_steps {
+                std::fs::copy(&file_name, &write_file_name)?;
+            }
+            H5File::open_rw(&write_file_name)?
+        };
+
+        self.step_indices.insert(time.to_string(), step_index);
+        self.h5_data_file = Some((h5_data_file, reference_path));
 
         Ok(())
     }
 
-    fn write_data_finalize(&mut self) -> IoResult<()> {
+    fn write_data_finalize(&mut self) -> XdmfResult<()> {
         if self.h5_data_file.is_none() {
-            return Err(IoError::other("Writing data was not initialized"));
+            return Err(XdmfError::Validation(
+                "Writing data was not initialized".into(),
+            ));
         }
 
         // TODO check if this flushes the file etc
-        self.h5_data_file = None;
+        let h5_data_file = self.h5_data_file.take();
+
+        if let Some((temp_file_name, file_name)) = self.pending_rename.take() {
+            // the file must be complete and closed before it replaces the file of the time step
+            if let Some((h5_data_file, _)) = h5_data_file {
+                h5_data_file.flush()?;
+                h5_data_file.close()?;
+            }
+            std::fs::rename(temp_file_name, file_name)?;
+        }
+
+        Ok(())
+    }
+
+    fn remove_step(&mut self, time: &str) -> XdmfResult<()> {
+        let Some(&step_index) = self.step_indices.get(time) else {
+            return Ok(());
+        };
+
+        let data_file = self.file_naming.data_file(time, step_index);
+        let (file_name, _) = self.file_paths(&data_file)?;
+        std::fs::remove_file(&file_name)?;
+
+        // the directory of the time step is shared by the writers with different prefixes, hence it is only removed when empty
+        if data_file.parent() != Some(Path::new(""))
+            && let Some(step_dir) = file_name.parent()
+            && std::fs::read_dir(step_dir)?.next().is_none()
+        {
+            std::fs::remove_dir(step_dir)?;
+        }
+
+        Ok(())
+    }
+
+    fn close(&mut self) -> XdmfResult<()> {
+        // the file of a time step is still open if writing it was interrupted, e.g. by a panic
+        if let Some((h5_data_file, _)) = self.h5_data_file.take() {
+            h5_data_file.flush()?;
+        }
+
+        // an incomplete time step does not replace its file
+        if let Some((temp_file_name, _)) = self.pending_rename.take() {
+            std::fs::remove_file(temp_file_name)?;
+        }
         Ok(())
     }
 }
 
-fn write_mesh(group: &H5Group, points: &[f64], cells: &[u64]) -> IoResult<(String, String)> {
+// temporary name of a file while it is written, e.g. `data_t_0.h5.tmp`
+fn temp_file_name(file_name: &Path) -> PathBuf {
+    let mut temp_file_name = file_name.as_os_str().to_owned();
+    temp_file_name.push(".tmp");
+    temp_file_name.into()
+}
+
+fn write_mesh(
+    group: &H5Group,
+    points: &[f64],
+    cells: &[u64],
+    index_precision: IndexPrecision,
+    filters: &[Filter],
+) -> XdmfResult<(String, String)> {
     let dataset_points = group
         .new_dataset::<f64>()
+        .set_filters(filters)
         .shape(points.len())
-        .create(POINTS)
-        .map_err(IoError::other)?;
+        .create(POINTS)?;
+
+    dataset_points.write(points)?;
 
-    dataset_points.write(points).map_err(IoError::other)?;
+    let data_name_cells = write_indices(group, CELLS, cells, index_precision, filters)?;
 
-    let dataset_cells = group
-        .new_dataset::<u64>()
-        .shape(cells.len())
-        .create(CELLS)
-        .map_err(IoError::other)?;
+    Ok((dataset_points.name(), data_name_cells))
+}
+
+// write the coordinates as separate datasets per direction
+fn write_mesh_soa(
+    group: &H5Group,
+    coordinates: [&[f64]; 3],
+    cells: &[u64],
+    index_precision: IndexPrecision,
+    filters: &[Filter],
+) -> XdmfResult<([String; 3], String)> {
+    let mut data_names_points = [String::new(), String::new(), String::new()];
+
+    for ((values, dataset_name), data_name) in coordinates
+        .iter()
+        .zip(POINTS_XYZ)
+        .zip(&mut data_names_points)
+    {
+        let dataset = group
+            .new_dataset::<f64>()
+            .set_filters(filters)
+            .shape(values.len())
+            .create(dataset_name)?;
+
+        dataset.write(values)?;
+        *data_name = dataset.name();
+    }
+
+    let data_name_cells = write_indices(group, CELLS, cells, index_precision, filters)?;
+
+    Ok((data_names_points, data_name_cells))
+}
+
+// write the indices as u32 or u64, depending on the precision they resolve to
+fn write_indices(
+    group: &H5Group,
+    dataset_name: &str,
+    indices: &[u64],
+    index_precision: IndexPrecision,
+    filters: &[Filter],
+) -> XdmfResult<String> {
+    if index_precision.resolve(indices)? == 4 {
+        write_index_dataset(group, dataset_name, &indices_u32(indices), filters)
+    } else {
+        write_index_dataset(group, dataset_name, indices, filters)
+    }
+}
 
-    dataset_cells.write(cells).map_err(IoError::other)?;
+fn write_index_dataset<T: H5Type>(
+    group: &H5Group,
+    dataset_name: &str,
+    indices: &[T],
+    filters: &[Filter],
+) -> XdmfResult<String> {
+    let dataset = group
+        .new_dataset::<T>()
+        .set_filters(filters)
+        .shape(indices.len())
+        .create(dataset_name)?;
+
+    dataset.write(indices)?;
+
+    Ok(dataset.name())
+}
 
-    Ok((dataset_points.name(), dataset_cells.name()))
+// the indices were checked to fit into u32, hence the truncation is lossless
+fn indices_u32(indices: &[u64]) -> Vec<u32> {
+    indices.iter().map(|&index| index as u32).collect()
 }
 
-fn write_values(group: &H5Group, dataset_name: &str, vals: &Values) -> IoResult<String> {
+fn write_values(
+    group: &H5Group,
+    dataset_name: &str,
+    vals: &Values,
+    shape: &[usize],
+    filters: &[Filter],
+) -> XdmfResult<String> {
+    // the data of an overwritten time step replaces the existing dataset
+    // note that HDF5 does not reclaim the space of the unlinked dataset
+    if group.link_exists(dataset_name) {
+        group.unlink(dataset_name)?;
+    }
+
     let data_set = match vals {
         Values::F64(_) => group.new_dataset::<f64>(),
+        Values::F32(_) => group.new_dataset::<f32>(),
         Values::U64(_) => group.new_dataset::<u64>(),
+        Values::I64(_) => group.new_dataset::<i64>(),
+        Values::U8(_) => group.new_dataset::<u8>(),
     };
 
     let data_set = data_set
-        .shape(vals.dimensions(crate::DataAttribute::Scalar).0)
-        .create(dataset_name)
-        .map_err(IoError::other)?;
+        .set_filters(filters)
+        .shape(shape.to_vec())
+        .create(dataset_name)?;
 
     match vals {
-        Values::F64(v) => data_set.write(v).map_err(IoError::other)?,
-        Values::U64(v) => data_set.write(v).map_err(IoError::other)?,
+        Values::F64(v) => data_set.write_raw(&**v)?,
+        Values::F32(v) => data_set.write_raw(&**v)?,
+        Values::U64(v) => data_set.write_raw(&**v)?,
+        Values::I64(v) => data_set.write_raw(&**v)?,
+        Values::U8(v) => data_set.write_raw(&**v)?,
     };
 
     Ok(data_set.name())
 }
 
-fn parent_and_filename(path: impl AsRef<Path>) -> Option<PathBuf> {
-    let path = path.as_ref();
-    let parent = path.parent()?.file_name()?;
-    let file_name = path.file_name()?;
-    Some(Path::new(parent).join(file_name))
+// the values are written in chunks into the dataset, such that only one chunk is held in memory
+fn write_values_streamed(
+    group: &H5Group,
+    dataset_name: &str,
+    values: &mut dyn Iterator<Item = f64>,
+    len: usize,
+    filters: &[Filter],
+) -> XdmfResult<String> {
+    // the data of an overwritten time step or of a retried field replaces the existing dataset
+    if group.link_exists(dataset_name) {
+        group.unlink(dataset_name)?;
+    }
+
+    let data_set = group
+        .new_dataset::<f64>()
+        .set_filters(filters)
+        .shape(len)
+        .create(dataset_name)?;
+
+    write_chunks(&data_set, dataset_name, values, None, 0, len)?;
+
+    Ok(data_set.name())
+}
+
+// write exactly `len` values into the dataset (or the given row of it), starting at `offset`
+fn write_chunks(
+    data_set: &Dataset,
+    dataset_name: &str,
+    values: &mut dyn Iterator<Item = f64>,
+    row: Option<usize>,
+    offset: usize,
+    len: usize,
+) -> XdmfResult<()> {
+    let mut chunk = Vec::with_capacity(STREAM_CHUNK_SIZE.min(len));
+    let mut num_written = 0;
+
+    while num_written < len {
+        chunk.clear();
+        chunk.extend((&mut *values).take(STREAM_CHUNK_SIZE.min(len - num_written)));
+
+        if chunk.is_empty() {
+            break;
+        }
+
+        let start = offset + num_written;
+        let range = start..start + chunk.len();
+        match row {
+            Some(row) => data_set.write_slice(chunk.as_slice(), (row, range))?,
+            None => data_set.write_slice(chunk.as_slice(), range)?,
+        }
+
+        num_written += chunk.len();
+    }
+
+    check_stream_exhausted(dataset_name, values, num_written, len)
+}
+
+// length of the chunks of a row of a time-major dataset, a row of large data is split into multiple chunks
+// as HDF5 limits the size of a chunk to 4 GiB
+fn row_chunk_len<T>(len: usize) -> usize {
+    len.clamp(1, MAX_CHUNK_BYTES / size_of::<T>().max(1))
+}
+
+// filters that are applied to all datasets, the bytes are shuffled before they are compressed
+fn filters(options: &Hdf5Options) -> XdmfResult<Vec<Filter>> {
+    let mut filters = Vec::new();
+
+    if options.shuffle() {
+        filters.push(Filter::shuffle());
+    }
+
+    if let Some(level) = options.compression_level() {
+        if !hdf5::filters::deflate_available() {
+            return Err(XdmfError::Unsupported(
+                "Compression requires an HDF5 library with deflate (zlib) support".into(),
+            ));
+        }
+        filters.push(Filter::deflate(level));
+    }
+
+    Ok(filters)
 }
 
 // Path that is written to the xdmf file, specifying where the data is stored in the h5 file
 // it consists of the path to the h5 file and the location within the file, which are separated by a colon
 // e.g. /path/to/file.h5:mesh/points
-fn full_path(path: &Path, data_name: &str) -> String {
-    format!(
-        "{}{}",
-        path.to_string_lossy(),
-        data_name.replacen('/', ":", 1)
-    )
+fn full_path(path: &str, data_name: &str) -> String {
+    format!("{path}{}", data_name.replacen('/', ":", 1))
 }
 
 #[cfg(test)]
@@ -307,25 +1210,26 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn row_chunk_len_limited() {
+        assert_eq!(row_chunk_len::<f64>(0), 1);
+        assert_eq!(row_chunk_len::<f64>(1000), 1000);
+
+        // a row with more than 2^31 values is split into chunks of at most 1 GiB
+        let len = 3 << 30;
+        assert_eq!(row_chunk_len::<f64>(len), 1 << 27);
+        assert_eq!(row_chunk_len::<u8>(len), 1 << 30);
+    }
+
     #[test]
     fn full_path_works() {
-        let file_name = Path::new("some/random/path/test.h5");
+        let file_name = "some/random/path/test.h5";
         let data_name = "/test_group/test_data";
 
         assert_eq!(
             full_path(file_name, data_name),
-            file_name.to_string_lossy().to_string() + ":test_group/test_data"
-        );
-    }
-
-    #[test]
-    fn parent_and_filename_works() {
-        assert_eq!(
-            parent_and_filename(Path::new("some/random/path/test.h5")).unwrap(),
-            PathBuf::from("path/test.h5")
+            "some/random/path/test.h5:test_group/test_data"
         );
-
-        assert!(parent_and_filename(Path::new("test.h5")).is_none(),);
     }
 
     #[test]
@@ -341,7 +1245,7 @@ mod tests {
         let points = vec![0.0, 1.0, 2.0];
         let cells = vec![0, 1, 2];
 
-        let (data_name_points, data_name_cells) = write_mesh(&group, &points, &cells).unwrap();
+        let (data_name_points, data_name_cells) = write_mesh(&group, &points, &cells, &[]).unwrap();
         assert_eq!(data_name_points, "/test_group/points");
         assert_eq!(data_name_cells, "/test_group/cells");
 
@@ -381,8 +1285,22 @@ mod tests {
         let vec_f64 = vec![1., 2., 3., 4., 5., 6.];
         let vec_u64 = vec![10_u64, 20, 30, 40, 50, 60];
 
-        let f64_path = write_values(&group, "test_f64", &vec_f64.clone().into()).unwrap();
-        let u64_path = write_values(&group, "test_u64", &vec_u64.clone().into()).unwrap();
+        let f64_path = write_values(
+            &group,
+            "test_f64",
+            &vec_f64.clone().into(),
+            &[vec_f64.len()],
+            &[],
+        )
+        .unwrap();
+        let u64_path = write_values(
+            &group,
+            "test_u64",
+            &vec_u64.clone().into(),
+            &[vec_u64.len()],
+            &[],
+        )
+        .unwrap();
 
         assert_eq!(f64_path, "/test_group/test_f64");
         assert_eq!(u64_path, "/test_group/test_u64");
@@ -408,6 +1326,36 @@ mod tests {
 
         assert_approx_eq!(&[f64], &vec_f64, &data_f64);
         assert_eq!(&vec_u64, &data_u64);
+
+        // the dataset has the shape of the data, e.g. of a field with multiple components per point
+        write_values(
+            &group,
+            "test_shaped",
+            &vec_f64.clone().into(),
+            &[1, 2, 3],
+            &[],
+        )
+        .unwrap();
+        let dataset = group.dataset("test_shaped").unwrap();
+        assert_eq!(dataset.shape(), vec![1, 2, 3]);
+        assert_eq!(dataset.read_raw::<f64>().unwrap(), vec_f64);
+    }
+
+    #[test]
+    fn write_values_streamed_replaces_dataset() {
+        let tmp_dir = temp_dir::TempDir::new().unwrap();
+        let h5_file = H5File::create(tmp_dir.path().join("test.h5")).unwrap();
+        let group = h5_file.create_group("test_group").unwrap();
+
+        // a retried field replaces the dataset of the failed attempt
+        for values in [[f64::NAN, 2.0], [3.0, 4.0]] {
+            let path =
+                write_values_streamed(&group, "streamed", &mut values.into_iter(), 2, &[]).unwrap();
+            assert_eq!(path, "/test_group/streamed");
+        }
+
+        let dataset = group.dataset("streamed").unwrap();
+        assert_eq!(dataset.read_raw::<f64>().unwrap(), vec![3.0, 4.0]);
     }
 
     #[test]
@@ -427,7 +1375,7 @@ mod tests {
         let res_write = writer.write_data(
             "test_data",
             attribute::Center::Node,
-            &Values::F64(vec![1.0, 2.0]),
+            &Values::from(vec![1.0, 2.0]),
         );
         assert_eq!(
             res_write.unwrap_err().to_string(),
@@ -451,7 +1399,8 @@ mod tests {
     fn mutliple_files_hdf5_writer_write_data_init_fin() {
         let tmp_dir = temp_dir::TempDir::new().unwrap();
         let file_name = tmp_dir.path().join("sub/folder/test.xdmf");
-        let mut writer = MultipleFilesHdf5Writer::new(&file_name).unwrap();
+        let mut writer =
+            MultipleFilesHdf5Writer::new(&file_name, &Hdf5FileNaming::default()).unwrap();
         assert!(writer.h5_data_file.is_none());
 
         let res_fin = writer.write_data_finalize();
@@ -463,7 +1412,7 @@ mod tests {
         let res_write = writer.write_data(
             "test_data",
             attribute::Center::Node,
-            &Values::F64(vec![1.0, 2.0]),
+            &Values::from(vec![1.0, 2.0]),
         );
         assert_eq!(
             res_write.unwrap_err().to_string(),
@@ -475,7 +1424,7 @@ mod tests {
         assert!(writer.h5_data_file.is_some());
 
         assert_eq!(
-            writer.h5_data_file.as_ref().unwrap().filename(),
+            writer.h5_data_file.as_ref().unwrap().0.filename(),
             exp_file_name.to_string_lossy()
         );
         assert!(exp_file_name.exists());
@@ -505,7 +1454,7 @@ mod tests {
     fn mutliple_files_hdf5_writer_new() {
         let tmp_dir = temp_dir::TempDir::new().unwrap();
         let file_name = tmp_dir.path().join("sub/folder/test.xdmf");
-        let writer = MultipleFilesHdf5Writer::new(&file_name).unwrap();
+        let writer = MultipleFilesHdf5Writer::new(&file_name, &Hdf5FileNaming::default()).unwrap();
         let exp_dir_name = file_name.with_extension("h5");
         assert_eq!(writer.h5_files_dir, exp_dir_name);
         assert!(writer.h5_files_dir.exists());
@@ -554,7 +1503,8 @@ mod tests {
     fn mutliple_files_hdf5_writer_write_mesh() {
         let tmp_dir = temp_dir::TempDir::new().unwrap();
         let file_name = tmp_dir.path().join("sub/folder/test.xdmf");
-        let mut writer = MultipleFilesHdf5Writer::new(file_name).unwrap();
+        let mut writer =
+            MultipleFilesHdf5Writer::new(file_name, &Hdf5FileNaming::default()).unwrap();
         let mesh_file = writer.h5_files_dir.join("mesh.h5");
         assert!(!mesh_file.exists());
 
@@ -591,7 +1541,7 @@ mod tests {
             .write_data(
                 "dummy_point_data",
                 attribute::Center::Node,
-                &Values::F64(data_points.clone()),
+                &Values::from(data_points.as_slice()),
             )
             .unwrap();
 
@@ -601,7 +1551,7 @@ mod tests {
             .write_data(
                 "some_cell_data",
                 attribute::Center::Cell,
-                &Values::F64(data_cells.clone()),
+                &Values::from(data_cells.as_slice()),
             )
             .unwrap();
 
@@ -640,7 +1590,8 @@ mod tests {
     fn mutliple_files_hdf5_writer_write_data() {
         let tmp_dir = temp_dir::TempDir::new().unwrap();
         let file_name = tmp_dir.path().join("sub/folder/test.xdmf");
-        let mut writer = MultipleFilesHdf5Writer::new(file_name).unwrap();
+        let mut writer =
+            MultipleFilesHdf5Writer::new(file_name, &Hdf5FileNaming::default()).unwrap();
         let write_time = "12.258";
         let data_file = writer.h5_files_dir.join(format!("data_t_{write_time}.h5"));
         assert!(!data_file.exists());
@@ -654,7 +1605,7 @@ mod tests {
             .write_data(
                 "dummy_point_data",
                 attribute::Center::Node,
-                &Values::F64(data_points.clone()),
+                &Values::from(data_points.as_slice()),
             )
             .unwrap();
 
@@ -664,7 +1615,7 @@ mod tests {
             .write_data(
                 "some_cell_data",
                 attribute::Center::Cell,
-                &Values::F64(data_cells.clone()),
+                &Values::from(data_cells.as_slice()),
             )
             .unwrap();
 
@@ -698,4 +1649,142 @@ mod tests {
         assert_approx_eq!(&[f64], &data_points, &points_data);
         assert_approx_eq!(&[f64], &data_cells, &cells_data);
     }
+
+    #[test]
+    fn mutliple_files_hdf5_writer_file_naming() {
+        let tmp_dir = temp_dir::TempDir::new().unwrap();
+        let file_name = tmp_dir.path().join("test.xdmf");
+        let file_naming = Hdf5FileNaming::default()
+            .with_prefix("run1_")
+            .with_step_index(3)
+            .with_step_directories();
+        let mut writer = MultipleFilesHdf5Writer::new(&file_name, &file_naming).unwrap();
+
+        let (points_path, _) = writer.write_mesh(&[0.0; 3], &[0]).unwrap();
+        assert_eq!(points_path, "test.h5/run1_mesh.h5:points".into());
+
+        let values = Values::from(vec![1.0]);
+        for (time, exp_path) in [
+            ("0.5", "test.h5/000/run1_data.h5:point_data/pressure"),
+            ("1.0", "test.h5/001/run1_data.h5:point_data/pressure"),
+        ] {
+            writer.write_data_initialize(time).unwrap();
+            let data_path = writer
+                .write_data("pressure", attribute::Center::Node, &values)
+                .unwrap();
+            writer.write_data_finalize().unwrap();
+
+            assert_eq!(data_path, exp_path.into());
+        }
+        assert!(writer.h5_files_dir.join("001/run1_data.h5").exists());
+
+        // the file of a time step that was written before is reopened
+        writer.write_data_initialize("0.5").unwrap();
+        let data_path = writer
+            .write_data("temperature", attribute::Center::Node, &values)
+            .unwrap();
+        writer.write_data_finalize().unwrap();
+        assert_eq!(
+            data_path,
+            "test.h5/000/run1_data.h5:point_data/temperature".into()
+        );
+
+        // the directory of a removed time step is removed along with its file
+        writer.remove_step("0.5").unwrap();
+        assert!(!writer.h5_files_dir.join("000").exists());
+        assert!(writer.h5_files_dir.join("001/run1_data.h5").exists());
+
+        let file_naming = Hdf5FileNaming::default().with_prefix("run2_");
+        assert_eq!(
+            file_naming.data_file("0.5", 0),
+            PathBuf::from("run2_data_t_0.5.h5")
+        );
+        assert_eq!(
+            file_naming.set_file("inlet"),
+            PathBuf::from("run2_sets_inlet.h5")
+        );
+        assert_eq!(
+            Hdf5FileNaming::default().data_file("0.5", 0),
+            PathBuf::from("data_t_0.5.h5")
+        );
+    }
+
+    #[test]
+    fn single_file_hdf5_writer_time_major_layout() {
+        let tmp_dir = temp_dir::TempDir::new().unwrap();
+        let file_name = tmp_dir.path().join("test.xdmf");
+        let mut writer = SingleFileHdf5Writer::new(&file_name).unwrap();
+        writer
+            .set_hdf5_options(&Hdf5Options::default().with_layout(Hdf5Layout::TimeMajorDatasets))
+            .unwrap();
+
+        // the last time step is overwritten, which replaces its row
+        for (time, values) in [
+            ("0.0", vec![1.0, 2.0]),
+            ("0.5", vec![3.0, 4.0]),
+            ("0.5", vec![5.0, 6.0]),
+        ] {
+            writer.write_data_initialize(time).unwrap();
+            let data_path = writer
+                .write_data("pressure", attribute::Center::Node, &values.into())
+                .unwrap();
+            writer.write_data_finalize().unwrap();
+
+            assert_eq!(data_path, "test.h5:data/point_data/pressure".into());
+        }
+
+        // the DataItem of the last written time step selects its row
+        let data_item = writer.wrap_data_item(DataItem {
+            dimensions: Some(Dimensions(vec![2])),
+            data: "test.h5:data/point_data/pressure".into(),
+            ..Default::default()
+        });
+        assert_eq!(data_item.data_items[0].data, "1 0 1 1 1 2".into());
+        assert_eq!(
+            data_item.data_items[1].dimensions,
+            Some(Dimensions(vec![2, 2]))
+        );
+
+        // the values of the streamed data are written into the row as well
+        writer.write_data_initialize("1.0").unwrap();
+        writer
+            .write_data_streamed(
+                "pressure",
+                attribute::Center::Node,
+                &mut [7.0, 8.0].into_iter(),
+                2,
+            )
+            .unwrap();
+
+        // the size of a field can not change between the time steps
+        assert_eq!(
+            writer
+                .write_data("pressure", attribute::Center::Node, &vec![1.0].into())
+                .unwrap_err()
+                .to_string(),
+            "Data 'pressure' must have the same size at all time steps with the TimeMajorDatasets Hdf5Layout, but has 1 values instead of 2"
+        );
+        writer.write_data_finalize().unwrap();
+
+        let dataset = writer.h5_file.dataset("data/point_data/pressure").unwrap();
+        assert_eq!(dataset.shape(), vec![3, 2]);
+        assert_approx_eq!(
+            &[f64],
+            &dataset.read_raw::<f64>().unwrap(),
+            &[1.0, 2.0, 5.0, 6.0, 7.0, 8.0]
+        );
+
+        // the layout is only supported by the single file
+        let mut writer =
+            MultipleFilesHdf5Writer::new(&file_name, &Hdf5FileNaming::default()).unwrap();
+        assert_eq!(
+            writer
+                .set_hdf5_options(
+                    &Hdf5Options::default().with_layout(Hdf5Layout::TimeMajorDatasets)
+                )
+                .unwrap_err()
+                .to_string(),
+            "TimeMajorDatasets Hdf5Layout is only supported by the Hdf5SingleFile DataStorage"
+        );
+    }
 }
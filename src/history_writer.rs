@@ -0,0 +1,216 @@
+//! This module contains the writer for the time histories at probe locations, e.g. point monitors of a simulation.
+
+use std::{
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    DataAttribute, DataMap, DataStorage, StepKey, TimeSeriesDataWriter, TimeSeriesWriter,
+    XdmfError, XdmfResult, number_format::FormatNumber, time_series_writer::is_valid_data_name,
+    xdmf_elements::Information,
+};
+
+/// Name of the `Information` holding the names of the probes, in the order of their points
+const PROBES_INFORMATION_NAME: &str = "probes";
+
+/// Name of the `Information` holding the path of the CSV file with the histories, relative to the XDMF file
+const CSV_INFORMATION_NAME: &str = "history_csv";
+
+/// Writer for the time histories of scalar values at named probes, e.g. the pressure at a monitor point or the forces on a body.
+///
+/// The probes are written as points (with a polyvertex topology) and the values of each step as data at these points,
+/// such that the histories can be plotted in Paraview, e.g. with "Plot Data Over Time".
+/// The names of the probes are written as `Information` named `probes`, in the order of the points.
+/// Optionally the histories are also appended to a CSV file, see [`HistoryWriter::with_csv`].
+/// ```rust
+/// use xdmf::HistoryWriter;
+///
+/// let mut history_writer = HistoryWriter::new(
+///     "xdmf_history",
+///     xdmf::DataStorage::AsciiInline,
+///     &[("inlet", [0.0, 0.0, 0.0]), ("outlet", [1.0, 0.0, 0.0])],
+/// )
+/// .expect("failed to create history writer")
+/// .with_csv()
+/// .expect("failed to create CSV file");
+///
+/// for i in 0..3 {
+///     // one value per probe
+///     history_writer
+///         .write_step(&(f64::from(i) * 0.1), &[("pressure", &[1.0, 0.5])])
+///         .expect("failed to write step");
+/// }
+///
+/// history_writer
+///     .close()
+///     .expect("failed to close history writer");
+/// ```
+pub struct HistoryWriter {
+    writer: TimeSeriesDataWriter,
+    csv_file_name: PathBuf,
+    csv_file: Option<BufWriter<Box<dyn Write + Send>>>,
+    probe_names: Vec<String>,
+    // names of the fields, which are the same at all steps
+    field_names: Option<Vec<String>>,
+    num_steps: usize,
+}
+
+impl HistoryWriter {
+    /// Create a new `HistoryWriter` for the given probes, each consisting of a name and its position.
+    ///
+    /// The names must be unique and contain only alphanumeric characters, underscores or dashes.
+    pub fn new(
+        file_name: impl AsRef<Path>,
+        data_storage: DataStorage,
+        probes: &[(&str, [f64; 3])],
+    ) -> XdmfResult<Self> {
+        Self::new_with_writer(TimeSeriesWriter::new(&file_name, data_storage)?, probes)
+    }
+
+    /// Create a new `HistoryWriter` for the given probes, using the given `TimeSeriesWriter`, e.g. with a custom [`FlushPolicy`](crate::FlushPolicy).
+    ///
+    /// See [`HistoryWriter::new`] for details.
+    pub fn new_with_writer(
+        writer: TimeSeriesWriter,
+        probes: &[(&str, [f64; 3])],
+    ) -> XdmfResult<Self> {
+        if probes.is_empty() {
+            return Err(XdmfError::Validation(
+                "At least one probe is required".into(),
+            ));
+        }
+
+        let mut probe_names = Vec::with_capacity(probes.len());
+        for (name, _) in probes {
+            if !is_valid_data_name(name) {
+                return Err(XdmfError::Validation(format!(
+                    "Probe name '{name}' is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
+                )));
+            }
+            if probe_names.iter().any(|probe_name| probe_name == name) {
+                return Err(XdmfError::Validation(format!(
+                    "Probe name '{name}' is not unique"
+                )));
+            }
+            probe_names.push(name.to_string());
+        }
+
+        let points: Vec<f64> = probes.iter().flat_map(|(_, point)| *point).collect();
+
+        let writer = writer
+            .with_information(PROBES_INFORMATION_NAME, probe_names.join(" "))
+            .write_points(&points)?;
+        let csv_file_name = writer.xdmf_file_name().with_extension("csv");
+
+        Ok(Self {
+            writer,
+            csv_file_name,
+            csv_file: None,
+            probe_names,
+            field_names: None,
+            num_steps: 0,
+        })
+    }
+
+    /// Additionally append the histories to a CSV file next to the XDMF file, e.g. for plotting them with other tools.
+    ///
+    /// The file has one row per step, with the time in the first column, followed by one column per field and probe named `<probe>.<field>`.
+    /// Its path relative to the XDMF file is written as `Information` named `history_csv`. Must be enabled before the first step is written.
+    pub fn with_csv(mut self) -> XdmfResult<Self> {
+        if self.num_steps > 0 {
+            return Err(XdmfError::Validation(
+                "The CSV file must be enabled before the first step is written".into(),
+            ));
+        }
+
+        let csv_path = self
+            .csv_file_name
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        self.csv_file = Some(BufWriter::new(
+            self.writer.file_system().create_file(&self.csv_file_name)?,
+        ));
+        self.writer
+            .add_information(Information::new(CSV_INFORMATION_NAME, csv_path));
+
+        Ok(self)
+    }
+
+    /// Write the values of the probes at a step, given as the name of each field with one value per probe.
+    ///
+    /// The step is identified by a [`StepKey`], e.g. the time. The same fields must be written at all steps.
+    pub fn write_step(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        fields: &[(&str, &[f64])],
+    ) -> XdmfResult<()> {
+        let (label, time) = self.writer.resolve_step(step, self.num_steps)?;
+
+        let field_names: Vec<String> = fields.iter().map(|(name, _)| name.to_string()).collect();
+        match &self.field_names {
+            Some(expected) if *expected != field_names => {
+                return Err(XdmfError::Validation(format!(
+                    "Fields of step '{label}' must be the same as at the first step, expected {expected:?}, got {field_names:?}"
+                )));
+            }
+            _ => {}
+        }
+
+        let point_data: DataMap = fields
+            .iter()
+            .map(|(name, values)| {
+                (
+                    name.to_string(),
+                    (DataAttribute::Scalar, values.to_vec().into()),
+                )
+            })
+            .collect();
+
+        if point_data.len() != fields.len() {
+            return Err(XdmfError::Validation(format!(
+                "Fields of step '{label}' must have unique names, got {field_names:?}"
+            )));
+        }
+
+        // the values of the probes are written at their points
+        self.writer.write_data(step, Some(&point_data), None)?;
+
+        if let Some(csv_file) = &mut self.csv_file {
+            if self.field_names.is_none() {
+                let header: Vec<String> = field_names
+                    .iter()
+                    .flat_map(|field| {
+                        self.probe_names
+                            .iter()
+                            .map(move |probe| format!("{probe}.{field}"))
+                    })
+                    .collect();
+                writeln!(csv_file, "time,{}", header.join(","))?;
+            }
+
+            let row: Vec<String> = fields
+                .iter()
+                .flat_map(|(_, values)| values.iter().map(FormatNumber::format_number))
+                .collect();
+            writeln!(csv_file, "{time},{}", row.join(","))?;
+            csv_file.flush()?;
+        }
+
+        self.field_names = Some(field_names);
+        self.num_steps += 1;
+
+        Ok(())
+    }
+
+    /// Close the writer, which writes the XDMF file a last time and finishes writing the CSV file.
+    pub fn close(mut self) -> XdmfResult<()> {
+        if let Some(csv_file) = &mut self.csv_file {
+            csv_file.flush()?;
+        }
+
+        self.writer.close()
+    }
+}
@@ -6,37 +6,104 @@ use std::{
     io::{Error as IoError, Result as IoResult},
     path::Path,
     str::FromStr,
+    sync::Arc,
 };
 
 use serde::{Deserialize, Serialize};
 use xdmf_elements::{
     attribute,
-    data_item::{DataContent, Format},
+    data_item::{DataContent, DataItem, Encoding, Format},
+    dimensions::Dimensions,
 };
 
 mod ascii_writer;
+#[cfg(feature = "async")]
+mod async_time_series_writer;
+mod averaging;
+mod base64_writer;
+#[cfg(feature = "hdf5")]
+pub mod checkpoint;
+pub mod compact;
+mod data_item_registry;
+pub mod dictionary;
+mod domain_builder;
+mod error;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod file_system;
 #[cfg(feature = "hdf5")]
 mod hdf5_writer;
+mod history_writer;
+pub mod merge;
+mod mesh_source;
+mod number_format;
+mod output_manager;
 
+mod parallel_time_series_writer;
+mod progress;
+#[cfg(feature = "python")]
+mod python;
+mod quality;
+mod rebase;
+mod recovery;
+pub mod reorder;
+mod step_key;
+mod subdivision;
 mod time_series_writer;
+mod time_series_writer_builder;
+mod transform;
+pub mod validate;
 mod values;
+#[cfg(feature = "vtk-interop")]
+mod vtk_interop;
 pub mod xdmf_elements;
 
 // Re-export types used in the public API
-pub use time_series_writer::{TimeSeriesDataWriter, TimeSeriesWriter};
-pub use values::Values;
-pub use xdmf_elements::CellType;
+#[cfg(feature = "async")]
+pub use async_time_series_writer::{AsyncTimeSeriesDataWriter, AsyncTimeSeriesWriter};
+pub use domain_builder::{DomainBuilder, GridBuilder};
+pub use error::{XdmfError, XdmfResult};
+pub use file_system::{FileSystem, LocalFileSystem, MemoryFileSystem};
+pub use history_writer::HistoryWriter;
+pub use mesh_source::{FaceVertexMesh, MeshSource};
+pub use output_manager::{OutputConfig, OutputManager, SimulationState};
+pub use parallel_time_series_writer::{ParallelTimeSeriesDataWriter, ParallelTimeSeriesWriter};
+pub use progress::{WriteEvent, WriteOperation};
+pub use quality::QualityMetric;
+pub use rebase::{rebase_references, rebase_xdmf_references};
+pub use recovery::{prune_dangling_steps, prune_xdmf_steps};
+pub use step_key::{FormattedTime, StepKey, StepLabel, TimeFormat, WithCycle};
+pub use time_series_writer::{
+    MultiBodyDataWriter, MultiMeshDataWriter, TimeSeriesDataWriter, TimeSeriesWriter,
+};
+pub use time_series_writer_builder::TimeSeriesWriterBuilder;
+pub use transform::Transform;
+pub use values::{GHOST_TYPE_NAME, Values};
+#[cfg(feature = "vtk-interop")]
+pub use vtk_interop::{export_vtu, mesh_from_vtk};
+pub use xdmf_elements::{CellType, XmlHeader};
 
 /// Map for data, relates name to attribtue and values
-pub type DataMap = BTreeMap<String, (DataAttribute, Values)>;
+///
+/// The values can borrow the data of the caller, see [`Values`].
+pub type DataMap<'a> = BTreeMap<String, (DataAttribute, Values<'a>)>;
+
+/// Part of a mesh, defined by its path in the hierarchy (e.g. "assembly/part/body"), points and cells
+pub type MeshPart<'a> = (&'a str, &'a [f64], (&'a [u64], &'a [CellType]));
 
 /// Type of storage used for the heavy data (e.g. ASCII or HDF5)
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum DataStorage {
     /// store the data in ASCII format, each set of data is stored in a separate file.
     Ascii,
+    /// same as [`DataStorage::Ascii`], but the files are compressed with gzip (`.txt.gz`), which reduces their size considerably.
+    /// Note that the reader must support compressed included files, e.g. if it reads them with libxml2 built with zlib.
+    AsciiGz,
     /// store the data in ASCII format, but inline in the XDMF file. This is only recommended for small datasets.
     AsciiInline,
+    /// store the data as base64 encoded binary, inline in the XDMF file. This results in a single self-contained file,
+    /// which is more compact than [`DataStorage::AsciiInline`]. Note that not all readers support encoded data.
+    Base64Inline,
     /// store the data in HDF5 format, all data in a single HDF5 file.
     Hdf5SingleFile,
     /// store the data in HDF5 format, one file per time step.
@@ -49,56 +116,599 @@ impl FromStr for DataStorage {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "ascii" => Ok(Self::Ascii),
+            "asciigz" | "ascii_gz" | "ascii-gz" => Ok(Self::AsciiGz),
             "asciiinline" | "ascii_inline" | "ascii-inline" => Ok(Self::AsciiInline),
+            "base64inline" | "base64_inline" | "base64-inline" => Ok(Self::Base64Inline),
             "hdf5singlefile" | "hdf5_single_file" | "hdf5-single-file" => Ok(Self::Hdf5SingleFile),
             "hdf5multiplefiles" | "hdf5_multiple_files" | "hdf5-multiple-files" => {
                 Ok(Self::Hdf5MultipleFiles)
             }
             _ => Err(format!(
-                "Invalid DataStorage variant: '{s}'. Valid options are: 'Ascii', 'AsciiInline', 'Hdf5SingleFile', 'Hdf5MultipleFiles'"
+                "Invalid DataStorage variant: '{s}'. Valid options are: 'Ascii', 'AsciiGz', 'AsciiInline', 'Base64Inline', 'Hdf5SingleFile', 'Hdf5MultipleFiles'"
             )),
         }
     }
 }
 
-/// this trait defines the interface used to write the heavy data
-pub(crate) trait DataWriter {
+impl DataStorage {
+    /// Whether the data storage is available, i.e. whether the required features are enabled.
+    pub fn is_available(self) -> bool {
+        match self {
+            Self::Ascii | Self::AsciiGz | Self::AsciiInline | Self::Base64Inline => true,
+            Self::Hdf5SingleFile | Self::Hdf5MultipleFiles => cfg!(feature = "hdf5"),
+        }
+    }
+}
+
+/// Policy for how often the XDMF file is rewritten while writing time steps.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FlushPolicy {
+    /// Rewrite the XDMF file after every time step, such that it is always up to date (e.g. after a crash)
+    #[default]
+    EveryStep,
+    /// Rewrite the XDMF file after every N time steps
+    EveryN(u32),
+    /// Only write the XDMF file when calling `finalize` or `flush_xml`
+    OnFinalize,
+}
+
+impl FlushPolicy {
+    // whether the XDMF file should be written, given the number of writes since it was last written
+    pub(crate) fn is_due(self, pending_writes: u32) -> bool {
+        match self {
+            Self::EveryStep => true,
+            Self::EveryN(n) => pending_writes >= n,
+            Self::OnFinalize => false,
+        }
+    }
+}
+
+/// Policy for which time steps are kept, e.g. to limit the disk usage of long-running simulations.
+///
+/// Pruned time steps are removed from the XDMF file, and their heavy data is deleted for the data storages
+/// that write separate files per time step ([`DataStorage::Ascii`], [`DataStorage::AsciiGz`] and [`DataStorage::Hdf5MultipleFiles`]).
+/// The time steps are counted in the order of writing, the most recently written time step is always kept.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RetentionPolicy {
+    /// Keep all time steps
+    #[default]
+    KeepAll,
+    /// Keep only the last N time steps
+    KeepLast(usize),
+    /// Keep every N-th time step (starting with the first one), and the most recently written time step
+    KeepEvery(usize),
+}
+
+impl RetentionPolicy {
+    // whether the time step with the given index is kept, given the index of the most recently written time step
+    pub(crate) fn is_kept(self, step_index: usize, latest_index: usize) -> bool {
+        match self {
+            Self::KeepAll => true,
+            Self::KeepLast(n) => latest_index - step_index < n.max(1),
+            Self::KeepEvery(n) => step_index.is_multiple_of(n.max(1)) || step_index == latest_index,
+        }
+    }
+}
+
+/// How the grids refer to the shared `DataItems`, e.g. the coordinates and connectivity of the mesh, which are used by the grids of all time steps.
+///
+/// Some readers (e.g. the `Xdmf3ReaderS` and `Xdmf3ReaderT` of Paraview) fail to resolve references to the `DataItems` of the domain,
+/// the other styles avoid these references for compatibility with them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReferenceStyle {
+    /// Write the `DataItems` once in the domain, and reference them from the grids by their `XPath`
+    #[default]
+    DomainReference,
+    /// Write a copy of the `DataItems` into every grid, with the inline data storages the values are repeated in every grid
+    InlinePerGrid,
+    /// Write a copy of the `DataItems` into every grid if they only contain the path to the heavy data (e.g. the HDF5 dataset),
+    /// with the inline data storages the values are written once in the domain and referenced, as with `DomainReference`
+    HdfPathPerGrid,
+}
+
+impl ReferenceStyle {
+    // whether the grids contain copies of the DataItems of the domain, instead of referencing them
+    pub(crate) fn embeds(self, data_storage: DataStorage) -> bool {
+        match self {
+            Self::DomainReference => false,
+            Self::InlinePerGrid => true,
+            Self::HdfPathPerGrid => !matches!(
+                data_storage,
+                DataStorage::AsciiInline | DataStorage::Base64Inline
+            ),
+        }
+    }
+}
+
+/// How the paths to the files of the heavy data are written into the XDMF file.
+///
+/// The paths always use forward slashes as separator, also on Windows, such that the files can be read on any platform.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PathPolicy {
+    /// Paths relative to the XDMF file, such that the XDMF file can be moved together with the files of the heavy data
+    #[default]
+    RelativeToXdmf,
+    /// Absolute paths, such that the XDMF file can be moved without the files of the heavy data
+    Absolute,
+}
+
+impl PathPolicy {
+    // path of a heavy data file as written into the XDMF file, given the path of the created file and its path relative to the XDMF file
+    pub(crate) fn reference_path(self, path: &Path, relative_path: &Path) -> XdmfResult<String> {
+        let path = match self {
+            Self::RelativeToXdmf => relative_path.to_path_buf(),
+            Self::Absolute => std::path::absolute(path)?,
+        };
+
+        Ok(path
+            .to_string_lossy()
+            .replace(std::path::MAIN_SEPARATOR, "/"))
+    }
+}
+
+/// Precision of the floating point data that is written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FloatPrecision {
+    /// Write the data as f32, which halves the size of the files
+    Single,
+    /// Write the data as f64
+    #[default]
+    Double,
+}
+
+/// Handling of NaN or infinite values in the data of the fields, e.g. after the solver diverged, see [`TimeSeriesWriter::with_nan_policy`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NanPolicy {
+    /// Write the values as they are
+    #[default]
+    Allow,
+    /// Fail writing a field that contains NaN or infinite values
+    Error,
+    /// Write the given value instead of NaN or infinite values
+    ReplaceWith(f64),
+}
+
+/// Handling of fields that are written with the same name as point- and cell-data of a time step, see [`TimeSeriesWriter::with_name_collision_policy`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum NameCollisionPolicy {
+    /// Write the fields with the same name, which readers such as Paraview might not distinguish
+    #[default]
+    Allow,
+    /// Fail writing the data of the time step
+    Error,
+    /// Append the given suffix to the name of the cell-data, e.g. `_cell`
+    SuffixCellData(String),
+}
+
+/// Precision of the connectivity and the indices of sets that are written, see [`TimeSeriesWriter::with_index_precision`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IndexPrecision {
+    /// Write the indices as u32 if all of them fit, otherwise as u64
+    #[default]
+    Auto,
+    /// Write the indices as u32, which halves the size of the files, fails if an index does not fit
+    U32,
+    /// Write the indices as u64
+    U64,
+}
+
+impl IndexPrecision {
+    // number of bytes per index the given indices are written with
+    pub(crate) fn resolve(self, indices: &[u64]) -> XdmfResult<u8> {
+        let fits_u32 = || indices.iter().all(|&index| u32::try_from(index).is_ok());
+
+        match self {
+            Self::Auto if fits_u32() => Ok(4),
+            Self::Auto | Self::U64 => Ok(8),
+            Self::U32 if fits_u32() => Ok(4),
+            Self::U32 => Err(XdmfError::Validation(format!(
+                "Indices can not be written as u32, the largest index {} exceeds {}",
+                indices.iter().max().copied().unwrap_or_default(),
+                u32::MAX
+            ))),
+        }
+    }
+}
+
+/// Options for the HDF5 data storages, they are ignored by the other data storages.
+/// ```rust
+/// let hdf5_options = xdmf::Hdf5Options::default()
+///     .with_compression(4)
+///     .with_shuffle();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Hdf5Options {
+    compression_level: Option<u8>,
+    shuffle: bool,
+    file_naming: Hdf5FileNaming,
+    layout: Hdf5Layout,
+    atomic_steps: bool,
+}
+
+impl Hdf5Options {
+    /// Compress the datasets with deflate (gzip), using the given level between 0 (fastest) and 9 (smallest).
+    pub fn with_compression(mut self, level: u8) -> Self {
+        self.compression_level = Some(level);
+        self
+    }
+
+    /// Shuffle the bytes of the values before compressing them, which often improves the compression of numeric data.
+    pub fn with_shuffle(mut self) -> Self {
+        self.shuffle = true;
+        self
+    }
+
+    /// Naming of the files written with [`DataStorage::Hdf5MultipleFiles`], see [`Hdf5FileNaming`].
+    pub fn with_file_naming(mut self, file_naming: Hdf5FileNaming) -> Self {
+        self.file_naming = file_naming;
+        self
+    }
+
+    /// Layout of the data of the time steps in the file written with [`DataStorage::Hdf5SingleFile`], see [`Hdf5Layout`].
+    pub fn with_layout(mut self, layout: Hdf5Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Write the file of each time step with [`DataStorage::Hdf5MultipleFiles`] to a temporary file first, which is renamed once the time step is complete.
+    ///
+    /// This way a crash while writing a time step never leaves a half-written file behind, a time step that is extended later is copied first.
+    /// Time steps whose file is missing after a crash can be removed from the XDMF file with [`prune_dangling_steps`].
+    pub fn with_atomic_steps(mut self) -> Self {
+        self.atomic_steps = true;
+        self
+    }
+
+    #[cfg(feature = "hdf5")]
+    pub(crate) fn compression_level(&self) -> Option<u8> {
+        self.compression_level
+    }
+
+    #[cfg(feature = "hdf5")]
+    pub(crate) fn shuffle(&self) -> bool {
+        self.shuffle
+    }
+
+    #[cfg(feature = "hdf5")]
+    pub(crate) fn file_naming(&self) -> &Hdf5FileNaming {
+        &self.file_naming
+    }
+
+    #[cfg(feature = "hdf5")]
+    pub(crate) fn layout(&self) -> Hdf5Layout {
+        self.layout
+    }
+
+    #[cfg(feature = "hdf5")]
+    pub(crate) fn atomic_steps(&self) -> bool {
+        self.atomic_steps
+    }
+
+    // check the options independent of the data storage
+    pub(crate) fn validate(&self) -> XdmfResult<()> {
+        if let Some(level) = self.compression_level
+            && level > 9
+        {
+            return Err(XdmfError::Validation(format!(
+                "Compression level must be between 0 and 9, but is {level}"
+            )));
+        }
+
+        self.file_naming.validate()
+    }
+}
+
+/// Layout of the data of the time steps in the file written with [`DataStorage::Hdf5SingleFile`].
+/// ```rust
+/// let hdf5_options = xdmf::Hdf5Options::default()
+///     .with_layout(xdmf::Hdf5Layout::TimeMajorDatasets)
+///     .with_compression(4);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Hdf5Layout {
+    /// A group per time step (`data/t_<time>/<center>`), containing a dataset per field
+    #[default]
+    GroupPerStep,
+    /// A dataset per field (`data/<center>/<field>`) with one row per time step, which grows along the time axis.
+    ///
+    /// The rows are chunked, which usually compresses better, and the time series of a field can be extracted from a single dataset.
+    /// The grid of each time step selects its row with a `HyperSlab` `DataItem`. The fields must have the same size and type at all time steps,
+    /// and this layout is not supported when writing collectively with parallel HDF5.
+    TimeMajorDatasets,
+}
+
+/// Naming of the files written with [`DataStorage::Hdf5MultipleFiles`] into the directory `<file_name>.h5`.
+///
+/// By default the mesh is written to `mesh.h5` and the data of each time step to `data_t_<time>.h5`.
+/// ```rust
+/// // writes `run1_mesh.h5` and `000000/run1_data.h5`, `000001/run1_data.h5`, ...
+/// let file_naming = xdmf::Hdf5FileNaming::default()
+///     .with_prefix("run1_")
+///     .with_step_index(6)
+///     .with_step_directories();
+///
+/// let hdf5_options = xdmf::Hdf5Options::default().with_file_naming(file_naming);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Hdf5FileNaming {
+    prefix: String,
+    step_index_width: Option<usize>,
+    step_directories: bool,
+}
+
+impl Hdf5FileNaming {
+    /// Prefix of the names of all files, such that multiple writers can share a directory.
+    pub fn with_prefix(mut self, prefix: impl ToString) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Identify the time steps by their index in the order of writing, zero-padded to the given width, instead of by their time.
+    ///
+    /// Static data (see [`TimeSeriesDataWriter::write_static_data`]) is written with its own index.
+    pub fn with_step_index(mut self, width: usize) -> Self {
+        self.step_index_width = Some(width);
+        self
+    }
+
+    /// Write the data of each time step into its own subdirectory, which makes deleting old time steps easy.
+    pub fn with_step_directories(mut self) -> Self {
+        self.step_directories = true;
+        self
+    }
+
+    /// Path of a mesh file, relative to the directory of the files.
+    #[cfg(feature = "hdf5")]
+    pub(crate) fn mesh_file(&self, name: Option<&str>) -> std::path::PathBuf {
+        let prefix = &self.prefix;
+        match name {
+            Some(name) => format!("{prefix}mesh_{name}.h5").into(),
+            None => format!("{prefix}mesh.h5").into(),
+        }
+    }
+
+    /// Path of the file of a set, relative to the directory of the files.
+    #[cfg(feature = "hdf5")]
+    pub(crate) fn set_file(&self, name: &str) -> std::path::PathBuf {
+        format!("{}sets_{name}.h5", self.prefix).into()
+    }
+
+    /// Path of the data file of a time step, relative to the directory of the files.
+    #[cfg(feature = "hdf5")]
+    pub(crate) fn data_file(&self, time: &str, step_index: usize) -> std::path::PathBuf {
+        let prefix = &self.prefix;
+        let step = match self.step_index_width {
+            Some(width) => format!("{step_index:0width$}"),
+            None => format!("t_{time}"),
+        };
+
+        if self.step_directories {
+            Path::new(&step).join(format!("{prefix}data.h5"))
+        } else {
+            format!("{prefix}data_{step}.h5").into()
+        }
+    }
+
+    // the prefix is part of the file names, hence it must not contain a path
+    fn validate(&self) -> XdmfResult<()> {
+        if self.prefix.contains(['/', '\\']) {
+            return Err(XdmfError::Validation(format!(
+                "Prefix '{}' of the HDF5 files must not contain path separators",
+                self.prefix
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Select the data storage to be used, falling back to the alternative if it is not available.
+pub(crate) fn select_data_storage(
+    data_storage: DataStorage,
+    fallback: DataStorage,
+) -> XdmfResult<DataStorage> {
+    if data_storage.is_available() {
+        return Ok(data_storage);
+    }
+
+    if !fallback.is_available() {
+        return Err(XdmfError::Unsupported(format!(
+            "Neither {data_storage:?} nor the fallback {fallback:?} DataStorage is available"
+        )));
+    }
+
+    log::warn!("{data_storage:?} DataStorage is not available, falling back to {fallback:?}");
+
+    Ok(fallback)
+}
+
+/// Heavy data name, center, values and dimensions of a field, see [`DataWriter::write_fields`].
+pub type FieldData<'a> = (&'a str, attribute::Center, &'a Values<'a>, &'a Dimensions);
+
+/// Writer for the heavy data, which can be implemented to store it in a custom backend, e.g. an object store.
+///
+/// Each write returns the [`DataContent`] that refers to the written data in the XDMF file, e.g. the path of an HDF5 dataset
+/// (as `file:/path`) for [`Format::HDF`], or the values as text for [`Format::XML`]. It is written as content of a `DataItem`,
+/// together with the dimensions, number type and precision of the values.
+/// The names of the data are unique within the label given to [`DataWriter::write_data_initialize`], i.e. the time step.
+///
+/// Implementations must persist all written data when `flush` or `close` is called, as the XDMF file
+/// referencing the data is written afterwards. `close` is called exactly once, when the time series writer
+/// is closed or dropped (also during a panic), no data is written afterwards.
+/// A custom writer is used with [`TimeSeriesWriter::new_with_data_writer`].
+pub trait DataWriter: Send {
+    /// Format of the written data, written as `Format` of the `DataItems`.
     fn format(&self) -> Format;
 
+    /// Encoding of the data that is written inline, if not plain text.
+    fn encoding(&self) -> Option<Encoding> {
+        None
+    }
+
+    /// Data storage the written data corresponds to, e.g. [`DataStorage::Hdf5MultipleFiles`] for files per time step.
+    ///
+    /// It is written as `Information` into the XDMF file, and the inline storages are not copied with [`ReferenceStyle::HdfPathPerGrid`].
     fn data_storage(&self) -> DataStorage;
 
-    fn write_mesh(&mut self, points: &[f64], cells: &[u64])
-    -> IoResult<(DataContent, DataContent)>;
+    /// Write the coordinates and the connectivity of the mesh.
+    fn write_mesh(
+        &mut self,
+        points: &[f64],
+        cells: &[u64],
+    ) -> XdmfResult<(DataContent, DataContent)>;
+
+    /// Write an additional mesh, e.g. an updated mesh or a part of the mesh, identified by its name.
+    fn write_named_mesh(
+        &mut self,
+        name: &str,
+        points: &[f64],
+        cells: &[u64],
+    ) -> XdmfResult<(DataContent, DataContent)>;
+
+    /// Write a mesh with the coordinates as separate arrays (x, y, z), identified by its name if given.
+    fn write_mesh_soa(
+        &mut self,
+        name: Option<&str>,
+        coordinates: [&[f64]; 3],
+        cells: &[u64],
+    ) -> XdmfResult<([DataContent; 3], DataContent)>;
+
+    /// Write the indices of the entities of a set, identified by its name.
+    fn write_set(&mut self, name: &str, indices: &[u64]) -> XdmfResult<DataContent>;
 
+    /// Write the values of a field, located at the given center.
     fn write_data(
         &mut self,
         name: &str,
         center: attribute::Center,
         data: &Values,
-    ) -> IoResult<DataContent>;
+    ) -> XdmfResult<DataContent>;
+
+    /// Write data provided by an iterator with exactly `len` values, without holding all values in memory.
+    fn write_data_streamed(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        values: &mut dyn Iterator<Item = f64>,
+        len: usize,
+    ) -> XdmfResult<DataContent>;
+
+    /// Write data with the given dimensions, the formats that store the shape of the data override this.
+    fn write_shaped_data(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        data: &Values,
+        _dimensions: &Dimensions,
+    ) -> XdmfResult<DataContent> {
+        self.write_data(name, center, data)
+    }
+
+    /// Write the data of multiple fields of a time step, the formats that can write them concurrently override this.
+    fn write_fields(&mut self, fields: &[FieldData<'_>]) -> XdmfResult<Vec<DataContent>> {
+        fields
+            .iter()
+            .map(|&(name, center, data, dimensions)| {
+                self.write_shaped_data(name, center, data, dimensions)
+            })
+            .collect()
+    }
+
+    /// Start writing the data of a time step (or of the static data), identified by its label.
+    fn write_data_initialize(&mut self, _time: &str) -> XdmfResult<()> {
+        Ok(())
+    }
 
-    fn write_data_initialize(&mut self, _time: &str) -> IoResult<()> {
+    /// Finish writing the data of the time step started with [`DataWriter::write_data_initialize`].
+    fn write_data_finalize(&mut self) -> XdmfResult<()> {
         Ok(())
     }
 
-    fn write_data_finalize(&mut self) -> IoResult<()> {
+    /// Adapt a data item describing written data, e.g. if only a part of the written dataset belongs to this writer.
+    fn wrap_data_item(&self, data_item: DataItem) -> DataItem {
+        data_item
+    }
+
+    /// Limit the size of data that is written inline into the XDMF file, if applicable.
+    fn set_inline_size_limit(&mut self, _size_limit: usize) {}
+
+    /// Set how the paths to the files of the heavy data are written, if the data is not written inline.
+    fn set_path_policy(&mut self, _path_policy: PathPolicy) {}
+
+    /// Precision of the connectivity and the indices of sets that are written, see [`IndexPrecision`].
+    ///
+    /// Writers that always write the indices as u64 keep the default.
+    fn index_precision(&self) -> IndexPrecision {
+        IndexPrecision::U64
+    }
+
+    /// Set the precision of the connectivity and the indices of sets, if applicable.
+    fn set_index_precision(&mut self, _index_precision: IndexPrecision) {}
+
+    /// Path of the file or directory the heavy data is written to, `None` if it is written into the XDMF file.
+    fn heavy_data_path(&self) -> Option<&Path> {
+        None
+    }
+
+    /// Set the options for writing HDF5 files, if applicable.
+    fn set_hdf5_options(&mut self, _options: &Hdf5Options) -> XdmfResult<()> {
         Ok(())
     }
 
-    // flush the writer, if applicable
-    fn flush(&mut self) -> IoResult<()> {
+    /// Delete the heavy data of a time step that is no longer referenced, if it is stored in separate files.
+    fn remove_step(&mut self, _time: &str) -> XdmfResult<()> {
         Ok(())
     }
+
+    /// Flush the writer, if applicable.
+    fn flush(&mut self) -> XdmfResult<()> {
+        Ok(())
+    }
+
+    /// Finish writing, all data must be persisted afterwards.
+    fn close(&mut self) -> XdmfResult<()> {
+        self.flush()
+    }
+}
+
+/// Check that a stream of values is exhausted after the expected number of values was written.
+pub(crate) fn check_stream_exhausted(
+    name: &str,
+    values: &mut dyn Iterator<Item = f64>,
+    num_written: usize,
+    len: usize,
+) -> XdmfResult<()> {
+    if num_written < len {
+        return Err(XdmfError::Validation(format!(
+            "Streamed data '{name}' ended after {num_written} values, but {len} values are expected"
+        )));
+    }
+
+    if values.next().is_some() {
+        return Err(XdmfError::Validation(format!(
+            "Streamed data '{name}' has more than the expected {len} values"
+        )));
+    }
+
+    Ok(())
 }
 
 /// Create a writer for the heavy data, based on the chosen data storage.
 pub(crate) fn create_writer(
     file_name: &Path,
     data_storage: DataStorage,
-) -> IoResult<Box<dyn DataWriter>> {
+    file_system: &Arc<dyn FileSystem>,
+) -> XdmfResult<Box<dyn DataWriter>> {
     match data_storage {
-        DataStorage::Ascii => Ok(Box::new(ascii_writer::AsciiWriter::new(file_name)?)),
+        DataStorage::Ascii => Ok(Box::new(ascii_writer::AsciiWriter::new(
+            file_name,
+            file_system.clone(),
+        )?)),
+        DataStorage::AsciiGz => Ok(Box::new(
+            ascii_writer::AsciiWriter::new(file_name, file_system.clone())?.with_gzip(),
+        )),
         DataStorage::AsciiInline => Ok(Box::new(ascii_writer::AsciiInlineWriter::new())),
+        DataStorage::Base64Inline => Ok(Box::new(base64_writer::Base64InlineWriter::new())),
         DataStorage::Hdf5SingleFile => {
             #[cfg(feature = "hdf5")]
             {
@@ -106,8 +716,8 @@ pub(crate) fn create_writer(
             }
             #[cfg(not(feature = "hdf5"))]
             {
-                Err(IoError::other(
-                    "Using Hdf5SingleFile DataStorage requires the hdf5 feature.",
+                Err(XdmfError::Unsupported(
+                    "Using Hdf5SingleFile DataStorage requires the hdf5 feature.".into(),
                 ))
             }
         }
@@ -116,12 +726,13 @@ pub(crate) fn create_writer(
             {
                 Ok(Box::new(hdf5_writer::MultipleFilesHdf5Writer::new(
                     file_name,
+                    &Hdf5FileNaming::default(),
                 )?))
             }
             #[cfg(not(feature = "hdf5"))]
             {
-                Err(IoError::other(
-                    "Using Hdf5MultipleFiles DataStorage requires the hdf5 feature.",
+                Err(XdmfError::Unsupported(
+                    "Using Hdf5MultipleFiles DataStorage requires the hdf5 feature.".into(),
                 ))
             }
         }
@@ -141,7 +752,7 @@ pub const fn is_hdf5_enabled() -> bool {
 }
 
 /// Type of the data (scalar, vector, tensor, etc.)
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum DataAttribute {
     /// single value
     Scalar,
@@ -149,12 +760,12 @@ pub enum DataAttribute {
     Vector,
     /// 2nd order tensor in 3D (9 components)
     Tensor,
-    /// Symmetric 2nd order tensor in 3D (6 components)
+    /// Symmetric 2nd order tensor in 3D (6 components, ordered XX, XY, XZ, YY, YZ, ZZ)
     Tensor6,
     /// Matrix with specified number of rows and columns
     Matrix(usize, usize),
-    /// Generic data with specified size
-    Generic(usize),
+    /// Generic data with the specified shape per entity, e.g. `vec![num_species, num_moments]`
+    Generic(Vec<usize>),
 }
 
 impl DataAttribute {
@@ -165,18 +776,37 @@ impl DataAttribute {
             Self::Tensor => 9,
             Self::Tensor6 => 6,
             Self::Matrix(n, m) => n * m,
-            Self::Generic(size) => *size,
+            Self::Generic(shape) => shape.iter().product(),
+        }
+    }
+
+    /// Dimensions of data with `len` values, i.e. one row per entity (point or cell).
+    pub(crate) fn dimensions(&self, len: usize) -> Dimensions {
+        match self {
+            Self::Scalar => Dimensions(vec![len]),
+            Self::Generic(shape) => Dimensions(
+                std::iter::once(len / self.size().max(1))
+                    .chain(shape.iter().copied())
+                    .collect(),
+            ),
+            _ => Dimensions(vec![len / self.size(), self.size()]),
         }
     }
 }
 
 impl From<DataAttribute> for attribute::AttributeType {
     fn from(data_attr: DataAttribute) -> Self {
+        Self::from(&data_attr)
+    }
+}
+
+impl From<&DataAttribute> for attribute::AttributeType {
+    fn from(data_attr: &DataAttribute) -> Self {
         match data_attr {
             DataAttribute::Scalar => Self::Scalar,
             DataAttribute::Vector => Self::Vector,
             DataAttribute::Tensor => Self::Tensor,
-            DataAttribute::Tensor6 => Self::Matrix, // writen as Matrix to get detected as symmetric tensor
+            DataAttribute::Tensor6 => Self::Tensor6,
             DataAttribute::Matrix(_, _) => Self::Matrix,
             DataAttribute::Generic(_) => Self::Matrix,
         }
@@ -237,6 +867,18 @@ mod tests {
         assert!(dirs_to_create.exists());
     }
 
+    #[test]
+    fn data_attribute_dimensions_large() {
+        let len = 3 << 32;
+
+        assert_eq!(DataAttribute::Scalar.dimensions(len).0, vec![len]);
+        assert_eq!(DataAttribute::Vector.dimensions(len).0, vec![1 << 32, 3]);
+        assert_eq!(
+            DataAttribute::Generic(vec![3, 2]).dimensions(6 << 32).0,
+            vec![1 << 32, 3, 2]
+        );
+    }
+
     #[test]
     fn test_data_attribute() {
         let scalar = DataAttribute::Scalar;
@@ -244,7 +886,7 @@ mod tests {
         let tensor = DataAttribute::Tensor;
         let tensor6 = DataAttribute::Tensor6;
         let matrix = DataAttribute::Matrix(3, 3);
-        let generic = DataAttribute::Generic(5);
+        let generic = DataAttribute::Generic(vec![5]);
 
         assert_eq!(scalar.size(), 1);
         assert_eq!(vector.size(), 3);
@@ -252,15 +894,134 @@ mod tests {
         assert_eq!(tensor6.size(), 6);
         assert_eq!(matrix.size(), 9);
         assert_eq!(generic.size(), 5);
+        assert_eq!(DataAttribute::Generic(vec![2, 3]).size(), 6);
+
+        assert_eq!(vector.dimensions(6), Dimensions(vec![2, 3]));
+        assert_eq!(generic.dimensions(10), Dimensions(vec![2, 5]));
+        assert_eq!(
+            DataAttribute::Generic(vec![2, 3]).dimensions(24),
+            Dimensions(vec![4, 2, 3])
+        );
 
         assert_eq!(attribute::AttributeType::Scalar, scalar.into());
         assert_eq!(attribute::AttributeType::Vector, vector.into());
         assert_eq!(attribute::AttributeType::Tensor, tensor.into());
-        assert_eq!(attribute::AttributeType::Matrix, tensor6.into());
+        assert_eq!(attribute::AttributeType::Tensor6, tensor6.into());
         assert_eq!(attribute::AttributeType::Matrix, matrix.into());
         assert_eq!(attribute::AttributeType::Matrix, generic.into());
     }
 
+    #[test]
+    fn test_data_storage_is_available() {
+        assert!(DataStorage::Ascii.is_available());
+        assert!(DataStorage::AsciiGz.is_available());
+        assert!(DataStorage::AsciiInline.is_available());
+        assert!(DataStorage::Base64Inline.is_available());
+        assert_eq!(
+            DataStorage::Hdf5SingleFile.is_available(),
+            cfg!(feature = "hdf5")
+        );
+        assert_eq!(
+            DataStorage::Hdf5MultipleFiles.is_available(),
+            cfg!(feature = "hdf5")
+        );
+    }
+
+    #[test]
+    fn test_select_data_storage() {
+        assert_eq!(
+            select_data_storage(DataStorage::Ascii, DataStorage::AsciiInline).unwrap(),
+            DataStorage::Ascii
+        );
+
+        if cfg!(feature = "hdf5") {
+            assert_eq!(
+                select_data_storage(DataStorage::Hdf5SingleFile, DataStorage::Ascii).unwrap(),
+                DataStorage::Hdf5SingleFile
+            );
+        } else {
+            assert_eq!(
+                select_data_storage(DataStorage::Hdf5SingleFile, DataStorage::Ascii).unwrap(),
+                DataStorage::Ascii
+            );
+            assert_eq!(
+                select_data_storage(DataStorage::Hdf5SingleFile, DataStorage::Hdf5MultipleFiles)
+                    .unwrap_err()
+                    .to_string(),
+                "Neither Hdf5SingleFile nor the fallback Hdf5MultipleFiles DataStorage is available"
+            );
+        }
+    }
+
+    #[test]
+    fn flush_policy_is_due() {
+        assert!(FlushPolicy::EveryStep.is_due(1));
+
+        assert!(!FlushPolicy::EveryN(3).is_due(2));
+        assert!(FlushPolicy::EveryN(3).is_due(3));
+
+        assert!(!FlushPolicy::OnFinalize.is_due(100));
+    }
+
+    #[test]
+    fn retention_policy_is_kept() {
+        assert!(RetentionPolicy::KeepAll.is_kept(0, 100));
+
+        assert!(!RetentionPolicy::KeepLast(3).is_kept(6, 9));
+        assert!(RetentionPolicy::KeepLast(3).is_kept(7, 9));
+        assert!(RetentionPolicy::KeepLast(0).is_kept(9, 9));
+
+        assert!(RetentionPolicy::KeepEvery(3).is_kept(6, 9));
+        assert!(!RetentionPolicy::KeepEvery(3).is_kept(7, 9));
+        assert!(RetentionPolicy::KeepEvery(3).is_kept(8, 8));
+        assert!(RetentionPolicy::KeepEvery(0).is_kept(7, 9));
+    }
+
+    #[test]
+    fn reference_style_embeds() {
+        assert!(!ReferenceStyle::DomainReference.embeds(DataStorage::Ascii));
+        assert!(ReferenceStyle::InlinePerGrid.embeds(DataStorage::AsciiInline));
+        assert!(ReferenceStyle::HdfPathPerGrid.embeds(DataStorage::Hdf5SingleFile));
+        assert!(ReferenceStyle::HdfPathPerGrid.embeds(DataStorage::AsciiGz));
+        assert!(!ReferenceStyle::HdfPathPerGrid.embeds(DataStorage::Base64Inline));
+    }
+
+    #[test]
+    fn path_policy_reference_path() {
+        let path = Path::new("results").join("case.txt").join("points.txt");
+        let relative_path = Path::new("case.txt").join("points.txt");
+
+        assert_eq!(
+            PathPolicy::RelativeToXdmf
+                .reference_path(&path, &relative_path)
+                .unwrap(),
+            "case.txt/points.txt"
+        );
+
+        let absolute_path = PathPolicy::Absolute
+            .reference_path(&path, &relative_path)
+            .unwrap();
+        assert!(Path::new(&absolute_path).is_absolute());
+        assert!(absolute_path.ends_with("/results/case.txt/points.txt"));
+        assert!(!absolute_path.contains('\\'));
+    }
+
+    #[test]
+    fn index_precision_resolve() {
+        let small = [0, 7, u64::from(u32::MAX)];
+        let large = [0, u64::from(u32::MAX) + 1];
+
+        assert_eq!(IndexPrecision::Auto.resolve(&small).unwrap(), 4);
+        assert_eq!(IndexPrecision::Auto.resolve(&large).unwrap(), 8);
+        assert_eq!(IndexPrecision::Auto.resolve(&[]).unwrap(), 4);
+        assert_eq!(IndexPrecision::U32.resolve(&small).unwrap(), 4);
+        assert_eq!(IndexPrecision::U64.resolve(&small).unwrap(), 8);
+        assert_eq!(
+            IndexPrecision::U32.resolve(&large).unwrap_err().to_string(),
+            "Indices can not be written as u32, the largest index 4294967296 exceeds 4294967295"
+        );
+    }
+
     #[test]
     fn test_data_storage_from_str() {
         // Test exact case matches
@@ -268,6 +1029,16 @@ mod tests {
         assert_eq!("Ascii".parse::<DataStorage>().unwrap(), DataStorage::Ascii);
         assert_eq!("ASCII".parse::<DataStorage>().unwrap(), DataStorage::Ascii);
 
+        // Test AsciiGz variants
+        assert_eq!(
+            "AsciiGz".parse::<DataStorage>().unwrap(),
+            DataStorage::AsciiGz
+        );
+        assert_eq!(
+            "ascii-gz".parse::<DataStorage>().unwrap(),
+            DataStorage::AsciiGz
+        );
+
         // Test AsciiInline variants
         assert_eq!(
             "asciiinline".parse::<DataStorage>().unwrap(),
@@ -282,6 +1053,16 @@ mod tests {
             DataStorage::AsciiInline
         );
 
+        // Test Base64Inline variants
+        assert_eq!(
+            "Base64Inline".parse::<DataStorage>().unwrap(),
+            DataStorage::Base64Inline
+        );
+        assert_eq!(
+            "base64_inline".parse::<DataStorage>().unwrap(),
+            DataStorage::Base64Inline
+        );
+
         // Test Hdf5SingleFile variants
         assert_eq!(
             "hdf5singlefile".parse::<DataStorage>().unwrap(),
@@ -314,13 +1095,13 @@ mod tests {
         let err = "invalid".parse::<DataStorage>().unwrap_err();
         assert_eq!(
             err,
-            "Invalid DataStorage variant: 'invalid'. Valid options are: 'Ascii', 'AsciiInline', 'Hdf5SingleFile', 'Hdf5MultipleFiles'"
+            "Invalid DataStorage variant: 'invalid'. Valid options are: 'Ascii', 'AsciiGz', 'AsciiInline', 'Base64Inline', 'Hdf5SingleFile', 'Hdf5MultipleFiles'"
         );
 
         let err = "".parse::<DataStorage>().unwrap_err();
         assert_eq!(
             err,
-            "Invalid DataStorage variant: ''. Valid options are: 'Ascii', 'AsciiInline', 'Hdf5SingleFile', 'Hdf5MultipleFiles'"
+            "Invalid DataStorage variant: ''. Valid options are: 'Ascii', 'AsciiGz', 'AsciiInline', 'Base64Inline', 'Hdf5SingleFile', 'Hdf5MultipleFiles'"
         );
     }
 }
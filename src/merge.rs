@@ -0,0 +1,331 @@
+//! Merging of the XDMF files that multiple processes wrote independently into one master file.
+//!
+//! If the ranks of a simulation can not coordinate their output at all, each can write its own complete XDMF file with a
+//! [`TimeSeriesWriter`](crate::TimeSeriesWriter). Afterwards [`merge_files`] combines them into a master file with a
+//! spatial collection of the grids of all files per time step, like the master file of the
+//! [`ParallelTimeSeriesWriter`](crate::ParallelTimeSeriesWriter). The heavy data is not copied, the master file references it.
+//! ```rust
+//! use xdmf::TimeSeriesWriter;
+//!
+//! for rank in 0..2 {
+//!     let file_name = format!("merged_rank_{rank}");
+//!     let mut ts_writer = TimeSeriesWriter::new(file_name, xdmf::DataStorage::Ascii)
+//!         .expect("failed to create XDMF writer")
+//!         .write_mesh(&[f64::from(rank), 0.0, 0.0], (&[], &[]))
+//!         .expect("failed to write mesh");
+//!
+//!     let point_data = vec![(
+//!         "rank".to_string(),
+//!         (xdmf::DataAttribute::Scalar, vec![f64::from(rank)].into()),
+//!     )]
+//!     .into_iter()
+//!     .collect();
+//!
+//!     for time in ["0.0", "1.0"] {
+//!         ts_writer
+//!             .write_data(time, Some(&point_data), None)
+//!             .expect("failed to write data");
+//!     }
+//! }
+//!
+//! xdmf::merge::merge_files(
+//!     &["merged_rank_0.xdmf2", "merged_rank_1.xdmf2"],
+//!     "merged_master.xdmf2",
+//! )
+//! .expect("failed to merge files");
+//!
+//! assert!(
+//!     xdmf::validate::validate_file("merged_master.xdmf2")
+//!         .expect("failed to read file")
+//!         .is_valid()
+//! );
+//! # std::fs::remove_file("merged_master.xdmf2").expect("failed to remove xdmf file");
+//! # for rank in 0..2 {
+//! #     std::fs::remove_file(format!("merged_rank_{rank}.xdmf2")).expect("failed to remove xdmf file");
+//! #     std::fs::remove_dir_all(format!("merged_rank_{rank}.txt")).expect("failed to remove txt files");
+//! # }
+//! ```
+
+use std::{fs::File, io::BufReader, path::Path};
+
+use crate::{
+    LocalFileSystem, XdmfError, XdmfResult, XmlHeader,
+    number_format::parse_time,
+    rebase::rebase_xdmf_references,
+    time_series_writer::{embed_references, temporal_collection, write_xdmf_file},
+    xdmf_elements::{
+        Information, Xdmf,
+        data_item::DataContent,
+        grid::{CollectionType, Grid, Time, TimeType},
+    },
+};
+
+/// Merge the XDMF files written independently by multiple ranks into a master file.
+///
+/// The first grid of each file is merged, i.e. the time series written by the `TimeSeriesWriter`, its subsets and submeshes are not.
+/// All files must contain the same times, the grids of a time step are matched by the value of their time.
+/// Files without a time series are merged into a single spatial collection.
+/// The files must be in the directory of the master file or in a subdirectory of it, as their heavy data is referenced relative to the master file.
+pub fn merge_files(
+    file_names: &[impl AsRef<Path>],
+    master_file_name: impl AsRef<Path>,
+) -> XdmfResult<()> {
+    let master_file_name = master_file_name.as_ref();
+    let master_dir = master_file_name.parent().unwrap_or_else(|| Path::new(""));
+
+    let xdmfs = file_names
+        .iter()
+        .map(|file_name| {
+            let file_name = file_name.as_ref();
+            let file_dir = file_name.parent().unwrap_or_else(|| Path::new(""));
+            let relative_dir = file_dir.strip_prefix(master_dir).map_err(|_err| {
+                XdmfError::Validation(format!(
+                    "File '{}' must be in the directory of the master file '{}' or in a subdirectory",
+                    file_name.display(),
+                    master_file_name.display()
+                ))
+            })?;
+
+            let mut xdmf = Xdmf::read_from(BufReader::new(File::open(file_name)?))?;
+
+            // the references are relative to the file, hence they are rewritten relative to the master file
+            if !relative_dir.as_os_str().is_empty() {
+                rebase_xdmf_references(&mut xdmf, "", relative_dir);
+            }
+
+            Ok(xdmf)
+        })
+        .collect::<XdmfResult<Vec<_>>>()?;
+
+    write_xdmf_file(
+        &LocalFileSystem,
+        &merge_xdmf(xdmfs)?,
+        &XmlHeader::default(),
+        None,
+        master_file_name,
+    )
+}
+
+/// Merge the `Xdmf` structures written independently by multiple ranks into one.
+///
+/// See [`merge_files`] for details, the references to the heavy data are kept as they are.
+pub fn merge_xdmf(xdmfs: impl IntoIterator<Item = Xdmf>) -> XdmfResult<Xdmf> {
+    let mut static_grids = Vec::new();
+    let mut time_series = Vec::new();
+
+    for (index, mut xdmf) in xdmfs.into_iter().enumerate() {
+        // the names of the DataItems of the domains are the same for all ranks
+        embed_references(&mut xdmf);
+
+        let grid = xdmf
+            .domains
+            .into_iter()
+            .next()
+            .and_then(|domain| domain.grids.into_iter().next())
+            .ok_or_else(|| XdmfError::Validation(format!("File {index} has no grid to merge")))?;
+
+        if grid.collection_type == Some(CollectionType::Temporal) {
+            time_series.push(time_steps(grid));
+        } else {
+            static_grids.push(grid);
+        }
+    }
+
+    let num_ranks = static_grids.len() + time_series.len();
+    let grid_to_write = match (static_grids.is_empty(), time_series.is_empty()) {
+        (_, true) => partitions("partitions", None, static_grids),
+        (true, false) => merge_time_series(time_series)?,
+        (false, false) => {
+            return Err(XdmfError::Validation(
+                "Either all or none of the files must contain a time series".into(),
+            ));
+        }
+    };
+
+    let mut xdmf = Xdmf {
+        information: vec![
+            Information::new("num_ranks", num_ranks),
+            Information::new("version", env!("CARGO_PKG_VERSION")),
+        ],
+        ..Default::default()
+    };
+    xdmf.domains[0].grids.push(grid_to_write);
+
+    Ok(xdmf)
+}
+
+// the grids of the time steps of a temporal collection, each with its own `Time`
+fn time_steps(mut grid: Grid) -> Vec<Grid> {
+    let mut time_grids = grid.grids.take().unwrap_or_default();
+
+    // the times of the steps are given as a list in the collection
+    if let Some(time) = grid.time
+        && time.time_type == Some(TimeType::List)
+        && let Some(data_item) = time.data_item
+        && let DataContent::Raw(times) = data_item.data
+    {
+        for (time_grid, time) in time_grids.iter_mut().zip(times.split_whitespace()) {
+            time_grid.time = Some(Time::new(time));
+        }
+    }
+
+    time_grids
+}
+
+fn merge_time_series(time_series: Vec<Vec<Grid>>) -> XdmfResult<Grid> {
+    let times = |time_grids: &[Grid]| -> XdmfResult<Vec<(f64, String)>> {
+        time_grids
+            .iter()
+            .map(|grid| {
+                let value = grid
+                    .time
+                    .as_ref()
+                    .and_then(|time| time.value.clone())
+                    .ok_or_else(|| {
+                        XdmfError::Validation(format!("Grid '{}' has no time", grid.name))
+                    })?;
+
+                Ok((parse_time(&value)?, value))
+            })
+            .collect()
+    };
+
+    let reference_times = times(&time_series[0])?;
+    for (index, time_grids) in time_series.iter().enumerate().skip(1) {
+        let matches = times(time_grids)?
+            .iter()
+            .map(|(time, _)| time)
+            .eq(reference_times.iter().map(|(time, _)| time));
+
+        if !matches {
+            return Err(XdmfError::Validation(format!(
+                "Times of file {index} do not match the times of the first file"
+            )));
+        }
+    }
+
+    let mut time_series: Vec<_> = time_series.into_iter().map(Vec::into_iter).collect();
+    let spatial_grids = reference_times
+        .into_iter()
+        .map(|(_, value)| {
+            let grids = time_series.iter_mut().filter_map(Iterator::next).collect();
+            partitions(
+                format!("partitions-t{value}"),
+                Some(Time::new(value)),
+                grids,
+            )
+        })
+        .collect();
+
+    Ok(temporal_collection("time_series", spatial_grids, false))
+}
+
+// spatial collection of the grids of all ranks, whose names are made unique by the rank
+fn partitions(name: impl ToString, time: Option<Time>, grids: Vec<Grid>) -> Grid {
+    let grids = grids
+        .into_iter()
+        .enumerate()
+        .map(|(rank, mut grid)| {
+            grid.name = format!("{}-rank{rank}", grid.name);
+            grid
+        })
+        .collect();
+
+    let mut spatial_grid = Grid::new_collection(name, CollectionType::Spatial, Some(grids));
+    spatial_grid.time = time;
+    spatial_grid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdmf_elements::{
+        Domain,
+        geometry::{Geometry, GeometryType},
+        topology::Topology,
+    };
+
+    fn rank_xdmf(times: &[&str], time_list: bool) -> Xdmf {
+        let grids = times
+            .iter()
+            .map(|time| {
+                let mut grid = Grid::new_uniform(
+                    format!("time_series-t{time}"),
+                    Geometry {
+                        geometry_type: GeometryType::XYZ,
+                        data_items: vec![],
+                    },
+                    Topology::new_co_rect_mesh([1, 1, 1]),
+                );
+                grid.time = Some(Time::new(time));
+                grid
+            })
+            .collect();
+
+        Xdmf::new(Domain::new(temporal_collection(
+            "time_series",
+            grids,
+            time_list,
+        )))
+    }
+
+    #[test]
+    fn merge_time_series() {
+        let xdmf = merge_xdmf([
+            rank_xdmf(&["0.0", "0.5"], false),
+            rank_xdmf(&["0", "0.5"], true),
+        ])
+        .unwrap();
+
+        let time_series = &xdmf.domains[0].grids[0];
+        assert_eq!(time_series.collection_type, Some(CollectionType::Temporal));
+
+        let spatial_grids = time_series.grids.as_ref().unwrap();
+        assert_eq!(spatial_grids.len(), 2);
+
+        let partitions = spatial_grids[1].grids.as_ref().unwrap();
+        assert_eq!(spatial_grids[1].name, "partitions-t0.5");
+        assert_eq!(
+            partitions
+                .iter()
+                .map(|grid| grid.name.as_str())
+                .collect::<Vec<_>>(),
+            ["time_series-t0.5-rank0", "time_series-t0.5-rank1"]
+        );
+        // the time of a list is given to the grid of each time step
+        assert_eq!(
+            partitions[1].time.as_ref().unwrap().value.as_deref(),
+            Some("0.5")
+        );
+    }
+
+    #[test]
+    fn merge_invalid() {
+        assert_eq!(
+            merge_xdmf([
+                rank_xdmf(&["0.0", "0.5"], false),
+                rank_xdmf(&["0.0", "1.0"], false)
+            ])
+            .unwrap_err()
+            .to_string(),
+            "Times of file 1 do not match the times of the first file"
+        );
+
+        let static_xdmf = Xdmf::new(Domain::new(Grid::new_collection(
+            "mesh",
+            CollectionType::Spatial,
+            None,
+        )));
+        assert_eq!(
+            merge_xdmf([rank_xdmf(&["0.0"], false), static_xdmf])
+                .unwrap_err()
+                .to_string(),
+            "Either all or none of the files must contain a time series"
+        );
+
+        assert_eq!(
+            merge_xdmf([Xdmf::default()]).unwrap_err().to_string(),
+            "File 0 has no grid to merge"
+        );
+    }
+}
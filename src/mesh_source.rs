@@ -0,0 +1,135 @@
+//! This module contains the [`MeshSource`] trait, which allows to write the meshes of other crates without flattening them manually.
+
+use crate::CellType;
+
+/// Mesh whose points and cells can be iterated, written with [`TimeSeriesWriter::write_mesh_from`](crate::TimeSeriesWriter::write_mesh_from).
+///
+/// Implementing it for the mesh type of another crate or of the simulation code avoids
+/// flattening the points and the connectivity manually before writing the mesh.
+/// ```rust
+/// use xdmf::{CellType, MeshSource};
+///
+/// // mesh type of a simulation code, consisting of quadrilaterals
+/// struct QuadMesh {
+///     nodes: Vec<(f64, f64)>,
+///     elements: Vec<[usize; 4]>,
+/// }
+///
+/// impl MeshSource for QuadMesh {
+///     fn points(&self) -> impl Iterator<Item = [f64; 3]> {
+///         self.nodes.iter().map(|&(x, y)| [x, y, 0.0])
+///     }
+///
+///     fn cells(&self) -> impl Iterator<Item = (CellType, impl IntoIterator<Item = u64>)> {
+///         self.elements.iter().map(|element| {
+///             (
+///                 CellType::Quadrilateral,
+///                 element.iter().map(|&node| node as u64),
+///             )
+///         })
+///     }
+/// }
+///
+/// let mesh = QuadMesh {
+///     nodes: vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)],
+///     elements: vec![[0, 1, 2, 3]],
+/// };
+///
+/// let ts_writer = xdmf::TimeSeriesWriter::new("xdmf_mesh_source", xdmf::DataStorage::AsciiInline)
+///     .expect("failed to create XDMF writer")
+///     .write_mesh_from(&mesh)
+///     .expect("failed to write mesh");
+/// ```
+pub trait MeshSource {
+    /// Coordinates of the points, the indices of the points in the cells refer to this order.
+    fn points(&self) -> impl Iterator<Item = [f64; 3]>;
+
+    /// Type and points of each cell, for polyhedra the face stream, see [`CellType::polyhedron`].
+    fn cells(&self) -> impl Iterator<Item = (CellType, impl IntoIterator<Item = u64>)>;
+
+    /// The interleaved coordinates of the points and the connectivity with the types of the cells,
+    /// as taken by [`TimeSeriesWriter::write_mesh`](crate::TimeSeriesWriter::write_mesh).
+    fn flatten(&self) -> (Vec<f64>, (Vec<u64>, Vec<CellType>)) {
+        let points = self.points().flatten().collect();
+
+        let mut connectivity = Vec::new();
+        let mut cell_types = Vec::new();
+        for (cell_type, cell) in self.cells() {
+            connectivity.extend(cell);
+            cell_types.push(cell_type);
+        }
+
+        (points, (connectivity, cell_types))
+    }
+}
+
+/// Mesh given as vertices and faces with the indices of their vertices, e.g. a triangulated surface.
+///
+/// The type of each face follows from its number of vertices: triangles, quadrilaterals and polygons,
+/// as well as vertices and edges for faces with one or two vertices.
+/// ```rust
+/// use xdmf::{FaceVertexMesh, MeshSource};
+///
+/// let mesh = FaceVertexMesh {
+///     vertices: vec![
+///         [0.0, 0.0, 0.0],
+///         [1.0, 0.0, 0.0],
+///         [0.0, 1.0, 0.0],
+///         [1.0, 1.0, 0.0],
+///     ],
+///     faces: vec![vec![0, 1, 2], vec![1, 3, 2]],
+/// };
+///
+/// let (points, (connectivity, cell_types)) = mesh.flatten();
+/// assert_eq!(points.len(), 12);
+/// assert_eq!(connectivity, [0, 1, 2, 1, 3, 2]);
+/// assert_eq!(cell_types, [xdmf::CellType::Triangle; 2]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FaceVertexMesh {
+    /// Coordinates of the vertices
+    pub vertices: Vec<[f64; 3]>,
+    /// Indices of the vertices of each face
+    pub faces: Vec<Vec<usize>>,
+}
+
+impl MeshSource for FaceVertexMesh {
+    fn points(&self) -> impl Iterator<Item = [f64; 3]> {
+        self.vertices.iter().copied()
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (CellType, impl IntoIterator<Item = u64>)> {
+        self.faces.iter().map(|face| {
+            let cell_type = match face.len() {
+                1 => CellType::Vertex,
+                2 => CellType::Edge,
+                3 => CellType::Triangle,
+                4 => CellType::Quadrilateral,
+                num_points => CellType::Polygon(num_points),
+            };
+
+            (cell_type, face.iter().map(|&vertex| vertex as u64))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flatten_face_vertex_mesh() {
+        let mesh = FaceVertexMesh {
+            vertices: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [2.0, 1.0, 0.0]],
+            faces: vec![vec![0, 1, 2, 1, 0], vec![1, 2], vec![2]],
+        };
+
+        let (points, (connectivity, cell_types)) = mesh.flatten();
+        assert_eq!(points, [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 2.0, 1.0, 0.0]);
+        assert_eq!(connectivity, [0, 1, 2, 1, 0, 1, 2, 2]);
+        assert_eq!(
+            cell_types,
+            [CellType::Polygon(5), CellType::Edge, CellType::Vertex]
+        );
+    }
+}
@@ -0,0 +1,332 @@
+//! Conversion of numbers from and to strings.
+//!
+//! All numeric string conversions of the crate are done here, such that readers and writers behave consistently.
+//! The formatting and parsing of Rust is locale-independent, i.e. the decimal separator is always `.`
+//! and no thousands separators are used, as expected by XDMF readers.
+//! Parsing is strict and checks for overflows.
+//!
+//! The numbers are formatted directly into the output buffer, such that no `String` is allocated per number.
+
+use std::{
+    fmt::Write as _,
+    io::{Result as IoResult, Write},
+};
+
+use crate::{XdmfError, XdmfResult};
+
+/// Size of the chunks in which the formatted numbers are passed to a writer.
+const WRITE_CHUNK_SIZE: usize = 1 << 16;
+
+pub(crate) trait FormatNumber {
+    /// Typical length of a formatted number, used to preallocate buffers.
+    const LENGTH_HINT: usize;
+
+    /// Append the formatted number to the buffer.
+    fn push_number(&self, buffer: &mut String);
+
+    fn format_number(&self) -> String {
+        let mut buffer = String::with_capacity(Self::LENGTH_HINT);
+        self.push_number(&mut buffer);
+        buffer
+    }
+}
+
+macro_rules! impl_format_float {
+    ($t:ty, $format:expr, $length_hint:expr) => {
+        impl FormatNumber for $t {
+            const LENGTH_HINT: usize = $length_hint;
+
+            fn push_number(&self, buffer: &mut String) {
+                // writing into a String can not fail
+                let _ = write!(buffer, $format, self);
+            }
+        }
+    };
+}
+
+macro_rules! impl_format_integer {
+    ($($t:ty),*) => {
+        $(
+            impl FormatNumber for $t {
+                const LENGTH_HINT: usize = 8;
+
+                fn push_number(&self, buffer: &mut String) {
+                    buffer.push_str(itoa::Buffer::new().format(*self));
+                }
+            }
+        )*
+    };
+}
+
+// Implement FormatNumber for various types
+// the precision of the floats is taken from meshio
+impl_format_float!(f32, "{:.7e}", 14);
+impl_format_float!(f64, "{:.16e}", 22);
+impl_format_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// Generic formatter for arrays of scalar numeric types
+pub(crate) fn array_to_string_fmt<T>(vec: &[T]) -> String
+where
+    T: FormatNumber,
+{
+    let mut buffer = String::with_capacity(vec.len() * (T::LENGTH_HINT + 1));
+
+    for (i, elem) in vec.iter().enumerate() {
+        if i > 0 {
+            buffer.push(' ');
+        }
+        elem.push_number(&mut buffer);
+    }
+
+    buffer
+}
+
+/// Generic formatter for arrays of either f64 or i32
+pub(crate) fn array_to_writer_fmt<T, W>(vec: &[T], writer: &mut W) -> IoResult<()>
+where
+    T: FormatNumber + Copy,
+    W: Write + ?Sized,
+{
+    iter_to_writer_fmt(vec.iter().copied(), writer).map(|_| ())
+}
+
+/// Generic formatter for iterators of scalar numeric types, returning the number of written values
+pub(crate) fn iter_to_writer_fmt<T, W>(
+    iter: impl Iterator<Item = T>,
+    writer: &mut W,
+) -> IoResult<usize>
+where
+    T: FormatNumber,
+    W: Write + ?Sized,
+{
+    let mut buffer = String::with_capacity(WRITE_CHUNK_SIZE + 2 * T::LENGTH_HINT);
+    let mut num_written = 0;
+
+    for elem in iter {
+        if num_written > 0 {
+            buffer.push(' ');
+        }
+        elem.push_number(&mut buffer);
+        num_written += 1;
+
+        if buffer.len() >= WRITE_CHUNK_SIZE {
+            writer.write_all(buffer.as_bytes())?;
+            buffer.clear();
+        }
+    }
+
+    // final newline
+    buffer.push('\n');
+    writer.write_all(buffer.as_bytes())?;
+
+    Ok(num_written)
+}
+
+/// Parse a time value, which must be a finite float.
+///
+/// Values that overflow the range of `f64` (e.g. "1e400") are rejected, as are "inf" and "NaN".
+pub(crate) fn parse_time(time: &str) -> XdmfResult<f64> {
+    match time.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(XdmfError::Validation(format!(
+            "Time must be a valid float, and not '{time}'"
+        ))),
+    }
+}
+
+/// Format dimensions as whitespace separated list, as used by the `Dimensions` attribute.
+pub(crate) fn format_dimensions(dimensions: &[usize]) -> String {
+    array_to_string_fmt(dimensions)
+}
+
+/// Parse whitespace separated dimensions.
+///
+/// Fails if a dimension is not a valid unsigned integer, or if the total number of entries overflows.
+pub(crate) fn parse_dimensions(dimensions: &str) -> Result<Vec<usize>, String> {
+    let dimensions = dimensions
+        .split_whitespace()
+        .map(|dim| {
+            dim.parse::<usize>()
+                .map_err(|err| format!("Invalid dimension '{dim}': {err}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if num_entries(&dimensions).is_none() {
+        return Err(format!(
+            "Total number of entries of dimensions {dimensions:?} overflows"
+        ));
+    }
+
+    Ok(dimensions)
+}
+
+/// Total number of entries of an array with the given dimensions, `None` in case of an overflow.
+pub(crate) fn num_entries(dimensions: &[usize]) -> Option<usize> {
+    dimensions
+        .iter()
+        .try_fold(1_usize, |acc, &dim| acc.checked_mul(dim))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_number_all_types() {
+        // floating point numbers
+        let num: f32 = 3.141_590_4;
+        assert_eq!(num.format_number(), "3.1415904e0");
+        let num: f64 = 1.234_567_89;
+        assert_eq!(num.format_number(), "1.2345678899999999e0");
+
+        // signed integer types
+        let num: i8 = -5;
+        assert_eq!(num.format_number(), "-5");
+        let num: i16 = -32768;
+        assert_eq!(num.format_number(), "-32768");
+        let num: i32 = 42;
+        assert_eq!(num.format_number(), "42");
+        let num: i64 = -1_234_567_890_123_456_789;
+        assert_eq!(num.format_number(), "-1234567890123456789");
+        let num: isize = -987_654_321;
+        assert_eq!(num.format_number(), "-987654321");
+
+        // unsigned integer types
+        let num: u8 = 255;
+        assert_eq!(num.format_number(), "255");
+        let num: u16 = 65535;
+        assert_eq!(num.format_number(), "65535");
+        let num: u32 = 4_294_967_295;
+        assert_eq!(num.format_number(), "4294967295");
+        let num: u64 = 1000;
+        assert_eq!(num.format_number(), "1000");
+        let num: usize = 123_456_789;
+        assert_eq!(num.format_number(), "123456789");
+
+        // the numbers are appended to the buffer
+        let mut buffer = String::from("x=");
+        (-0.5_f64).push_number(&mut buffer);
+        assert_eq!(buffer, "x=-5.0000000000000000e-1");
+    }
+
+    #[test]
+    fn array_to_string_fmt_multiple_types() {
+        let vec_f64 = vec![1.0, 2.0, 3.0];
+        let result_f64 = array_to_string_fmt(&vec_f64);
+        assert_eq!(
+            result_f64,
+            "1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0"
+        );
+
+        let vec_u64 = vec![1_u64, 2, 3];
+        let result_u64 = array_to_string_fmt(&vec_u64);
+        assert_eq!(result_u64, "1 2 3");
+    }
+
+    #[test]
+    fn array_to_writer_fmt_multiple_types() {
+        let vec_f64 = vec![1.0, 2.0, 3.0];
+        let mut buffer = Vec::new();
+        array_to_writer_fmt(&vec_f64, &mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0\n"
+        );
+
+        let vec_u64 = vec![1_u64, 2, 3];
+        let mut buffer = Vec::new();
+        array_to_writer_fmt(&vec_u64, &mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "1 2 3\n");
+
+        let mut buffer = Vec::new();
+        array_to_writer_fmt::<f64, _>(&[], &mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "\n");
+    }
+
+    #[test]
+    fn iter_to_writer_fmt_chunks() {
+        // more values than fit into one chunk
+        let values: Vec<f64> = (0..10_000).map(|i| f64::from(i) * 0.1 - 500.0).collect();
+
+        let mut buffer = Vec::new();
+        let num_written = iter_to_writer_fmt(values.iter().copied(), &mut buffer).unwrap();
+
+        assert_eq!(num_written, values.len());
+        assert!(buffer.len() > WRITE_CHUNK_SIZE);
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            array_to_string_fmt(&values) + "\n"
+        );
+    }
+
+    #[test]
+    fn parse_time_works() {
+        assert_eq!(parse_time("1.5").unwrap().to_bits(), 1.5_f64.to_bits());
+        assert_eq!(
+            parse_time("-2e-3").unwrap().to_bits(),
+            (-2e-3_f64).to_bits()
+        );
+        assert_eq!(parse_time("10").unwrap().to_bits(), 10.0_f64.to_bits());
+
+        for invalid in ["", "1,5", " 1.0", "abc", "inf", "NaN", "1e400"] {
+            assert_eq!(
+                parse_time(invalid).unwrap_err().to_string(),
+                format!("Time must be a valid float, and not '{invalid}'")
+            );
+        }
+    }
+
+    #[test]
+    fn dimensions_roundtrip() {
+        assert_eq!(format_dimensions(&[2, 3, 4]), "2 3 4");
+        assert_eq!(format_dimensions(&[]), "");
+        assert_eq!(parse_dimensions("2 3 4").unwrap(), vec![2, 3, 4]);
+        assert_eq!(parse_dimensions("  5\n 6 ").unwrap(), vec![5, 6]);
+        assert!(parse_dimensions("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn dimensions_large() {
+        // more entries than fit into i32 or u32, e.g. the connectivity of a gigascale mesh
+        let dimensions = [3_000_000_000, 8];
+        assert_eq!(format_dimensions(&dimensions), "3000000000 8");
+        assert_eq!(
+            parse_dimensions("3000000000 8").unwrap(),
+            dimensions.to_vec()
+        );
+        assert_eq!(
+            parse_dimensions(&format!("{} 1", u64::MAX)).unwrap(),
+            vec![usize::MAX, 1]
+        );
+    }
+
+    #[test]
+    fn parse_dimensions_invalid() {
+        assert_eq!(
+            parse_dimensions("2 -3").unwrap_err(),
+            "Invalid dimension '-3': invalid digit found in string"
+        );
+        assert_eq!(
+            parse_dimensions("2 1.5").unwrap_err(),
+            "Invalid dimension '1.5': invalid digit found in string"
+        );
+        assert_eq!(
+            parse_dimensions("99999999999999999999").unwrap_err(),
+            "Invalid dimension '99999999999999999999': number too large to fit in target type"
+        );
+        assert_eq!(
+            parse_dimensions(&format!("{} 2", usize::MAX)).unwrap_err(),
+            format!(
+                "Total number of entries of dimensions [{}, 2] overflows",
+                usize::MAX
+            )
+        );
+    }
+
+    #[test]
+    fn num_entries_works() {
+        assert_eq!(num_entries(&[2, 3, 4]), Some(24));
+        assert_eq!(num_entries(&[]), Some(1));
+        assert_eq!(num_entries(&[usize::MAX, 2]), None);
+    }
+}
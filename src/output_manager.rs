@@ -0,0 +1,275 @@
+//! This module contains the [`OutputManager`], which drives a [`TimeSeriesWriter`] as configured in the input of a solver.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{
+    DataAttribute, DataMap, DataStorage, MeshSource, RetentionPolicy, StepKey,
+    TimeSeriesDataWriter, TimeSeriesWriter, TimeSeriesWriterBuilder, XdmfError, XdmfResult,
+    xdmf_elements::attribute::Center,
+};
+
+/// Configuration of an [`OutputManager`], e.g. deserialized from the input file of a solver.
+///
+/// All options have defaults, missing options are set to them when deserializing.
+/// The storage is given by its name, in any of the forms accepted by `DataStorage::from_str`, e.g. `"hdf5-single-file"`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    /// Directory of the written files, it is created if it does not exist
+    pub directory: PathBuf,
+    /// Name of the XDMF file without extension, `"output"` by default
+    pub basename: String,
+    /// Data storage of the heavy data, the default of [`TimeSeriesWriterBuilder::storage`] if not set
+    #[serde(deserialize_with = "deserialize_storage")]
+    pub storage: Option<DataStorage>,
+    /// Names of the written fields of the state, all fields if empty
+    pub fields: Vec<String>,
+    /// Every how many steps the fields are written, every step by default
+    pub frequency: usize,
+    /// Number of the most recently written time steps that are kept, all if not set
+    pub retention: Option<usize>,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::new(),
+            basename: "output".to_string(),
+            storage: None,
+            fields: Vec::new(),
+            frequency: 1,
+            retention: None,
+        }
+    }
+}
+
+// the storage is parsed from its name, such that all names of `DataStorage::from_str` are accepted
+fn deserialize_storage<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<DataStorage>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|storage| storage.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+/// State of a simulation, whose fields are written by the [`OutputManager`].
+///
+/// The values can be borrowed from the state, such that they are written without copying them.
+pub trait SimulationState {
+    /// Fields at the points of the mesh
+    fn point_data(&self) -> DataMap<'_>;
+
+    /// Fields at the cells of the mesh, none by default
+    fn cell_data(&self) -> DataMap<'_> {
+        DataMap::new()
+    }
+}
+
+/// Facade writing the output of a simulation as configured by an [`OutputConfig`], such that a solver only calls [`OutputManager::step`].
+///
+/// The manager creates the [`TimeSeriesWriter`] in the configured directory, writes the mesh, and writes the configured fields
+/// of every N-th step. The fields written at the first written step are registered (see [`TimeSeriesDataWriter::register_fields`]),
+/// hence all written steps have the same fields.
+/// ```rust
+/// use xdmf::{
+///     DataAttribute, DataMap, FaceVertexMesh, OutputConfig, OutputManager, SimulationState,
+/// };
+///
+/// struct State {
+///     temperature: Vec<f64>,
+///     pressure: Vec<f64>,
+/// }
+///
+/// impl SimulationState for State {
+///     fn point_data(&self) -> DataMap<'_> {
+///         DataMap::from([
+///             (
+///                 "temperature".to_string(),
+///                 (DataAttribute::Scalar, self.temperature.as_slice().into()),
+///             ),
+///             (
+///                 "pressure".to_string(),
+///                 (DataAttribute::Scalar, self.pressure.as_slice().into()),
+///             ),
+///         ])
+///     }
+/// }
+///
+/// let config = OutputConfig {
+///     directory: "xdmf_output_manager".into(),
+///     storage: Some(xdmf::DataStorage::AsciiInline),
+///     fields: vec!["temperature".to_string()],
+///     frequency: 10,
+///     ..Default::default()
+/// };
+///
+/// let mesh = FaceVertexMesh {
+///     vertices: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+///     faces: vec![vec![0, 1, 2]],
+/// };
+///
+/// let mut manager = OutputManager::new(&config, &mesh).expect("failed to create output manager");
+///
+/// let mut state = State {
+///     temperature: vec![20.0; 3],
+///     pressure: vec![1e5; 3],
+/// };
+/// for i in 0..100 {
+///     state.temperature.iter_mut().for_each(|t| *t += 0.1);
+///
+///     // only the temperature of every 10th step is written
+///     manager
+///         .step(&(f64::from(i) * 0.01), &state)
+///         .expect("failed to write step");
+/// }
+///
+/// manager.close().expect("failed to close output manager");
+/// ```
+pub struct OutputManager {
+    writer: TimeSeriesDataWriter,
+    fields: Vec<String>,
+    frequency: usize,
+    num_steps: usize,
+    // whether the fields were registered, which happens at the first written step
+    registered: bool,
+}
+
+impl OutputManager {
+    /// Create the writer as configured and write the mesh.
+    pub fn new(config: &OutputConfig, mesh: &impl MeshSource) -> XdmfResult<Self> {
+        Self::new_with_builder(config, TimeSeriesWriter::builder(), mesh)
+    }
+
+    /// Create the writer with the given builder, e.g. with further options, to which the configuration is applied, and write the mesh.
+    ///
+    /// See [`OutputManager::new`] for details.
+    pub fn new_with_builder(
+        config: &OutputConfig,
+        builder: TimeSeriesWriterBuilder,
+        mesh: &impl MeshSource,
+    ) -> XdmfResult<Self> {
+        if config.frequency == 0 {
+            return Err(XdmfError::Validation(
+                "Output frequency must be at least 1".into(),
+            ));
+        }
+
+        let mut builder = builder
+            .directory(&config.directory)
+            .basename(&config.basename);
+        if let Some(storage) = config.storage {
+            builder = builder.storage(storage);
+        }
+        if let Some(num_steps) = config.retention {
+            builder = builder.retention_policy(RetentionPolicy::KeepLast(num_steps));
+        }
+
+        Ok(Self {
+            writer: builder.build()?.write_mesh_from(mesh)?,
+            fields: config.fields.clone(),
+            frequency: config.frequency,
+            num_steps: 0,
+            registered: false,
+        })
+    }
+
+    /// Advance the simulation by a step, writing the configured fields of the state if the step is due.
+    ///
+    /// The step is identified by a [`StepKey`], e.g. the time. Returns whether the step was written.
+    pub fn step(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        state: &impl SimulationState,
+    ) -> XdmfResult<bool> {
+        // the counter only advances once the step was handled, a failed step does not shift the frequency
+        if !self.num_steps.is_multiple_of(self.frequency) {
+            self.num_steps += 1;
+            return Ok(false);
+        }
+
+        let point_data = self.selected_fields(state.point_data());
+        let cell_data = self.selected_fields(state.cell_data());
+
+        if !self.registered {
+            if let Some(missing) = self
+                .fields
+                .iter()
+                .find(|name| !point_data.contains_key(*name) && !cell_data.contains_key(*name))
+            {
+                return Err(XdmfError::Validation(format!(
+                    "Field '{missing}' of the output configuration is not provided by the state"
+                )));
+            }
+
+            let fields: Vec<(&str, DataAttribute, Center)> =
+                point_data
+                    .iter()
+                    .map(|(name, (attribute, _))| (name.as_str(), attribute.clone(), Center::Node))
+                    .chain(cell_data.iter().map(|(name, (attribute, _))| {
+                        (name.as_str(), attribute.clone(), Center::Cell)
+                    }))
+                    .collect();
+            self.writer.register_fields(&fields)?;
+            self.registered = true;
+        }
+
+        self.writer.write_data(
+            step,
+            (!point_data.is_empty()).then_some(&point_data),
+            (!cell_data.is_empty()).then_some(&cell_data),
+        )?;
+        self.num_steps += 1;
+
+        Ok(true)
+    }
+
+    /// Close the writer, which writes the XDMF file a last time, see [`TimeSeriesDataWriter::close`].
+    pub fn close(self) -> XdmfResult<()> {
+        self.writer.close()
+    }
+
+    // the configured fields of the given data, all if none are configured
+    fn selected_fields<'a>(&self, data: DataMap<'a>) -> DataMap<'a> {
+        if self.fields.is_empty() {
+            return data;
+        }
+
+        data.into_iter()
+            .filter(|(name, _)| self.fields.contains(name))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_output_config() {
+        let config: OutputConfig = quick_xml::de::from_str(
+            "<output><storage>ascii-inline</storage><fields>pressure</fields><fields>velocity</fields><frequency>5</frequency></output>",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            OutputConfig {
+                storage: Some(DataStorage::AsciiInline),
+                fields: vec!["pressure".to_string(), "velocity".to_string()],
+                frequency: 5,
+                ..Default::default()
+            }
+        );
+
+        let error =
+            quick_xml::de::from_str::<OutputConfig>("<output><storage>netcdf</storage></output>")
+                .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Invalid DataStorage variant: 'netcdf'")
+        );
+    }
+}
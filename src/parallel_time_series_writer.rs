@@ -0,0 +1,783 @@
+//! This module contains functionalities for writing a series of time steps of a domain-decomposed (e.g. MPI-parallel) simulation.
+//!
+//! Each rank writes its partition of the mesh and data to its own files, using a regular [`TimeSeriesWriter`].
+//! The root rank additionally writes a master XDMF file, which combines the partitions of all ranks in a
+//! spatial collection per time step, such that the full domain is visualized.
+//! The partitions are combined with `XInclude`, hence no communication between the ranks is necessary.
+//! Alternatively, with the `hdf5-mpio` feature the heavy data of all ranks can be written collectively into a single
+//! HDF5 file, see `ParallelTimeSeriesWriter::new_collective`.
+//!
+//! Note that the master file references the files of all ranks, which means that it can only be read
+//! once all ranks have finished writing the respective time step.
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    CellType, DataAttribute, DataMap, DataStorage, GHOST_TYPE_NAME, LocalFileSystem, MeshPart,
+    MeshSource, QualityMetric, StepKey, TimeSeriesDataWriter, TimeSeriesWriter,
+    TimeSeriesWriterBuilder, Values, XdmfError, XdmfResult,
+    time_series_writer::{temporal_collection, write_xdmf_file},
+    xdmf_elements::{
+        Information, Xdmf, attribute,
+        data_item::XInclude,
+        grid::{CollectionType, Grid, Time},
+        set::SetType,
+    },
+};
+
+/// Writer for time series data in XDMF format of a domain-decomposed simulation, one instance per rank.
+pub struct ParallelTimeSeriesWriter {
+    master_file_name: PathBuf,
+    rank: usize,
+    num_ranks: usize,
+    data_storage: DataStorage,
+    writer: TimeSeriesWriter,
+}
+
+impl ParallelTimeSeriesWriter {
+    /// Create a new `ParallelTimeSeriesWriter` for the given rank, with the options of the given builder.
+    ///
+    /// The basename of the builder names the master file, the data of each rank is written to files with the suffix `_r<rank>`,
+    /// while rank 0 additionally writes the master file. See [`TimeSeriesWriterBuilder::build`] for details.
+    /// ```rust
+    /// use xdmf::{ParallelTimeSeriesWriter, TimeSeriesWriter};
+    /// let (rank, num_ranks) = (0, 4); // e.g. obtained from MPI
+    /// let xdmf_writer = ParallelTimeSeriesWriter::new(
+    ///     TimeSeriesWriter::builder()
+    ///         .basename("name_parallel_xdmf_file")
+    ///         .storage(xdmf::DataStorage::AsciiInline),
+    ///     rank,
+    ///     num_ranks,
+    /// )
+    /// .expect("failed to create XDMF writer");
+    /// ```
+    pub fn new(
+        builder: TimeSeriesWriterBuilder,
+        rank: usize,
+        num_ranks: usize,
+    ) -> XdmfResult<Self> {
+        check_ranks(rank, num_ranks)?;
+
+        let file_name = builder.file_name()?;
+        let rank_basename = rank_file_name(&file_name, rank)
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        let writer = builder
+            .basename(rank_basename)
+            .build()?
+            .with_name_suffix(rank_suffix(rank));
+
+        Ok(Self {
+            master_file_name: file_name.with_extension("xdmf2"),
+            rank,
+            num_ranks,
+            data_storage: writer.data_storage(),
+            writer,
+        })
+    }
+
+    /// Create a new `ParallelTimeSeriesWriter` that writes the heavy data of all ranks collectively into a single HDF5 file, using parallel HDF5 (MPI-IO).
+    ///
+    /// Rank and number of ranks are taken from the communicator. Each rank still writes its own XDMF file,
+    /// which selects the part of the rank from the datasets containing the values of all ranks.
+    /// As the datasets are created collectively, all ranks must write the same meshes, sets and data in the same order.
+    /// The options are taken from the builder, except for the storage, which is always [`DataStorage::Hdf5SingleFile`].
+    ///
+    /// Requires the `hdf5-mpio` feature and an HDF5 library that was built with MPI support.
+    #[cfg(feature = "hdf5-mpio")]
+    pub fn new_collective(
+        builder: TimeSeriesWriterBuilder,
+        comm: &impl mpi::topology::Communicator,
+    ) -> XdmfResult<Self> {
+        let (rank, num_ranks) = (comm.rank() as usize, comm.size() as usize);
+        check_ranks(rank, num_ranks)?;
+
+        let file_name = builder.file_name()?;
+        let writer = TimeSeriesWriter::new_with_writer(
+            &rank_file_name(&file_name, rank),
+            std::sync::Arc::new(LocalFileSystem),
+            || {
+                Ok(Box::new(
+                    crate::hdf5_writer::SingleFileHdf5Writer::new_collective(&file_name, comm)?,
+                ))
+            },
+        )?;
+        let writer = builder
+            .configure(writer)?
+            .with_name_suffix(rank_suffix(rank));
+
+        Ok(Self {
+            master_file_name: file_name.with_extension("xdmf2"),
+            rank,
+            num_ranks,
+            data_storage: DataStorage::Hdf5SingleFile,
+            writer,
+        })
+    }
+
+    /// Writes the partition of the mesh of this rank, returning a `ParallelTimeSeriesDataWriter` for writing time steps.
+    ///
+    /// See [`TimeSeriesWriter::write_mesh`] for details.
+    pub fn write_mesh(
+        self,
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+    ) -> XdmfResult<ParallelTimeSeriesDataWriter> {
+        let ts_writer = self.writer.write_mesh(points, cells)?;
+
+        Self::create_data_writer(
+            self.master_file_name,
+            self.rank,
+            self.num_ranks,
+            self.data_storage,
+            ts_writer,
+        )
+    }
+
+    /// Writes the partition of the mesh of this rank given by a [`MeshSource`], returning a `ParallelTimeSeriesDataWriter` for writing time steps.
+    ///
+    /// See [`TimeSeriesWriter::write_mesh_from`] for details.
+    pub fn write_mesh_from(
+        self,
+        mesh: &impl MeshSource,
+    ) -> XdmfResult<ParallelTimeSeriesDataWriter> {
+        let ts_writer = self.writer.write_mesh_from(mesh)?;
+
+        Self::create_data_writer(
+            self.master_file_name,
+            self.rank,
+            self.num_ranks,
+            self.data_storage,
+            ts_writer,
+        )
+    }
+
+    /// Writes the partition of the mesh of this rank together with quality metrics of its cells,
+    /// returning a `ParallelTimeSeriesDataWriter` for writing time steps.
+    ///
+    /// See [`TimeSeriesWriter::write_mesh_with_quality`] for details.
+    pub fn write_mesh_with_quality(
+        self,
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+        metrics: &[QualityMetric],
+    ) -> XdmfResult<ParallelTimeSeriesDataWriter> {
+        let ts_writer = self
+            .writer
+            .write_mesh_with_quality(points, cells, metrics)?;
+
+        Self::create_data_writer(
+            self.master_file_name,
+            self.rank,
+            self.num_ranks,
+            self.data_storage,
+            ts_writer,
+        )
+    }
+
+    /// Writes the partition of the mesh of this rank with the coordinates given as separate arrays,
+    /// returning a `ParallelTimeSeriesDataWriter` for writing time steps.
+    ///
+    /// See [`TimeSeriesWriter::write_mesh_soa`] for details.
+    pub fn write_mesh_soa(
+        self,
+        x: &[f64],
+        y: &[f64],
+        z: &[f64],
+        cells: (&[u64], &[CellType]),
+    ) -> XdmfResult<ParallelTimeSeriesDataWriter> {
+        let ts_writer = self.writer.write_mesh_soa(x, y, z, cells)?;
+
+        Self::create_data_writer(
+            self.master_file_name,
+            self.rank,
+            self.num_ranks,
+            self.data_storage,
+            ts_writer,
+        )
+    }
+
+    /// Writes the partition of a point cloud of this rank, returning a `ParallelTimeSeriesDataWriter` for writing time steps.
+    ///
+    /// See [`TimeSeriesWriter::write_points`] for details.
+    pub fn write_points(self, points: &[f64]) -> XdmfResult<ParallelTimeSeriesDataWriter> {
+        self.write_mesh(points, (&[], &[]))
+    }
+
+    /// Writes the partition of a mesh consisting of multiple parts, returning a `ParallelTimeSeriesDataWriter` for writing time steps.
+    ///
+    /// See [`TimeSeriesWriter::write_mesh_tree`] for details.
+    pub fn write_mesh_tree(self, parts: &[MeshPart]) -> XdmfResult<ParallelTimeSeriesDataWriter> {
+        let ts_writer = self.writer.write_mesh_tree(parts)?;
+
+        Self::create_data_writer(
+            self.master_file_name,
+            self.rank,
+            self.num_ranks,
+            self.data_storage,
+            ts_writer,
+        )
+    }
+
+    /// Writes the partition of a mesh whose cells are assigned to parts, returning a `ParallelTimeSeriesDataWriter` for writing time steps.
+    ///
+    /// See [`TimeSeriesWriter::write_mesh_partitioned`] for details.
+    pub fn write_mesh_partitioned(
+        self,
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+        part_ids: &[u64],
+    ) -> XdmfResult<ParallelTimeSeriesDataWriter> {
+        let ts_writer = self
+            .writer
+            .write_mesh_partitioned(points, cells, part_ids)?;
+
+        Self::create_data_writer(
+            self.master_file_name,
+            self.rank,
+            self.num_ranks,
+            self.data_storage,
+            ts_writer,
+        )
+    }
+
+    fn create_data_writer(
+        master_file_name: PathBuf,
+        rank: usize,
+        num_ranks: usize,
+        data_storage: DataStorage,
+        ts_writer: TimeSeriesDataWriter,
+    ) -> XdmfResult<ParallelTimeSeriesDataWriter> {
+        let parallel_writer = ParallelTimeSeriesDataWriter {
+            master_file_name,
+            rank,
+            num_ranks,
+            data_storage,
+            writer: ts_writer,
+            steps: Vec::new(),
+            ghost_points: None,
+            ghost_cells: None,
+        };
+
+        parallel_writer.write_master()?;
+
+        Ok(parallel_writer)
+    }
+}
+
+/// Writer for time series data of a domain-decomposed simulation. Can be used after writing the mesh with `ParallelTimeSeriesWriter::write_mesh`.
+pub struct ParallelTimeSeriesDataWriter {
+    master_file_name: PathBuf,
+    rank: usize,
+    num_ranks: usize,
+    data_storage: DataStorage,
+    writer: TimeSeriesDataWriter,
+    // label and time value of the written steps
+    steps: Vec<(String, String)>,
+    // ghost type of the points and cells of this rank, added to the data of each time step
+    ghost_points: Option<Values<'static>>,
+    ghost_cells: Option<Values<'static>>,
+}
+
+impl ParallelTimeSeriesDataWriter {
+    /// Write point and cell data of the partition of this rank for a specific time step.
+    ///
+    /// All ranks must write the same time steps. See [`TimeSeriesDataWriter::write_data`] for details.
+    pub fn write_data(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        point_data: Option<&DataMap>,
+        cell_data: Option<&DataMap>,
+    ) -> XdmfResult<()> {
+        let resolved_step = self.writer.resolve_step(step, self.steps.len())?;
+        let point_data_with_ghosts =
+            with_ghost_type(point_data, self.ghost_points.as_ref(), "point")?;
+        let cell_data_with_ghosts = with_ghost_type(cell_data, self.ghost_cells.as_ref(), "cell")?;
+        self.writer.write_data(
+            step,
+            point_data_with_ghosts.as_ref().or(point_data),
+            cell_data_with_ghosts.as_ref().or(cell_data),
+        )?;
+        self.steps.push(resolved_step);
+
+        self.write_master_with_policy()
+    }
+
+    /// Write point and cell data of the partition of this rank for a time step, replacing the data if the time step has already been written.
+    ///
+    /// See [`TimeSeriesDataWriter::write_data_overwrite`] for details.
+    pub fn write_data_overwrite(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        point_data: Option<&DataMap>,
+        cell_data: Option<&DataMap>,
+    ) -> XdmfResult<()> {
+        let resolved_step = self.writer.resolve_step(step, self.steps.len())?;
+        let point_data_with_ghosts =
+            with_ghost_type(point_data, self.ghost_points.as_ref(), "point")?;
+        let cell_data_with_ghosts = with_ghost_type(cell_data, self.ghost_cells.as_ref(), "cell")?;
+        self.writer.write_data_overwrite(
+            step,
+            point_data_with_ghosts.as_ref().or(point_data),
+            cell_data_with_ghosts.as_ref().or(cell_data),
+        )?;
+
+        self.add_step(resolved_step)
+    }
+
+    /// Mark points of the partition of this rank as ghosts, i.e. as duplicates of points that are owned by another rank.
+    ///
+    /// The ghost type (see [`Values::ghost_type`]) is then added as point-data [`GHOST_TYPE_NAME`] to each time step
+    /// written with `write_data` or `write_data_overwrite`, such that Paraview hides the duplicates when combining the partitions.
+    /// After `write_mesh_update` the ghosts must be set again.
+    /// ```rust
+    /// use xdmf::{ParallelTimeSeriesWriter, TimeSeriesWriter};
+    /// let builder = TimeSeriesWriter::builder()
+    ///     .basename("xdmf_set_ghost_cells")
+    ///     .storage(xdmf::DataStorage::AsciiInline);
+    /// let mut xdmf_writer = ParallelTimeSeriesWriter::new(builder, 1, 2)
+    ///     .expect("failed to create XDMF writer")
+    ///     .write_mesh(
+    ///         &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 2.0, 0.0, 0.0],
+    ///         (&[0, 1, 1, 2], &[xdmf::CellType::Edge; 2]),
+    ///     )
+    ///     .expect("failed to write mesh");
+    ///
+    /// // the first cell is owned by the neighboring rank
+    /// xdmf_writer
+    ///     .set_ghost_cells(&[0])
+    ///     .expect("failed to set ghost cells");
+    ///
+    /// let cell_data = vec![(
+    ///     "pressure".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![1.0, 2.0].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// // the ghost type is added to the cell data
+    /// xdmf_writer
+    ///     .write_data("0.0", None, Some(&cell_data))
+    ///     .expect("failed to write data");
+    /// ```
+    pub fn set_ghost_points(&mut self, ghost_indices: &[u64]) -> XdmfResult<()> {
+        self.ghost_points = Some(ghost_type(
+            ghost_indices,
+            self.writer.num_points(),
+            "point",
+        )?);
+        Ok(())
+    }
+
+    /// Mark cells of the partition of this rank as ghosts, i.e. as duplicates of cells that are owned by another rank.
+    ///
+    /// See [`ParallelTimeSeriesDataWriter::set_ghost_points`] for details.
+    pub fn set_ghost_cells(&mut self, ghost_indices: &[u64]) -> XdmfResult<()> {
+        self.ghost_cells = Some(ghost_type(ghost_indices, self.writer.num_cells(), "cell")?);
+        Ok(())
+    }
+
+    /// Write point data of the partition of this rank for a specific time step, with the values provided by an iterator.
+    ///
+    /// See [`TimeSeriesDataWriter::write_point_data_streamed`] for details.
+    pub fn write_point_data_streamed(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        name: &str,
+        data_attribute: DataAttribute,
+        values: impl IntoIterator<Item = f64>,
+        len: usize,
+    ) -> XdmfResult<()> {
+        let resolved_step = self.writer.resolve_step(step, self.steps.len())?;
+        self.writer
+            .write_point_data_streamed(step, name, data_attribute, values, len)?;
+
+        self.add_step(resolved_step)
+    }
+
+    /// Write cell data of the partition of this rank for a specific time step, with the values provided by an iterator.
+    ///
+    /// See [`TimeSeriesDataWriter::write_point_data_streamed`] for details.
+    pub fn write_cell_data_streamed(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        name: &str,
+        data_attribute: DataAttribute,
+        values: impl IntoIterator<Item = f64>,
+        len: usize,
+    ) -> XdmfResult<()> {
+        let resolved_step = self.writer.resolve_step(step, self.steps.len())?;
+        self.writer
+            .write_cell_data_streamed(step, name, data_attribute, values, len)?;
+
+        self.add_step(resolved_step)
+    }
+
+    // streamed data can be added to an existing step, only new steps are added to the master file
+    fn add_step(&mut self, resolved_step: (String, String)) -> XdmfResult<()> {
+        if self
+            .steps
+            .iter()
+            .any(|(label, _)| *label == resolved_step.0)
+        {
+            return Ok(());
+        }
+
+        self.steps.push(resolved_step);
+        self.write_master_with_policy()
+    }
+
+    // the master file is written together with the file of this rank, as determined by the flush policy
+    fn write_master_with_policy(&self) -> XdmfResult<()> {
+        if self.writer.is_xml_pending() {
+            return Ok(());
+        }
+
+        self.write_master()
+    }
+
+    /// Write the XDMF files of this rank and the master file now, independent of the [`FlushPolicy`](crate::FlushPolicy).
+    ///
+    /// See [`TimeSeriesDataWriter::flush_xml`] for details.
+    pub fn flush_xml(&mut self) -> XdmfResult<()> {
+        self.writer.flush_xml()?;
+        self.write_master()
+    }
+
+    /// Finish writing, equivalent to [`ParallelTimeSeriesDataWriter::close`].
+    pub fn finalize(self) -> XdmfResult<()> {
+        self.close()
+    }
+
+    /// Close the writer of this rank, the master file is written a last time.
+    ///
+    /// See [`TimeSeriesDataWriter::close`] for details.
+    pub fn close(mut self) -> XdmfResult<()> {
+        self.writer.close_in_place()?;
+        self.write_master()
+    }
+
+    /// Register the fields that are written in each time step.
+    ///
+    /// All ranks must register the same fields. See [`TimeSeriesDataWriter::register_fields`] for details.
+    pub fn register_fields(
+        &mut self,
+        fields: &[(&str, DataAttribute, attribute::Center)],
+    ) -> XdmfResult<()> {
+        self.writer.register_fields(fields)
+    }
+
+    /// Register a field whose values are computed by the reader from other fields.
+    ///
+    /// All ranks must register the same derived fields. See [`TimeSeriesDataWriter::register_derived_field`] for details.
+    pub fn register_derived_field(
+        &mut self,
+        name: &str,
+        data_attribute: DataAttribute,
+        center: attribute::Center,
+        function: &str,
+        fields: &[&str],
+    ) -> XdmfResult<()> {
+        self.writer
+            .register_derived_field(name, data_attribute, center, function, fields)
+    }
+
+    /// Write point and cell data of the partition of this rank that is constant in time, e.g. material properties.
+    ///
+    /// See [`TimeSeriesDataWriter::write_static_data`] for details.
+    pub fn write_static_data(
+        &mut self,
+        point_data: Option<&DataMap>,
+        cell_data: Option<&DataMap>,
+    ) -> XdmfResult<()> {
+        self.writer.write_static_data(point_data, cell_data)
+    }
+
+    /// Write a set of points or cells of the partition of this rank, e.g. the nodes of a boundary condition.
+    ///
+    /// The indices refer to the partition of this rank. See [`TimeSeriesDataWriter::write_set`] for details.
+    pub fn write_set(&mut self, name: &str, set_type: SetType, indices: &[u64]) -> XdmfResult<()> {
+        self.writer.write_set(name, set_type, indices)
+    }
+
+    /// Write data of a time step that is defined only on the entities of a set of the partition of this rank.
+    ///
+    /// See [`TimeSeriesDataWriter::write_set_data`] for details.
+    pub fn write_set_data(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        set_name: &str,
+        set_data: &DataMap,
+    ) -> XdmfResult<()> {
+        self.writer.write_set_data(step, set_name, set_data)
+    }
+
+    /// Update the partition of the mesh of this rank.
+    ///
+    /// See [`TimeSeriesDataWriter::write_mesh_update`] for details.
+    pub fn write_mesh_update(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+    ) -> XdmfResult<()> {
+        self.writer.write_mesh_update(step, points, cells)?;
+
+        // the ghosts refer to the previous mesh
+        self.ghost_points = None;
+        self.ghost_cells = None;
+
+        Ok(())
+    }
+
+    /// The rank of this writer.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// The total number of ranks.
+    pub fn num_ranks(&self) -> usize {
+        self.num_ranks
+    }
+
+    /// Writes the master file, only done on the root rank.
+    fn write_master(&self) -> XdmfResult<()> {
+        if self.rank != 0 {
+            return Ok(());
+        }
+
+        let rank_files = (0..self.num_ranks)
+            .map(|rank| {
+                rank_file_name(&self.master_file_name, rank)
+                    .with_extension("xdmf2")
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .ok_or_else(|| {
+                        XdmfError::Validation("Master file name must have a valid file name".into())
+                    })
+            })
+            .collect::<XdmfResult<Vec<_>>>()?;
+
+        // includes the grids selected by the xpath from all ranks
+        let include_grids = |xpath: &str| {
+            rank_files
+                .iter()
+                .map(|file| XInclude::new_xpointer(file, xpath))
+                .collect::<Vec<_>>()
+        };
+
+        let grid_to_write = if self.steps.is_empty() {
+            let mut spatial_grid =
+                Grid::new_collection("partitions", CollectionType::Spatial, None);
+            spatial_grid.includes = Some(include_grids("/Xdmf/Domain/Grid"));
+            spatial_grid
+        } else {
+            let time_grids = self
+                .steps
+                .iter()
+                .map(|(label, time)| {
+                    let mut spatial_grid = Grid::new_collection(
+                        format!("partitions-t{label}"),
+                        CollectionType::Spatial,
+                        None,
+                    );
+                    spatial_grid.time = Some(Time::new(time));
+                    spatial_grid.includes = Some(include_grids(&format!(
+                        "/Xdmf/Domain/Grid/Grid[@Name='time_series-t{label}']"
+                    )));
+                    spatial_grid
+                })
+                .collect();
+
+            temporal_collection("time_series", time_grids, self.writer.time_list())
+        };
+
+        let mut xdmf = Xdmf {
+            information: vec![
+                Information::new("data_storage", format!("{:?}", self.data_storage)),
+                Information::new("num_ranks", self.num_ranks),
+                Information::new("version", env!("CARGO_PKG_VERSION")),
+            ],
+            ..Default::default()
+        };
+        xdmf.information
+            .extend(self.writer.information().iter().cloned());
+        xdmf.domains[0].grids.push(grid_to_write);
+        xdmf.domains[0].includes = include_grids("/Xdmf/Domain/DataItem");
+
+        write_xdmf_file(
+            &LocalFileSystem,
+            &xdmf,
+            self.writer.xml_header(),
+            self.writer.progress_callback(),
+            &self.master_file_name,
+        )
+    }
+}
+
+// the master file is completed when the writer goes out of scope without being closed,
+// the file of this rank is completed by the wrapped writer
+impl Drop for ParallelTimeSeriesDataWriter {
+    fn drop(&mut self) {
+        if self.writer.is_xml_pending()
+            && let Err(err) = self.flush_xml()
+        {
+            log::error!(
+                "Closing the XDMF writer of '{}' failed: {err}",
+                self.master_file_name.display()
+            );
+        }
+    }
+}
+
+fn check_ranks(rank: usize, num_ranks: usize) -> XdmfResult<()> {
+    if num_ranks == 0 {
+        return Err(XdmfError::Validation(
+            "Number of ranks must be larger than 0".into(),
+        ));
+    }
+
+    if rank >= num_ranks {
+        return Err(XdmfError::Validation(format!(
+            "Rank {rank} is out of bounds for {num_ranks} ranks"
+        )));
+    }
+
+    Ok(())
+}
+
+fn rank_suffix(rank: usize) -> String {
+    format!("_r{rank}")
+}
+
+// ghost type of the entities of the partition, marking the given indices as ghosts
+fn ghost_type(
+    ghost_indices: &[u64],
+    num_entities: usize,
+    entity_label: &str,
+) -> XdmfResult<Values<'static>> {
+    let mut is_ghost = vec![false; num_entities];
+
+    for &index in ghost_indices {
+        let ghost = usize::try_from(index)
+            .ok()
+            .and_then(|index| is_ghost.get_mut(index))
+            .ok_or_else(|| {
+                XdmfError::Validation(format!(
+                    "Ghost {entity_label} index {index} is out of bounds for {num_entities} {entity_label}s"
+                ))
+            })?;
+        *ghost = true;
+    }
+
+    Ok(Values::ghost_type(&is_ghost))
+}
+
+// the data with the ghost type added, None if no ghosts are set
+// the values are borrowed from the data
+fn with_ghost_type<'d>(
+    data: Option<&'d DataMap>,
+    ghost_type: Option<&'d Values>,
+    entity_label: &str,
+) -> XdmfResult<Option<DataMap<'d>>> {
+    let Some(ghost_type) = ghost_type else {
+        return Ok(None);
+    };
+
+    if data.is_some_and(|data| data.contains_key(GHOST_TYPE_NAME)) {
+        return Err(XdmfError::Validation(format!(
+            "The ghost type of the {entity_label}s is added automatically, hence {entity_label}-data '{GHOST_TYPE_NAME}' must not be given"
+        )));
+    }
+
+    let mut data_with_ghost_type: DataMap = data
+        .into_iter()
+        .flatten()
+        .map(|(name, (data_attribute, values))| {
+            (
+                name.clone(),
+                (data_attribute.clone(), values.slice(0..values.len())),
+            )
+        })
+        .collect();
+
+    data_with_ghost_type.insert(
+        GHOST_TYPE_NAME.to_string(),
+        (DataAttribute::Scalar, ghost_type.slice(0..ghost_type.len())),
+    );
+
+    Ok(Some(data_with_ghost_type))
+}
+
+// file name of the files of a rank, without extension
+fn rank_file_name(file_name: &Path, rank: usize) -> PathBuf {
+    let stem = file_name
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    file_name.with_file_name(format!("{stem}{}", rank_suffix(rank)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_file_name_works() {
+        assert_eq!(
+            rank_file_name(Path::new("out/case.xdmf2"), 3),
+            PathBuf::from("out/case_r3")
+        );
+        assert_eq!(
+            rank_file_name(Path::new("case"), 0),
+            PathBuf::from("case_r0")
+        );
+    }
+
+    #[test]
+    fn parallel_writer_invalid_rank() {
+        let tmp_dir = temp_dir::TempDir::new().unwrap();
+        let builder = TimeSeriesWriter::builder()
+            .directory(tmp_dir.path())
+            .basename("parallel")
+            .storage(DataStorage::AsciiInline);
+
+        let res = ParallelTimeSeriesWriter::new(builder.clone(), 2, 2);
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "Rank 2 is out of bounds for 2 ranks"
+        );
+
+        let res = ParallelTimeSeriesWriter::new(builder, 0, 0);
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "Number of ranks must be larger than 0"
+        );
+    }
+
+    #[test]
+    fn parallel_writer_files() {
+        let tmp_dir = temp_dir::TempDir::new().unwrap();
+        let file_name = tmp_dir.path().join("parallel");
+
+        for rank in 0..2 {
+            let builder = TimeSeriesWriter::builder()
+                .directory(tmp_dir.path())
+                .basename("parallel")
+                .storage(DataStorage::AsciiInline);
+
+            ParallelTimeSeriesWriter::new(builder, rank, 2)
+                .unwrap()
+                .write_mesh(&[0.0; 6], (&[0, 1], &[CellType::Edge]))
+                .unwrap();
+        }
+
+        assert!(file_name.with_extension("xdmf2").exists());
+        assert!(tmp_dir.path().join("parallel_r0.xdmf2").exists());
+        assert!(tmp_dir.path().join("parallel_r1.xdmf2").exists());
+    }
+}
@@ -0,0 +1,291 @@
+//! This module contains the reporting of the progress of writing, e.g. to monitor the I/O of a simulation on a cluster.
+
+use std::{
+    io::{Result as IoResult, Write},
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    DataStorage, DataWriter, FieldData, Hdf5Options, IndexPrecision, PathPolicy, Values,
+    XdmfResult,
+    xdmf_elements::{
+        attribute,
+        data_item::{DataContent, DataItem, Encoding, Format},
+        dimensions::Dimensions,
+    },
+};
+
+/// Callback that is called after each write, see [`TimeSeriesWriter::with_progress_callback`](crate::TimeSeriesWriter::with_progress_callback).
+pub(crate) type ProgressCallback = Arc<dyn Fn(&WriteEvent) + Send + Sync>;
+
+/// Kind of a write that is reported to the progress callback.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WriteOperation {
+    /// Heavy data of a mesh, i.e. its coordinates and connectivity
+    Mesh,
+    /// Heavy data of the indices of a set or subset
+    Set,
+    /// Heavy data of a field, e.g. an attribute of a time step
+    Data,
+    /// The XDMF file
+    Xml,
+}
+
+/// A completed write, reported to the progress callback.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WriteEvent {
+    /// Kind of the write
+    pub operation: WriteOperation,
+    /// Name of the written data, e.g. the name of the heavy data of a field, or the path of the XDMF file
+    pub name: String,
+    /// Number of written bytes, for the heavy data the size of the values in memory (before formatting or compression)
+    pub num_bytes: usize,
+    /// Time it took to write, for fields that are written concurrently the time it took to write all of them
+    pub duration: Duration,
+}
+
+/// Writer of the heavy data that reports each write to the progress callback.
+pub(crate) struct ProgressWriter {
+    writer: Box<dyn DataWriter>,
+    callback: ProgressCallback,
+}
+
+impl ProgressWriter {
+    pub(crate) fn new(writer: Box<dyn DataWriter>, callback: ProgressCallback) -> Self {
+        Self { writer, callback }
+    }
+
+    fn report(&self, operation: WriteOperation, name: &str, num_bytes: usize, start: Instant) {
+        (self.callback)(&WriteEvent {
+            operation,
+            name: name.to_string(),
+            num_bytes,
+            duration: start.elapsed(),
+        });
+    }
+}
+
+// number of bytes of the values in memory
+fn values_num_bytes(values: &Values) -> usize {
+    values.len() * usize::from(values.precision())
+}
+
+impl DataWriter for ProgressWriter {
+    fn format(&self) -> Format {
+        self.writer.format()
+    }
+
+    fn encoding(&self) -> Option<Encoding> {
+        self.writer.encoding()
+    }
+
+    fn data_storage(&self) -> DataStorage {
+        self.writer.data_storage()
+    }
+
+    fn write_mesh(
+        &mut self,
+        points: &[f64],
+        cells: &[u64],
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        let start = Instant::now();
+        let mesh = self.writer.write_mesh(points, cells)?;
+        self.report(
+            WriteOperation::Mesh,
+            "mesh",
+            size_of_val(points) + size_of_val(cells),
+            start,
+        );
+
+        Ok(mesh)
+    }
+
+    fn write_named_mesh(
+        &mut self,
+        name: &str,
+        points: &[f64],
+        cells: &[u64],
+    ) -> XdmfResult<(DataContent, DataContent)> {
+        let start = Instant::now();
+        let mesh = self.writer.write_named_mesh(name, points, cells)?;
+        self.report(
+            WriteOperation::Mesh,
+            name,
+            size_of_val(points) + size_of_val(cells),
+            start,
+        );
+
+        Ok(mesh)
+    }
+
+    fn write_mesh_soa(
+        &mut self,
+        name: Option<&str>,
+        coordinates: [&[f64]; 3],
+        cells: &[u64],
+    ) -> XdmfResult<([DataContent; 3], DataContent)> {
+        let start = Instant::now();
+        let mesh = self.writer.write_mesh_soa(name, coordinates, cells)?;
+        let num_bytes =
+            coordinates.iter().map(|x| size_of_val(*x)).sum::<usize>() + size_of_val(cells);
+        self.report(
+            WriteOperation::Mesh,
+            name.unwrap_or("mesh"),
+            num_bytes,
+            start,
+        );
+
+        Ok(mesh)
+    }
+
+    fn write_set(&mut self, name: &str, indices: &[u64]) -> XdmfResult<DataContent> {
+        let start = Instant::now();
+        let set = self.writer.write_set(name, indices)?;
+        self.report(WriteOperation::Set, name, size_of_val(indices), start);
+
+        Ok(set)
+    }
+
+    fn write_data(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        data: &Values,
+    ) -> XdmfResult<DataContent> {
+        let start = Instant::now();
+        let content = self.writer.write_data(name, center, data)?;
+        self.report(WriteOperation::Data, name, values_num_bytes(data), start);
+
+        Ok(content)
+    }
+
+    fn write_data_streamed(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        values: &mut dyn Iterator<Item = f64>,
+        len: usize,
+    ) -> XdmfResult<DataContent> {
+        let start = Instant::now();
+        let content = self.writer.write_data_streamed(name, center, values, len)?;
+        self.report(WriteOperation::Data, name, len * size_of::<f64>(), start);
+
+        Ok(content)
+    }
+
+    fn write_shaped_data(
+        &mut self,
+        name: &str,
+        center: attribute::Center,
+        data: &Values,
+        dimensions: &Dimensions,
+    ) -> XdmfResult<DataContent> {
+        let start = Instant::now();
+        let content = self
+            .writer
+            .write_shaped_data(name, center, data, dimensions)?;
+        self.report(WriteOperation::Data, name, values_num_bytes(data), start);
+
+        Ok(content)
+    }
+
+    fn write_fields(&mut self, fields: &[FieldData<'_>]) -> XdmfResult<Vec<DataContent>> {
+        // the fields may be written concurrently, hence only the time of writing all of them is known
+        let start = Instant::now();
+        let contents = self.writer.write_fields(fields)?;
+        let duration = start.elapsed();
+
+        for (name, _, data, _) in fields {
+            (self.callback)(&WriteEvent {
+                operation: WriteOperation::Data,
+                name: name.to_string(),
+                num_bytes: values_num_bytes(data),
+                duration,
+            });
+        }
+
+        Ok(contents)
+    }
+
+    fn write_data_initialize(&mut self, time: &str) -> XdmfResult<()> {
+        self.writer.write_data_initialize(time)
+    }
+
+    fn write_data_finalize(&mut self) -> XdmfResult<()> {
+        self.writer.write_data_finalize()
+    }
+
+    fn wrap_data_item(&self, data_item: DataItem) -> DataItem {
+        self.writer.wrap_data_item(data_item)
+    }
+
+    fn set_inline_size_limit(&mut self, size_limit: usize) {
+        self.writer.set_inline_size_limit(size_limit);
+    }
+
+    fn set_path_policy(&mut self, path_policy: PathPolicy) {
+        self.writer.set_path_policy(path_policy);
+    }
+
+    fn heavy_data_path(&self) -> Option<&Path> {
+        self.writer.heavy_data_path()
+    }
+
+    fn index_precision(&self) -> IndexPrecision {
+        self.writer.index_precision()
+    }
+
+    fn set_index_precision(&mut self, index_precision: IndexPrecision) {
+        self.writer.set_index_precision(index_precision);
+    }
+
+    fn set_hdf5_options(&mut self, options: &Hdf5Options) -> XdmfResult<()> {
+        self.writer.set_hdf5_options(options)
+    }
+
+    fn remove_step(&mut self, time: &str) -> XdmfResult<()> {
+        self.writer.remove_step(time)
+    }
+
+    fn flush(&mut self) -> XdmfResult<()> {
+        self.writer.flush()
+    }
+
+    fn close(&mut self) -> XdmfResult<()> {
+        self.writer.close()
+    }
+}
+
+/// Counts the bytes written to the wrapped writer, e.g. the size of the XDMF file.
+pub(crate) struct ByteCounter<W> {
+    writer: W,
+    num_bytes: usize,
+}
+
+impl<W: Write> ByteCounter<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        Self {
+            writer,
+            num_bytes: 0,
+        }
+    }
+
+    pub(crate) fn num_bytes(&self) -> usize {
+        self.num_bytes
+    }
+}
+
+impl<W: Write> Write for ByteCounter<W> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        let num_written = self.writer.write(buf)?;
+        self.num_bytes += num_written;
+
+        Ok(num_written)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,321 @@
+//! Python bindings of the [`TimeSeriesWriter`], enabled with the `python` feature.
+//!
+//! The extension module is built with [maturin](https://www.maturin.rs), e.g. `maturin develop --features python`.
+//! The points and the fields are given as numpy arrays, which are written without copying them if they are C-contiguous.
+//! The cells are given like for meshio, as list of the name of the cell type and the array of the points of its cells.
+//! ```python
+//! import numpy as np
+//! import xdmf
+//!
+//! points = np.array([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]])
+//! cells = [("triangle", np.array([[0, 1, 2]]))]
+//!
+//! with xdmf.TimeSeriesWriter("result", "hdf5-single-file").write_mesh(points, cells) as writer:
+//!     for time in [0.0, 0.5]:
+//!         writer.write_data(time, point_data={"temperature": np.full(3, 20.0)})
+//! ```
+
+use std::{path::PathBuf, sync::Mutex};
+
+use numpy::{PyReadonlyArray2, PyReadonlyArrayDyn, PyUntypedArrayMethods};
+use pyo3::{
+    exceptions::{PyIOError, PyNotImplementedError, PyRuntimeError, PyTypeError, PyValueError},
+    prelude::*,
+    types::PyDict,
+};
+
+use crate::{
+    CellType, DataAttribute, DataMap, DataStorage, TimeSeriesDataWriter, TimeSeriesWriter, Values,
+    XdmfError,
+};
+
+// the errors of the writer are raised as the corresponding Python exceptions, e.g. invalid input as `ValueError`
+fn to_py_err(error: XdmfError) -> PyErr {
+    match error {
+        XdmfError::Io(error) => PyIOError::new_err(error.to_string()),
+        XdmfError::Validation(message) => PyValueError::new_err(message),
+        XdmfError::Unsupported(message) => PyNotImplementedError::new_err(message),
+        error => PyRuntimeError::new_err(error.to_string()),
+    }
+}
+
+/// Writer of the mesh, returning the writer of the time steps, see [`TimeSeriesWriter`].
+#[pyclass(name = "TimeSeriesWriter")]
+struct PyTimeSeriesWriter {
+    // taken when the mesh is written, the mutex makes the writer shareable between threads as required by Python
+    writer: Mutex<Option<TimeSeriesWriter>>,
+}
+
+#[pymethods]
+impl PyTimeSeriesWriter {
+    /// Create a writer of the XDMF file with the given name, the storage is given by its name, e.g. `"ascii"`.
+    #[new]
+    #[pyo3(signature = (file_name, storage = "hdf5-single-file"))]
+    fn new(file_name: PathBuf, storage: &str) -> PyResult<Self> {
+        let data_storage: DataStorage = storage.parse().map_err(PyValueError::new_err)?;
+
+        Ok(Self {
+            writer: Mutex::new(Some(
+                TimeSeriesWriter::new(file_name, data_storage).map_err(to_py_err)?,
+            )),
+        })
+    }
+
+    /// Write the mesh, given by the points as array of shape (n, 3) and the cells as list of (cell type, connectivity).
+    fn write_mesh(
+        &mut self,
+        points: PyReadonlyArray2<'_, f64>,
+        cells: Vec<(String, Bound<'_, PyAny>)>,
+    ) -> PyResult<PyTimeSeriesDataWriter> {
+        if points.shape()[1] != 3 {
+            return Err(PyValueError::new_err(format!(
+                "Points must have the shape (n, 3), but have {:?}",
+                points.shape()
+            )));
+        }
+        let points = points
+            .as_slice()
+            .map_err(|_err| PyValueError::new_err("Points must be C-contiguous"))?;
+
+        let (connectivity, cell_types) = flatten_cells(&cells)?;
+
+        let writer = unpoisoned(&mut self.writer)?
+            .take()
+            .ok_or_else(|| PyRuntimeError::new_err("The mesh has already been written"))?;
+
+        Ok(PyTimeSeriesDataWriter {
+            writer: Mutex::new(Some(
+                writer
+                    .write_mesh(points, (&connectivity, &cell_types))
+                    .map_err(to_py_err)?,
+            )),
+        })
+    }
+}
+
+/// Writer of the time steps, see [`TimeSeriesDataWriter`]. Closes the writer when used as context manager.
+#[pyclass(name = "TimeSeriesDataWriter")]
+struct PyTimeSeriesDataWriter {
+    // taken when the writer is closed
+    writer: Mutex<Option<TimeSeriesDataWriter>>,
+}
+
+#[pymethods]
+impl PyTimeSeriesDataWriter {
+    /// Write the fields of a time step, given as dictionaries of the names of the fields and their arrays.
+    ///
+    /// The first dimension of an array is the number of points or cells, the others define the `DataAttribute`,
+    /// e.g. (n, 3) for a vector.
+    #[pyo3(signature = (time, point_data = None, cell_data = None))]
+    fn write_data(
+        &mut self,
+        time: f64,
+        point_data: Option<Bound<'_, PyDict>>,
+        cell_data: Option<Bound<'_, PyDict>>,
+    ) -> PyResult<()> {
+        let point_arrays = point_data.as_ref().map(extract_arrays).transpose()?;
+        let cell_arrays = cell_data.as_ref().map(extract_arrays).transpose()?;
+
+        let point_data = point_arrays.as_deref().map(data_map).transpose()?;
+        let cell_data = cell_arrays.as_deref().map(data_map).transpose()?;
+
+        open_writer(&mut self.writer)?
+            .write_data(&time, point_data.as_ref(), cell_data.as_ref())
+            .map_err(to_py_err)
+    }
+
+    /// Write the XDMF file now, see [`TimeSeriesDataWriter::flush_xml`].
+    fn flush_xml(&mut self) -> PyResult<()> {
+        open_writer(&mut self.writer)?
+            .flush_xml()
+            .map_err(to_py_err)
+    }
+
+    /// Close the writer, which writes the XDMF file a last time. Closing it again has no effect.
+    fn close(&mut self) -> PyResult<()> {
+        unpoisoned(&mut self.writer)?
+            .take()
+            .map_or(Ok(()), TimeSeriesDataWriter::close)
+            .map_err(to_py_err)
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, _args: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.close()
+    }
+}
+
+fn open_writer(
+    writer: &mut Mutex<Option<TimeSeriesDataWriter>>,
+) -> PyResult<&mut TimeSeriesDataWriter> {
+    unpoisoned(writer)?
+        .as_mut()
+        .ok_or_else(|| PyRuntimeError::new_err("The writer is closed"))
+}
+
+// the writer can not be used anymore after a panic while writing
+fn unpoisoned<T>(writer: &mut Mutex<T>) -> PyResult<&mut T> {
+    writer
+        .get_mut()
+        .map_err(|_err| PyRuntimeError::new_err("The writer is unusable after a panic"))
+}
+
+// the connectivity of the blocks of cells of the same type, named like in meshio
+fn flatten_cells(cells: &[(String, Bound<'_, PyAny>)]) -> PyResult<(Vec<u64>, Vec<CellType>)> {
+    let mut connectivity = Vec::new();
+    let mut cell_types = Vec::new();
+
+    for (name, block) in cells {
+        let block: PyReadonlyArray2<'_, i64> = block.extract().map_err(|_err| {
+            PyTypeError::new_err(format!(
+                "Cells of type '{name}' must be a two-dimensional array of int64"
+            ))
+        })?;
+        let [num_cells, num_points] = [block.shape()[0], block.shape()[1]];
+        let cell_type = cell_type_from_name(name, num_points)?;
+
+        for &point in block.as_array() {
+            connectivity.push(u64::try_from(point).map_err(|_err| {
+                PyValueError::new_err(format!("Cells of type '{name}' have a negative index"))
+            })?);
+        }
+        cell_types.extend(std::iter::repeat_n(cell_type, num_cells));
+    }
+
+    Ok((connectivity, cell_types))
+}
+
+fn cell_type_from_name(name: &str, num_points: usize) -> PyResult<CellType> {
+    let cell_type = match name {
+        "vertex" => CellType::Vertex,
+        "line" => CellType::Edge,
+        "triangle" => CellType::Triangle,
+        "quad" => CellType::Quadrilateral,
+        "tetra" => CellType::Tetrahedron,
+        "pyramid" => CellType::Pyramid,
+        "wedge" => CellType::Wedge,
+        "hexahedron" => CellType::Hexahedron,
+        "line3" => CellType::Edge3,
+        "quad9" => CellType::Quadrilateral9,
+        "triangle6" => CellType::Triangle6,
+        "quad8" => CellType::Quadrilateral8,
+        "tetra10" => CellType::Tetrahedron10,
+        "pyramid13" => CellType::Pyramid13,
+        "wedge15" => CellType::Wedge15,
+        "wedge18" => CellType::Wedge18,
+        "hexahedron20" => CellType::Hexahedron20,
+        "hexahedron24" => CellType::Hexahedron24,
+        "hexahedron27" => CellType::Hexahedron27,
+        "polygon" => CellType::Polygon(num_points),
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Cell type '{name}' is not supported"
+            )));
+        }
+    };
+
+    if cell_type.num_points() != num_points {
+        return Err(PyValueError::new_err(format!(
+            "Cells of type '{name}' must have {} points, but have {num_points}",
+            cell_type.num_points()
+        )));
+    }
+
+    Ok(cell_type)
+}
+
+// numpy array of one of the number types of `Values`
+enum Array<'py> {
+    F64(PyReadonlyArrayDyn<'py, f64>),
+    F32(PyReadonlyArrayDyn<'py, f32>),
+    I64(PyReadonlyArrayDyn<'py, i64>),
+    U64(PyReadonlyArrayDyn<'py, u64>),
+    U8(PyReadonlyArrayDyn<'py, u8>),
+}
+
+impl<'py> Array<'py> {
+    fn extract(name: &str, array: &Bound<'py, PyAny>) -> PyResult<Self> {
+        array
+            .extract()
+            .map(Self::F64)
+            .or_else(|_err| array.extract().map(Self::F32))
+            .or_else(|_err| array.extract().map(Self::I64))
+            .or_else(|_err| array.extract().map(Self::U64))
+            .or_else(|_err| array.extract().map(Self::U8))
+            .map_err(|_err| {
+                PyTypeError::new_err(format!(
+                    "Field '{name}' must be an array of float64, float32, int64, uint64 or uint8"
+                ))
+            })
+    }
+
+    fn shape(&self) -> &[usize] {
+        match self {
+            Self::F64(array) => array.shape(),
+            Self::F32(array) => array.shape(),
+            Self::I64(array) => array.shape(),
+            Self::U64(array) => array.shape(),
+            Self::U8(array) => array.shape(),
+        }
+    }
+
+    // the values are borrowed from the array, which must be C-contiguous
+    fn values(&self) -> Option<Values<'_>> {
+        match self {
+            Self::F64(array) => array.as_slice().ok().map(Values::from),
+            Self::F32(array) => array.as_slice().ok().map(Values::from),
+            Self::I64(array) => array.as_slice().ok().map(Values::from),
+            Self::U64(array) => array.as_slice().ok().map(Values::from),
+            Self::U8(array) => array.as_slice().ok().map(Values::from),
+        }
+    }
+}
+
+fn extract_arrays<'py>(data: &Bound<'py, PyDict>) -> PyResult<Vec<(String, Array<'py>)>> {
+    data.iter()
+        .map(|(name, array)| {
+            let name: String = name.extract()?;
+            let array = Array::extract(&name, &array)?;
+            Ok((name, array))
+        })
+        .collect()
+}
+
+fn data_map<'a>(arrays: &'a [(String, Array<'_>)]) -> PyResult<DataMap<'a>> {
+    arrays
+        .iter()
+        .map(|(name, array)| {
+            let data_attribute = match array.shape() {
+                [] => {
+                    return Err(PyValueError::new_err(format!(
+                        "Field '{name}' must have at least one dimension"
+                    )));
+                }
+                [_] => DataAttribute::Scalar,
+                [_, 3] => DataAttribute::Vector,
+                [_, 6] => DataAttribute::Tensor6,
+                [_, 9] | [_, 3, 3] => DataAttribute::Tensor,
+                [_, rows, columns] => DataAttribute::Matrix(*rows, *columns),
+                [_, shape @ ..] => DataAttribute::Generic(shape.to_vec()),
+            };
+            let values = array.values().ok_or_else(|| {
+                PyValueError::new_err(format!("Field '{name}' must be C-contiguous"))
+            })?;
+
+            Ok((name.clone(), (data_attribute, values)))
+        })
+        .collect()
+}
+
+/// The `xdmf` Python module.
+#[pymodule]
+#[pyo3(name = "xdmf")]
+fn python_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyTimeSeriesWriter>()?;
+    module.add_class::<PyTimeSeriesDataWriter>()?;
+    Ok(())
+}
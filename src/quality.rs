@@ -0,0 +1,476 @@
+//! This module contains the quality metrics of the cells of a mesh, which are written as static cell data.
+//!
+//! The metrics are computed from the corners of the cells, hence quadratic cells are evaluated like their linear counterparts.
+//! XDMF uses the node ordering of VTK, for which the Jacobians of valid cells are positive.
+
+use crate::{CellType, DataAttribute, DataMap, XdmfError, XdmfResult};
+
+/// Quality metric of the cells of a mesh, polyhedra are not supported, see [`TimeSeriesWriter::write_mesh_with_quality`](crate::TimeSeriesWriter::write_mesh_with_quality).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QualityMetric {
+    /// Volume of the 3D cells, area of the 2D cells and length of the lines
+    Volume,
+    /// Ratio of the longest to the shortest edge, 1 for cells with edges of equal length
+    AspectRatio,
+    /// Minimum of the Jacobians at the corners, normalized by the lengths of the adjacent edges.
+    /// It is 1 for ideal cells and negative for inverted cells.
+    ScaledJacobian,
+}
+
+impl QualityMetric {
+    /// Name of the cell data holding the metric.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Volume => "cell_volume",
+            Self::AspectRatio => "aspect_ratio",
+            Self::ScaledJacobian => "scaled_jacobian",
+        }
+    }
+}
+
+type Point = [f64; 3];
+
+// edges, outward oriented faces and the corners of a 3D cell, each corner with its three adjacent corners
+// in the order in which they form a right-handed system for a valid cell
+struct Shape {
+    edges: &'static [[usize; 2]],
+    faces: &'static [&'static [usize]],
+    corners: &'static [[usize; 4]],
+    // inverse of the scaled Jacobian at the corners of the ideal cell
+    jacobian_scale: f64,
+}
+
+const TETRAHEDRON: Shape = Shape {
+    edges: &[[0, 1], [1, 2], [2, 0], [0, 3], [1, 3], [2, 3]],
+    faces: &[&[0, 2, 1], &[0, 1, 3], &[0, 3, 2], &[1, 2, 3]],
+    corners: &[[0, 1, 2, 3], [1, 2, 0, 3], [2, 0, 1, 3], [3, 0, 2, 1]],
+    jacobian_scale: std::f64::consts::SQRT_2,
+};
+
+// the Jacobian is not defined at the apex, where four edges meet
+const PYRAMID: Shape = Shape {
+    edges: &[
+        [0, 1],
+        [1, 2],
+        [2, 3],
+        [3, 0],
+        [0, 4],
+        [1, 4],
+        [2, 4],
+        [3, 4],
+    ],
+    faces: &[
+        &[0, 3, 2, 1],
+        &[0, 1, 4],
+        &[1, 2, 4],
+        &[2, 3, 4],
+        &[3, 0, 4],
+    ],
+    corners: &[[0, 1, 3, 4], [1, 2, 0, 4], [2, 3, 1, 4], [3, 0, 2, 4]],
+    jacobian_scale: std::f64::consts::SQRT_2,
+};
+
+const WEDGE: Shape = Shape {
+    edges: &[
+        [0, 1],
+        [1, 2],
+        [2, 0],
+        [3, 4],
+        [4, 5],
+        [5, 3],
+        [0, 3],
+        [1, 4],
+        [2, 5],
+    ],
+    faces: &[
+        &[0, 2, 1],
+        &[3, 4, 5],
+        &[0, 1, 4, 3],
+        &[0, 3, 5, 2],
+        &[1, 2, 5, 4],
+    ],
+    corners: &[
+        [0, 1, 2, 3],
+        [1, 2, 0, 4],
+        [2, 0, 1, 5],
+        [3, 5, 4, 0],
+        [4, 3, 5, 1],
+        [5, 4, 3, 2],
+    ],
+    // the triangles of the ideal wedge are equilateral
+    jacobian_scale: 2.0 / 1.732_050_807_568_877_2,
+};
+
+const HEXAHEDRON: Shape = Shape {
+    edges: &[
+        [0, 1],
+        [1, 2],
+        [2, 3],
+        [3, 0],
+        [4, 5],
+        [5, 6],
+        [6, 7],
+        [7, 4],
+        [0, 4],
+        [1, 5],
+        [2, 6],
+        [3, 7],
+    ],
+    faces: &[
+        &[0, 3, 2, 1],
+        &[4, 5, 6, 7],
+        &[0, 1, 5, 4],
+        &[3, 7, 6, 2],
+        &[0, 4, 7, 3],
+        &[1, 2, 6, 5],
+    ],
+    corners: &[
+        [0, 1, 3, 4],
+        [1, 2, 0, 5],
+        [2, 3, 1, 6],
+        [3, 0, 2, 7],
+        [4, 7, 5, 0],
+        [5, 4, 6, 1],
+        [6, 5, 7, 2],
+        [7, 6, 4, 3],
+    ],
+    jacobian_scale: 1.0,
+};
+
+// number of corners of a cell type, quadratic cells list their corners first
+fn num_corners(cell_type: CellType) -> Option<usize> {
+    match cell_type {
+        CellType::Vertex => Some(1),
+        CellType::Edge | CellType::Edge3 => Some(2),
+        CellType::Triangle | CellType::Triangle6 => Some(3),
+        CellType::Quadrilateral
+        | CellType::Quadrilateral8
+        | CellType::Quadrilateral9
+        | CellType::Tetrahedron
+        | CellType::Tetrahedron10 => Some(4),
+        CellType::Pyramid | CellType::Pyramid13 => Some(5),
+        CellType::Wedge | CellType::Wedge15 | CellType::Wedge18 => Some(6),
+        CellType::Hexahedron
+        | CellType::Hexahedron20
+        | CellType::Hexahedron24
+        | CellType::Hexahedron27 => Some(8),
+        CellType::Polygon(num_points) => Some(num_points),
+        CellType::Polyhedron(_) => None,
+    }
+}
+
+fn shape(cell_type: CellType) -> Option<&'static Shape> {
+    match cell_type {
+        CellType::Tetrahedron | CellType::Tetrahedron10 => Some(&TETRAHEDRON),
+        CellType::Pyramid | CellType::Pyramid13 => Some(&PYRAMID),
+        CellType::Wedge | CellType::Wedge15 | CellType::Wedge18 => Some(&WEDGE),
+        CellType::Hexahedron
+        | CellType::Hexahedron20
+        | CellType::Hexahedron24
+        | CellType::Hexahedron27 => Some(&HEXAHEDRON),
+        _ => None,
+    }
+}
+
+fn sub(a: Point, b: Point) -> Point {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: Point, b: Point) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Point, b: Point) -> Point {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn norm(a: Point) -> f64 {
+    dot(a, a).sqrt()
+}
+
+// ratio of the value to the product of the lengths, 0 for degenerate edges
+fn normalized(value: f64, lengths: &[f64]) -> f64 {
+    let product: f64 = lengths.iter().product();
+    if product > 0.0 { value / product } else { 0.0 }
+}
+
+// ratio of the longest to the shortest edge
+fn aspect_ratio(corners: &[Point], edges: impl Iterator<Item = [usize; 2]>) -> f64 {
+    let (min, max) = edges
+        .map(|[a, b]| norm(sub(corners[b], corners[a])))
+        .fold((f64::INFINITY, 0.0_f64), |(min, max), length| {
+            (min.min(length), max.max(length))
+        });
+
+    if max > 0.0 { max / min } else { 1.0 }
+}
+
+// twice the vector area of a polygon, its direction is the normal of the polygon
+fn vector_area(corners: &[Point]) -> Point {
+    (1..corners.len().saturating_sub(1)).fold([0.0; 3], |sum, i| {
+        let area = cross(sub(corners[i], corners[0]), sub(corners[i + 1], corners[0]));
+        [sum[0] + area[0], sum[1] + area[1], sum[2] + area[2]]
+    })
+}
+
+// volume enclosed by the outward oriented faces, non-planar faces are split into triangles around their center
+fn volume(corners: &[Point], faces: &[&[usize]]) -> f64 {
+    let origin = corners[0];
+    let mut volume = 0.0;
+
+    for face in faces {
+        let points: Vec<Point> = face.iter().map(|&i| sub(corners[i], origin)).collect();
+        let center = points.iter().fold([0.0; 3], |sum, point| {
+            [sum[0] + point[0], sum[1] + point[1], sum[2] + point[2]]
+        });
+        let center = center.map(|x| x / points.len() as f64);
+
+        for (i, point) in points.iter().enumerate() {
+            let next = points[(i + 1) % points.len()];
+            volume += dot(*point, cross(next, center));
+        }
+    }
+
+    volume / 6.0
+}
+
+// minimum of the scaled Jacobians at the corners of a polygon, relative to its normal
+fn polygon_scaled_jacobian(corners: &[Point]) -> f64 {
+    let area = vector_area(corners);
+    let normal = area.map(|x| normalized(x, &[norm(area)]));
+    let num_corners = corners.len();
+
+    // the corners of the ideal, regular polygon have the same interior angle
+    let angle = std::f64::consts::PI * (num_corners as f64 - 2.0) / num_corners as f64;
+
+    (0..num_corners)
+        .map(|i| {
+            let next = sub(corners[(i + 1) % num_corners], corners[i]);
+            let previous = sub(corners[(i + num_corners - 1) % num_corners], corners[i]);
+            normalized(
+                dot(cross(next, previous), normal),
+                &[norm(next), norm(previous)],
+            ) / angle.sin()
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+// minimum of the scaled Jacobians at the corners of a 3D cell
+fn scaled_jacobian(corners: &[Point], shape: &Shape) -> f64 {
+    shape
+        .corners
+        .iter()
+        .map(|&[corner, a, b, c]| {
+            let [a, b, c] = [a, b, c].map(|i| sub(corners[i], corners[corner]));
+            normalized(dot(a, cross(b, c)), &[norm(a), norm(b), norm(c)]) * shape.jacobian_scale
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+// metric of a single cell, given by its corners
+fn cell_metric(cell_type: CellType, corners: &[Point], metric: QualityMetric) -> f64 {
+    if let Some(shape) = shape(cell_type) {
+        return match metric {
+            QualityMetric::Volume => volume(corners, shape.faces),
+            QualityMetric::AspectRatio => aspect_ratio(corners, shape.edges.iter().copied()),
+            QualityMetric::ScaledJacobian => scaled_jacobian(corners, shape),
+        };
+    }
+
+    // vertices, lines and polygons
+    let ring = (0..corners.len()).map(|i| [i, (i + 1) % corners.len()]);
+
+    match (corners.len(), metric) {
+        (1, QualityMetric::Volume) => 0.0,
+        (2, QualityMetric::Volume) => norm(sub(corners[1], corners[0])),
+        (1 | 2, _) => 1.0,
+        (_, QualityMetric::Volume) => norm(vector_area(corners)) / 2.0,
+        (_, QualityMetric::AspectRatio) => aspect_ratio(corners, ring),
+        (_, QualityMetric::ScaledJacobian) => polygon_scaled_jacobian(corners),
+    }
+}
+
+/// Compute the quality metrics of the cells, as cell data named after the metrics.
+///
+/// The connectivity must have been validated against the points before.
+pub(crate) fn quality_data(
+    points: &[f64],
+    (connectivity, cell_types): (&[u64], &[CellType]),
+    metrics: &[QualityMetric],
+) -> XdmfResult<DataMap<'static>> {
+    let mut values = vec![Vec::with_capacity(cell_types.len()); metrics.len()];
+
+    let mut offset = 0;
+    for &cell_type in cell_types {
+        let num_corners = num_corners(cell_type).ok_or_else(|| {
+            XdmfError::Unsupported(format!(
+                "Quality metrics are not supported for cells of type '{cell_type:?}'"
+            ))
+        })?;
+
+        let corners: Vec<Point> = connectivity[offset..offset + num_corners]
+            .iter()
+            .map(|&index| {
+                let index = index as usize * 3;
+                [points[index], points[index + 1], points[index + 2]]
+            })
+            .collect();
+
+        for (metric, values) in metrics.iter().zip(&mut values) {
+            values.push(cell_metric(cell_type, &corners, *metric));
+        }
+
+        offset += cell_type.num_points();
+    }
+
+    Ok(metrics
+        .iter()
+        .zip(values)
+        .map(|(metric, values)| {
+            (
+                metric.name().to_string(),
+                (DataAttribute::Scalar, values.into()),
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Values;
+
+    const UNIT_CUBE: [Point; 8] = [
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [1.0, 1.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 1.0],
+        [1.0, 1.0, 1.0],
+        [0.0, 1.0, 1.0],
+    ];
+
+    fn metrics(cell_type: CellType, corners: &[Point]) -> [f64; 3] {
+        [
+            QualityMetric::Volume,
+            QualityMetric::AspectRatio,
+            QualityMetric::ScaledJacobian,
+        ]
+        .map(|metric| cell_metric(cell_type, corners, metric))
+    }
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-12, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn metrics_of_ideal_cells() {
+        let sqrt_3 = 3.0_f64.sqrt();
+
+        assert_close(
+            &metrics(CellType::Edge, &[[0.0; 3], [2.0, 0.0, 0.0]]),
+            &[2.0, 1.0, 1.0],
+        );
+        assert_close(
+            &metrics(
+                CellType::Triangle,
+                &[[0.0; 3], [1.0, 0.0, 0.0], [0.5, sqrt_3 / 2.0, 0.0]],
+            ),
+            &[sqrt_3 / 4.0, 1.0, 1.0],
+        );
+        assert_close(
+            &metrics(CellType::Quadrilateral, &UNIT_CUBE[..4]),
+            &[1.0, 1.0, 1.0],
+        );
+        assert_close(
+            &metrics(
+                CellType::Tetrahedron,
+                &[
+                    [1.0, 1.0, 1.0],
+                    [-1.0, 1.0, -1.0],
+                    [1.0, -1.0, -1.0],
+                    [-1.0, -1.0, 1.0],
+                ],
+            ),
+            &[8.0 / 3.0, 1.0, 1.0],
+        );
+        assert_close(&metrics(CellType::Hexahedron, &UNIT_CUBE), &[1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn metrics_of_distorted_cells() {
+        // a box with edges of length 1, 1 and 2
+        let stretched = UNIT_CUBE.map(|[x, y, z]| [x, y, 2.0 * z]);
+        assert_close(&metrics(CellType::Hexahedron, &stretched), &[2.0, 2.0, 1.0]);
+
+        // the wedge is half of the cube, the pyramid a third
+        let wedge = [0, 1, 3, 4, 5, 7].map(|i| UNIT_CUBE[i]);
+        assert_close(
+            &metrics(CellType::Wedge, &wedge),
+            &[0.5, 2.0_f64.sqrt(), 2.0 / 6.0_f64.sqrt()],
+        );
+        let pyramid = [0, 1, 2, 3, 6].map(|i| UNIT_CUBE[i]);
+        assert_close(
+            &[cell_metric(
+                CellType::Pyramid,
+                &pyramid,
+                QualityMetric::Volume,
+            )],
+            &[1.0 / 3.0],
+        );
+
+        // swapping two corners inverts the cell
+        let mut inverted = UNIT_CUBE;
+        inverted.swap(0, 1);
+        assert!(
+            cell_metric(
+                CellType::Hexahedron,
+                &inverted,
+                QualityMetric::ScaledJacobian
+            ) < 0.0
+        );
+    }
+
+    #[test]
+    fn quality_data_of_mesh() {
+        let points: Vec<f64> = UNIT_CUBE.iter().flatten().copied().collect();
+        let data = quality_data(
+            &points,
+            (
+                &[0, 1, 2, 3, 4, 6, 0, 1, 2, 6],
+                &[CellType::Polygon(4), CellType::Edge, CellType::Tetrahedron],
+            ),
+            &[QualityMetric::Volume, QualityMetric::ScaledJacobian],
+        )
+        .unwrap();
+
+        assert_eq!(
+            data.keys().collect::<Vec<_>>(),
+            ["cell_volume", "scaled_jacobian"]
+        );
+        let Values::F64(volumes) = &data["cell_volume"].1 else {
+            panic!("volumes must be of type f64");
+        };
+        assert_close(volumes, &[1.0, 2.0_f64.sqrt(), 1.0 / 6.0]);
+
+        let (cell_type, connectivity) =
+            CellType::polyhedron(&[&[0, 1, 2], &[0, 1, 3], &[0, 2, 3], &[1, 2, 3]]);
+        assert_eq!(
+            quality_data(
+                &points,
+                (&connectivity, &[cell_type]),
+                &[QualityMetric::Volume]
+            )
+            .unwrap_err()
+            .to_string(),
+            "Quality metrics are not supported for cells of type 'Polyhedron(17)'"
+        );
+    }
+}
@@ -0,0 +1,361 @@
+use std::{fs::File, io::BufReader, path::Path, sync::Arc};
+
+use crate::{
+    XdmfResult,
+    xdmf_elements::{
+        Domain, Xdmf, XmlHeader,
+        attribute::Attribute,
+        data_item::{DataContent, DataItem, Format},
+        grid::Grid,
+    },
+};
+
+/// Rewrite the references to the heavy data of an XDMF file on disk, e.g. after the folder of the heavy data was renamed.
+///
+/// Every reference whose path starts with `from` (compared per path component) gets the prefix `to` instead,
+/// this applies to the `href` of `XInclude`s and to the file names of HDF5 datasets.
+/// If `from` is empty, all relative paths are prefixed with `to`, e.g. with `".."` after moving the XDMF file into a subfolder.
+/// The XDMF file is read and written again, returns the number of rewritten references.
+/// ```rust
+/// # use xdmf::TimeSeriesWriter;
+/// TimeSeriesWriter::new("rebased_xdmf_file", xdmf::DataStorage::Ascii)
+///     .expect("failed to create XDMF writer")
+///     .write_mesh(&[0.0; 3], (&[], &[]))
+///     .expect("failed to write mesh");
+///
+/// std::fs::rename("rebased_xdmf_file.txt", "rebased_xdmf_file_data")
+///     .expect("failed to rename folder");
+///
+/// let num_rebased = xdmf::rebase_references(
+///     "rebased_xdmf_file.xdmf2",
+///     "rebased_xdmf_file.txt",
+///     "rebased_xdmf_file_data",
+/// )
+/// .expect("failed to rebase references");
+///
+/// assert_eq!(num_rebased, 2);
+/// assert!(
+///     xdmf::validate::validate_file("rebased_xdmf_file.xdmf2")
+///         .expect("failed to read file")
+///         .is_valid()
+/// );
+/// # std::fs::remove_file("rebased_xdmf_file.xdmf2").expect("failed to remove xdmf file");
+/// # std::fs::remove_dir_all("rebased_xdmf_file_data").expect("failed to remove txt files");
+/// ```
+pub fn rebase_references(
+    file_name: impl AsRef<Path>,
+    from: impl AsRef<Path>,
+    to: impl AsRef<Path>,
+) -> XdmfResult<usize> {
+    let file_name = file_name.as_ref();
+    let mut xdmf = Xdmf::read_from(BufReader::new(File::open(file_name)?))?;
+
+    let num_rebased = rebase_xdmf_references(&mut xdmf, from, to);
+    rewrite_file(file_name, &xdmf)?;
+
+    Ok(num_rebased)
+}
+
+// write a modified XDMF file again
+pub(crate) fn rewrite_file(file_name: &Path, xdmf: &Xdmf) -> XdmfResult<()> {
+    // the header is not part of the XDMF structure, it is kept as it was
+    let contents = std::fs::read_to_string(file_name)?;
+    let header = XmlHeader::default()
+        .with_declaration(contents.trim_start().starts_with("<?xml"))
+        .with_doctype(contents.contains("<!DOCTYPE"));
+
+    let mut buffer = Vec::new();
+    xdmf.write_to_with_header(&mut buffer, &header)?;
+    std::fs::write(file_name, buffer)?;
+
+    Ok(())
+}
+
+/// Rewrite the references to the heavy data of an `Xdmf` structure.
+///
+/// See [`rebase_references`] for details, returns the number of rewritten references.
+pub fn rebase_xdmf_references(
+    xdmf: &mut Xdmf,
+    from: impl AsRef<Path>,
+    to: impl AsRef<Path>,
+) -> usize {
+    let rebaser = Rebaser {
+        from: normalized_path(from.as_ref()),
+        to: normalized_path(to.as_ref()),
+    };
+
+    xdmf.domains
+        .iter_mut()
+        .map(|domain| rebaser.rebase_domain(domain))
+        .sum()
+}
+
+struct Rebaser {
+    from: String,
+    to: String,
+}
+
+impl Rebaser {
+    fn rebase_domain(&self, domain: &mut Domain) -> usize {
+        let mut num_rebased = self.rebase_data_items(&mut domain.data_items);
+
+        for include in &mut domain.includes {
+            num_rebased += usize::from(include.rebase(|path| self.rebase_path(path)));
+        }
+
+        for grid in &mut domain.grids {
+            num_rebased += self.rebase_grid(grid);
+        }
+
+        num_rebased
+    }
+
+    fn rebase_grid(&self, grid: &mut Grid) -> usize {
+        let mut num_rebased = 0;
+
+        for include in grid.includes.iter_mut().flatten() {
+            num_rebased += usize::from(include.rebase(|path| self.rebase_path(path)));
+        }
+
+        num_rebased += self.rebase_data_items(grid.data_item.iter_mut());
+
+        if let Some(geometry) = &mut grid.geometry {
+            num_rebased += self.rebase_data_items(&mut geometry.data_items);
+        }
+
+        if let Some(topology) = &mut grid.topology {
+            num_rebased += self.rebase_data_items(topology.data_item.iter_mut());
+        }
+
+        if let Some(time) = &mut grid.time {
+            num_rebased += self.rebase_data_items(time.data_item.iter_mut());
+        }
+
+        num_rebased += self.rebase_attributes(grid.attributes.iter_mut().flatten());
+
+        for set in grid.sets.iter_mut().flatten() {
+            num_rebased += self.rebase_data_items(&mut set.data_items);
+            num_rebased += self.rebase_attributes(&mut set.attributes);
+        }
+
+        for sub_grid in grid.grids.iter_mut().flatten() {
+            num_rebased += self.rebase_grid(sub_grid);
+        }
+
+        num_rebased
+    }
+
+    fn rebase_attributes<'a>(
+        &self,
+        attributes: impl IntoIterator<Item = &'a mut Arc<Attribute>>,
+    ) -> usize {
+        attributes
+            .into_iter()
+            .map(|attribute| {
+                // shared attributes are only copied if they have references to rewrite
+                if attribute
+                    .data_items
+                    .iter()
+                    .any(|data_item| self.has_rebased_reference(data_item))
+                {
+                    self.rebase_data_items(&mut Arc::make_mut(attribute).data_items)
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+
+    fn rebase_data_items<'a>(
+        &self,
+        data_items: impl IntoIterator<Item = &'a mut DataItem>,
+    ) -> usize {
+        data_items
+            .into_iter()
+            .map(|data_item| self.rebase_data_item(data_item))
+            .sum()
+    }
+
+    fn rebase_data_item(&self, data_item: &mut DataItem) -> usize {
+        let rebased = match (data_item.format.unwrap_or_default(), &mut data_item.data) {
+            (_, DataContent::Include(include)) => include.rebase(|path| self.rebase_path(path)),
+            (Format::HDF, DataContent::Raw(text)) => match self.rebase_hdf5_path(text) {
+                Some(rebased_text) => {
+                    *text = rebased_text;
+                    true
+                }
+                None => false,
+            },
+            (Format::XML | Format::Binary, DataContent::Raw(_)) => false,
+        };
+
+        usize::from(rebased) + self.rebase_data_items(&mut data_item.data_items)
+    }
+
+    // whether a reference of the data item or its children would be rewritten
+    fn has_rebased_reference(&self, data_item: &DataItem) -> bool {
+        let rebased = match (data_item.format.unwrap_or_default(), &data_item.data) {
+            (_, DataContent::Include(include)) => self.rebase_path(include.file_path()).is_some(),
+            (Format::HDF, DataContent::Raw(text)) => self.rebase_hdf5_path(text).is_some(),
+            (Format::XML | Format::Binary, DataContent::Raw(_)) => false,
+        };
+
+        rebased
+            || data_item
+                .data_items
+                .iter()
+                .any(|child| self.has_rebased_reference(child))
+    }
+
+    // HDF5 datasets are given as "<file>:<dataset>"
+    fn rebase_hdf5_path(&self, text: &str) -> Option<String> {
+        let (file_name, dataset) = text.trim().split_once(':')?;
+        let file_name = self.rebase_path(file_name)?;
+
+        Some(format!("{file_name}:{dataset}"))
+    }
+
+    fn rebase_path(&self, path: &str) -> Option<String> {
+        let rest = if self.from.is_empty() {
+            // only relative paths depend on the location of the XDMF file
+            if path.starts_with('/') || Path::new(path).is_absolute() {
+                return None;
+            }
+            path
+        } else {
+            match path.strip_prefix(self.from.as_str())? {
+                "" => return Some(self.to.clone()),
+                rest => rest.strip_prefix('/')?,
+            }
+        };
+
+        if self.to.is_empty() {
+            Some(rest.to_string())
+        } else {
+            Some(format!("{}/{rest}", self.to))
+        }
+    }
+}
+
+// paths are written with forward slashes as separator, see `PathPolicy`
+fn normalized_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace(std::path::MAIN_SEPARATOR, "/")
+        .trim_end_matches('/')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdmf_elements::{
+        attribute::{AttributeType, Center},
+        data_item::XInclude,
+        geometry::{Geometry, GeometryType},
+        topology::Topology,
+    };
+
+    fn rebased_paths(from: &str, to: &str) -> Vec<Option<String>> {
+        let rebaser = Rebaser {
+            from: from.to_string(),
+            to: to.to_string(),
+        };
+
+        [
+            "case.txt/points.txt",
+            "case.txt",
+            "case.txt2/points.txt",
+            "/abs/case.txt",
+        ]
+        .into_iter()
+        .map(|path| rebaser.rebase_path(path))
+        .collect()
+    }
+
+    #[test]
+    fn rebase_path() {
+        assert_eq!(
+            rebased_paths("case.txt", "run/data"),
+            vec![
+                Some("run/data/points.txt".to_string()),
+                Some("run/data".to_string()),
+                None,
+                None
+            ]
+        );
+
+        assert_eq!(
+            rebased_paths("", ".."),
+            vec![
+                Some("../case.txt/points.txt".to_string()),
+                Some("../case.txt".to_string()),
+                Some("../case.txt2/points.txt".to_string()),
+                None
+            ]
+        );
+
+        assert_eq!(
+            rebased_paths("/abs", ""),
+            vec![None, None, None, Some("case.txt".to_string())]
+        );
+    }
+
+    #[test]
+    fn rebase_xdmf() {
+        let points = DataItem {
+            data: XInclude::new("case.txt/points.txt", true).into(),
+            ..Default::default()
+        };
+        let data = DataItem {
+            format: Some(Format::HDF),
+            data: "case.h5:/data/pressure".to_string().into(),
+            ..Default::default()
+        };
+
+        let attribute = Arc::new(Attribute {
+            name: "pressure".to_string(),
+            attribute_type: AttributeType::Scalar,
+            center: Center::Node,
+            information: Vec::new(),
+            data_items: vec![data],
+        });
+
+        let mut grid = Grid::new_uniform(
+            "mesh",
+            Geometry {
+                geometry_type: GeometryType::XYZ,
+                data_items: vec![points],
+            },
+            Topology::new_co_rect_mesh([1, 1, 1]),
+        );
+        grid.attributes = Some(vec![attribute.clone()]);
+
+        let mut xdmf = Xdmf::new(Domain::new(grid));
+
+        assert_eq!(rebase_xdmf_references(&mut xdmf, "case.h5", "run.h5"), 1);
+        assert_eq!(rebase_xdmf_references(&mut xdmf, "missing", "run"), 0);
+
+        let grid = &xdmf.domains[0].grids[0];
+        let rebased_attribute = &grid.attributes.as_ref().unwrap()[0];
+        assert_eq!(
+            rebased_attribute.data_items[0].data,
+            "run.h5:/data/pressure".to_string().into()
+        );
+        // the shared attribute is not modified
+        assert_eq!(
+            attribute.data_items[0].data,
+            "case.h5:/data/pressure".to_string().into()
+        );
+
+        assert_eq!(rebase_xdmf_references(&mut xdmf, "", ".."), 2);
+        assert_eq!(
+            xdmf.domains[0].grids[0]
+                .geometry
+                .as_ref()
+                .unwrap()
+                .data_items[0]
+                .data,
+            XInclude::new("../case.txt/points.txt", true).into()
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::{fs::File, io::BufReader, path::Path};
+
+use crate::{
+    XdmfResult,
+    rebase::rewrite_file,
+    xdmf_elements::{
+        Xdmf,
+        data_item::{DataContent, DataItem, Format},
+        grid::{CollectionType, Grid, Time, TimeType},
+    },
+};
+
+/// Remove the time steps of an XDMF file whose heavy data files are missing, e.g. after a crash while writing them.
+///
+/// The grids of the time steps of all temporal collections are checked, a time step is removed if any file it references
+/// (with an `XInclude` or as HDF5 file of a dataset) does not exist. Files are searched relative to the directory of the XDMF file.
+/// Returns the names of the removed grids, the file is only written again if grids were removed.
+/// Together with [`Hdf5Options::with_atomic_steps`](crate::Hdf5Options::with_atomic_steps) the remaining time steps are complete.
+/// ```rust
+/// # use xdmf::TimeSeriesWriter;
+/// let mut ts_writer = TimeSeriesWriter::new("pruned_xdmf_file", xdmf::DataStorage::Ascii)
+///     .expect("failed to create XDMF writer")
+///     .write_mesh(&[0.0; 3], (&[], &[]))
+///     .expect("failed to write mesh");
+///
+/// let point_data = vec![(
+///     "temperature".to_string(),
+///     (xdmf::DataAttribute::Scalar, vec![20.0].into()),
+/// )]
+/// .into_iter()
+/// .collect();
+///
+/// ts_writer
+///     .write_data("0.0", Some(&point_data), None)
+///     .expect("failed to write data");
+///
+/// let pruned_steps =
+///     xdmf::prune_dangling_steps("pruned_xdmf_file.xdmf2").expect("failed to prune time steps");
+/// assert!(pruned_steps.is_empty());
+/// # std::fs::remove_file("pruned_xdmf_file.xdmf2").expect("failed to remove xdmf file");
+/// # std::fs::remove_dir_all("pruned_xdmf_file.txt").expect("failed to remove txt files");
+/// ```
+pub fn prune_dangling_steps(file_name: impl AsRef<Path>) -> XdmfResult<Vec<String>> {
+    let file_name = file_name.as_ref();
+    let base_dir = file_name.parent().unwrap_or_else(|| Path::new(""));
+
+    let mut xdmf = Xdmf::read_from(BufReader::new(File::open(file_name)?))?;
+
+    let pruned = prune_xdmf_steps(&mut xdmf, base_dir);
+    if !pruned.is_empty() {
+        rewrite_file(file_name, &xdmf)?;
+    }
+
+    Ok(pruned)
+}
+
+/// Remove the time steps of an `Xdmf` structure whose heavy data files are missing.
+///
+/// See [`prune_dangling_steps`] for details, files are searched relative to `base_dir`.
+pub fn prune_xdmf_steps(xdmf: &mut Xdmf, base_dir: impl AsRef<Path>) -> Vec<String> {
+    let base_dir = base_dir.as_ref();
+    let mut pruned = Vec::new();
+
+    for domain in &mut xdmf.domains {
+        for grid in &mut domain.grids {
+            prune_grid(grid, base_dir, &mut pruned);
+        }
+    }
+
+    pruned
+}
+
+fn prune_grid(grid: &mut Grid, base_dir: &Path, pruned: &mut Vec<String>) {
+    let Some(sub_grids) = &mut grid.grids else {
+        return;
+    };
+
+    if grid.collection_type == Some(CollectionType::Temporal) {
+        let is_complete: Vec<bool> = sub_grids
+            .iter()
+            .map(|sub_grid| {
+                let mut files = Vec::new();
+                grid_files(sub_grid, &mut files);
+                files.iter().all(|file| base_dir.join(file).exists())
+            })
+            .collect();
+
+        // the times of the steps are given as a list in the collection, which is kept consistent
+        if let Some(time) = &mut grid.time
+            && time.time_type == Some(TimeType::List)
+            && let Some(DataItem {
+                data: DataContent::Raw(times),
+                ..
+            }) = &time.data_item
+        {
+            let times: Vec<&str> = times
+                .split_whitespace()
+                .zip(&is_complete)
+                .filter_map(|(time, is_complete)| is_complete.then_some(time))
+                .collect();
+            *time = Time::new_list(times);
+        }
+
+        let mut is_complete = is_complete.into_iter();
+        sub_grids.retain(|sub_grid| {
+            let is_complete = is_complete.next().unwrap_or(true);
+            if !is_complete {
+                pruned.push(sub_grid.name.clone());
+            }
+            is_complete
+        });
+    }
+
+    for sub_grid in sub_grids {
+        prune_grid(sub_grid, base_dir, pruned);
+    }
+}
+
+// paths of the files referenced by a grid and its children
+fn grid_files(grid: &Grid, files: &mut Vec<String>) {
+    files.extend(
+        grid.includes
+            .iter()
+            .flatten()
+            .map(|include| include.file_path().to_string()),
+    );
+
+    let data_items = grid
+        .data_item
+        .iter()
+        .chain(
+            grid.geometry
+                .iter()
+                .flat_map(|geometry| &geometry.data_items),
+        )
+        .chain(
+            grid.topology
+                .iter()
+                .filter_map(|topology| topology.data_item.as_ref()),
+        )
+        .chain(grid.time.iter().filter_map(|time| time.data_item.as_ref()))
+        .chain(
+            grid.attributes
+                .iter()
+                .flatten()
+                .flat_map(|attribute| &attribute.data_items),
+        )
+        .chain(grid.sets.iter().flatten().flat_map(|set| {
+            set.data_items.iter().chain(
+                set.attributes
+                    .iter()
+                    .flat_map(|attribute| &attribute.data_items),
+            )
+        }));
+
+    for data_item in data_items {
+        data_item_files(data_item, files);
+    }
+
+    for sub_grid in grid.grids.iter().flatten() {
+        grid_files(sub_grid, files);
+    }
+}
+
+fn data_item_files(data_item: &DataItem, files: &mut Vec<String>) {
+    match (data_item.format.unwrap_or_default(), &data_item.data) {
+        (_, DataContent::Include(include)) => files.push(include.file_path().to_string()),
+        // HDF5 datasets are given as "<file>:<dataset>"
+        (Format::HDF, DataContent::Raw(text)) => {
+            if let Some((file_name, _)) = text.trim().split_once(':') {
+                files.push(file_name.to_string());
+            }
+        }
+        (Format::XML | Format::Binary, DataContent::Raw(_)) => {}
+    }
+
+    for child in &data_item.data_items {
+        data_item_files(child, files);
+    }
+}
@@ -0,0 +1,235 @@
+//! Conversion of the connectivity of cells from the node ordering of other conventions into the ordering of XDMF.
+//!
+//! XDMF uses the same node ordering as VTK. The corner nodes are ordered the same in all supported conventions,
+//! but the nodes of higher-order cells (e.g. the mid-edge nodes of a `Hexahedron20`) are ordered differently.
+//! ```rust
+//! use xdmf::{
+//!     CellType,
+//!     reorder::{NodeOrdering, to_xdmf_ordering},
+//! };
+//!
+//! // connectivity of a Tetrahedron10 as exported by Gmsh
+//! let connectivity = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+//! let reordered = to_xdmf_ordering(
+//!     &connectivity,
+//!     &[CellType::Tetrahedron10],
+//!     NodeOrdering::Gmsh,
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(reordered, [0, 1, 2, 3, 4, 5, 6, 7, 9, 8]);
+//! ```
+
+use crate::{CellType, XdmfError, XdmfResult};
+
+/// Convention of the order of the nodes within the cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeOrdering {
+    /// Ordering of VTK, which is the same as the ordering of XDMF
+    Vtk,
+    /// Ordering of [Gmsh](https://gmsh.info/doc/texinfo/gmsh.html#Node-ordering)
+    Gmsh,
+    /// Ordering of Abaqus, which is the same as the ordering of VTK for the available cells (e.g. `C3D10`, `C3D15` and `C3D20`)
+    Abaqus,
+}
+
+/// Permute the connectivity of the cells from the given node ordering into the ordering of XDMF.
+///
+/// The connectivity contains the nodes of all cells consecutively, as passed to [`crate::TimeSeriesWriter::write_mesh`].
+pub fn to_xdmf_ordering(
+    connectivity: &[u64],
+    cell_types: &[CellType],
+    node_ordering: NodeOrdering,
+) -> XdmfResult<Vec<u64>> {
+    let expected_len: usize = cell_types.iter().map(CellType::num_points).sum();
+    if connectivity.len() != expected_len {
+        return Err(XdmfError::Validation(format!(
+            "Connectivity has {} entries, but the cells have {expected_len} nodes",
+            connectivity.len()
+        )));
+    }
+
+    let mut reordered = Vec::with_capacity(connectivity.len());
+    let mut index = 0;
+
+    for cell_type in cell_types {
+        let num_points = cell_type.num_points();
+        let nodes = &connectivity[index..index + num_points];
+
+        match permutation(*cell_type, node_ordering)? {
+            Some(permutation) => reordered.extend(permutation.iter().map(|&i| nodes[i])),
+            None => reordered.extend_from_slice(nodes),
+        }
+
+        index += num_points;
+    }
+
+    Ok(reordered)
+}
+
+// indices of the nodes of the source ordering in the order of XDMF, None if the orderings are the same
+// the tables are derived from the definitions of the edges and faces in the respective documentation
+fn permutation(
+    cell_type: CellType,
+    node_ordering: NodeOrdering,
+) -> XdmfResult<Option<&'static [usize]>> {
+    if node_ordering != NodeOrdering::Gmsh {
+        return Ok(None);
+    }
+
+    Ok(match cell_type {
+        CellType::Tetrahedron10 => Some(&[0, 1, 2, 3, 4, 5, 6, 7, 9, 8]),
+        CellType::Pyramid13 => Some(&[0, 1, 2, 3, 4, 5, 8, 10, 6, 7, 9, 11, 12]),
+        CellType::Wedge15 => Some(&[0, 1, 2, 3, 4, 5, 6, 9, 7, 12, 14, 13, 8, 10, 11]),
+        CellType::Wedge18 => Some(&[0, 1, 2, 3, 4, 5, 6, 9, 7, 12, 14, 13, 8, 10, 11, 15, 17, 16]),
+        CellType::Hexahedron20 => Some(&[
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 11, 13, 9, 16, 18, 19, 17, 10, 12, 14, 15,
+        ]),
+        CellType::Hexahedron27 => Some(&[
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 11, 13, 9, 16, 18, 19, 17, 10, 12, 14, 15, 22, 23, 21, 24,
+            20, 25, 26,
+        ]),
+        CellType::Hexahedron24 => {
+            return Err(XdmfError::Unsupported(
+                "Gmsh has no Hexahedron24 cell, hence its node ordering is not defined".into(),
+            ));
+        }
+        CellType::Vertex
+        | CellType::Edge
+        | CellType::Triangle
+        | CellType::Quadrilateral
+        | CellType::Tetrahedron
+        | CellType::Pyramid
+        | CellType::Wedge
+        | CellType::Hexahedron
+        | CellType::Edge3
+        | CellType::Quadrilateral9
+        | CellType::Triangle6
+        | CellType::Quadrilateral8
+        | CellType::Polygon(_)
+        | CellType::Polyhedron(_) => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_CELL_TYPES: [CellType; 19] = [
+        CellType::Vertex,
+        CellType::Edge,
+        CellType::Triangle,
+        CellType::Quadrilateral,
+        CellType::Tetrahedron,
+        CellType::Pyramid,
+        CellType::Wedge,
+        CellType::Hexahedron,
+        CellType::Edge3,
+        CellType::Quadrilateral9,
+        CellType::Triangle6,
+        CellType::Quadrilateral8,
+        CellType::Tetrahedron10,
+        CellType::Pyramid13,
+        CellType::Wedge15,
+        CellType::Wedge18,
+        CellType::Hexahedron20,
+        CellType::Hexahedron24,
+        CellType::Hexahedron27,
+    ];
+
+    #[test]
+    fn permutations_are_valid() {
+        for cell_type in ALL_CELL_TYPES {
+            let Ok(Some(permutation)) = permutation(cell_type, NodeOrdering::Gmsh) else {
+                continue;
+            };
+
+            let mut sorted = permutation.to_vec();
+            sorted.sort_unstable();
+
+            assert_eq!(
+                sorted,
+                (0..cell_type.num_points()).collect::<Vec<_>>(),
+                "{cell_type:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn to_xdmf_ordering_gmsh() {
+        // the mid-edge nodes of a Gmsh Hexahedron20, identified by their corner nodes
+        let gmsh_edges = [
+            (0, 1),
+            (0, 3),
+            (0, 4),
+            (1, 2),
+            (1, 5),
+            (2, 3),
+            (2, 6),
+            (3, 7),
+            (4, 5),
+            (4, 7),
+            (5, 6),
+            (6, 7),
+        ];
+        let xdmf_edges = [
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (0, 3),
+            (4, 5),
+            (5, 6),
+            (6, 7),
+            (4, 7),
+            (0, 4),
+            (1, 5),
+            (2, 6),
+            (3, 7),
+        ];
+
+        let edge_node = |edge: (u64, u64)| 10 * edge.0 + edge.1 + 100;
+        let connectivity: Vec<u64> = (0..8).chain(gmsh_edges.map(edge_node)).collect();
+        let expected: Vec<u64> = (0..8).chain(xdmf_edges.map(edge_node)).collect();
+
+        // a triangle in front checks the offsets of mixed cells
+        let connectivity: Vec<u64> = [7, 8, 9].into_iter().chain(connectivity).collect();
+        let expected: Vec<u64> = [7, 8, 9].into_iter().chain(expected).collect();
+
+        assert_eq!(
+            to_xdmf_ordering(
+                &connectivity,
+                &[CellType::Triangle, CellType::Hexahedron20],
+                NodeOrdering::Gmsh
+            )
+            .unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn to_xdmf_ordering_vtk_and_abaqus() {
+        let connectivity: Vec<u64> = (0..20).collect();
+
+        for node_ordering in [NodeOrdering::Vtk, NodeOrdering::Abaqus] {
+            assert_eq!(
+                to_xdmf_ordering(&connectivity, &[CellType::Hexahedron20], node_ordering).unwrap(),
+                connectivity
+            );
+        }
+    }
+
+    #[test]
+    fn to_xdmf_ordering_invalid() {
+        assert_eq!(
+            to_xdmf_ordering(&[0, 1], &[CellType::Triangle], NodeOrdering::Gmsh)
+                .unwrap_err()
+                .to_string(),
+            "Connectivity has 2 entries, but the cells have 3 nodes"
+        );
+
+        assert!(matches!(
+            to_xdmf_ordering(&[0; 24], &[CellType::Hexahedron24], NodeOrdering::Gmsh),
+            Err(XdmfError::Unsupported(_))
+        ));
+    }
+}
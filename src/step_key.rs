@@ -0,0 +1,394 @@
+//! This module contains the `StepKey` trait, which identifies the time steps of a time series.
+
+use crate::{
+    XdmfError, XdmfResult,
+    number_format::{FormatNumber, parse_time},
+};
+
+/// Identifier of a time step, e.g. the time as float, the cycle as integer, or a label.
+///
+/// Strings are interpreted as time, hence they must be parseable as float.
+/// Use [`StepLabel`] for identifying steps with arbitrary labels.
+pub trait StepKey {
+    /// Unique label of the step, used in the names of grids and heavy data.
+    fn label(&self) -> String;
+
+    /// Value of the `Time` element of the step, which must be parseable as float.
+    ///
+    /// If `None`, the index of the step is used instead.
+    fn time_value(&self) -> Option<String>;
+
+    /// Cycle of the step (e.g. the solver iteration), which is stored in addition to the time.
+    ///
+    /// See [`WithCycle`] for adding a cycle to a key.
+    fn cycle(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Identifies a time step by a label (e.g. `"initial"` or `"load_case_1"`), which is not related to a time.
+///
+/// The index of the step is used as value of the `Time` element.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StepLabel(pub String);
+
+impl StepKey for StepLabel {
+    fn label(&self) -> String {
+        self.0.clone()
+    }
+
+    fn time_value(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Format of the label of a time, see [`FormattedTime`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimeFormat {
+    /// Shortest representation that is parsed to the same float, e.g. `0.1` or `2`.
+    #[default]
+    Shortest,
+    /// Fixed number of decimals, e.g. `0.100` for 3 decimals.
+    Fixed(usize),
+    /// Scientific notation with the given number of decimals, e.g. `1.00e-1` for 2 decimals.
+    Scientific(usize),
+}
+
+/// Identifies a time step by its time, with the label formatted according to the [`TimeFormat`].
+///
+/// The value of the `Time` element is the time in full precision, only the label (used in the names of grids and heavy data) is formatted.
+/// Note that times which differ by less than the precision of the format have the same label, hence they can not both be written.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FormattedTime {
+    /// Time of the step.
+    pub time: f64,
+    /// Format of the label.
+    pub format: TimeFormat,
+}
+
+impl FormattedTime {
+    /// Create the key of a time step with the given time and format of its label.
+    #[must_use]
+    pub const fn new(time: f64, format: TimeFormat) -> Self {
+        Self { time, format }
+    }
+}
+
+impl StepKey for FormattedTime {
+    fn label(&self) -> String {
+        match self.format {
+            TimeFormat::Shortest => self.time.label(),
+            TimeFormat::Fixed(decimals) => format!("{:.*}", decimals, self.time),
+            TimeFormat::Scientific(decimals) => format!("{:.*e}", decimals, self.time),
+        }
+    }
+
+    fn time_value(&self) -> Option<String> {
+        self.time.time_value()
+    }
+}
+
+/// Adds the cycle (e.g. the solver iteration) to the key of a time step, like the cycle in `VisIt`.
+///
+/// The step is identified by its key, the cycle is stored as `Information` named `Cycle` in the grid of the time step,
+/// from where it can be read with [`Grid::cycle`](crate::xdmf_elements::grid::Grid::cycle).
+/// ```rust
+/// use xdmf::{TimeSeriesWriter, WithCycle};
+/// let mut time_series_writer =
+///     TimeSeriesWriter::new("xdmf_with_cycle", xdmf::DataStorage::AsciiInline)
+///         .expect("failed to create XDMF writer")
+///         .write_points(&[0.0, 0.0, 0.0])
+///         .expect("failed to write points");
+///
+/// let point_data = vec![(
+///     "temperature".to_string(),
+///     (xdmf::DataAttribute::Scalar, vec![300.0].into()),
+/// )]
+/// .into_iter()
+/// .collect();
+///
+/// // time 0.25 was reached after 42 iterations of the solver
+/// time_series_writer
+///     .write_data(&WithCycle::new(0.25, 42), Some(&point_data), None)
+///     .expect("failed to write time step data");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WithCycle<K> {
+    /// Key of the step, e.g. its time.
+    pub step: K,
+    /// Cycle of the step.
+    pub cycle: u64,
+}
+
+impl<K: StepKey> WithCycle<K> {
+    /// Create the key of a time step with the given key and cycle.
+    pub const fn new(step: K, cycle: u64) -> Self {
+        Self { step, cycle }
+    }
+}
+
+impl<K: StepKey> StepKey for WithCycle<K> {
+    fn label(&self) -> String {
+        self.step.label()
+    }
+
+    fn time_value(&self) -> Option<String> {
+        self.step.time_value()
+    }
+
+    fn cycle(&self) -> Option<u64> {
+        Some(self.cycle)
+    }
+}
+
+impl<T: StepKey + ?Sized> StepKey for &T {
+    fn label(&self) -> String {
+        (**self).label()
+    }
+
+    fn time_value(&self) -> Option<String> {
+        (**self).time_value()
+    }
+
+    fn cycle(&self) -> Option<u64> {
+        (**self).cycle()
+    }
+}
+
+impl StepKey for str {
+    fn label(&self) -> String {
+        self.to_string()
+    }
+
+    fn time_value(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl StepKey for String {
+    fn label(&self) -> String {
+        self.clone()
+    }
+
+    fn time_value(&self) -> Option<String> {
+        Some(self.clone())
+    }
+}
+
+impl StepKey for f64 {
+    fn label(&self) -> String {
+        format!("{self}")
+    }
+
+    fn time_value(&self) -> Option<String> {
+        Some(self.label())
+    }
+}
+
+macro_rules! impl_step_key_cycle {
+    ($($t:ty),*) => {
+        $(
+            impl StepKey for $t {
+                fn label(&self) -> String {
+                    self.format_number()
+                }
+
+                fn time_value(&self) -> Option<String> {
+                    Some(self.label())
+                }
+            }
+        )*
+    };
+}
+
+// integers are interpreted as cycles
+impl_step_key_cycle!(i32, i64, u32, u64, usize);
+
+/// Validate the key of a step, returning its label and the value of the `Time` element.
+///
+/// The index of the step is used as time value, if the key does not define one.
+pub(crate) fn resolve_step(
+    step: &(impl StepKey + ?Sized),
+    index: usize,
+) -> XdmfResult<(String, String)> {
+    let time_value = match step.time_value() {
+        Some(time_value) => {
+            parse_time(&time_value)?;
+            time_value
+        }
+        None => index.format_number(),
+    };
+
+    let label = step.label();
+
+    if !is_valid_label(&label) {
+        return Err(XdmfError::Validation(format!(
+            "Step label '{label}' is not valid, must be non-empty and contain only alphanumeric characters, underscores, dashes, dots or plus signs"
+        )));
+    }
+
+    Ok((label, time_value))
+}
+
+/// Validate the key of a step like [`resolve_step`], multiplying its time by the scale, e.g. to convert nanoseconds to seconds.
+///
+/// Neither the label nor the index that is used if the key does not define a time are scaled.
+pub(crate) fn resolve_scaled_step(
+    step: &(impl StepKey + ?Sized),
+    index: usize,
+    time_scale: Option<f64>,
+) -> XdmfResult<(String, String)> {
+    let (label, time_value) = resolve_step(step, index)?;
+
+    let Some(time_scale) = time_scale else {
+        return Ok((label, time_value));
+    };
+
+    // a negative scale would reverse the order of the time steps
+    if !(time_scale.is_finite() && time_scale > 0.0) {
+        return Err(XdmfError::Validation(format!(
+            "Time scale must be a positive finite number, and not '{time_scale}'"
+        )));
+    }
+
+    if step.time_value().is_none() {
+        return Ok((label, time_value));
+    }
+
+    // formatted like a time given as f64, the scaled time is checked as it could overflow
+    let scaled_time = format!("{}", parse_time(&time_value)? * time_scale);
+    parse_time(&scaled_time)?;
+
+    Ok((label, scaled_time))
+}
+
+// the label is used in file names, hence only a restricted set of characters is allowed
+fn is_valid_label(label: &str) -> bool {
+    !label.is_empty()
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_step_time() {
+        assert_eq!(
+            resolve_step("0.5", 3).unwrap(),
+            ("0.5".to_string(), "0.5".to_string())
+        );
+        assert_eq!(
+            resolve_step(&"1e-3".to_string(), 3).unwrap(),
+            ("1e-3".to_string(), "1e-3".to_string())
+        );
+        assert_eq!(
+            resolve_step(&2.5, 3).unwrap(),
+            ("2.5".to_string(), "2.5".to_string())
+        );
+        assert_eq!(
+            resolve_step(&1.0, 3).unwrap(),
+            ("1".to_string(), "1".to_string())
+        );
+
+        assert_eq!(
+            resolve_step("abc", 3).unwrap_err().to_string(),
+            "Time must be a valid float, and not 'abc'"
+        );
+        assert_eq!(
+            resolve_step(&f64::NAN, 3).unwrap_err().to_string(),
+            "Time must be a valid float, and not 'NaN'"
+        );
+    }
+
+    #[test]
+    fn resolve_scaled_step_time() {
+        assert_eq!(
+            resolve_scaled_step(&2500, 3, Some(1e-3)).unwrap(),
+            ("2500".to_string(), "2.5".to_string())
+        );
+        assert_eq!(
+            resolve_scaled_step("0.5", 3, None).unwrap(),
+            ("0.5".to_string(), "0.5".to_string())
+        );
+        assert_eq!(
+            resolve_scaled_step(&StepLabel("initial".to_string()), 3, Some(1e-3)).unwrap(),
+            ("initial".to_string(), "3".to_string())
+        );
+
+        for time_scale in [0.0, -1.0, f64::NAN] {
+            assert_eq!(
+                resolve_scaled_step(&1.0, 3, Some(time_scale))
+                    .unwrap_err()
+                    .to_string(),
+                format!("Time scale must be a positive finite number, and not '{time_scale}'")
+            );
+        }
+    }
+
+    #[test]
+    fn resolve_step_formatted_time() {
+        assert_eq!(
+            resolve_step(&FormattedTime::new(0.1, TimeFormat::Shortest), 3).unwrap(),
+            ("0.1".to_string(), "0.1".to_string())
+        );
+        assert_eq!(
+            resolve_step(&FormattedTime::new(0.1, TimeFormat::Fixed(3)), 3).unwrap(),
+            ("0.100".to_string(), "0.1".to_string())
+        );
+        assert_eq!(
+            resolve_step(&FormattedTime::new(0.000_125, TimeFormat::Scientific(2)), 3).unwrap(),
+            ("1.25e-4".to_string(), "0.000125".to_string())
+        );
+        assert_eq!(
+            resolve_step(&FormattedTime::new(f64::INFINITY, TimeFormat::Fixed(3)), 3)
+                .unwrap_err()
+                .to_string(),
+            "Time must be a valid float, and not 'inf'"
+        );
+    }
+
+    #[test]
+    fn resolve_step_cycle() {
+        assert_eq!(
+            resolve_step(&7_u64, 3).unwrap(),
+            ("7".to_string(), "7".to_string())
+        );
+        assert_eq!(
+            resolve_step(&-2, 3).unwrap(),
+            ("-2".to_string(), "-2".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_step_with_cycle() {
+        let step = WithCycle::new(FormattedTime::new(0.5, TimeFormat::Fixed(2)), 12);
+        assert_eq!(
+            resolve_step(&step, 3).unwrap(),
+            ("0.50".to_string(), "0.5".to_string())
+        );
+        assert_eq!(step.cycle(), Some(12));
+        assert_eq!(0.5.cycle(), None);
+    }
+
+    #[test]
+    fn resolve_step_label() {
+        assert_eq!(
+            resolve_step(&StepLabel("load_case-1".to_string()), 3).unwrap(),
+            ("load_case-1".to_string(), "3".to_string())
+        );
+
+        for label in ["", "a/b", "with space"] {
+            assert_eq!(
+                resolve_step(&StepLabel(label.to_string()), 3)
+                    .unwrap_err()
+                    .to_string(),
+                format!(
+                    "Step label '{label}' is not valid, must be non-empty and contain only alphanumeric characters, underscores, dashes, dots or plus signs"
+                )
+            );
+        }
+    }
+}
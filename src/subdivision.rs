@@ -0,0 +1,380 @@
+//! This module contains the subdivision of quadratic cells into linear cells, for readers that do not support quadratic cells.
+//!
+//! The linear cells only use the existing points, hence the point data is written unchanged,
+//! while the data of a subdivided cell is written for each of its linear cells.
+//! XDMF uses the node ordering of VTK, the tables are derived from the definitions of the nodes in its documentation.
+
+use crate::CellType;
+
+/// Connectivity and types of the linear cells, and the index of the given cell of each linear cell
+pub(crate) type LinearCells = (Vec<u64>, Vec<CellType>, Vec<usize>);
+
+// linear cells of a cell type, each with the local indices of its nodes
+type Subdivision = &'static [(CellType, &'static [&'static [usize]])];
+
+const EDGE3: &[&[usize]] = &[&[0, 2], &[2, 1]];
+
+const TRIANGLE6: &[&[usize]] = &[&[0, 3, 5], &[3, 1, 4], &[5, 4, 2], &[3, 4, 5]];
+
+// without the center node, the corners are cut off as triangles around the quadrilateral of the mid-edge nodes
+const QUADRILATERAL8_TRIANGLES: &[&[usize]] = &[&[0, 4, 7], &[4, 1, 5], &[5, 2, 6], &[6, 3, 7]];
+const QUADRILATERAL8_QUADRILATERALS: &[&[usize]] = &[&[4, 5, 6, 7]];
+
+const QUADRILATERAL9: &[&[usize]] = &[&[0, 4, 8, 7], &[4, 1, 5, 8], &[8, 5, 2, 6], &[7, 8, 6, 3]];
+
+// the corners are cut off as tetrahedra, the remaining octahedron is split along the diagonal between nodes 6 and 8
+const TETRAHEDRON10: &[&[usize]] = &[
+    &[0, 4, 6, 7],
+    &[4, 1, 5, 8],
+    &[6, 5, 2, 9],
+    &[7, 8, 9, 3],
+    &[6, 8, 4, 5],
+    &[6, 8, 5, 9],
+    &[6, 8, 9, 7],
+    &[6, 8, 7, 4],
+];
+
+// the triangles of the bottom, middle and top layer are subdivided like a `Triangle6`
+const WEDGE18: &[&[usize]] = &[
+    &[0, 6, 8, 12, 15, 17],
+    &[6, 1, 7, 15, 13, 16],
+    &[8, 7, 2, 17, 16, 14],
+    &[6, 7, 8, 15, 16, 17],
+    &[12, 15, 17, 3, 9, 11],
+    &[15, 13, 16, 9, 4, 10],
+    &[17, 16, 14, 11, 10, 5],
+    &[15, 16, 17, 9, 10, 11],
+];
+
+const HEXAHEDRON27: &[&[usize]] = &[
+    &[0, 8, 24, 11, 16, 22, 26, 20],
+    &[8, 1, 9, 24, 22, 17, 21, 26],
+    &[24, 9, 2, 10, 26, 21, 18, 23],
+    &[11, 24, 10, 3, 20, 26, 23, 19],
+    &[16, 22, 26, 20, 4, 12, 25, 15],
+    &[22, 17, 21, 26, 12, 5, 13, 25],
+    &[26, 21, 18, 23, 25, 13, 6, 14],
+    &[20, 26, 23, 19, 15, 25, 14, 7],
+];
+
+// cells without interior nodes can not be subdivided into linear cells of the same kind, they are reduced to their corners
+const PYRAMID_CORNERS: &[&[usize]] = &[&[0, 1, 2, 3, 4]];
+const WEDGE_CORNERS: &[&[usize]] = &[&[0, 1, 2, 3, 4, 5]];
+const HEXAHEDRON_CORNERS: &[&[usize]] = &[&[0, 1, 2, 3, 4, 5, 6, 7]];
+
+// the linear cells a quadratic cell is subdivided into, with the local indices of their nodes, None for linear cells
+fn linear_cells(cell_type: CellType) -> Option<Subdivision> {
+    Some(match cell_type {
+        CellType::Edge3 => &[(CellType::Edge, EDGE3)],
+        CellType::Triangle6 => &[(CellType::Triangle, TRIANGLE6)],
+        CellType::Quadrilateral8 => &[
+            (CellType::Triangle, QUADRILATERAL8_TRIANGLES),
+            (CellType::Quadrilateral, QUADRILATERAL8_QUADRILATERALS),
+        ],
+        CellType::Quadrilateral9 => &[(CellType::Quadrilateral, QUADRILATERAL9)],
+        CellType::Tetrahedron10 => &[(CellType::Tetrahedron, TETRAHEDRON10)],
+        CellType::Pyramid13 => &[(CellType::Pyramid, PYRAMID_CORNERS)],
+        CellType::Wedge15 => &[(CellType::Wedge, WEDGE_CORNERS)],
+        CellType::Wedge18 => &[(CellType::Wedge, WEDGE18)],
+        CellType::Hexahedron20 | CellType::Hexahedron24 => {
+            &[(CellType::Hexahedron, HEXAHEDRON_CORNERS)]
+        }
+        CellType::Hexahedron27 => &[(CellType::Hexahedron, HEXAHEDRON27)],
+        CellType::Vertex
+        | CellType::Edge
+        | CellType::Triangle
+        | CellType::Quadrilateral
+        | CellType::Tetrahedron
+        | CellType::Pyramid
+        | CellType::Wedge
+        | CellType::Hexahedron
+        | CellType::Polygon(_)
+        | CellType::Polyhedron(_) => return None,
+    })
+}
+
+/// Subdivide the quadratic cells into linear cells, None if the mesh contains no quadratic cells.
+///
+/// Returns the connectivity and the types of the linear cells, and the index of the given cell of each linear cell.
+/// The connectivity must be consistent with the cell types.
+pub(crate) fn subdivide_quadratic_cells(
+    connectivity: &[u64],
+    cell_types: &[CellType],
+) -> Option<LinearCells> {
+    if !cell_types
+        .iter()
+        .any(|&cell_type| linear_cells(cell_type).is_some())
+    {
+        return None;
+    }
+
+    let mut linear_connectivity = Vec::with_capacity(connectivity.len());
+    let mut linear_cell_types = Vec::with_capacity(cell_types.len());
+    let mut cell_map = Vec::with_capacity(cell_types.len());
+    let mut offset = 0;
+
+    for (cell, &cell_type) in cell_types.iter().enumerate() {
+        let nodes = &connectivity[offset..offset + cell_type.num_points()];
+        offset += cell_type.num_points();
+
+        let Some(sub_cells) = linear_cells(cell_type) else {
+            linear_connectivity.extend_from_slice(nodes);
+            linear_cell_types.push(cell_type);
+            cell_map.push(cell);
+            continue;
+        };
+
+        for &(sub_cell_type, sub_cells) in sub_cells {
+            for sub_cell in sub_cells {
+                linear_connectivity.extend(sub_cell.iter().map(|&node| nodes[node]));
+                linear_cell_types.push(sub_cell_type);
+                cell_map.push(cell);
+            }
+        }
+    }
+
+    Some((linear_connectivity, linear_cell_types, cell_map))
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::assert_approx_eq;
+
+    use super::*;
+
+    // coordinates of the higher-order nodes as the mean of the given corners
+    fn with_midpoints(corners: &[[f64; 3]], nodes: &[&[usize]]) -> Vec<[f64; 3]> {
+        let mut coordinates = corners.to_vec();
+        coordinates.extend(nodes.iter().map(|node_corners| {
+            let num_corners = node_corners.len() as f64;
+            std::array::from_fn(|i| {
+                node_corners.iter().map(|&c| corners[c][i]).sum::<f64>() / num_corners
+            })
+        }));
+        coordinates
+    }
+
+    // area of a polygon in the xy-plane (shoelace formula), positive if its nodes are ordered counterclockwise
+    fn signed_area(points: &[[f64; 3]]) -> f64 {
+        let shifted = points.iter().cycle().skip(1);
+        points
+            .iter()
+            .zip(shifted)
+            .map(|(p, q)| p[0] * q[1] - q[0] * p[1])
+            .sum::<f64>()
+            / 2.0
+    }
+
+    // volume of the parallelepiped spanned by the edges from the first node, positive for a correctly oriented cell
+    fn oriented_volume(points: &[[f64; 3]], [a, b, c]: [usize; 3]) -> f64 {
+        let edge = |i: usize| -> [f64; 3] { std::array::from_fn(|j| points[i][j] - points[0][j]) };
+        let (a, b, c) = (edge(a), edge(b), edge(c));
+
+        a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+            + a[2] * (b[0] * c[1] - b[1] * c[0])
+    }
+
+    fn subdivide(cell_type: CellType) -> (Vec<u64>, Vec<CellType>, Vec<usize>) {
+        let connectivity: Vec<u64> = (0..cell_type.num_points() as u64).collect();
+        subdivide_quadratic_cells(&connectivity, &[cell_type]).unwrap()
+    }
+
+    // the linear cells of a single quadratic cell, with the coordinates of their nodes
+    fn linear_cell_points(cell_type: CellType, coordinates: &[[f64; 3]]) -> Vec<Vec<[f64; 3]>> {
+        let (connectivity, cell_types, _) = subdivide(cell_type);
+        let mut offset = 0;
+
+        cell_types
+            .iter()
+            .map(|linear_cell_type| {
+                let nodes = &connectivity[offset..offset + linear_cell_type.num_points()];
+                offset += linear_cell_type.num_points();
+                nodes
+                    .iter()
+                    .map(|&node| coordinates[node as usize])
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn subdivide_mixed_cells() {
+        let connectivity = [0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 4];
+        let cell_types = [CellType::Triangle6, CellType::Edge, CellType::Edge3];
+
+        let (linear_connectivity, linear_cell_types, cell_map) =
+            subdivide_quadratic_cells(&connectivity, &cell_types).unwrap();
+
+        assert_eq!(
+            linear_connectivity,
+            [0, 3, 5, 3, 1, 4, 5, 4, 2, 3, 4, 5, 6, 7, 0, 4, 4, 1]
+        );
+        assert_eq!(
+            linear_cell_types,
+            [
+                CellType::Triangle,
+                CellType::Triangle,
+                CellType::Triangle,
+                CellType::Triangle,
+                CellType::Edge,
+                CellType::Edge,
+                CellType::Edge
+            ]
+        );
+        assert_eq!(cell_map, [0, 0, 0, 0, 1, 2, 2]);
+
+        // linear meshes are not subdivided
+        assert!(
+            subdivide_quadratic_cells(&[0, 1, 2, 3], &[CellType::Triangle, CellType::Polygon(1)])
+                .is_none()
+        );
+
+        let (_, cell_types, _) = subdivide(CellType::Hexahedron20);
+        assert_eq!(cell_types, [CellType::Hexahedron]);
+    }
+
+    #[test]
+    fn subdivide_surface_cells() {
+        let corners = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+        ];
+        let quadrilateral9 = with_midpoints(
+            &corners,
+            &[&[0, 1], &[1, 2], &[2, 3], &[3, 0], &[0, 1, 2, 3]],
+        );
+        let triangle6 = with_midpoints(&corners[..3], &[&[0, 1], &[1, 2], &[2, 0]]);
+
+        for (cell_type, coordinates, num_linear_cells) in [
+            (CellType::Triangle6, &triangle6[..], 4),
+            (CellType::Quadrilateral8, &quadrilateral9[..8], 5),
+            (CellType::Quadrilateral9, &quadrilateral9[..], 4),
+        ] {
+            let linear_cells = linear_cell_points(cell_type, coordinates);
+            assert_eq!(linear_cells.len(), num_linear_cells, "{cell_type:?}");
+
+            // the linear cells are oriented counterclockwise, as the cell
+            let area: f64 = linear_cells
+                .iter()
+                .map(|points| {
+                    let area = signed_area(points);
+                    assert!(area > 0.0, "{cell_type:?}: {points:?}");
+                    area
+                })
+                .sum();
+            let expected_area = if cell_type == CellType::Triangle6 {
+                0.5
+            } else {
+                1.0
+            };
+            assert_approx_eq!(f64, area, expected_area, ulps = 4);
+        }
+    }
+
+    #[test]
+    fn subdivide_volume_cells() {
+        let tetrahedron = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+        ];
+        let tetrahedron10 = with_midpoints(
+            &tetrahedron,
+            &[&[0, 1], &[1, 2], &[0, 2], &[0, 3], &[1, 3], &[2, 3]],
+        );
+
+        let volume: f64 = linear_cell_points(CellType::Tetrahedron10, &tetrahedron10)
+            .iter()
+            .map(|points| {
+                let volume = oriented_volume(points, [1, 2, 3]) / 6.0;
+                assert!(volume > 0.0, "{points:?}");
+                volume
+            })
+            .sum();
+        assert_approx_eq!(f64, volume, 1.0 / 6.0, ulps = 4);
+
+        let wedge = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [0.0, 1.0, 1.0],
+        ];
+        let wedge18 = with_midpoints(
+            &wedge,
+            &[
+                &[0, 1],
+                &[1, 2],
+                &[2, 0],
+                &[3, 4],
+                &[4, 5],
+                &[5, 3],
+                &[0, 3],
+                &[1, 4],
+                &[2, 5],
+                &[0, 1, 4, 3],
+                &[1, 2, 5, 4],
+                &[2, 0, 3, 5],
+            ],
+        );
+
+        let volume: f64 = linear_cell_points(CellType::Wedge18, &wedge18)
+            .iter()
+            .map(|points| {
+                let volume = oriented_volume(points, [1, 2, 3]) / 2.0;
+                assert!(volume > 0.0, "{points:?}");
+                volume
+            })
+            .sum();
+        assert_approx_eq!(f64, volume, 0.5, ulps = 4);
+
+        let hexahedron = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [1.0, 1.0, 1.0],
+            [0.0, 1.0, 1.0],
+        ];
+        let hexahedron27 = with_midpoints(
+            &hexahedron,
+            &[
+                &[0, 1],
+                &[1, 2],
+                &[2, 3],
+                &[3, 0],
+                &[4, 5],
+                &[5, 6],
+                &[6, 7],
+                &[7, 4],
+                &[0, 4],
+                &[1, 5],
+                &[2, 6],
+                &[3, 7],
+                &[0, 3, 7, 4],
+                &[1, 2, 6, 5],
+                &[0, 1, 5, 4],
+                &[3, 2, 6, 7],
+                &[0, 1, 2, 3],
+                &[4, 5, 6, 7],
+                &[0, 1, 2, 3, 4, 5, 6, 7],
+            ],
+        );
+
+        let volume: f64 = linear_cell_points(CellType::Hexahedron27, &hexahedron27)
+            .iter()
+            .map(|points| {
+                let volume = oriented_volume(points, [1, 3, 4]);
+                assert!(volume > 0.0, "{points:?}");
+                volume
+            })
+            .sum();
+        assert_approx_eq!(f64, volume, 1.0, ulps = 4);
+    }
+}
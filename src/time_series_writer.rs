@@ -7,26 +7,73 @@
 
 use std::{
     collections::{BTreeMap, HashSet},
-    io::{BufWriter, Error as IoError, ErrorKind::InvalidInput, Result as IoResult, Write},
+    io::{BufWriter, Write},
+    ops::Range,
     path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
 };
 
+use xxhash_rust::xxh64::{Xxh64, xxh64};
+
 use crate::{
-    CellType, DataMap, DataStorage, DataWriter, create_writer, mpi_safe_create_dir_all,
+    CellType, DataAttribute, DataMap, DataStorage, DataWriter, FieldData, FileSystem,
+    FloatPrecision, FlushPolicy, Hdf5Options, IndexPrecision, LocalFileSystem, MeshPart,
+    MeshSource, NameCollisionPolicy, NanPolicy, PathPolicy, ReferenceStyle, RetentionPolicy,
+    TimeSeriesWriterBuilder, Transform, Values, XdmfError, XdmfResult, XmlHeader,
+    ascii_writer::values_to_string,
+    averaging::{AveragedField, CellPoints},
+    create_writer,
+    data_item_registry::DataItemRegistry,
+    dictionary::{COMPONENT_NAMES_NAME, FieldDescriptor},
+    number_format::{FormatNumber, parse_time},
+    progress::{ByteCounter, ProgressCallback, ProgressWriter, WriteEvent, WriteOperation},
+    quality::{QualityMetric, quality_data},
+    select_data_storage,
+    step_key::{StepKey, resolve_scaled_step, resolve_step},
+    subdivision::{LinearCells, subdivide_quadratic_cells},
+    values::{CHECKSUM_NAME, CHECKSUM_PREFIX},
     xdmf_elements::{
-        Information, Xdmf, attribute,
-        data_item::{DataItem, NumberType},
+        Domain, Information, Xdmf, attribute,
+        data_item::{DataContent, DataItem, Format, NumberType, Reference, XInclude},
         dimensions::Dimensions,
         geometry::{Geometry, GeometryType},
-        grid::{CollectionType, Grid, GridType, Time},
+        grid::{CYCLE_INFORMATION_NAME, CollectionType, Grid, GridType, Time},
+        set::{Set, SetType},
         topology::{Topology, TopologyType},
     },
 };
 
+/// Label under which the static data is written, instead of the label of a time step
+const STATIC_DATA_LABEL: &str = "static";
+
+/// Name of the `Information` holding the unit of the times
+const TIME_UNIT_INFORMATION_NAME: &str = "time_unit";
+
 /// Writer for time series data in XDMF format.
 pub struct TimeSeriesWriter {
     xdmf_file_name: PathBuf,
     writer: Box<dyn DataWriter>,
+    name_suffix: String,
+    information: Vec<Information>,
+    field_information: BTreeMap<String, Vec<Information>>,
+    flush_policy: FlushPolicy,
+    retention_policy: RetentionPolicy,
+    time_list: bool,
+    latest_step_file: bool,
+    // number of time steps per XDMF file, if the time steps are split into multiple files
+    rollover: Option<usize>,
+    time_scale: Option<f64>,
+    tensor6_as_matrix: bool,
+    transform: Option<Transform>,
+    file_system: Arc<dyn FileSystem>,
+    // output frequency of the fields that are not written at every time step
+    output_frequencies: BTreeMap<String, usize>,
+    reference_style: ReferenceStyle,
+    xml_header: XmlHeader,
+    progress_callback: Option<ProgressCallback>,
+    subdivide_quadratic: bool,
+    field_options: FieldOptions,
 }
 
 impl TimeSeriesWriter {
@@ -36,435 +83,6173 @@ impl TimeSeriesWriter {
     /// let xdmf_writer = TimeSeriesWriter::new("name_xdmf_file", xdmf::DataStorage::AsciiInline)
     ///     .expect("failed to create XDMF writer");
     /// ```
-    pub fn new(file_name: impl AsRef<Path>, data_storage: DataStorage) -> IoResult<Self> {
-        let xdmf_file_name = file_name.as_ref().to_path_buf().with_extension("xdmf2");
+    pub fn new(file_name: impl AsRef<Path>, data_storage: DataStorage) -> XdmfResult<Self> {
+        let file_system: Arc<dyn FileSystem> = Arc::new(LocalFileSystem);
 
-        validate_file_name(&xdmf_file_name)?;
+        Self::new_with_writer(file_name.as_ref(), file_system.clone(), || {
+            create_writer(file_name.as_ref(), data_storage, &file_system)
+        })
+    }
 
-        // create the parent directory if it does not exist
-        if let Some(parent) = xdmf_file_name.parent() {
-            mpi_safe_create_dir_all(parent)?;
+    /// Create a new `TimeSeriesWriter`, writing all files to the given [`FileSystem`] instead of to disk.
+    ///
+    /// E.g. with the [`MemoryFileSystem`](crate::MemoryFileSystem) the XDMF file and the heavy data are kept in memory, to send them to an in-situ visualization service.
+    /// The HDF5 data storages are not supported, as the HDF5 library creates its files itself.
+    /// See [`MemoryFileSystem`](crate::MemoryFileSystem) for an example.
+    pub fn new_with_file_system(
+        file_name: impl AsRef<Path>,
+        data_storage: DataStorage,
+        file_system: impl FileSystem + 'static,
+    ) -> XdmfResult<Self> {
+        if matches!(
+            data_storage,
+            DataStorage::Hdf5SingleFile | DataStorage::Hdf5MultipleFiles
+        ) {
+            return Err(XdmfError::Unsupported(format!(
+                "{data_storage:?} DataStorage can only write to the local file system"
+            )));
         }
 
-        Ok(Self {
-            xdmf_file_name,
-            writer: create_writer(file_name.as_ref(), data_storage)?,
+        let file_system: Arc<dyn FileSystem> = Arc::new(file_system);
+
+        Self::new_with_writer(file_name.as_ref(), file_system.clone(), || {
+            create_writer(file_name.as_ref(), data_storage, &file_system)
         })
     }
 
-    /// Writes the mesh to the XDMF file, returning a `TimeSeriesDataWriter` for writing time steps.
+    /// Create a new `TimeSeriesWriter`, writing the heavy data with a custom [`DataWriter`], e.g. to an object store.
     ///
-    /// Sizes of the inputs are validated to ensure consistency with the mesh and defined cell types.
+    /// The XDMF file is written to disk, it refers to the heavy data with the [`DataContent`] returned by the writer.
+    /// See [`DataWriter`] for the contract of the writer.
+    pub fn new_with_data_writer(
+        file_name: impl AsRef<Path>,
+        data_writer: impl DataWriter + 'static,
+    ) -> XdmfResult<Self> {
+        Self::new_with_writer(file_name.as_ref(), Arc::new(LocalFileSystem), || {
+            Ok(Box::new(data_writer))
+        })
+    }
+
+    /// Create a new `TimeSeriesWriter` that writes the heavy data into a group of a shared HDF5 file, e.g. for the cases of a parameter sweep.
+    ///
+    /// Each case has its own XDMF file, while the heavy data of all cases is written to `<h5_file_name>.h5`, each into its own `group` (e.g. `"/case_42"`).
+    /// An existing file is opened and its other groups are kept, but the group must not exist yet. The data is written as with [`DataStorage::Hdf5SingleFile`].
     /// ```rust
     /// use xdmf::TimeSeriesWriter;
-    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_mesh", xdmf::DataStorage::AsciiInline)
+    /// # if xdmf::is_hdf5_enabled() {
+    /// for case in 0..3 {
+    ///     let xdmf_writer = TimeSeriesWriter::new_with_hdf5_group(
+    ///         format!("sweep_case_{case}"),
+    ///         "sweep_results",
+    ///         &format!("/case_{case}"),
+    ///     )
     ///     .expect("failed to create XDMF writer");
+    /// }
+    /// # }
+    /// ```
+    pub fn new_with_hdf5_group(
+        file_name: impl AsRef<Path>,
+        h5_file_name: impl AsRef<Path>,
+        group: &str,
+    ) -> XdmfResult<Self> {
+        let group = group.trim_matches('/');
+
+        if group.is_empty() {
+            return Err(XdmfError::Validation(
+                "Group of the HDF5 file must not be empty or the root group".into(),
+            ));
+        }
+
+        Self::new_with_writer(file_name.as_ref(), Arc::new(LocalFileSystem), || {
+            #[cfg(feature = "hdf5")]
+            {
+                Ok(Box::new(
+                    crate::hdf5_writer::SingleFileHdf5Writer::new_in_group(
+                        h5_file_name.as_ref(),
+                        &format!("/{group}"),
+                    )?,
+                ))
+            }
+            #[cfg(not(feature = "hdf5"))]
+            {
+                let _ = h5_file_name;
+                Err(XdmfError::Unsupported(
+                    "Writing into a group of an HDF5 file requires the hdf5 feature.".into(),
+                ))
+            }
+        })
+    }
+
+    /// Create a builder for a `TimeSeriesWriter`, which allows to set all options in one place.
     ///
-    /// // define 3 points and 2 cells (a line and a triangle)
-    /// let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
-    /// let connectivity = [0, 1, 0, 2, 1]; // line (0,1) and triangle (0,2,1)
-    /// let cell_types = [xdmf::CellType::Edge, xdmf::CellType::Triangle];
-    ///
-    /// // write the mesh
-    /// let mut ts_writer = xdmf_writer.write_mesh(&coords, (&connectivity, &cell_types));
+    /// See [`TimeSeriesWriterBuilder`] for the available options and their defaults.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::builder()
+    ///     .basename("name_xdmf_file_builder")
+    ///     .storage(xdmf::DataStorage::AsciiInline)
+    ///     .build()
+    ///     .expect("failed to create XDMF writer");
     /// ```
-    pub fn write_mesh(
-        mut self,
-        points: &[f64],
-        cells: (&[u64], &[CellType]),
-    ) -> IoResult<TimeSeriesDataWriter> {
-        validate_points_and_cells(points, cells)?;
+    pub fn builder() -> TimeSeriesWriterBuilder {
+        TimeSeriesWriterBuilder::default()
+    }
 
-        let num_points = points.len() / 3;
-        let num_cells = if cells.1.is_empty() {
-            num_points
-        } else {
-            cells.1.len()
-        };
+    // the writer of the heavy data is created once the file name was validated
+    pub(crate) fn new_with_writer(
+        file_name: &Path,
+        file_system: Arc<dyn FileSystem>,
+        create_data_writer: impl FnOnce() -> XdmfResult<Box<dyn DataWriter>>,
+    ) -> XdmfResult<Self> {
+        let xdmf_file_name = file_name.to_path_buf().with_extension("xdmf2");
 
-        let (topo_type, prepared_cells) = prepare_cells(cells, num_points);
+        validate_file_name(&xdmf_file_name)?;
 
-        let (points_data, cells_data) = self.writer.write_mesh(points, &prepared_cells)?;
+        // create the parent directory if it does not exist
+        if let Some(parent) = xdmf_file_name.parent() {
+            file_system.create_dir_all(parent)?;
+        }
 
-        let data_item_coords = DataItem {
-            name: Some("coords".to_string()),
-            dimensions: Some(Dimensions(vec![num_points, 3])),
-            data: points_data,
-            number_type: Some(NumberType::Float),
-            precision: Some(8),
-            format: Some(self.writer.format()),
-            reference: None,
-        };
+        Ok(Self {
+            xdmf_file_name,
+            writer: create_data_writer()?,
+            name_suffix: String::new(),
+            information: Vec::new(),
+            field_information: BTreeMap::new(),
+            flush_policy: FlushPolicy::default(),
+            retention_policy: RetentionPolicy::default(),
+            time_list: false,
+            latest_step_file: false,
+            rollover: None,
+            time_scale: None,
+            tensor6_as_matrix: false,
+            transform: None,
+            file_system,
+            output_frequencies: BTreeMap::new(),
+            reference_style: ReferenceStyle::default(),
+            xml_header: XmlHeader::default(),
+            progress_callback: None,
+            subdivide_quadratic: false,
+            field_options: FieldOptions::default(),
+        })
+    }
 
-        let data_item_connectivity = DataItem {
-            name: Some("connectivity".to_string()),
-            dimensions: Some(Dimensions(vec![prepared_cells.len()])),
-            number_type: Some(NumberType::UInt),
-            data: cells_data,
-            format: Some(self.writer.format()),
-            precision: Some(8),
-            reference: None,
-        };
+    /// Create a new `TimeSeriesWriter`, falling back to another `DataStorage` if the chosen one is not available.
+    ///
+    /// This allows to use the same application on systems without HDF5, a warning is logged when falling back.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new_with_fallback(
+    ///     "name_xdmf_file_fallback",
+    ///     xdmf::DataStorage::Hdf5SingleFile,
+    ///     xdmf::DataStorage::Ascii,
+    /// )
+    /// .expect("failed to create XDMF writer");
+    /// ```
+    pub fn new_with_fallback(
+        file_name: impl AsRef<Path>,
+        data_storage: DataStorage,
+        fallback: DataStorage,
+    ) -> XdmfResult<Self> {
+        Self::new(file_name, select_data_storage(data_storage, fallback)?)
+    }
 
-        let data_item_coords_ref =
-            DataItem::new_reference(&data_item_coords, "/Xdmf/Domain/DataItem");
-        let data_item_connectivity_ref =
-            DataItem::new_reference(&data_item_connectivity, "/Xdmf/Domain/DataItem");
+    /// Limit the size (in bytes) of each data array that is written inline into the XDMF file.
+    ///
+    /// Writing data that exceeds the limit results in an error, which protects from accidentally creating huge XML files.
+    /// Only affects [`DataStorage::AsciiInline`] and [`DataStorage::Base64Inline`], as the other storages do not write the data inline.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new("name_xdmf_file_limit", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer")
+    ///     .with_inline_size_limit(10 * 1024 * 1024); // 10 MB
+    /// ```
+    pub fn with_inline_size_limit(mut self, size_limit: usize) -> Self {
+        self.writer.set_inline_size_limit(size_limit);
+        self
+    }
 
-        let geometry = Geometry {
-            geometry_type: GeometryType::XYZ,
-            data_item: data_item_coords_ref,
-        };
-        let topology = Topology {
-            topology_type: topo_type,
-            number_of_elements: num_cells.to_string(),
-            data_item: data_item_connectivity_ref,
-        };
+    /// Set how the paths to the files of the heavy data are written, by default they are relative to the XDMF file.
+    ///
+    /// With [`PathPolicy::Absolute`] the XDMF file can be moved without the files of the heavy data, e.g. to a local machine
+    /// while the data stays on a shared file system. Only affects the data storages that write the data into separate files.
+    /// ```rust
+    /// use xdmf::{PathPolicy, TimeSeriesWriter};
+    /// let xdmf_writer = TimeSeriesWriter::new("name_xdmf_file_paths", xdmf::DataStorage::Ascii)
+    ///     .expect("failed to create XDMF writer")
+    ///     .with_path_policy(PathPolicy::Absolute);
+    /// ```
+    pub fn with_path_policy(mut self, path_policy: PathPolicy) -> Self {
+        self.writer.set_path_policy(path_policy);
+        self
+    }
 
-        let mut ts_writer = TimeSeriesDataWriter {
-            xdmf_file_name: self.xdmf_file_name,
-            writer: self.writer,
-            grid: Grid::new_uniform("mesh", geometry, topology),
-            data_items: vec![data_item_coords, data_item_connectivity],
-            attributes: vec![],
-            writen_times: HashSet::new(),
-            num_points,
-            num_cells,
-        };
+    /// Add custom information to the XDMF file, e.g. the version of the solver or the git hash of the input.
+    ///
+    /// It is written as `Information` element next to the information written by this crate.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new("name_xdmf_file_info", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer")
+    ///     .with_information("solver_version", "1.2.3");
+    /// ```
+    pub fn with_information(mut self, name: impl ToString, value: impl ToString) -> Self {
+        self.information.push(Information::new(name, value));
+        self
+    }
 
-        ts_writer.write()?;
+    /// Add custom information to a field, e.g. its units.
+    ///
+    /// It is written as `Information` element into each `Attribute` of the field (for all time steps and centers).
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("name_xdmf_file_field_info", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer")
+    ///         .with_field_information("temperature", "units", "K");
+    /// ```
+    pub fn with_field_information(
+        mut self,
+        field_name: impl ToString,
+        name: impl ToString,
+        value: impl ToString,
+    ) -> Self {
+        self.field_information
+            .entry(field_name.to_string())
+            .or_default()
+            .push(Information::new(name, value));
+        self
+    }
 
-        Ok(ts_writer)
+    /// Add the units and the description of a field, see the [`dictionary`](crate::dictionary) for common quantities.
+    ///
+    /// They are written as `Information` elements named "units" and "description", like [`TimeSeriesWriter::with_field_information`].
+    /// ```rust
+    /// use xdmf::{
+    ///     TimeSeriesWriter,
+    ///     dictionary::{Quantity, UnitSystem},
+    /// };
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "name_xdmf_file_field_descriptor",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer")
+    /// .with_field_descriptor(&Quantity::Temperature.descriptor(UnitSystem::Si));
+    /// ```
+    pub fn with_field_descriptor(mut self, descriptor: &FieldDescriptor) -> Self {
+        self.field_information
+            .entry(descriptor.name().to_string())
+            .or_default()
+            .extend(descriptor.information());
+        self
     }
-}
 
-// Validate that the points and cells are valid
-fn validate_points_and_cells(points: &[f64], cells: (&[u64], &[CellType])) -> IoResult<()> {
-    // at least one point is required
-    if points.is_empty() {
-        return Err(IoError::new(InvalidInput, "At least one point is required"));
+    /// Name the components of a vector, tensor or matrix field, e.g. `["vx", "vy", "vz"]` instead of 0, 1 and 2.
+    ///
+    /// The names are written as `Information` element named `component_names` into each `Attribute` of the field,
+    /// separated by spaces, i.e. they must not contain whitespace. Writing the field fails if the number of names
+    /// does not match the number of components of its [`DataAttribute`].
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "name_xdmf_file_component_names",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer")
+    /// .with_component_names("velocity", &["vx", "vy", "vz"])
+    /// .with_component_names("stress", &["sxx", "sxy", "sxz", "syy", "syz", "szz"]);
+    /// ```
+    pub fn with_component_names(
+        self,
+        field_name: impl ToString,
+        component_names: &[impl ToString],
+    ) -> Self {
+        let component_names: Vec<String> =
+            component_names.iter().map(ToString::to_string).collect();
+        self.with_field_information(field_name, COMPONENT_NAMES_NAME, component_names.join(" "))
     }
 
-    // check that points are a multiple of 3 (x, y, z)
-    if !points.len().is_multiple_of(3) {
-        return Err(IoError::new(InvalidInput, "Points must have 3 dimensions"));
+    /// Set how often the XDMF file is rewritten, by default it is rewritten after every written time step.
+    ///
+    /// Rewriting the XDMF file after every time step is crash-safe, but the work grows with the number of time steps.
+    /// With [`FlushPolicy::EveryN`] or [`FlushPolicy::OnFinalize`] redundant rewrites are skipped,
+    /// the pending time steps are written with `flush_xml`, and when the writer is closed or dropped.
+    /// ```rust
+    /// use xdmf::{FlushPolicy, TimeSeriesWriter};
+    /// let xdmf_writer = TimeSeriesWriter::new("name_xdmf_file_flush", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer")
+    ///     .with_flush_policy(FlushPolicy::EveryN(10));
+    /// ```
+    pub fn with_flush_policy(mut self, flush_policy: FlushPolicy) -> Self {
+        self.flush_policy = flush_policy;
+        self
     }
 
-    // check cells connectivity indices
-    let max_connectivity_index = cells.0.iter().max();
+    /// Set which time steps are kept, e.g. only the last N time steps of a long-running simulation.
+    ///
+    /// Whenever a time step is written, the time steps that are no longer kept are removed from the XDMF file.
+    /// Their heavy data is deleted once the XDMF file was written without them, for the data storages with separate files per time step.
+    /// The meshes and the static data are not deleted, and a pruned time step can not be written again.
+    /// Only the writers of a single mesh prune time steps, writing multiple meshes (see [`TimeSeriesWriter::add_mesh`])
+    /// or bodies (see [`TimeSeriesWriter::into_multi_body_writer`]) fails with [`XdmfError::Unsupported`] unless all time steps are kept.
+    /// ```rust
+    /// use xdmf::{RetentionPolicy, TimeSeriesWriter};
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("name_xdmf_file_retention", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer")
+    ///         .with_retention_policy(RetentionPolicy::KeepLast(100));
+    /// ```
+    pub fn with_retention_policy(mut self, retention_policy: RetentionPolicy) -> Self {
+        self.retention_policy = retention_policy;
+        self
+    }
 
-    if let Some(&max_index) = max_connectivity_index
-        && max_index as usize >= points.len() / 3
-    {
-        return Err(IoError::new(
-            InvalidInput,
-            format!(
-                "Connectivity indices out of bounds for the given points, max index: {}, but number of points is {}",
-                max_index,
-                points.len() / 3
-            ),
-        ));
+    /// Write the times of all time steps as one list in the temporal collection, instead of one `Time` per time step.
+    ///
+    /// Some readers handle a large number of time steps considerably faster with a `Time` of `TimeType` `List`.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("name_xdmf_file_time_list", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer")
+    ///         .with_time_list();
+    /// ```
+    pub fn with_time_list(mut self) -> Self {
+        self.time_list = true;
+        self
     }
 
-    // check that the number of connectivities matches the expected number based on the cell types
-    let exp_num_points: usize = cells.1.iter().map(|ct| ct.num_points()).sum();
-    if exp_num_points != cells.0.len() {
-        return Err(IoError::new(
-            InvalidInput,
-            format!(
-                "Size of connectivities not match the expected number based on the cell types: {} != {}",
-                cells.0.len(),
-                exp_num_points
-            ),
-        ));
+    /// Write an additional XDMF file with only the most recent time step, e.g. to monitor a running simulation.
+    ///
+    /// The file is named like the XDMF file with the suffix `_latest`, e.g. `case_latest.xdmf2`.
+    /// It is refreshed whenever the XDMF file is written and references the heavy data that is already written, i.e. no data is duplicated.
+    /// Subsets and submeshes are only written to the XDMF file of the whole time series.
+    /// The file is only written for a single mesh, writing multiple meshes (see [`TimeSeriesWriter::add_mesh`])
+    /// or bodies (see [`TimeSeriesWriter::into_multi_body_writer`]) fails with [`XdmfError::Unsupported`] if it is requested.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("name_xdmf_file_latest", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer")
+    ///         .with_latest_step_file(true);
+    /// ```
+    pub fn with_latest_step_file(mut self, latest_step_file: bool) -> Self {
+        self.latest_step_file = latest_step_file;
+        self
     }
 
-    Ok(())
-}
+    /// Split the time steps into multiple XDMF files of `steps_per_file` time steps each, e.g. for tens of thousands of time steps.
+    ///
+    /// The time steps are written in the order of writing into files named like the XDMF file with the index of the file,
+    /// e.g. `case_0000.xdmf2`, `case_0001.xdmf2` and so on. The XDMF file (e.g. `case.xdmf2`) includes the time steps of
+    /// all files with `XInclude`, the heavy data is not affected. A file is only rewritten if its time steps changed,
+    /// hence readers only parse the small files of the time steps they load, and the cost of writing the XML does not grow with the number of time steps.
+    /// Each file contains the mesh as well, i.e. it can also be opened on its own.
+    /// The times stay with the grids of the time steps, i.e. [`Self::with_time_list`] does not apply to the files.
+    /// Only the time steps of a single mesh are split, writing multiple meshes (see [`TimeSeriesWriter::add_mesh`])
+    /// or bodies (see [`TimeSeriesWriter::into_multi_body_writer`]) fails with [`XdmfError::Unsupported`] if a rollover is set.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("name_xdmf_file_rollover", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer")
+    ///         .with_rollover(1000);
+    /// ```
+    pub fn with_rollover(mut self, steps_per_file: usize) -> Self {
+        self.rollover = Some(steps_per_file.max(1));
+        self
+    }
 
-// Poly-cells need to additionally specify the number of points
-fn poly_cell_points(cell_type: CellType) -> Option<u64> {
-    // For polyvertex and polyline, need to add the number of points
-    match cell_type {
-        CellType::Vertex => {
-            // polyvertex with one point
-            Some(1)
-        }
-        CellType::Edge => {
-            // polyline with two points
-            Some(2)
-        }
-        _ => None,
+    /// Multiply the times of the time steps by a factor when writing them, e.g. `1e-9` for a solver time in nanoseconds that is visualized in seconds.
+    ///
+    /// Only the values of the `Time` elements are scaled, the labels of the steps (used in the names of grids and heavy data) are kept.
+    /// Steps without a time (e.g. a [`StepLabel`](crate::StepLabel)) keep their index as time. The factor must be positive.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("name_xdmf_file_time_scale", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer")
+    ///         .with_time_scale(1e-9)
+    ///         .with_time_unit("s");
+    /// ```
+    pub fn with_time_scale(mut self, time_scale: f64) -> Self {
+        self.time_scale = Some(time_scale);
+        self
     }
-}
 
-/// Prepare cells / connectivity for writing. The cell type is prepended to the connectivity list,
-/// and for poly-cells, the number of points is also added.
-/// TODO if all cells are the same, then the type information can be stored as `TopologyType`
-fn prepare_cells(cells: (&[u64], &[CellType]), num_points: usize) -> (TopologyType, Vec<u64>) {
-    if cells.1.is_empty() {
-        // if there are no cells, use polyvertex on nodes
-        // this is required by paraview to visualize only points
-        return (TopologyType::Polyvertex, (0..num_points as u64).collect());
+    /// Write the unit of the times as `Information` named `time_unit` of the XDMF file, e.g. `"s"`.
+    ///
+    /// The unit refers to the written times, i.e. after scaling them with [`TimeSeriesWriter::with_time_scale`].
+    pub fn with_time_unit(self, time_unit: impl ToString) -> Self {
+        self.with_information(TIME_UNIT_INFORMATION_NAME, time_unit)
     }
 
-    let mut cells_with_types = Vec::with_capacity(cells.0.len() + cells.1.len());
-    let mut index = 0_usize;
+    /// Write fields of [`DataAttribute::Tensor6`] with the `AttributeType` `Matrix`, as done by previous versions.
+    ///
+    /// By default they are written as `Tensor6`, with the components in the order of XDMF (XX, XY, XZ, YY, YZ, ZZ).
+    /// Paraview detects a `Matrix` with 6 components as symmetric tensor as well, but expects the components in the order of VTK (XX, YY, ZZ, XY, YZ, XZ).
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "name_xdmf_file_tensor6_as_matrix",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer")
+    /// .with_tensor6_as_matrix();
+    /// ```
+    pub fn with_tensor6_as_matrix(mut self) -> Self {
+        self.tensor6_as_matrix = true;
+        self
+    }
 
-    for cell_type in cells.1 {
-        let num_points = cell_type.num_points();
-        cells_with_types.push(*cell_type as u64);
+    /// Subdivide quadratic cells into linear cells when writing the mesh, for readers that do not display quadratic cells correctly.
+    ///
+    /// All points are kept, hence point data is written unchanged, while the data of each quadratic cell is written for each of its linear cells.
+    /// Cells whose midside points can not be used for the subdivision (`Pyramid13`, `Wedge15`, `Hexahedron20` and `Hexahedron24`) are reduced to their corner points.
+    /// Applies to [`TimeSeriesWriter::write_mesh`], [`TimeSeriesWriter::write_mesh_soa`] and mesh updates, by default the cells are written as given.
+    /// Cell sets, subsets and streamed cell data are not supported for subdivided meshes.
+    /// Writing multiple meshes (see [`TimeSeriesWriter::add_mesh`]) or bodies (see [`TimeSeriesWriter::into_multi_body_writer`])
+    /// fails with [`XdmfError::Unsupported`] if the subdivision is requested.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "name_xdmf_file_subdivide_quadratic",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer")
+    /// .with_subdivide_quadratic(true);
+    /// ```
+    pub fn with_subdivide_quadratic(mut self, subdivide_quadratic: bool) -> Self {
+        self.subdivide_quadratic = subdivide_quadratic;
+        self
+    }
 
-        if let Some(n_points_poly) = poly_cell_points(*cell_type) {
-            // poly-cells need to specify the number of points
-            cells_with_types.push(n_points_poly);
-        }
+    /// Set how the grids refer to the shared `DataItems`, e.g. the coordinates and connectivity of the mesh.
+    ///
+    /// By default they are written once in the domain and referenced by the grids of all time steps.
+    /// Readers that fail to resolve these references (e.g. the `Xdmf3ReaderS` and `Xdmf3ReaderT` of Paraview) can read the files
+    /// written with [`ReferenceStyle::InlinePerGrid`] or [`ReferenceStyle::HdfPathPerGrid`], which copy the `DataItems` into the grids.
+    /// ```rust
+    /// use xdmf::{ReferenceStyle, TimeSeriesWriter};
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("name_xdmf_file_reference_style", xdmf::DataStorage::Ascii)
+    ///         .expect("failed to create XDMF writer")
+    ///         .with_reference_style(ReferenceStyle::HdfPathPerGrid);
+    /// ```
+    pub fn with_reference_style(mut self, reference_style: ReferenceStyle) -> Self {
+        self.reference_style = reference_style;
+        self
+    }
 
-        cells_with_types.extend_from_slice(&cells.0[index..index + num_points]);
+    /// Write the XML declaration and/or the `DOCTYPE` at the beginning of the XDMF file, as required by some readers.
+    ///
+    /// By default neither is written. See [`XmlHeader`] for details.
+    /// ```rust
+    /// use xdmf::{TimeSeriesWriter, XmlHeader};
+    /// let xdmf_writer = TimeSeriesWriter::new("name_xdmf_file_xml_header", xdmf::DataStorage::Ascii)
+    ///     .expect("failed to create XDMF writer")
+    ///     .with_xml_header(
+    ///         XmlHeader::default()
+    ///             .with_declaration(true)
+    ///             .with_doctype(true),
+    ///     );
+    /// ```
+    pub fn with_xml_header(mut self, xml_header: XmlHeader) -> Self {
+        self.xml_header = xml_header;
+        self
+    }
 
-        index += num_points; // move index to the next cell
+    /// Call a function after each write, e.g. to log the progress and the duration of large writes.
+    ///
+    /// It is called with a [`WriteEvent`] after writing the heavy data of each mesh, set and field, and after each write of the XDMF file.
+    /// The event contains the name of the written data, the number of bytes and the duration, e.g. to monitor the I/O on a cluster.
+    /// ```rust
+    /// use xdmf::{TimeSeriesWriter, WriteEvent};
+    /// let xdmf_writer = TimeSeriesWriter::new("name_xdmf_file_progress", xdmf::DataStorage::Ascii)
+    ///     .expect("failed to create XDMF writer")
+    ///     .with_progress_callback(|event: &WriteEvent| {
+    ///         log::info!(
+    ///             "wrote {} bytes of '{}' in {:?}",
+    ///             event.num_bytes,
+    ///             event.name,
+    ///             event.duration
+    ///         );
+    ///     });
+    /// ```
+    pub fn with_progress_callback(
+        mut self,
+        callback: impl Fn(&WriteEvent) + Send + Sync + 'static,
+    ) -> Self {
+        let callback: ProgressCallback = Arc::new(callback);
+        self.writer = Box::new(ProgressWriter::new(self.writer, callback.clone()));
+        self.progress_callback = Some(callback);
+        self
     }
 
-    (TopologyType::Mixed, cells_with_types)
-}
+    /// Transform the coordinates of the points when writing the mesh, e.g. to convert them from millimeters to meters.
+    ///
+    /// The transformation is applied to all meshes written by this writer, including mesh updates and the parts of a mesh tree,
+    /// the coordinates of the caller are not modified. See [`Transform`] for details.
+    /// Image data can only be scaled and translated, as its geometry is aligned with the axes.
+    /// ```rust
+    /// use xdmf::{TimeSeriesWriter, Transform};
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("name_xdmf_file_transform", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer")
+    ///         .with_transform(Transform::default().with_scale(1e-3));
+    /// ```
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = Some(transform);
+        self
+    }
 
-/// Writer for time series data in XDMF format. Can be used after writing the mesh with `TimeSeriesWriter::write_mesh`.
-pub struct TimeSeriesDataWriter {
-    xdmf_file_name: PathBuf,
-    writer: Box<dyn DataWriter>,
-    grid: Grid,
-    data_items: Vec<DataItem>,
-    attributes: Vec<(String, Vec<attribute::Attribute>)>,
-    writen_times: HashSet<String>,
-    num_points: usize,
-    num_cells: usize,
-}
+    /// Set the precision of the floating point data of a field, overriding the precision of the writer.
+    ///
+    /// The precision determines the `Precision` of the `DataItem` and how the values are written,
+    /// e.g. [`FloatPrecision::Single`] writes 8 significant digits instead of 17 in text formats.
+    /// ```rust
+    /// use xdmf::{FloatPrecision, TimeSeriesWriter};
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "name_xdmf_file_field_precision",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer")
+    /// .with_field_precision("temperature", FloatPrecision::Single);
+    /// ```
+    pub fn with_field_precision(
+        mut self,
+        field_name: impl ToString,
+        precision: FloatPrecision,
+    ) -> Self {
+        self.field_options
+            .float_precision
+            .fields
+            .insert(field_name.to_string(), precision);
+        self
+    }
 
-impl TimeSeriesDataWriter {
-    /// Write point and cell data for a specific time step.
+    /// Set the precision of the connectivity and the indices of sets, subsets and submeshes, see [`IndexPrecision`].
     ///
-    /// Accepts str for time to avoid dealing with formatting, thus leaving it to the user.
-    /// Sizes of the data arrays are validated to ensure consistency with the mesh and defined dat types.
+    /// By default the indices are written as u32 if all of them fit, which halves their size in binary formats.
+    /// The `Precision` of the `DataItems` is set accordingly, [`IndexPrecision::U32`] fails when writing an index that does not fit.
     /// ```rust
-    /// use xdmf::TimeSeriesWriter;
-    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_data", xdmf::DataStorage::AsciiInline)
-    ///     .expect("failed to create XDMF writer");
+    /// use xdmf::{IndexPrecision, TimeSeriesWriter};
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "name_xdmf_file_index_precision",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer")
+    /// .with_index_precision(IndexPrecision::U64);
+    /// ```
+    pub fn with_index_precision(mut self, index_precision: IndexPrecision) -> Self {
+        self.writer.set_index_precision(index_precision);
+        self
+    }
+
+    /// Write a field only at every N-th time step, e.g. an expensive field that is not needed at every time step.
     ///
-    /// // define 3 points and 2 cells (a line and a triangle)
-    /// let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
-    /// let connectivity = [0, 1, 0, 2, 1]; // line (0,1) and triangle (0,2,1)
-    /// let cell_types = [xdmf::CellType::Edge, xdmf::CellType::Triangle];
+    /// The data of all time steps can be passed with all fields, the writer only writes the fields that are due.
+    /// The time steps are counted in the order of writing, the field is written at the first time step and at every N-th after it.
+    /// Fields without an output frequency are written at every time step. Applies to [`TimeSeriesDataWriter::write_data`] and its variants.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "name_xdmf_file_field_output_frequency",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer")
+    /// .with_field_output_frequency("stress", 10);
+    /// ```
+    pub fn with_field_output_frequency(
+        mut self,
+        field_name: impl ToString,
+        every_n_steps: usize,
+    ) -> Self {
+        self.output_frequencies
+            .insert(field_name.to_string(), every_n_steps);
+        self
+    }
+
+    /// Additionally write a field of the point-data as cell-data, averaged over the points of each cell, e.g. for threshold filters.
     ///
-    /// // write the mesh
-    /// let mut time_series_writer = xdmf_writer
-    ///     .write_mesh(&coords, (&connectivity, &cell_types))
-    ///     .expect("failed to write mesh");
+    /// Whenever the point-data of a time step contains the field, the average of the values of the points of each cell is
+    /// written as cell-data with the given name, in the same pass over the cells for all components. Integer values are averaged as floats.
+    /// The points of the cells are kept by the writer for this, hence it requires a mesh written with [`Self::write_mesh`] or
+    /// [`Self::write_mesh_soa`] (or updated with [`TimeSeriesDataWriter::write_mesh_update`]). Applies to [`TimeSeriesDataWriter::write_data`] and its variants.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let mut ts_writer = TimeSeriesWriter::new(
+    ///     "name_xdmf_file_cell_average",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer")
+    /// .with_cell_average("temperature", "temperature_cell")
+    /// .write_mesh(
+    ///     &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+    ///     (&[0, 1], &[xdmf::CellType::Edge]),
+    /// )
+    /// .expect("failed to write mesh");
     ///
-    /// // define some point and cell data for time step 0.0
     /// let point_data = vec![(
-    ///     "point_data".to_string(),
-    ///     (xdmf::DataAttribute::Vector, vec![0.0; 9].into()),
+    ///     "temperature".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![290.0, 300.0].into()),
     /// )]
     /// .into_iter()
     /// .collect();
     ///
-    /// let cell_data = vec![(
-    ///     "cell_data".to_string(),
-    ///     (xdmf::DataAttribute::Scalar, vec![0.0, 1.0].into()),
-    /// )]
+    /// // the cell-data "temperature_cell" with the value 295.0 is written as well
+    /// ts_writer
+    ///     .write_data("0.0", Some(&point_data), None)
+    ///     .expect("failed to write data");
+    /// ```
+    pub fn with_cell_average(mut self, point_field: impl ToString, name: impl ToString) -> Self {
+        self.field_options.averaged_fields.push(AveragedField {
+            source: point_field.to_string(),
+            name: name.to_string(),
+            center: attribute::Center::Cell,
+        });
+        self
+    }
+
+    /// Additionally write a field of the cell-data as point-data, averaged over the cells of each point.
+    ///
+    /// The points that are not part of any cell get zero values. See [`Self::with_cell_average`] for details.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "name_xdmf_file_point_average",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer")
+    /// .with_point_average("stress", "stress_point");
+    /// ```
+    pub fn with_point_average(mut self, cell_field: impl ToString, name: impl ToString) -> Self {
+        self.field_options.averaged_fields.push(AveragedField {
+            source: cell_field.to_string(),
+            name: name.to_string(),
+            center: attribute::Center::Node,
+        });
+        self
+    }
+
+    /// Write the minimum, maximum and mean of the values of each attribute as its `Information`, e.g. for checks of the results in a CI pipeline.
+    ///
+    /// The statistics are computed over all components of the values (e.g. all components of a vector) as they are written,
+    /// i.e. in the precision of the field. They are written as `Information` elements named "min", "max" and "mean".
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("name_xdmf_file_statistics", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer")
+    ///         .with_statistics(true);
+    /// ```
+    pub fn with_statistics(mut self, statistics: bool) -> Self {
+        self.field_options.statistics = statistics;
+        self
+    }
+
+    /// Write a checksum of the values of each attribute as its `Information`, e.g. to prove the integrity of the heavy data for provenance.
+    ///
+    /// The checksum is the XXH64 (with seed 0) of the values as little-endian bytes of their number type and precision, as they are written.
+    /// Hence it does not depend on the data storage, and streamed data is hashed while it is written.
+    /// It is written as `Information` named "checksum" with the value `xxh64:<hex>`, naming the algorithm, and checked with [`validate::verify_checksums_file`](crate::validate::verify_checksums_file).
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("name_xdmf_file_checksums", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer")
+    ///         .with_checksums(true);
+    /// ```
+    pub fn with_checksums(mut self, checksums: bool) -> Self {
+        self.field_options.checksums = checksums;
+        self
+    }
+
+    /// Prefix the names of all attributes in the XDMF file, e.g. `"solid/"` and `"fluid/"` to distinguish the fields of the solvers of a coupled simulation.
+    ///
+    /// The fields are still given by their names without the prefix when writing, e.g. for [`TimeSeriesWriter::with_field_information`].
+    /// The names of the files of the heavy data do not contain the prefix either.
+    /// See [`AttributeBuilder::name_prefix`](crate::xdmf_elements::attribute::AttributeBuilder::name_prefix) for custom documents.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("name_xdmf_file_name_prefix", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer")
+    ///         .with_field_name_prefix("fluid/");
+    /// ```
+    pub fn with_field_name_prefix(mut self, name_prefix: impl ToString) -> Self {
+        self.field_options.name_prefix = name_prefix.to_string();
+        self
+    }
+
+    /// Set how NaN or infinite values in the data of the fields are handled, e.g. after the solver diverged, see [`NanPolicy`].
+    ///
+    /// By default the values are written as they are, which some readers do not handle well.
+    /// With [`NanPolicy::Error`] writing a field containing such values fails, the error states how many values are affected.
+    /// With [`NanPolicy::ReplaceWith`] they are replaced, and a warning with the number of replaced values is logged for each field.
+    /// Streamed values are checked while they are written, hence the error is returned after their heavy data was written.
+    /// ```rust
+    /// use xdmf::{NanPolicy, TimeSeriesWriter};
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("name_xdmf_file_nan_policy", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer")
+    ///         .with_nan_policy(NanPolicy::ReplaceWith(0.0));
+    /// ```
+    pub fn with_nan_policy(mut self, nan_policy: NanPolicy) -> Self {
+        self.field_options.nan_policy = nan_policy;
+        self
+    }
+
+    /// Set how fields are handled that are written with the same name as point- and cell-data of a time step, see [`NameCollisionPolicy`].
+    ///
+    /// By default both fields are written with the same name, but readers such as Paraview might silently show only one of them.
+    /// With [`NameCollisionPolicy::Error`] writing the data of such a time step fails.
+    /// With [`NameCollisionPolicy::SuffixCellData`] the suffix is appended to the name of the attribute of the cell-data.
+    /// Collisions with static data and with data streamed in separate calls for the same time step are detected as well.
+    /// ```rust
+    /// use xdmf::{NameCollisionPolicy, TimeSeriesWriter};
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "name_xdmf_file_name_collision_policy",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer")
+    /// .with_name_collision_policy(NameCollisionPolicy::SuffixCellData("_cell".into()));
+    /// ```
+    pub fn with_name_collision_policy(mut self, policy: NameCollisionPolicy) -> Self {
+        self.field_options.name_collision_policy = policy;
+        self
+    }
+
+    // precision of the floating point data of the fields, set by the builder
+    pub(crate) fn set_float_precision(&mut self, float_precision: FloatPrecision) {
+        self.field_options.float_precision.default = float_precision;
+    }
+
+    // options of the HDF5 data storages, set by the builder
+    pub(crate) fn set_hdf5_options(&mut self, hdf5_options: &Hdf5Options) -> XdmfResult<()> {
+        self.writer.set_hdf5_options(hdf5_options)
+    }
+
+    /// Adds a named mesh, returning a `MultiMeshDataWriter` for adding further meshes and writing their time steps.
+    ///
+    /// This allows to write multiple independent meshes into one file, e.g. the fluid and the structure domain of a coupled simulation.
+    /// Each mesh has its own time series, they are combined in a spatial collection.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_add_mesh", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    /// let cell_types = [xdmf::CellType::Triangle];
+    ///
+    /// let mut mm_writer = xdmf_writer
+    ///     .add_mesh("fluid", &coords, (&[0, 1, 2], &cell_types))
+    ///     .expect("failed to add mesh");
+    ///
+    /// mm_writer
+    ///     .add_mesh(
+    ///         "structure",
+    ///         &coords[..6],
+    ///         (&[0, 1], &[xdmf::CellType::Edge]),
+    ///     )
+    ///     .expect("failed to add mesh");
+    ///
+    /// let pressure = vec![(
+    ///     "pressure".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![1.0, 2.0, 3.0].into()),
+    /// )]
     /// .into_iter()
     /// .collect();
     ///
-    /// // write the data for 10 time steps
-    /// for i in 0..10 {
-    ///     time_series_writer
-    ///         .write_data(&i.to_string(), Some(&point_data), Some(&cell_data))
-    ///         .expect("failed to write time step data");
+    /// mm_writer
+    ///     .write_data("fluid", "0.0", Some(&pressure), None)
+    ///     .expect("failed to write time step data");
+    /// ```
+    pub fn add_mesh(
+        self,
+        name: &str,
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+    ) -> XdmfResult<MultiMeshDataWriter> {
+        let mut mm_writer = self.into_multi_mesh_writer()?;
+        mm_writer.add_mesh(name, points, cells)?;
+
+        Ok(mm_writer)
+    }
+
+    /// Adds a named mesh to a named domain, returning a `MultiMeshDataWriter` for adding more meshes and writing their time steps.
+    ///
+    /// Each domain is written as separate XDMF `Domain` element, e.g. for differently post-processed variants of the same case.
+    /// Readers such as Paraview allow to select the domain that is loaded. Meshes added with [`TimeSeriesWriter::add_mesh`] are written into the first, unnamed domain.
+    /// The names of the meshes must be unique over all domains, the data is written by the name of the mesh.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("xdmf_add_mesh_to_domain", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer");
+    ///
+    /// let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    /// let cells = (&[0, 1, 2][..], &[xdmf::CellType::Triangle][..]);
+    ///
+    /// let mut mm_writer = xdmf_writer
+    ///     .add_mesh("fluid", &coords, cells)
+    ///     .expect("failed to add mesh");
+    ///
+    /// // the same case, but e.g. with smoothed fields
+    /// mm_writer
+    ///     .add_mesh_to_domain("smoothed", "fluid_smoothed", &coords, cells)
+    ///     .expect("failed to add mesh");
+    /// ```
+    pub fn add_mesh_to_domain(
+        self,
+        domain: &str,
+        name: &str,
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+    ) -> XdmfResult<MultiMeshDataWriter> {
+        let mut mm_writer = self.into_multi_mesh_writer()?;
+        mm_writer.add_mesh_to_domain(domain, name, points, cells)?;
+
+        Ok(mm_writer)
+    }
+
+    /// Start writing a scene of moving bodies, returning a `MultiBodyDataWriter` for writing the bodies of each time step.
+    ///
+    /// Each time step is written as spatial collection of the grids of the bodies, each with its own mesh of this time step.
+    /// Hence the bodies can move and deform, and they can be added or removed over time.
+    /// See [`MultiBodyDataWriter::write_body`] for details.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let mut body_writer = TimeSeriesWriter::new("xdmf_multi_body", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer")
+    ///     .into_multi_body_writer()
+    ///     .expect("failed to start writing bodies");
+    ///
+    /// let cells = (&[0, 1][..], &[xdmf::CellType::Edge][..]);
+    ///
+    /// for (step, x) in [(0.0, 0.0), (1.0, 0.5)] {
+    ///     body_writer
+    ///         .write_body(
+    ///             "rod",
+    ///             &step,
+    ///             &[x, 0.0, 0.0, x + 1.0, 0.0, 0.0],
+    ///             cells,
+    ///             None,
+    ///             None,
+    ///         )
+    ///         .expect("failed to write body");
     /// }
+    ///
+    /// // a second body enters the scene
+    /// body_writer
+    ///     .write_body("ball", &1.0, &[3.0, 0.0, 0.0], (&[], &[]), None, None)
+    ///     .expect("failed to write body");
     /// ```
-    pub fn write_data(
-        &mut self,
-        time: &str,
-        point_data: Option<&DataMap>,
-        cell_data: Option<&DataMap>,
-    ) -> IoResult<()> {
-        self.validate_data(time, point_data, cell_data)?;
-
-        self.writer.write_data_initialize(time)?;
-        let format = self.writer.format();
-
-        let mut new_attributes = Vec::new();
-
-        let mut create_attributes =
-            |data_map: Option<&DataMap>, center: attribute::Center| -> IoResult<()> {
-                for (data_name, data) in data_map.unwrap_or(&BTreeMap::new()) {
-                    let vals = &data.1;
-
-                    let data_item = DataItem {
-                        name: None,
-                        dimensions: Some(vals.dimensions(data.0)),
-                        number_type: Some(vals.number_type()),
-                        format: Some(format),
-                        precision: Some(vals.precision()),
-                        data: self.writer.write_data(data_name, center, vals)?,
-                        reference: None,
-                    };
-
-                    let attribute = attribute::Attribute {
-                        name: data_name.clone(),
-                        attribute_type: data.0.into(),
-                        center,
-                        data_items: vec![data_item],
-                    };
-
-                    new_attributes.push(attribute);
+    pub fn into_multi_body_writer(self) -> XdmfResult<MultiBodyDataWriter> {
+        let WriterOptions {
+            xdmf_file_name,
+            writer,
+            name_suffix,
+            information,
+            field_information,
+            flush_policy,
+            time_list,
+            time_scale,
+            tensor6_as_matrix,
+            transform,
+            file_system,
+            output_frequencies,
+            reference_style,
+            xml_header,
+            progress_callback,
+            field_options,
+        } = self.into_parts()?;
+
+        Ok(MultiBodyDataWriter {
+            xdmf_file_name,
+            writer,
+            name_suffix,
+            information,
+            field_information,
+            data_items: DataItemRegistry::new(),
+            steps: vec![],
+            flush_policy,
+            time_list,
+            time_scale,
+            tensor6_as_matrix,
+            transform,
+            file_system,
+            output_frequencies,
+            reference_style,
+            xml_header,
+            progress_callback,
+            field_options,
+            pending_writes: 0,
+            closed: false,
+        })
+    }
+
+    fn into_multi_mesh_writer(self) -> XdmfResult<MultiMeshDataWriter> {
+        let WriterOptions {
+            xdmf_file_name,
+            writer,
+            name_suffix,
+            information,
+            field_information,
+            flush_policy,
+            time_list,
+            time_scale,
+            tensor6_as_matrix,
+            transform,
+            file_system,
+            output_frequencies,
+            reference_style,
+            xml_header,
+            progress_callback,
+            field_options,
+        } = self.into_parts()?;
+
+        Ok(MultiMeshDataWriter {
+            xdmf_file_name,
+            writer,
+            name_suffix,
+            information,
+            field_information,
+            data_items: DataItemRegistry::new(),
+            domains: vec![],
+            meshes: vec![],
+            flush_policy,
+            time_list,
+            time_scale,
+            tensor6_as_matrix,
+            transform,
+            file_system,
+            output_frequencies,
+            reference_style,
+            xml_header,
+            progress_callback,
+            field_options,
+            pending_writes: 0,
+            closed: false,
+        })
+    }
+
+    // the options of the writers of multiple meshes and bodies, which write all of their time steps into the one XDMF file
+    // all options are destructured, such that an option that they do not support can not be dropped silently
+    fn into_parts(self) -> XdmfResult<WriterOptions> {
+        let Self {
+            xdmf_file_name,
+            writer,
+            name_suffix,
+            information,
+            field_information,
+            flush_policy,
+            time_list,
+            time_scale,
+            tensor6_as_matrix,
+            transform,
+            file_system,
+            output_frequencies,
+            reference_style,
+            xml_header,
+            progress_callback,
+            field_options,
+            retention_policy,
+            latest_step_file,
+            rollover,
+            subdivide_quadratic,
+        } = self;
+
+        if retention_policy != RetentionPolicy::KeepAll {
+            return Err(XdmfError::Unsupported(
+                "Retention policies are only supported when writing a single mesh, not for multiple meshes or bodies".into(),
+            ));
+        }
+
+        if latest_step_file {
+            return Err(XdmfError::Unsupported(
+                "A file with the latest time step is only supported when writing a single mesh, not for multiple meshes or bodies".into(),
+            ));
+        }
+
+        if rollover.is_some() {
+            return Err(XdmfError::Unsupported(
+                "Splitting the time steps into multiple files is only supported when writing a single mesh, not for multiple meshes or bodies".into(),
+            ));
+        }
+
+        if subdivide_quadratic {
+            return Err(XdmfError::Unsupported(
+                "Subdividing quadratic cells is only supported when writing a single mesh, not for multiple meshes or bodies".into(),
+            ));
+        }
+
+        Ok(WriterOptions {
+            xdmf_file_name,
+            writer,
+            name_suffix,
+            information,
+            field_information,
+            flush_policy,
+            time_list,
+            time_scale,
+            tensor6_as_matrix,
+            transform,
+            file_system,
+            output_frequencies,
+            reference_style,
+            xml_header,
+            progress_callback,
+            field_options,
+        })
+    }
+
+    /// Append a suffix to the names of the mesh `DataItems`, to make them unique when multiple files are combined.
+    pub(crate) fn with_name_suffix(mut self, suffix: impl ToString) -> Self {
+        self.name_suffix = suffix.to_string();
+        self
+    }
+
+    pub(crate) fn data_storage(&self) -> DataStorage {
+        self.writer.data_storage()
+    }
+
+    // the writer of the time steps of the given mesh, the mesh specific parts such as the cell order are set by the caller
+    fn into_data_writer(
+        self,
+        grid: Grid,
+        data_items: DataItemRegistry,
+        num_points: usize,
+        num_cells: usize,
+    ) -> TimeSeriesDataWriter {
+        TimeSeriesDataWriter {
+            xdmf_file_name: self.xdmf_file_name,
+            writer: self.writer,
+            name_suffix: self.name_suffix,
+            information: self.information,
+            field_information: self.field_information,
+            grid,
+            data_items,
+            time_grids: vec![],
+            writen_times: HashSet::new(),
+            steps: vec![],
+            times: vec![],
+            written_fields: vec![],
+            pruned_steps: vec![],
+            registered_fields: vec![],
+            derived_fields: vec![],
+            cell_points: None,
+            static_attributes: vec![],
+            subsets: vec![],
+            submeshes: vec![],
+            cell_order: None,
+            cell_layout: None,
+            parts: vec![],
+            num_points,
+            num_cells,
+            num_edges: None,
+            num_faces: None,
+            flush_policy: self.flush_policy,
+            retention_policy: self.retention_policy,
+            time_list: self.time_list,
+            latest_step_file: self.latest_step_file,
+            rollover: self.rollover.map(Rollover::new),
+            time_scale: self.time_scale,
+            tensor6_as_matrix: self.tensor6_as_matrix,
+            transform: self.transform,
+            file_system: self.file_system,
+            output_frequencies: self.output_frequencies,
+            reference_style: self.reference_style,
+            xml_header: self.xml_header,
+            progress_callback: self.progress_callback,
+            subdivide_quadratic: self.subdivide_quadratic,
+            field_options: self.field_options,
+            pending_writes: 0,
+            closed: false,
+        }
+    }
+
+    /// Writes the mesh to the XDMF file, returning a `TimeSeriesDataWriter` for writing time steps.
+    ///
+    /// Sizes of the inputs are validated to ensure consistency with the mesh and defined cell types.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_mesh", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// // define 3 points and 2 cells (a line and a triangle)
+    /// let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    /// let connectivity = [0, 1, 0, 2, 1]; // line (0,1) and triangle (0,2,1)
+    /// let cell_types = [xdmf::CellType::Edge, xdmf::CellType::Triangle];
+    ///
+    /// // write the mesh
+    /// let mut ts_writer = xdmf_writer.write_mesh(&coords, (&connectivity, &cell_types));
+    /// ```
+    pub fn write_mesh(
+        mut self,
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+    ) -> XdmfResult<TimeSeriesDataWriter> {
+        let linear_cells =
+            subdivide_if_requested(self.subdivide_quadratic, points.len() / 3, cells)?;
+
+        let mut data_items = DataItemRegistry::new();
+        let mesh = create_mesh(
+            self.writer.as_mut(),
+            &mut data_items,
+            Coordinates::Interleaved(points),
+            linear_cells
+                .as_ref()
+                .map_or(cells, |(connectivity, cell_types, _)| {
+                    (connectivity, cell_types)
+                }),
+            &self.name_suffix,
+            None,
+            self.transform.as_ref(),
+        )?;
+
+        // the data of the given cells is written for each of their linear cells
+        let (num_cells, cell_order, cell_layout) = match linear_cells {
+            Some((_, _, cell_map)) => (cells.1.len(), Some(cell_map), None),
+            None => (mesh.num_cells, None, cell_layout(&mesh.grid, cells)),
+        };
+        let cell_points =
+            cell_points_if_averaged(&self.field_options.averaged_fields, mesh.num_points, cells)?;
+
+        let mut ts_writer =
+            self.into_data_writer(mesh.grid, data_items, mesh.num_points, num_cells);
+        ts_writer.cell_points = cell_points;
+        ts_writer.cell_order = cell_order;
+        ts_writer.cell_layout = cell_layout;
+
+        ts_writer.write()?;
+
+        Ok(ts_writer)
+    }
+
+    /// Writes a mesh given by a [`MeshSource`], e.g. the mesh type of another crate, returning a `TimeSeriesDataWriter` for writing time steps.
+    ///
+    /// The points and cells are flattened and written like with [`Self::write_mesh`].
+    /// ```rust
+    /// use xdmf::{FaceVertexMesh, TimeSeriesWriter};
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_mesh_from", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// let mesh = FaceVertexMesh {
+    ///     vertices: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+    ///     faces: vec![vec![0, 1, 2]],
+    /// };
+    ///
+    /// let mut ts_writer = xdmf_writer.write_mesh_from(&mesh);
+    /// ```
+    pub fn write_mesh_from(self, mesh: &impl MeshSource) -> XdmfResult<TimeSeriesDataWriter> {
+        let (points, (connectivity, cell_types)) = mesh.flatten();
+
+        self.write_mesh(&points, (&connectivity, &cell_types))
+    }
+
+    /// Writes the mesh like [`Self::write_mesh`], together with quality metrics of its cells as static cell data, see [`QualityMetric`].
+    ///
+    /// The metrics are computed from the points after the [`Transform`], and written once as cell data named after the metrics,
+    /// e.g. `scaled_jacobian`, which is part of all time steps, see [`TimeSeriesDataWriter::write_static_data`].
+    /// ```rust
+    /// use xdmf::{QualityMetric, TimeSeriesWriter};
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "xdmf_write_mesh_with_quality",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer");
+    ///
+    /// let mut ts_writer = xdmf_writer.write_mesh_with_quality(
+    ///     &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+    ///     (&[0, 1, 2], &[xdmf::CellType::Triangle]),
+    ///     &[QualityMetric::Volume, QualityMetric::ScaledJacobian],
+    /// );
+    /// ```
+    pub fn write_mesh_with_quality(
+        self,
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+        metrics: &[QualityMetric],
+    ) -> XdmfResult<TimeSeriesDataWriter> {
+        let transformed_points = self
+            .transform
+            .as_ref()
+            .map(|transform| transform.apply_interleaved(points));
+
+        let mut ts_writer = self.write_mesh(points, cells)?;
+
+        if !metrics.is_empty() {
+            let quality_data = quality_data(
+                transformed_points.as_deref().unwrap_or(points),
+                cells,
+                metrics,
+            )?;
+            ts_writer.write_static_data(None, Some(&quality_data))?;
+        }
+
+        Ok(ts_writer)
+    }
+
+    /// Writes a mesh with the coordinates given as separate arrays, returning a `TimeSeriesDataWriter` for writing time steps.
+    ///
+    /// The arrays are written unmodified (unless a [`Transform`] is set) and referenced with an `X_Y_Z` geometry,
+    /// avoiding to interleave the coordinates when they are stored as struct of arrays.
+    /// All arrays must have the same length, the cells are given as in [`Self::write_mesh`].
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_mesh_soa", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// // define 3 points and a triangle
+    /// let x = [0.0, 1.0, 0.0];
+    /// let y = [0.0, 0.0, 1.0];
+    /// let z = [0.0, 0.0, 0.0];
+    ///
+    /// let mut ts_writer =
+    ///     xdmf_writer.write_mesh_soa(&x, &y, &z, (&[0, 1, 2], &[xdmf::CellType::Triangle]));
+    /// ```
+    pub fn write_mesh_soa(
+        mut self,
+        x: &[f64],
+        y: &[f64],
+        z: &[f64],
+        cells: (&[u64], &[CellType]),
+    ) -> XdmfResult<TimeSeriesDataWriter> {
+        let linear_cells = subdivide_if_requested(self.subdivide_quadratic, x.len(), cells)?;
+
+        let mut data_items = DataItemRegistry::new();
+        let mesh = create_mesh(
+            self.writer.as_mut(),
+            &mut data_items,
+            Coordinates::Separate([x, y, z]),
+            linear_cells
+                .as_ref()
+                .map_or(cells, |(connectivity, cell_types, _)| {
+                    (connectivity, cell_types)
+                }),
+            &self.name_suffix,
+            None,
+            self.transform.as_ref(),
+        )?;
+
+        // the data of the given cells is written for each of their linear cells
+        let (num_cells, cell_order, cell_layout) = match linear_cells {
+            Some((_, _, cell_map)) => (cells.1.len(), Some(cell_map), None),
+            None => (mesh.num_cells, None, cell_layout(&mesh.grid, cells)),
+        };
+        let cell_points =
+            cell_points_if_averaged(&self.field_options.averaged_fields, mesh.num_points, cells)?;
+
+        let mut ts_writer =
+            self.into_data_writer(mesh.grid, data_items, mesh.num_points, num_cells);
+        ts_writer.cell_points = cell_points;
+        ts_writer.cell_order = cell_order;
+        ts_writer.cell_layout = cell_layout;
+
+        ts_writer.write()?;
+
+        Ok(ts_writer)
+    }
+
+    /// Writes a point cloud without cells, e.g. the particles of a DEM simulation, returning a `TimeSeriesDataWriter` for writing time steps.
+    ///
+    /// A polyvertex topology covering all points is generated, such that the points can be visualized.
+    /// As each point is a cell of the topology, the cell data is per point as well.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_points", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// // define 2 particles
+    /// let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+    ///
+    /// let mut ts_writer = xdmf_writer.write_points(&coords);
+    /// ```
+    pub fn write_points(self, points: &[f64]) -> XdmfResult<TimeSeriesDataWriter> {
+        self.write_mesh(points, (&[], &[]))
+    }
+
+    /// Writes image (voxel) data, i.e. a structured grid with uniform spacing, returning a `TimeSeriesDataWriter` for writing time steps.
+    ///
+    /// The grid is defined by its origin, the spacing and the number of points per direction, all in XYZ order.
+    /// No points or cells are written, they are implicitly given by a `3DCoRectMesh` topology and an `ORIGIN_DXDYDZ` geometry.
+    /// The data is ordered with x varying fastest and written with the shape of the grid, e.g. (nz, ny, nx) for a scalar at the points.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("xdmf_write_image_data", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer");
+    ///
+    /// // 4 x 3 x 2 voxels with a size of 0.5
+    /// let mut ts_writer = xdmf_writer
+    ///     .write_image_data([0.0, 0.0, 0.0], [0.5, 0.5, 0.5], [5, 4, 3])
+    ///     .expect("failed to write image");
+    ///
+    /// let cell_data = vec![(
+    ///     "segmentation".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![1_u8; 4 * 3 * 2].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// ts_writer
+    ///     .write_data("0.0", None, Some(&cell_data))
+    ///     .expect("failed to write data");
+    /// ```
+    pub fn write_image_data(
+        self,
+        origin: [f64; 3],
+        spacing: [f64; 3],
+        dims: [usize; 3],
+    ) -> XdmfResult<TimeSeriesDataWriter> {
+        if dims.iter().any(|num_points| *num_points < 2) {
+            return Err(XdmfError::Validation(format!(
+                "Image data requires at least 2 points per direction, but has {dims:?}"
+            )));
+        }
+
+        if spacing
+            .iter()
+            .chain(&origin)
+            .any(|value| !value.is_finite())
+            || spacing.iter().any(|value| *value <= 0.0)
+        {
+            return Err(XdmfError::Validation(format!(
+                "Image data requires a finite origin and a positive spacing, but has {origin:?} and {spacing:?}"
+            )));
+        }
+
+        // the geometry is aligned with the axes, hence it can only be scaled and translated
+        let (origin, spacing) = if let Some(transform) = &self.transform {
+            let scales = axis_aligned_scales(transform, "Image data")?;
+
+            (
+                transform.apply(origin),
+                std::array::from_fn(|i| spacing[i] * scales[i]),
+            )
+        } else {
+            (origin, spacing)
+        };
+
+        // XDMF expects all values of structured grids in ZYX order
+        let zyx_item = |values: [f64; 3]| DataItem {
+            dimensions: Some(Dimensions(vec![3])),
+            number_type: Some(NumberType::Float),
+            format: Some(Format::XML),
+            precision: Some(8),
+            data: values
+                .iter()
+                .rev()
+                .map(FormatNumber::format_number)
+                .collect::<Vec<_>>()
+                .join(" ")
+                .into(),
+            ..Default::default()
+        };
+
+        let geometry = Geometry {
+            geometry_type: GeometryType::OriginDxDyDz,
+            data_items: vec![zyx_item(origin), zyx_item(spacing)],
+        };
+        let topology = Topology::new_co_rect_mesh([dims[2], dims[1], dims[0]]);
+
+        self.write_structured_grid(geometry, topology, dims)
+    }
+
+    /// Writes a rectilinear mesh, i.e. a structured grid with a varying spacing per direction, returning a `TimeSeriesDataWriter` for writing time steps.
+    ///
+    /// The grid is defined by the coordinates of its points along each axis, which must be strictly increasing.
+    /// Only these coordinates are written, with a `3DRectMesh` topology and a `VXVYVZ` geometry, instead of the coordinates of all points.
+    /// As they are small, they are written inline into the XDMF file, regardless of the `DataStorage`.
+    /// The data is ordered with x varying fastest, as for [`Self::write_image_data`].
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "xdmf_write_rectilinear_mesh",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer");
+    ///
+    /// // 3 x 2 x 1 cells, refined towards x = 0
+    /// let mut ts_writer = xdmf_writer
+    ///     .write_rectilinear_mesh(&[0.0, 0.1, 0.3, 1.0], &[0.0, 0.5, 1.0], &[0.0, 2.0])
+    ///     .expect("failed to write rectilinear mesh");
+    ///
+    /// let point_data = vec![(
+    ///     "temperature".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![20.0; 4 * 3 * 2].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// ts_writer
+    ///     .write_data("0.0", Some(&point_data), None)
+    ///     .expect("failed to write data");
+    /// ```
+    pub fn write_rectilinear_mesh(
+        self,
+        x: &[f64],
+        y: &[f64],
+        z: &[f64],
+    ) -> XdmfResult<TimeSeriesDataWriter> {
+        let axes = [x, y, z];
+        let dims = axes.map(<[f64]>::len);
+
+        if dims.iter().any(|num_points| *num_points < 2) {
+            return Err(XdmfError::Validation(format!(
+                "Rectilinear mesh requires at least 2 points per direction, but has {dims:?}"
+            )));
+        }
+
+        if axes.iter().any(|axis| {
+            axis.iter().any(|value| !value.is_finite())
+                || axis.windows(2).any(|pair| pair[0] >= pair[1])
+        }) {
+            return Err(XdmfError::Validation(
+                "Rectilinear mesh requires finite and strictly increasing coordinates of the axes"
+                    .into(),
+            ));
+        }
+
+        // the geometry is aligned with the axes, hence each axis is scaled and translated independently
+        let transform = self
+            .transform
+            .as_ref()
+            .map(|transform| {
+                axis_aligned_scales(transform, "Rectilinear meshes").map(|_| transform)
+            })
+            .transpose()?;
+
+        // unlike the other structured geometries, the axes are given in XYZ order
+        let data_items = (0..3)
+            .map(|direction| {
+                let coordinates: Vec<String> = axes[direction]
+                    .iter()
+                    .map(|&coordinate| {
+                        transform.map_or(coordinate, |transform| {
+                            let mut point = [0.0; 3];
+                            point[direction] = coordinate;
+                            transform.apply(point)[direction]
+                        })
+                    })
+                    .map(|coordinate| coordinate.format_number())
+                    .collect();
+
+                DataItem {
+                    dimensions: Some(Dimensions(vec![coordinates.len()])),
+                    number_type: Some(NumberType::Float),
+                    format: Some(Format::XML),
+                    precision: Some(8),
+                    data: coordinates.join(" ").into(),
+                    ..Default::default()
                 }
+            })
+            .collect();
 
-                Ok(())
-            };
+        let geometry = Geometry {
+            geometry_type: GeometryType::VXVYVZ,
+            data_items,
+        };
+        let topology = Topology::new_rect_mesh([dims[2], dims[1], dims[0]]);
+
+        self.write_structured_grid(geometry, topology, dims)
+    }
+
+    // write a structured grid with the given number of points per direction (in XYZ order), whose points and cells are implicit
+    fn write_structured_grid(
+        self,
+        geometry: Geometry,
+        topology: Topology,
+        dims: [usize; 3],
+    ) -> XdmfResult<TimeSeriesDataWriter> {
+        let (num_edges, num_faces) = structured_edge_face_counts(&dims);
+
+        let mut ts_writer = self.into_data_writer(
+            Grid::new_uniform("mesh", geometry, topology),
+            DataItemRegistry::new(),
+            dims.iter().product(),
+            dims.iter().map(|num_points| num_points - 1).product(),
+        );
+        ts_writer.num_edges = Some(num_edges);
+        ts_writer.num_faces = Some(num_faces);
+
+        ts_writer.write()?;
+
+        Ok(ts_writer)
+    }
+
+    /// Writes a mesh read with [vtkio](https://github.com/elrnv/vtkio), returning a `TimeSeriesDataWriter` for writing time steps.
+    ///
+    /// See [`crate::mesh_from_vtk`] for the supported meshes.
+    #[cfg(feature = "vtk-interop")]
+    pub fn write_vtk_mesh(
+        self,
+        piece: &vtkio::model::UnstructuredGridPiece,
+    ) -> XdmfResult<TimeSeriesDataWriter> {
+        let (points, connectivity, cell_types) = crate::mesh_from_vtk(piece)?;
+
+        self.write_mesh(&points, (&connectivity, &cell_types))
+    }
+
+    /// Writes a mesh consisting of multiple parts, returning a `TimeSeriesDataWriter` for writing time steps.
+    ///
+    /// The path of each part (e.g. "assembly/part/body") defines its position in the hierarchy,
+    /// which is retained as a tree of grids, e.g. shown in the multiblock view of Paraview.
+    /// The data of all parts is written together, concatenated in the order of the parts.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_mesh_tree", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    /// let cell_types = [xdmf::CellType::Triangle];
+    ///
+    /// // write two bodies of the same part
+    /// let mut ts_writer = xdmf_writer.write_mesh_tree(&[
+    ///     ("assembly/part/body_1", &coords, (&[0, 1, 2], &cell_types)),
+    ///     ("assembly/part/body_2", &coords, (&[0, 2, 1], &cell_types)),
+    /// ]);
+    /// ```
+    pub fn write_mesh_tree(mut self, parts: &[MeshPart]) -> XdmfResult<TimeSeriesDataWriter> {
+        if parts.is_empty() {
+            return Err(XdmfError::Validation(
+                "At least one mesh part must be provided".into(),
+            ));
+        }
+
+        let mut tree = Grid::new_tree("mesh", None);
+        let mut data_items = DataItemRegistry::new();
+        let mut part_infos = Vec::with_capacity(parts.len());
+        let (mut num_points, mut num_cells) = (0, 0);
+
+        for (i, (path, points, cells)) in parts.iter().enumerate() {
+            let segments = split_part_path(path)?;
+
+            let mut mesh = create_mesh(
+                self.writer.as_mut(),
+                &mut data_items,
+                Coordinates::Interleaved(points),
+                *cells,
+                &self.name_suffix,
+                Some(&format!("part{i}")),
+                self.transform.as_ref(),
+            )?;
+            mesh.grid.name.clone_from(&segments[segments.len() - 1]);
+
+            insert_into_tree(&mut tree, &segments, mesh.grid, path)?;
+
+            part_infos.push(PartInfo {
+                path: segments,
+                points: num_points..num_points + mesh.num_points,
+                cells: num_cells..num_cells + mesh.num_cells,
+            });
+            num_points += mesh.num_points;
+            num_cells += mesh.num_cells;
+        }
+
+        let mut ts_writer = self.into_data_writer(tree, data_items, num_points, num_cells);
+        ts_writer.parts = part_infos;
+
+        ts_writer.write()?;
+
+        Ok(ts_writer)
+    }
+
+    /// Writes a mesh whose cells are assigned to parts (e.g. materials), returning a `TimeSeriesDataWriter` for writing time steps.
+    ///
+    /// Each part is written as a grid named `part_<id>` of a spatial collection, e.g. shown as separate block in Paraview.
+    /// The coordinates and the connectivity are written once, the connectivity grouped by part, of which each grid selects its cells.
+    /// The data of the time steps is given for the whole mesh, the point data is shared by all parts,
+    /// while the cell data is split into the parts.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "xdmf_write_mesh_partitioned",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer");
+    ///
+    /// let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0];
+    /// let connectivity = [0, 1, 2, 1, 3, 2, 0, 1];
+    /// let cell_types = [
+    ///     xdmf::CellType::Triangle,
+    ///     xdmf::CellType::Triangle,
+    ///     xdmf::CellType::Edge,
+    /// ];
+    ///
+    /// // the triangles are made of steel (part 1), the edge is a rebar (part 2)
+    /// let mut ts_writer = xdmf_writer
+    ///     .write_mesh_partitioned(&coords, (&connectivity, &cell_types), &[1, 1, 2])
+    ///     .expect("failed to write mesh");
+    ///
+    /// let cell_data = vec![(
+    ///     "stress".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![1.0, 2.0, 3.0].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// ts_writer
+    ///     .write_data("0.0", None, Some(&cell_data))
+    ///     .expect("failed to write time step data");
+    /// ```
+    pub fn write_mesh_partitioned(
+        mut self,
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+        part_ids: &[u64],
+    ) -> XdmfResult<TimeSeriesDataWriter> {
+        let (connectivity, cell_types) = cells;
+
+        if cell_types.is_empty() {
+            return Err(XdmfError::Validation(
+                "At least one cell is required for a partitioned mesh".into(),
+            ));
+        }
+
+        if part_ids.len() != cell_types.len() {
+            return Err(XdmfError::Validation(format!(
+                "Number of part ids ({}) must match the number of cells ({})",
+                part_ids.len(),
+                cell_types.len()
+            )));
+        }
+
+        validate_points_and_cells(points, cells)?;
+
+        // group the cells by part, keeping their order within each part
+        let mut cell_order: Vec<usize> = (0..cell_types.len()).collect();
+        cell_order.sort_by_key(|&cell| part_ids[cell]);
+
+        let offsets: Vec<usize> = cell_types
+            .iter()
+            .scan(0, |offset, cell_type| {
+                let start = *offset;
+                *offset += cell_type.num_points();
+                Some(start)
+            })
+            .collect();
+        let grouped_connectivity: Vec<u64> = cell_order
+            .iter()
+            .flat_map(|&cell| {
+                &connectivity[offsets[cell]..offsets[cell] + cell_types[cell].num_points()]
+            })
+            .copied()
+            .collect();
+        let grouped_cell_types: Vec<CellType> =
+            cell_order.iter().map(|&cell| cell_types[cell]).collect();
+
+        let mut data_items = DataItemRegistry::new();
+        let mesh = create_mesh(
+            self.writer.as_mut(),
+            &mut data_items,
+            Coordinates::Interleaved(points),
+            (&grouped_connectivity, &grouped_cell_types),
+            &self.name_suffix,
+            None,
+            self.transform.as_ref(),
+        )?;
+
+        let (Some(geometry), Some(topology)) = (mesh.grid.geometry, mesh.grid.topology) else {
+            return Err(XdmfError::Validation(
+                "Partitioned mesh has no geometry or topology".into(),
+            ));
+        };
+        let connectivity_item = topology.data_item.unwrap_or_default();
+
+        // number of entries of a cell in the written connectivity, mixed topologies also contain the cell types
+        let cell_len = |cell_type: CellType| match topology.topology_type {
+            TopologyType::Mixed => {
+                1 + usize::from(poly_cell_points(cell_type).is_some()) + cell_type.num_points()
+            }
+            _ => cell_type.num_points(),
+        };
+        let connectivity_len: usize = cell_types
+            .iter()
+            .map(|&cell_type| cell_len(cell_type))
+            .sum();
+
+        let mut part_grids = Vec::new();
+        let mut part_infos = Vec::new();
+        let (mut cell_start, mut entry_start) = (0, 0);
+
+        for part_cells in
+            cell_order.chunk_by(|&cell_1, &cell_2| part_ids[cell_1] == part_ids[cell_2])
+        {
+            let name = format!("part_{}", part_ids[part_cells[0]]);
+            let num_entries: usize = part_cells
+                .iter()
+                .map(|&cell| cell_len(cell_types[cell]))
+                .sum();
+
+            let part_connectivity = DataItem::new_hyperslab(
+                DataItem {
+                    dimensions: Some(Dimensions(vec![num_entries])),
+                    ..connectivity_item.clone()
+                },
+                entry_start,
+                num_entries,
+                connectivity_len,
+            );
+            part_grids.push(Grid::new_uniform(
+                &name,
+                geometry.clone(),
+                Topology {
+                    topology_type: topology.topology_type,
+                    number_of_elements: part_cells.len().format_number(),
+                    dimensions: None,
+                    data_item: Some(part_connectivity),
+                },
+            ));
+
+            part_infos.push(PartInfo {
+                path: vec![name],
+                points: 0..mesh.num_points,
+                cells: cell_start..cell_start + part_cells.len(),
+            });
+            cell_start += part_cells.len();
+            entry_start += num_entries;
+        }
+
+        let mut ts_writer = self.into_data_writer(
+            Grid::new_collection("mesh", CollectionType::Spatial, Some(part_grids)),
+            data_items,
+            mesh.num_points,
+            mesh.num_cells,
+        );
+        ts_writer.cell_order = Some(cell_order);
+        ts_writer.parts = part_infos;
+
+        ts_writer.write()?;
+
+        Ok(ts_writer)
+    }
+}
+
+// the data of the cells in the given order, e.g. grouped by the parts of a partitioned mesh
+fn grouped_data<'a>(data: &DataMap, order: &[usize]) -> DataMap<'a> {
+    data.iter()
+        .map(|(name, (data_attribute, values))| {
+            (
+                name.clone(),
+                (
+                    data_attribute.clone(),
+                    values.permuted(order, data_attribute.size()),
+                ),
+            )
+        })
+        .collect()
+}
+
+// the linear cells of the given quadratic cells if requested, with the index of the given cell of each linear cell
+fn subdivide_if_requested(
+    subdivide_quadratic: bool,
+    num_points: usize,
+    cells: (&[u64], &[CellType]),
+) -> XdmfResult<Option<LinearCells>> {
+    if !subdivide_quadratic {
+        return Ok(None);
+    }
+
+    // the connectivity is validated before it is subdivided
+    validate_cells(num_points, cells)?;
+
+    Ok(subdivide_quadratic_cells(cells.0, cells.1))
+}
+
+// the points of the cells if fields are averaged between points and cells
+fn cell_points_if_averaged(
+    averaged_fields: &[AveragedField],
+    num_points: usize,
+    cells: (&[u64], &[CellType]),
+) -> XdmfResult<Option<CellPoints>> {
+    if averaged_fields.is_empty() {
+        return Ok(None);
+    }
+
+    CellPoints::new(num_points, cells).map(Some)
+}
+
+// split the path of a mesh part into its segments, which must not be empty
+fn split_part_path(path: &str) -> XdmfResult<Vec<String>> {
+    let segments: Vec<String> = path.split('/').map(str::to_string).collect();
+
+    if segments.iter().any(String::is_empty) {
+        return Err(XdmfError::Validation(format!(
+            "Mesh part path '{path}' must consist of non-empty segments separated by '/'"
+        )));
+    }
+
+    Ok(segments)
+}
+
+// insert the grid of a mesh part into the tree, creating the intermediate tree grids as needed
+fn insert_into_tree(
+    tree: &mut Grid,
+    segments: &[String],
+    leaf: Grid,
+    path: &str,
+) -> XdmfResult<()> {
+    let conflict_error = || {
+        XdmfError::Validation(format!(
+            "Mesh part path '{path}' conflicts with the path of another part"
+        ))
+    };
+
+    let grids = tree.grids.get_or_insert_with(Vec::new);
+
+    let Some((name, remaining)) = segments.split_first() else {
+        return Err(conflict_error());
+    };
+
+    let existing = grids.iter_mut().find(|grid| &grid.name == name);
+
+    if remaining.is_empty() {
+        if existing.is_some() {
+            return Err(conflict_error());
+        }
+        grids.push(leaf);
+        return Ok(());
+    }
+
+    match existing {
+        Some(subtree) if subtree.grid_type == GridType::Tree => {
+            insert_into_tree(subtree, remaining, leaf, path)
+        }
+        Some(_) => Err(conflict_error()),
+        None => {
+            let mut subtree = Grid::new_tree(name, None);
+            insert_into_tree(&mut subtree, remaining, leaf, path)?;
+            grids.push(subtree);
+            Ok(())
+        }
+    }
+}
+
+// find the grid of a mesh part in the tree
+fn find_in_tree<'a>(tree: &'a mut Grid, segments: &[String]) -> Option<&'a mut Grid> {
+    let (name, remaining) = segments.split_first()?;
+    let grid = tree
+        .grids
+        .as_mut()?
+        .iter_mut()
+        .find(|grid| &grid.name == name)?;
+
+    if remaining.is_empty() {
+        Some(grid)
+    } else {
+        find_in_tree(grid, remaining)
+    }
+}
+
+/// Grid of a mesh, referencing the registered `DataItems` of the mesh.
+struct MeshGrid {
+    grid: Grid,
+    num_points: usize,
+    num_cells: usize,
+}
+
+/// Coordinates of the points of a mesh.
+#[derive(Clone, Copy)]
+enum Coordinates<'a> {
+    /// Interleaved coordinates (x0, y0, z0, x1, y1, z1, ...)
+    Interleaved(&'a [f64]),
+    /// Separate arrays of the x, y and z coordinates
+    Separate([&'a [f64]; 3]),
+}
+
+impl Coordinates<'_> {
+    // validate the coordinates together with the cells and return the number of points
+    fn validate(self, cells: (&[u64], &[CellType])) -> XdmfResult<usize> {
+        match self {
+            Self::Interleaved(points) => {
+                validate_points_and_cells(points, cells)?;
+                Ok(points.len() / 3)
+            }
+            Self::Separate([x, y, z]) => {
+                if x.len() != y.len() || x.len() != z.len() {
+                    return Err(XdmfError::Validation(format!(
+                        "Coordinate arrays must have the same length, but have {}, {} and {}",
+                        x.len(),
+                        y.len(),
+                        z.len()
+                    )));
+                }
+
+                if x.is_empty() {
+                    return Err(XdmfError::Validation(
+                        "At least one point is required".into(),
+                    ));
+                }
+
+                validate_cells(x.len(), cells)?;
+                Ok(x.len())
+            }
+        }
+    }
+
+    // apply the transformation to the coordinates, keeping their layout
+    fn transformed(self, transform: &Transform) -> XdmfResult<TransformedCoordinates> {
+        transform.validate()?;
+
+        Ok(match self {
+            Self::Interleaved(points) => {
+                TransformedCoordinates::Interleaved(transform.apply_interleaved(points))
+            }
+            Self::Separate(xyz) => TransformedCoordinates::Separate(transform.apply_separate(xyz)),
+        })
+    }
+}
+
+/// Coordinates of the points of a mesh after applying a [`Transform`].
+enum TransformedCoordinates {
+    Interleaved(Vec<f64>),
+    Separate([Vec<f64>; 3]),
+}
+
+impl TransformedCoordinates {
+    fn as_coordinates(&self) -> Coordinates<'_> {
+        match self {
+            Self::Interleaved(points) => Coordinates::Interleaved(points),
+            Self::Separate([x, y, z]) => Coordinates::Separate([x, y, z]),
+        }
+    }
+}
+
+/// Validate the mesh, write its heavy data and create the grid referencing it.
+///
+/// If a name is given, the mesh is an additional mesh, e.g. an updated mesh or a part of the mesh.
+/// If a transformation is given, it is applied to the coordinates before they are written.
+fn create_mesh(
+    writer: &mut dyn DataWriter,
+    data_items: &mut DataItemRegistry,
+    coordinates: Coordinates,
+    cells: (&[u64], &[CellType]),
+    name_suffix: &str,
+    mesh_name: Option<&str>,
+    transform: Option<&Transform>,
+) -> XdmfResult<MeshGrid> {
+    let num_points = coordinates.validate(cells)?;
+
+    // the transformed coordinates are held by the writer, the coordinates of the caller are not modified
+    let transformed = transform
+        .map(|transform| coordinates.transformed(transform))
+        .transpose()?;
+    let coordinates = transformed
+        .as_ref()
+        .map_or(coordinates, TransformedCoordinates::as_coordinates);
+
+    let num_cells = if cells.1.is_empty() {
+        num_points
+    } else {
+        cells.1.len()
+    };
+
+    let (topo_type, prepared_cells) = prepare_cells(cells, num_points);
+    let cells_precision = writer.index_precision().resolve(&prepared_cells)?;
+
+    let name_suffix = mesh_name.map_or_else(
+        || name_suffix.to_string(),
+        |mesh_name| format!("{name_suffix}_{mesh_name}"),
+    );
+
+    let (geometry_type, coords_items, cells_data) = match coordinates {
+        Coordinates::Interleaved(points) => {
+            let (points_data, cells_data) = if let Some(mesh_name) = mesh_name {
+                writer.write_named_mesh(mesh_name, points, &prepared_cells)?
+            } else {
+                writer.write_mesh(points, &prepared_cells)?
+            };
+
+            (
+                GeometryType::XYZ,
+                vec![(
+                    format!("coords{name_suffix}"),
+                    points_data,
+                    vec![num_points, 3],
+                )],
+                cells_data,
+            )
+        }
+        Coordinates::Separate(xyz) => {
+            let ([x_data, y_data, z_data], cells_data) =
+                writer.write_mesh_soa(mesh_name, xyz, &prepared_cells)?;
+
+            (
+                GeometryType::SeparateXYZ,
+                vec![
+                    (format!("coords_x{name_suffix}"), x_data, vec![num_points]),
+                    (format!("coords_y{name_suffix}"), y_data, vec![num_points]),
+                    (format!("coords_z{name_suffix}"), z_data, vec![num_points]),
+                ],
+                cells_data,
+            )
+        }
+    };
+
+    let connectivity_name = format!("connectivity{name_suffix}");
+
+    let mut geometry_items = Vec::with_capacity(coords_items.len());
+    for (coords_name, points_data, dimensions) in coords_items {
+        data_items.register(writer.wrap_data_item(DataItem {
+            name: Some(coords_name.clone()),
+            dimensions: Some(Dimensions(dimensions)),
+            data: points_data,
+            number_type: Some(NumberType::Float),
+            precision: Some(8),
+            format: Some(writer.format()),
+            reference: None,
+            item_type: None,
+            encoding: writer.encoding(),
+            endian: None,
+            function: None,
+            data_items: vec![],
+        }))?;
+
+        geometry_items.push(data_items.reference(&coords_name)?);
+    }
+
+    data_items.register(writer.wrap_data_item(DataItem {
+        name: Some(connectivity_name.clone()),
+        dimensions: Some(Dimensions(vec![prepared_cells.len()])),
+        number_type: Some(NumberType::UInt),
+        data: cells_data,
+        format: Some(writer.format()),
+        precision: Some(cells_precision),
+        reference: None,
+        item_type: None,
+        encoding: writer.encoding(),
+        endian: None,
+        function: None,
+        data_items: vec![],
+    }))?;
+
+    let geometry = Geometry {
+        geometry_type,
+        data_items: geometry_items,
+    };
+    let topology = Topology {
+        topology_type: topo_type,
+        number_of_elements: num_cells.format_number(),
+        dimensions: None,
+        data_item: Some(data_items.reference(&connectivity_name)?),
+    };
+
+    Ok(MeshGrid {
+        grid: Grid::new_uniform("mesh", geometry, topology),
+        num_points,
+        num_cells,
+    })
+}
+
+// Validate that the points and cells are valid
+pub(crate) fn validate_points_and_cells(
+    points: &[f64],
+    cells: (&[u64], &[CellType]),
+) -> XdmfResult<()> {
+    validate_points(points)?;
+    validate_cells(points.len() / 3, cells)
+}
+
+// Validate that the interleaved points are valid
+fn validate_points(points: &[f64]) -> XdmfResult<()> {
+    // at least one point is required
+    if points.is_empty() {
+        return Err(XdmfError::Validation(
+            "At least one point is required".into(),
+        ));
+    }
+
+    // check that points are a multiple of 3 (x, y, z)
+    if !points.len().is_multiple_of(3) {
+        return Err(XdmfError::Validation(
+            "Points must have 3 dimensions".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+// Validate that the cells are valid for the given number of points
+fn validate_cells(num_points: usize, cells: (&[u64], &[CellType])) -> XdmfResult<()> {
+    // check that the number of connectivities matches the expected number based on the cell types
+    let exp_num_points: usize = cells.1.iter().map(|ct| ct.num_points()).sum();
+    if exp_num_points != cells.0.len() {
+        return Err(XdmfError::Validation(format!(
+            "Size of connectivities not match the expected number based on the cell types: {} != {}",
+            cells.0.len(),
+            exp_num_points
+        )));
+    }
+
+    // check cells connectivity indices, the face streams of polyhedra also contain the number of faces and points
+    let mut max_connectivity_index = None;
+    let mut index = 0;
+
+    for cell_type in cells.1 {
+        let nodes = &cells.0[index..index + cell_type.num_points()];
+        let max_cell_index = match cell_type {
+            CellType::Polyhedron(_) => face_stream_points(nodes)?.into_iter().max(),
+            _ => nodes.iter().copied().max(),
+        };
+
+        max_connectivity_index = max_connectivity_index.max(max_cell_index);
+        index += nodes.len();
+    }
+
+    // the number of points is compared as u64, as the indices might not fit into usize on 32 bit targets
+    if let Some(max_index) = max_connectivity_index
+        && max_index >= num_points as u64
+    {
+        return Err(XdmfError::Validation(format!(
+            "Connectivity indices out of bounds for the given points, max index: {max_index}, but number of points is {num_points}"
+        )));
+    }
+
+    Ok(())
+}
+
+// the points of the faces of a polyhedron, given as face stream
+pub(crate) fn face_stream_points(face_stream: &[u64]) -> XdmfResult<Vec<u64>> {
+    let invalid = || {
+        XdmfError::Validation(format!(
+            "Face stream of polyhedron does not match its size of {}: {face_stream:?}",
+            face_stream.len()
+        ))
+    };
+
+    let (&num_faces, mut faces) = face_stream.split_first().ok_or_else(invalid)?;
+    let mut points = Vec::new();
+
+    for _ in 0..num_faces {
+        let (&num_face_points, rest) = faces.split_first().ok_or_else(invalid)?;
+        let face_points = usize::try_from(num_face_points)
+            .ok()
+            .and_then(|num_face_points| rest.get(..num_face_points))
+            .ok_or_else(invalid)?;
+
+        points.extend_from_slice(face_points);
+        faces = &rest[face_points.len()..];
+    }
+
+    if num_faces == 0 || !faces.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(points)
+}
+
+// Poly-cells need to additionally specify the number of points
+fn poly_cell_points(cell_type: CellType) -> Option<u64> {
+    // For polyvertex and polyline, need to add the number of points
+    match cell_type {
+        CellType::Vertex => {
+            // polyvertex with one point
+            Some(1)
+        }
+        CellType::Edge => {
+            // polyline with two points
+            Some(2)
+        }
+        CellType::Polygon(num_points) => Some(num_points as u64),
+        // the face stream of a polyhedron starts with its number of faces
+        _ => None,
+    }
+}
+
+/// The uniform topology type for meshes consisting only of cells of the given type.
+///
+/// Edges are written as mixed topology, since polylines require specifying the number of points per element.
+/// The same applies to polygons and polyhedra.
+fn uniform_topology_type(cell_type: CellType) -> Option<TopologyType> {
+    match cell_type {
+        CellType::Vertex => Some(TopologyType::Polyvertex),
+        CellType::Edge | CellType::Polygon(_) | CellType::Polyhedron(_) => None,
+        CellType::Triangle => Some(TopologyType::Triangle),
+        CellType::Quadrilateral => Some(TopologyType::Quadrilateral),
+        CellType::Tetrahedron => Some(TopologyType::Tetrahedron),
+        CellType::Pyramid => Some(TopologyType::Pyramid),
+        CellType::Wedge => Some(TopologyType::Wedge),
+        CellType::Hexahedron => Some(TopologyType::Hexahedron),
+        CellType::Edge3 => Some(TopologyType::Edge3),
+        CellType::Quadrilateral9 => Some(TopologyType::Quadrilateral9),
+        CellType::Triangle6 => Some(TopologyType::Triangle6),
+        CellType::Quadrilateral8 => Some(TopologyType::Quadrilateral8),
+        CellType::Tetrahedron10 => Some(TopologyType::Tetrahedron10),
+        CellType::Pyramid13 => Some(TopologyType::Pyramid13),
+        CellType::Wedge15 => Some(TopologyType::Wedge15),
+        CellType::Wedge18 => Some(TopologyType::Wedge18),
+        CellType::Hexahedron20 => Some(TopologyType::Hexahedron20),
+        CellType::Hexahedron24 => Some(TopologyType::Hexahedron24),
+        CellType::Hexahedron27 => Some(TopologyType::Hexahedron27),
+    }
+}
+
+/// Prepare cells / connectivity for writing.
+///
+/// If all cells are of the same type, then the type is stored as `TopologyType` and the connectivity is used as is.
+/// Otherwise the cell type is prepended to the connectivity list, and for poly-cells, the number of points is also added.
+// position of the given cells in the connectivity written for the grid, `None` if the grid has no connectivity
+fn cell_layout(grid: &Grid, cells: (&[u64], &[CellType])) -> Option<CellLayout> {
+    let topology = grid.topology.as_ref()?;
+    topology.data_item.as_ref()?;
+
+    if topology.topology_type != TopologyType::Mixed {
+        // a mesh without cells is written as polyvertex, with one point per cell
+        return Some(CellLayout::Uniform(
+            cells
+                .1
+                .first()
+                .map_or(1, |cell_type| cell_type.num_points()),
+        ));
+    }
+
+    // each cell starts with its type, poly-cells also with the number of their points
+    let offsets = std::iter::once(0)
+        .chain(cells.1.iter().scan(0, |offset, &cell_type| {
+            *offset += 1
+                + u64::from(poly_cell_points(cell_type).is_some())
+                + cell_type.num_points() as u64;
+            Some(*offset)
+        }))
+        .collect();
+
+    Some(CellLayout::Mixed(offsets))
+}
+
+// coordinates of the values of the given cells, each cell having values of the given shape (empty for scalars)
+fn cell_value_coordinates(cell_indices: &[u64], shape: &[usize]) -> Vec<u64> {
+    let num_values: usize = shape.iter().product();
+    let mut coordinates = Vec::with_capacity(cell_indices.len() * num_values * (1 + shape.len()));
+
+    for &cell in cell_indices {
+        for value in 0..num_values {
+            coordinates.push(cell);
+
+            // the index of the value in each dimension of the shape, the last one varying fastest
+            let start = coordinates.len();
+            let mut remainder = value;
+            for &len in shape.iter().rev() {
+                coordinates.push((remainder % len) as u64);
+                remainder /= len;
+            }
+            coordinates[start..].reverse();
+        }
+    }
+
+    coordinates
+}
+
+fn prepare_cells(cells: (&[u64], &[CellType]), num_points: usize) -> (TopologyType, Vec<u64>) {
+    if cells.1.is_empty() {
+        // if there are no cells, use polyvertex on nodes
+        // this is required by paraview to visualize only points
+        return (TopologyType::Polyvertex, (0..num_points as u64).collect());
+    }
+
+    let first_cell_type = cells.1[0];
+    if cells
+        .1
+        .iter()
+        .all(|cell_type| *cell_type == first_cell_type)
+        && let Some(topology_type) = uniform_topology_type(first_cell_type)
+    {
+        return (topology_type, cells.0.to_vec());
+    }
+
+    let mut cells_with_types = Vec::with_capacity(cells.0.len() + cells.1.len());
+    let mut index = 0_usize;
+
+    for cell_type in cells.1 {
+        let num_points = cell_type.num_points();
+        cells_with_types.push(cell_type.xdmf_id());
+
+        if let Some(n_points_poly) = poly_cell_points(*cell_type) {
+            // poly-cells need to specify the number of points
+            cells_with_types.push(n_points_poly);
+        }
+
+        cells_with_types.extend_from_slice(&cells.0[index..index + num_points]);
+
+        index += num_points; // move index to the next cell
+    }
+
+    (TopologyType::Mixed, cells_with_types)
+}
+
+/// Writer for time series data in XDMF format. Can be used after writing the mesh with `TimeSeriesWriter::write_mesh`.
+pub struct TimeSeriesDataWriter {
+    xdmf_file_name: PathBuf,
+    writer: Box<dyn DataWriter>,
+    name_suffix: String,
+    information: Vec<Information>,
+    field_information: BTreeMap<String, Vec<Information>>,
+    grid: Grid,
+    data_items: DataItemRegistry,
+    time_grids: Vec<Grid>,
+    writen_times: HashSet<String>,
+    // label and index (in the order of writing) of the time steps in `time_grids`
+    steps: Vec<(String, usize)>,
+    // values of the `Time` elements of the time steps in `time_grids`
+    times: Vec<String>,
+    // fields whose data has been written, in the order of their first write
+    written_fields: Vec<(String, DataAttribute, attribute::Center)>,
+    // labels of the time steps pruned by the retention policy, their heavy data is deleted once the XDMF file is written
+    pruned_steps: Vec<String>,
+    registered_fields: Vec<(String, DataAttribute, attribute::Center)>,
+    derived_fields: Vec<DerivedField>,
+    // points of the cells, only kept if averaged fields are requested
+    cell_points: Option<CellPoints>,
+    // attributes of the fields that are written once, referencing their data
+    static_attributes: Vec<Arc<attribute::Attribute>>,
+    // names of the subsets of the mesh, and the references to their cell indices
+    subsets: Vec<(String, DataItem)>,
+    submeshes: Vec<Submesh>,
+    parts: Vec<PartInfo>,
+    // the index of the given cell for each written cell, for a partitioned mesh grouped by part,
+    // for a subdivided mesh repeated for each of its linear cells
+    cell_order: Option<Vec<usize>>,
+    // position of the cells in the written connectivity, `None` for meshes without connectivity or of multiple parts
+    cell_layout: Option<CellLayout>,
+    num_points: usize,
+    num_cells: usize,
+    // declared by the user, or derived for image data
+    num_edges: Option<usize>,
+    num_faces: Option<usize>,
+    flush_policy: FlushPolicy,
+    retention_policy: RetentionPolicy,
+    time_list: bool,
+    // whether a file with only the most recent time step is written as well
+    latest_step_file: bool,
+    rollover: Option<Rollover>,
+    time_scale: Option<f64>,
+    tensor6_as_matrix: bool,
+    transform: Option<Transform>,
+    file_system: Arc<dyn FileSystem>,
+    // output frequency of the fields that are not written at every time step
+    output_frequencies: BTreeMap<String, usize>,
+    reference_style: ReferenceStyle,
+    xml_header: XmlHeader,
+    progress_callback: Option<ProgressCallback>,
+    subdivide_quadratic: bool,
+    field_options: FieldOptions,
+    // number of writes since the XDMF file was last written
+    pending_writes: u32,
+    closed: bool,
+}
+
+/// Split of the time steps into multiple XDMF files, see `TimeSeriesWriter::with_rollover`.
+struct Rollover {
+    steps_per_file: usize,
+    // checksums of the contents of the written files, a file is only rewritten if its contents changed
+    checksums: Vec<u64>,
+}
+
+impl Rollover {
+    fn new(steps_per_file: usize) -> Self {
+        Self {
+            steps_per_file,
+            checksums: Vec::new(),
+        }
+    }
+}
+
+/// Field whose values are computed by the reader from other fields of the same time step.
+struct DerivedField {
+    name: String,
+    data_attribute: DataAttribute,
+    center: attribute::Center,
+    function: String,
+    fields: Vec<String>,
+}
+
+/// Position of a mesh part in the tree, and the range of its points and cells in the data.
+struct PartInfo {
+    path: Vec<String>,
+    points: Range<usize>,
+    cells: Range<usize>,
+}
+
+/// Position of the cells in the written connectivity, to select the connectivity of the cells of a submesh.
+enum CellLayout {
+    /// All cells have the same number of entries
+    Uniform(usize),
+    /// Start of each cell in the connectivity of a mixed topology, followed by its length
+    Mixed(Vec<u64>),
+}
+
+impl CellLayout {
+    // indices of the entries of the given cells in the connectivity
+    fn entries(&self, cell_indices: &[u64]) -> Vec<u64> {
+        match self {
+            Self::Uniform(num_entries) => {
+                let num_entries = *num_entries as u64;
+                cell_indices
+                    .iter()
+                    .flat_map(|&cell| cell * num_entries..(cell + 1) * num_entries)
+                    .collect()
+            }
+            Self::Mixed(offsets) => cell_indices
+                .iter()
+                .flat_map(|&cell| offsets[cell as usize]..offsets[cell as usize + 1])
+                .collect(),
+        }
+    }
+}
+
+/// A submesh of the cells of the mesh, whose grids select the connectivity and the cell data of the grids of the mesh.
+struct Submesh {
+    name: String,
+    cell_indices: Vec<u64>,
+    num_entries: usize,
+    // reference to the coordinates of the entries of the cells in the connectivity
+    connectivity: DataItem,
+    // references to the coordinates of the cell values, per shape of the values of a cell (empty for scalars)
+    cell_values: Vec<(Vec<usize>, DataItem)>,
+}
+
+impl TimeSeriesDataWriter {
+    /// Write point and cell data for a specific time step.
+    ///
+    /// The time step is identified by a [`StepKey`], e.g. the time as str (leaving the formatting to the user) or f64,
+    /// the cycle as integer, or a [`StepLabel`](crate::StepLabel). The format of the label of a f64 time can be chosen with [`FormattedTime`](crate::FormattedTime).
+    /// The time steps can be written in any order, they are emitted in increasing order of their time.
+    /// Sizes of the data arrays are validated to ensure consistency with the mesh and defined dat types.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_data", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// // define 3 points and 2 cells (a line and a triangle)
+    /// let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    /// let connectivity = [0, 1, 0, 2, 1]; // line (0,1) and triangle (0,2,1)
+    /// let cell_types = [xdmf::CellType::Edge, xdmf::CellType::Triangle];
+    ///
+    /// // write the mesh
+    /// let mut time_series_writer = xdmf_writer
+    ///     .write_mesh(&coords, (&connectivity, &cell_types))
+    ///     .expect("failed to write mesh");
+    ///
+    /// // define some point and cell data for time step 0.0
+    /// let point_data = vec![(
+    ///     "point_data".to_string(),
+    ///     (xdmf::DataAttribute::Vector, vec![0.0; 9].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let cell_data = vec![(
+    ///     "cell_data".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![0.0, 1.0].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// // write the data for 10 time steps
+    /// for i in 0..10 {
+    ///     time_series_writer
+    ///         .write_data(&i.to_string(), Some(&point_data), Some(&cell_data))
+    ///         .expect("failed to write time step data");
+    /// }
+    /// ```
+    pub fn write_data(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        point_data: Option<&DataMap>,
+        cell_data: Option<&DataMap>,
+    ) -> XdmfResult<()> {
+        self.write_step(step, point_data, cell_data, false, false)
+    }
+
+    /// Write point and cell data for a time step, writing the heavy data of the fields concurrently.
+    ///
+    /// This speeds up writing many large fields, e.g. on parallel file systems, where a single stream underutilizes the I/O bandwidth.
+    /// The text and base64 formats are written concurrently, while the HDF5 library serializes all calls,
+    /// hence the fields are written one after another for the HDF5 data storages.
+    /// Requires the `parallel` feature. See [`TimeSeriesDataWriter::write_data`] for details.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_data_parallel", xdmf::DataStorage::Ascii)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// let mut time_series_writer = xdmf_writer
+    ///     .write_mesh(
+    ///         &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+    ///         (&[0, 1], &[xdmf::CellType::Edge]),
+    ///     )
+    ///     .expect("failed to write mesh");
+    ///
+    /// let point_data = (0..8)
+    ///     .map(|i| {
+    ///         (
+    ///             format!("field_{i}"),
+    ///             (xdmf::DataAttribute::Scalar, vec![f64::from(i); 2].into()),
+    ///         )
+    ///     })
+    ///     .collect();
+    ///
+    /// time_series_writer
+    ///     .write_data_parallel("0.0", Some(&point_data), None)
+    ///     .expect("failed to write time step data");
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn write_data_parallel(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        point_data: Option<&DataMap>,
+        cell_data: Option<&DataMap>,
+    ) -> XdmfResult<()> {
+        self.write_step(step, point_data, cell_data, false, true)
+    }
+
+    /// Write point and cell data for a time step, replacing the data if the time step has already been written.
+    ///
+    /// This allows to re-write the data of a time step, e.g. after a repeated sub-iteration of an iterative coupling scheme.
+    /// All attributes of the time step are replaced (including its global data), and the heavy data is overwritten.
+    /// If the time step has not been written yet, this is the same as [`TimeSeriesDataWriter::write_data`].
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("xdmf_write_data_overwrite", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer");
+    ///
+    /// let mut time_series_writer = xdmf_writer
+    ///     .write_mesh(&[0.0, 0.0, 0.0], (&[], &[]))
+    ///     .expect("failed to write mesh");
+    ///
+    /// for iteration in 0..3 {
+    ///     let point_data = vec![(
+    ///         "displacement".to_string(),
+    ///         (xdmf::DataAttribute::Scalar, vec![iteration as f64].into()),
+    ///     )]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    ///     // only the data of the last iteration is kept
+    ///     time_series_writer
+    ///         .write_data_overwrite("1.0", Some(&point_data), None)
+    ///         .expect("failed to write time step data");
+    /// }
+    /// ```
+    pub fn write_data_overwrite(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        point_data: Option<&DataMap>,
+        cell_data: Option<&DataMap>,
+    ) -> XdmfResult<()> {
+        self.write_step(step, point_data, cell_data, true, false)
+    }
+
+    // write the data of a time step, an existing time step is only replaced if overwriting is requested,
+    // the fields are written concurrently if requested
+    fn write_step(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        point_data: Option<&DataMap>,
+        cell_data: Option<&DataMap>,
+        overwrite: bool,
+        parallel: bool,
+    ) -> XdmfResult<()> {
+        let grid_name = format!("time_series-t{}", step.label());
+        let existing_index = self
+            .time_grids
+            .iter()
+            .position(|grid| grid.name == grid_name);
+
+        let (label, time) = self.resolve_step(step, self.writen_times.len())?;
+
+        if existing_index.is_some() && !overwrite {
+            return Err(XdmfError::Validation(format!(
+                "Time step '{label}' has already been written"
+            )));
+        }
+        self.check_not_pruned(&label, existing_index)?;
+
+        validate_data((self.num_points, self.num_cells), point_data, cell_data)?;
+        check_registered_fields(&self.registered_fields, point_data, attribute::Center::Node)?;
+        check_registered_fields(&self.registered_fields, cell_data, attribute::Center::Cell)?;
+        check_derived_fields(&self.derived_fields, point_data, cell_data)?;
+        check_static_fields(&self.static_attributes, point_data, attribute::Center::Node)?;
+        check_static_fields(&self.static_attributes, cell_data, attribute::Center::Cell)?;
+
+        // an existing time step keeps its index, hence the same fields are due when overwriting it
+        let step_index =
+            existing_index.map_or(self.writen_times.len(), |index| self.steps[index].1);
+        let due_point_data = due_fields(&self.output_frequencies, point_data, step_index);
+        let due_cell_data = due_fields(&self.output_frequencies, cell_data, step_index);
+        let (point_data, cell_data) = (
+            due_point_data.as_ref().or(point_data),
+            due_cell_data.as_ref().or(cell_data),
+        );
+        let (averaged_point_data, averaged_cell_data) = add_averaged_fields(
+            &self.field_options.averaged_fields,
+            self.cell_points.as_ref(),
+            (point_data, cell_data),
+        )?;
+        let (point_data, cell_data) = (
+            averaged_point_data.as_ref().or(point_data),
+            averaged_cell_data.as_ref().or(cell_data),
+        );
+        self.record_fields(point_data, attribute::Center::Node);
+        self.record_fields(cell_data, attribute::Center::Cell);
+
+        self.writer.write_data_initialize(&label)?;
+
+        // an existing time step keeps its mesh, which might differ from the current one after a mesh update,
+        // and its time, e.g. the index of a step label
+        let mut grid = existing_index.map_or_else(
+            || {
+                let mut grid = self.grid.clone();
+                grid.name = grid_name;
+                grid.time = Some(Time::new(time));
+                grid
+            },
+            |index| self.time_grids[index].clone(),
+        );
+        grid.information = step_information(step);
+        grid.attributes = None;
+
+        // the cells of a partitioned mesh are grouped by part, and the cells of a subdivided mesh repeat the data of their given cell
+        let grouped_cell_data = self
+            .cell_order
+            .as_deref()
+            .zip(cell_data)
+            .map(|(order, data)| grouped_data(data, order));
+        let cell_data = grouped_cell_data.as_ref().or(cell_data);
+
+        if self.parts.is_empty() {
+            let mut attributes = create_attributes(
+                self.writer.as_mut(),
+                &self.field_information,
+                &self.field_options,
+                (point_data, cell_data),
+                None,
+                None,
+                parallel,
+            )?;
+            add_derived_attributes(
+                &self.derived_fields,
+                &self.field_information,
+                &mut attributes,
+            );
+            if let Some(dimensions) = grid
+                .topology
+                .as_ref()
+                .and_then(|topology| topology.dimensions.as_ref())
+            {
+                reshape_image_attributes(&mut attributes, &dimensions.0);
+            }
+            attributes.extend(self.static_attributes.iter().cloned());
+            self.select_submesh_cell_values(&attributes)?;
+            resolve_name_collisions(
+                &self.field_options.name_collision_policy,
+                &mut attributes,
+                &label,
+            )?;
+            grid.attributes = Some(attributes);
+        } else {
+            // the points of a partitioned mesh are shared by all parts
+            let (shared_point_attributes, point_data) = if self.cell_order.is_some() {
+                let attributes = create_attributes(
+                    self.writer.as_mut(),
+                    &self.field_information,
+                    &self.field_options,
+                    (point_data, None),
+                    None,
+                    None,
+                    parallel,
+                )?;
+                (attributes, None)
+            } else {
+                (vec![], point_data)
+            };
+
+            for i in 0..self.parts.len() {
+                let (points, cells) = (self.parts[i].points.clone(), self.parts[i].cells.clone());
+                let mut attributes = shared_point_attributes.clone();
+                attributes.extend(create_attributes(
+                    self.writer.as_mut(),
+                    &self.field_information,
+                    &self.field_options,
+                    (point_data, cell_data),
+                    Some(&format!("part{i}")),
+                    Some((points, cells)),
+                    parallel,
+                )?);
+                add_derived_attributes(
+                    &self.derived_fields,
+                    &self.field_information,
+                    &mut attributes,
+                );
+                resolve_name_collisions(
+                    &self.field_options.name_collision_policy,
+                    &mut attributes,
+                    &label,
+                )?;
+
+                let part_grid = find_in_tree(&mut grid, &self.parts[i].path)
+                    .ok_or_else(|| XdmfError::Validation("Mesh part not found in tree".into()))?;
+                part_grid.attributes = Some(attributes);
+            }
+        }
+
+        if let Some(index) = existing_index {
+            self.time_grids[index] = grid;
+        } else {
+            self.add_time_grid(label, grid);
+        }
+
+        self.writer.write_data_finalize()?;
+
+        self.write_with_policy()
+    }
+
+    /// Write global (grid-centered) data for a time step, e.g. the total energy or the residual norm.
+    ///
+    /// Each entry holds exactly one value of the given `DataAttribute`, e.g. one value for a scalar.
+    /// The point and/or cell data of the time step must be written before.
+    /// As the data is small, it is always written inline into the XDMF file, regardless of the `DataStorage`.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("xdmf_write_global_data", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer");
+    ///
+    /// let mut time_series_writer = xdmf_writer
+    ///     .write_mesh(&[0.0, 0.0, 0.0], (&[], &[]))
+    ///     .expect("failed to write mesh");
+    ///
+    /// let point_data = vec![(
+    ///     "point_data".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![0.0].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let global_data = vec![(
+    ///     "kinetic_energy".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![1.5].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// time_series_writer
+    ///     .write_data("0.0", Some(&point_data), None)
+    ///     .expect("failed to write time step data");
+    /// time_series_writer
+    ///     .write_global_data("0.0", &global_data)
+    ///     .expect("failed to write global data");
+    /// ```
+    pub fn write_global_data(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        global_data: &DataMap,
+    ) -> XdmfResult<()> {
+        let (label, _) = resolve_step(step, self.time_grids.len())?;
+        check_data_size(Some(global_data), 1, "global")?;
+        validate_data_name(Some(global_data), "global")?;
+        check_registered_fields(
+            &self.registered_fields,
+            Some(global_data),
+            attribute::Center::Grid,
+        )?;
+
+        let grid_name = format!("time_series-t{label}");
+        let grid = self
+            .time_grids
+            .iter_mut()
+            .find(|grid| grid.name == grid_name)
+            .ok_or_else(|| {
+                XdmfError::Validation(format!(
+                        "Time step '{label}' has not been written yet, its point or cell data must be written first"
+                    ))
+            })?;
+
+        let attributes = grid.attributes.get_or_insert_with(Vec::new);
+
+        if let Some(data_name) = global_data.keys().find(|data_name| {
+            attributes
+                .iter()
+                .any(|attr| attr.center == attribute::Center::Grid && attr.name == **data_name)
+        }) {
+            return Err(XdmfError::Validation(format!(
+                "Global data '{data_name}' has already been written for time step '{label}'"
+            )));
+        }
+
+        for (data_name, data) in global_data {
+            let vals = &data.1;
+            record_field(
+                &mut self.written_fields,
+                data_name,
+                &data.0,
+                attribute::Center::Grid,
+            );
+
+            attributes.push(Arc::new(attribute::Attribute {
+                name: data_name.clone(),
+                attribute_type: (&data.0).into(),
+                center: attribute::Center::Grid,
+                information: information_of_field(&self.field_information, data_name),
+                data_items: vec![DataItem {
+                    name: None,
+                    dimensions: Some(vals.dimensions(&data.0)),
+                    number_type: Some(vals.number_type()),
+                    format: Some(Format::XML),
+                    precision: Some(vals.precision()),
+                    data: values_to_string(vals).into(),
+                    reference: None,
+                    item_type: None,
+                    encoding: None,
+                    endian: None,
+                    function: None,
+                    data_items: vec![],
+                }],
+            }));
+        }
+
+        self.write_with_policy()
+    }
+
+    /// Write point and cell data that is constant in time, e.g. material properties.
+    ///
+    /// The data is written only once, and all time steps written afterwards reference it,
+    /// which saves disk space and I/O time compared to writing it in every time step.
+    /// Static fields must not be part of the data of the time steps, and they are not validated against the registered fields.
+    /// Static data belongs to the mesh, hence it must be written again after [`TimeSeriesDataWriter::write_mesh_update`].
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("xdmf_write_static_data", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer");
+    ///
+    /// let mut time_series_writer = xdmf_writer
+    ///     .write_mesh(
+    ///         &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+    ///         (&[0, 1], &[xdmf::CellType::Edge]),
+    ///     )
+    ///     .expect("failed to write mesh");
+    ///
+    /// let material = vec![(
+    ///     "material".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![1_u64].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// time_series_writer
+    ///     .write_static_data(None, Some(&material))
+    ///     .expect("failed to write static data");
+    ///
+    /// for i in 0..10 {
+    ///     let point_data = vec![(
+    ///         "temperature".to_string(),
+    ///         (xdmf::DataAttribute::Scalar, vec![i as f64; 2].into()),
+    ///     )]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    ///     // the material is added to each time step
+    ///     time_series_writer
+    ///         .write_data(&i.to_string(), Some(&point_data), None)
+    ///         .expect("failed to write time step data");
+    /// }
+    /// ```
+    pub fn write_static_data(
+        &mut self,
+        point_data: Option<&DataMap>,
+        cell_data: Option<&DataMap>,
+    ) -> XdmfResult<()> {
+        if !self.parts.is_empty() {
+            return Err(XdmfError::Unsupported(
+                "Static data is not supported for meshes consisting of multiple parts".into(),
+            ));
+        }
+
+        validate_data((self.num_points, self.num_cells), point_data, cell_data)?;
+
+        for (data, center) in [
+            (point_data, attribute::Center::Node),
+            (cell_data, attribute::Center::Cell),
+        ] {
+            if let Some(data_name) = find_static_field(&self.static_attributes, data, center) {
+                return Err(XdmfError::Validation(format!(
+                    "Static {}-data '{data_name}' has already been written",
+                    center_label(center).unwrap_or_default()
+                )));
+            }
+        }
+
+        self.record_fields(point_data, attribute::Center::Node);
+        self.record_fields(cell_data, attribute::Center::Cell);
+
+        self.writer.write_data_initialize(STATIC_DATA_LABEL)?;
+
+        let grouped_cell_data = self
+            .cell_order
+            .as_deref()
+            .zip(cell_data)
+            .map(|(order, data)| grouped_data(data, order));
+
+        let mut attributes = create_attributes(
+            self.writer.as_mut(),
+            &self.field_information,
+            &self.field_options,
+            (point_data, grouped_cell_data.as_ref().or(cell_data)),
+            None,
+            None,
+            false,
+        )?;
+        if let Some(dimensions) = self
+            .grid
+            .topology
+            .as_ref()
+            .and_then(|topology| topology.dimensions.as_ref())
+        {
+            reshape_image_attributes(&mut attributes, &dimensions.0);
+        }
+
+        self.writer.write_data_finalize()?;
+
+        self.select_submesh_cell_values(&attributes)?;
+
+        // the data is registered once, the attributes of the time steps reference it
+        for attribute in attributes {
+            let mut attribute = Arc::unwrap_or_clone(attribute);
+            let item_name = format!(
+                "{STATIC_DATA_LABEL}_{}_{}{}",
+                center_label(attribute.center).unwrap_or_default(),
+                attribute.name,
+                self.name_suffix
+            );
+
+            for mut data_item in std::mem::take(&mut attribute.data_items) {
+                data_item.name = Some(item_name.clone());
+                self.data_items.register(data_item)?;
+                attribute
+                    .data_items
+                    .push(self.data_items.reference(&item_name)?);
+            }
+
+            self.static_attributes.push(Arc::new(attribute));
+        }
+
+        self.write()
+    }
+
+    /// Declare the number of edges of the mesh, which is required for writing edge-data.
+    ///
+    /// For image data it is derived from the grid. After [`TimeSeriesDataWriter::write_mesh_update`] it must be declared again.
+    pub fn set_num_edges(&mut self, num_edges: usize) {
+        self.num_edges = Some(num_edges);
+    }
+
+    /// Declare the number of faces of the mesh, which is required for writing face-data.
+    ///
+    /// See [`TimeSeriesDataWriter::set_num_edges`] for details.
+    pub fn set_num_faces(&mut self, num_faces: usize) {
+        self.num_faces = Some(num_faces);
+    }
+
+    /// Write edge- and face-centered data for a time step, e.g. the velocities on the faces of a staggered grid.
+    ///
+    /// The number of edges and faces must be declared with [`TimeSeriesDataWriter::set_num_edges`] and [`TimeSeriesDataWriter::set_num_faces`],
+    /// for image data they are derived from the grid. The point and/or cell data of the time step must be written before.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "xdmf_write_edge_and_face_data",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer");
+    ///
+    /// // a single voxel, with 12 edges and 6 faces
+    /// let mut ts_writer = xdmf_writer
+    ///     .write_image_data([0.0; 3], [1.0; 3], [2, 2, 2])
+    ///     .expect("failed to write image data");
+    ///
+    /// let cell_data = vec![(
+    ///     "pressure".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![1.0].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let face_data = vec![(
+    ///     "normal_velocity".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![0.5; 6].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// ts_writer
+    ///     .write_data("0.0", None, Some(&cell_data))
+    ///     .expect("failed to write data");
+    /// ts_writer
+    ///     .write_edge_and_face_data("0.0", None, Some(&face_data))
+    ///     .expect("failed to write face data");
+    /// ```
+    pub fn write_edge_and_face_data(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        edge_data: Option<&DataMap>,
+        face_data: Option<&DataMap>,
+    ) -> XdmfResult<()> {
+        let (label, _) = resolve_step(step, self.time_grids.len())?;
+
+        let data_maps = [
+            (edge_data, attribute::Center::Edge, self.num_edges),
+            (face_data, attribute::Center::Face, self.num_faces),
+        ];
+
+        if data_maps
+            .iter()
+            .all(|(data, ..)| data.is_none_or(BTreeMap::is_empty))
+        {
+            return Err(XdmfError::Validation(
+                "At least one of edge_data or face_data must be provided".into(),
+            ));
+        }
+
+        if !self.parts.is_empty() {
+            return Err(XdmfError::Validation(
+                "Edge- and face-data is not supported for meshes consisting of multiple parts"
+                    .into(),
+            ));
+        }
+
+        let grid_name = format!("time_series-t{label}");
+        let grid_index = self
+            .time_grids
+            .iter()
+            .position(|grid| grid.name == grid_name)
+            .ok_or_else(|| {
+                XdmfError::Validation(format!(
+                    "Time step '{label}' has not been written yet, its point or cell data must be written first"
+                ))
+            })?;
+
+        for (data, center, num_entities) in data_maps {
+            let Some(data_map) = data else {
+                continue;
+            };
+
+            let entity_label = center_label(center).unwrap_or_default();
+            let num_entities = num_entities.ok_or_else(|| {
+                XdmfError::Validation(format!(
+                    "Number of {entity_label}s must be declared before writing {entity_label}-data"
+                ))
+            })?;
+
+            check_data_size(Some(data_map), num_entities, entity_label)?;
+            validate_data_name(Some(data_map), entity_label)?;
+            check_registered_fields(&self.registered_fields, Some(data_map), center)?;
+
+            if let Some(data_name) = data_map.keys().find(|data_name| {
+                self.time_grids[grid_index]
+                    .attributes
+                    .iter()
+                    .flatten()
+                    .any(|attr| attr.center == center && attr.name == **data_name)
+            }) {
+                return Err(XdmfError::Validation(format!(
+                    "Data '{data_name}' of {entity_label}-data has already been written for time step '{label}'"
+                )));
+            }
+        }
+
+        for (data, center, _) in data_maps {
+            self.record_fields(data, center);
+        }
+
+        self.writer.write_data_initialize(&label)?;
+        let attributes = data_maps
+            .into_iter()
+            .flat_map(|(data, center, _)| {
+                data.into_iter()
+                    .flatten()
+                    .map(move |(data_name, data)| (data_name, data, center))
+            })
+            .map(|(data_name, (data_attribute, values), center)| {
+                create_attribute(
+                    self.writer.as_mut(),
+                    &self.field_information,
+                    &self.field_options,
+                    (data_name, data_attribute, values),
+                    center,
+                    data_name,
+                )
+            })
+            .collect::<XdmfResult<Vec<_>>>();
+        self.writer.write_data_finalize()?;
+
+        self.time_grids[grid_index]
+            .attributes
+            .get_or_insert_with(Vec::new)
+            .extend(attributes?);
+
+        self.write_with_policy()
+    }
+
+    /// Write point data for a time step, with the values provided by an iterator.
+    ///
+    /// This avoids holding all values in memory, as they are written in chunks to the heavy data files.
+    /// The iterator must provide exactly `len` values, which must equal the number of points times the size of the `DataAttribute`.
+    /// If the time step has not been written yet, it is created. Otherwise the data is added to the existing time step.
+    /// Streaming is not supported for meshes consisting of multiple parts.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_data_streamed", xdmf::DataStorage::Ascii)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// let num_points = 1000;
+    /// let points: Vec<f64> = (0..num_points * 3).map(|i| i as f64).collect();
+    ///
+    /// let mut time_series_writer = xdmf_writer
+    ///     .write_mesh(&points, (&[], &[]))
+    ///     .expect("failed to write mesh");
+    ///
+    /// // the values are computed on the fly, without creating a vector
+    /// time_series_writer
+    ///     .write_point_data_streamed(
+    ///         "0.0",
+    ///         "temperature",
+    ///         xdmf::DataAttribute::Scalar,
+    ///         (0..num_points).map(|i| 20.0 + i as f64 * 0.1),
+    ///         num_points,
+    ///     )
+    ///     .expect("failed to write streamed data");
+    /// ```
+    pub fn write_point_data_streamed(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        name: &str,
+        data_attribute: DataAttribute,
+        values: impl IntoIterator<Item = f64>,
+        len: usize,
+    ) -> XdmfResult<()> {
+        self.write_data_streamed(
+            step,
+            attribute::Center::Node,
+            name,
+            data_attribute,
+            &mut values.into_iter(),
+            len,
+        )
+    }
+
+    /// Write cell data for a time step, with the values provided by an iterator.
+    ///
+    /// See [`TimeSeriesDataWriter::write_point_data_streamed`] for details.
+    pub fn write_cell_data_streamed(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        name: &str,
+        data_attribute: DataAttribute,
+        values: impl IntoIterator<Item = f64>,
+        len: usize,
+    ) -> XdmfResult<()> {
+        self.write_data_streamed(
+            step,
+            attribute::Center::Cell,
+            name,
+            data_attribute,
+            &mut values.into_iter(),
+            len,
+        )
+    }
+
+    fn write_data_streamed(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        center: attribute::Center,
+        name: &str,
+        data_attribute: DataAttribute,
+        values: &mut dyn Iterator<Item = f64>,
+        len: usize,
+    ) -> XdmfResult<()> {
+        let (label, time) = self.resolve_step(step, self.writen_times.len())?;
+
+        let (num_entities, entity_label) = match center {
+            attribute::Center::Node => (self.num_points, "point"),
+            _ => (self.num_cells, "cell"),
+        };
+
+        if !is_valid_data_name(name) {
+            return Err(XdmfError::Validation(format!(
+                "Data name '{name}' of {entity_label}-data is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
+            )));
+        }
+
+        let exp_size = num_entities * data_attribute.size();
+        if len != exp_size {
+            return Err(XdmfError::Validation(format!(
+                "Size of {entity_label}-data '{name}' must be {exp_size}, but is {len}"
+            )));
+        }
+
+        if !self.parts.is_empty() {
+            return Err(XdmfError::Validation(
+                "Streaming data is not supported for meshes consisting of multiple parts".into(),
+            ));
+        }
+
+        if self.cell_order.is_some() && center == attribute::Center::Cell {
+            return Err(XdmfError::Unsupported(
+                "Streaming cell data is not supported for meshes with subdivided quadratic cells"
+                    .into(),
+            ));
+        }
+
+        // the data of a time step is streamed field by field, hence missing fields can not be detected
+        check_registered_field(&self.registered_fields, name, &data_attribute, center)?;
+        validate_component_names(&self.field_information, name, &data_attribute)?;
+
+        let grid_name = format!("time_series-t{label}");
+        let grid_index = self
+            .time_grids
+            .iter()
+            .position(|grid| grid.name == grid_name);
+        self.check_not_pruned(&label, grid_index)?;
+
+        // a cell-data field keeps its original name in case its attribute was renamed
+        let suffixed_name = match &self.field_options.name_collision_policy {
+            NameCollisionPolicy::SuffixCellData(suffix) if center == attribute::Center::Cell => {
+                Some(format!("{name}{suffix}"))
+            }
+            _ => None,
+        };
+        if let Some(index) = grid_index
+            && self.time_grids[index]
+                .attributes
+                .iter()
+                .flatten()
+                .any(|attr| {
+                    attr.center == center
+                        && (attr.name == name || suffixed_name.as_ref() == Some(&attr.name))
+                })
+        {
+            return Err(XdmfError::Validation(format!(
+                "Data '{name}' of {entity_label}-data has already been written for time step '{label}'"
+            )));
+        }
+
+        // the values are checked and hashed while they are streamed, without collecting them
+        let nan_policy = self.field_options.nan_policy;
+        let mut num_non_finite = 0;
+        let mut hasher = self.field_options.checksums.then(|| Xxh64::new(0));
+        let mut values = values.map(|value| {
+            let value = if value.is_finite() || nan_policy == NanPolicy::Allow {
+                value
+            } else {
+                num_non_finite += 1;
+                match nan_policy {
+                    NanPolicy::ReplaceWith(replacement) => replacement,
+                    NanPolicy::Allow | NanPolicy::Error => value,
+                }
+            };
+
+            if let Some(hasher) = &mut hasher {
+                hasher.update(&value.to_le_bytes());
+            }
+            value
+        });
+
+        self.writer.write_data_initialize(&label)?;
+        let data = self
+            .writer
+            .write_data_streamed(name, center, &mut values, len);
+        self.writer.write_data_finalize()?;
+        let data = data?;
+
+        // the non-finite values are only known after streaming, a rejected field can be written again
+        check_non_finite(nan_policy, name, num_non_finite)?;
+        record_field(&mut self.written_fields, name, &data_attribute, center);
+
+        let data_item = self.writer.wrap_data_item(DataItem {
+            name: None,
+            dimensions: Some(data_attribute.dimensions(len)),
+            number_type: Some(NumberType::Float),
+            format: Some(self.writer.format()),
+            precision: Some(8),
+            data,
+            reference: None,
+            item_type: None,
+            encoding: self.writer.encoding(),
+            endian: None,
+            function: None,
+            data_items: vec![],
+        });
+
+        let mut information = information_of_field(&self.field_information, name);
+        if let Some(hasher) = hasher {
+            information.push(checksum_information(hasher.digest()));
+        }
+
+        let attribute = Arc::new(attribute::Attribute {
+            name: name.to_string(),
+            attribute_type: data_attribute.into(),
+            center,
+            information,
+            data_items: vec![data_item],
+        });
+        self.select_submesh_cell_values(std::slice::from_ref(&attribute))?;
+
+        let mut attributes = grid_index
+            .and_then(|index| self.time_grids[index].attributes.clone())
+            .unwrap_or_default();
+        attributes.push(attribute);
+        resolve_name_collisions(
+            &self.field_options.name_collision_policy,
+            &mut attributes,
+            &label,
+        )?;
+
+        if let Some(index) = grid_index {
+            self.time_grids[index].attributes = Some(attributes);
+        } else {
+            let mut grid = self.grid.clone();
+            grid.name = grid_name;
+            grid.time = Some(Time::new(time));
+            grid.information = step_information(step);
+            grid.attributes = Some(attributes);
+
+            self.add_time_grid(label, grid);
+        }
+
+        self.write_with_policy()
+    }
+
+    /// Update the mesh, e.g. after remeshing or adaptive refinement.
+    ///
+    /// The new mesh is used from the given time step on, i.e. for all subsequent calls of `write_data`.
+    /// The data of this time step must be written after updating the mesh.
+    /// The updated mesh consists of a single part, i.e. a mesh tree is replaced.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer =
+    ///     TimeSeriesWriter::new("xdmf_write_mesh_update", xdmf::DataStorage::AsciiInline)
+    ///         .expect("failed to create XDMF writer");
+    ///
+    /// // write the initial mesh, a single triangle
+    /// let mut ts_writer = xdmf_writer
+    ///     .write_mesh(
+    ///         &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+    ///         (&[0, 1, 2], &[xdmf::CellType::Triangle]),
+    ///     )
+    ///     .expect("failed to write mesh");
+    ///
+    /// // the mesh was refined, now it consists of two triangles
+    /// ts_writer
+    ///     .write_mesh_update(
+    ///         "1.0",
+    ///         &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0],
+    ///         (&[0, 1, 2, 1, 3, 2], &[xdmf::CellType::Triangle; 2]),
+    ///     )
+    ///     .expect("failed to update mesh");
+    /// ```
+    pub fn write_mesh_update(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+    ) -> XdmfResult<()> {
+        let (label, _) = resolve_step(step, self.time_grids.len())?;
+
+        if self.writen_times.contains(&label) {
+            return Err(XdmfError::Validation(format!(
+                "Mesh cannot be updated for time step '{label}', it has already been written"
+            )));
+        }
+
+        if !self.subsets.is_empty() {
+            return Err(XdmfError::Unsupported(
+                "Mesh cannot be updated after subsets were written, their cell indices refer to the initial mesh".into(),
+            ));
+        }
+
+        if !self.submeshes.is_empty() {
+            return Err(XdmfError::Unsupported(
+                "Mesh cannot be updated after submeshes were written, their cell indices refer to the initial mesh".into(),
+            ));
+        }
+
+        let linear_cells =
+            subdivide_if_requested(self.subdivide_quadratic, points.len() / 3, cells)?;
+
+        let mesh = create_mesh(
+            self.writer.as_mut(),
+            &mut self.data_items,
+            Coordinates::Interleaved(points),
+            linear_cells
+                .as_ref()
+                .map_or(cells, |(connectivity, cell_types, _)| {
+                    (connectivity, cell_types)
+                }),
+            &self.name_suffix,
+            Some(&format!("t{label}")),
+            self.transform.as_ref(),
+        )?;
+
+        let (num_cells, cell_order, cell_layout) = match linear_cells {
+            Some((_, _, cell_map)) => (cells.1.len(), Some(cell_map), None),
+            None => (mesh.num_cells, None, cell_layout(&mesh.grid, cells)),
+        };
+
+        self.cell_points =
+            cell_points_if_averaged(&self.field_options.averaged_fields, mesh.num_points, cells)?;
+        self.grid = mesh.grid;
+        self.parts.clear();
+        self.cell_order = cell_order;
+        self.cell_layout = cell_layout;
+        self.num_points = mesh.num_points;
+        self.num_cells = num_cells;
+        self.num_edges = None;
+        self.num_faces = None;
+        self.static_attributes.clear();
+
+        Ok(())
+    }
+
+    /// Write a time step of a point cloud whose number of points changes over time, e.g. particles entering and leaving the domain.
+    ///
+    /// The points and their polyvertex topology are written for this time step, and the point data is validated
+    /// against the number of points of this time step instead of the initial points.
+    /// It is equivalent to [`Self::write_mesh_update`] with the points only, followed by [`Self::write_data`].
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_particles", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// let mut ts_writer = xdmf_writer
+    ///     .write_points(&[0.0, 0.0, 0.0])
+    ///     .expect("failed to write points");
+    ///
+    /// // a particle is added in each time step
+    /// for num_particles in 1..4 {
+    ///     let points: Vec<f64> = (0..num_particles)
+    ///         .flat_map(|i| [f64::from(i), 0.0, 0.0])
+    ///         .collect();
+    ///     let point_data = vec![(
+    ///         "diameter".to_string(),
+    ///         (
+    ///             xdmf::DataAttribute::Scalar,
+    ///             vec![0.1; points.len() / 3].into(),
+    ///         ),
+    ///     )]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    ///     ts_writer
+    ///         .write_particles(&num_particles, &points, Some(&point_data))
+    ///         .expect("failed to write particles");
+    /// }
+    /// ```
+    pub fn write_particles(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        points: &[f64],
+        point_data: Option<&DataMap>,
+    ) -> XdmfResult<()> {
+        self.write_mesh_update(step, points, (&[], &[]))?;
+        self.write_data(step, point_data, None)
+    }
+
+    /// Register the fields that are written in each time step, guaranteeing a consistent set of attributes.
+    ///
+    /// Paraview does not handle fields that appear or disappear between time steps well.
+    /// After registering, the data of each time step is validated against the registered fields,
+    /// i.e. missing or unregistered fields, as well as fields with a different `DataAttribute`, result in an error.
+    /// Global data is validated against the fields registered with `Center::Grid`.
+    /// Fields must be registered before the first time step is written.
+    /// ```rust
+    /// use xdmf::{DataAttribute, TimeSeriesWriter, xdmf_elements::attribute::Center};
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_register_fields", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// let mut time_series_writer = xdmf_writer
+    ///     .write_mesh(&[0.0, 0.0, 0.0], (&[], &[]))
+    ///     .expect("failed to write mesh");
+    ///
+    /// time_series_writer
+    ///     .register_fields(&[
+    ///         ("temperature", DataAttribute::Scalar, Center::Node),
+    ///         ("velocity", DataAttribute::Vector, Center::Node),
+    ///     ])
+    ///     .expect("failed to register fields");
+    ///
+    /// let point_data = vec![(
+    ///     "temperature".to_string(),
+    ///     (DataAttribute::Scalar, vec![20.0].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// // the velocity is missing
+    /// assert!(
+    ///     time_series_writer
+    ///         .write_data("0.0", Some(&point_data), None)
+    ///         .is_err()
+    /// );
+    /// ```
+    pub fn register_fields(
+        &mut self,
+        fields: &[(&str, DataAttribute, attribute::Center)],
+    ) -> XdmfResult<()> {
+        if !self.time_grids.is_empty() {
+            return Err(XdmfError::Validation(
+                "Fields must be registered before the first time step is written".into(),
+            ));
+        }
+
+        if fields.is_empty() {
+            return Err(XdmfError::Validation(
+                "At least one field must be registered".into(),
+            ));
+        }
+
+        let mut registered_fields: Vec<(String, DataAttribute, attribute::Center)> = Vec::new();
+
+        for (name, data_attribute, center) in fields {
+            let entity_label = center_label(*center).ok_or_else(|| {
+                XdmfError::Unsupported(format!(
+                    "Field '{name}' can not be registered, only fields of point-, cell-, edge-, face- or global-data are supported"
+                ))
+            })?;
+
+            if !is_valid_data_name(name) {
+                return Err(XdmfError::Validation(format!(
+                    "Data name '{name}' of {entity_label}-data is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
+                )));
+            }
+
+            if registered_fields
+                .iter()
+                .any(|field| field.0 == *name && field.2 == *center)
+            {
+                return Err(XdmfError::Validation(format!(
+                    "Field '{name}' of {entity_label}-data is registered multiple times"
+                )));
+            }
+
+            registered_fields.push(((*name).to_string(), data_attribute.clone(), *center));
+        }
+
+        self.registered_fields = registered_fields;
+
+        Ok(())
+    }
+
+    /// Register a field whose values are computed by the reader from other fields, e.g. the magnitude of a vector.
+    ///
+    /// The `function` refers to the source `fields` as `$0`, `$1`, ..., see the
+    /// [XDMF documentation](https://www.xdmf.org/index.php/XDMF_Model_and_Format.html#DataItem) for the supported expressions.
+    /// The source fields must have the same center as the derived field.
+    /// The derived field is added to all time steps written afterwards with `write_data` or `write_data_overwrite`
+    /// that contain all of its source fields, without writing additional heavy data.
+    /// ```rust
+    /// use xdmf::{DataAttribute, TimeSeriesWriter, xdmf_elements::attribute::Center};
+    /// let xdmf_writer = TimeSeriesWriter::new(
+    ///     "xdmf_register_derived_field",
+    ///     xdmf::DataStorage::AsciiInline,
+    /// )
+    /// .expect("failed to create XDMF writer");
+    ///
+    /// let mut time_series_writer = xdmf_writer
+    ///     .write_mesh(&[0.0, 0.0, 0.0], (&[], &[]))
+    ///     .expect("failed to write mesh");
+    ///
+    /// time_series_writer
+    ///     .register_derived_field(
+    ///         "speed",
+    ///         DataAttribute::Scalar,
+    ///         Center::Node,
+    ///         "SQRT($0*$0 + $1*$1)",
+    ///         &["velocity_x", "velocity_y"],
+    ///     )
+    ///     .expect("failed to register derived field");
+    ///
+    /// let point_data = vec![
+    ///     (
+    ///         "velocity_x".to_string(),
+    ///         (DataAttribute::Scalar, vec![3.0].into()),
+    ///     ),
+    ///     (
+    ///         "velocity_y".to_string(),
+    ///         (DataAttribute::Scalar, vec![4.0].into()),
+    ///     ),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// time_series_writer
+    ///     .write_data("0.0", Some(&point_data), None)
+    ///     .expect("failed to write time step data");
+    /// ```
+    pub fn register_derived_field(
+        &mut self,
+        name: &str,
+        data_attribute: DataAttribute,
+        center: attribute::Center,
+        function: &str,
+        fields: &[&str],
+    ) -> XdmfResult<()> {
+        let entity_label = match center {
+            attribute::Center::Node => "point",
+            attribute::Center::Cell => "cell",
+            _ => {
+                return Err(XdmfError::Unsupported(format!(
+                    "Derived field '{name}' can not be registered, only fields of point- or cell-data are supported"
+                )));
+            }
+        };
+
+        if !is_valid_data_name(name) {
+            return Err(XdmfError::Validation(format!(
+                "Data name '{name}' of {entity_label}-data is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
+            )));
+        }
+
+        if self
+            .derived_fields
+            .iter()
+            .any(|field| field.name == name && field.center == center)
+        {
+            return Err(XdmfError::Validation(format!(
+                "Derived field '{name}' of {entity_label}-data is registered multiple times"
+            )));
+        }
+
+        if fields.is_empty() || function.trim().is_empty() {
+            return Err(XdmfError::Validation(format!(
+                "Derived field '{name}' must have a function and at least one source field"
+            )));
+        }
+
+        if let Some(index) = function_arguments(function).find(|index| *index >= fields.len()) {
+            return Err(XdmfError::Validation(format!(
+                "Function of derived field '{name}' refers to ${index}, but only {} source fields are given",
+                fields.len()
+            )));
+        }
+
+        self.derived_fields.push(DerivedField {
+            name: name.to_string(),
+            data_attribute,
+            center,
+            function: function.to_string(),
+            fields: fields.iter().map(ToString::to_string).collect(),
+        });
+
+        Ok(())
+    }
+
+    /// Write a set of points or cells, e.g. the nodes of a boundary condition, such that it can be selected in Paraview.
+    ///
+    /// The indices refer to the points or cells of the mesh. Sets belong to the mesh,
+    /// hence they must be written before the first time step and are not part of an updated mesh.
+    /// Only sets of points (`SetType::Node`) and cells (`SetType::Cell`) can be written.
+    /// ```rust
+    /// use xdmf::{TimeSeriesWriter, xdmf_elements::set::SetType};
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_set", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// let mut time_series_writer = xdmf_writer
+    ///     .write_mesh(
+    ///         &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+    ///         (&[0, 1], &[xdmf::CellType::Edge]),
+    ///     )
+    ///     .expect("failed to write mesh");
+    ///
+    /// time_series_writer
+    ///     .write_set("inlet", SetType::Node, &[0])
+    ///     .expect("failed to write set");
+    /// ```
+    pub fn write_set(&mut self, name: &str, set_type: SetType, indices: &[u64]) -> XdmfResult<()> {
+        if !self.time_grids.is_empty() {
+            return Err(XdmfError::Validation(
+                "Sets must be written before the first time step is written".into(),
+            ));
+        }
+
+        if !self.parts.is_empty() {
+            return Err(XdmfError::Unsupported(
+                "Sets are not supported for meshes consisting of multiple parts".into(),
+            ));
+        }
+
+        if !is_valid_data_name(name) {
+            return Err(XdmfError::Validation(format!(
+                "Set name '{name}' is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
+            )));
+        }
+
+        let sets = self.grid.sets.get_or_insert_default();
+
+        if sets.iter().any(|set| set.name == name) {
+            return Err(XdmfError::Validation(format!(
+                "Set '{name}' has already been written"
+            )));
+        }
+
+        let num_entities = match set_type {
+            SetType::Node => self.num_points,
+            SetType::Cell if self.cell_order.is_some() => {
+                return Err(XdmfError::Unsupported(
+                    "Cell sets are not supported for meshes with subdivided quadratic cells".into(),
+                ));
+            }
+            SetType::Cell => self.num_cells,
+            SetType::Face | SetType::Edge => {
+                return Err(XdmfError::Unsupported(format!(
+                    "Sets of type {set_type:?} are not supported"
+                )));
+            }
+        };
+
+        if let Some(&max_index) = indices.iter().max()
+            && max_index as usize >= num_entities
+        {
+            return Err(XdmfError::Validation(format!(
+                "Indices of set '{name}' out of bounds, max index: {max_index}, but number of entities is {num_entities}"
+            )));
+        }
+
+        let item_name = format!("set_{name}{}", self.name_suffix);
+
+        let precision = self.writer.index_precision().resolve(indices)?;
+        let data = self.writer.write_set(name, indices)?;
+        let data_item = self.writer.wrap_data_item(DataItem {
+            name: Some(item_name.clone()),
+            dimensions: Some(Dimensions(vec![indices.len()])),
+            number_type: Some(NumberType::UInt),
+            data,
+            format: Some(self.writer.format()),
+            precision: Some(precision),
+            reference: None,
+            item_type: None,
+            encoding: self.writer.encoding(),
+            endian: None,
+            function: None,
+            data_items: vec![],
+        });
+        self.data_items.register(data_item)?;
+
+        let set = Set {
+            name: name.to_string(),
+            set_type,
+            data_items: vec![self.data_items.reference(&item_name)?],
+            attributes: vec![],
+        };
+        self.grid.sets.get_or_insert_default().push(set);
+
+        self.write()
+    }
+
+    /// Write data of a time step that is defined only on the entities of a set, e.g. the wall shear stress on a boundary.
+    ///
+    /// Each field has one entry per index of the set, centered on the points or cells of the set according to its `SetType`.
+    /// The data is written as attributes of the set within the grid of the time step, instead of the attributes of the grid.
+    /// The point and/or cell data of the time step must be written before.
+    /// ```rust
+    /// use xdmf::{TimeSeriesWriter, xdmf_elements::set::SetType};
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_set_data", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// let mut time_series_writer = xdmf_writer
+    ///     .write_mesh(
+    ///         &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 2.0, 0.0, 0.0],
+    ///         (&[0, 1, 1, 2], &[xdmf::CellType::Edge; 2]),
+    ///     )
+    ///     .expect("failed to write mesh");
+    ///
+    /// time_series_writer
+    ///     .write_set("wall", SetType::Node, &[0, 2])
+    ///     .expect("failed to write set");
+    ///
+    /// let point_data = vec![(
+    ///     "pressure".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![1.0; 3].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// // one value per node of the wall
+    /// let wall_data = vec![(
+    ///     "wall_shear_stress".to_string(),
+    ///     (xdmf::DataAttribute::Scalar, vec![0.1, 0.2].into()),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// time_series_writer
+    ///     .write_data("0.0", Some(&point_data), None)
+    ///     .expect("failed to write time step data");
+    /// time_series_writer
+    ///     .write_set_data("0.0", "wall", &wall_data)
+    ///     .expect("failed to write set data");
+    /// ```
+    pub fn write_set_data(
+        &mut self,
+        step: &(impl StepKey + ?Sized),
+        set_name: &str,
+        set_data: &DataMap,
+    ) -> XdmfResult<()> {
+        let (label, _) = resolve_step(step, self.time_grids.len())?;
+
+        let grid_name = format!("time_series-t{label}");
+        let grid_index = self
+            .time_grids
+            .iter()
+            .position(|grid| grid.name == grid_name)
+            .ok_or_else(|| {
+                XdmfError::Validation(format!(
+                    "Time step '{label}' has not been written yet, its point or cell data must be written first"
+                ))
+            })?;
+
+        let set = self.time_grids[grid_index]
+            .sets
+            .iter_mut()
+            .flatten()
+            .find(|set| set.name == set_name)
+            .ok_or_else(|| {
+                XdmfError::Validation(format!(
+                    "Set '{set_name}' is not part of the mesh of time step '{label}'"
+                ))
+            })?;
+
+        // the indices of the set are registered as DataItem of the domain, which holds their number
+        let num_entities = self
+            .data_items
+            .get(&format!("set_{set_name}{}", self.name_suffix))
+            .and_then(|data_item| data_item.dimensions.as_ref())
+            .map(|dimensions| dimensions.0.iter().product())
+            .ok_or_else(|| {
+                XdmfError::Validation(format!("Indices of set '{set_name}' are not registered"))
+            })?;
+
+        let center = match set.set_type {
+            SetType::Node => attribute::Center::Node,
+            SetType::Cell => attribute::Center::Cell,
+            SetType::Face => attribute::Center::Face,
+            SetType::Edge => attribute::Center::Edge,
+        };
+
+        check_data_size(Some(set_data), num_entities, set_name)?;
+        validate_data_name(Some(set_data), set_name)?;
+
+        if let Some(data_name) = set_data
+            .keys()
+            .find(|data_name| set.attributes.iter().any(|attr| attr.name == **data_name))
+        {
+            return Err(XdmfError::Validation(format!(
+                "Data '{data_name}' of set '{set_name}' has already been written for time step '{label}'"
+            )));
+        }
+
+        self.writer.write_data_initialize(&label)?;
+        let attributes = set_data
+            .iter()
+            .map(|(data_name, (data_attribute, values))| {
+                create_attribute(
+                    self.writer.as_mut(),
+                    &self.field_information,
+                    &self.field_options,
+                    (data_name, data_attribute, values),
+                    center,
+                    &format!("{data_name}_set_{set_name}"),
+                )
+            })
+            .collect::<XdmfResult<Vec<_>>>();
+        self.writer.write_data_finalize()?;
+
+        set.attributes.extend(attributes?);
+
+        self.write_with_policy()
+    }
+
+    /// Write a subset of the cells of the mesh, e.g. a region of interest, which is shown as separate grid by the readers.
+    ///
+    /// Only the cell indices are written, the subset references the geometry and topology of the mesh instead of duplicating them.
+    /// It is written as grid of type `SubSet` next to the time series, referencing the grid of each time step,
+    /// from which the readers extract the cells including the point and cell data.
+    /// Subsets must be written before the first time step is written.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_subset", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// let mut time_series_writer = xdmf_writer
+    ///     .write_mesh(
+    ///         &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 2.0, 0.0, 0.0],
+    ///         (&[0, 1, 1, 2], &[xdmf::CellType::Edge; 2]),
+    ///     )
+    ///     .expect("failed to write mesh");
+    ///
+    /// // the second edge
+    /// time_series_writer
+    ///     .write_subset("outlet", &[1])
+    ///     .expect("failed to write subset");
+    /// ```
+    pub fn write_subset(&mut self, name: &str, cell_indices: &[u64]) -> XdmfResult<()> {
+        self.check_cell_selection(("Subset", "Subsets"), name, cell_indices)?;
+
+        let item_name = format!("subset_{name}{}", self.name_suffix);
+
+        // the dot can not be part of the names of sets, hence the heavy data of sets and subsets does not collide
+        let precision = self.writer.index_precision().resolve(cell_indices)?;
+        let data = self
+            .writer
+            .write_set(&format!("subset.{name}"), cell_indices)?;
+        let data_item = self.writer.wrap_data_item(DataItem {
+            name: Some(item_name.clone()),
+            dimensions: Some(Dimensions(vec![cell_indices.len()])),
+            number_type: Some(NumberType::UInt),
+            data,
+            format: Some(self.writer.format()),
+            precision: Some(precision),
+            reference: None,
+            item_type: None,
+            encoding: self.writer.encoding(),
+            endian: None,
+            function: None,
+            data_items: vec![],
+        });
+        self.data_items.register(data_item)?;
+
+        self.subsets
+            .push((name.to_string(), self.data_items.reference(&item_name)?));
+
+        self.write()
+    }
+
+    /// Write a submesh of the cells of the mesh, e.g. a region of interest, which is shown as separate grid by the readers.
+    ///
+    /// Unlike a subset (see [`TimeSeriesDataWriter::write_subset`]), the submesh is written as uniform grid, which is supported by more readers, e.g. Paraview.
+    /// It shares the coordinates and the point data of the mesh, while its connectivity and cell data are selected from those of the mesh
+    /// with `DataItems` of `ItemType` `Coordinates`. Only the indices of the selected values are written as heavy data.
+    /// Submeshes must be written before the first time step, they are not supported for image data.
+    /// ```rust
+    /// use xdmf::TimeSeriesWriter;
+    /// let xdmf_writer = TimeSeriesWriter::new("xdmf_write_submesh", xdmf::DataStorage::AsciiInline)
+    ///     .expect("failed to create XDMF writer");
+    ///
+    /// let mut time_series_writer = xdmf_writer
+    ///     .write_mesh(
+    ///         &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 2.0, 0.0, 0.0],
+    ///         (&[0, 1, 1, 2], &[xdmf::CellType::Edge; 2]),
+    ///     )
+    ///     .expect("failed to write mesh");
+    ///
+    /// // the second edge
+    /// time_series_writer
+    ///     .write_submesh("outlet", &[1])
+    ///     .expect("failed to write submesh");
+    /// ```
+    pub fn write_submesh(&mut self, name: &str, cell_indices: &[u64]) -> XdmfResult<()> {
+        self.check_cell_selection(("Submesh", "Submeshes"), name, cell_indices)?;
+
+        let entries = self
+            .cell_layout
+            .as_ref()
+            .ok_or_else(|| {
+                XdmfError::Unsupported(
+                    "Submeshes are only supported for meshes with a connectivity, e.g. not for image data".into(),
+                )
+            })?
+            .entries(cell_indices);
+
+        let connectivity = self.write_submesh_coordinates(name, "connectivity", &entries)?;
+        self.submeshes.push(Submesh {
+            name: name.to_string(),
+            cell_indices: cell_indices.to_vec(),
+            num_entries: entries.len(),
+            connectivity,
+            cell_values: vec![],
+        });
+
+        // static data might have been written before
+        let static_attributes = self.static_attributes.clone();
+        self.select_submesh_cell_values(&static_attributes)?;
+
+        self.write()
+    }
+
+    // check the name and the cell indices of a subset or submesh (given in singular and plural)
+    fn check_cell_selection(
+        &self,
+        (kind, kinds): (&str, &str),
+        name: &str,
+        cell_indices: &[u64],
+    ) -> XdmfResult<()> {
+        if !self.time_grids.is_empty() {
+            return Err(XdmfError::Validation(format!(
+                "{kinds} must be written before the first time step is written"
+            )));
+        }
+
+        if !self.parts.is_empty() {
+            return Err(XdmfError::Unsupported(format!(
+                "{kinds} are not supported for meshes consisting of multiple parts"
+            )));
+        }
+
+        if self.cell_order.is_some() {
+            return Err(XdmfError::Unsupported(format!(
+                "{kinds} are not supported for meshes with subdivided quadratic cells"
+            )));
+        }
+
+        if !is_valid_data_name(name) {
+            return Err(XdmfError::Validation(format!(
+                "{kind} name '{name}' is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
+            )));
+        }
+
+        // both are written as grids of the domain, hence their names must be unique
+        if self.subsets.iter().any(|(subset, _)| subset == name)
+            || self.submeshes.iter().any(|submesh| submesh.name == name)
+        {
+            return Err(XdmfError::Validation(format!(
+                "{kind} '{name}' has already been written, the names of subsets and submeshes must be unique"
+            )));
+        }
+
+        if let Some(&max_index) = cell_indices.iter().max()
+            && max_index as usize >= self.num_cells
+        {
+            return Err(XdmfError::Validation(format!(
+                "Indices of {} '{name}' out of bounds, max index: {max_index}, but number of cells is {}",
+                kind.to_lowercase(),
+                self.num_cells
+            )));
+        }
+
+        Ok(())
+    }
+
+    // write the coordinates of the values selected by a submesh and get a reference to them
+    fn write_submesh_coordinates(
+        &mut self,
+        name: &str,
+        label: &str,
+        coordinates: &[u64],
+    ) -> XdmfResult<DataItem> {
+        let item_name = format!("submesh_{name}_{label}{}", self.name_suffix);
+
+        // the dot can not be part of the names of sets, hence the heavy data of sets and submeshes does not collide
+        let precision = self.writer.index_precision().resolve(coordinates)?;
+        let data = self
+            .writer
+            .write_set(&format!("submesh.{name}.{label}"), coordinates)?;
+        let data_item = self.writer.wrap_data_item(DataItem {
+            name: Some(item_name.clone()),
+            dimensions: Some(Dimensions(vec![coordinates.len()])),
+            number_type: Some(NumberType::UInt),
+            data,
+            format: Some(self.writer.format()),
+            precision: Some(precision),
+            reference: None,
+            item_type: None,
+            encoding: self.writer.encoding(),
+            endian: None,
+            function: None,
+            data_items: vec![],
+        });
+        self.data_items.register(data_item)?;
+
+        self.data_items.reference(&item_name)
+    }
+
+    // write the coordinates of the cell values of the submeshes for the shapes of the cell data of the attributes,
+    // unless they were written for an earlier attribute
+    fn select_submesh_cell_values(
+        &mut self,
+        attributes: &[Arc<attribute::Attribute>],
+    ) -> XdmfResult<()> {
+        for attribute in attributes {
+            if attribute.center != attribute::Center::Cell {
+                continue;
+            }
+            let Some(shape) = attribute
+                .data_items
+                .first()
+                .and_then(|data_item| self.value_shape(data_item))
+            else {
+                continue;
+            };
+
+            for index in 0..self.submeshes.len() {
+                let submesh = &self.submeshes[index];
+                if submesh
+                    .cell_values
+                    .iter()
+                    .any(|(selected, _)| *selected == shape)
+                {
+                    continue;
+                }
+
+                let name = submesh.name.clone();
+                let coordinates = cell_value_coordinates(&submesh.cell_indices, &shape);
+                let label = shape
+                    .iter()
+                    .fold("cells".to_string(), |label, len| format!("{label}_{len}"));
+
+                let selection = self.write_submesh_coordinates(&name, &label, &coordinates)?;
+                self.submeshes[index]
+                    .cell_values
+                    .push((shape.clone(), selection));
+            }
+        }
+
+        Ok(())
+    }
+
+    // shape of the values of each entity of a DataItem, resolving references to the registered DataItems
+    fn value_shape(&self, data_item: &DataItem) -> Option<Vec<usize>> {
+        let data_item = Reference::of_data_item(data_item)
+            .and_then(|reference| self.data_items.get(reference.name()))
+            .unwrap_or(data_item);
+
+        data_item
+            .dimensions
+            .as_ref()
+            .map(|dimensions| dimensions.0.iter().skip(1).copied().collect())
+    }
+
+    // the grid of a submesh, selecting its cells of the mesh, or a temporal collection selecting them of the grids of the time steps
+    fn submesh_grid(&self, submesh: &Submesh) -> Grid {
+        if self.time_grids.is_empty() {
+            return self.select_submesh(submesh, &self.grid, submesh.name.clone());
+        }
+
+        let grids = self
+            .time_grids
+            .iter()
+            .map(|grid| {
+                self.select_submesh(submesh, grid, format!("{}-{}", submesh.name, grid.name))
+            })
+            .collect();
+
+        temporal_collection(&submesh.name, grids, self.time_list)
+    }
+
+    // the cells of a submesh of the given grid, sharing its coordinates and point data, and selecting its connectivity and cell data
+    fn select_submesh(&self, submesh: &Submesh, grid: &Grid, name: String) -> Grid {
+        let num_cells = submesh.cell_indices.len();
+
+        let mut selection = grid.clone();
+        selection.name = name;
+        // the sets refer to the entities of the mesh
+        selection.sets = None;
+
+        if let Some(topology) = &mut selection.topology {
+            topology.number_of_elements = num_cells.format_number();
+            topology.data_item = topology.data_item.take().map(|connectivity| {
+                DataItem::new_coordinates(
+                    connectivity,
+                    submesh.connectivity.clone(),
+                    Dimensions(vec![submesh.num_entries]),
+                )
+            });
+        }
+
+        selection.attributes = grid.attributes.as_ref().map(|attributes| {
+            attributes
+                .iter()
+                .filter_map(|attribute| match attribute.center {
+                    attribute::Center::Node | attribute::Center::Grid => Some(attribute.clone()),
+                    attribute::Center::Cell => {
+                        let data_item = attribute.data_items.first()?;
+                        let shape = self.value_shape(data_item)?;
+                        let (_, coordinates) = submesh
+                            .cell_values
+                            .iter()
+                            .find(|(selected, _)| *selected == shape)?;
+
+                        Some(Arc::new(attribute::Attribute {
+                            data_items: vec![DataItem::new_coordinates(
+                                data_item.clone(),
+                                coordinates.clone(),
+                                Dimensions(std::iter::once(num_cells).chain(shape).collect()),
+                            )],
+                            ..attribute.as_ref().clone()
+                        }))
+                    }
+                    // the edges and faces of the submesh are not known
+                    _ => None,
+                })
+                .collect()
+        });
+
+        selection
+    }
+
+    // the grid of a subset, referencing the mesh, or a temporal collection referencing the grids of the time steps
+    fn subset_grid(&self, name: &str, cell_indices: &DataItem) -> Grid {
+        if self.time_grids.is_empty() {
+            return Grid::new_subset(
+                name,
+                format!("/Xdmf/Domain/Grid[@Name=\"{}\"]", self.grid.name),
+                Some(cell_indices.clone()),
+            );
+        }
+
+        let grids = self
+            .time_grids
+            .iter()
+            .map(|grid| {
+                let mut subset = Grid::new_subset(
+                    format!("{name}-{}", grid.name),
+                    format!(
+                        "/Xdmf/Domain/Grid[@Name=\"time_series\"]/Grid[@Name=\"{}\"]",
+                        grid.name
+                    ),
+                    Some(cell_indices.clone()),
+                );
+                subset.time.clone_from(&grid.time);
+                subset
+            })
+            .collect();
+
+        temporal_collection(name, grids, self.time_list)
+    }
+
+    /// Custom information of the XDMF file, see [`TimeSeriesWriter::with_information`].
+    pub(crate) fn information(&self) -> &[Information] {
+        &self.information
+    }
+
+    /// Header of the XDMF file, see [`TimeSeriesWriter::with_xml_header`].
+    pub(crate) fn xml_header(&self) -> &XmlHeader {
+        &self.xml_header
+    }
+
+    /// Callback that is called after each write, see [`TimeSeriesWriter::with_progress_callback`].
+    pub(crate) fn progress_callback(&self) -> Option<&ProgressCallback> {
+        self.progress_callback.as_ref()
+    }
+
+    /// Add custom information to the XDMF file, which is part of it the next time it is written.
+    pub(crate) fn add_information(&mut self, information: Information) {
+        self.information.push(information);
+    }
+
+    /// Path of the XDMF file, including its extension.
+    pub fn xdmf_file_name(&self) -> &Path {
+        &self.xdmf_file_name
+    }
+
+    /// Path of the file or directory the heavy data is written to, `None` if it is written into the XDMF file.
+    pub fn heavy_data_path(&self) -> Option<&Path> {
+        self.writer.heavy_data_path()
+    }
+
+    /// Values of the `Time` elements of the time steps in the XDMF file, in the order they were written.
+    ///
+    /// Time steps pruned by the [`RetentionPolicy`] are not included.
+    pub fn times(&self) -> &[String] {
+        &self.times
+    }
+
+    /// Name, type and center of the fields whose data has been written, in the order they were first written.
+    ///
+    /// This includes static, global, edge and face data, but not derived fields.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, DataAttribute, attribute::Center)> {
+        self.written_fields
+            .iter()
+            .map(|(name, data_attribute, center)| (name.as_str(), data_attribute.clone(), *center))
+    }
+
+    /// Number of points of the current mesh.
+    pub fn num_points(&self) -> usize {
+        self.num_points
+    }
+
+    /// Number of cells of the current mesh, as it was written, i.e. before subdividing quadratic cells.
+    pub fn num_cells(&self) -> usize {
+        self.num_cells
+    }
+
+    // remember the fields of the data, for [`TimeSeriesDataWriter::fields`]
+    fn record_fields(&mut self, data: Option<&DataMap>, center: attribute::Center) {
+        for (data_name, (data_attribute, _)) in data.into_iter().flatten() {
+            record_field(&mut self.written_fields, data_name, data_attribute, center);
+        }
+    }
+
+    /// File system the files are written to, e.g. for additional files next to the XDMF file.
+    pub(crate) fn file_system(&self) -> &dyn FileSystem {
+        self.file_system.as_ref()
+    }
+
+    /// Write the XDMF file now, independent of the [`FlushPolicy`].
+    ///
+    /// This is required after the last time step if the XDMF file is not rewritten after every time step.
+    pub fn flush_xml(&mut self) -> XdmfResult<()> {
+        self.write()?;
+        self.pending_writes = 0;
+
+        // the XDMF file no longer references the pruned time steps
+        for label in std::mem::take(&mut self.pruned_steps) {
+            self.writer.remove_step(&label)?;
+        }
+
+        Ok(())
+    }
+
+    /// Finish writing, equivalent to [`TimeSeriesDataWriter::close`].
+    pub fn finalize(self) -> XdmfResult<()> {
+        self.close()
+    }
+
+    /// Close the writer, which finishes writing the heavy data and writes the XDMF file a last time.
+    ///
+    /// This is also done when the writer is dropped (e.g. during a panic), but errors can only be logged then.
+    /// Hence calling `close` explicitly is recommended, to handle the errors.
+    pub fn close(mut self) -> XdmfResult<()> {
+        self.close_in_place()
+    }
+
+    // close the writer, it must not be used for writing afterwards
+    pub(crate) fn close_in_place(&mut self) -> XdmfResult<()> {
+        if self.closed {
+            return Ok(());
+        }
+        self.closed = true;
+
+        self.flush_xml()?;
+        self.writer.close()
+    }
+
+    /// Whether the times are written as a list, see [`TimeSeriesWriter::with_time_list`].
+    pub(crate) fn time_list(&self) -> bool {
+        self.time_list
+    }
+
+    // label and value of the `Time` element of a step, see [`TimeSeriesWriter::with_time_scale`]
+    pub(crate) fn resolve_step(
+        &self,
+        step: &(impl StepKey + ?Sized),
+        index: usize,
+    ) -> XdmfResult<(String, String)> {
+        resolve_scaled_step(step, index, self.time_scale)
+    }
+
+    /// Whether time steps were written that are not yet part of the XDMF file.
+    pub(crate) fn is_xml_pending(&self) -> bool {
+        self.pending_writes > 0
+    }
+
+    // add the grid of a new time step, pruning the time steps that are not kept anymore
+    fn add_time_grid(&mut self, label: String, grid: Grid) {
+        let step_index = self.writen_times.len();
+        self.writen_times.insert(label.clone());
+        self.times.push(
+            grid.time
+                .as_ref()
+                .and_then(|time| time.value.clone())
+                .unwrap_or_default(),
+        );
+        self.time_grids.push(grid);
+        self.steps.push((label, step_index));
+
+        let mut index = 0;
+        while index < self.steps.len() {
+            if self
+                .retention_policy
+                .is_kept(self.steps[index].1, step_index)
+            {
+                index += 1;
+            } else {
+                self.time_grids.remove(index);
+                self.times.remove(index);
+                self.pruned_steps.push(self.steps.remove(index).0);
+            }
+        }
+    }
+
+    // a pruned time step is no longer part of the XDMF file, and its heavy data might have been deleted
+    fn check_not_pruned(&self, label: &str, existing_index: Option<usize>) -> XdmfResult<()> {
+        if existing_index.is_none() && self.writen_times.contains(label) {
+            return Err(XdmfError::Validation(format!(
+                "Time step '{label}' has been pruned by the retention policy, it can not be written again"
+            )));
+        }
+
+        Ok(())
+    }
+
+    // write the XDMF file if it is due according to the flush policy
+    fn write_with_policy(&mut self) -> XdmfResult<()> {
+        self.pending_writes += 1;
+
+        if self.flush_policy.is_due(self.pending_writes) {
+            self.flush_xml()?;
+        }
+
+        Ok(())
+    }
+
+    fn write(&mut self) -> XdmfResult<()> {
+        self.writer.flush()?;
+
+        // If there are no attributes aka time-data, write the grid directly
+        let grid_to_write = if self.time_grids.is_empty() {
+            self.grid.clone()
+        } else if let Some(steps_per_file) = self
+            .rollover
+            .as_ref()
+            .map(|rollover| rollover.steps_per_file)
+        {
+            self.write_rollover_files(steps_per_file)?
+        } else {
+            temporal_collection("time_series", self.time_grids.clone(), self.time_list)
+        };
+
+        let mut xdmf = self.create_xdmf(grid_to_write);
+        xdmf.domains[0].grids.extend(
+            self.subsets
+                .iter()
+                .map(|(name, cell_indices)| self.subset_grid(name, cell_indices)),
+        );
+        xdmf.domains[0].grids.extend(
+            self.submeshes
+                .iter()
+                .map(|submesh| self.submesh_grid(submesh)),
+        );
+        self.write_xdmf(&xdmf, &self.xdmf_file_name)?;
+
+        // the time steps are written in order, the last one is the most recent
+        if self.latest_step_file
+            && let Some(latest_grid) = self.time_grids.last()
+        {
+            let xdmf = self.create_xdmf(temporal_collection(
+                "time_series",
+                vec![latest_grid.clone()],
+                self.time_list,
+            ));
+            self.write_xdmf(&xdmf, &latest_step_file_name(&self.xdmf_file_name))?;
+        }
+
+        Ok(())
+    }
+
+    // write the time steps into files of `steps_per_file` time steps each, returning the collection including them
+    fn write_rollover_files(&mut self, steps_per_file: usize) -> XdmfResult<Grid> {
+        let written_checksums = self
+            .rollover
+            .as_ref()
+            .map(|rollover| rollover.checksums.as_slice())
+            .unwrap_or_default();
+
+        // the files are split along the global time order, not the order of writing
+        let mut sorted_grids = self.time_grids.clone();
+        sorted_grids.sort_by(|grid_1, grid_2| grid_time(grid_1).total_cmp(&grid_time(grid_2)));
+
+        let mut checksums = Vec::new();
+        let mut includes = Vec::new();
+
+        for (index, time_grids) in sorted_grids.chunks(steps_per_file).enumerate() {
+            let file_name = rollover_file_name(&self.xdmf_file_name, index);
+
+            // the times stay with the grids, as the grids are included into the XDMF file
+            let xdmf = self.create_xdmf(temporal_collection(
+                "time_series",
+                time_grids.to_vec(),
+                false,
+            ));
+            let mut contents = Vec::new();
+            xdmf.write_to_with_header(&mut contents, &self.xml_header)?;
+
+            let checksum = xxh64(&contents, 0);
+            if written_checksums.get(index) != Some(&checksum) {
+                write_file_atomically(
+                    self.file_system.as_ref(),
+                    self.progress_callback.as_ref(),
+                    &file_name,
+                    |file| Ok(file.write_all(&contents)?),
+                )?;
+            }
+            checksums.push(checksum);
+
+            includes.push(XInclude::new_xpointer(
+                file_name.file_name().unwrap_or_default().to_string_lossy(),
+                "/Xdmf/Domain/Grid/Grid",
+            ));
+        }
+
+        // the time steps of the remaining files were pruned by the retention policy
+        for index in checksums.len()..written_checksums.len() {
+            self.file_system
+                .remove_file(&rollover_file_name(&self.xdmf_file_name, index))?;
+        }
+
+        if let Some(rollover) = &mut self.rollover {
+            rollover.checksums = checksums;
+        }
+
+        let mut collection = Grid::new_collection("time_series", CollectionType::Temporal, None);
+        collection.includes = Some(includes);
+
+        Ok(collection)
+    }
+
+    fn create_xdmf(&self, grid_to_write: Grid) -> Xdmf {
+        let mut xdmf = create_xdmf(
+            self.writer.as_ref(),
+            &self.information,
+            grid_to_write,
+            &self.data_items,
+        );
+
+        if self.tensor6_as_matrix {
+            tensor6_as_matrix(&mut xdmf);
+        }
+
+        prefix_attribute_names(&mut xdmf, &self.field_options.name_prefix);
+
+        if self.reference_style.embeds(self.writer.data_storage()) {
+            embed_references(&mut xdmf);
+        }
+
+        xdmf
+    }
+
+    fn write_xdmf(&self, xdmf: &Xdmf, xdmf_file_name: &Path) -> XdmfResult<()> {
+        write_xdmf_file(
+            self.file_system.as_ref(),
+            xdmf,
+            &self.xml_header,
+            self.progress_callback.as_ref(),
+            xdmf_file_name,
+        )
+    }
+}
+
+/// Writer for the time series of multiple independent meshes in XDMF format. Can be used after adding a mesh with `TimeSeriesWriter::add_mesh`.
+pub struct MultiMeshDataWriter {
+    xdmf_file_name: PathBuf,
+    writer: Box<dyn DataWriter>,
+    name_suffix: String,
+    information: Vec<Information>,
+    field_information: BTreeMap<String, Vec<Information>>,
+    data_items: DataItemRegistry,
+    domains: Vec<NamedDomain>,
+    meshes: Vec<MeshSeries>,
+    flush_policy: FlushPolicy,
+    time_list: bool,
+    time_scale: Option<f64>,
+    tensor6_as_matrix: bool,
+    transform: Option<Transform>,
+    file_system: Arc<dyn FileSystem>,
+    // output frequency of the fields that are not written at every time step
+    output_frequencies: BTreeMap<String, usize>,
+    reference_style: ReferenceStyle,
+    xml_header: XmlHeader,
+    progress_callback: Option<ProgressCallback>,
+    field_options: FieldOptions,
+    // number of writes since the XDMF file was last written
+    pending_writes: u32,
+    closed: bool,
+}
+
+/// A named mesh along with its time series.
+struct MeshSeries {
+    name: String,
+    // name of the domain, `None` for the first (unnamed) domain
+    domain: Option<String>,
+    mesh: MeshGrid,
+    time_grids: Vec<Grid>,
+}
+
+/// A named domain along with the `DataItems` of its meshes.
+struct NamedDomain {
+    name: String,
+    data_items: DataItemRegistry,
+}
+
+impl MultiMeshDataWriter {
+    /// Adds another named mesh.
+    ///
+    /// Meshes can also be added after time steps were written, their time series then start with the next written time step.
+    /// See [`TimeSeriesWriter::add_mesh`] for details.
+    pub fn add_mesh(
+        &mut self,
+        name: &str,
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+    ) -> XdmfResult<()> {
+        self.add_mesh_to(None, name, points, cells)
+    }
+
+    /// Adds another named mesh to a named domain, which is created if it does not exist yet.
+    ///
+    /// See [`TimeSeriesWriter::add_mesh_to_domain`] for details.
+    pub fn add_mesh_to_domain(
+        &mut self,
+        domain: &str,
+        name: &str,
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+    ) -> XdmfResult<()> {
+        if !is_valid_data_name(domain) {
+            return Err(XdmfError::Validation(format!(
+                "Domain name '{domain}' is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
+            )));
+        }
+
+        self.add_mesh_to(Some(domain), name, points, cells)
+    }
+
+    // add a mesh to the given domain, or to the first domain if none is given
+    fn add_mesh_to(
+        &mut self,
+        domain: Option<&str>,
+        name: &str,
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+    ) -> XdmfResult<()> {
+        if !is_valid_data_name(name) {
+            return Err(XdmfError::Validation(format!(
+                "Mesh name '{name}' is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
+            )));
+        }
+
+        if self.meshes.iter().any(|mesh| mesh.name == name) {
+            return Err(XdmfError::Validation(format!(
+                "Mesh '{name}' has already been added"
+            )));
+        }
+
+        // the meshes reference the DataItems of their domain
+        let data_items = match domain {
+            None => &mut self.data_items,
+            Some(domain) => {
+                let index = self
+                    .domains
+                    .iter()
+                    .position(|named| named.name == domain)
+                    .unwrap_or_else(|| {
+                        self.domains.push(NamedDomain {
+                            name: domain.to_string(),
+                            data_items: DataItemRegistry::new(),
+                        });
+                        self.domains.len() - 1
+                    });
+
+                &mut self.domains[index].data_items
+            }
+        };
+
+        let mut mesh = create_mesh(
+            self.writer.as_mut(),
+            data_items,
+            Coordinates::Interleaved(points),
+            cells,
+            &self.name_suffix,
+            Some(name),
+            self.transform.as_ref(),
+        )?;
+        mesh.grid.name = name.to_string();
+
+        self.meshes.push(MeshSeries {
+            name: name.to_string(),
+            domain: domain.map(str::to_string),
+            mesh,
+            time_grids: vec![],
+        });
+
+        self.write()
+    }
+
+    /// Write point and cell data of a mesh for a specific time step.
+    ///
+    /// See [`TimeSeriesDataWriter::write_data`] for details.
+    pub fn write_data(
+        &mut self,
+        mesh_name: &str,
+        step: &(impl StepKey + ?Sized),
+        point_data: Option<&DataMap>,
+        cell_data: Option<&DataMap>,
+    ) -> XdmfResult<()> {
+        self.write_step(mesh_name, step, point_data, cell_data, false)
+    }
+
+    /// Write point and cell data of a mesh for a time step, replacing the data if the time step has already been written.
+    ///
+    /// See [`TimeSeriesDataWriter::write_data_overwrite`] for details.
+    pub fn write_data_overwrite(
+        &mut self,
+        mesh_name: &str,
+        step: &(impl StepKey + ?Sized),
+        point_data: Option<&DataMap>,
+        cell_data: Option<&DataMap>,
+    ) -> XdmfResult<()> {
+        self.write_step(mesh_name, step, point_data, cell_data, true)
+    }
+
+    // write the data of a time step of a mesh, an existing time step is only replaced if overwriting is requested
+    fn write_step(
+        &mut self,
+        mesh_name: &str,
+        step: &(impl StepKey + ?Sized),
+        point_data: Option<&DataMap>,
+        cell_data: Option<&DataMap>,
+        overwrite: bool,
+    ) -> XdmfResult<()> {
+        let mesh = self
+            .meshes
+            .iter_mut()
+            .find(|mesh| mesh.name == mesh_name)
+            .ok_or_else(|| {
+                XdmfError::Validation(format!("Mesh '{mesh_name}' has not been added"))
+            })?;
+
+        let grid_name = format!("{mesh_name}-t{}", step.label());
+        let existing_index = mesh
+            .time_grids
+            .iter()
+            .position(|grid| grid.name == grid_name);
+
+        let (label, time) = resolve_scaled_step(
+            step,
+            existing_index.unwrap_or(mesh.time_grids.len()),
+            self.time_scale,
+        )?;
+
+        if existing_index.is_some() && !overwrite {
+            return Err(XdmfError::Validation(format!(
+                "Time step '{label}' has already been written"
+            )));
+        }
+
+        validate_data(
+            (mesh.mesh.num_points, mesh.mesh.num_cells),
+            point_data,
+            cell_data,
+        )?;
+
+        let step_index = existing_index.unwrap_or(mesh.time_grids.len());
+        let due_point_data = due_fields(&self.output_frequencies, point_data, step_index);
+        let due_cell_data = due_fields(&self.output_frequencies, cell_data, step_index);
+        let (point_data, cell_data) = (
+            due_point_data.as_ref().or(point_data),
+            due_cell_data.as_ref().or(cell_data),
+        );
+
+        self.writer.write_data_initialize(&label)?;
+        let attributes = create_attributes(
+            self.writer.as_mut(),
+            &self.field_information,
+            &self.field_options,
+            (point_data, cell_data),
+            Some(mesh_name),
+            None,
+            false,
+        );
+        self.writer.write_data_finalize()?;
+
+        let mut attributes = attributes?;
+        resolve_name_collisions(
+            &self.field_options.name_collision_policy,
+            &mut attributes,
+            &label,
+        )?;
+
+        let mut grid = mesh.mesh.grid.clone();
+        grid.name = grid_name;
+        grid.time = Some(Time::new(time));
+        grid.information = step_information(step);
+        grid.attributes = Some(attributes);
+
+        if let Some(index) = existing_index {
+            mesh.time_grids[index] = grid;
+        } else {
+            mesh.time_grids.push(grid);
+        }
+
+        self.write_with_policy()
+    }
+
+    /// Write the XDMF file now, independent of the [`FlushPolicy`].
+    ///
+    /// See [`TimeSeriesDataWriter::flush_xml`] for details.
+    pub fn flush_xml(&mut self) -> XdmfResult<()> {
+        self.write()?;
+        self.pending_writes = 0;
+        Ok(())
+    }
+
+    /// Finish writing, equivalent to [`MultiMeshDataWriter::close`].
+    pub fn finalize(self) -> XdmfResult<()> {
+        self.close()
+    }
+
+    /// Close the writer, which finishes writing the heavy data and writes the XDMF file a last time.
+    ///
+    /// See [`TimeSeriesDataWriter::close`] for details.
+    pub fn close(mut self) -> XdmfResult<()> {
+        self.close_in_place()
+    }
+
+    // close the writer, it must not be used for writing afterwards
+    fn close_in_place(&mut self) -> XdmfResult<()> {
+        if self.closed {
+            return Ok(());
+        }
+        self.closed = true;
+
+        self.flush_xml()?;
+        self.writer.close()
+    }
+
+    // write the XDMF file if it is due according to the flush policy
+    fn write_with_policy(&mut self) -> XdmfResult<()> {
+        self.pending_writes += 1;
+
+        if self.flush_policy.is_due(self.pending_writes) {
+            self.flush_xml()?;
+        }
+
+        Ok(())
+    }
+
+    fn write(&mut self) -> XdmfResult<()> {
+        self.writer.flush()?;
+
+        // meshes without time steps are written directly
+        let meshes_of = |domain: Option<&str>| {
+            let grids = self
+                .meshes
+                .iter()
+                .filter(|mesh| mesh.domain.as_deref() == domain)
+                .map(|mesh| {
+                    if mesh.time_grids.is_empty() {
+                        mesh.mesh.grid.clone()
+                    } else {
+                        temporal_collection(&mesh.name, mesh.time_grids.clone(), self.time_list)
+                    }
+                })
+                .collect();
+
+            Grid::new_collection("meshes", CollectionType::Spatial, Some(grids))
+        };
+
+        let mut xdmf = create_xdmf(
+            self.writer.as_ref(),
+            &self.information,
+            meshes_of(None),
+            &self.data_items,
+        );
+
+        // a domain is only created along with its first mesh, which might have failed to be added
+        for domain in self.domains.iter().filter(|domain| {
+            self.meshes
+                .iter()
+                .any(|mesh| mesh.domain.as_deref() == Some(domain.name.as_str()))
+        }) {
+            xdmf.domains.push(Domain {
+                name: Some(domain.name.clone()),
+                grids: vec![meshes_of(Some(&domain.name))],
+                data_items: domain.data_items.data_items().cloned().collect(),
+                includes: Vec::new(),
+            });
+        }
+
+        if self.tensor6_as_matrix {
+            tensor6_as_matrix(&mut xdmf);
+        }
+
+        prefix_attribute_names(&mut xdmf, &self.field_options.name_prefix);
+
+        if self.reference_style.embeds(self.writer.data_storage()) {
+            embed_references(&mut xdmf);
+        }
+
+        write_xdmf_file(
+            self.file_system.as_ref(),
+            &xdmf,
+            &self.xml_header,
+            self.progress_callback.as_ref(),
+            &self.xdmf_file_name,
+        )
+    }
+}
+
+// the heavy data and the XDMF file are completed when the writer goes out of scope without being closed
+impl Drop for TimeSeriesDataWriter {
+    fn drop(&mut self) {
+        if let Err(err) = self.close_in_place() {
+            log::error!(
+                "Closing the XDMF writer of '{}' failed: {err}",
+                self.xdmf_file_name.display()
+            );
+        }
+    }
+}
+
+impl Drop for MultiMeshDataWriter {
+    fn drop(&mut self) {
+        if let Err(err) = self.close_in_place() {
+            log::error!(
+                "Closing the XDMF writer of '{}' failed: {err}",
+                self.xdmf_file_name.display()
+            );
+        }
+    }
+}
+
+/// Writer for scenes of moving bodies in XDMF format. Can be used after starting it with `TimeSeriesWriter::into_multi_body_writer`.
+pub struct MultiBodyDataWriter {
+    xdmf_file_name: PathBuf,
+    writer: Box<dyn DataWriter>,
+    name_suffix: String,
+    information: Vec<Information>,
+    field_information: BTreeMap<String, Vec<Information>>,
+    data_items: DataItemRegistry,
+    // spatial collections of the bodies of the time steps, in the order of writing
+    steps: Vec<Grid>,
+    flush_policy: FlushPolicy,
+    time_list: bool,
+    time_scale: Option<f64>,
+    tensor6_as_matrix: bool,
+    transform: Option<Transform>,
+    file_system: Arc<dyn FileSystem>,
+    // output frequency of the fields that are not written at every time step
+    output_frequencies: BTreeMap<String, usize>,
+    reference_style: ReferenceStyle,
+    xml_header: XmlHeader,
+    progress_callback: Option<ProgressCallback>,
+    field_options: FieldOptions,
+    // number of writes since the XDMF file was last written
+    pending_writes: u32,
+    closed: bool,
+}
+
+impl MultiBodyDataWriter {
+    /// Write the mesh and the point and cell data of a body for a time step.
+    ///
+    /// The mesh of the body is written for every time step, hence it can move, deform or change its cells.
+    /// The heavy data is written per body and time step, e.g. into files named by both.
+    /// A body is part of the time steps it is written for, such that bodies can be added or removed over time.
+    /// The point and cell data are optional, e.g. for obstacles that only move.
+    /// Each body can only be written once per time step, the names of the bodies follow the rules of the names of meshes.
+    pub fn write_body(
+        &mut self,
+        name: &str,
+        step: &(impl StepKey + ?Sized),
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+        point_data: Option<&DataMap>,
+        cell_data: Option<&DataMap>,
+    ) -> XdmfResult<()> {
+        if !is_valid_data_name(name) {
+            return Err(XdmfError::Validation(format!(
+                "Body name '{name}' is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
+            )));
+        }
+
+        let step_name = format!("bodies-t{}", step.label());
+        let existing_index = self.steps.iter().position(|grid| grid.name == step_name);
+        let step_index = existing_index.unwrap_or(self.steps.len());
+
+        let (label, time) = resolve_scaled_step(step, step_index, self.time_scale)?;
+        let grid_name = format!("{name}-t{label}");
+
+        if let Some(index) = existing_index
+            && self.steps[index]
+                .grids
+                .iter()
+                .flatten()
+                .any(|grid| grid.name == grid_name)
+        {
+            return Err(XdmfError::Validation(format!(
+                "Body '{name}' has already been written for time step '{label}'"
+            )));
+        }
+
+        self.writer.write_data_initialize(&label)?;
+        let body_grid = self.create_body_grid(
+            name,
+            &label,
+            points,
+            cells,
+            (point_data, cell_data),
+            step_index,
+        );
+        self.writer.write_data_finalize()?;
+
+        let mut body_grid = body_grid?;
+        body_grid.name = grid_name;
+
+        let index = existing_index.unwrap_or_else(|| {
+            let mut step_grid = Grid::new_collection(step_name, CollectionType::Spatial, None);
+            step_grid.time = Some(Time::new(time));
+            step_grid.information = step_information(step);
+            self.steps.push(step_grid);
+            self.steps.len() - 1
+        });
+        self.steps[index]
+            .grids
+            .get_or_insert_default()
+            .push(body_grid);
+
+        self.write_with_policy()
+    }
+
+    // write the mesh and the data of a body, named by the body and the label of the time step
+    fn create_body_grid(
+        &mut self,
+        name: &str,
+        label: &str,
+        points: &[f64],
+        cells: (&[u64], &[CellType]),
+        (point_data, cell_data): (Option<&DataMap>, Option<&DataMap>),
+        step_index: usize,
+    ) -> XdmfResult<Grid> {
+        let mesh = create_mesh(
+            self.writer.as_mut(),
+            &mut self.data_items,
+            Coordinates::Interleaved(points),
+            cells,
+            &self.name_suffix,
+            Some(&format!("{name}_t{label}")),
+            self.transform.as_ref(),
+        )?;
+
+        // a body can be written without data, e.g. an obstacle that only moves
+        if point_data.is_some() || cell_data.is_some() {
+            validate_data((mesh.num_points, mesh.num_cells), point_data, cell_data)?;
+        }
+
+        let due_point_data = due_fields(&self.output_frequencies, point_data, step_index);
+        let due_cell_data = due_fields(&self.output_frequencies, cell_data, step_index);
+
+        let mut attributes = create_attributes(
+            self.writer.as_mut(),
+            &self.field_information,
+            &self.field_options,
+            (
+                due_point_data.as_ref().or(point_data),
+                due_cell_data.as_ref().or(cell_data),
+            ),
+            Some(name),
+            None,
+            false,
+        )?;
+        resolve_name_collisions(
+            &self.field_options.name_collision_policy,
+            &mut attributes,
+            label,
+        )?;
+
+        let mut grid = mesh.grid;
+        grid.attributes = Some(attributes);
+
+        Ok(grid)
+    }
+
+    /// Write the XDMF file now, independent of the [`FlushPolicy`].
+    ///
+    /// See [`TimeSeriesDataWriter::flush_xml`] for details.
+    pub fn flush_xml(&mut self) -> XdmfResult<()> {
+        self.write()?;
+        self.pending_writes = 0;
+        Ok(())
+    }
+
+    /// Finish writing, equivalent to [`MultiBodyDataWriter::close`].
+    pub fn finalize(self) -> XdmfResult<()> {
+        self.close()
+    }
+
+    /// Close the writer, which finishes writing the heavy data and writes the XDMF file a last time.
+    ///
+    /// See [`TimeSeriesDataWriter::close`] for details.
+    pub fn close(mut self) -> XdmfResult<()> {
+        self.close_in_place()
+    }
+
+    // close the writer, it must not be used for writing afterwards
+    fn close_in_place(&mut self) -> XdmfResult<()> {
+        if self.closed {
+            return Ok(());
+        }
+        self.closed = true;
+
+        self.flush_xml()?;
+        self.writer.close()
+    }
+
+    // write the XDMF file if it is due according to the flush policy
+    fn write_with_policy(&mut self) -> XdmfResult<()> {
+        self.pending_writes += 1;
+
+        if self.flush_policy.is_due(self.pending_writes) {
+            self.flush_xml()?;
+        }
+
+        Ok(())
+    }
+
+    fn write(&mut self) -> XdmfResult<()> {
+        self.writer.flush()?;
+
+        let mut xdmf = create_xdmf(
+            self.writer.as_ref(),
+            &self.information,
+            temporal_collection("bodies", self.steps.clone(), self.time_list),
+            &self.data_items,
+        );
+
+        if self.tensor6_as_matrix {
+            tensor6_as_matrix(&mut xdmf);
+        }
+
+        prefix_attribute_names(&mut xdmf, &self.field_options.name_prefix);
+
+        if self.reference_style.embeds(self.writer.data_storage()) {
+            embed_references(&mut xdmf);
+        }
+
+        write_xdmf_file(
+            self.file_system.as_ref(),
+            &xdmf,
+            &self.xml_header,
+            self.progress_callback.as_ref(),
+            &self.xdmf_file_name,
+        )
+    }
+}
+
+impl Drop for MultiBodyDataWriter {
+    fn drop(&mut self) {
+        if let Err(err) = self.close_in_place() {
+            log::error!(
+                "Closing the XDMF writer of '{}' failed: {err}",
+                self.xdmf_file_name.display()
+            );
+        }
+    }
+}
+
+// temporal collection of the grids of the time steps, optionally moving their times into one list of the collection
+// the time steps can be written in any order, but are emitted in increasing order of their time
+pub(crate) fn temporal_collection(
+    name: impl ToString,
+    mut grids: Vec<Grid>,
+    time_list: bool,
+) -> Grid {
+    grids.sort_by(|grid_1, grid_2| grid_time(grid_1).total_cmp(&grid_time(grid_2)));
+
+    if !time_list {
+        return Grid::new_collection(name, CollectionType::Temporal, Some(grids));
+    }
+
+    let times: Vec<String> = grids
+        .iter_mut()
+        .filter_map(|grid| grid.time.take()?.value)
+        .collect();
+
+    let mut collection = Grid::new_collection(name, CollectionType::Temporal, Some(grids));
+    collection.time = Some(Time::new_list(times));
+    collection
+}
+
+// the fields of the data that are due at the time step with the given index, borrowing their values,
+// `None` if all fields are written at every time step
+fn due_fields<'a>(
+    output_frequencies: &BTreeMap<String, usize>,
+    data: Option<&'a DataMap>,
+    step_index: usize,
+) -> Option<DataMap<'a>> {
+    if output_frequencies.is_empty() {
+        return None;
+    }
+
+    data.map(|data| {
+        data.iter()
+            .filter(|(name, _)| {
+                output_frequencies
+                    .get(*name)
+                    .is_none_or(|every_n_steps| step_index.is_multiple_of((*every_n_steps).max(1)))
+            })
+            .map(|(name, (data_attribute, values))| {
+                (
+                    name.clone(),
+                    (data_attribute.clone(), values.slice(0..values.len())),
+                )
+            })
+            .collect()
+    })
+}
+
+// the cycle of a step is stored as information of its grid
+fn step_information(step: &(impl StepKey + ?Sized)) -> Vec<Information> {
+    step.cycle()
+        .map(|cycle| Information::new(CYCLE_INFORMATION_NAME, cycle))
+        .into_iter()
+        .collect()
+}
+
+// the time of the grid of a time step, which was validated when it was written
+fn grid_time(grid: &Grid) -> f64 {
+    grid.time
+        .as_ref()
+        .and_then(|time| time.value.as_deref())
+        .and_then(|value| parse_time(value).ok())
+        .unwrap_or_default()
+}
+
+// the XDMF with the given grid and the data items of the meshes, along with the information of the file
+pub(crate) fn create_xdmf(
+    writer: &dyn DataWriter,
+    information: &[Information],
+    grid: Grid,
+    data_items: &DataItemRegistry,
+) -> Xdmf {
+    let mut xdmf = Xdmf {
+        information: vec![
+            Information::new("data_storage", format!("{:?}", writer.data_storage())),
+            Information::new("version", env!("CARGO_PKG_VERSION")),
+        ],
+        ..Default::default()
+    };
+    xdmf.information.extend(information.iter().cloned());
+    xdmf.domains[0].grids.push(grid);
+    xdmf.domains[0]
+        .data_items
+        .extend(data_items.data_items().cloned());
+
+    xdmf
+}
+
+// replace the references of the grids to the DataItems of the domain by copies of them, which are then removed from the domain
+pub(crate) fn embed_references(xdmf: &mut Xdmf) {
+    for domain in &mut xdmf.domains {
+        let data_items = std::mem::take(&mut domain.data_items);
+
+        for grid in &mut domain.grids {
+            grid_embed_references(grid, &data_items);
+        }
+    }
+}
+
+fn grid_embed_references(grid: &mut Grid, data_items: &[DataItem]) {
+    let grid_data_items = grid
+        .geometry
+        .iter_mut()
+        .flat_map(|geometry| geometry.data_items.iter_mut())
+        .chain(&mut grid.data_item)
+        .chain(
+            grid.topology
+                .iter_mut()
+                .flat_map(|topology| &mut topology.data_item),
+        )
+        .chain(
+            grid.sets
+                .iter_mut()
+                .flatten()
+                .flat_map(|set| &mut set.data_items),
+        )
+        .chain(
+            grid.attributes
+                .iter_mut()
+                .flatten()
+                .flat_map(|attribute| &mut Arc::make_mut(attribute).data_items),
+        );
+
+    for data_item in grid_data_items {
+        embed_reference(data_item, data_items);
+    }
+
+    for child in grid.grids.iter_mut().flatten() {
+        grid_embed_references(child, data_items);
+    }
+}
+
+// the referenced DataItem is copied without its name, which must be unique within the domain
+fn embed_reference(data_item: &mut DataItem, data_items: &[DataItem]) {
+    if let Some(reference) = Reference::of_data_item(data_item)
+        && let Some(source) = data_items
+            .iter()
+            .find(|source| source.name.as_deref() == Some(reference.name()))
+    {
+        *data_item = DataItem {
+            name: None,
+            ..source.clone()
+        };
+    }
+
+    for child in &mut data_item.data_items {
+        embed_reference(child, data_items);
+    }
+}
+
+// write the attributes of symmetric tensors as `Matrix`, see `TimeSeriesWriter::with_tensor6_as_matrix`
+fn tensor6_as_matrix(xdmf: &mut Xdmf) {
+    xdmf.domains
+        .iter_mut()
+        .flat_map(|domain| domain.grids.iter_mut())
+        .for_each(grid_tensor6_as_matrix);
+}
+
+fn grid_tensor6_as_matrix(grid: &mut Grid) {
+    let set_attributes = grid
+        .sets
+        .iter_mut()
+        .flatten()
+        .flat_map(|set| &mut set.attributes);
+
+    for attribute in grid.attributes.iter_mut().flatten().chain(set_attributes) {
+        if attribute.attribute_type == attribute::AttributeType::Tensor6 {
+            Arc::make_mut(attribute).attribute_type = attribute::AttributeType::Matrix;
+        }
+    }
+
+    grid.grids
+        .iter_mut()
+        .flatten()
+        .for_each(grid_tensor6_as_matrix);
+}
+
+// the names of the attributes are only prefixed in the XDMF file, the fields are identified by their names while writing
+fn prefix_attribute_names(xdmf: &mut Xdmf, prefix: &str) {
+    if prefix.is_empty() {
+        return;
+    }
+
+    xdmf.domains
+        .iter_mut()
+        .flat_map(|domain| domain.grids.iter_mut())
+        .for_each(|grid| grid_prefix_attribute_names(grid, prefix));
+}
+
+fn grid_prefix_attribute_names(grid: &mut Grid, prefix: &str) {
+    let set_attributes = grid
+        .sets
+        .iter_mut()
+        .flatten()
+        .flat_map(|set| &mut set.attributes);
+
+    for attribute in grid.attributes.iter_mut().flatten().chain(set_attributes) {
+        Arc::make_mut(attribute).name.insert_str(0, prefix);
+    }
+
+    grid.grids
+        .iter_mut()
+        .flatten()
+        .for_each(|child| grid_prefix_attribute_names(child, prefix));
+}
+
+// the file with only the most recent time step, e.g. `case_latest.xdmf2` for `case.xdmf2`
+fn latest_step_file_name(xdmf_file_name: &Path) -> PathBuf {
+    suffixed_file_name(xdmf_file_name, "_latest")
+}
+
+// name of the file with the time steps of the given index, next to the XDMF file, e.g. `case_0001.xdmf2`
+fn rollover_file_name(xdmf_file_name: &Path, index: usize) -> PathBuf {
+    suffixed_file_name(xdmf_file_name, &format!("_{index:04}"))
+}
+
+fn suffixed_file_name(xdmf_file_name: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = xdmf_file_name
+        .file_stem()
+        .unwrap_or_default()
+        .to_os_string();
+    file_name.push(suffix);
+    if let Some(extension) = xdmf_file_name.extension() {
+        file_name.push(".");
+        file_name.push(extension);
+    }
+
+    xdmf_file_name.with_file_name(file_name)
+}
+
+/// Write the XDMF file to a temporary file first and then rename it, to avoid access races with readers.
+pub(crate) fn write_xdmf_file(
+    file_system: &dyn FileSystem,
+    xdmf: &Xdmf,
+    xml_header: &XmlHeader,
+    progress_callback: Option<&ProgressCallback>,
+    xdmf_file_name: &Path,
+) -> XdmfResult<()> {
+    write_file_atomically(
+        file_system,
+        progress_callback,
+        xdmf_file_name,
+        |mut xdmf_file| xdmf.write_to_with_header(&mut xdmf_file, xml_header),
+    )
+}
+
+// write the contents of an XDMF file into a temporary file and rename it afterwards
+fn write_file_atomically(
+    file_system: &dyn FileSystem,
+    progress_callback: Option<&ProgressCallback>,
+    xdmf_file_name: &Path,
+    write_contents: impl FnOnce(&mut dyn Write) -> XdmfResult<()>,
+) -> XdmfResult<()> {
+    let temp_xdmf_file_name = xdmf_file_name.with_extension("xdmf.tmp");
+    // the clock is only read for the progress callback, as it is not available on wasm32-unknown-unknown
+    let start = progress_callback.is_some().then(Instant::now);
+
+    let mut xdmf_file = ByteCounter::new(BufWriter::new(
+        file_system.create_file(&temp_xdmf_file_name)?,
+    ));
+    write_contents(&mut xdmf_file)?;
+    xdmf_file.flush()?;
+
+    file_system.rename(&temp_xdmf_file_name, xdmf_file_name)?;
+
+    if let (Some(progress_callback), Some(start)) = (progress_callback, start) {
+        progress_callback(&WriteEvent {
+            operation: WriteOperation::Xml,
+            name: xdmf_file_name.display().to_string(),
+            num_bytes: xdmf_file.num_bytes(),
+            duration: start.elapsed(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Write the heavy data and create the attributes referencing it.
+///
+/// If a name is given, it is appended to the names of the heavy data, to keep them unique.
+/// If ranges of points and cells are given, only the data within them is used.
+/// The fields are written concurrently if requested and supported by the writer.
+fn create_attributes(
+    writer: &mut dyn DataWriter,
+    field_information: &BTreeMap<String, Vec<Information>>,
+    field_options: &FieldOptions,
+    (point_data, cell_data): (Option<&DataMap>, Option<&DataMap>),
+    part_name: Option<&str>,
+    ranges: Option<(Range<usize>, Range<usize>)>,
+    parallel: bool,
+) -> XdmfResult<Vec<Arc<attribute::Attribute>>> {
+    let (point_range, cell_range) =
+        ranges.map_or((None, None), |(points, cells)| (Some(points), Some(cells)));
+
+    let mut fields = Vec::new();
+
+    for (data_map, center, range) in [
+        (point_data, attribute::Center::Node, point_range),
+        (cell_data, attribute::Center::Cell, cell_range),
+    ] {
+        for (data_name, (data_attribute, data)) in data_map.into_iter().flatten() {
+            let vals = range.as_ref().map_or_else(
+                || data.slice(0..data.len()),
+                |range| {
+                    let size = data_attribute.size();
+                    data.slice(range.start * size..range.end * size)
+                },
+            );
+            validate_component_names(field_information, data_name, data_attribute)?;
+            let vals = values_with_nan_policy(field_options.nan_policy, data_name, vals)?;
+            let vals = values_in_precision(&field_options.float_precision, data_name, vals);
+
+            let heavy_data_name = part_name.map_or_else(
+                || data_name.clone(),
+                |part_name| format!("{data_name}_{part_name}"),
+            );
+
+            let dimensions = vals.dimensions(data_attribute);
+            fields.push((
+                data_name,
+                data_attribute,
+                center,
+                heavy_data_name,
+                vals,
+                dimensions,
+            ));
+        }
+    }
+
+    let field_data: Vec<FieldData> = fields
+        .iter()
+        .map(|(_, _, center, heavy_data_name, vals, dimensions)| {
+            (heavy_data_name.as_str(), *center, vals, dimensions)
+        })
+        .collect();
+
+    let heavy_data = if parallel {
+        writer.write_fields(&field_data)?
+    } else {
+        field_data
+            .iter()
+            .map(|&(name, center, vals, dimensions)| {
+                writer.write_shaped_data(name, center, vals, dimensions)
+            })
+            .collect::<XdmfResult<Vec<_>>>()?
+    };
+
+    Ok(fields
+        .into_iter()
+        .zip(heavy_data)
+        .map(
+            |((data_name, data_attribute, center, _, vals, dimensions), heavy_data)| {
+                field_attribute(
+                    writer,
+                    field_information,
+                    field_options,
+                    (data_name, data_attribute, &vals),
+                    center,
+                    dimensions,
+                    heavy_data,
+                )
+            },
+        )
+        .collect())
+}
+
+// write the values of a field and create its attribute
+fn create_attribute(
+    writer: &mut dyn DataWriter,
+    field_information: &BTreeMap<String, Vec<Information>>,
+    field_options: &FieldOptions,
+    (data_name, data_attribute, vals): (&str, &DataAttribute, &Values),
+    center: attribute::Center,
+    heavy_data_name: &str,
+) -> XdmfResult<Arc<attribute::Attribute>> {
+    let vals = values_with_nan_policy(
+        field_options.nan_policy,
+        data_name,
+        vals.slice(0..vals.len()),
+    )?;
+    let vals = values_in_precision(&field_options.float_precision, data_name, vals);
+
+    let dimensions = vals.dimensions(data_attribute);
+    let heavy_data = writer.write_shaped_data(heavy_data_name, center, &vals, &dimensions)?;
+
+    Ok(field_attribute(
+        writer,
+        field_information,
+        field_options,
+        (data_name, data_attribute, &vals),
+        center,
+        dimensions,
+        heavy_data,
+    ))
+}
+
+// the values of a field in the precision that is requested for it
+fn values_in_precision<'a>(
+    float_precision: &FloatPrecisions,
+    data_name: &str,
+    vals: Values<'a>,
+) -> Values<'a> {
+    if float_precision.of_field(data_name) == FloatPrecision::Single
+        && let Some(single_values) = vals.to_single_precision()
+    {
+        single_values
+    } else {
+        vals
+    }
+}
+
+// the values of a field with the NaN or infinite values handled according to the policy
+fn values_with_nan_policy<'a>(
+    nan_policy: NanPolicy,
+    data_name: &str,
+    vals: Values<'a>,
+) -> XdmfResult<Values<'a>> {
+    if nan_policy == NanPolicy::Allow {
+        return Ok(vals);
+    }
+
+    let num_non_finite = vals.num_non_finite();
+    check_non_finite(nan_policy, data_name, num_non_finite)?;
+
+    match nan_policy {
+        NanPolicy::ReplaceWith(replacement) if num_non_finite > 0 => {
+            Ok(vals.with_non_finite_replaced(replacement).unwrap_or(vals))
+        }
+        _ => Ok(vals),
+    }
+}
+
+// report the number of NaN or infinite values of a field, which is an error for `NanPolicy::Error`
+fn check_non_finite(
+    nan_policy: NanPolicy,
+    data_name: &str,
+    num_non_finite: usize,
+) -> XdmfResult<()> {
+    if num_non_finite == 0 {
+        return Ok(());
+    }
+
+    match nan_policy {
+        NanPolicy::Allow => Ok(()),
+        NanPolicy::Error => Err(XdmfError::Validation(format!(
+            "Data '{data_name}' contains {num_non_finite} NaN or infinite values"
+        ))),
+        NanPolicy::ReplaceWith(replacement) => {
+            log::warn!(
+                "Replaced {num_non_finite} NaN or infinite values of data '{data_name}' with {replacement}"
+            );
+            Ok(())
+        }
+    }
+}
+
+// remember a written field, once per name and center
+fn record_field(
+    written_fields: &mut Vec<(String, DataAttribute, attribute::Center)>,
+    data_name: &str,
+    data_attribute: &DataAttribute,
+    center: attribute::Center,
+) {
+    if !written_fields
+        .iter()
+        .any(|(name, _, field_center)| name == data_name && *field_center == center)
+    {
+        written_fields.push((data_name.to_string(), data_attribute.clone(), center));
+    }
+}
+
+// handle the fields of a time step that have the same name as point- and cell-data, see [`NameCollisionPolicy`]
+fn resolve_name_collisions(
+    policy: &NameCollisionPolicy,
+    attributes: &mut [Arc<attribute::Attribute>],
+    label: &str,
+) -> XdmfResult<()> {
+    if *policy == NameCollisionPolicy::Allow {
+        return Ok(());
+    }
+
+    let point_data_names: HashSet<String> = attributes
+        .iter()
+        .filter(|attr| attr.center == attribute::Center::Node)
+        .map(|attr| attr.name.clone())
+        .collect();
+
+    for attr in attributes.iter_mut().filter(|attr| {
+        attr.center == attribute::Center::Cell && point_data_names.contains(&attr.name)
+    }) {
+        match policy {
+            NameCollisionPolicy::Error => {
+                return Err(XdmfError::Validation(format!(
+                    "Data '{}' is written as point- and cell-data of time step '{label}'",
+                    attr.name
+                )));
+            }
+            NameCollisionPolicy::SuffixCellData(suffix) => {
+                Arc::make_mut(attr).name.push_str(suffix);
+            }
+            NameCollisionPolicy::Allow => {}
+        }
+    }
 
-        create_attributes(point_data, attribute::Center::Node)?;
-        create_attributes(cell_data, attribute::Center::Cell)?;
+    Ok(())
+}
 
-        self.attributes.push((time.to_string(), new_attributes));
-        self.writen_times.insert(time.to_string());
+// create the attribute of a field, referencing its written heavy data
+fn field_attribute(
+    writer: &dyn DataWriter,
+    field_information: &BTreeMap<String, Vec<Information>>,
+    field_options: &FieldOptions,
+    (data_name, data_attribute, vals): (&str, &DataAttribute, &Values),
+    center: attribute::Center,
+    dimensions: Dimensions,
+    heavy_data: DataContent,
+) -> Arc<attribute::Attribute> {
+    let data_item = writer.wrap_data_item(DataItem {
+        name: None,
+        dimensions: Some(dimensions),
+        number_type: Some(vals.number_type()),
+        format: Some(writer.format()),
+        precision: Some(vals.precision()),
+        data: heavy_data,
+        reference: None,
+        item_type: None,
+        encoding: writer.encoding(),
+        endian: None,
+        function: None,
+        data_items: vec![],
+    });
+
+    let mut information = information_of_field(field_information, data_name);
+    if field_options.statistics {
+        information.extend(statistics_information(vals));
+    }
+    if field_options.checksums {
+        information.push(checksum_information(vals.checksum()));
+    }
 
-        self.writer.write_data_finalize()?;
+    Arc::new(attribute::Attribute {
+        name: data_name.to_string(),
+        attribute_type: data_attribute.into(),
+        center,
+        information,
+        data_items: vec![data_item],
+    })
+}
 
-        self.write()
-    }
+// minimum, maximum and mean over all components of the values, empty if there are no values
+fn statistics_information(vals: &Values) -> Vec<Information> {
+    vals.statistics()
+        .map(|(min, max, mean)| {
+            vec![
+                Information::new("min", min.format_number()),
+                Information::new("max", max.format_number()),
+                Information::new("mean", mean.format_number()),
+            ]
+        })
+        .unwrap_or_default()
+}
 
-    fn write(&mut self) -> IoResult<()> {
-        self.writer.flush()?;
+fn checksum_information(checksum: u64) -> Information {
+    Information::new(CHECKSUM_NAME, format!("{CHECKSUM_PREFIX}{checksum:016x}"))
+}
 
-        // create the XDMF structure
-        let time_grids = self
-            .attributes
-            .iter()
-            .map(|(time, attributes)| {
-                let mut grid = self.grid.clone();
+// number of edges and faces of a structured grid with the given number of points per direction
+fn structured_edge_face_counts(dims: &[usize]) -> (usize, usize) {
+    // product of the number of points (or cells, with offset 1) of all directions except the given one
+    let product_without = |direction: usize, offset: usize| -> usize {
+        dims.iter()
+            .enumerate()
+            .filter(|(other, _)| *other != direction)
+            .map(|(_, num_points)| num_points - offset)
+            .product()
+    };
 
-                match grid.grid_type {
-                    GridType::Uniform => {
-                        grid.name = format!("time_series-t{time}");
-                        grid.time = Some(Time::new(time));
-                        grid.attributes = Some(attributes.clone());
-                        grid
-                    }
-                    _ => unimplemented!("Only Uniform grids are supported for time series"),
-                }
-            })
-            .collect();
+    let num_edges = (0..dims.len())
+        .map(|direction| (dims[direction] - 1) * product_without(direction, 0))
+        .sum();
+    let num_faces = (0..dims.len())
+        .map(|direction| dims[direction] * product_without(direction, 1))
+        .sum();
 
-        let temporal_grid =
-            Grid::new_collection("time_series", CollectionType::Temporal, Some(time_grids));
+    (num_edges, num_faces)
+}
 
-        // If there are no attributes aka time-data, write the grid directly
-        let grid_to_write = if self.attributes.is_empty() {
-            self.grid.clone()
-        } else {
-            temporal_grid
-        };
+// scales of a transformation of a structured grid, which is aligned with the axes and can hence only be scaled and translated
+fn axis_aligned_scales(transform: &Transform, grid_kind: &str) -> XdmfResult<[f64; 3]> {
+    transform.validate()?;
+    transform
+        .axis_scales()
+        .filter(|scales| scales.iter().all(|scale| *scale > 0.0))
+        .ok_or_else(|| {
+            XdmfError::Validation(format!(
+                "{grid_kind} can only be scaled by positive factors and translated, but the transformation rotates, mirrors or shears it"
+            ))
+        })
+}
 
-        let mut xdmf = Xdmf {
-            information: vec![
-                Information::new("data_storage", format!("{:?}", self.writer.data_storage())),
-                Information::new("version", env!("CARGO_PKG_VERSION")),
-            ],
-            ..Default::default()
+// the data of an image has the shape of its points or cells, e.g. (nz, ny, nx, 3) for a vector at the points
+fn reshape_image_attributes(
+    attributes: &mut [Arc<attribute::Attribute>],
+    point_dimensions: &[usize],
+) {
+    for attribute in attributes {
+        let shape: Vec<usize> = match attribute.center {
+            attribute::Center::Node => point_dimensions.to_vec(),
+            attribute::Center::Cell => point_dimensions.iter().map(|num| num - 1).collect(),
+            attribute::Center::Grid
+            | attribute::Center::Edge
+            | attribute::Center::Face
+            | attribute::Center::Other => continue,
         };
-        xdmf.domains[0].grids.push(grid_to_write);
-        xdmf.domains[0].data_items.extend(self.data_items.clone());
 
-        // Write the XDMF file to a temporary file first to avoid access races
-        let temp_xdmf_file_name = self.xdmf_file_name.with_extension("xdmf.tmp");
+        for data_item in &mut Arc::make_mut(attribute).data_items {
+            if let Some(Dimensions(dimensions)) = &mut data_item.dimensions {
+                dimensions.splice(..1, shape.iter().copied());
+            }
+        }
+    }
+}
 
-        let mut xdmf_file = BufWriter::new(std::fs::File::create(&temp_xdmf_file_name)?);
-        xdmf.write_to(&mut xdmf_file)?;
-        xdmf_file.flush()?;
+// precision of the floating point data of the fields, with overrides for individual fields
+#[derive(Clone, Debug, Default)]
+struct FloatPrecisions {
+    default: FloatPrecision,
+    fields: BTreeMap<String, FloatPrecision>,
+}
 
-        std::fs::rename(&temp_xdmf_file_name, &self.xdmf_file_name)
+impl FloatPrecisions {
+    fn of_field(&self, field_name: &str) -> FloatPrecision {
+        self.fields.get(field_name).copied().unwrap_or(self.default)
     }
+}
 
-    fn validate_data(
-        &self,
-        time: &str,
-        point_data: Option<&DataMap>,
-        cell_data: Option<&DataMap>,
-    ) -> IoResult<()> {
-        // check if time can be parsed as a float
-        if time.parse::<f64>().is_err() {
-            return Err(IoError::new(
-                InvalidInput,
-                format!("Time must be a valid float, and not '{time}'"),
-            ));
-        }
+/// Options of a `TimeSeriesWriter` that are shared by the writers of multiple meshes and bodies, see `TimeSeriesWriter::into_parts`.
+struct WriterOptions {
+    xdmf_file_name: PathBuf,
+    writer: Box<dyn DataWriter>,
+    name_suffix: String,
+    information: Vec<Information>,
+    field_information: BTreeMap<String, Vec<Information>>,
+    flush_policy: FlushPolicy,
+    time_list: bool,
+    time_scale: Option<f64>,
+    tensor6_as_matrix: bool,
+    transform: Option<Transform>,
+    file_system: Arc<dyn FileSystem>,
+    output_frequencies: BTreeMap<String, usize>,
+    reference_style: ReferenceStyle,
+    xml_header: XmlHeader,
+    progress_callback: Option<ProgressCallback>,
+    field_options: FieldOptions,
+}
 
-        // check if the time step has already been written
-        if self.writen_times.contains(time) {
-            return Err(IoError::new(
-                InvalidInput,
-                format!("Time step '{time}' has already been written"),
-            ));
-        }
+// options for writing the attributes of the fields
+#[derive(Clone, Debug, Default)]
+struct FieldOptions {
+    float_precision: FloatPrecisions,
+    nan_policy: NanPolicy,
+    name_collision_policy: NameCollisionPolicy,
+    // whether the minimum, maximum and mean of the values are written as information of the attributes
+    statistics: bool,
+    // whether the checksum of the values is written as information of the attributes
+    checksums: bool,
+    // prefix of the names of the attributes in the XDMF file, see `TimeSeriesWriter::with_field_name_prefix`
+    name_prefix: String,
+    // fields averaged from the points to the cells or vice versa, see `TimeSeriesWriter::with_cell_average`
+    averaged_fields: Vec<AveragedField>,
+}
 
-        // check if some data is provided
-        if (point_data.unwrap_or(&BTreeMap::new()).len()
-            + cell_data.unwrap_or(&BTreeMap::new()).len())
-            == 0
-        {
-            return Err(IoError::new(
-                InvalidInput,
-                "At least one of point_data or cell_data must be provided",
-            ));
+// custom information of a field, written into each of its attributes
+fn information_of_field(
+    field_information: &BTreeMap<String, Vec<Information>>,
+    field_name: &str,
+) -> Vec<Information> {
+    field_information
+        .get(field_name)
+        .cloned()
+        .unwrap_or_default()
+}
+
+// the number of component names of a field must match the number of its components
+fn validate_component_names(
+    field_information: &BTreeMap<String, Vec<Information>>,
+    field_name: &str,
+    data_attribute: &DataAttribute,
+) -> XdmfResult<()> {
+    let component_names = field_information
+        .get(field_name)
+        .into_iter()
+        .flatten()
+        .filter(|information| information.name == COMPONENT_NAMES_NAME);
+
+    for information in component_names {
+        let num_names = information.value.split_whitespace().count();
+        if num_names != data_attribute.size() {
+            return Err(XdmfError::Validation(format!(
+                "Field '{field_name}' has {} components, but {num_names} component names are given",
+                data_attribute.size()
+            )));
         }
+    }
 
-        check_data_size(point_data, self.num_points, "point")?;
-        check_data_size(cell_data, self.num_cells, "cell")?;
+    Ok(())
+}
 
-        // check that names do not contain forbidden characters
-        validate_data_name(point_data, "point")?;
-        validate_data_name(cell_data, "cell")
+// validate the data of a time step against the number of points and cells of the mesh
+pub(crate) fn validate_data(
+    (num_points, num_cells): (usize, usize),
+    point_data: Option<&DataMap>,
+    cell_data: Option<&DataMap>,
+) -> XdmfResult<()> {
+    // check if some data is provided
+    if (point_data.unwrap_or(&BTreeMap::new()).len() + cell_data.unwrap_or(&BTreeMap::new()).len())
+        == 0
+    {
+        return Err(XdmfError::Validation(
+            "At least one of point_data or cell_data must be provided".into(),
+        ));
     }
+
+    check_data_size(point_data, num_points, "point")?;
+    check_data_size(cell_data, num_cells, "cell")?;
+
+    // check that names do not contain forbidden characters
+    validate_data_name(point_data, "point")?;
+    validate_data_name(cell_data, "cell")
 }
 
 // check sizes of point_data and cell_data
-fn check_data_size(data_input: Option<&DataMap>, num_entities: usize, label: &str) -> IoResult<()> {
+pub(crate) fn check_data_size(
+    data_input: Option<&DataMap>,
+    num_entities: usize,
+    label: &str,
+) -> XdmfResult<()> {
     if let Some(data_map) = data_input {
         for (name, data) in data_map {
             let exp_size = num_entities * data.0.size();
             if data.1.len() != exp_size {
-                return Err(IoError::new(
-                    InvalidInput,
-                    format!(
-                        "Size of {label}-data '{name}' must be {}, but is {}",
-                        exp_size,
-                        data.1.len()
-                    ),
-                ));
+                return Err(XdmfError::Validation(format!(
+                    "Size of {label}-data '{name}' must be {}, but is {}",
+                    exp_size,
+                    data.1.len()
+                )));
             }
         }
     }
     Ok(())
 }
 
-fn validate_data_name(data_input: Option<&DataMap>, label: &str) -> IoResult<()> {
+// the data with the fields averaged from the other center added, None if no field of the data is averaged
+fn add_averaged_fields<'a>(
+    averaged_fields: &[AveragedField],
+    cell_points: Option<&CellPoints>,
+    (point_data, cell_data): (Option<&'a DataMap>, Option<&'a DataMap>),
+) -> XdmfResult<(Option<DataMap<'a>>, Option<DataMap<'a>>)> {
+    let mut averaged_point_data = None;
+    let mut averaged_cell_data = None;
+
+    for averaged_field in averaged_fields {
+        let (source_data, data, averaged_data) = match averaged_field.center {
+            attribute::Center::Cell => (point_data, cell_data, &mut averaged_cell_data),
+            _ => (cell_data, point_data, &mut averaged_point_data),
+        };
+
+        let Some((data_attribute, values)) =
+            source_data.and_then(|source_data| source_data.get(&averaged_field.source))
+        else {
+            continue;
+        };
+
+        let cell_points = cell_points.ok_or_else(|| {
+            XdmfError::Unsupported(format!(
+                "Field '{}' can only be averaged for meshes written with `write_mesh` or `write_mesh_soa`",
+                averaged_field.source
+            ))
+        })?;
+
+        if data.is_some_and(|data| data.contains_key(&averaged_field.name)) {
+            return Err(XdmfError::Validation(format!(
+                "Averaged field '{}' conflicts with the {}-data of the same name",
+                averaged_field.name,
+                center_label(averaged_field.center).unwrap_or_default()
+            )));
+        }
+
+        let averages = match averaged_field.center {
+            attribute::Center::Cell => cell_points.cell_averages(values, data_attribute.size()),
+            _ => cell_points.point_averages(values, data_attribute.size()),
+        };
+
+        // the values of the given data are borrowed
+        averaged_data
+            .get_or_insert_with(|| {
+                data.into_iter()
+                    .flatten()
+                    .map(|(name, (data_attribute, values))| {
+                        (
+                            name.clone(),
+                            (data_attribute.clone(), values.slice(0..values.len())),
+                        )
+                    })
+                    .collect::<DataMap>()
+            })
+            .insert(
+                averaged_field.name.clone(),
+                (data_attribute.clone(), averages.into()),
+            );
+    }
+
+    Ok((averaged_point_data, averaged_cell_data))
+}
+
+// check that the written data does not conflict with the derived fields
+fn check_derived_fields(
+    derived_fields: &[DerivedField],
+    point_data: Option<&DataMap>,
+    cell_data: Option<&DataMap>,
+) -> XdmfResult<()> {
+    for (data_map, center, entity_label) in [
+        (point_data, attribute::Center::Node, "point"),
+        (cell_data, attribute::Center::Cell, "cell"),
+    ] {
+        if let Some(derived_field) = derived_fields.iter().find(|field| {
+            field.center == center && data_map.is_some_and(|data| data.contains_key(&field.name))
+        }) {
+            return Err(XdmfError::Validation(format!(
+                "Data '{}' of {entity_label}-data conflicts with the derived field of the same name",
+                derived_field.name
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+// add the attributes of the derived fields whose source fields are all part of the attributes
+fn add_derived_attributes(
+    derived_fields: &[DerivedField],
+    field_information: &BTreeMap<String, Vec<Information>>,
+    attributes: &mut Vec<Arc<attribute::Attribute>>,
+) {
+    let mut derived_attributes = Vec::new();
+
+    for derived_field in derived_fields {
+        let Some(arguments) = derived_field
+            .fields
+            .iter()
+            .map(|field| {
+                attributes
+                    .iter()
+                    .find(|attr| attr.center == derived_field.center && attr.name == *field)
+                    .and_then(|attr| attr.data_items.first().cloned())
+            })
+            .collect::<Option<Vec<DataItem>>>()
+        else {
+            continue;
+        };
+
+        // the number of entities is the first dimension of the source data
+        let num_entities = arguments
+            .first()
+            .and_then(|argument| argument.dimensions.as_ref())
+            .and_then(|dimensions| dimensions.0.first().copied())
+            .unwrap_or_default();
+
+        derived_attributes.push(Arc::new(attribute::Attribute {
+            name: derived_field.name.clone(),
+            attribute_type: (&derived_field.data_attribute).into(),
+            center: derived_field.center,
+            information: information_of_field(field_information, &derived_field.name),
+            data_items: vec![DataItem::new_function(
+                &derived_field.function,
+                derived_field
+                    .data_attribute
+                    .dimensions(num_entities * derived_field.data_attribute.size()),
+                arguments,
+            )],
+        }));
+    }
+
+    attributes.extend(derived_attributes);
+}
+
+// indices of the arguments of a function, i.e. 0 and 1 for "$0 * $1"
+pub(crate) fn function_arguments(function: &str) -> impl Iterator<Item = usize> + '_ {
+    function.split('$').skip(1).filter_map(|part| {
+        let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse().ok()
+    })
+}
+
+// check that the data matches the registered fields of the given center, if fields were registered
+fn check_registered_fields(
+    registered_fields: &[(String, DataAttribute, attribute::Center)],
+    data_input: Option<&DataMap>,
+    center: attribute::Center,
+) -> XdmfResult<()> {
+    if registered_fields.is_empty() {
+        return Ok(());
+    }
+
+    let empty_data_map = BTreeMap::new();
+    let data_map = data_input.unwrap_or(&empty_data_map);
+
+    for (name, (data_attribute, _)) in data_map {
+        check_registered_field(registered_fields, name, data_attribute, center)?;
+    }
+
+    if let Some((name, ..)) = registered_fields
+        .iter()
+        .find(|field| field.2 == center && !data_map.contains_key(&field.0))
+    {
+        return Err(XdmfError::Validation(format!(
+            "Registered field '{name}' of {}-data is missing",
+            center_label(center).unwrap_or_default()
+        )));
+    }
+
+    Ok(())
+}
+
+// check that the data does not contain fields that were written as static data
+fn check_static_fields(
+    static_attributes: &[Arc<attribute::Attribute>],
+    data_input: Option<&DataMap>,
+    center: attribute::Center,
+) -> XdmfResult<()> {
+    if let Some(data_name) = find_static_field(static_attributes, data_input, center) {
+        return Err(XdmfError::Validation(format!(
+            "Field '{data_name}' of {}-data was written as static data, hence it must not be written for a time step",
+            center_label(center).unwrap_or_default()
+        )));
+    }
+
+    Ok(())
+}
+
+// the first field of the data that was written as static data of the given center
+fn find_static_field<'a>(
+    static_attributes: &[Arc<attribute::Attribute>],
+    data_input: Option<&'a DataMap>,
+    center: attribute::Center,
+) -> Option<&'a String> {
+    data_input
+        .into_iter()
+        .flat_map(BTreeMap::keys)
+        .find(|data_name| {
+            static_attributes
+                .iter()
+                .any(|attr| attr.center == center && attr.name == **data_name)
+        })
+}
+
+// check that a field is registered with the given data attribute, if fields were registered
+fn check_registered_field(
+    registered_fields: &[(String, DataAttribute, attribute::Center)],
+    name: &str,
+    data_attribute: &DataAttribute,
+    center: attribute::Center,
+) -> XdmfResult<()> {
+    if registered_fields.is_empty() {
+        return Ok(());
+    }
+
+    let entity_label = center_label(center).unwrap_or_default();
+
+    match registered_fields
+        .iter()
+        .find(|field| field.0 == name && field.2 == center)
+    {
+        None => Err(XdmfError::Validation(format!(
+            "Field '{name}' of {entity_label}-data is not registered"
+        ))),
+        Some((_, registered_attribute, _)) if registered_attribute != data_attribute => {
+            Err(XdmfError::Validation(format!(
+                "Field '{name}' of {entity_label}-data is registered as {registered_attribute:?}, but is {data_attribute:?}"
+            )))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+// label of the data of a center, as used in error messages
+fn center_label(center: attribute::Center) -> Option<&'static str> {
+    match center {
+        attribute::Center::Node => Some("point"),
+        attribute::Center::Cell => Some("cell"),
+        attribute::Center::Grid => Some("global"),
+        attribute::Center::Edge => Some("edge"),
+        attribute::Center::Face => Some("face"),
+        attribute::Center::Other => None,
+    }
+}
+
+fn validate_data_name(data_input: Option<&DataMap>, label: &str) -> XdmfResult<()> {
     if let Some(data_map) = data_input {
         for name in data_map.keys() {
             if !is_valid_data_name(name) {
-                return Err(IoError::new(
-                    InvalidInput,
-                    format!(
-                        "Data name '{name}' of {label}-data is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
-                    ),
-                ));
+                return Err(XdmfError::Validation(format!(
+                    "Data name '{name}' of {label}-data is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
+                )));
             };
         }
     }
     Ok(())
 }
 
-fn is_valid_data_name(name: &str) -> bool {
+pub(crate) fn is_valid_data_name(name: &str) -> bool {
     if name.is_empty() {
         return false;
     }
@@ -474,26 +6259,25 @@ fn is_valid_data_name(name: &str) -> bool {
 }
 
 /// Validate the file name for the XDMF file.
-fn validate_file_name(file_name: &Path) -> IoResult<()> {
+pub(crate) fn validate_file_name(file_name: &Path) -> XdmfResult<()> {
     // Ensure it's valid UTF-8
     let Some(name) = file_name.to_str() else {
-        return Err(IoError::new(InvalidInput, "File name must be valid UTF-8"));
+        return Err(XdmfError::Validation(
+            "File name must be valid UTF-8".into(),
+        ));
     };
 
     if name.is_empty() {
-        return Err(IoError::new(InvalidInput, "File name must not be empty"));
+        return Err(XdmfError::Validation("File name must not be empty".into()));
     }
 
     let invalid_chars = ['?', '\0', ':', '*', '"', '<', '>', '|'];
 
     // Check for invalid characters
     if name.chars().any(|c| invalid_chars.contains(&c)) {
-        return Err(IoError::new(
-            InvalidInput,
-            format!(
-                "File name '{name}' cannot contain the following characters: {invalid_chars:?}"
-            ),
-        ));
+        return Err(XdmfError::Validation(format!(
+            "File name '{name}' cannot contain the following characters: {invalid_chars:?}"
+        )));
     }
 
     Ok(())
@@ -503,13 +6287,23 @@ fn validate_file_name(file_name: &Path) -> IoResult<()> {
 mod tests {
     use super::*;
     use crate::{
-        DataAttribute,
+        DataAttribute, mpi_safe_create_dir_all,
         xdmf_elements::{
             data_item::{DataContent, Format},
             grid::Grid,
         },
     };
 
+    #[test]
+    fn test_structured_edge_face_counts() {
+        // a single voxel
+        assert_eq!(structured_edge_face_counts(&[2, 2, 2]), (12, 6));
+        // 2x3x4 cells
+        assert_eq!(structured_edge_face_counts(&[3, 4, 5]), (133, 98));
+        // a single layer of 2x2 pixels
+        assert_eq!(structured_edge_face_counts(&[2, 3, 3]), (33, 20));
+    }
+
     #[test]
     fn test_poly_cell_points() {
         assert_eq!(poly_cell_points(CellType::Vertex), Some(1));
@@ -531,6 +6325,8 @@ mod tests {
         assert_eq!(poly_cell_points(CellType::Hexahedron20), None);
         assert_eq!(poly_cell_points(CellType::Hexahedron24), None);
         assert_eq!(poly_cell_points(CellType::Hexahedron27), None);
+        assert_eq!(poly_cell_points(CellType::Polygon(5)), Some(5));
+        assert_eq!(poly_cell_points(CellType::Polyhedron(21)), None);
     }
 
     #[test]
@@ -557,157 +6353,129 @@ mod tests {
 
     #[test]
     fn prepare_cells_by_celltype() {
-        assert_eq!(
-            prepare_cells((&[5], &[CellType::Vertex]), 0).1,
-            vec![1, 1, 5]
-        );
+        // an edge is added in front, such that the topology is mixed
+        let prepare_mixed = |connectivity: &[u64], cell_types: &[CellType]| {
+            let connectivity: Vec<u64> = [0, 0].iter().chain(connectivity).copied().collect();
+            let cell_types: Vec<CellType> =
+                [CellType::Edge].iter().chain(cell_types).copied().collect();
+            let (topo_type, cells_prep) = prepare_cells((&connectivity, &cell_types), 0);
+            assert_eq!(topo_type, TopologyType::Mixed);
+            cells_prep[4..].to_vec()
+        };
 
-        assert_eq!(
-            prepare_cells((&[5, 6], &[CellType::Edge]), 0).1,
-            vec![2, 2, 5, 6]
-        );
+        assert_eq!(prepare_mixed(&[5], &[CellType::Vertex]), vec![1, 1, 5]);
+
+        assert_eq!(prepare_mixed(&[5, 6], &[CellType::Edge]), vec![2, 2, 5, 6]);
 
         assert_eq!(
-            prepare_cells((&[5, 6, 7], &[CellType::Triangle]), 0).1,
+            prepare_mixed(&[5, 6, 7], &[CellType::Triangle]),
             vec![4, 5, 6, 7]
         );
 
         assert_eq!(
-            prepare_cells((&[5, 6, 7, 8], &[CellType::Quadrilateral]), 0).1,
+            prepare_mixed(&[5, 6, 7, 8], &[CellType::Quadrilateral]),
             vec![5, 5, 6, 7, 8]
         );
 
         assert_eq!(
-            prepare_cells((&[5, 6, 7, 8], &[CellType::Tetrahedron]), 0).1,
+            prepare_mixed(&[5, 6, 7, 8], &[CellType::Tetrahedron]),
             vec![6, 5, 6, 7, 8]
         );
 
         assert_eq!(
-            prepare_cells((&[5, 6, 7, 8, 9], &[CellType::Pyramid]), 0).1,
+            prepare_mixed(&[5, 6, 7, 8, 9], &[CellType::Pyramid]),
             vec![7, 5, 6, 7, 8, 9]
         );
 
         assert_eq!(
-            prepare_cells((&[5, 6, 7, 8, 9, 10], &[CellType::Wedge]), 0).1,
+            prepare_mixed(&[5, 6, 7, 8, 9, 10], &[CellType::Wedge]),
             vec![8, 5, 6, 7, 8, 9, 10]
         );
 
         assert_eq!(
-            prepare_cells((&[5, 6, 7, 8, 9, 10, 11, 12], &[CellType::Hexahedron]), 0).1,
+            prepare_mixed(&[5, 6, 7, 8, 9, 10, 11, 12], &[CellType::Hexahedron]),
             vec![9, 5, 6, 7, 8, 9, 10, 11, 12]
         );
 
         assert_eq!(
-            prepare_cells((&[5, 6, 7], &[CellType::Edge3]), 0).1,
+            prepare_mixed(&[5, 6, 7], &[CellType::Edge3]),
             vec![34, 5, 6, 7]
         );
 
         assert_eq!(
-            prepare_cells(
-                (
-                    &[5, 6, 7, 8, 9, 10, 11, 12, 13],
-                    &[CellType::Quadrilateral9]
-                ),
-                0
-            )
-            .1,
+            prepare_mixed(
+                &[5, 6, 7, 8, 9, 10, 11, 12, 13],
+                &[CellType::Quadrilateral9]
+            ),
             vec![35, 5, 6, 7, 8, 9, 10, 11, 12, 13]
         );
 
         assert_eq!(
-            prepare_cells((&[5, 6, 7, 8, 9, 10], &[CellType::Triangle6]), 0).1,
+            prepare_mixed(&[5, 6, 7, 8, 9, 10], &[CellType::Triangle6]),
             vec![36, 5, 6, 7, 8, 9, 10]
         );
 
         assert_eq!(
-            prepare_cells(
-                (&[5, 6, 7, 8, 9, 10, 11, 12], &[CellType::Quadrilateral8]),
-                0
-            )
-            .1,
+            prepare_mixed(&[5, 6, 7, 8, 9, 10, 11, 12], &[CellType::Quadrilateral8]),
             vec![37, 5, 6, 7, 8, 9, 10, 11, 12]
         );
 
         assert_eq!(
-            prepare_cells(
-                (
-                    &[5, 6, 7, 8, 9, 10, 11, 12, 13, 14],
-                    &[CellType::Tetrahedron10]
-                ),
-                0
-            )
-            .1,
+            prepare_mixed(
+                &[5, 6, 7, 8, 9, 10, 11, 12, 13, 14],
+                &[CellType::Tetrahedron10]
+            ),
             vec![38, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]
         );
 
         assert_eq!(
-            prepare_cells(
-                (
-                    &[5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17],
-                    &[CellType::Pyramid13]
-                ),
-                0
-            )
-            .1,
+            prepare_mixed(
+                &[5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17],
+                &[CellType::Pyramid13]
+            ),
             vec![39, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17]
         );
 
         assert_eq!(
-            prepare_cells(
-                (
-                    &[5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19],
-                    &[CellType::Wedge15]
-                ),
-                0
-            )
-            .1,
+            prepare_mixed(
+                &[5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19],
+                &[CellType::Wedge15]
+            ),
             vec![40, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]
         );
 
         assert_eq!(
-            prepare_cells(
-                (
-                    &[
-                        5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22
-                    ],
-                    &[CellType::Wedge18]
-                ),
-                0
-            )
-            .1,
+            prepare_mixed(
+                &[
+                    5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22
+                ],
+                &[CellType::Wedge18]
+            ),
             vec![
                 41, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22
             ]
         );
 
         assert_eq!(
-            prepare_cells(
-                (
-                    &[
-                        5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24
-                    ],
-                    &[CellType::Hexahedron20]
-                ),
-                0
-            )
-            .1,
+            prepare_mixed(
+                &[
+                    5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24
+                ],
+                &[CellType::Hexahedron20]
+            ),
             vec![
                 48, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24
             ]
         );
 
         assert_eq!(
-            prepare_cells(
-                (
-                    &[
-                        5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
-                        25, 26, 27, 28
-                    ],
-                    &[CellType::Hexahedron24]
-                ),
-                0
-            )
-            .1,
+            prepare_mixed(
+                &[
+                    5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+                    26, 27, 28
+                ],
+                &[CellType::Hexahedron24]
+            ),
             vec![
                 49, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
                 26, 27, 28
@@ -715,22 +6483,62 @@ mod tests {
         );
 
         assert_eq!(
-            prepare_cells(
-                (
-                    &[
-                        5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
-                        25, 26, 27, 28, 29, 30, 31
-                    ],
-                    &[CellType::Hexahedron27]
-                ),
-                0
-            )
-            .1,
+            prepare_mixed(
+                &[
+                    5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+                    26, 27, 28, 29, 30, 31
+                ],
+                &[CellType::Hexahedron27]
+            ),
             vec![
                 50, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
                 26, 27, 28, 29, 30, 31
             ]
         );
+
+        assert_eq!(
+            prepare_mixed(&[5, 6, 7, 8, 9], &[CellType::Polygon(5)]),
+            vec![3, 5, 5, 6, 7, 8, 9]
+        );
+
+        // a tetrahedron given as polyhedron
+        let (polyhedron, face_stream) =
+            CellType::polyhedron(&[&[5, 6, 7], &[5, 6, 8], &[6, 7, 8], &[7, 5, 8]]);
+        assert_eq!(
+            prepare_mixed(&face_stream, &[polyhedron]),
+            vec![16, 4, 3, 5, 6, 7, 3, 5, 6, 8, 3, 6, 7, 8, 3, 7, 5, 8]
+        );
+    }
+
+    #[test]
+    fn test_prepare_cells_uniform() {
+        let (topo_type, cells_prep) = prepare_cells(
+            (
+                &[0, 1, 2, 1, 3, 2],
+                &[CellType::Triangle, CellType::Triangle],
+            ),
+            0,
+        );
+        assert_eq!(topo_type, TopologyType::Triangle);
+        assert_eq!(cells_prep, vec![0, 1, 2, 1, 3, 2]);
+
+        let (topo_type, cells_prep) = prepare_cells((&[0, 1, 2, 3], &[CellType::Tetrahedron]), 0);
+        assert_eq!(topo_type, TopologyType::Tetrahedron);
+        assert_eq!(cells_prep, vec![0, 1, 2, 3]);
+
+        let (topo_type, cells_prep) = prepare_cells((&[3, 1], &[CellType::Vertex; 2]), 0);
+        assert_eq!(topo_type, TopologyType::Polyvertex);
+        assert_eq!(cells_prep, vec![3, 1]);
+
+        // edges are always written as mixed topology
+        let (topo_type, cells_prep) = prepare_cells((&[0, 1], &[CellType::Edge]), 0);
+        assert_eq!(topo_type, TopologyType::Mixed);
+        assert_eq!(cells_prep, vec![2, 2, 0, 1]);
+
+        // as are polygons, even if all have the same number of points
+        let (topo_type, cells_prep) = prepare_cells((&[0, 1, 2, 3], &[CellType::Polygon(4)]), 0);
+        assert_eq!(topo_type, TopologyType::Mixed);
+        assert_eq!(cells_prep, vec![3, 4, 0, 1, 2, 3]);
     }
 
     #[test]
@@ -827,6 +6635,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_points_and_cells_large_index() {
+        // an index that would wrap to a valid one if truncated to 32 bits
+        let index = (1 << 32) + 1;
+        let res = validate_points_and_cells(&[0.0; 9], (&[0, 1, index], &[CellType::Triangle]));
+
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Connectivity indices out of bounds for the given points, max index: 4294967297, but number of points is 3"
+        );
+    }
+
     #[test]
     fn validate_points_and_cells_conn_mismatch() {
         let res = validate_points_and_cells(
@@ -849,6 +6669,90 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_points_and_cells_polyhedron() {
+        let (polyhedron, face_stream) =
+            CellType::polyhedron(&[&[0, 1, 2], &[0, 1, 3], &[1, 2, 3], &[2, 0, 3]]);
+
+        validate_points_and_cells(&[0.0; 12], (&face_stream, &[polyhedron])).unwrap();
+
+        // the number of faces and points are not checked as indices
+        let face: &[u64] = &[0, 1, 2];
+        let (polyhedron, face_stream) = CellType::polyhedron(&[face; 4]);
+        validate_points_and_cells(&[0.0; 9], (&face_stream, &[polyhedron])).unwrap();
+
+        assert_eq!(
+            validate_points_and_cells(&[0.0; 9], (&[1, 3, 0, 1, 5], &[CellType::Polyhedron(5)]))
+                .unwrap_err()
+                .to_string(),
+            "Connectivity indices out of bounds for the given points, max index: 5, but number of points is 3"
+        );
+
+        assert_eq!(
+            validate_points_and_cells(&[0.0; 9], (&[1, 3, 0, 1], &[CellType::Polyhedron(4)]))
+                .unwrap_err()
+                .to_string(),
+            "Face stream of polyhedron does not match its size of 4: [1, 3, 0, 1]"
+        );
+
+        assert_eq!(
+            validate_points_and_cells(&[0.0; 9], (&[1, 2, 0, 1, 2], &[CellType::Polyhedron(5)]))
+                .unwrap_err()
+                .to_string(),
+            "Face stream of polyhedron does not match its size of 5: [1, 2, 0, 1, 2]"
+        );
+    }
+
+    #[test]
+    fn test_split_part_path() {
+        assert_eq!(
+            split_part_path("assembly/part/body").unwrap(),
+            vec!["assembly", "part", "body"]
+        );
+        assert_eq!(split_part_path("body").unwrap(), vec!["body"]);
+
+        for path in ["", "assembly//body", "/body", "body/"] {
+            assert_eq!(
+                split_part_path(path).unwrap_err().to_string(),
+                format!(
+                    "Mesh part path '{path}' must consist of non-empty segments separated by '/'"
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn write_mesh_tree_conflicting_paths() {
+        let tmp_dir = temp_dir::TempDir::new().unwrap();
+        let points = [0.0; 3];
+
+        for paths in [["a/b", "a/b"], ["a/b", "a/b/c"], ["a/b/c", "a/b"]] {
+            let writer =
+                TimeSeriesWriter::new(tmp_dir.path().join("tree"), DataStorage::AsciiInline)
+                    .unwrap();
+
+            let res = writer.write_mesh_tree(&[
+                (paths[0], &points, (&[], &[])),
+                (paths[1], &points, (&[], &[])),
+            ]);
+
+            assert_eq!(
+                res.err().unwrap().to_string(),
+                format!(
+                    "Mesh part path '{}' conflicts with the path of another part",
+                    paths[1]
+                )
+            );
+        }
+
+        let writer =
+            TimeSeriesWriter::new(tmp_dir.path().join("tree"), DataStorage::AsciiInline).unwrap();
+        assert_eq!(
+            writer.write_mesh_tree(&[]).err().unwrap().to_string(),
+            "At least one mesh part must be provided"
+        );
+    }
+
     #[test]
     fn time_series_writer_create_folder() {
         let tmp_dir = temp_dir::TempDir::new().unwrap();
@@ -1206,12 +7110,12 @@ mod tests {
         fn dummy_geometry() -> Geometry {
             Geometry {
                 geometry_type: GeometryType::XYZ,
-                data_item: DataItem {
+                data_items: vec![DataItem {
                     dimensions: Some(Dimensions(vec![5, 3])),
                     data: "0 1 0 0 1.5 0 0.5 1.5 0.5 1 1.5 0 1 1 0".into(),
                     number_type: Some(NumberType::Float),
                     ..Default::default()
-                },
+                }],
             }
         }
 
@@ -1219,12 +7123,13 @@ mod tests {
             Topology {
                 topology_type: TopologyType::Triangle,
                 number_of_elements: "2".into(),
-                data_item: DataItem {
+                dimensions: None,
+                data_item: Some(DataItem {
                     dimensions: Some(Dimensions(vec![6])),
                     number_type: Some(NumberType::Int),
                     data: "0 1 2 2 3 4".into(),
                     ..Default::default()
-                },
+                }),
             }
         }
 
@@ -1243,19 +7148,51 @@ mod tests {
                 &mut self,
                 _points: &[f64],
                 _cells: &[u64],
-            ) -> IoResult<(DataContent, DataContent)> {
+            ) -> XdmfResult<(DataContent, DataContent)> {
                 Ok((
                     DataContent::Raw("points".to_string()),
                     DataContent::Raw("cells".to_string()),
                 ))
             }
 
+            fn write_named_mesh(
+                &mut self,
+                _name: &str,
+                points: &[f64],
+                cells: &[u64],
+            ) -> XdmfResult<(DataContent, DataContent)> {
+                self.write_mesh(points, cells)
+            }
+
+            fn write_mesh_soa(
+                &mut self,
+                _name: Option<&str>,
+                _coordinates: [&[f64]; 3],
+                _cells: &[u64],
+            ) -> XdmfResult<([DataContent; 3], DataContent)> {
+                unimplemented!()
+            }
+
+            fn write_set(&mut self, name: &str, _indices: &[u64]) -> XdmfResult<DataContent> {
+                Ok(DataContent::Raw(format!("set_{name}")))
+            }
+
             fn write_data(
                 &mut self,
                 name: &str,
                 _center: attribute::Center,
                 _data: &crate::values::Values,
-            ) -> IoResult<DataContent> {
+            ) -> XdmfResult<DataContent> {
+                Ok(DataContent::Raw(format!("data_for_{name}")))
+            }
+
+            fn write_data_streamed(
+                &mut self,
+                name: &str,
+                _center: attribute::Center,
+                _values: &mut dyn Iterator<Item = f64>,
+                _len: usize,
+            ) -> XdmfResult<DataContent> {
                 Ok(DataContent::Raw(format!("data_for_{name}")))
             }
         }
@@ -1263,16 +7200,17 @@ mod tests {
         let tmp_dir = temp_dir::TempDir::new().unwrap();
         let xdmf_file_path = tmp_dir.path().join("test_write_data_preserve_order.xdmf2");
 
-        let mut writer = TimeSeriesDataWriter {
-            xdmf_file_name: xdmf_file_path.clone(),
-            writer: Box::new(DummyWriter),
-            grid: Grid::new_uniform("test", dummy_geometry(), dummy_topology()),
-            data_items: Vec::new(),
-            num_points: 0,
-            num_cells: 0,
-            attributes: Vec::new(),
-            writen_times: HashSet::new(),
-        };
+        let mut writer =
+            TimeSeriesWriter::new_with_writer(&xdmf_file_path, Arc::new(LocalFileSystem), || {
+                Ok(Box::new(DummyWriter))
+            })
+            .unwrap()
+            .into_data_writer(
+                Grid::new_uniform("test", dummy_geometry(), dummy_topology()),
+                DataItemRegistry::new(),
+                0,
+                0,
+            );
 
         let point_data = vec![(
             "scalar_data".to_string(),
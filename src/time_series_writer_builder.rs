@@ -0,0 +1,514 @@
+//! This module contains the builder of the [`TimeSeriesWriter`], which bundles all options of the writer in one place.
+
+use std::{
+    fmt,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    DataStorage, FloatPrecision, FlushPolicy, Hdf5Options, IndexPrecision, NameCollisionPolicy,
+    NanPolicy, PathPolicy, ReferenceStyle, RetentionPolicy, TimeSeriesWriter, Transform,
+    WriteEvent, XdmfError, XdmfResult, XmlHeader,
+    dictionary::{COMPONENT_NAMES_NAME, FieldDescriptor},
+    progress::ProgressCallback,
+    select_data_storage,
+};
+
+/// Builder for a [`TimeSeriesWriter`], created with [`TimeSeriesWriter::builder`].
+///
+/// All options except the basename of the files have defaults.
+/// ```rust
+/// use xdmf::{DataStorage, FloatPrecision, TimeSeriesWriter};
+/// let xdmf_writer = TimeSeriesWriter::builder()
+///     .directory("xdmf_builder_output")
+///     .basename("simulation")
+///     .storage(DataStorage::AsciiInline)
+///     .precision(FloatPrecision::Single)
+///     .information("solver_version", "1.2.3")
+///     .build()
+///     .expect("failed to create XDMF writer");
+/// ```
+#[derive(Clone, Debug)]
+pub struct TimeSeriesWriterBuilder {
+    directory: PathBuf,
+    basename: Option<String>,
+    storage: DataStorage,
+    fallback_storage: Option<DataStorage>,
+    precision: FloatPrecision,
+    field_precisions: Vec<(String, FloatPrecision)>,
+    index_precision: IndexPrecision,
+    field_output_frequencies: Vec<(String, usize)>,
+    cell_averages: Vec<(String, String)>,
+    point_averages: Vec<(String, String)>,
+    hdf5_options: Hdf5Options,
+    hdf5_group: Option<(PathBuf, String)>,
+    information: Vec<(String, String)>,
+    field_information: Vec<(String, String, String)>,
+    inline_size_limit: Option<usize>,
+    path_policy: PathPolicy,
+    flush_policy: FlushPolicy,
+    retention_policy: RetentionPolicy,
+    time_list: bool,
+    latest_step_file: bool,
+    rollover: Option<usize>,
+    time_scale: Option<f64>,
+    time_unit: Option<String>,
+    tensor6_as_matrix: bool,
+    subdivide_quadratic: bool,
+    statistics: bool,
+    checksums: bool,
+    field_name_prefix: String,
+    nan_policy: NanPolicy,
+    name_collision_policy: NameCollisionPolicy,
+    reference_style: ReferenceStyle,
+    xml_header: XmlHeader,
+    transform: Option<Transform>,
+    progress_callback: Option<BuilderCallback>,
+}
+
+// the progress callback is shared by the clones of the builder
+#[derive(Clone)]
+struct BuilderCallback(ProgressCallback);
+
+impl fmt::Debug for BuilderCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+impl Default for TimeSeriesWriterBuilder {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::new(),
+            basename: None,
+            storage: if cfg!(feature = "hdf5") {
+                DataStorage::Hdf5SingleFile
+            } else {
+                DataStorage::Ascii
+            },
+            fallback_storage: None,
+            precision: FloatPrecision::default(),
+            field_precisions: Vec::new(),
+            index_precision: IndexPrecision::default(),
+            field_output_frequencies: Vec::new(),
+            cell_averages: Vec::new(),
+            point_averages: Vec::new(),
+            hdf5_options: Hdf5Options::default(),
+            hdf5_group: None,
+            information: Vec::new(),
+            field_information: Vec::new(),
+            inline_size_limit: None,
+            path_policy: PathPolicy::default(),
+            flush_policy: FlushPolicy::default(),
+            retention_policy: RetentionPolicy::default(),
+            time_list: false,
+            latest_step_file: false,
+            rollover: None,
+            time_scale: None,
+            time_unit: None,
+            tensor6_as_matrix: false,
+            subdivide_quadratic: false,
+            statistics: false,
+            checksums: false,
+            field_name_prefix: String::new(),
+            nan_policy: NanPolicy::default(),
+            name_collision_policy: NameCollisionPolicy::default(),
+            reference_style: ReferenceStyle::default(),
+            xml_header: XmlHeader::default(),
+            transform: None,
+            progress_callback: None,
+        }
+    }
+}
+
+impl TimeSeriesWriterBuilder {
+    /// Directory of the written files, it is created if it does not exist. Defaults to the current working directory.
+    pub fn directory(mut self, directory: impl AsRef<Path>) -> Self {
+        self.directory = directory.as_ref().to_path_buf();
+        self
+    }
+
+    /// Name of the XDMF file without extension, which is also used for the files of the heavy data. Must be given.
+    pub fn basename(mut self, basename: impl ToString) -> Self {
+        self.basename = Some(basename.to_string());
+        self
+    }
+
+    /// Storage of the heavy data, defaults to [`DataStorage::Hdf5SingleFile`] ([`DataStorage::Ascii`] without the `hdf5` feature).
+    pub fn storage(mut self, storage: DataStorage) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// Storage that is used if the chosen one is not available, see [`TimeSeriesWriter::new_with_fallback`].
+    pub fn fallback_storage(mut self, fallback_storage: DataStorage) -> Self {
+        self.fallback_storage = Some(fallback_storage);
+        self
+    }
+
+    /// Precision of the floating point data of the fields, double precision by default.
+    ///
+    /// The coordinates of the mesh and streamed data are always written in double precision.
+    pub fn precision(mut self, precision: FloatPrecision) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Precision of the floating point data of a field, see [`TimeSeriesWriter::with_field_precision`].
+    pub fn field_precision(mut self, field_name: impl ToString, precision: FloatPrecision) -> Self {
+        self.field_precisions
+            .push((field_name.to_string(), precision));
+        self
+    }
+
+    /// Precision of the connectivity and the indices of sets, see [`TimeSeriesWriter::with_index_precision`].
+    pub fn index_precision(mut self, index_precision: IndexPrecision) -> Self {
+        self.index_precision = index_precision;
+        self
+    }
+
+    /// Write a field only at every N-th time step, see [`TimeSeriesWriter::with_field_output_frequency`].
+    pub fn field_output_frequency(
+        mut self,
+        field_name: impl ToString,
+        every_n_steps: usize,
+    ) -> Self {
+        self.field_output_frequencies
+            .push((field_name.to_string(), every_n_steps));
+        self
+    }
+
+    /// Write a field of the point-data also as cell-data, see [`TimeSeriesWriter::with_cell_average`].
+    pub fn cell_average(mut self, point_field: impl ToString, name: impl ToString) -> Self {
+        self.cell_averages
+            .push((point_field.to_string(), name.to_string()));
+        self
+    }
+
+    /// Write a field of the cell-data also as point-data, see [`TimeSeriesWriter::with_point_average`].
+    pub fn point_average(mut self, cell_field: impl ToString, name: impl ToString) -> Self {
+        self.point_averages
+            .push((cell_field.to_string(), name.to_string()));
+        self
+    }
+
+    /// Options for the HDF5 data storages, e.g. compression.
+    pub fn hdf5_options(mut self, hdf5_options: Hdf5Options) -> Self {
+        self.hdf5_options = hdf5_options;
+        self
+    }
+
+    /// Write the heavy data into a group of a shared HDF5 file in the directory, see [`TimeSeriesWriter::new_with_hdf5_group`].
+    ///
+    /// Requires the [`DataStorage::Hdf5SingleFile`] storage.
+    pub fn hdf5_group(mut self, h5_file_name: impl AsRef<Path>, group: impl ToString) -> Self {
+        self.hdf5_group = Some((h5_file_name.as_ref().to_path_buf(), group.to_string()));
+        self
+    }
+
+    /// Add custom information to the XDMF file, see [`TimeSeriesWriter::with_information`].
+    pub fn information(mut self, name: impl ToString, value: impl ToString) -> Self {
+        self.information.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Add custom information to a field, see [`TimeSeriesWriter::with_field_information`].
+    pub fn field_information(
+        mut self,
+        field_name: impl ToString,
+        name: impl ToString,
+        value: impl ToString,
+    ) -> Self {
+        self.field_information
+            .push((field_name.to_string(), name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Add the units and the description of a field, see [`TimeSeriesWriter::with_field_descriptor`].
+    pub fn field_descriptor(mut self, descriptor: &FieldDescriptor) -> Self {
+        self.field_information
+            .extend(descriptor.information().into_iter().map(|information| {
+                (
+                    descriptor.name().to_string(),
+                    information.name,
+                    information.value,
+                )
+            }));
+        self
+    }
+
+    /// Name the components of a vector, tensor or matrix field, see [`TimeSeriesWriter::with_component_names`].
+    pub fn component_names(
+        mut self,
+        field_name: impl ToString,
+        component_names: &[impl ToString],
+    ) -> Self {
+        let component_names: Vec<String> =
+            component_names.iter().map(ToString::to_string).collect();
+        self.field_information.push((
+            field_name.to_string(),
+            COMPONENT_NAMES_NAME.to_string(),
+            component_names.join(" "),
+        ));
+        self
+    }
+
+    /// Limit the size (in bytes) of each data array that is written inline, see [`TimeSeriesWriter::with_inline_size_limit`].
+    pub fn inline_size_limit(mut self, size_limit: usize) -> Self {
+        self.inline_size_limit = Some(size_limit);
+        self
+    }
+
+    /// How the paths to the files of the heavy data are written, see [`TimeSeriesWriter::with_path_policy`].
+    pub fn path_policy(mut self, path_policy: PathPolicy) -> Self {
+        self.path_policy = path_policy;
+        self
+    }
+
+    /// Set how often the XDMF file is rewritten, see [`TimeSeriesWriter::with_flush_policy`].
+    pub fn flush_policy(mut self, flush_policy: FlushPolicy) -> Self {
+        self.flush_policy = flush_policy;
+        self
+    }
+
+    /// Set which time steps are kept, see [`TimeSeriesWriter::with_retention_policy`].
+    pub fn retention_policy(mut self, retention_policy: RetentionPolicy) -> Self {
+        self.retention_policy = retention_policy;
+        self
+    }
+
+    /// Write the times of all time steps as one list, see [`TimeSeriesWriter::with_time_list`].
+    pub fn time_list(mut self, time_list: bool) -> Self {
+        self.time_list = time_list;
+        self
+    }
+
+    /// Write an additional XDMF file with only the most recent time step, see [`TimeSeriesWriter::with_latest_step_file`].
+    pub fn latest_step_file(mut self, latest_step_file: bool) -> Self {
+        self.latest_step_file = latest_step_file;
+        self
+    }
+
+    /// Split the time steps into multiple XDMF files, see [`TimeSeriesWriter::with_rollover`].
+    pub fn rollover(mut self, steps_per_file: usize) -> Self {
+        self.rollover = Some(steps_per_file);
+        self
+    }
+
+    /// Factor the times of the time steps are multiplied by, see [`TimeSeriesWriter::with_time_scale`].
+    pub fn time_scale(mut self, time_scale: f64) -> Self {
+        self.time_scale = Some(time_scale);
+        self
+    }
+
+    /// Unit of the times, written as `Information`, see [`TimeSeriesWriter::with_time_unit`].
+    pub fn time_unit(mut self, time_unit: impl ToString) -> Self {
+        self.time_unit = Some(time_unit.to_string());
+        self
+    }
+
+    /// Write symmetric tensors with the `AttributeType` `Matrix`, see [`TimeSeriesWriter::with_tensor6_as_matrix`].
+    pub fn tensor6_as_matrix(mut self, tensor6_as_matrix: bool) -> Self {
+        self.tensor6_as_matrix = tensor6_as_matrix;
+        self
+    }
+
+    /// Subdivide quadratic cells into linear cells, see [`TimeSeriesWriter::with_subdivide_quadratic`].
+    pub fn subdivide_quadratic(mut self, subdivide_quadratic: bool) -> Self {
+        self.subdivide_quadratic = subdivide_quadratic;
+        self
+    }
+
+    /// Write the minimum, maximum and mean of the values of each attribute, see [`TimeSeriesWriter::with_statistics`].
+    pub fn statistics(mut self, statistics: bool) -> Self {
+        self.statistics = statistics;
+        self
+    }
+
+    /// Write a checksum of the values of each attribute, see [`TimeSeriesWriter::with_checksums`].
+    pub fn checksums(mut self, checksums: bool) -> Self {
+        self.checksums = checksums;
+        self
+    }
+
+    /// Prefix of the names of all attributes in the XDMF file, see [`TimeSeriesWriter::with_field_name_prefix`].
+    pub fn field_name_prefix(mut self, name_prefix: impl ToString) -> Self {
+        self.field_name_prefix = name_prefix.to_string();
+        self
+    }
+
+    /// Handling of NaN or infinite values in the data of the fields, see [`TimeSeriesWriter::with_nan_policy`].
+    pub fn nan_policy(mut self, nan_policy: NanPolicy) -> Self {
+        self.nan_policy = nan_policy;
+        self
+    }
+
+    /// Handling of fields with the same name as point- and cell-data, see [`TimeSeriesWriter::with_name_collision_policy`].
+    pub fn name_collision_policy(mut self, policy: NameCollisionPolicy) -> Self {
+        self.name_collision_policy = policy;
+        self
+    }
+
+    /// How the grids refer to the shared `DataItems`, see [`TimeSeriesWriter::with_reference_style`].
+    pub fn reference_style(mut self, reference_style: ReferenceStyle) -> Self {
+        self.reference_style = reference_style;
+        self
+    }
+
+    /// Header at the beginning of the XDMF file, see [`TimeSeriesWriter::with_xml_header`].
+    pub fn xml_header(mut self, xml_header: XmlHeader) -> Self {
+        self.xml_header = xml_header;
+        self
+    }
+
+    /// Transform the coordinates of the points when writing the mesh, see [`TimeSeriesWriter::with_transform`].
+    pub fn transform(mut self, transform: Transform) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
+    /// Call a function after each write, see [`TimeSeriesWriter::with_progress_callback`].
+    pub fn progress_callback(
+        mut self,
+        callback: impl Fn(&WriteEvent) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress_callback = Some(BuilderCallback(Arc::new(callback)));
+        self
+    }
+
+    /// Create the `TimeSeriesWriter` with the chosen options.
+    pub fn build(self) -> XdmfResult<TimeSeriesWriter> {
+        let file_name = self.file_name()?;
+
+        let storage = match self.fallback_storage {
+            Some(fallback_storage) => select_data_storage(self.storage, fallback_storage)?,
+            None => self.storage,
+        };
+
+        let writer = match &self.hdf5_group {
+            Some((h5_file_name, group)) => {
+                if storage != DataStorage::Hdf5SingleFile {
+                    return Err(XdmfError::Validation(format!(
+                        "Writing into a group of an HDF5 file requires the Hdf5SingleFile DataStorage, but it is {storage:?}"
+                    )));
+                }
+
+                TimeSeriesWriter::new_with_hdf5_group(
+                    file_name,
+                    self.directory.join(h5_file_name),
+                    group,
+                )?
+            }
+            None => TimeSeriesWriter::new(file_name, storage)?,
+        };
+
+        self.configure(writer)
+    }
+
+    // path of the files without extension, after validating the options
+    pub(crate) fn file_name(&self) -> XdmfResult<PathBuf> {
+        let basename = self.basename.as_ref().ok_or_else(|| {
+            XdmfError::Validation("The basename of the files must be given".into())
+        })?;
+
+        // the directory is given separately, such that the basename is used as is for all files
+        if !matches!(
+            Path::new(&basename)
+                .components()
+                .collect::<Vec<_>>()
+                .as_slice(),
+            [Component::Normal(_)]
+        ) {
+            return Err(XdmfError::Validation(format!(
+                "Basename '{basename}' must be a plain file name, use directory() for its location"
+            )));
+        }
+
+        self.hdf5_options.validate()?;
+        if let Some(transform) = &self.transform {
+            transform.validate()?;
+        }
+
+        Ok(self.directory.join(basename))
+    }
+
+    // apply the options to a writer that was created for the file name of the builder, e.g. for a rank of a parallel simulation
+    pub(crate) fn configure(self, writer: TimeSeriesWriter) -> XdmfResult<TimeSeriesWriter> {
+        let mut writer = writer
+            .with_path_policy(self.path_policy)
+            .with_flush_policy(self.flush_policy)
+            .with_retention_policy(self.retention_policy)
+            .with_latest_step_file(self.latest_step_file)
+            .with_statistics(self.statistics)
+            .with_checksums(self.checksums)
+            .with_field_name_prefix(self.field_name_prefix)
+            .with_nan_policy(self.nan_policy)
+            .with_name_collision_policy(self.name_collision_policy)
+            .with_reference_style(self.reference_style)
+            .with_xml_header(self.xml_header)
+            .with_subdivide_quadratic(self.subdivide_quadratic);
+
+        if let Some(size_limit) = self.inline_size_limit {
+            writer = writer.with_inline_size_limit(size_limit);
+        }
+
+        if self.time_list {
+            writer = writer.with_time_list();
+        }
+
+        if let Some(steps_per_file) = self.rollover {
+            writer = writer.with_rollover(steps_per_file);
+        }
+
+        if let Some(time_scale) = self.time_scale {
+            writer = writer.with_time_scale(time_scale);
+        }
+
+        if let Some(time_unit) = self.time_unit {
+            writer = writer.with_time_unit(time_unit);
+        }
+
+        if self.tensor6_as_matrix {
+            writer = writer.with_tensor6_as_matrix();
+        }
+
+        if let Some(transform) = self.transform {
+            writer = writer.with_transform(transform);
+        }
+
+        for (name, value) in self.information {
+            writer = writer.with_information(name, value);
+        }
+
+        for (field_name, name, value) in self.field_information {
+            writer = writer.with_field_information(field_name, name, value);
+        }
+
+        for (field_name, precision) in self.field_precisions {
+            writer = writer.with_field_precision(field_name, precision);
+        }
+
+        for (field_name, every_n_steps) in self.field_output_frequencies {
+            writer = writer.with_field_output_frequency(field_name, every_n_steps);
+        }
+
+        for (point_field, name) in self.cell_averages {
+            writer = writer.with_cell_average(point_field, name);
+        }
+
+        for (cell_field, name) in self.point_averages {
+            writer = writer.with_point_average(cell_field, name);
+        }
+
+        if let Some(BuilderCallback(callback)) = self.progress_callback {
+            writer = writer.with_progress_callback(move |event| callback(event));
+        }
+
+        writer = writer.with_index_precision(self.index_precision);
+        writer.set_float_precision(self.precision);
+        writer.set_hdf5_options(&self.hdf5_options)?;
+
+        Ok(writer)
+    }
+}
@@ -0,0 +1,178 @@
+//! This module contains the affine transformation of the coordinates, which is applied when the mesh is written.
+
+use crate::{XdmfError, XdmfResult};
+
+/// Affine transformation of the coordinates of the points, applied when the mesh is written.
+///
+/// A point `p` is written as `scale * matrix * p + offset`, e.g. to convert the coordinates from millimeters to meters,
+/// or to move the partition of a rank into the global frame. The coordinates of the caller are not modified.
+/// Only the coordinates are transformed, the values of the fields (e.g. vectors) are written as given.
+/// ```rust
+/// // convert from millimeters to meters and move the mesh by 1m in y direction
+/// let transform = xdmf::Transform::default()
+///     .with_scale(1e-3)
+///     .with_offset([0.0, 1.0, 0.0]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    scale: f64,
+    matrix: [[f64; 3]; 3],
+    offset: [f64; 3],
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            offset: [0.0; 3],
+        }
+    }
+}
+
+impl Transform {
+    /// Scale the coordinates by the given factor, which must not be zero.
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Multiply the coordinates with the given matrix (in row-major order), e.g. a rotation. Applied together with the scale.
+    pub fn with_matrix(mut self, matrix: [[f64; 3]; 3]) -> Self {
+        self.matrix = matrix;
+        self
+    }
+
+    /// Translate the coordinates by the given offset, which is added after scaling and multiplying with the matrix.
+    pub fn with_offset(mut self, offset: [f64; 3]) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    // transform a single point
+    pub(crate) fn apply(&self, point: [f64; 3]) -> [f64; 3] {
+        std::array::from_fn(|i| {
+            let row = self.matrix[i];
+            self.scale * (row[0] * point[0] + row[1] * point[1] + row[2] * point[2])
+                + self.offset[i]
+        })
+    }
+
+    // transform interleaved coordinates (x0, y0, z0, x1, y1, z1, ...)
+    pub(crate) fn apply_interleaved(&self, points: &[f64]) -> Vec<f64> {
+        points
+            .chunks_exact(3)
+            .flat_map(|point| self.apply([point[0], point[1], point[2]]))
+            .collect()
+    }
+
+    // transform separate arrays of the x, y and z coordinates
+    pub(crate) fn apply_separate(&self, [x, y, z]: [&[f64]; 3]) -> [Vec<f64>; 3] {
+        let mut transformed = [
+            Vec::with_capacity(x.len()),
+            Vec::with_capacity(y.len()),
+            Vec::with_capacity(z.len()),
+        ];
+
+        for ((x, y), z) in x.iter().zip(y).zip(z) {
+            for (coordinates, value) in transformed.iter_mut().zip(self.apply([*x, *y, *z])) {
+                coordinates.push(value);
+            }
+        }
+
+        transformed
+    }
+
+    // scaling factors per direction, if the transformation does not rotate or shear the coordinates
+    pub(crate) fn axis_scales(&self) -> Option<[f64; 3]> {
+        let is_diagonal = (0..3).all(|i| (0..3).all(|j| i == j || self.matrix[i][j] == 0.0));
+
+        is_diagonal.then(|| std::array::from_fn(|i| self.scale * self.matrix[i][i]))
+    }
+
+    // the transformation must be invertible and finite, otherwise the mesh would be degenerated
+    pub(crate) fn validate(&self) -> XdmfResult<()> {
+        if !self
+            .matrix
+            .iter()
+            .flatten()
+            .chain(&self.offset)
+            .chain([&self.scale])
+            .all(|value| value.is_finite())
+        {
+            return Err(XdmfError::Validation(format!(
+                "Transformation must consist of finite values, but is {self:?}"
+            )));
+        }
+
+        let [a, b, c] = self.matrix;
+        let determinant = a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+            + a[2] * (b[0] * c[1] - b[1] * c[0]);
+
+        if self.scale == 0.0 || determinant == 0.0 {
+            return Err(XdmfError::Validation(format!(
+                "Transformation must be invertible, but is {self:?}"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    fn transform_points() {
+        let transform = Transform::default()
+            .with_scale(2.0)
+            .with_matrix([[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]])
+            .with_offset([1.0, 0.0, -1.0]);
+
+        assert_approx_eq!(&[f64], &transform.apply([1.0, 2.0, 3.0]), &[-3.0, 2.0, 5.0]);
+        assert_approx_eq!(
+            &[f64],
+            &transform.apply_interleaved(&[1.0, 2.0, 3.0, 0.0, 0.0, 0.0]),
+            &[-3.0, 2.0, 5.0, 1.0, 0.0, -1.0]
+        );
+
+        let [x, y, z] = transform.apply_separate([&[1.0, 0.0], &[2.0, 0.0], &[3.0, 0.0]]);
+        assert_approx_eq!(&[f64], &x, &[-3.0, 1.0]);
+        assert_approx_eq!(&[f64], &y, &[2.0, 0.0]);
+        assert_approx_eq!(&[f64], &z, &[5.0, -1.0]);
+
+        assert!(transform.axis_scales().is_none());
+        let scales = Transform::default()
+            .with_scale(1e-3)
+            .with_matrix([[1.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, -1.0]])
+            .axis_scales()
+            .unwrap();
+        assert_approx_eq!(&[f64], &scales, &[1e-3, 2e-3, -1e-3]);
+    }
+
+    #[test]
+    fn transform_validate() {
+        Transform::default().validate().unwrap();
+
+        assert_eq!(
+            Transform::default()
+                .with_scale(0.0)
+                .validate()
+                .unwrap_err()
+                .to_string(),
+            "Transformation must be invertible, but is Transform { scale: 0.0, matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]], offset: [0.0, 0.0, 0.0] }"
+        );
+
+        Transform::default()
+            .with_matrix([[1.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]])
+            .validate()
+            .unwrap_err();
+        Transform::default()
+            .with_offset([f64::NAN, 0.0, 0.0])
+            .validate()
+            .unwrap_err();
+    }
+}
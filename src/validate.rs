@@ -0,0 +1,1746 @@
+//! Validation of XDMF files, e.g. to check the output of simulations in CI.
+//!
+//! The light data is checked for consistency (dimensions, number types and precisions, references, sizes of attributes),
+//! as well as the heavy data it points to (included text files, also compressed with gzip, and HDF5 datasets).
+//! HDF5 datasets are only opened if the `hdf5` feature is enabled, otherwise only the existence of the files is checked.
+//! ```rust
+//! # use xdmf::TimeSeriesWriter;
+//! TimeSeriesWriter::new("validated_xdmf_file", xdmf::DataStorage::Ascii)
+//!     .expect("failed to create XDMF writer")
+//!     .write_mesh(&[0.0; 3], (&[], &[]))
+//!     .expect("failed to write mesh");
+//!
+//! let report = xdmf::validate::validate_file("validated_xdmf_file.xdmf2").expect("failed to read file");
+//!
+//! for issue in report.issues() {
+//!     println!("{issue}");
+//! }
+//! assert!(report.is_valid());
+//! # std::fs::remove_file("validated_xdmf_file.xdmf2").expect("failed to remove xdmf file");
+//! # std::fs::remove_dir_all("validated_xdmf_file.txt").expect("failed to remove txt files");
+//! ```
+
+use std::{
+    collections::HashSet,
+    fmt,
+    fs::File,
+    io::{BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use flate2::read::GzDecoder;
+use xxhash_rust::xxh64::xxh64;
+
+use crate::{
+    XdmfResult, base64_writer,
+    time_series_writer::function_arguments,
+    values::{CHECKSUM_NAME, CHECKSUM_PREFIX},
+    xdmf_elements::{
+        Domain, Xdmf,
+        attribute::{Attribute, AttributeType, Center},
+        data_item::{
+            DataContent, DataItem, Encoding, Format, ItemType, NumberType, Reference, XInclude,
+        },
+        dimensions::Dimensions,
+        geometry::{Geometry, GeometryType},
+        grid::{CollectionType, Grid, GridType, Section, Time, TimeType},
+        set::{Set, SetType},
+        topology::{Topology, TopologyType},
+    },
+};
+
+/// Severity of an [`Issue`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The file can be read, but might not be what was intended.
+    Warning,
+    /// The file is inconsistent and can not be read correctly.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single finding of the validation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Issue {
+    /// Severity of the issue
+    pub severity: Severity,
+    /// Location of the issue within the XDMF structure, e.g. `/Xdmf/Domain/Grid[@Name="mesh"]/Geometry`
+    pub location: String,
+    /// Description of the issue
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.location, self.message)
+    }
+}
+
+/// Result of the validation, a list of all issues that were found.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    /// All issues, in the order in which they were found.
+    pub fn issues(&self) -> &[Issue] {
+        &self.issues
+    }
+
+    /// Issues with [`Severity::Error`].
+    pub fn errors(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+    }
+
+    /// Issues with [`Severity::Warning`].
+    pub fn warnings(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Warning)
+    }
+
+    /// Whether no errors were found. Warnings are allowed.
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+}
+
+/// Validate an XDMF file on disk.
+///
+/// Files of the heavy data are searched relative to the directory of the XDMF file.
+/// Errors are only returned if the file can not be read or parsed, issues with its content are part of the report.
+pub fn validate_file(file_name: impl AsRef<Path>) -> XdmfResult<ValidationReport> {
+    let file_name = file_name.as_ref();
+    let xdmf = Xdmf::read_from(BufReader::new(File::open(file_name)?))?;
+
+    Ok(validate(
+        &xdmf,
+        file_name.parent().unwrap_or_else(|| Path::new("")),
+    ))
+}
+
+/// Validate an `Xdmf` structure.
+///
+/// Files of the heavy data are searched relative to `base_dir`, which usually is the directory of the XDMF file.
+pub fn validate(xdmf: &Xdmf, base_dir: impl AsRef<Path>) -> ValidationReport {
+    let mut validator = Validator {
+        base_dir: base_dir.as_ref().to_path_buf(),
+        issues: Vec::new(),
+    };
+
+    if xdmf.domains.is_empty() {
+        validator.error("/Xdmf", "No Domain is defined");
+    }
+
+    for domain in &xdmf.domains {
+        validator.check_domain(domain, "/Xdmf/Domain");
+    }
+
+    ValidationReport {
+        issues: validator.issues,
+    }
+}
+
+/// Verify the checksums of the attributes of an XDMF file on disk, see [`TimeSeriesWriter::with_checksums`](crate::TimeSeriesWriter::with_checksums).
+///
+/// The values of each attribute with a checksum are read from the heavy data and hashed again, a mismatch is reported as error.
+/// Attributes without checksum are skipped. The values of HDF5 datasets can only be read if the `hdf5` feature is enabled.
+/// ```rust
+/// # use xdmf::TimeSeriesWriter;
+/// let point_data = vec![(
+///     "temperature".to_string(),
+///     (xdmf::DataAttribute::Scalar, vec![20.0].into()),
+/// )]
+/// .into_iter()
+/// .collect();
+///
+/// TimeSeriesWriter::new("verified_xdmf_file", xdmf::DataStorage::Ascii)
+///     .expect("failed to create XDMF writer")
+///     .with_checksums(true)
+///     .write_mesh(&[0.0; 3], (&[], &[]))
+///     .expect("failed to write mesh")
+///     .write_data("0.0", Some(&point_data), None)
+///     .expect("failed to write data");
+///
+/// let report = xdmf::validate::verify_checksums_file("verified_xdmf_file.xdmf2")
+///     .expect("failed to read file");
+/// assert!(report.issues().is_empty());
+/// # std::fs::remove_file("verified_xdmf_file.xdmf2").expect("failed to remove xdmf file");
+/// # std::fs::remove_dir_all("verified_xdmf_file.txt").expect("failed to remove txt files");
+/// ```
+pub fn verify_checksums_file(file_name: impl AsRef<Path>) -> XdmfResult<ValidationReport> {
+    let file_name = file_name.as_ref();
+    let xdmf = Xdmf::read_from(BufReader::new(File::open(file_name)?))?;
+
+    Ok(verify_checksums(
+        &xdmf,
+        file_name.parent().unwrap_or_else(|| Path::new("")),
+    ))
+}
+
+/// Verify the checksums of the attributes of an `Xdmf` structure.
+///
+/// Files of the heavy data are searched relative to `base_dir`, see [`verify_checksums_file`] for details.
+pub fn verify_checksums(xdmf: &Xdmf, base_dir: impl AsRef<Path>) -> ValidationReport {
+    let mut validator = Validator {
+        base_dir: base_dir.as_ref().to_path_buf(),
+        issues: Vec::new(),
+    };
+
+    for domain in &xdmf.domains {
+        for grid in &domain.grids {
+            validator.verify_grid_checksums(grid, &grid_location("/Xdmf/Domain", grid));
+        }
+    }
+
+    ValidationReport {
+        issues: validator.issues,
+    }
+}
+
+struct Validator {
+    base_dir: PathBuf,
+    issues: Vec<Issue>,
+}
+
+impl Validator {
+    fn error(&mut self, location: &str, message: impl ToString) {
+        self.issue(Severity::Error, location, message);
+    }
+
+    fn warning(&mut self, location: &str, message: impl ToString) {
+        self.issue(Severity::Warning, location, message);
+    }
+
+    fn issue(&mut self, severity: Severity, location: &str, message: impl ToString) {
+        self.issues.push(Issue {
+            severity,
+            location: location.to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    fn check_domain(&mut self, domain: &Domain, location: &str) {
+        for data_item in &domain.data_items {
+            let item_location = data_item_location(location, data_item);
+            self.check_data_item(domain, data_item, &item_location);
+        }
+
+        for include in &domain.includes {
+            self.check_include_exists(include, location);
+        }
+
+        for grid in &domain.grids {
+            self.check_grid(domain, grid, &grid_location(location, grid));
+        }
+    }
+
+    fn check_grid(&mut self, domain: &Domain, grid: &Grid, location: &str) {
+        for include in grid.includes.iter().flatten() {
+            self.check_include_exists(include, location);
+        }
+
+        match grid.grid_type {
+            GridType::Uniform => self.check_uniform_grid(domain, grid, location),
+            GridType::Collection | GridType::Tree => {
+                let sub_grids = grid.grids.as_deref().unwrap_or_default();
+
+                if grid.collection_type == Some(CollectionType::Temporal) {
+                    self.check_times(domain, grid, sub_grids, location);
+                }
+
+                for sub_grid in sub_grids {
+                    self.check_grid(domain, sub_grid, &grid_location(location, sub_grid));
+                }
+            }
+            GridType::SubSet => self.check_subset(domain, grid, location),
+        }
+    }
+
+    fn check_subset(&mut self, domain: &Domain, grid: &Grid, location: &str) {
+        // the referenced grid is given as the only child grid
+        let [reference] = grid.grids.as_deref().unwrap_or_default() else {
+            self.error(
+                location,
+                "Grid of type SubSet must reference exactly one Grid",
+            );
+            return;
+        };
+        self.check_grid_reference(domain, reference, &format!("{location}/Grid"));
+
+        let num_cells = match grid.section.unwrap_or_default() {
+            Section::DataItem => match &grid.data_item {
+                Some(data_item) => {
+                    self.check_data_item(domain, data_item, &format!("{location}/DataItem"))
+                }
+                None => {
+                    self.error(
+                        location,
+                        "Grid of type SubSet with Section DataItem has no DataItem",
+                    );
+                    None
+                }
+            },
+            // the number of cells of the referenced grid is not determined
+            Section::All => None,
+        };
+
+        for attribute in grid.attributes.iter().flatten() {
+            let num_entities = match attribute.center {
+                Center::Cell => num_cells,
+                Center::Grid => Some(1),
+                Center::Node | Center::Edge | Center::Face | Center::Other => None,
+            };
+
+            self.check_attribute(
+                domain,
+                attribute,
+                num_entities,
+                &format!("{location}/Attribute[@Name=\"{}\"]", attribute.name),
+            );
+        }
+    }
+
+    fn check_grid_reference(&mut self, domain: &Domain, reference: &Grid, location: &str) {
+        let Some(reference_type) = &reference.reference else {
+            self.error(location, "Grid referenced by a SubSet has no Reference");
+            return;
+        };
+
+        let path = reference
+            .reference_path
+            .as_deref()
+            .unwrap_or_default()
+            .trim();
+
+        // only paths selecting grids by their names are resolved, as written by this crate
+        let names = grid_path_names(path);
+        let (Some(names), "XML") = (names, reference_type.as_str()) else {
+            self.warning(location, format!("Reference '{path}' is not checked"));
+            return;
+        };
+
+        let mut grids = domain.grids.as_slice();
+        let mut target = None;
+        for name in names {
+            let Some(grid) = grids.iter().find(|grid| grid.name == name) else {
+                self.error(location, format!("Reference '{path}' can not be resolved"));
+                return;
+            };
+            grids = grid.grids.as_deref().unwrap_or_default();
+            target = Some(grid);
+        }
+
+        if let Some(target) = target
+            && target.grid_type != GridType::Uniform
+        {
+            self.error(
+                location,
+                format!("Reference '{path}' must refer to a uniform grid"),
+            );
+        }
+    }
+
+    fn check_times(&mut self, domain: &Domain, collection: &Grid, grids: &[Grid], location: &str) {
+        // the times of all grids are given as a list in the collection
+        if let Some(time) = &collection.time
+            && time.time_type == Some(TimeType::List)
+        {
+            self.check_time_list(domain, time, grids.len(), &format!("{location}/Time"));
+            return;
+        }
+
+        let mut times = HashSet::new();
+
+        for grid in grids {
+            match grid.time.as_ref().and_then(|time| time.value.as_deref()) {
+                None => self.warning(
+                    &grid_location(location, grid),
+                    "Grid in a temporal collection has no Time",
+                ),
+                Some(value) if !times.insert(value) => self.warning(
+                    &grid_location(location, grid),
+                    format!("Time '{value}' is used by multiple grids"),
+                ),
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn check_time_list(&mut self, domain: &Domain, time: &Time, num_grids: usize, location: &str) {
+        let Some(data_item) = &time.data_item else {
+            self.error(location, "Time of TimeType List has no DataItem");
+            return;
+        };
+
+        if let Some(num_values) =
+            self.check_data_item(domain, data_item, &format!("{location}/DataItem"))
+            && num_values != num_grids
+        {
+            self.error(
+                location,
+                format!(
+                    "Number of times ({num_values}) does not match the number of grids ({num_grids})"
+                ),
+            );
+        }
+    }
+
+    fn check_uniform_grid(&mut self, domain: &Domain, grid: &Grid, location: &str) {
+        let num_points = match &grid.geometry {
+            Some(geometry) => {
+                self.check_geometry(domain, geometry, &format!("{location}/Geometry"))
+            }
+            None => {
+                self.error(location, "Uniform grid has no Geometry");
+                None
+            }
+        };
+
+        let num_cells = match &grid.topology {
+            Some(topology) => {
+                self.check_topology(domain, topology, &format!("{location}/Topology"))
+            }
+            None => {
+                self.error(location, "Uniform grid has no Topology");
+                None
+            }
+        };
+
+        // the points of structured topologies are not listed by the geometry
+        let num_points = num_points.or_else(|| structured_num_points(grid.topology.as_ref()?));
+
+        let mut names = HashSet::new();
+        for attribute in grid.attributes.iter().flatten() {
+            let attribute_location = format!("{location}/Attribute[@Name=\"{}\"]", attribute.name);
+
+            if !names.insert((attribute.name.as_str(), attribute.center as u8)) {
+                self.warning(
+                    &attribute_location,
+                    "Attribute with the same name and center is defined multiple times",
+                );
+            }
+
+            let num_entities = match attribute.center {
+                Center::Node => num_points,
+                Center::Cell => num_cells,
+                Center::Grid => Some(1),
+                Center::Edge | Center::Face | Center::Other => None,
+            };
+
+            self.check_attribute(domain, attribute, num_entities, &attribute_location);
+        }
+
+        for set in grid.sets.iter().flatten() {
+            self.check_set(
+                domain,
+                set,
+                &format!("{location}/Set[@Name=\"{}\"]", set.name),
+            );
+        }
+    }
+
+    fn check_set(&mut self, domain: &Domain, set: &Set, location: &str) {
+        // faces and edges are identified by the cell and their local index within the cell
+        let num_data_items = match set.set_type {
+            SetType::Node | SetType::Cell => 1,
+            SetType::Face | SetType::Edge => 2,
+        };
+
+        if set.data_items.len() != num_data_items {
+            self.error(
+                location,
+                format!(
+                    "Set of SetType {:?} must have {num_data_items} DataItems, but has {}",
+                    set.set_type,
+                    set.data_items.len()
+                ),
+            );
+        }
+
+        let num_values: Vec<_> = set
+            .data_items
+            .iter()
+            .map(|data_item| {
+                self.check_data_item(domain, data_item, &format!("{location}/DataItem"))
+            })
+            .collect();
+
+        // the attributes of the set have one entry per index of the set, given by its first DataItem
+        let num_entities = num_values.first().copied().flatten();
+
+        for attribute in &set.attributes {
+            self.check_attribute(
+                domain,
+                attribute,
+                num_entities,
+                &format!("{location}/Attribute[@Name=\"{}\"]", attribute.name),
+            );
+        }
+    }
+
+    // returns the number of cells, if it could be determined
+    // returns the number of points
+    fn check_geometry(
+        &mut self,
+        domain: &Domain,
+        geometry: &Geometry,
+        location: &str,
+    ) -> Option<usize> {
+        let geometry_type = geometry.geometry_type;
+
+        if geometry.data_items.len() != geometry_type.num_data_items() {
+            self.error(
+                location,
+                format!(
+                    "GeometryType {geometry_type:?} requires {} DataItems, but has {}",
+                    geometry_type.num_data_items(),
+                    geometry.data_items.len()
+                ),
+            );
+            return None;
+        }
+
+        let num_values: Vec<Option<usize>> = geometry
+            .data_items
+            .iter()
+            .enumerate()
+            .map(|(i, data_item)| {
+                let item_location = if geometry_type.num_data_items() == 1 {
+                    format!("{location}/DataItem")
+                } else {
+                    format!("{location}/DataItem[{}]", i + 1)
+                };
+                self.check_data_item(domain, data_item, &item_location)
+            })
+            .collect();
+        let num_values: Vec<usize> = num_values.into_iter().collect::<Option<_>>()?;
+
+        match geometry_type {
+            GeometryType::XYZ | GeometryType::XY => {
+                let dim = if geometry_type == GeometryType::XYZ {
+                    3
+                } else {
+                    2
+                };
+
+                if num_values[0].is_multiple_of(dim) {
+                    Some(num_values[0] / dim)
+                } else {
+                    self.error(
+                        location,
+                        format!(
+                            "Number of values ({}) must be a multiple of {dim} for GeometryType {geometry_type:?}",
+                            num_values[0]
+                        ),
+                    );
+                    None
+                }
+            }
+            GeometryType::SeparateXYZ => {
+                if num_values.iter().all(|num| *num == num_values[0]) {
+                    Some(num_values[0])
+                } else {
+                    self.error(
+                        location,
+                        format!(
+                            "The DataItems of GeometryType {geometry_type:?} must have the same number of values, but have {num_values:?}"
+                        ),
+                    );
+                    None
+                }
+            }
+            // the points of a rectilinear grid are all combinations of the coordinates of the axes
+            GeometryType::VXVYVZ => Some(num_values.iter().product()),
+            // origin and spacing, the number of points is defined by the topology
+            GeometryType::OriginDxDyDz => {
+                if num_values.iter().any(|num| *num != 3) {
+                    self.error(
+                        location,
+                        format!(
+                            "The DataItems of GeometryType {geometry_type:?} must have 3 values, but have {num_values:?}"
+                        ),
+                    );
+                }
+                None
+            }
+        }
+    }
+
+    fn check_topology(
+        &mut self,
+        domain: &Domain,
+        topology: &Topology,
+        location: &str,
+    ) -> Option<usize> {
+        if topology.topology_type.is_structured() {
+            return self.check_structured_topology(topology, location);
+        }
+
+        let Some(data_item) = &topology.data_item else {
+            self.error(
+                location,
+                format!(
+                    "TopologyType {:?} requires a DataItem",
+                    topology.topology_type
+                ),
+            );
+            return None;
+        };
+
+        let num_values = self.check_data_item(domain, data_item, &format!("{location}/DataItem"));
+
+        let Ok(num_cells) = topology.number_of_elements.parse::<usize>() else {
+            self.error(
+                location,
+                format!(
+                    "NumberOfElements '{}' is not a valid number",
+                    topology.number_of_elements
+                ),
+            );
+            return None;
+        };
+
+        // the size of mixed topologies depends on the cell types, which would require reading the data
+        if let (Some(num_values), Some(nodes_per_element)) =
+            (num_values, nodes_per_element(topology.topology_type))
+            && num_values != num_cells * nodes_per_element
+        {
+            self.error(
+                location,
+                format!(
+                    "Number of values ({num_values}) does not match {num_cells} elements of TopologyType {:?} with {nodes_per_element} nodes each",
+                    topology.topology_type
+                ),
+            );
+        }
+
+        Some(num_cells)
+    }
+
+    // returns the number of cells of a structured topology, defined by the number of points per direction
+    fn check_structured_topology(&mut self, topology: &Topology, location: &str) -> Option<usize> {
+        match &topology.dimensions {
+            Some(Dimensions(dimensions)) if dimensions.len() == 3 => Some(
+                dimensions
+                    .iter()
+                    .map(|num_points| num_points.saturating_sub(1))
+                    .product(),
+            ),
+            _ => {
+                self.error(
+                    location,
+                    format!(
+                        "TopologyType {:?} requires Dimensions with 3 values",
+                        topology.topology_type
+                    ),
+                );
+                None
+            }
+        }
+    }
+
+    fn check_attribute(
+        &mut self,
+        domain: &Domain,
+        attribute: &Attribute,
+        num_entities: Option<usize>,
+        location: &str,
+    ) {
+        let [data_item] = attribute.data_items.as_slice() else {
+            self.error(
+                location,
+                format!(
+                    "Attribute must have exactly one DataItem, but has {}",
+                    attribute.data_items.len()
+                ),
+            );
+            return;
+        };
+
+        let num_values = self.check_data_item(domain, data_item, &format!("{location}/DataItem"));
+
+        let (Some(num_values), Some(num_entities)) = (num_values, num_entities) else {
+            return;
+        };
+
+        let matches = match num_components(attribute.attribute_type) {
+            Some(num_components) => num_values == num_entities * num_components,
+            None => num_entities > 0 && num_values % num_entities == 0,
+        };
+
+        if !matches {
+            self.error(
+                location,
+                format!(
+                    "Number of values ({num_values}) does not match {num_entities} entities with Center {:?} and AttributeType {:?}",
+                    attribute.center, attribute.attribute_type
+                ),
+            );
+        }
+    }
+
+    // returns the number of values, if it could be determined
+    fn check_data_item(
+        &mut self,
+        domain: &Domain,
+        data_item: &DataItem,
+        location: &str,
+    ) -> Option<usize> {
+        if let Some(reference) = &data_item.reference {
+            return self.check_reference(domain, data_item, reference, location);
+        }
+
+        if data_item.item_type == Some(ItemType::Function) {
+            return self.check_function(domain, data_item, location);
+        }
+
+        if data_item.item_type == Some(ItemType::HyperSlab) {
+            return self.check_hyperslab(domain, data_item, location);
+        }
+
+        if data_item.item_type == Some(ItemType::Coordinates) {
+            return self.check_coordinates(domain, data_item, location);
+        }
+
+        let number_type = data_item.number_type.unwrap_or_default();
+        let precision = data_item.precision.unwrap_or(4);
+
+        if !valid_precisions(number_type).contains(&precision) {
+            self.error(
+                location,
+                format!("Precision {precision} is not valid for NumberType {number_type:?}"),
+            );
+        }
+
+        let Some(dimensions) = &data_item.dimensions else {
+            self.error(location, "DataItem has no Dimensions");
+            return None;
+        };
+
+        let num_values = dimensions.0.iter().product();
+
+        match (data_item.format.unwrap_or_default(), &data_item.data) {
+            (Format::XML, DataContent::Raw(text))
+                if data_item.encoding == Some(Encoding::Base64) =>
+            {
+                self.check_base64(text, precision, num_values, location);
+            }
+            (Format::XML, DataContent::Raw(text)) => {
+                self.check_values(text, number_type, num_values, location);
+            }
+            (Format::XML, DataContent::Include(include)) => {
+                if include.is_text() {
+                    if let Some(text) = self.read_include(include, location) {
+                        self.check_values(&text, number_type, num_values, location);
+                    }
+                } else {
+                    self.check_include_exists(include, location);
+                }
+            }
+            (Format::HDF, DataContent::Raw(text)) => self.check_hdf5(text, num_values, location),
+            (Format::HDF, DataContent::Include(_)) => {
+                self.error(location, "HDF data must be given as 'file:path/to/dataset'");
+            }
+            (Format::Binary, _) => self.warning(location, "Binary data is not checked"),
+        }
+
+        Some(num_values)
+    }
+
+    fn check_function(
+        &mut self,
+        domain: &Domain,
+        data_item: &DataItem,
+        location: &str,
+    ) -> Option<usize> {
+        for (i, child) in data_item.data_items.iter().enumerate() {
+            self.check_data_item(domain, child, &format!("{location}/DataItem[{}]", i + 1));
+        }
+
+        let Some(function) = &data_item.function else {
+            self.error(location, "Function DataItem has no Function");
+            return None;
+        };
+
+        let num_arguments = data_item.data_items.len();
+        if let Some(index) = function_arguments(function).find(|index| *index >= num_arguments) {
+            self.error(
+                location,
+                format!(
+                    "Function refers to ${index}, but only {num_arguments} DataItems are given"
+                ),
+            );
+        }
+
+        let Some(dimensions) = &data_item.dimensions else {
+            self.error(location, "DataItem has no Dimensions");
+            return None;
+        };
+
+        Some(dimensions.0.iter().product())
+    }
+
+    // the first DataItem selects the values (start, stride and count per dimension) of the second DataItem
+    fn check_hyperslab(
+        &mut self,
+        domain: &Domain,
+        data_item: &DataItem,
+        location: &str,
+    ) -> Option<usize> {
+        let [selection, source] = data_item.data_items.as_slice() else {
+            self.error(
+                location,
+                format!(
+                    "HyperSlab DataItem must have exactly two DataItems, but has {}",
+                    data_item.data_items.len()
+                ),
+            );
+            return None;
+        };
+
+        let num_selection_values =
+            self.check_data_item(domain, selection, &format!("{location}/DataItem[1]"));
+        let rank = source
+            .dimensions
+            .as_ref()
+            .map(|dimensions| dimensions.0.len());
+        self.check_data_item(domain, source, &format!("{location}/DataItem[2]"));
+
+        let Some(dimensions) = &data_item.dimensions else {
+            self.error(location, "DataItem has no Dimensions");
+            return None;
+        };
+
+        let num_values = dimensions.0.iter().product();
+
+        if let (Some(num_selection_values), Some(rank)) = (num_selection_values, rank)
+            && num_selection_values != 3 * rank
+        {
+            self.error(
+                location,
+                format!(
+                    "HyperSlab selection has {num_selection_values} values, but must have 3 for each of the {rank} dimensions"
+                ),
+            );
+        } else if let DataContent::Raw(text) = &selection.data
+            && let Ok(selection) = text
+                .split_whitespace()
+                .map(str::parse::<usize>)
+                .collect::<Result<Vec<_>, _>>()
+            && let Some(counts) = selection.chunks(selection.len() / 3).nth(2)
+            && counts.iter().product::<usize>() != num_values
+        {
+            self.error(
+                location,
+                format!(
+                    "Number of selected values ({}) does not match the Dimensions ({num_values})",
+                    counts.iter().product::<usize>()
+                ),
+            );
+        }
+
+        Some(num_values)
+    }
+
+    // the first DataItem holds the index per dimension of each value of the second DataItem that is selected
+    fn check_coordinates(
+        &mut self,
+        domain: &Domain,
+        data_item: &DataItem,
+        location: &str,
+    ) -> Option<usize> {
+        let [coordinates, source] = data_item.data_items.as_slice() else {
+            self.error(
+                location,
+                format!(
+                    "Coordinates DataItem must have exactly two DataItems, but has {}",
+                    data_item.data_items.len()
+                ),
+            );
+            return None;
+        };
+
+        let num_coordinate_values =
+            self.check_data_item(domain, coordinates, &format!("{location}/DataItem[1]"));
+        let rank = source
+            .dimensions
+            .as_ref()
+            .map(|dimensions| dimensions.0.len());
+        self.check_data_item(domain, source, &format!("{location}/DataItem[2]"));
+
+        let Some(dimensions) = &data_item.dimensions else {
+            self.error(location, "DataItem has no Dimensions");
+            return None;
+        };
+
+        let num_values = dimensions.0.iter().product();
+
+        // the rank of a referenced source is not known
+        if let (Some(num_coordinate_values), Some(rank)) = (num_coordinate_values, rank)
+            && num_coordinate_values != num_values * rank
+        {
+            self.error(
+                location,
+                format!(
+                    "Coordinates have {num_coordinate_values} values, but must have {rank} for each of the {num_values} selected values"
+                ),
+            );
+        }
+
+        Some(num_values)
+    }
+
+    fn check_reference(
+        &mut self,
+        domain: &Domain,
+        data_item: &DataItem,
+        reference: &str,
+        location: &str,
+    ) -> Option<usize> {
+        let DataContent::Raw(path) = &data_item.data else {
+            self.error(location, "Reference must be given as text");
+            return None;
+        };
+
+        if reference != "XML" {
+            self.warning(location, format!("Reference '{reference}' is not checked"));
+            return None;
+        }
+
+        // only references to named DataItems of the domain are resolved, as written by this crate
+        let Some(reference) = Reference::from_xpath(path) else {
+            self.warning(location, format!("Reference '{path}' is not checked"));
+            return None;
+        };
+
+        let Some(target) = domain
+            .data_items
+            .iter()
+            .find(|item| item.name.as_deref() == Some(reference.name()))
+        else {
+            self.error(location, format!("Reference '{path}' can not be resolved"));
+            return None;
+        };
+
+        if target.reference.is_some() {
+            self.warning(
+                location,
+                format!("Reference '{path}' refers to another reference"),
+            );
+            return None;
+        }
+
+        // the target itself is checked as part of the domain
+        target
+            .dimensions
+            .as_ref()
+            .map(|dimensions| dimensions.0.iter().product())
+    }
+
+    fn check_values(
+        &mut self,
+        text: &str,
+        number_type: NumberType,
+        num_values: usize,
+        location: &str,
+    ) {
+        let mut count = 0;
+        let mut invalid_value = None;
+
+        for value in text.split_whitespace() {
+            count += 1;
+            if invalid_value.is_none() && !is_valid_value(value, number_type) {
+                invalid_value = Some(value);
+            }
+        }
+
+        if count != num_values {
+            self.error(
+                location,
+                format!("Number of values ({count}) does not match the Dimensions ({num_values})"),
+            );
+        }
+
+        if let Some(value) = invalid_value {
+            self.error(
+                location,
+                format!("Value '{value}' is not valid for NumberType {number_type:?}"),
+            );
+        }
+    }
+
+    fn check_base64(&mut self, text: &str, precision: u8, num_values: usize, location: &str) {
+        let Some(bytes) = base64_writer::decode(text) else {
+            self.error(location, "Data is not valid base64");
+            return;
+        };
+
+        let num_bytes = num_values * usize::from(precision);
+        if bytes.len() != num_bytes {
+            self.error(
+                location,
+                format!(
+                    "Number of bytes ({}) does not match the Dimensions ({num_values}) and Precision ({precision})",
+                    bytes.len()
+                ),
+            );
+        }
+    }
+
+    fn read_include(&mut self, include: &XInclude, location: &str) -> Option<String> {
+        if !self.check_include_exists(include, location) {
+            return None;
+        }
+
+        read_text_file(&self.base_dir.join(include.file_path()))
+            .map_err(|err| {
+                self.error(
+                    location,
+                    format!(
+                        "Included file '{}' can not be read: {err}",
+                        include.file_path()
+                    ),
+                );
+            })
+            .ok()
+    }
+
+    // returns whether the file exists
+    fn check_include_exists(&mut self, include: &XInclude, location: &str) -> bool {
+        let exists = self.base_dir.join(include.file_path()).is_file();
+
+        if !exists {
+            self.error(
+                location,
+                format!("Included file '{}' does not exist", include.file_path()),
+            );
+        }
+
+        exists
+    }
+
+    fn verify_grid_checksums(&mut self, grid: &Grid, location: &str) {
+        let set_attributes = grid.sets.iter().flatten().flat_map(|set| {
+            set.attributes.iter().map(move |attribute| {
+                (attribute, format!("{location}/Set[@Name=\"{}\"]", set.name))
+            })
+        });
+
+        let attributes = grid
+            .attributes
+            .iter()
+            .flatten()
+            .map(|attribute| (attribute, location.to_string()))
+            .chain(set_attributes);
+
+        for (attribute, parent_location) in attributes {
+            self.verify_checksum(
+                attribute,
+                &format!("{parent_location}/Attribute[@Name=\"{}\"]", attribute.name),
+            );
+        }
+
+        for sub_grid in grid.grids.iter().flatten() {
+            self.verify_grid_checksums(sub_grid, &grid_location(location, sub_grid));
+        }
+    }
+
+    fn verify_checksum(&mut self, attribute: &Attribute, location: &str) {
+        let Some(information) = attribute
+            .information
+            .iter()
+            .find(|information| information.name == CHECKSUM_NAME)
+        else {
+            return;
+        };
+
+        // the value names the algorithm, e.g. `xxh64:<hex>`
+        let Some((algorithm, checksum)) = information.value.split_once(':') else {
+            self.error(
+                location,
+                format!("Checksum '{}' is not valid", information.value),
+            );
+            return;
+        };
+        if format!("{algorithm}:") != CHECKSUM_PREFIX {
+            self.error(
+                location,
+                format!("Checksum algorithm '{algorithm}' is not supported"),
+            );
+            return;
+        }
+        let Ok(expected) = u64::from_str_radix(checksum, 16) else {
+            self.error(
+                location,
+                format!("Checksum '{}' is not valid", information.value),
+            );
+            return;
+        };
+
+        let [data_item] = attribute.data_items.as_slice() else {
+            self.warning(
+                location,
+                "Checksum of an Attribute with multiple DataItems is not verified",
+            );
+            return;
+        };
+
+        let Some(bytes) = self.data_item_bytes(data_item, location) else {
+            return;
+        };
+
+        let checksum = xxh64(&bytes, 0);
+        if checksum != expected {
+            self.error(
+                location,
+                format!(
+                    "Checksum {CHECKSUM_PREFIX}{checksum:016x} of the values does not match the written checksum {}",
+                    information.value
+                ),
+            );
+        }
+    }
+
+    // the values of a data item as little-endian bytes of its number type, as hashed for the checksum
+    fn data_item_bytes(&mut self, data_item: &DataItem, location: &str) -> Option<Vec<u8>> {
+        let number_type = data_item.number_type.unwrap_or_default();
+        let precision = data_item.precision.unwrap_or(4);
+
+        let text = match (
+            data_item.item_type,
+            data_item.format.unwrap_or_default(),
+            &data_item.data,
+        ) {
+            (None | Some(ItemType::Uniform), Format::XML, DataContent::Raw(text))
+                if data_item.encoding == Some(Encoding::Base64) =>
+            {
+                let bytes = base64_writer::decode(text);
+                if bytes.is_none() {
+                    self.error(location, "Data is not valid base64");
+                }
+                return bytes;
+            }
+            (None | Some(ItemType::Uniform), Format::XML, DataContent::Raw(text)) => text.clone(),
+            (None | Some(ItemType::Uniform), Format::XML, DataContent::Include(include))
+                if include.is_text() =>
+            {
+                self.read_include(include, location)?
+            }
+            #[cfg(feature = "hdf5")]
+            (None | Some(ItemType::Uniform), Format::HDF, DataContent::Raw(text)) => {
+                return read_hdf5_bytes(&self.base_dir, text, number_type, precision)
+                    .map_err(|message| self.error(location, message))
+                    .ok();
+            }
+            #[cfg(not(feature = "hdf5"))]
+            (None | Some(ItemType::Uniform), Format::HDF, DataContent::Raw(_)) => {
+                self.warning(
+                    location,
+                    "Checksum of HDF5 data is only verified with the hdf5 feature",
+                );
+                return None;
+            }
+            _ => {
+                self.warning(
+                    location,
+                    "Checksum of the DataItem is not verified, only plain data is supported",
+                );
+                return None;
+            }
+        };
+
+        let bytes = text_to_bytes(&text, number_type, precision);
+        if bytes.is_none() {
+            self.error(
+                location,
+                format!(
+                    "Values are not valid for NumberType {number_type:?} and Precision {precision}"
+                ),
+            );
+        }
+        bytes
+    }
+
+    fn check_hdf5(&mut self, text: &str, num_values: usize, location: &str) {
+        let Some((file_name, dataset)) = text.trim().split_once(':') else {
+            self.error(location, "HDF data must be given as 'file:path/to/dataset'");
+            return;
+        };
+
+        let file_path = self.base_dir.join(file_name);
+
+        if !file_path.is_file() {
+            self.error(location, format!("HDF5 file '{file_name}' does not exist"));
+            return;
+        }
+
+        #[cfg(feature = "hdf5")]
+        if let Err(message) = check_hdf5_dataset(&file_path, dataset, num_values) {
+            self.error(location, message);
+        }
+
+        #[cfg(not(feature = "hdf5"))]
+        let _ = (dataset, num_values);
+    }
+}
+
+#[cfg(feature = "hdf5")]
+fn check_hdf5_dataset(file_path: &Path, dataset: &str, num_values: usize) -> Result<(), String> {
+    let h5_file = hdf5::File::open(file_path).map_err(|err| {
+        format!(
+            "HDF5 file '{}' can not be opened: {err}",
+            file_path.display()
+        )
+    })?;
+
+    let size = h5_file
+        .dataset(dataset)
+        .map_err(|err| format!("HDF5 dataset '{dataset}' can not be opened: {err}"))?
+        .size();
+
+    if size == num_values {
+        Ok(())
+    } else {
+        Err(format!(
+            "HDF5 dataset '{dataset}' has {size} values, but the Dimensions are {num_values}"
+        ))
+    }
+}
+
+// the values of an HDF5 dataset as little-endian bytes of the given number type
+#[cfg(feature = "hdf5")]
+fn read_hdf5_bytes(
+    base_dir: &Path,
+    text: &str,
+    number_type: NumberType,
+    precision: u8,
+) -> Result<Vec<u8>, String> {
+    fn to_bytes<T: hdf5::H5Type, const N: usize>(
+        dataset: &hdf5::Dataset,
+        to_le_bytes: fn(T) -> [u8; N],
+    ) -> Result<Vec<u8>, String> {
+        Ok(dataset
+            .read_raw::<T>()
+            .map_err(|err| format!("HDF5 dataset can not be read: {err}"))?
+            .into_iter()
+            .flat_map(to_le_bytes)
+            .collect())
+    }
+
+    let Some((file_name, dataset)) = text.trim().split_once(':') else {
+        return Err("HDF data must be given as 'file:path/to/dataset'".into());
+    };
+
+    let dataset = hdf5::File::open(base_dir.join(file_name))
+        .and_then(|h5_file| h5_file.dataset(dataset))
+        .map_err(|err| format!("HDF5 dataset '{text}' can not be opened: {err}"))?;
+
+    match (number_type, precision) {
+        (NumberType::Float, 8) => to_bytes(&dataset, f64::to_le_bytes),
+        (NumberType::Float, 4) => to_bytes(&dataset, f32::to_le_bytes),
+        (NumberType::Int, 8) => to_bytes(&dataset, i64::to_le_bytes),
+        (NumberType::Int, 4) => to_bytes(&dataset, i32::to_le_bytes),
+        (NumberType::UInt, 8) => to_bytes(&dataset, u64::to_le_bytes),
+        (NumberType::UInt, 4) => to_bytes(&dataset, u32::to_le_bytes),
+        (NumberType::UChar, 1) => to_bytes(&dataset, u8::to_le_bytes),
+        (NumberType::Char, 1) => to_bytes(&dataset, i8::to_le_bytes),
+        _ => Err(format!(
+            "Checksum of NumberType {number_type:?} with Precision {precision} is not supported"
+        )),
+    }
+}
+
+// the values given as text as little-endian bytes of the given number type, `None` if a value is not valid
+fn text_to_bytes(text: &str, number_type: NumberType, precision: u8) -> Option<Vec<u8>> {
+    fn parse<T: std::str::FromStr, const N: usize>(
+        text: &str,
+        to_le_bytes: fn(T) -> [u8; N],
+    ) -> Option<Vec<u8>> {
+        let mut bytes = Vec::new();
+        for value in text.split_whitespace() {
+            bytes.extend(to_le_bytes(value.parse().ok()?));
+        }
+        Some(bytes)
+    }
+
+    match (number_type, precision) {
+        (NumberType::Float, 8) => parse(text, f64::to_le_bytes),
+        (NumberType::Float, 4) => parse(text, f32::to_le_bytes),
+        (NumberType::Int, 8) => parse(text, i64::to_le_bytes),
+        (NumberType::Int, 4) => parse(text, i32::to_le_bytes),
+        (NumberType::UInt, 8) => parse(text, u64::to_le_bytes),
+        (NumberType::UInt, 4) => parse(text, u32::to_le_bytes),
+        (NumberType::UChar, 1) => parse(text, u8::to_le_bytes),
+        (NumberType::Char, 1) => parse(text, i8::to_le_bytes),
+        _ => None,
+    }
+}
+
+// read an included text file, files compressed with gzip are decompressed
+fn read_text_file(file_path: &Path) -> std::io::Result<String> {
+    if file_path
+        .extension()
+        .is_none_or(|extension| extension != "gz")
+    {
+        return std::fs::read_to_string(file_path);
+    }
+
+    let mut text = String::new();
+    GzDecoder::new(BufReader::new(File::open(file_path)?)).read_to_string(&mut text)?;
+
+    Ok(text)
+}
+
+// the names of the grids along an `XPath` like `/Xdmf/Domain/Grid[@Name="a"]/Grid[@Name="b"]`
+fn grid_path_names(path: &str) -> Option<Vec<&str>> {
+    path.strip_prefix("/Xdmf/Domain/")?
+        .split('/')
+        .map(|segment| segment.strip_prefix("Grid[@Name=\"")?.strip_suffix("\"]"))
+        .collect()
+}
+
+fn grid_location(parent: &str, grid: &Grid) -> String {
+    format!("{parent}/Grid[@Name=\"{}\"]", grid.name)
+}
+
+fn data_item_location(parent: &str, data_item: &DataItem) -> String {
+    match &data_item.name {
+        Some(name) => format!("{parent}/DataItem[@Name=\"{name}\"]"),
+        None => format!("{parent}/DataItem"),
+    }
+}
+
+fn valid_precisions(number_type: NumberType) -> &'static [u8] {
+    match number_type {
+        NumberType::Float => &[4, 8],
+        NumberType::Int | NumberType::UInt => &[1, 2, 4, 8],
+        NumberType::Char | NumberType::UChar => &[1],
+    }
+}
+
+fn is_valid_value(value: &str, number_type: NumberType) -> bool {
+    match number_type {
+        NumberType::Float => value.parse::<f64>().is_ok(),
+        NumberType::Int | NumberType::Char => value.parse::<i64>().is_ok(),
+        NumberType::UInt | NumberType::UChar => value.parse::<u64>().is_ok(),
+    }
+}
+
+// number of nodes per element for uniform topologies, None for mixed topologies
+fn nodes_per_element(topology_type: TopologyType) -> Option<usize> {
+    match topology_type {
+        TopologyType::Mixed => None,
+        TopologyType::Polyvertex => Some(1),
+        TopologyType::Triangle => Some(3),
+        TopologyType::Quadrilateral | TopologyType::Tetrahedron => Some(4),
+        TopologyType::Pyramid => Some(5),
+        TopologyType::Wedge => Some(6),
+        TopologyType::Hexahedron => Some(8),
+        TopologyType::Edge3 => Some(3),
+        TopologyType::Triangle6 => Some(6),
+        TopologyType::Quadrilateral8 => Some(8),
+        TopologyType::Quadrilateral9 => Some(9),
+        TopologyType::Tetrahedron10 => Some(10),
+        TopologyType::Pyramid13 => Some(13),
+        TopologyType::Wedge15 => Some(15),
+        TopologyType::Wedge18 => Some(18),
+        TopologyType::Hexahedron20 => Some(20),
+        TopologyType::Hexahedron24 => Some(24),
+        TopologyType::Hexahedron27 => Some(27),
+        TopologyType::Polygon
+        | TopologyType::Polyhedron
+        | TopologyType::CoRectMesh3D
+        | TopologyType::RectMesh3D => None,
+    }
+}
+
+// number of points of a structured topology, None for unstructured topologies
+fn structured_num_points(topology: &Topology) -> Option<usize> {
+    if !topology.topology_type.is_structured() {
+        return None;
+    }
+
+    topology
+        .dimensions
+        .as_ref()
+        .map(|dimensions| dimensions.0.iter().product())
+}
+
+// number of components per entity, None if any number is allowed
+fn num_components(attribute_type: AttributeType) -> Option<usize> {
+    match attribute_type {
+        AttributeType::Scalar => Some(1),
+        AttributeType::Vector => Some(3),
+        AttributeType::Tensor6 => Some(6),
+        AttributeType::Tensor => Some(9),
+        AttributeType::Matrix => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xdmf_elements::{dimensions::Dimensions, geometry::Geometry};
+
+    fn data_item(dimensions: &[usize], number_type: NumberType, data: &str) -> DataItem {
+        DataItem {
+            dimensions: Some(Dimensions(dimensions.to_vec())),
+            number_type: Some(number_type),
+            precision: Some(8),
+            data: data.into(),
+            ..Default::default()
+        }
+    }
+
+    fn dummy_grid() -> Grid {
+        Grid::new_uniform(
+            "mesh",
+            Geometry {
+                geometry_type: GeometryType::XYZ,
+                data_items: vec![data_item(&[3, 3], NumberType::Float, "0 0 0 1 0 0 0 1 0")],
+            },
+            Topology {
+                topology_type: TopologyType::Triangle,
+                number_of_elements: "1".into(),
+                dimensions: None,
+                data_item: Some(data_item(&[3], NumberType::UInt, "0 1 2")),
+            },
+        )
+    }
+
+    fn messages(report: &ValidationReport) -> Vec<String> {
+        report.issues().iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn validate_valid_grid() {
+        let mut grid = dummy_grid();
+        grid.attributes = Some(vec![
+            Attribute {
+                name: "temperature".into(),
+                attribute_type: AttributeType::Scalar,
+                center: Center::Node,
+                information: vec![],
+                data_items: vec![data_item(&[3], NumberType::Float, "1.0 2.0 3.0")],
+            }
+            .into(),
+        ]);
+
+        let report = validate(&Xdmf::new(Domain::new(grid)), "");
+
+        assert!(report.is_valid());
+        assert!(report.issues().is_empty());
+    }
+
+    #[test]
+    fn validate_data_items() {
+        let mut grid = dummy_grid();
+        let topology = grid.topology.as_mut().unwrap();
+        let topology_item = topology
+            .data_item
+            .insert(data_item(&[3], NumberType::UInt, "0 1 -2"));
+        topology_item.precision = Some(3);
+        grid.geometry.as_mut().unwrap().data_items[0].dimensions = Some(Dimensions(vec![4, 3]));
+
+        let report = validate(&Xdmf::new(Domain::new(grid)), "");
+
+        assert!(!report.is_valid());
+        pretty_assertions::assert_eq!(
+            messages(&report),
+            vec![
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Geometry/DataItem: Number of values (9) does not match the Dimensions (12)",
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Topology/DataItem: Precision 3 is not valid for NumberType UInt",
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Topology/DataItem: Value '-2' is not valid for NumberType UInt",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_sizes() {
+        let mut grid = dummy_grid();
+        grid.topology.as_mut().unwrap().number_of_elements = "2".into();
+        grid.attributes = Some(vec![
+            Attribute {
+                name: "velocity".into(),
+                attribute_type: AttributeType::Vector,
+                center: Center::Node,
+                information: vec![],
+                data_items: vec![data_item(&[3], NumberType::Float, "1.0 2.0 3.0")],
+            }
+            .into(),
+            Attribute {
+                name: "velocity".into(),
+                attribute_type: AttributeType::Scalar,
+                center: Center::Node,
+                information: vec![],
+                data_items: vec![],
+            }
+            .into(),
+        ]);
+        // the attributes of a set have one entry per index of the set
+        grid.sets = Some(vec![Set {
+            name: "wall".into(),
+            set_type: SetType::Node,
+            data_items: vec![data_item(&[2], NumberType::UInt, "0 2")],
+            attributes: vec![
+                Attribute {
+                    name: "pressure".into(),
+                    attribute_type: AttributeType::Scalar,
+                    center: Center::Node,
+                    information: vec![],
+                    data_items: vec![data_item(&[3], NumberType::Float, "1.0 2.0 3.0")],
+                }
+                .into(),
+            ],
+        }]);
+
+        let report = validate(&Xdmf::new(Domain::new(grid)), "");
+
+        pretty_assertions::assert_eq!(
+            messages(&report),
+            vec![
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Topology: Number of values (3) does not match 2 elements of TopologyType Triangle with 3 nodes each",
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Attribute[@Name=\"velocity\"]: Number of values (3) does not match 3 entities with Center Node and AttributeType Vector",
+                "warning: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Attribute[@Name=\"velocity\"]: Attribute with the same name and center is defined multiple times",
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Attribute[@Name=\"velocity\"]: Attribute must have exactly one DataItem, but has 0",
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Set[@Name=\"wall\"]/Attribute[@Name=\"pressure\"]: Number of values (3) does not match 2 entities with Center Node and AttributeType Scalar",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_structured_grid() {
+        let geometry = Geometry {
+            geometry_type: GeometryType::OriginDxDyDz,
+            data_items: vec![
+                data_item(&[3], NumberType::Float, "0 0 0"),
+                data_item(&[3], NumberType::Float, "1 1 1"),
+            ],
+        };
+
+        let mut grid = Grid::new_uniform(
+            "mesh",
+            geometry.clone(),
+            Topology::new_co_rect_mesh([2, 2, 3]),
+        );
+        grid.attributes = Some(vec![
+            Attribute {
+                name: "density".into(),
+                attribute_type: AttributeType::Scalar,
+                center: Center::Cell,
+                information: vec![],
+                data_items: vec![data_item(&[1, 1, 2], NumberType::Float, "1 2")],
+            }
+            .into(),
+        ]);
+
+        let report = validate(&Xdmf::new(Domain::new(grid)), "");
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+
+        let mut geometry = geometry;
+        geometry.data_items[1] = data_item(&[2], NumberType::Float, "1 1");
+        let mut topology = Topology::new_co_rect_mesh([2, 2, 3]);
+        topology.dimensions = None;
+
+        let report = validate(
+            &Xdmf::new(Domain::new(Grid::new_uniform("mesh", geometry, topology))),
+            "",
+        );
+
+        pretty_assertions::assert_eq!(
+            messages(&report),
+            vec![
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Geometry: The DataItems of GeometryType OriginDxDyDz must have 3 values, but have [3, 2]",
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Topology: TopologyType CoRectMesh3D requires Dimensions with 3 values",
+            ]
+        );
+
+        let mut grid = dummy_grid();
+        grid.topology.as_mut().unwrap().data_item = None;
+
+        let report = validate(&Xdmf::new(Domain::new(grid)), "");
+
+        pretty_assertions::assert_eq!(
+            messages(&report),
+            vec![
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Topology: TopologyType Triangle requires a DataItem",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_hyperslab() {
+        let mut grid = dummy_grid();
+        // the dimensions of the selected values, which are replaced by the full length in the source
+        let full_cells = data_item(&[3], NumberType::UInt, "0 1 2 2 1 0");
+        grid.topology.as_mut().unwrap().data_item =
+            Some(DataItem::new_hyperslab(full_cells.clone(), 3, 3, 6));
+
+        let report = validate(&Xdmf::new(Domain::new(grid.clone())), "");
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+
+        let mut hyperslab = DataItem::new_hyperslab(full_cells, 3, 2, 6);
+        grid.topology.as_mut().unwrap().data_item = Some(hyperslab.clone());
+        hyperslab.data_items.pop();
+        grid.geometry.as_mut().unwrap().data_items[0] = hyperslab;
+
+        let report = validate(&Xdmf::new(Domain::new(grid)), "");
+
+        pretty_assertions::assert_eq!(
+            messages(&report),
+            vec![
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Geometry/DataItem: HyperSlab DataItem must have exactly two DataItems, but has 1",
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Topology/DataItem: Number of selected values (2) does not match the Dimensions (3)",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_coordinates() {
+        let mut grid = dummy_grid();
+        // the points of the second cell
+        let cells = data_item(&[2, 3], NumberType::UInt, "0 1 2 2 1 0");
+        grid.topology.as_mut().unwrap().data_item = Some(DataItem::new_coordinates(
+            cells.clone(),
+            data_item(&[3, 2], NumberType::UInt, "1 0 1 1 1 2"),
+            Dimensions(vec![3]),
+        ));
+
+        let report = validate(&Xdmf::new(Domain::new(grid.clone())), "");
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+
+        let coordinates = data_item(&[2, 2], NumberType::UInt, "1 0 1 1");
+        let mut selection = DataItem::new_coordinates(cells, coordinates, Dimensions(vec![3]));
+        grid.topology.as_mut().unwrap().data_item = Some(selection.clone());
+        selection.data_items.pop();
+        grid.geometry.as_mut().unwrap().data_items[0] = selection;
+
+        let report = validate(&Xdmf::new(Domain::new(grid)), "");
+
+        pretty_assertions::assert_eq!(
+            messages(&report),
+            vec![
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Geometry/DataItem: Coordinates DataItem must have exactly two DataItems, but has 1",
+                "error: /Xdmf/Domain/Grid[@Name=\"mesh\"]/Topology/DataItem: Coordinates have 4 values, but must have 2 for each of the 3 selected values",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_time_list() {
+        let mut temporal = Grid::new_collection(
+            "time_series",
+            CollectionType::Temporal,
+            Some(vec![dummy_grid(), dummy_grid()]),
+        );
+        temporal.time = Some(Time::new_list(["0.0", "1.0", "2.0"]));
+
+        let report = validate(&Xdmf::new(Domain::new(temporal)), "");
+
+        pretty_assertions::assert_eq!(
+            messages(&report),
+            vec![
+                "error: /Xdmf/Domain/Grid[@Name=\"time_series\"]/Time: Number of times (3) does not match the number of grids (2)",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_references_and_files() {
+        let mut coords = data_item(&[3, 3], NumberType::Float, "");
+        coords.name = Some("coords".into());
+        coords.data = XInclude::new("missing/points.txt", true).into();
+
+        let mut grid = dummy_grid();
+        grid.geometry.as_mut().unwrap().data_items[0] =
+            Reference::builder().source(&coords).build().unwrap().into();
+        grid.topology.as_mut().unwrap().data_item = Some(
+            Reference::builder()
+                .source(&DataItem {
+                    name: Some("cells".into()),
+                    ..Default::default()
+                })
+                .build()
+                .unwrap()
+                .into(),
+        );
+
+        let mut temporal = Grid::new_collection("time_series", CollectionType::Temporal, None);
+        temporal.grids = Some(vec![grid]);
+
+        let mut domain = Domain::new(temporal);
+        domain.data_items.push(coords);
+        domain.data_items.push(DataItem {
+            format: Some(Format::HDF),
+            data: "missing.h5:mesh/cells".into(),
+            ..data_item(&[3], NumberType::UInt, "")
+        });
+
+        let report = validate(&Xdmf::new(domain), "");
+
+        pretty_assertions::assert_eq!(
+            messages(&report),
+            vec![
+                "error: /Xdmf/Domain/DataItem[@Name=\"coords\"]: Included file 'missing/points.txt' does not exist",
+                "error: /Xdmf/Domain/DataItem: HDF5 file 'missing.h5' does not exist",
+                "warning: /Xdmf/Domain/Grid[@Name=\"time_series\"]/Grid[@Name=\"mesh\"]: Grid in a temporal collection has no Time",
+                "error: /Xdmf/Domain/Grid[@Name=\"time_series\"]/Grid[@Name=\"mesh\"]/Topology/DataItem: Reference '/Xdmf/Domain/DataItem[@Name=\"cells\"]' can not be resolved",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_subset() {
+        let cell_indices = data_item(&[1], NumberType::UInt, "0");
+
+        let mut subset = Grid::new_subset(
+            "subset",
+            "/Xdmf/Domain/Grid[@Name=\"mesh\"]",
+            Some(cell_indices.clone()),
+        );
+        subset.attributes = Some(vec![
+            Attribute {
+                name: "pressure".into(),
+                attribute_type: AttributeType::Scalar,
+                center: Center::Cell,
+                information: vec![],
+                data_items: vec![data_item(&[2], NumberType::Float, "1 2")],
+            }
+            .into(),
+        ]);
+
+        let mut domain = Domain::new(dummy_grid());
+        domain.grids.push(subset);
+        domain.grids.push(Grid::new_subset(
+            "unresolved",
+            "/Xdmf/Domain/Grid[@Name=\"missing\"]",
+            Some(cell_indices),
+        ));
+        domain
+            .grids
+            .push(Grid::new_subset("unchecked", "//Grid[1]", None));
+
+        let report = validate(&Xdmf::new(domain), "");
+
+        pretty_assertions::assert_eq!(
+            messages(&report),
+            vec![
+                "error: /Xdmf/Domain/Grid[@Name=\"subset\"]/Attribute[@Name=\"pressure\"]: Number of values (2) does not match 1 entities with Center Cell and AttributeType Scalar",
+                "error: /Xdmf/Domain/Grid[@Name=\"unresolved\"]/Grid: Reference '/Xdmf/Domain/Grid[@Name=\"missing\"]' can not be resolved",
+                "warning: /Xdmf/Domain/Grid[@Name=\"unchecked\"]/Grid: Reference '//Grid[1]' is not checked",
+            ]
+        );
+    }
+}
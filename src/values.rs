@@ -1,68 +1,378 @@
 //! This module contains the wrapper type for using a common interface for different data types.
 
+use std::{borrow::Cow, ops::Range};
+
+use xxhash_rust::xxh64::Xxh64;
+
 use crate::{
-    DataAttribute,
-    xdmf_elements::{data_item::NumberType, dimensions::Dimensions},
+    DataAttribute, XdmfError, XdmfResult,
+    xdmf_elements::{
+        data_item::{Endian, NumberType},
+        dimensions::Dimensions,
+    },
 };
 
+/// Name of the field that Paraview uses to mark ghost points or cells, see [`Values::ghost_type`].
+pub const GHOST_TYPE_NAME: &str = "vtkGhostType";
+
+// flag of Paraview for duplicated points and cells, i.e. owned by another partition
+const DUPLICATE_GHOST: u8 = 1;
+
+/// Name of the `Information` of an attribute holding the checksum of its values.
+pub(crate) const CHECKSUM_NAME: &str = "checksum";
+
+/// Prefix of the value of the checksum, naming the algorithm.
+pub(crate) const CHECKSUM_PREFIX: &str = "xxh64:";
+
 /// Wrapper around different types of data, used to provide a unified interface.
-pub enum Values {
-    /// vector of f64 values
-    F64(Vec<f64>),
-    /// vector of u64 values
-    U64(Vec<u64>),
+///
+/// The values are either owned or borrowed, such that large fields can be written without copying them first.
+/// ```rust
+/// use xdmf::Values;
+///
+/// let pressure = vec![1.0, 2.0, 3.0];
+///
+/// // borrowed, the data stays with the caller
+/// let borrowed = Values::from(pressure.as_slice());
+///
+/// // owned
+/// let owned = Values::from(pressure);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Values<'a> {
+    /// f64 values
+    F64(Cow<'a, [f64]>),
+    /// f32 values, e.g. to reduce the size of the files
+    F32(Cow<'a, [f32]>),
+    /// u64 values
+    U64(Cow<'a, [u64]>),
+    /// i64 values, e.g. material IDs
+    I64(Cow<'a, [i64]>),
+    /// u8 values, e.g. masks
+    U8(Cow<'a, [u8]>),
 }
 
-impl From<Vec<f64>> for Values {
-    fn from(vec: Vec<f64>) -> Self {
-        Self::F64(vec)
-    }
+macro_rules! impl_from_values {
+    ($t:ty, $variant:ident) => {
+        impl From<Vec<$t>> for Values<'_> {
+            fn from(vec: Vec<$t>) -> Self {
+                Self::$variant(Cow::Owned(vec))
+            }
+        }
+
+        impl<'a> From<&'a [$t]> for Values<'a> {
+            fn from(slice: &'a [$t]) -> Self {
+                Self::$variant(Cow::Borrowed(slice))
+            }
+        }
+    };
 }
 
-impl From<Vec<u64>> for Values {
-    fn from(vec: Vec<u64>) -> Self {
-        Self::U64(vec)
+impl_from_values!(f64, F64);
+impl_from_values!(f32, F32);
+impl_from_values!(u64, U64);
+impl_from_values!(i64, I64);
+impl_from_values!(u8, U8);
+
+impl Values<'_> {
+    /// Ghost type mask as used by Paraview, marking the points or cells that are duplicates of another partition.
+    ///
+    /// Written as scalar field with the name [`GHOST_TYPE_NAME`], Paraview hides the ghost entities,
+    /// e.g. the overlapping cells of the partitions of a domain-decomposed simulation.
+    /// ```rust
+    /// use xdmf::{DataAttribute, GHOST_TYPE_NAME, Values};
+    ///
+    /// let cell_data: xdmf::DataMap = vec![(
+    ///     GHOST_TYPE_NAME.to_string(),
+    ///     (
+    ///         DataAttribute::Scalar,
+    ///         Values::ghost_type(&[false, true, false]),
+    ///     ),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    /// ```
+    pub fn ghost_type(is_ghost: &[bool]) -> Self {
+        Self::U8(
+            is_ghost
+                .iter()
+                .map(|&is_ghost| if is_ghost { DUPLICATE_GHOST } else { 0 })
+                .collect(),
+        )
     }
-}
 
-impl Values {
-    pub(crate) fn precision(&self) -> u8 {
+    /// Vector field given as three separate arrays of its components, e.g. the velocities `u`, `v` and `w`.
+    ///
+    /// Returns the entry of a [`DataMap`](crate::DataMap) with [`DataAttribute::Vector`], for point- as well as cell-data.
+    /// The components are interleaved into one array of the values of the entities, such that the caller does not need to copy them.
+    /// ```rust
+    /// use xdmf::Values;
+    ///
+    /// let (u, v, w) = (vec![1.0, 2.0], vec![0.0, 0.5], vec![0.0, 0.0]);
+    ///
+    /// let point_data: xdmf::DataMap = vec![(
+    ///     "velocity".to_string(),
+    ///     Values::from_components([&u, &v, &w]).expect("components differ in length"),
+    /// )]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// assert_eq!(
+    ///     point_data["velocity"].1,
+    ///     Values::from(vec![1.0, 0.0, 0.0, 2.0, 0.5, 0.0])
+    /// );
+    /// ```
+    pub fn from_components<T: Copy>(components: [&[T]; 3]) -> XdmfResult<(DataAttribute, Self)>
+    where
+        Self: From<Vec<T>>,
+    {
+        let [x, y, z] = components;
+
+        if x.len() != y.len() || x.len() != z.len() {
+            return Err(XdmfError::Validation(format!(
+                "Components of a vector must have the same length, but have {}, {} and {}",
+                x.len(),
+                y.len(),
+                z.len()
+            )));
+        }
+
+        let values: Vec<T> = x
+            .iter()
+            .zip(y)
+            .zip(z)
+            .flat_map(|((&x, &y), &z)| [x, y, z])
+            .collect();
+
+        Ok((DataAttribute::Vector, values.into()))
+    }
+
+    /// Convert into values that own their data, e.g. to keep them beyond the lifetime of borrowed data.
+    pub fn into_owned(self) -> Values<'static> {
+        match self {
+            Self::F64(v) => Values::F64(Cow::Owned(v.into_owned())),
+            Self::F32(v) => Values::F32(Cow::Owned(v.into_owned())),
+            Self::U64(v) => Values::U64(Cow::Owned(v.into_owned())),
+            Self::I64(v) => Values::I64(Cow::Owned(v.into_owned())),
+            Self::U8(v) => Values::U8(Cow::Owned(v.into_owned())),
+        }
+    }
+
+    /// Number of bytes of each value, as written to the `Precision` of the `DataItem`.
+    pub fn precision(&self) -> u8 {
         match self {
-            Self::F64(_) => 8,
-            Self::U64(_) => 8,
+            Self::F64(_) | Self::U64(_) | Self::I64(_) => 8,
+            Self::F32(_) => 4,
+            Self::U8(_) => 1,
         }
     }
 
-    pub(crate) fn number_type(&self) -> NumberType {
+    /// Number type of the values, as written to the `NumberType` of the `DataItem`.
+    pub fn number_type(&self) -> NumberType {
         match self {
-            Self::F64(_) => NumberType::Float,
+            Self::F64(_) | Self::F32(_) => NumberType::Float,
             Self::U64(_) => NumberType::UInt,
+            Self::I64(_) => NumberType::Int,
+            Self::U8(_) => NumberType::UChar,
+        }
+    }
+
+    pub(crate) fn dimensions(&self, attribute: &DataAttribute) -> Dimensions {
+        attribute.dimensions(self.len())
+    }
+
+    // the values within the range, borrowed from self
+    pub(crate) fn slice(&self, range: Range<usize>) -> Values<'_> {
+        match self {
+            Self::F64(v) => Values::F64(Cow::Borrowed(&v[range])),
+            Self::F32(v) => Values::F32(Cow::Borrowed(&v[range])),
+            Self::U64(v) => Values::U64(Cow::Borrowed(&v[range])),
+            Self::I64(v) => Values::I64(Cow::Borrowed(&v[range])),
+            Self::U8(v) => Values::U8(Cow::Borrowed(&v[range])),
+        }
+    }
+
+    // the values of the entities in the given order, each entity consisting of `num_components` values
+    pub(crate) fn permuted(&self, order: &[usize], num_components: usize) -> Values<'static> {
+        fn permute<T: Copy>(values: &[T], order: &[usize], num_components: usize) -> Vec<T> {
+            order
+                .iter()
+                .flat_map(|&entity| &values[entity * num_components..(entity + 1) * num_components])
+                .copied()
+                .collect()
+        }
+
+        match self {
+            Self::F64(v) => Values::F64(Cow::Owned(permute(v, order, num_components))),
+            Self::F32(v) => Values::F32(Cow::Owned(permute(v, order, num_components))),
+            Self::U64(v) => Values::U64(Cow::Owned(permute(v, order, num_components))),
+            Self::I64(v) => Values::I64(Cow::Owned(permute(v, order, num_components))),
+            Self::U8(v) => Values::U8(Cow::Owned(permute(v, order, num_components))),
         }
     }
 
-    pub(crate) fn dimensions(&self, attribute: DataAttribute) -> Dimensions {
-        match attribute {
-            DataAttribute::Scalar => match self {
-                Self::F64(v) => Dimensions(vec![v.len()]),
-                Self::U64(v) => Dimensions(vec![v.len()]),
-            },
-            _ => match self {
-                Self::F64(v) => Dimensions(vec![v.len() / attribute.size(), attribute.size()]),
-                Self::U64(v) => Dimensions(vec![v.len() / attribute.size(), attribute.size()]),
-            },
+    // the values converted to single precision, None if they are not double precision floats
+    pub(crate) fn to_single_precision(&self) -> Option<Values<'static>> {
+        match self {
+            Self::F64(v) => Some(Values::F32(v.iter().map(|&v| v as f32).collect())),
+            Self::F32(_) | Self::U64(_) | Self::I64(_) | Self::U8(_) => None,
         }
     }
 
-    pub(crate) fn len(&self) -> usize {
+    // number of NaN or infinite values, which only floating point values can contain
+    pub(crate) fn num_non_finite(&self) -> usize {
+        match self {
+            Self::F64(v) => v.iter().filter(|v| !v.is_finite()).count(),
+            Self::F32(v) => v.iter().filter(|v| !v.is_finite()).count(),
+            Self::U64(_) | Self::I64(_) | Self::U8(_) => 0,
+        }
+    }
+
+    // the values with the NaN or infinite values replaced, None if they can not contain such values
+    pub(crate) fn with_non_finite_replaced(&self, replacement: f64) -> Option<Values<'static>> {
+        match self {
+            Self::F64(v) => Some(Values::F64(
+                v.iter()
+                    .map(|&v| if v.is_finite() { v } else { replacement })
+                    .collect(),
+            )),
+            Self::F32(v) => Some(Values::F32(
+                v.iter()
+                    .map(|&v| if v.is_finite() { v } else { replacement as f32 })
+                    .collect(),
+            )),
+            Self::U64(_) | Self::I64(_) | Self::U8(_) => None,
+        }
+    }
+
+    // the values as f64, borrowed if they are f64 already
+    pub(crate) fn to_f64(&self) -> Cow<'_, [f64]> {
+        match self {
+            Self::F64(v) => Cow::Borrowed(v),
+            Self::F32(v) => v.iter().map(|&v| f64::from(v)).collect(),
+            Self::U64(v) => v.iter().map(|&v| v as f64).collect(),
+            Self::I64(v) => v.iter().map(|&v| v as f64).collect(),
+            Self::U8(v) => v.iter().map(|&v| f64::from(v)).collect(),
+        }
+    }
+
+    // minimum, maximum and mean of the values in a single pass, None if there are no values
+    pub(crate) fn statistics(&self) -> Option<(f64, f64, f64)> {
+        fn min_max_mean(values: impl ExactSizeIterator<Item = f64>) -> Option<(f64, f64, f64)> {
+            let len = values.len();
+            let (min, max, sum) = values.fold(
+                (f64::INFINITY, f64::NEG_INFINITY, 0.0),
+                |(min, max, sum), value| (min.min(value), max.max(value), sum + value),
+            );
+
+            (len > 0).then(|| (min, max, sum / len as f64))
+        }
+
+        match self {
+            Self::F64(v) => min_max_mean(v.iter().copied()),
+            Self::F32(v) => min_max_mean(v.iter().map(|&v| f64::from(v))),
+            Self::U64(v) => min_max_mean(v.iter().map(|&v| v as f64)),
+            Self::I64(v) => min_max_mean(v.iter().map(|&v| v as f64)),
+            Self::U8(v) => min_max_mean(v.iter().map(|&v| f64::from(v))),
+        }
+    }
+
+    // XXH64 of the values as little-endian bytes of their number type, independent of the data storage
+    pub(crate) fn checksum(&self) -> u64 {
+        fn hash<T: Copy, const N: usize>(values: &[T], to_le_bytes: fn(T) -> [u8; N]) -> u64 {
+            let mut hasher = Xxh64::new(0);
+            // hashing blocks of values is considerably faster than hashing value by value
+            for block in values.chunks(4096) {
+                let bytes: Vec<u8> = block.iter().flat_map(|&value| to_le_bytes(value)).collect();
+                hasher.update(&bytes);
+            }
+            hasher.digest()
+        }
+
+        match self {
+            Self::F64(v) => hash(v, f64::to_le_bytes),
+            Self::F32(v) => hash(v, f32::to_le_bytes),
+            Self::U64(v) => hash(v, u64::to_le_bytes),
+            Self::I64(v) => hash(v, i64::to_le_bytes),
+            Self::U8(v) => hash(v, u8::to_le_bytes),
+        }
+    }
+
+    /// Write the values as raw bytes in the given byte order, e.g. into a file referenced by a `DataItem` with `Format::Binary`.
+    ///
+    /// The values are swapped while writing if the byte order differs from the one of this machine,
+    /// the `Endian` of the `DataItem` must be set accordingly.
+    /// ```rust
+    /// use xdmf::{
+    ///     Values,
+    ///     xdmf_elements::data_item::{DataItem, Endian, Format},
+    /// };
+    ///
+    /// let values = Values::from(vec![1_u64, 2, 3]);
+    ///
+    /// let mut bytes = Vec::new();
+    /// values
+    ///     .write_binary(&mut bytes, Endian::Big)
+    ///     .expect("failed to write values");
+    /// assert_eq!(bytes[..8], [0, 0, 0, 0, 0, 0, 0, 1]);
+    ///
+    /// let data_item = DataItem {
+    ///     format: Some(Format::Binary),
+    ///     endian: Some(Endian::Big),
+    ///     number_type: Some(values.number_type()),
+    ///     precision: Some(values.precision()),
+    ///     data: "values.bin".to_string().into(),
+    ///     ..Default::default()
+    /// };
+    /// ```
+    pub fn write_binary(&self, writer: &mut impl std::io::Write, endian: Endian) -> XdmfResult<()> {
+        fn write<T: Copy, const N: usize>(
+            writer: &mut impl std::io::Write,
+            values: &[T],
+            to_bytes: fn(T) -> [u8; N],
+        ) -> XdmfResult<()> {
+            // writing blocks of values is considerably faster than writing value by value
+            for block in values.chunks(4096) {
+                let bytes: Vec<u8> = block.iter().flat_map(|&value| to_bytes(value)).collect();
+                writer.write_all(&bytes)?;
+            }
+            Ok(())
+        }
+
+        match (self, endian.is_little()) {
+            (Self::F64(v), true) => write(writer, v, f64::to_le_bytes),
+            (Self::F64(v), false) => write(writer, v, f64::to_be_bytes),
+            (Self::F32(v), true) => write(writer, v, f32::to_le_bytes),
+            (Self::F32(v), false) => write(writer, v, f32::to_be_bytes),
+            (Self::U64(v), true) => write(writer, v, u64::to_le_bytes),
+            (Self::U64(v), false) => write(writer, v, u64::to_be_bytes),
+            (Self::I64(v), true) => write(writer, v, i64::to_le_bytes),
+            (Self::I64(v), false) => write(writer, v, i64::to_be_bytes),
+            (Self::U8(v), _) => Ok(writer.write_all(v)?),
+        }
+    }
+
+    /// Number of values.
+    pub fn len(&self) -> usize {
         match self {
             Self::F64(v) => v.len(),
+            Self::F32(v) => v.len(),
             Self::U64(v) => v.len(),
+            Self::I64(v) => v.len(),
+            Self::U8(v) => v.len(),
         }
     }
+
+    /// Whether there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use xxhash_rust::xxh64::xxh64;
+
     use super::*;
 
     #[test]
@@ -75,19 +385,19 @@ mod tests {
         assert_eq!(values.number_type(), NumberType::Float);
         assert_eq!(values.precision(), 8);
         assert_eq!(
-            values.dimensions(DataAttribute::Scalar),
+            values.dimensions(&DataAttribute::Scalar),
             Dimensions(vec![6])
         );
         assert_eq!(
-            values.dimensions(DataAttribute::Vector),
+            values.dimensions(&DataAttribute::Vector),
             Dimensions(vec![2, 3])
         );
         assert_eq!(
-            values.dimensions(DataAttribute::Tensor6),
+            values.dimensions(&DataAttribute::Tensor6),
             Dimensions(vec![1, 6])
         );
         assert_eq!(
-            values.dimensions(DataAttribute::Matrix(3, 2)),
+            values.dimensions(&DataAttribute::Matrix(3, 2)),
             Dimensions(vec![1, 6])
         );
         assert_eq!(values.len(), 6);
@@ -102,9 +412,157 @@ mod tests {
         assert_eq!(values.number_type(), NumberType::UInt);
         assert_eq!(values.precision(), 8);
         assert_eq!(
-            values.dimensions(DataAttribute::Scalar),
+            values.dimensions(&DataAttribute::Scalar),
             Dimensions(vec![6])
         );
         assert_eq!(values.len(), 6);
     }
+
+    #[test]
+    fn vec_i64_and_u8() {
+        let values: Values = vec![-1_i64, 2, 3].into();
+        assert_eq!(values.number_type(), NumberType::Int);
+        assert_eq!(values.precision(), 8);
+        assert_eq!(values.len(), 3);
+
+        let values: Values = vec![0_u8, 1].into();
+        assert_eq!(values.number_type(), NumberType::UChar);
+        assert_eq!(values.precision(), 1);
+        assert_eq!(values.len(), 2);
+    }
+
+    #[test]
+    fn vec_f32() {
+        let values: Values = vec![1.5_f32, 2.0].into();
+        assert_eq!(values.number_type(), NumberType::Float);
+        assert_eq!(values.precision(), 4);
+        assert_eq!(values.len(), 2);
+        assert!(values.to_single_precision().is_none());
+
+        let values: Values = vec![1.5, 2.0].into();
+        let single = values.to_single_precision().unwrap();
+        assert!(matches!(&single, Values::F32(v) if **v == [1.5, 2.0]));
+        assert_eq!(single.precision(), 4);
+    }
+
+    #[test]
+    fn statistics() {
+        let values: Values = vec![1.5, -2.0, 3.5].into();
+        assert_eq!(values.statistics(), Some((-2.0, 3.5, 1.0)));
+
+        let values: Values = vec![0_u8, 1, 1, 2].into();
+        assert_eq!(values.statistics(), Some((0.0, 2.0, 1.0)));
+
+        let values: Values = Vec::<i64>::new().into();
+        assert_eq!(values.statistics(), None);
+    }
+
+    #[test]
+    fn checksum() {
+        let values: Values = vec![1.0, 2.0].into();
+        assert_eq!(
+            values.checksum(),
+            xxh64(&[1.0_f64.to_le_bytes(), 2.0_f64.to_le_bytes()].concat(), 0)
+        );
+
+        // the number type is part of the checksum
+        let values_f32: Values = vec![1.0_f32, 2.0].into();
+        assert_ne!(values_f32.checksum(), values.checksum());
+
+        let values: Values = (0..10_000_u64).collect::<Vec<_>>().into();
+        let bytes: Vec<u8> = (0..10_000_u64).flat_map(u64::to_le_bytes).collect();
+        assert_eq!(values.checksum(), xxh64(&bytes, 0));
+    }
+
+    #[test]
+    fn non_finite() {
+        let values: Values = vec![1.0, f64::NAN, f64::INFINITY, -2.0].into();
+        assert_eq!(values.num_non_finite(), 2);
+        assert_eq!(
+            values.with_non_finite_replaced(0.0),
+            Some(Values::from(vec![1.0, 0.0, 0.0, -2.0]))
+        );
+
+        let values: Values = vec![f32::NEG_INFINITY, 3.0].into();
+        assert_eq!(values.num_non_finite(), 1);
+        assert_eq!(
+            values.with_non_finite_replaced(-1.0),
+            Some(Values::from(vec![-1.0_f32, 3.0]))
+        );
+
+        let values: Values = vec![1_u64, 2].into();
+        assert_eq!(values.num_non_finite(), 0);
+        assert_eq!(values.with_non_finite_replaced(0.0), None);
+    }
+
+    #[test]
+    fn permuted() {
+        // two entities with two components each
+        let values: Values = vec![1_i64, 2, 3, 4].into();
+        assert_eq!(
+            values.permuted(&[1, 0], 2),
+            Values::from(vec![3_i64, 4, 1, 2])
+        );
+        assert_eq!(
+            values.permuted(&[1, 1], 2),
+            Values::from(vec![3_i64, 4, 3, 4])
+        );
+    }
+
+    #[test]
+    fn borrowed() {
+        let vec_f64 = vec![1., 2., 3., 4., 5., 6.];
+
+        let values = Values::from(vec_f64.as_slice());
+        assert!(matches!(&values, Values::F64(Cow::Borrowed(_))));
+        assert_eq!(values.len(), 6);
+
+        let part = values.slice(2..4);
+        assert!(matches!(&part, Values::F64(Cow::Borrowed(v)) if *v == [3., 4.]));
+
+        let owned = values.into_owned();
+        assert!(matches!(&owned, Values::F64(Cow::Owned(v)) if *v == vec_f64));
+    }
+
+    #[test]
+    fn write_binary() {
+        let write = |values: Values, endian| {
+            let mut bytes = Vec::new();
+            values.write_binary(&mut bytes, endian).unwrap();
+            bytes
+        };
+
+        assert_eq!(
+            write(Values::from(vec![1.0_f32, -2.0]), Endian::Little),
+            [0, 0, 128, 63, 0, 0, 0, 192]
+        );
+        assert_eq!(
+            write(Values::from(vec![1.0_f32, -2.0]), Endian::Big),
+            [63, 128, 0, 0, 192, 0, 0, 0]
+        );
+        assert_eq!(write(Values::from(vec![-1_i64]), Endian::Big), [255; 8]);
+        assert_eq!(write(Values::from(vec![1_u8, 2]), Endian::Big), [1, 2]);
+    }
+
+    #[test]
+    fn ghost_type() {
+        let values = Values::ghost_type(&[false, true, true]);
+        assert!(matches!(&values, Values::U8(v) if **v == [0, 1, 1]));
+        assert_eq!(values.number_type(), NumberType::UChar);
+    }
+
+    #[test]
+    fn from_components() {
+        let (attribute, values) =
+            Values::from_components([&[1.0_f32, 2.0], &[3.0, 4.0], &[5.0, 6.0]]).unwrap();
+        assert_eq!(attribute, DataAttribute::Vector);
+        assert!(matches!(&values, Values::F32(v) if **v == [1.0, 3.0, 5.0, 2.0, 4.0, 6.0]));
+
+        assert_eq!(
+            Values::from_components([&[1.0], &[2.0, 3.0], &[4.0]])
+                .unwrap_err()
+                .to_string(),
+            "Components of a vector must have the same length, but have 1, 2 and 1"
+        );
+    }
 }
@@ -0,0 +1,383 @@
+//! Conversion between the meshes and data of this crate and the VTK data model of [vtkio](https://github.com/elrnv/vtkio).
+//!
+//! This allows to write meshes that were read from VTK files, and to export the data to `.vtu` files for tools that can not read XDMF.
+//! The ordering of the points within the cells is the same in VTK and XDMF, hence the connectivity is used as is.
+
+use std::{io::Error as IoError, path::Path};
+
+use vtkio::{
+    Vtk,
+    model::{
+        Attribute, Attributes, ByteOrder, CellType as VtkCellType, Cells, DataSet, IOBuffer,
+        UnstructuredGridPiece, Version, VertexNumbers,
+    },
+};
+
+use crate::{
+    CellType, DataAttribute, DataMap, Values, XdmfError, XdmfResult,
+    time_series_writer::{check_data_size, validate_points_and_cells},
+};
+
+/// Cell types of this crate and their VTK counterparts
+const CELL_TYPES: [(CellType, VtkCellType); 19] = [
+    (CellType::Vertex, VtkCellType::Vertex),
+    (CellType::Edge, VtkCellType::Line),
+    (CellType::Triangle, VtkCellType::Triangle),
+    (CellType::Quadrilateral, VtkCellType::Quad),
+    (CellType::Tetrahedron, VtkCellType::Tetra),
+    (CellType::Pyramid, VtkCellType::Pyramid),
+    (CellType::Wedge, VtkCellType::Wedge),
+    (CellType::Hexahedron, VtkCellType::Hexahedron),
+    (CellType::Edge3, VtkCellType::QuadraticEdge),
+    (CellType::Quadrilateral9, VtkCellType::BiquadraticQuad),
+    (CellType::Triangle6, VtkCellType::QuadraticTriangle),
+    (CellType::Quadrilateral8, VtkCellType::QuadraticQuad),
+    (CellType::Tetrahedron10, VtkCellType::QuadraticTetra),
+    (CellType::Pyramid13, VtkCellType::QuadraticPyramid),
+    (CellType::Wedge15, VtkCellType::QuadraticWedge),
+    (CellType::Wedge18, VtkCellType::BiquadraticQuadraticWedge),
+    (CellType::Hexahedron20, VtkCellType::QuadraticHexahedron),
+    (
+        CellType::Hexahedron24,
+        VtkCellType::BiquadraticQuadraticHexahedron,
+    ),
+    (CellType::Hexahedron27, VtkCellType::TriquadraticHexahedron),
+];
+
+// the number of points is required for polygons, which have an arbitrary number of points
+fn cell_type_from_vtk(vtk_cell_type: VtkCellType, num_points: usize) -> XdmfResult<CellType> {
+    if vtk_cell_type == VtkCellType::Polygon {
+        return Ok(CellType::Polygon(num_points));
+    }
+
+    CELL_TYPES
+        .iter()
+        .find(|(_, vtk_type)| *vtk_type == vtk_cell_type)
+        .map(|(cell_type, _)| *cell_type)
+        .ok_or_else(|| {
+            XdmfError::Unsupported(format!(
+                "VTK cell type '{vtk_cell_type:?}' is not supported"
+            ))
+        })
+}
+
+fn cell_type_to_vtk(cell_type: CellType) -> XdmfResult<VtkCellType> {
+    if let CellType::Polygon(_) = cell_type {
+        return Ok(VtkCellType::Polygon);
+    }
+
+    // polyhedra require the faces to be stored separately, which is not supported by vtkio
+    CELL_TYPES
+        .iter()
+        .find(|(ct, _)| *ct == cell_type)
+        .map(|(_, vtk_type)| *vtk_type)
+        .ok_or_else(|| {
+            XdmfError::Unsupported(format!(
+                "Cell type '{cell_type:?}' can not be exported to VTK"
+            ))
+        })
+}
+
+/// Convert an unstructured VTK mesh into points, connectivity and cell types, as used by [`crate::TimeSeriesWriter::write_mesh`].
+///
+/// Only the cell types that have a counterpart in [`CellType`] are supported.
+pub fn mesh_from_vtk(
+    piece: &UnstructuredGridPiece,
+) -> XdmfResult<(Vec<f64>, Vec<u64>, Vec<CellType>)> {
+    let points = piece.points.clone().cast_into::<f64>().ok_or_else(|| {
+        XdmfError::Validation("Points of the VTK mesh can not be converted to f64".into())
+    })?;
+
+    let vtk_cell_types = &piece.cells.types;
+    let invalid_cell = |i_cell: usize| {
+        XdmfError::Validation(format!(
+            "Cell {i_cell} of the VTK mesh has an invalid number of points"
+        ))
+    };
+
+    let (connectivity, num_cell_points) = match &piece.cells.cell_verts {
+        VertexNumbers::Legacy { vertices, .. } => {
+            // each cell is stored as `n i_1 ... i_n`
+            let mut connectivity = Vec::with_capacity(vertices.len());
+            let mut num_cell_points = Vec::with_capacity(vtk_cell_types.len());
+            let mut index = 0;
+
+            for i_cell in 0..vtk_cell_types.len() {
+                let cell_vertices = vertices
+                    .get(index)
+                    .and_then(|&num_points| vertices.get(index + 1..=index + num_points as usize))
+                    .ok_or_else(|| invalid_cell(i_cell))?;
+
+                connectivity.extend(cell_vertices.iter().map(|&vertex| u64::from(vertex)));
+                num_cell_points.push(cell_vertices.len());
+                index += cell_vertices.len() + 1;
+            }
+
+            (connectivity, num_cell_points)
+        }
+        VertexNumbers::XML {
+            connectivity,
+            offsets,
+        } => {
+            // the offsets point to the end of each cell
+            let num_cell_points = offsets
+                .iter()
+                .scan(0, |start, &end| {
+                    let num_points = end.saturating_sub(*start) as usize;
+                    *start = end;
+                    Some(num_points)
+                })
+                .collect();
+
+            (connectivity.clone(), num_cell_points)
+        }
+    };
+
+    if vtk_cell_types.len() != num_cell_points.len() {
+        return Err(XdmfError::Validation(format!(
+            "VTK mesh has {} cell types, but {} cells",
+            vtk_cell_types.len(),
+            num_cell_points.len()
+        )));
+    }
+
+    let cell_types = vtk_cell_types
+        .iter()
+        .zip(&num_cell_points)
+        .enumerate()
+        .map(|(i_cell, (vtk_cell_type, &num_points))| {
+            let cell_type = cell_type_from_vtk(*vtk_cell_type, num_points)?;
+
+            if cell_type.num_points() == num_points {
+                Ok(cell_type)
+            } else {
+                Err(invalid_cell(i_cell))
+            }
+        })
+        .collect::<XdmfResult<Vec<_>>>()?;
+
+    Ok((points, connectivity, cell_types))
+}
+
+/// Export a mesh and its data to a `.vtu` file, e.g. for tools that can not read XDMF.
+///
+/// The arguments are the same as for writing the mesh and the data of a time step with the `TimeSeriesWriter`.
+/// If no cells are given, each point is exported as a vertex.
+/// ```rust
+/// xdmf::export_vtu(
+///     "name_vtu_file",
+///     &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+///     (&[0, 1, 2], &[xdmf::CellType::Triangle]),
+///     None,
+///     None,
+/// )
+/// .expect("failed to export vtu file");
+/// # std::fs::remove_file("name_vtu_file.vtu").expect("failed to remove vtu file");
+/// ```
+pub fn export_vtu(
+    file_name: impl AsRef<Path>,
+    points: &[f64],
+    cells: (&[u64], &[CellType]),
+    point_data: Option<&DataMap>,
+    cell_data: Option<&DataMap>,
+) -> XdmfResult<()> {
+    validate_points_and_cells(points, cells)?;
+
+    let num_points = points.len() / 3;
+
+    let (connectivity, cell_types) = if cells.1.is_empty() {
+        (
+            (0..num_points as u64).collect(),
+            vec![CellType::Vertex; num_points],
+        )
+    } else {
+        (cells.0.to_vec(), cells.1.to_vec())
+    };
+
+    check_data_size(point_data, num_points, "point")?;
+    check_data_size(cell_data, cell_types.len(), "cell")?;
+
+    let offsets = cell_types
+        .iter()
+        .scan(0_u64, |offset, cell_type| {
+            *offset += cell_type.num_points() as u64;
+            Some(*offset)
+        })
+        .collect();
+
+    let vtk = Vtk {
+        version: Version { major: 1, minor: 0 },
+        byte_order: ByteOrder::native(),
+        title: String::new(),
+        file_path: None,
+        data: DataSet::inline(UnstructuredGridPiece {
+            points: IOBuffer::F64(points.to_vec()),
+            cells: Cells {
+                cell_verts: VertexNumbers::XML {
+                    connectivity,
+                    offsets,
+                },
+                types: cell_types
+                    .into_iter()
+                    .map(cell_type_to_vtk)
+                    .collect::<XdmfResult<_>>()?,
+            },
+            data: Attributes {
+                point: attributes_to_vtk(point_data),
+                cell: attributes_to_vtk(cell_data),
+            },
+        }),
+    };
+
+    vtk.export(file_name.as_ref().with_extension("vtu"))
+        .map_err(|err| XdmfError::Io(IoError::other(err)))
+}
+
+fn attributes_to_vtk(data_map: Option<&DataMap>) -> Vec<Attribute> {
+    data_map
+        .into_iter()
+        .flatten()
+        .map(|(name, (data_attribute, values))| {
+            let attribute = match data_attribute {
+                DataAttribute::Scalar => Attribute::scalars(name, 1),
+                DataAttribute::Vector => Attribute::vectors(name),
+                DataAttribute::Tensor => Attribute::tensors(name),
+                _ => Attribute::generic(name, data_attribute.size() as u32),
+            };
+
+            match values {
+                Values::F64(v) => attribute.with_data(v.to_vec()),
+                Values::F32(v) => attribute.with_data(v.to_vec()),
+                Values::U64(v) => attribute.with_data(v.to_vec()),
+                Values::I64(v) => attribute.with_data(v.to_vec()),
+                Values::U8(v) => attribute.with_data(v.to_vec()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cell_types_roundtrip() {
+        for (cell_type, vtk_cell_type) in CELL_TYPES {
+            assert_eq!(cell_type_to_vtk(cell_type).unwrap(), vtk_cell_type);
+            assert_eq!(
+                cell_type_from_vtk(vtk_cell_type, cell_type.num_points()).unwrap(),
+                cell_type
+            );
+        }
+
+        assert_eq!(
+            cell_type_to_vtk(CellType::Polygon(5)).unwrap(),
+            VtkCellType::Polygon
+        );
+        assert_eq!(
+            cell_type_from_vtk(VtkCellType::Polygon, 5).unwrap(),
+            CellType::Polygon(5)
+        );
+
+        assert_eq!(
+            cell_type_from_vtk(VtkCellType::Voxel, 8)
+                .unwrap_err()
+                .to_string(),
+            "VTK cell type 'Voxel' is not supported"
+        );
+        assert_eq!(
+            cell_type_to_vtk(CellType::Polyhedron(17))
+                .unwrap_err()
+                .to_string(),
+            "Cell type 'Polyhedron(17)' can not be exported to VTK"
+        );
+    }
+
+    #[test]
+    fn mesh_from_vtk_legacy() {
+        let piece = UnstructuredGridPiece {
+            points: IOBuffer::F32(vec![
+                0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0,
+            ]),
+            cells: Cells {
+                cell_verts: VertexNumbers::Legacy {
+                    num_cells: 3,
+                    vertices: vec![3, 0, 1, 2, 2, 1, 3, 4, 0, 1, 3, 2],
+                },
+                types: vec![
+                    VtkCellType::Triangle,
+                    VtkCellType::Line,
+                    VtkCellType::Polygon,
+                ],
+            },
+            data: Attributes::new(),
+        };
+
+        let (points, connectivity, cell_types) = mesh_from_vtk(&piece).unwrap();
+
+        assert_eq!(points.len(), 12);
+        assert_eq!(connectivity, vec![0, 1, 2, 1, 3, 0, 1, 3, 2]);
+        assert_eq!(
+            cell_types,
+            vec![CellType::Triangle, CellType::Edge, CellType::Polygon(4)]
+        );
+
+        // the number of points does not match the cell type
+        let piece = UnstructuredGridPiece {
+            cells: Cells {
+                cell_verts: VertexNumbers::Legacy {
+                    num_cells: 1,
+                    vertices: vec![2, 0, 1],
+                },
+                types: vec![VtkCellType::Triangle],
+            },
+            ..piece
+        };
+
+        assert_eq!(
+            mesh_from_vtk(&piece).unwrap_err().to_string(),
+            "Cell 0 of the VTK mesh has an invalid number of points"
+        );
+    }
+
+    #[test]
+    fn export_vtu_roundtrip() {
+        let tmp_dir = temp_dir::TempDir::new().unwrap();
+        let file_name = tmp_dir.path().join("exported");
+
+        let points = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0];
+        let cells = (
+            [0_u64, 1, 2, 1, 3, 2, 0, 1, 3, 2].as_slice(),
+            [CellType::Triangle, CellType::Triangle, CellType::Polygon(4)].as_slice(),
+        );
+
+        let point_data = vec![(
+            "temperature".to_string(),
+            (DataAttribute::Scalar, vec![1.0, 2.0, 3.0, 4.0].into()),
+        )]
+        .into_iter()
+        .collect();
+
+        export_vtu(&file_name, &points, cells, Some(&point_data), None).unwrap();
+
+        let vtk = Vtk::import(file_name.with_extension("vtu")).unwrap();
+        let DataSet::UnstructuredGrid { pieces, .. } = vtk.data else {
+            panic!("expected an unstructured grid");
+        };
+        let piece = pieces[0].load_piece_data(None).unwrap();
+
+        let (read_points, read_connectivity, read_cell_types) = mesh_from_vtk(&piece).unwrap();
+        assert_eq!(read_points, points);
+        assert_eq!(read_connectivity, cells.0);
+        assert_eq!(read_cell_types, cells.1);
+        assert_eq!(piece.data.point.len(), 1);
+
+        // the size of the data is checked
+        assert_eq!(
+            export_vtu(&file_name, &points, cells, None, Some(&point_data))
+                .unwrap_err()
+                .to_string(),
+            "Size of cell-data 'temperature' must be 3, but is 4"
+        );
+    }
+}
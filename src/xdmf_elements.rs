@@ -2,6 +2,9 @@
 //!
 //! The official documentaion for these can be found [here](https://www.xdmf.org/index.php/XDMF_Model_and_Format.html).
 
+use std::collections::HashSet;
+
+use quick_xml::events::{BytesDecl, BytesText, Event};
 use serde::{Deserialize, Serialize};
 
 pub mod attribute;
@@ -9,11 +12,14 @@ pub mod data_item;
 pub mod dimensions;
 pub mod geometry;
 pub mod grid;
+pub mod set;
 pub mod topology;
 
-use data_item::DataItem;
+use data_item::{DataItem, Reference, XInclude};
 use grid::Grid;
 
+use crate::{XdmfError, XdmfResult};
+
 /// Name of the root element of an XDMF file.
 pub const XDMF_TAG: &str = "Xdmf";
 
@@ -24,7 +30,7 @@ pub struct Xdmf {
     #[doc(hidden)]
     pub version: String,
 
-    #[serde(rename = "@xmlns:xi")]
+    #[serde(rename = "@xmlns:xi", default = "xinclude_url")]
     #[doc(hidden)]
     pub xinclude_url: String,
 
@@ -32,7 +38,7 @@ pub struct Xdmf {
     #[doc(hidden)]
     pub domains: Vec<Domain>,
 
-    #[serde(rename = "Information", skip_serializing_if = "Vec::is_empty")]
+    #[serde(rename = "Information", default, skip_serializing_if = "Vec::is_empty")]
     #[doc(hidden)]
     pub information: Vec<Information>,
 }
@@ -42,7 +48,7 @@ impl Xdmf {
     pub fn new(domain: Domain) -> Self {
         Self {
             version: "2.0".to_string(),
-            xinclude_url: "http://www.w3.org/2001/XInclude".to_string(),
+            xinclude_url: xinclude_url(),
             domains: vec![domain],
             information: vec![],
         }
@@ -51,35 +57,156 @@ impl Xdmf {
     /// Write the serialized XDMF to the given writer.
     ///
     /// "Pretty-printing" with 4 spaces for indentation is used to format the output, making it human-readable.
-    pub fn write_to(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
-        let mut file_writer = quick_xml::Writer::new_with_indent(writer, b' ', 4);
-        file_writer
-            .write_serializable(XDMF_TAG, self)
-            .map_err(std::io::Error::other)
+    /// The indentation can be changed, or disabled for compact output, with [`Xdmf::write_to_with_header`].
+    /// Before writing, the [`Reference`]s are validated, see [`Domain::check_references`].
+    pub fn write_to(&self, writer: &mut impl std::io::Write) -> XdmfResult<()> {
+        self.write_to_with_header(writer, &XmlHeader::default())
+    }
+
+    /// Write the serialized XDMF to the given writer, preceded by the given XML declaration and `DOCTYPE`,
+    /// and formatted with the indentation of the header.
+    ///
+    /// See [`Xdmf::write_to`] for details.
+    pub fn write_to_with_header(
+        &self,
+        writer: &mut impl std::io::Write,
+        header: &XmlHeader,
+    ) -> XdmfResult<()> {
+        for domain in &self.domains {
+            domain.check_references()?;
+        }
+
+        let mut file_writer = if header.compact {
+            quick_xml::Writer::new(writer)
+        } else {
+            quick_xml::Writer::new_with_indent(writer, header.indent_char, header.indent_size)
+        };
+
+        if header.declaration {
+            file_writer.write_event(Event::Decl(BytesDecl::new(
+                "1.0",
+                Some(&header.encoding),
+                None,
+            )))?;
+        }
+        if header.doctype {
+            file_writer.write_event(Event::DocType(BytesText::from_escaped(
+                "Xdmf SYSTEM \"Xdmf.dtd\"",
+            )))?;
+        }
+
+        Ok(file_writer.write_serializable(XDMF_TAG, self)?)
+    }
+
+    /// Read an XDMF from the given reader, e.g. a file written with [`Xdmf::write_to`].
+    ///
+    /// Writing it again results in the same file. Optional attributes that are missing (e.g. the `AttributeType` or `Center` of an `Attribute`)
+    /// get the defaults of XDMF, and `Type` is accepted instead of `TopologyType`, `GeometryType` and `AttributeType`.
+    pub fn read_from(reader: impl std::io::BufRead) -> XdmfResult<Self> {
+        Ok(quick_xml::de::from_reader(reader)?)
     }
 }
 
+// namespace of XInclude, which is declared by all written files, also if it was missing when reading
+fn xinclude_url() -> String {
+    "http://www.w3.org/2001/XInclude".to_string()
+}
+
 impl Default for Xdmf {
     fn default() -> Self {
         Self::new(Domain::default())
     }
 }
 
+/// Header written before the root element of an XDMF file, by default there is none, and the formatting of the XML.
+///
+/// Some readers require the XML declaration (`<?xml version="1.0" encoding="UTF-8"?>`) and/or the `DOCTYPE` (`<!DOCTYPE Xdmf SYSTEM "Xdmf.dtd">`).
+/// The XML is indented with 4 spaces by default, large files with inline data are smaller and faster to write without indentation.
+/// ```rust
+/// let header = xdmf::XmlHeader::default()
+///     .with_declaration(true)
+///     .with_doctype(true)
+///     .with_indent(b'\t', 1);
+///
+/// let compact_header = xdmf::XmlHeader::default().with_compact(true);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XmlHeader {
+    declaration: bool,
+    encoding: String,
+    doctype: bool,
+    indent_char: u8,
+    indent_size: usize,
+    compact: bool,
+}
+
+impl Default for XmlHeader {
+    fn default() -> Self {
+        Self {
+            declaration: false,
+            encoding: "UTF-8".to_string(),
+            doctype: false,
+            indent_char: b' ',
+            indent_size: 4,
+            compact: false,
+        }
+    }
+}
+
+impl XmlHeader {
+    /// Write the XML declaration.
+    pub fn with_declaration(mut self, declaration: bool) -> Self {
+        self.declaration = declaration;
+        self
+    }
+
+    /// Set the encoding given in the XML declaration, `UTF-8` by default.
+    ///
+    /// The file is always written as UTF-8, hence the encoding must be compatible with the content, e.g. `ISO-8859-1` for ASCII names.
+    pub fn with_encoding(mut self, encoding: impl ToString) -> Self {
+        self.encoding = encoding.to_string();
+        self
+    }
+
+    /// Write the `DOCTYPE` referencing `Xdmf.dtd`.
+    pub fn with_doctype(mut self, doctype: bool) -> Self {
+        self.doctype = doctype;
+        self
+    }
+
+    /// Indent the nested elements by `indent_size` times `indent_char` per level, 4 spaces by default.
+    pub fn with_indent(mut self, indent_char: u8, indent_size: usize) -> Self {
+        self.indent_char = indent_char;
+        self.indent_size = indent_size;
+        self
+    }
+
+    /// Write the XML without indentation and line breaks.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+}
+
 /// Stores application-specific metadata that doesn't fit into the standard data model.
 ///
 /// The `Information` element is designed to hold additional, system- or code-specific
 /// details that can be safely ignored by other components.
 ///
 /// See <https://www.xdmf.org/index.php/XDMF_Model_and_Format.html#Information>
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Information {
     #[serde(rename = "@Name")]
     #[doc(hidden)]
     pub name: String,
 
-    #[serde(rename = "@Value")]
+    #[serde(rename = "@Value", default)]
     #[doc(hidden)]
     pub value: String,
+
+    #[serde(rename = "Information", default, skip_serializing_if = "Vec::is_empty")]
+    /// Nested information, e.g. to group related metadata
+    pub information: Vec<Self>,
 }
 
 impl Information {
@@ -88,80 +215,173 @@ impl Information {
         Self {
             name: name.to_string(),
             value: value.to_string(),
+            information: Vec::new(),
         }
     }
+
+    /// Add a nested information.
+    pub fn with_information(mut self, information: Self) -> Self {
+        self.information.push(information);
+        self
+    }
 }
 
 /// Top level container for grids, represents a computational domain.
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Domain {
-    #[serde(rename = "Grid")]
+    #[serde(rename = "@Name", default, skip_serializing_if = "Option::is_none")]
+    #[doc(hidden)]
+    pub name: Option<String>,
+
+    #[serde(rename = "Grid", default)]
     #[doc(hidden)]
     pub grids: Vec<Grid>,
 
-    #[serde(rename = "DataItem", skip_serializing_if = "Vec::is_empty")]
+    #[serde(rename = "DataItem", default, skip_serializing_if = "Vec::is_empty")]
     #[doc(hidden)]
     pub data_items: Vec<DataItem>,
+
+    #[serde(
+        rename = "xi:include",
+        alias = "include",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    #[doc(hidden)]
+    pub includes: Vec<XInclude>,
 }
 
 impl Domain {
     /// Create a new domain with a single grid
     pub fn new(grid: Grid) -> Self {
         Self {
+            name: None,
             grids: vec![grid],
             data_items: Vec::new(),
+            includes: Vec::new(),
+        }
+    }
+
+    /// Check that the names of the `DataItems` of the domain are unique, and that the [`Reference`]s of its grids can be resolved.
+    ///
+    /// References that do not refer to a named `DataItem` of the domain (e.g. custom `XPaths`) are not checked.
+    /// If `DataItems` are included from other files, only the uniqueness of the names is checked.
+    pub fn check_references(&self) -> XdmfResult<()> {
+        let mut names = HashSet::new();
+        if let Some(name) = self
+            .data_items
+            .iter()
+            .filter_map(|data_item| data_item.name.as_deref())
+            .find(|name| !names.insert(*name))
+        {
+            return Err(XdmfError::Validation(format!(
+                "Name '{name}' of DataItem is not unique within the domain"
+            )));
+        }
+
+        if !self.includes.is_empty() {
+            return Ok(());
+        }
+
+        let mut data_items: Vec<&DataItem> = self.data_items.iter().collect();
+        for grid in &self.grids {
+            collect_data_items(grid, &mut data_items);
         }
+
+        while let Some(data_item) = data_items.pop() {
+            data_items.extend(&data_item.data_items);
+
+            if let Some(reference) = Reference::of_data_item(data_item)
+                && !names.contains(reference.name())
+            {
+                return Err(XdmfError::Validation(format!(
+                    "Reference to DataItem '{}' can not be resolved within the domain",
+                    reference.name()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// the DataItems of the grid and its children, without their nested DataItems
+fn collect_data_items<'a>(grid: &'a Grid, data_items: &mut Vec<&'a DataItem>) {
+    if let Some(geometry) = &grid.geometry {
+        data_items.extend(&geometry.data_items);
+    }
+    if let Some(topology) = &grid.topology {
+        data_items.extend(&topology.data_item);
+    }
+    if let Some(time) = &grid.time {
+        data_items.extend(&time.data_item);
+    }
+    for attribute in grid.attributes.iter().flatten() {
+        data_items.extend(&attribute.data_items);
+    }
+    for set in grid.sets.iter().flatten() {
+        data_items.extend(&set.data_items);
+    }
+    for child in grid.grids.iter().flatten() {
+        collect_data_items(child, data_items);
     }
 }
 
 /// Cell types as defined in the VTK file format.
 ///
 /// See <https://vtk.org/wp-content/uploads/2015/04/file-formats.pdf> for details.
+/// Besides the cells with a fixed number of points, polygons and polyhedra with an arbitrary number of points are supported.
+/// Their connectivity is written as mixed topology, polyhedra are only supported by readers of XDMF3.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-#[repr(u8)]
 pub enum CellType {
     #[doc(hidden)]
-    Vertex = 1,
+    Vertex,
     #[doc(hidden)]
-    Edge = 2,
+    Edge,
     #[doc(hidden)]
-    Triangle = 4,
+    Triangle,
     #[doc(hidden)]
-    Quadrilateral = 5,
+    Quadrilateral,
     #[doc(hidden)]
-    Tetrahedron = 6,
+    Tetrahedron,
     #[doc(hidden)]
-    Pyramid = 7,
+    Pyramid,
     #[doc(hidden)]
-    Wedge = 8,
+    Wedge,
     #[doc(hidden)]
-    Hexahedron = 9,
+    Hexahedron,
     #[doc(hidden)]
-    Edge3 = 34,
+    Edge3,
     #[doc(hidden)]
-    Quadrilateral9 = 35,
+    Quadrilateral9,
     #[doc(hidden)]
-    Triangle6 = 36,
+    Triangle6,
     #[doc(hidden)]
-    Quadrilateral8 = 37,
+    Quadrilateral8,
     #[doc(hidden)]
-    Tetrahedron10 = 38,
+    Tetrahedron10,
     #[doc(hidden)]
-    Pyramid13 = 39,
+    Pyramid13,
     #[doc(hidden)]
-    Wedge15 = 40,
+    Wedge15,
     #[doc(hidden)]
-    Wedge18 = 41,
+    Wedge18,
     #[doc(hidden)]
-    Hexahedron20 = 48,
+    Hexahedron20,
     #[doc(hidden)]
-    Hexahedron24 = 49,
+    Hexahedron24,
     #[doc(hidden)]
-    Hexahedron27 = 50,
+    Hexahedron27,
+    /// Polygon with the given number of points
+    Polygon(usize),
+    /// Polyhedron, its connectivity is a face stream with the given number of entries, see [`CellType::polyhedron`]
+    Polyhedron(usize),
 }
 
 impl CellType {
     /// The number of points for the given cell type.
+    ///
+    /// For a polyhedron this is the number of entries of its face stream in the connectivity.
     pub fn num_points(&self) -> usize {
         match self {
             Self::Vertex => 1,
@@ -183,8 +403,96 @@ impl CellType {
             Self::Hexahedron20 => 20,
             Self::Hexahedron24 => 24,
             Self::Hexahedron27 => 27,
+            Self::Polygon(num_points) | Self::Polyhedron(num_points) => *num_points,
         }
     }
+
+    /// Create a polyhedron from its faces, returning the cell type and its connectivity.
+    ///
+    /// The connectivity is a face stream, i.e. the number of faces, followed by the number of points and the points of each face.
+    /// ```rust
+    /// use xdmf::CellType;
+    ///
+    /// // a pyramid with a square base
+    /// let (cell_type, connectivity) = CellType::polyhedron(&[
+    ///     &[0, 1, 2, 3],
+    ///     &[0, 1, 4],
+    ///     &[1, 2, 4],
+    ///     &[2, 3, 4],
+    ///     &[3, 0, 4],
+    /// ]);
+    ///
+    /// assert_eq!(cell_type, CellType::Polyhedron(22));
+    /// assert_eq!(connectivity[..6], [5, 4, 0, 1, 2, 3]);
+    /// ```
+    pub fn polyhedron(faces: &[&[u64]]) -> (Self, Vec<u64>) {
+        let mut face_stream = vec![faces.len() as u64];
+
+        for face in faces {
+            face_stream.push(face.len() as u64);
+            face_stream.extend_from_slice(face);
+        }
+
+        (Self::Polyhedron(face_stream.len()), face_stream)
+    }
+
+    // identifier of the cell type in the connectivity of a mixed topology
+    pub(crate) fn xdmf_id(self) -> u64 {
+        match self {
+            Self::Vertex => 1,
+            Self::Edge => 2,
+            Self::Polygon(_) => 3,
+            Self::Triangle => 4,
+            Self::Quadrilateral => 5,
+            Self::Tetrahedron => 6,
+            Self::Pyramid => 7,
+            Self::Wedge => 8,
+            Self::Hexahedron => 9,
+            Self::Polyhedron(_) => 16,
+            Self::Edge3 => 34,
+            Self::Quadrilateral9 => 35,
+            Self::Triangle6 => 36,
+            Self::Quadrilateral8 => 37,
+            Self::Tetrahedron10 => 38,
+            Self::Pyramid13 => 39,
+            Self::Wedge15 => 40,
+            Self::Wedge18 => 41,
+            Self::Hexahedron20 => 48,
+            Self::Hexahedron24 => 49,
+            Self::Hexahedron27 => 50,
+        }
+    }
+
+    // inverse of `xdmf_id`, the number of points is required for polygons and polyhedra
+    #[cfg(any(feature = "hdf5", feature = "ffi"))]
+    pub(crate) fn from_xdmf_id(id: u64, num_points: usize) -> Option<Self> {
+        let cell_type = match id {
+            1 => Self::Vertex,
+            2 => Self::Edge,
+            3 => Self::Polygon(num_points),
+            4 => Self::Triangle,
+            5 => Self::Quadrilateral,
+            6 => Self::Tetrahedron,
+            7 => Self::Pyramid,
+            8 => Self::Wedge,
+            9 => Self::Hexahedron,
+            16 => Self::Polyhedron(num_points),
+            34 => Self::Edge3,
+            35 => Self::Quadrilateral9,
+            36 => Self::Triangle6,
+            37 => Self::Quadrilateral8,
+            38 => Self::Tetrahedron10,
+            39 => Self::Pyramid13,
+            40 => Self::Wedge15,
+            41 => Self::Wedge18,
+            48 => Self::Hexahedron20,
+            49 => Self::Hexahedron24,
+            50 => Self::Hexahedron27,
+            _ => return None,
+        };
+
+        (cell_type.num_points() == num_points).then_some(cell_type)
+    }
 }
 
 #[cfg(test)]
@@ -240,22 +548,23 @@ mod tests {
             "test_grid",
             geometry::Geometry {
                 geometry_type: geometry::GeometryType::XYZ,
-                data_item: data_item::DataItem {
+                data_items: vec![data_item::DataItem {
                     dimensions: Some(dimensions::Dimensions(vec![3])),
                     data: "1.0 2.0 3.0".into(),
                     number_type: Some(data_item::NumberType::Float),
                     ..Default::default()
-                },
+                }],
             },
             topology::Topology {
                 topology_type: topology::TopologyType::Triangle,
                 number_of_elements: "1".to_string(),
-                data_item: data_item::DataItem {
+                dimensions: None,
+                data_item: Some(data_item::DataItem {
                     dimensions: Some(dimensions::Dimensions(vec![3])),
                     number_type: Some(data_item::NumberType::Int),
                     data: "0 1 2".into(),
                     ..Default::default()
-                },
+                }),
             },
         );
         let domain = Domain::new(grid);
@@ -279,4 +588,21 @@ mod tests {
         let domain = Domain::default();
         pretty_assertions::assert_eq!(to_string(&domain).unwrap(), "<Domain/>");
     }
+
+    #[test]
+    fn nested_information_serialization() {
+        let information = Information::new("solver", "fluid")
+            .with_information(Information::new("version", "2.1"));
+
+        let serialized = to_string(&information).unwrap();
+        pretty_assertions::assert_eq!(
+            serialized,
+            "<Information Name=\"solver\" Value=\"fluid\">\
+                <Information Name=\"version\" Value=\"2.1\"/>\
+            </Information>"
+        );
+
+        let deserialized: Information = quick_xml::de::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, information);
+    }
 }
@@ -2,7 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::data_item::DataItem;
+use super::{Information, data_item::DataItem};
+
+/// Name of the `Information` with the names of the components of an attribute, see [`AttributeBuilder::component_names`].
+pub const COMPONENT_NAMES_NAME: &str = "component_names";
 
 /// The Attribute element defines values associated with the mesh.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -11,19 +14,110 @@ pub struct Attribute {
     #[doc(hidden)]
     pub name: String,
 
-    #[serde(rename = "@AttributeType")]
+    #[serde(rename = "@AttributeType", alias = "@Type", default)]
     #[doc(hidden)]
     pub attribute_type: AttributeType,
 
-    #[serde(rename = "@Center")]
+    #[serde(rename = "@Center", default)]
     #[doc(hidden)]
     pub center: Center,
 
+    #[serde(rename = "Information", default, skip_serializing_if = "Vec::is_empty")]
+    #[doc(hidden)]
+    pub information: Vec<Information>,
+
     #[serde(rename = "DataItem")]
     #[doc(hidden)]
     pub data_items: Vec<DataItem>,
 }
 
+impl Attribute {
+    /// Create a builder for an attribute with its metadata, see [`AttributeBuilder`].
+    pub fn builder(
+        name: impl ToString,
+        attribute_type: AttributeType,
+        center: Center,
+    ) -> AttributeBuilder {
+        AttributeBuilder::new(name, attribute_type, center)
+    }
+}
+
+/// Builder for an `Attribute` with its metadata, e.g. units and the names of its components.
+/// ```rust
+/// use xdmf::xdmf_elements::{
+///     Information,
+///     attribute::{AttributeBuilder, AttributeType, Center},
+///     data_item::DataItem,
+/// };
+///
+/// let attribute = AttributeBuilder::new("velocity", AttributeType::Vector, Center::Node)
+///     .name_prefix("fluid/")
+///     .information(Information::new("units", "m/s"))
+///     .component_names(["u", "v", "w"])
+///     .data_item(DataItem::default())
+///     .build();
+///
+/// assert_eq!(attribute.name, "fluid/velocity");
+/// assert_eq!(attribute.information.len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct AttributeBuilder {
+    attribute: Attribute,
+    name_prefix: String,
+}
+
+impl AttributeBuilder {
+    /// Create a new builder for an attribute without data.
+    pub fn new(name: impl ToString, attribute_type: AttributeType, center: Center) -> Self {
+        Self {
+            attribute: Attribute {
+                name: name.to_string(),
+                attribute_type,
+                center,
+                ..Default::default()
+            },
+            name_prefix: String::new(),
+        }
+    }
+
+    /// Prefix of the name, e.g. `"solid/"` and `"fluid/"` to distinguish the fields of the solvers of a coupled simulation.
+    pub fn name_prefix(mut self, name_prefix: impl ToString) -> Self {
+        self.name_prefix = name_prefix.to_string();
+        self
+    }
+
+    /// Add an information, which can contain nested information, see [`Information::with_information`].
+    pub fn information(mut self, information: Information) -> Self {
+        self.attribute.information.push(information);
+        self
+    }
+
+    /// Names of the components, e.g. of a vector, as a hint for readers that label them.
+    ///
+    /// They are written as `Information` named `component_names`, with a nested `Information` named `component` per component in order.
+    pub fn component_names<T: ToString>(mut self, names: impl IntoIterator<Item = T>) -> Self {
+        let component_names = names.into_iter().fold(
+            Information::new(COMPONENT_NAMES_NAME, ""),
+            |information, name| information.with_information(Information::new("component", name)),
+        );
+
+        self.attribute.information.push(component_names);
+        self
+    }
+
+    /// Add a data item with the values of the attribute.
+    pub fn data_item(mut self, data_item: DataItem) -> Self {
+        self.attribute.data_items.push(data_item);
+        self
+    }
+
+    /// Build the attribute.
+    pub fn build(mut self) -> Attribute {
+        self.attribute.name.insert_str(0, &self.name_prefix);
+        self.attribute
+    }
+}
+
 /// Type of the data (scalar, vector, tensor, etc.)
 #[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum AttributeType {
@@ -82,12 +176,34 @@ mod tests {
         assert_eq!(attribute.center, Center::Node);
     }
 
+    #[test]
+    fn attribute_builder() {
+        let attribute = Attribute::builder("displacement", AttributeType::Vector, Center::Node)
+            .name_prefix("solid/")
+            .component_names(["x", "y", "z"])
+            .data_item(DataItem::default())
+            .build();
+
+        pretty_assertions::assert_eq!(
+            to_string(&attribute).unwrap(),
+            "<Attribute Name=\"solid/displacement\" AttributeType=\"Vector\" Center=\"Node\">\
+                <Information Name=\"component_names\" Value=\"\">\
+                    <Information Name=\"component\" Value=\"x\"/>\
+                    <Information Name=\"component\" Value=\"y\"/>\
+                    <Information Name=\"component\" Value=\"z\"/>\
+                </Information>\
+                <DataItem Dimensions=\"1\" NumberType=\"Float\" Format=\"XML\" Precision=\"4\"/>\
+            </Attribute>"
+        );
+    }
+
     #[test]
     fn attribute_serialization() {
         let attribute = Attribute {
             name: String::from("Temperature"),
             attribute_type: AttributeType::Scalar,
             center: Center::Cell,
+            information: vec![],
             data_items: vec![DataItem::default(), DataItem::default()],
         };
 
@@ -100,6 +216,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn attribute_with_information_serialization() {
+        let attribute = Attribute {
+            name: String::from("Temperature"),
+            information: vec![Information::new("units", "K")],
+            data_items: vec![DataItem::default()],
+            ..Default::default()
+        };
+
+        pretty_assertions::assert_eq!(
+            to_string(&attribute).unwrap(),
+            "<Attribute Name=\"Temperature\" AttributeType=\"Scalar\" Center=\"Node\">\
+                <Information Name=\"units\" Value=\"K\"/>\
+                <DataItem Dimensions=\"1\" NumberType=\"Float\" Format=\"XML\" Precision=\"4\"/>\
+            </Attribute>"
+        );
+    }
+
     #[test]
     fn attribute_type_default() {
         assert_eq!(AttributeType::default(), AttributeType::Scalar);
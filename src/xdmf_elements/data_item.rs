@@ -3,14 +3,27 @@
 use serde::{Deserialize, Serialize};
 
 use super::dimensions::Dimensions;
+use crate::{XdmfError, XdmfResult, number_format::array_to_string_fmt};
+
+/// Path of the `DataItems` of the domain, which can be referenced by their name
+const DOMAIN_DATA_ITEM_PATH: &str = "/Xdmf/Domain/DataItem";
 
 /// Core datastructure to define how, where, and in which format data is stored.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "DataItemXml")]
 pub struct DataItem {
     #[serde(rename = "@Name", skip_serializing_if = "Option::is_none")]
     #[doc(hidden)]
     pub name: Option<String>,
 
+    #[serde(rename = "@ItemType", skip_serializing_if = "Option::is_none")]
+    #[doc(hidden)]
+    pub item_type: Option<ItemType>,
+
+    #[serde(rename = "@Function", skip_serializing_if = "Option::is_none")]
+    /// Expression of a `Function` item, referring to its child `DataItems` as `$0`, `$1`, ...
+    pub function: Option<String>,
+
     #[serde(rename = "@Dimensions", skip_serializing_if = "Option::is_none")]
     #[doc(hidden)]
     pub dimensions: Option<Dimensions>,
@@ -27,34 +40,121 @@ pub struct DataItem {
     /// Precision of the data, in bits (e.g. 4 for f32, 8 for f64)
     pub precision: Option<u8>,
 
-    #[serde(flatten)]
+    #[serde(rename = "@Encoding", skip_serializing_if = "Option::is_none")]
+    /// Encoding of inline data, plain text if not set
+    pub encoding: Option<Encoding>,
+
+    #[serde(rename = "@Endian", skip_serializing_if = "Option::is_none")]
+    /// Byte order of [`Format::Binary`] data, native if not set
+    pub endian: Option<Endian>,
+
+    #[serde(flatten, skip_serializing_if = "DataContent::is_empty")]
     #[doc(hidden)]
     pub data: DataContent,
 
     #[serde(rename = "@Reference", skip_serializing_if = "Option::is_none")]
     #[doc(hidden)]
     pub reference: Option<String>,
+
+    #[serde(rename = "DataItem", skip_serializing_if = "Vec::is_empty")]
+    #[doc(hidden)]
+    pub data_items: Vec<Self>,
+}
+
+// quick-xml can not deserialize the flattened `DataContent`, hence the content is read as either text or include.
+// The namespace prefix is stripped by quick-xml, hence the include is matched by its local name.
+#[derive(Deserialize)]
+struct DataItemXml {
+    #[serde(rename = "@Name")]
+    name: Option<String>,
+
+    #[serde(rename = "@ItemType")]
+    item_type: Option<ItemType>,
+
+    #[serde(rename = "@Function")]
+    function: Option<String>,
+
+    #[serde(rename = "@Dimensions")]
+    dimensions: Option<Dimensions>,
+
+    #[serde(rename = "@NumberType")]
+    number_type: Option<NumberType>,
+
+    #[serde(rename = "@Format")]
+    format: Option<Format>,
+
+    #[serde(rename = "@Precision")]
+    precision: Option<u8>,
+
+    #[serde(rename = "@Encoding")]
+    encoding: Option<Encoding>,
+
+    #[serde(rename = "@Endian")]
+    endian: Option<Endian>,
+
+    #[serde(rename = "@Reference")]
+    reference: Option<String>,
+
+    #[serde(rename = "include")]
+    include: Option<XInclude>,
+
+    #[serde(rename = "DataItem", default)]
+    data_items: Vec<DataItem>,
+
+    #[serde(rename = "$text", default)]
+    text: String,
+}
+
+impl From<DataItemXml> for DataItem {
+    fn from(xml: DataItemXml) -> Self {
+        Self {
+            name: xml.name,
+            item_type: xml.item_type,
+            function: xml.function,
+            dimensions: xml.dimensions,
+            number_type: xml.number_type,
+            format: xml.format,
+            precision: xml.precision,
+            encoding: xml.encoding,
+            endian: xml.endian,
+            data: xml.include.map_or_else(|| xml.text.into(), Into::into),
+            reference: xml.reference,
+            data_items: xml.data_items,
+        }
+    }
 }
 
 impl Default for DataItem {
     fn default() -> Self {
         Self {
             name: None,
+            item_type: None,
+            encoding: None,
+            endian: None,
+            function: None,
             dimensions: Some(Dimensions(vec![1])),
             number_type: Some(NumberType::default()),
             format: Some(Format::default()),
             precision: Some(4),
             data: String::new().into(),
             reference: None,
+            data_items: vec![],
         }
     }
 }
 
 impl DataItem {
     /// Create a new data item that references another data item
+    #[deprecated(
+        note = "use `Reference`, which requires a named source instead of referencing `MISSING`"
+    )]
     pub fn new_reference(source: &Self, source_path: &str) -> Self {
         Self {
             name: None,
+            item_type: None,
+            encoding: None,
+            endian: None,
+            function: None,
             dimensions: None,
             number_type: None,
             format: None,
@@ -66,8 +166,247 @@ impl DataItem {
             )
             .into(),
             reference: Some("XML".to_string()),
+            data_items: vec![],
+        }
+    }
+
+    /// Create a new data item whose values are computed by the reader with the given function, e.g. `"$0 * $1"`.
+    ///
+    /// The arguments of the function (`$0`, `$1`, ...) are the given data items, in that order.
+    pub fn new_function(
+        function: impl ToString,
+        dimensions: Dimensions,
+        data_items: Vec<Self>,
+    ) -> Self {
+        Self {
+            name: None,
+            item_type: Some(ItemType::Function),
+            encoding: None,
+            endian: None,
+            function: Some(function.to_string()),
+            dimensions: Some(dimensions),
+            number_type: None,
+            format: None,
+            precision: None,
+            data: String::new().into(),
+            reference: None,
+            data_items,
         }
     }
+
+    /// Create a new data item that selects the values `start..start + count` of the one-dimensional data of the given item.
+    ///
+    /// The given item describes the full data, e.g. a dataset that was written collectively by multiple ranks,
+    /// its name and dimensions are moved to the new item and `len` is the length of the full data.
+    pub fn new_hyperslab(source: Self, start: usize, count: usize, len: usize) -> Self {
+        Self::hyperslab(source, &[start], &[count], vec![len])
+    }
+
+    /// Create a new data item that selects a row of two-dimensional data with `num_rows` rows, e.g. the values of a time step
+    /// in a dataset with one row per time step.
+    ///
+    /// The given item describes the values of the row, its name and dimensions are moved to the new item.
+    pub fn new_hyperslab_row(source: Self, row: usize, num_rows: usize) -> Self {
+        let row_len = source
+            .dimensions
+            .as_ref()
+            .map_or(0, |dimensions| dimensions.0.iter().product());
+
+        Self::hyperslab(source, &[row, 0], &[1, row_len], vec![num_rows, row_len])
+    }
+
+    /// Create a new data item that selects the values of the given item at the given coordinates, e.g. the values of some cells.
+    ///
+    /// The coordinates hold the index of each selected value per dimension of the given item, e.g. the row and column
+    /// of the selected values of two-dimensional data. The name of the given item is moved to the new item,
+    /// the dimensions are those of the selected values.
+    pub fn new_coordinates(source: Self, coordinates: Self, dimensions: Dimensions) -> Self {
+        Self {
+            name: source.name.clone(),
+            item_type: Some(ItemType::Coordinates),
+            encoding: None,
+            endian: None,
+            function: None,
+            dimensions: Some(dimensions),
+            number_type: None,
+            format: None,
+            precision: None,
+            data: String::new().into(),
+            reference: None,
+            data_items: vec![
+                coordinates,
+                Self {
+                    name: None,
+                    ..source
+                },
+            ],
+        }
+    }
+
+    // select `count` values from `start` per dimension (with stride 1) of the source with the given full dimensions
+    fn hyperslab(
+        source: Self,
+        start: &[usize],
+        count: &[usize],
+        full_dimensions: Vec<usize>,
+    ) -> Self {
+        let strides = vec![1; start.len()];
+        let selection = Self {
+            dimensions: Some(Dimensions(vec![3, start.len()])),
+            number_type: Some(NumberType::UInt),
+            precision: Some(8),
+            data: array_to_string_fmt(&[start, &strides, count].concat()).into(),
+            ..Default::default()
+        };
+
+        Self {
+            name: source.name.clone(),
+            item_type: Some(ItemType::HyperSlab),
+            encoding: None,
+            endian: None,
+            function: None,
+            dimensions: source.dimensions.clone(),
+            number_type: None,
+            format: None,
+            precision: None,
+            data: String::new().into(),
+            reference: None,
+            data_items: vec![
+                selection,
+                Self {
+                    name: None,
+                    dimensions: Some(Dimensions(full_dimensions)),
+                    ..source
+                },
+            ],
+        }
+    }
+}
+
+/// Reference to a named `DataItem` of the domain, which is shared instead of repeating its data.
+///
+/// Created with [`Reference::builder`], and converted into the referencing `DataItem` with `DataItem::from`.
+/// When the XDMF is written with [`Xdmf::write_to`](super::Xdmf::write_to), it is validated that the names of the `DataItems`
+/// of each domain are unique and that each reference can be resolved.
+/// ```rust
+/// use xdmf::xdmf_elements::data_item::{DataItem, Reference};
+///
+/// let coords = DataItem {
+///     name: Some("coords".to_string()),
+///     ..Default::default()
+/// };
+///
+/// let reference = Reference::builder()
+///     .source(&coords)
+///     .build()
+///     .expect("failed to create reference");
+/// assert_eq!(reference.xpath(), "/Xdmf/Domain/DataItem[@Name=\"coords\"]");
+///
+/// let data_item = DataItem::from(reference);
+///
+/// // the source must have a name
+/// assert!(
+///     Reference::builder()
+///         .source(&DataItem::default())
+///         .build()
+///         .is_err()
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reference {
+    name: String,
+}
+
+impl Reference {
+    /// Create a builder for a reference.
+    pub fn builder<'a>() -> ReferenceBuilder<'a> {
+        ReferenceBuilder::default()
+    }
+
+    /// Name of the referenced `DataItem`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// `XPath` of the referenced `DataItem`, as written to the XDMF file.
+    pub fn xpath(&self) -> String {
+        format!("{DOMAIN_DATA_ITEM_PATH}[@Name=\"{}\"]", self.name)
+    }
+
+    /// The reference of a referencing `DataItem`, if it references a named `DataItem` of the domain.
+    pub fn of_data_item(data_item: &DataItem) -> Option<Self> {
+        match (&data_item.reference, &data_item.data) {
+            (Some(reference), DataContent::Raw(xpath)) if reference == "XML" => {
+                Self::from_xpath(xpath)
+            }
+            _ => None,
+        }
+    }
+
+    // parse the XPath of a reference to a named DataItem of the domain, as written by `Reference::xpath`
+    pub(crate) fn from_xpath(xpath: &str) -> Option<Self> {
+        xpath
+            .trim()
+            .strip_prefix(DOMAIN_DATA_ITEM_PATH)?
+            .strip_prefix("[@Name=\"")?
+            .strip_suffix("\"]")
+            .map(|name| Self {
+                name: name.to_string(),
+            })
+    }
+}
+
+impl From<Reference> for DataItem {
+    fn from(reference: Reference) -> Self {
+        Self {
+            name: None,
+            item_type: None,
+            encoding: None,
+            endian: None,
+            function: None,
+            dimensions: None,
+            number_type: None,
+            format: None,
+            precision: None,
+            data: reference.xpath().into(),
+            reference: Some("XML".to_string()),
+            data_items: vec![],
+        }
+    }
+}
+
+/// Builder for a [`Reference`], created with [`Reference::builder`].
+#[derive(Clone, Debug, Default)]
+pub struct ReferenceBuilder<'a> {
+    source: Option<&'a DataItem>,
+}
+
+impl<'a> ReferenceBuilder<'a> {
+    /// The referenced `DataItem`, which must have a name.
+    pub fn source(mut self, source: &'a DataItem) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Create the reference, which fails if no source is given or if the source can not be referenced by its name.
+    pub fn build(self) -> XdmfResult<Reference> {
+        let source = self.source.ok_or_else(|| {
+            XdmfError::Validation("The source of the reference must be given".into())
+        })?;
+
+        let name = source.name.as_ref().ok_or_else(|| {
+            XdmfError::Validation("The source of the reference must have a name".into())
+        })?;
+
+        // the name is quoted in the XPath
+        if name.is_empty() || name.contains('"') {
+            return Err(XdmfError::Validation(format!(
+                "Name '{name}' of the source of the reference is not valid, it must be non-empty and must not contain quotes"
+            )));
+        }
+
+        Ok(Reference { name: name.clone() })
+    }
 }
 
 /// Used to include data from an external file using `XInclude`
@@ -81,6 +420,10 @@ pub struct XInclude {
     #[serde(rename = "@parse", skip_serializing_if = "Option::is_none")]
     #[doc(hidden)]
     parse: Option<String>,
+
+    #[serde(rename = "@xpointer", skip_serializing_if = "Option::is_none")]
+    #[doc(hidden)]
+    xpointer: Option<String>,
 }
 
 impl XInclude {
@@ -89,6 +432,37 @@ impl XInclude {
         Self {
             file_path: file_path.to_string(),
             parse: include_as_text.then(|| "text".to_string()), // xml is default
+            xpointer: None,
+        }
+    }
+
+    /// Create a new `XInclude` instance that includes the xml elements selected by an `XPointer` expression
+    pub fn new_xpointer(file_path: impl ToString, xpath: &str) -> Self {
+        Self {
+            file_path: file_path.to_string(),
+            parse: None,
+            xpointer: Some(format!("xpointer({xpath})")),
+        }
+    }
+
+    /// Path of the included file
+    pub fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    /// Whether the file is included as text (instead of xml)
+    pub fn is_text(&self) -> bool {
+        self.parse.as_deref() == Some("text")
+    }
+
+    // replaces the path of the included file, returns whether it was replaced
+    pub(crate) fn rebase(&mut self, rebase_path: impl FnOnce(&str) -> Option<String>) -> bool {
+        match rebase_path(&self.file_path) {
+            Some(file_path) => {
+                self.file_path = file_path;
+                true
+            }
+            None => false,
         }
     }
 }
@@ -105,6 +479,13 @@ pub enum DataContent {
     Include(XInclude),
 }
 
+impl DataContent {
+    // a function item has no content of its own, only child items
+    fn is_empty(&self) -> bool {
+        matches!(self, Self::Raw(text) if text.is_empty())
+    }
+}
+
 impl From<String> for DataContent {
     fn from(data: String) -> Self {
         Self::Raw(data)
@@ -123,6 +504,24 @@ impl From<XInclude> for DataContent {
     }
 }
 
+/// Type of a data item, e.g. plain values or values computed by a function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ItemType {
+    #[default]
+    #[doc(hidden)]
+    Uniform,
+    #[doc(hidden)]
+    Collection,
+    #[doc(hidden)]
+    Tree,
+    #[doc(hidden)]
+    HyperSlab,
+    #[doc(hidden)]
+    Coordinates,
+    #[doc(hidden)]
+    Function,
+}
+
 /// Specifies the type of data stored, such as f64 or i32.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum NumberType {
@@ -139,6 +538,36 @@ pub enum NumberType {
     UChar,
 }
 
+/// Encoding of data that is stored inline in the XML file.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Encoding {
+    /// The values are stored as little-endian bytes, encoded as base64
+    Base64,
+}
+
+/// Byte order of the values of [`Format::Binary`] data, see [`Values::write_binary`](crate::Values::write_binary).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Endian {
+    /// Byte order of the machine the data is read on
+    #[default]
+    Native,
+    /// Least significant byte first, e.g. x86-64 and most ARM machines
+    Little,
+    /// Most significant byte first
+    Big,
+}
+
+impl Endian {
+    /// Whether the values are stored with the least significant byte first, resolving [`Endian::Native`] for this machine.
+    pub fn is_little(self) -> bool {
+        match self {
+            Self::Native => cfg!(target_endian = "little"),
+            Self::Little => true,
+            Self::Big => false,
+        }
+    }
+}
+
 /// The format in which the heavy data is stored.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum Format {
@@ -195,6 +624,11 @@ mod tests {
             precision: Some(8),
             data: "custom_data".to_string().into(),
             reference: None,
+            item_type: None,
+            encoding: None,
+            endian: None,
+            function: None,
+            data_items: vec![],
         };
         assert_eq!(custom_item.name, Some("custom_data_item".to_string()));
         assert_eq!(custom_item.dimensions, Some(Dimensions(vec![2, 3])));
@@ -212,7 +646,13 @@ mod tests {
             ..Default::default()
         };
 
-        let ref_item = DataItem::new_reference(&source_data_item, "/Xdmf/Domain/DataItem");
+        let reference = Reference::builder()
+            .source(&source_data_item)
+            .build()
+            .unwrap();
+        assert_eq!(reference.name(), "source_data_item");
+
+        let ref_item = DataItem::from(reference.clone());
 
         assert!(ref_item.name.is_none());
         assert!(ref_item.dimensions.is_none());
@@ -224,6 +664,37 @@ mod tests {
             "/Xdmf/Domain/DataItem[@Name=\"source_data_item\"]".into()
         );
         assert_eq!(ref_item.reference, Some("XML".to_string()));
+        assert_eq!(Reference::of_data_item(&ref_item), Some(reference));
+        assert_eq!(Reference::of_data_item(&source_data_item), None);
+    }
+
+    #[test]
+    fn reference_invalid_source() {
+        assert_eq!(
+            Reference::builder().build().unwrap_err().to_string(),
+            "The source of the reference must be given"
+        );
+
+        assert_eq!(
+            Reference::builder()
+                .source(&DataItem::default())
+                .build()
+                .unwrap_err()
+                .to_string(),
+            "The source of the reference must have a name"
+        );
+
+        assert_eq!(
+            Reference::builder()
+                .source(&DataItem {
+                    name: Some("a\"b".to_string()),
+                    ..Default::default()
+                })
+                .build()
+                .unwrap_err()
+                .to_string(),
+            "Name 'a\"b' of the source of the reference is not valid, it must be non-empty and must not contain quotes"
+        );
     }
 
     #[test]
@@ -236,6 +707,11 @@ mod tests {
             precision: Some(8),
             data: "custom_data".to_string().into(),
             reference: None,
+            item_type: None,
+            encoding: None,
+            endian: None,
+            function: None,
+            data_items: vec![],
         };
 
         pretty_assertions::assert_eq!(
@@ -246,6 +722,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn data_item_binary_endian() {
+        let data_item = DataItem {
+            format: Some(Format::Binary),
+            endian: Some(Endian::Big),
+            data: "values.bin".to_string().into(),
+            ..Default::default()
+        };
+
+        let serialized = to_string(&XmlRoot { data_item }).unwrap();
+        pretty_assertions::assert_eq!(
+            serialized,
+            "<XmlRoot>\
+            <DataItem Dimensions=\"1\" NumberType=\"Float\" Format=\"Binary\" Precision=\"4\" Endian=\"Big\">values.bin</DataItem>\
+            </XmlRoot>"
+        );
+
+        let data_item: DataItem = quick_xml::de::from_str(
+            "<DataItem Format=\"Binary\" Endian=\"Little\">values.bin</DataItem>",
+        )
+        .unwrap();
+        assert_eq!(data_item.endian, Some(Endian::Little));
+
+        assert_eq!(Endian::Native.is_little(), cfg!(target_endian = "little"));
+    }
+
     #[test]
     fn data_item_reference_serialize() {
         let source_data_item = DataItem {
@@ -253,7 +755,11 @@ mod tests {
             ..Default::default()
         };
 
-        let ref_item = DataItem::new_reference(&source_data_item, "/Xdmf/Domain/DataItem");
+        let ref_item: DataItem = Reference::builder()
+            .source(&source_data_item)
+            .build()
+            .unwrap()
+            .into();
 
         pretty_assertions::assert_eq!(
             to_string(&XmlRoot {
@@ -276,6 +782,11 @@ mod tests {
             precision: Some(8),
             data: XInclude::new("coords.txt".to_string(), true).into(),
             reference: None,
+            item_type: None,
+            encoding: None,
+            endian: None,
+            function: None,
+            data_items: vec![],
         };
         assert_eq!(custom_item.name, Some("custom_data_item".to_string()));
         assert_eq!(custom_item.dimensions, Some(Dimensions(vec![2, 3])));
@@ -301,6 +812,172 @@ mod tests {
         );
     }
 
+    #[test]
+    fn data_item_function_serialize() {
+        let argument = DataItem {
+            dimensions: Some(Dimensions(vec![2])),
+            data: "1 2".into(),
+            ..Default::default()
+        };
+
+        let function_item = DataItem::new_function(
+            "$0 * $1",
+            Dimensions(vec![2]),
+            vec![argument.clone(), argument],
+        );
+
+        let serialized = to_string(&XmlRoot {
+            data_item: function_item,
+        })
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            serialized,
+            "<XmlRoot>\
+                <DataItem ItemType=\"Function\" Function=\"$0 * $1\" Dimensions=\"2\">\
+                    <DataItem Dimensions=\"2\" NumberType=\"Float\" Format=\"XML\" Precision=\"4\">1 2</DataItem>\
+                    <DataItem Dimensions=\"2\" NumberType=\"Float\" Format=\"XML\" Precision=\"4\">1 2</DataItem>\
+                </DataItem>\
+            </XmlRoot>"
+        );
+
+        let data_item: DataItem = quick_xml::de::from_str(
+            serialized
+                .trim_start_matches("<XmlRoot>")
+                .trim_end_matches("</XmlRoot>"),
+        )
+        .unwrap();
+
+        assert_eq!(data_item.item_type, Some(ItemType::Function));
+        assert_eq!(data_item.function, Some("$0 * $1".to_string()));
+        assert_eq!(data_item.data_items.len(), 2);
+        assert_eq!(data_item.data_items[1].data, "1 2".into());
+    }
+
+    #[test]
+    fn data_item_hyperslab_serialize() {
+        let source = DataItem {
+            name: Some("coords".to_string()),
+            dimensions: Some(Dimensions(vec![2, 3])),
+            format: Some(Format::HDF),
+            precision: Some(8),
+            data: "mesh.h5:/mesh/points".into(),
+            ..Default::default()
+        };
+
+        let serialized = to_string(&XmlRoot {
+            data_item: DataItem::new_hyperslab(source, 6, 6, 18),
+        })
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            serialized,
+            "<XmlRoot>\
+                <DataItem Name=\"coords\" ItemType=\"HyperSlab\" Dimensions=\"2 3\">\
+                    <DataItem Dimensions=\"3 1\" NumberType=\"UInt\" Format=\"XML\" Precision=\"8\">6 1 6</DataItem>\
+                    <DataItem Dimensions=\"18\" NumberType=\"Float\" Format=\"HDF\" Precision=\"8\">mesh.h5:/mesh/points</DataItem>\
+                </DataItem>\
+            </XmlRoot>"
+        );
+
+        // the row of a time step in a dataset with one row per time step
+        let source = DataItem {
+            dimensions: Some(Dimensions(vec![2, 3])),
+            format: Some(Format::HDF),
+            precision: Some(8),
+            data: "results.h5:/data/node/velocity".into(),
+            ..Default::default()
+        };
+
+        let serialized = to_string(&XmlRoot {
+            data_item: DataItem::new_hyperslab_row(source, 1, 4),
+        })
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            serialized,
+            "<XmlRoot>\
+                <DataItem ItemType=\"HyperSlab\" Dimensions=\"2 3\">\
+                    <DataItem Dimensions=\"3 2\" NumberType=\"UInt\" Format=\"XML\" Precision=\"8\">1 0 1 1 1 6</DataItem>\
+                    <DataItem Dimensions=\"4 6\" NumberType=\"Float\" Format=\"HDF\" Precision=\"8\">results.h5:/data/node/velocity</DataItem>\
+                </DataItem>\
+            </XmlRoot>"
+        );
+    }
+
+    #[test]
+    fn data_item_coordinates_serialize() {
+        let source = DataItem {
+            name: Some("pressure".to_string()),
+            dimensions: Some(Dimensions(vec![3, 2])),
+            format: Some(Format::HDF),
+            precision: Some(8),
+            data: "results.h5:/data/cell/pressure".into(),
+            ..Default::default()
+        };
+        let coordinates = DataItem {
+            dimensions: Some(Dimensions(vec![2, 2])),
+            number_type: Some(NumberType::UInt),
+            precision: Some(8),
+            data: "2 0 2 1".into(),
+            ..Default::default()
+        };
+
+        let serialized = to_string(&XmlRoot {
+            data_item: DataItem::new_coordinates(source, coordinates, Dimensions(vec![1, 2])),
+        })
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            serialized,
+            "<XmlRoot>\
+                <DataItem Name=\"pressure\" ItemType=\"Coordinates\" Dimensions=\"1 2\">\
+                    <DataItem Dimensions=\"2 2\" NumberType=\"UInt\" Format=\"XML\" Precision=\"8\">2 0 2 1</DataItem>\
+                    <DataItem Dimensions=\"3 2\" NumberType=\"Float\" Format=\"HDF\" Precision=\"8\">results.h5:/data/cell/pressure</DataItem>\
+                </DataItem>\
+            </XmlRoot>"
+        );
+
+        let data_item: DataItem = quick_xml::de::from_str(
+            serialized
+                .trim_start_matches("<XmlRoot>")
+                .trim_end_matches("</XmlRoot>"),
+        )
+        .unwrap();
+
+        assert_eq!(data_item.item_type, Some(ItemType::Coordinates));
+        assert_eq!(data_item.data_items.len(), 2);
+    }
+
+    #[test]
+    fn data_item_deserialize() {
+        let data_item: DataItem = quick_xml::de::from_str(
+            "<DataItem Name=\"coords\" Dimensions=\"2 3\" NumberType=\"Float\" Format=\"XML\" Precision=\"8\">0 1 2 3 4 5</DataItem>",
+        )
+        .unwrap();
+
+        assert_eq!(data_item.name, Some("coords".to_string()));
+        assert_eq!(data_item.dimensions, Some(Dimensions(vec![2, 3])));
+        assert_eq!(data_item.precision, Some(8));
+        assert_eq!(data_item.data, "0 1 2 3 4 5".into());
+
+        let data_item: DataItem = quick_xml::de::from_str(
+            "<DataItem Dimensions=\"6\"><xi:include href=\"coords.txt\" parse=\"text\"/></DataItem>",
+        )
+        .unwrap();
+
+        assert_eq!(data_item.data, XInclude::new("coords.txt", true).into());
+        assert!(data_item.number_type.is_none());
+
+        let data_item: DataItem = quick_xml::de::from_str(
+            "<DataItem Reference=\"XML\">/Xdmf/Domain/DataItem[@Name=\"coords\"]</DataItem>",
+        )
+        .unwrap();
+
+        assert_eq!(data_item.reference, Some("XML".to_string()));
+        assert!(data_item.dimensions.is_none());
+    }
+
     #[test]
     fn xinclude_serialize() {
         pretty_assertions::assert_eq!(
@@ -312,4 +989,16 @@ mod tests {
             "<xi:include href=\"coords.txt\" parse=\"text\"/>"
         );
     }
+
+    #[test]
+    fn xinclude_xpointer_serialize() {
+        pretty_assertions::assert_eq!(
+            to_string(&XInclude::new_xpointer(
+                "mesh.xdmf2",
+                "/Xdmf/Domain/DataItem"
+            ))
+            .unwrap(),
+            "<xi:include href=\"mesh.xdmf2\" xpointer=\"xpointer(/Xdmf/Domain/DataItem)\"/>"
+        );
+    }
 }
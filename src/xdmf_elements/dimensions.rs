@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::number_format::{format_dimensions, parse_dimensions};
+
 /// Represents the dimensions of a data array in XDMF format.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Dimensions(pub Vec<usize>);
@@ -11,13 +13,7 @@ impl Serialize for Dimensions {
     where
         S: serde::Serializer,
     {
-        let s = self
-            .0
-            .iter()
-            .map(|n| n.to_string())
-            .collect::<Vec<_>>()
-            .join(" ");
-        serializer.serialize_str(&s)
+        serializer.serialize_str(&format_dimensions(&self.0))
     }
 }
 
@@ -27,11 +23,9 @@ impl<'de> Deserialize<'de> for Dimensions {
         D: serde::Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        let dimensions = s
-            .split_whitespace()
-            .map(|n| n.parse().map_err(serde::de::Error::custom))
-            .collect::<Result<Vec<_>, _>>()?;
-        Ok(Self(dimensions))
+        parse_dimensions(&s)
+            .map(Self)
+            .map_err(serde::de::Error::custom)
     }
 }
 
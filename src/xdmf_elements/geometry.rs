@@ -7,16 +7,16 @@ use super::data_item::DataItem;
 /// The Geometry element describes the XYZ values of the mesh points.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Geometry {
-    #[serde(rename = "@GeometryType")]
+    #[serde(rename = "@GeometryType", alias = "@Type", default)]
     #[doc(hidden)]
     pub geometry_type: GeometryType,
 
+    /// One `DataItem` with all coordinates, or one per direction for [`GeometryType::SeparateXYZ`] and [`GeometryType::VXVYVZ`]
     #[serde(rename = "DataItem")]
-    #[doc(hidden)]
-    pub data_item: DataItem,
+    pub data_items: Vec<DataItem>,
 }
 
-/// Type of geometry, either 3D (XYZ) or 2D (XY).
+/// Type of geometry, either 3D (XYZ) or 2D (XY), with the coordinates interleaved or as separate arrays.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum GeometryType {
     #[default]
@@ -24,6 +24,25 @@ pub enum GeometryType {
     XYZ,
     #[doc(hidden)]
     XY,
+    /// Separate arrays of the x, y and z coordinates of the points
+    #[serde(rename = "X_Y_Z")]
+    SeparateXYZ,
+    /// Separate arrays of the x, y and z coordinates of the axes of a rectilinear grid
+    VXVYVZ,
+    /// Origin and spacing (both in ZYX order) of an image, used with [`super::topology::TopologyType::CoRectMesh3D`]
+    #[serde(rename = "ORIGIN_DXDYDZ")]
+    OriginDxDyDz,
+}
+
+impl GeometryType {
+    /// Number of `DataItems` of a Geometry with this type.
+    pub fn num_data_items(self) -> usize {
+        match self {
+            Self::XYZ | Self::XY => 1,
+            Self::OriginDxDyDz => 2,
+            Self::SeparateXYZ | Self::VXVYVZ => 3,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -31,17 +50,46 @@ mod tests {
     use quick_xml::se::to_string;
 
     use super::*;
+    use crate::xdmf_elements::dimensions::Dimensions;
 
     #[test]
     fn geometry_type_default() {
         assert_eq!(GeometryType::default(), GeometryType::XYZ);
     }
 
+    #[test]
+    fn geometry_separate_serialization() {
+        let data_item = DataItem {
+            dimensions: Some(Dimensions(vec![2])),
+            data: "0 1".into(),
+            ..Default::default()
+        };
+
+        let geometry = Geometry {
+            geometry_type: GeometryType::SeparateXYZ,
+            data_items: vec![data_item.clone(), data_item.clone(), data_item],
+        };
+
+        let serialized = to_string(&geometry).unwrap();
+        pretty_assertions::assert_eq!(
+            serialized,
+            "<Geometry GeometryType=\"X_Y_Z\">\
+            <DataItem Dimensions=\"2\" NumberType=\"Float\" Format=\"XML\" Precision=\"4\">0 1</DataItem>\
+            <DataItem Dimensions=\"2\" NumberType=\"Float\" Format=\"XML\" Precision=\"4\">0 1</DataItem>\
+            <DataItem Dimensions=\"2\" NumberType=\"Float\" Format=\"XML\" Precision=\"4\">0 1</DataItem>\
+            </Geometry>"
+        );
+
+        let deserialized: Geometry = quick_xml::de::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.geometry_type, GeometryType::SeparateXYZ);
+        assert_eq!(deserialized.data_items.len(), 3);
+    }
+
     #[test]
     fn geometry_serialization() {
         let geometry = Geometry {
             geometry_type: GeometryType::XY,
-            data_item: DataItem::default(),
+            data_items: vec![DataItem::default()],
         };
 
         pretty_assertions::assert_eq!(
@@ -1,17 +1,28 @@
 //! This module contains the Grid element, which specifies (a port of) the computational domain.
 
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
-use super::{attribute::Attribute, geometry::Geometry, topology::Topology};
+use super::{
+    Information,
+    attribute::Attribute,
+    data_item::{DataItem, NumberType, XInclude},
+    dimensions::Dimensions,
+    geometry::Geometry,
+    set::Set,
+    topology::Topology,
+};
 
 /// Definition of a grid, can be a uniform grid, or a composition of grids.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Grid {
-    #[serde(rename = "@Name")]
+    // grids referencing another grid do not need a name
+    #[serde(rename = "@Name", default)]
     #[doc(hidden)]
     pub name: String,
 
-    #[serde(rename = "@GridType")]
+    #[serde(rename = "@GridType", default)]
     #[doc(hidden)]
     pub grid_type: GridType,
 
@@ -19,6 +30,18 @@ pub struct Grid {
     #[doc(hidden)]
     pub collection_type: Option<CollectionType>,
 
+    /// Part of the referenced grid that a `SubSet` grid consists of
+    #[serde(rename = "@Section", skip_serializing_if = "Option::is_none")]
+    pub section: Option<Section>,
+
+    #[serde(rename = "@Reference", skip_serializing_if = "Option::is_none")]
+    #[doc(hidden)]
+    pub reference: Option<String>,
+
+    /// Indices of the cells of the referenced grid that a `SubSet` grid with `Section::DataItem` consists of
+    #[serde(rename = "DataItem", skip_serializing_if = "Option::is_none")]
+    pub data_item: Option<DataItem>,
+
     #[serde(rename = "Geometry", skip_serializing_if = "Option::is_none")]
     #[doc(hidden)]
     pub geometry: Option<Geometry>,
@@ -31,13 +54,43 @@ pub struct Grid {
     #[doc(hidden)]
     pub grids: Option<Vec<Self>>,
 
+    #[serde(
+        rename = "xi:include",
+        alias = "include",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[doc(hidden)]
+    pub includes: Option<Vec<XInclude>>,
+
     #[serde(rename = "Time", skip_serializing_if = "Option::is_none")]
     #[doc(hidden)]
     pub time: Option<Time>,
 
+    /// Custom information of the grid, e.g. the cycle of a time step
+    #[serde(rename = "Information", default, skip_serializing_if = "Vec::is_empty")]
+    pub information: Vec<Information>,
+
     #[serde(rename = "Attribute", skip_serializing_if = "Option::is_none")]
     #[doc(hidden)]
-    pub attributes: Option<Vec<Attribute>>,
+    pub attributes: Option<Vec<Arc<Attribute>>>,
+
+    /// Groups of entities of the grid, e.g. the nodes of a boundary
+    #[serde(rename = "Set", skip_serializing_if = "Option::is_none")]
+    pub sets: Option<Vec<Set>>,
+
+    /// `XPath` of the referenced grid, if this grid is a reference (e.g. to the grid of a `SubSet`)
+    #[serde(rename = "$text", default, skip_serializing_if = "Option::is_none")]
+    pub reference_path: Option<String>,
+}
+
+/// Part of the referenced grid that a `SubSet` grid consists of.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Section {
+    /// The cells with the indices given by the `DataItem` of the subset
+    #[default]
+    DataItem,
+    /// All cells of the referenced grid, e.g. to add attributes to it
+    All,
 }
 
 /// The Time element is a child of the Grid element and specifies the temporal information for the grid.
@@ -45,18 +98,59 @@ pub struct Grid {
 ///  Represented as string, such that the user has to make the decision about formatting.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Time {
-    #[serde(rename = "@Value")]
+    #[serde(rename = "@TimeType", skip_serializing_if = "Option::is_none")]
+    /// Type of the time, a single value if not set
+    pub time_type: Option<TimeType>,
+
+    #[serde(rename = "@Value", skip_serializing_if = "Option::is_none")]
     #[doc(hidden)]
-    pub value: String,
+    pub value: Option<String>,
+
+    #[serde(rename = "DataItem", skip_serializing_if = "Option::is_none")]
+    /// Values of the times, e.g. one per grid of a temporal collection for `TimeType::List`
+    pub data_item: Option<DataItem>,
 }
 
 impl Time {
     /// Create a new time instance
     pub fn new(value: impl ToString) -> Self {
         Self {
-            value: value.to_string(),
+            time_type: None,
+            value: Some(value.to_string()),
+            data_item: None,
         }
     }
+
+    /// Create a new time instance with a list of values, one per grid of the temporal collection it belongs to
+    pub fn new_list<T: ToString>(values: impl IntoIterator<Item = T>) -> Self {
+        let values: Vec<String> = values.into_iter().map(|value| value.to_string()).collect();
+
+        Self {
+            time_type: Some(TimeType::List),
+            value: None,
+            data_item: Some(DataItem {
+                dimensions: Some(Dimensions(vec![values.len()])),
+                number_type: Some(NumberType::Float),
+                precision: Some(8),
+                data: values.join(" ").into(),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+/// Type of the Time element, i.e. how the values are given.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum TimeType {
+    #[default]
+    #[doc(hidden)]
+    Single,
+    #[doc(hidden)]
+    HyperSlab,
+    #[doc(hidden)]
+    List,
+    #[doc(hidden)]
+    Range,
 }
 
 impl Grid {
@@ -69,8 +163,15 @@ impl Grid {
             geometry: Some(geometry),
             topology: Some(topology),
             grids: None,
+            includes: None,
             time: None,
+            information: vec![],
             attributes: None,
+            sets: None,
+            section: None,
+            reference: None,
+            data_item: None,
+            reference_path: None,
         }
     }
 
@@ -88,7 +189,14 @@ impl Grid {
             topology: None,
             attributes: None,
             grids,
+            includes: None,
             time: None,
+            information: vec![],
+            sets: None,
+            section: None,
+            reference: None,
+            data_item: None,
+            reference_path: None,
         }
     }
 
@@ -99,14 +207,69 @@ impl Grid {
             grid_type: GridType::Tree,
             collection_type: None,
             grids,
+            includes: None,
             geometry: None,
             topology: None,
             attributes: None,
             time: None,
+            information: vec![],
+            sets: None,
+            section: None,
+            reference: None,
+            data_item: None,
+            reference_path: None,
         }
     }
+
+    /// Create a new subset grid of the grid with the given `XPath`, e.g. `/Xdmf/Domain/Grid[@Name="mesh"]`.
+    ///
+    /// The subset consists of the cells of the referenced grid with the given indices, or of all its cells if no indices are given.
+    /// Its geometry and topology are taken from the referenced grid, hence only the attributes of the subset are added to it.
+    pub fn new_subset(
+        name: impl ToString,
+        reference_path: impl ToString,
+        cell_indices: Option<DataItem>,
+    ) -> Self {
+        let mut reference = Self::new_tree(name.to_string(), None);
+        reference.grid_type = GridType::Uniform;
+        reference.reference = Some("XML".into());
+        reference.reference_path = Some(reference_path.to_string());
+
+        Self {
+            name: name.to_string(),
+            grid_type: GridType::SubSet,
+            collection_type: None,
+            section: Some(if cell_indices.is_some() {
+                Section::DataItem
+            } else {
+                Section::All
+            }),
+            reference: None,
+            data_item: cell_indices,
+            geometry: None,
+            topology: None,
+            grids: Some(vec![reference]),
+            includes: None,
+            time: None,
+            information: vec![],
+            attributes: None,
+            sets: None,
+            reference_path: None,
+        }
+    }
+
+    /// The cycle (e.g. the solver iteration) of the grid of a time step, stored as `Information` named `Cycle`.
+    pub fn cycle(&self) -> Option<u64> {
+        self.information
+            .iter()
+            .find(|information| information.name == CYCLE_INFORMATION_NAME)
+            .and_then(|information| information.value.parse().ok())
+    }
 }
 
+/// Name of the `Information` holding the cycle of the grid of a time step
+pub(crate) const CYCLE_INFORMATION_NAME: &str = "Cycle";
+
 /// Type of the grid, can be a single uniform grid, a collection of grids, or a hierarchical tree of grids.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum GridType {
@@ -148,12 +311,12 @@ mod tests {
     fn dummy_geometry() -> Geometry {
         Geometry {
             geometry_type: GeometryType::XYZ,
-            data_item: DataItem {
+            data_items: vec![DataItem {
                 dimensions: Some(Dimensions(vec![5, 3])),
                 data: "0 1 0 0 1.5 0 0.5 1.5 0.5 1 1.5 0 1 1 0".into(),
                 number_type: Some(NumberType::Float),
                 ..Default::default()
-            },
+            }],
         }
     }
 
@@ -161,12 +324,13 @@ mod tests {
         Topology {
             topology_type: TopologyType::Triangle,
             number_of_elements: "2".into(),
-            data_item: DataItem {
+            dimensions: None,
+            data_item: Some(DataItem {
                 dimensions: Some(Dimensions(vec![6])),
                 number_type: Some(NumberType::Int),
                 data: "0 1 2 2 3 4".into(),
                 ..Default::default()
-            },
+            }),
         }
     }
 
@@ -175,6 +339,7 @@ mod tests {
             name: String::from("Temperature"),
             attribute_type: AttributeType::Scalar,
             center: Center::Cell,
+            information: vec![],
             data_items: vec![DataItem {
                 dimensions: Some(Dimensions(vec![2])),
                 data: "2 3".into(),
@@ -225,12 +390,48 @@ mod tests {
         assert!(grid.attributes.is_none());
     }
 
+    #[test]
+    fn grid_new_subset() {
+        let indices = DataItem {
+            dimensions: Some(Dimensions(vec![1])),
+            number_type: Some(NumberType::UInt),
+            data: "1".into(),
+            ..Default::default()
+        };
+        let mut grid =
+            Grid::new_subset("inlet", "/Xdmf/Domain/Grid[@Name=\"mesh\"]", Some(indices));
+        grid.attributes = Some(vec![Arc::new(Attribute {
+            data_items: vec![DataItem {
+                dimensions: Some(Dimensions(vec![1])),
+                data: "3".into(),
+                number_type: Some(NumberType::Float),
+                ..Default::default()
+            }],
+            ..dummy_attribute()
+        })]);
+
+        let expected = r#"<Grid Name="inlet" GridType="SubSet" Section="DataItem"><DataItem Dimensions="1" NumberType="UInt" Format="XML" Precision="4">1</DataItem><Grid Name="inlet" GridType="Uniform" Reference="XML">/Xdmf/Domain/Grid[@Name="mesh"]</Grid><Attribute Name="Temperature" AttributeType="Scalar" Center="Cell"><DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="4">3</DataItem></Attribute></Grid>"#;
+        assert_eq!(to_string(&grid).unwrap(), expected);
+
+        // the reference is read from formatted files as well
+        let formatted = "<Grid GridType=\"SubSet\" Section=\"All\">\n  <Grid Reference=\"XML\">\n    /Xdmf/Domain/Grid[@Name=\"mesh\"]\n  </Grid>\n</Grid>";
+        let grid: Grid = quick_xml::de::from_str(formatted).unwrap();
+        assert_eq!(grid.grid_type, GridType::SubSet);
+        assert_eq!(grid.section, Some(Section::All));
+        let reference = &grid.grids.unwrap()[0];
+        assert_eq!(reference.reference.as_deref(), Some("XML"));
+        assert_eq!(
+            reference.reference_path.as_deref().map(str::trim),
+            Some("/Xdmf/Domain/Grid[@Name=\"mesh\"]")
+        );
+    }
+
     #[test]
     fn time_new() {
         let time = Time::new(42);
-        assert_eq!(time.value, "42");
+        assert_eq!(time.value.as_deref(), Some("42"));
         let time_str = Time::new("2024-06-01");
-        assert_eq!(time_str.value, "2024-06-01");
+        assert_eq!(time_str.value.as_deref(), Some("2024-06-01"));
     }
 
     #[test]
@@ -239,13 +440,27 @@ mod tests {
         pretty_assertions::assert_eq!(to_string(&time).unwrap(), "<Time Value=\"2024-06-01\"/>");
     }
 
+    #[test]
+    fn time_list_serialization() {
+        let time = Time::new_list(["0.0", "0.5", "1.0"]);
+        assert_eq!(time.time_type, Some(TimeType::List));
+        assert!(time.value.is_none());
+
+        pretty_assertions::assert_eq!(
+            to_string(&time).unwrap(),
+            "<Time TimeType=\"List\">\
+                <DataItem Dimensions=\"3\" NumberType=\"Float\" Format=\"XML\" Precision=\"8\">0.0 0.5 1.0</DataItem>\
+            </Time>"
+        );
+    }
+
     #[test]
     fn grid_serialization() {
         let geometry = dummy_geometry();
         let topology = dummy_topology();
         let mut grid = Grid::new_uniform("serialize", geometry, topology);
         grid.time = Some(Time::new(1.23));
-        grid.attributes = Some(vec![dummy_attribute()]);
+        grid.attributes = Some(vec![dummy_attribute().into()]);
 
         pretty_assertions::assert_eq!(
             to_string(&grid).unwrap(),
@@ -264,6 +479,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn grid_with_includes_serialization() {
+        let mut grid = Grid::new_collection("partitions", CollectionType::Spatial, None);
+        grid.includes = Some(vec![
+            XInclude::new_xpointer("part_r0.xdmf2", "/Xdmf/Domain/Grid"),
+            XInclude::new_xpointer("part_r1.xdmf2", "/Xdmf/Domain/Grid"),
+        ]);
+
+        pretty_assertions::assert_eq!(
+            to_string(&grid).unwrap(),
+            "<Grid Name=\"partitions\" GridType=\"Collection\" CollectionType=\"Spatial\">\
+                <xi:include href=\"part_r0.xdmf2\" xpointer=\"xpointer(/Xdmf/Domain/Grid)\"/>\
+                <xi:include href=\"part_r1.xdmf2\" xpointer=\"xpointer(/Xdmf/Domain/Grid)\"/>\
+            </Grid>"
+        );
+    }
+
     #[test]
     fn gridtype_default() {
         assert_eq!(GridType::default(), GridType::Uniform);
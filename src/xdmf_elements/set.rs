@@ -0,0 +1,77 @@
+//! This module contains the Set element, which marks a group of entities of a grid, e.g. a boundary.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::{attribute::Attribute, data_item::DataItem};
+
+/// Group of entities of the grid, e.g. the nodes of a boundary condition.
+/// Check the documentation [here](https://www.xdmf.org/index.php/XDMF_Model_and_Format.html#Set).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Set {
+    #[serde(rename = "@Name")]
+    #[doc(hidden)]
+    pub name: String,
+
+    #[serde(rename = "@SetType")]
+    #[doc(hidden)]
+    pub set_type: SetType,
+
+    /// Indices of the entities, for faces and edges additionally the local indices within the cells
+    #[serde(rename = "DataItem")]
+    pub data_items: Vec<DataItem>,
+
+    /// Values defined only on the entities of the set, e.g. the wall shear stress on a boundary
+    #[serde(rename = "Attribute", default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<Arc<Attribute>>,
+}
+
+/// Type of the entities of a set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum SetType {
+    #[default]
+    #[doc(hidden)]
+    Node,
+    #[doc(hidden)]
+    Cell,
+    #[doc(hidden)]
+    Face,
+    #[doc(hidden)]
+    Edge,
+}
+
+#[cfg(test)]
+mod tests {
+    use quick_xml::se::to_string;
+
+    use super::*;
+    use crate::xdmf_elements::{data_item::NumberType, dimensions::Dimensions};
+
+    #[test]
+    fn set_serialization() {
+        let set = Set {
+            name: "inlet".to_string(),
+            set_type: SetType::Node,
+            data_items: vec![DataItem {
+                dimensions: Some(Dimensions(vec![2])),
+                number_type: Some(NumberType::UInt),
+                data: "0 3".into(),
+                ..Default::default()
+            }],
+            attributes: vec![],
+        };
+
+        let serialized = to_string(&set).unwrap();
+
+        pretty_assertions::assert_eq!(
+            serialized,
+            "<Set Name=\"inlet\" SetType=\"Node\"><DataItem Dimensions=\"2\" NumberType=\"UInt\" Format=\"XML\" Precision=\"4\">0 3</DataItem></Set>"
+        );
+
+        let deserialized: Set = quick_xml::de::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.name, "inlet");
+        assert_eq!(deserialized.set_type, SetType::Node);
+        assert_eq!(deserialized.data_items.len(), 1);
+    }
+}
@@ -2,23 +2,55 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::data_item::DataItem;
+use super::{data_item::DataItem, dimensions::Dimensions};
 
 /// Described the topology of the mesh, i.e. how the points are connected to form elements.
 /// Check the documentation [here](https://www.xdmf.org/index.php/XDMF_Model_and_Format.html#Topology).
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Topology {
-    #[serde(rename = "@TopologyType")]
+    #[serde(rename = "@TopologyType", alias = "@Type")]
     #[doc(hidden)]
     pub topology_type: TopologyType,
 
-    #[serde(rename = "@NumberOfElements")]
+    #[serde(
+        rename = "@NumberOfElements",
+        default,
+        skip_serializing_if = "String::is_empty"
+    )]
     #[doc(hidden)]
     pub number_of_elements: String,
 
-    #[serde(rename = "DataItem")]
-    #[doc(hidden)]
-    pub data_item: DataItem,
+    /// Number of points per direction (in ZYX order) of structured topologies, which have no connectivity
+    #[serde(rename = "@Dimensions", skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<Dimensions>,
+
+    /// Connectivity of the elements, None for structured topologies
+    #[serde(rename = "DataItem", skip_serializing_if = "Option::is_none")]
+    pub data_item: Option<DataItem>,
+}
+
+impl Topology {
+    /// Create the topology of an image, i.e. a structured grid with uniform spacing
+    ///
+    /// The number of points per direction are given in ZYX order, as used by XDMF.
+    pub fn new_co_rect_mesh(dimensions: [usize; 3]) -> Self {
+        Self {
+            topology_type: TopologyType::CoRectMesh3D,
+            number_of_elements: String::new(),
+            dimensions: Some(Dimensions(dimensions.to_vec())),
+            data_item: None,
+        }
+    }
+
+    /// Create the topology of a rectilinear grid, i.e. a structured grid with a varying spacing per direction
+    ///
+    /// The number of points per direction are given in ZYX order, as used by XDMF.
+    pub fn new_rect_mesh(dimensions: [usize; 3]) -> Self {
+        Self {
+            topology_type: TopologyType::RectMesh3D,
+            ..Self::new_co_rect_mesh(dimensions)
+        }
+    }
 }
 
 /// Type of topology of the mesh.
@@ -30,9 +62,67 @@ pub enum TopologyType {
     #[doc(hidden)]
     Polyvertex,
     #[doc(hidden)]
+    Polygon,
+    #[doc(hidden)]
     Triangle,
     #[doc(hidden)]
     Quadrilateral,
+    #[doc(hidden)]
+    Tetrahedron,
+    #[doc(hidden)]
+    Pyramid,
+    #[doc(hidden)]
+    Wedge,
+    #[doc(hidden)]
+    Hexahedron,
+    #[doc(hidden)]
+    Polyhedron,
+    #[doc(hidden)]
+    #[serde(rename = "Edge_3")]
+    Edge3,
+    #[doc(hidden)]
+    #[serde(rename = "Triangle_6")]
+    Triangle6,
+    #[doc(hidden)]
+    #[serde(rename = "Quadrilateral_8")]
+    Quadrilateral8,
+    #[doc(hidden)]
+    #[serde(rename = "Quadrilateral_9")]
+    Quadrilateral9,
+    #[doc(hidden)]
+    #[serde(rename = "Tetrahedron_10")]
+    Tetrahedron10,
+    #[doc(hidden)]
+    #[serde(rename = "Pyramid_13")]
+    Pyramid13,
+    #[doc(hidden)]
+    #[serde(rename = "Wedge_15")]
+    Wedge15,
+    #[doc(hidden)]
+    #[serde(rename = "Wedge_18")]
+    Wedge18,
+    #[doc(hidden)]
+    #[serde(rename = "Hexahedron_20")]
+    Hexahedron20,
+    #[doc(hidden)]
+    #[serde(rename = "Hexahedron_24")]
+    Hexahedron24,
+    #[doc(hidden)]
+    #[serde(rename = "Hexahedron_27")]
+    Hexahedron27,
+    /// Structured grid with uniform spacing (e.g. voxel data), used with [`super::geometry::GeometryType::OriginDxDyDz`]
+    #[serde(rename = "3DCoRectMesh")]
+    CoRectMesh3D,
+    /// Structured grid with a varying spacing per direction, used with [`super::geometry::GeometryType::VXVYVZ`]
+    #[serde(rename = "3DRectMesh")]
+    RectMesh3D,
+}
+
+impl TopologyType {
+    /// Whether the topology is structured, i.e. its points and cells are given by its `Dimensions` instead of a connectivity.
+    pub fn is_structured(self) -> bool {
+        matches!(self, Self::CoRectMesh3D | Self::RectMesh3D)
+    }
 }
 
 #[cfg(test)]
@@ -46,7 +136,8 @@ mod tests {
         let topology = Topology {
             topology_type: TopologyType::Triangle,
             number_of_elements: "3".to_string(),
-            data_item: DataItem::default(),
+            dimensions: None,
+            data_item: Some(DataItem::default()),
         };
 
         pretty_assertions::assert_eq!(
@@ -54,4 +145,50 @@ mod tests {
             "<Topology TopologyType=\"Triangle\" NumberOfElements=\"3\"><DataItem Dimensions=\"1\" NumberType=\"Float\" Format=\"XML\" Precision=\"4\"/></Topology>"
         );
     }
+    #[test]
+    fn topology_type_serialization() {
+        let topology = Topology {
+            topology_type: TopologyType::Hexahedron27,
+            number_of_elements: "1".to_string(),
+            dimensions: None,
+            data_item: Some(DataItem::default()),
+        };
+
+        pretty_assertions::assert_eq!(
+            to_string(&topology).unwrap(),
+            "<Topology TopologyType=\"Hexahedron_27\" NumberOfElements=\"1\"><DataItem Dimensions=\"1\" NumberType=\"Float\" Format=\"XML\" Precision=\"4\"/></Topology>"
+        );
+    }
+
+    #[test]
+    fn topology_co_rect_mesh_serialization() {
+        let topology = Topology::new_co_rect_mesh([4, 3, 2]);
+
+        let serialized = to_string(&topology).unwrap();
+        pretty_assertions::assert_eq!(
+            serialized,
+            "<Topology TopologyType=\"3DCoRectMesh\" Dimensions=\"4 3 2\"/>"
+        );
+
+        let deserialized: Topology = quick_xml::de::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.topology_type, TopologyType::CoRectMesh3D);
+        assert_eq!(deserialized.dimensions, Some(Dimensions(vec![4, 3, 2])));
+        assert!(deserialized.data_item.is_none());
+    }
+
+    #[test]
+    fn topology_rect_mesh_serialization() {
+        let topology = Topology::new_rect_mesh([4, 3, 2]);
+
+        let serialized = to_string(&topology).unwrap();
+        pretty_assertions::assert_eq!(
+            serialized,
+            "<Topology TopologyType=\"3DRectMesh\" Dimensions=\"4 3 2\"/>"
+        );
+
+        let deserialized: Topology = quick_xml::de::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.topology_type, TopologyType::RectMesh3D);
+        assert!(deserialized.topology_type.is_structured());
+        assert!(!TopologyType::Mixed.is_structured());
+    }
 }
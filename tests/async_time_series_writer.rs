@@ -0,0 +1,103 @@
+#![cfg(feature = "async")]
+
+use temp_dir::TempDir;
+use xdmf::{AsyncTimeSeriesWriter, TimeSeriesWriter};
+
+fn point_data(value: f64) -> xdmf::DataMap<'static> {
+    vec![(
+        "point_data".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![value; 3].into()),
+    )]
+    .into_iter()
+    .collect()
+}
+
+// the async writer must produce the same output as the sync writer
+#[tokio::test]
+async fn write_xdmf_async_same_as_sync() {
+    let tmp_dir = TempDir::new().unwrap();
+    let points = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    let cells = (vec![0, 1, 2], vec![xdmf::CellType::Triangle]);
+
+    let sync_file_path = tmp_dir.path().join("sync");
+    let mut sync_writer = TimeSeriesWriter::new(&sync_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh(&points, (&cells.0, &cells.1))
+        .unwrap();
+
+    let async_file_path = tmp_dir.path().join("async");
+    let mut async_writer = AsyncTimeSeriesWriter::new(
+        TimeSeriesWriter::builder()
+            .directory(tmp_dir.path())
+            .basename("async")
+            .storage(xdmf::DataStorage::AsciiInline),
+    )
+    .await
+    .unwrap()
+    .write_mesh(points, cells)
+    .await
+    .unwrap();
+
+    for (i, time) in ["0.0", "0.5", "1.0"].into_iter().enumerate() {
+        let value = i as f64;
+
+        sync_writer
+            .write_data(time, Some(&point_data(value)), None)
+            .unwrap();
+        async_writer
+            .write_data(time, Some(point_data(value)), None)
+            .await
+            .unwrap();
+    }
+
+    // errors are returned and the writer can still be used afterwards
+    assert_eq!(
+        async_writer
+            .write_data("1.0", Some(point_data(0.0)), None)
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Time step '1.0' has already been written"
+    );
+
+    async_writer
+        .write_data(2.0, Some(point_data(3.0)), None)
+        .await
+        .unwrap();
+    sync_writer
+        .write_data(&2.0, Some(&point_data(3.0)), None)
+        .unwrap();
+
+    let read_sync = std::fs::read_to_string(sync_file_path.with_extension("xdmf2")).unwrap();
+    let read_async = std::fs::read_to_string(async_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(read_sync, read_async);
+}
+
+// an already configured writer can be used, e.g. with options that the builder does not provide
+#[tokio::test]
+async fn write_xdmf_async_from_writer() {
+    let tmp_dir = TempDir::new().unwrap();
+    let file_path = tmp_dir.path().join("async");
+
+    let num_events = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let counter = num_events.clone();
+    let writer = TimeSeriesWriter::new(&file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_progress_callback(move |_event| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        });
+
+    let mut async_writer = AsyncTimeSeriesWriter::from(writer)
+        .write_points(vec![0.0; 9])
+        .await
+        .unwrap();
+    async_writer
+        .write_data("0.0", Some(point_data(1.0)), None)
+        .await
+        .unwrap();
+    async_writer.close().await.unwrap();
+
+    assert!(num_events.load(std::sync::atomic::Ordering::Relaxed) > 0);
+    assert!(file_path.with_extension("xdmf2").exists());
+}
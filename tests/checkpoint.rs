@@ -0,0 +1,127 @@
+#![cfg(feature = "hdf5")]
+
+use temp_dir::TempDir;
+use xdmf::{CellType, DataAttribute, checkpoint::Checkpoint};
+
+#[test]
+fn checkpoint_write_and_load() {
+    let tmp_dir = TempDir::new().unwrap();
+    let file_name = tmp_dir.path().join("restart");
+
+    let points = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0];
+    let connectivity = [0, 1, 0, 1, 3, 2];
+    let cell_types = [CellType::Edge, CellType::Polygon(4)];
+
+    let point_data = vec![
+        (
+            "displacement".to_string(),
+            (DataAttribute::Vector, vec![0.1; 12].into()),
+        ),
+        (
+            "temperature".to_string(),
+            (DataAttribute::Scalar, vec![1.0_f32, 2.0, 3.0, 4.0].into()),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    let cell_data = vec![
+        (
+            "material".to_string(),
+            (DataAttribute::Scalar, vec![1_u64, 2].into()),
+        ),
+        (
+            "species".to_string(),
+            (DataAttribute::Generic(vec![2, 3]), vec![0.25; 12].into()),
+        ),
+        (
+            "stress".to_string(),
+            (DataAttribute::Matrix(2, 2), vec![0.5; 8].into()),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    // written out of order, the second time step without cell data
+    for time in [1.0, 0.5] {
+        Checkpoint::write(
+            &file_name,
+            time,
+            &points,
+            (&connectivity, &cell_types),
+            Some(&point_data),
+            (time > 0.5).then_some(&cell_data),
+        )
+        .unwrap();
+    }
+
+    assert_eq!(Checkpoint::times(&file_name).unwrap(), vec![0.5, 1.0]);
+
+    let checkpoint = Checkpoint::load(&file_name, 1.0).unwrap();
+    assert_eq!(
+        checkpoint,
+        Checkpoint {
+            time: 1.0,
+            points: points.to_vec(),
+            connectivity: connectivity.to_vec(),
+            cell_types: cell_types.to_vec(),
+            point_data: point_data.clone(),
+            cell_data,
+        }
+    );
+
+    let checkpoint = Checkpoint::load(&file_name, 0.5).unwrap();
+    assert_eq!(checkpoint.point_data, point_data);
+    assert!(checkpoint.cell_data.is_empty());
+
+    assert_eq!(
+        Checkpoint::load(&file_name, 0.7).unwrap_err().to_string(),
+        format!(
+            "Checkpoint file '{}' does not contain time 0.7, available are: [0.5, 1]",
+            file_name.with_extension("h5").display()
+        )
+    );
+}
+
+#[test]
+fn checkpoint_invalid_input() {
+    let tmp_dir = TempDir::new().unwrap();
+    let file_name = tmp_dir.path().join("restart");
+
+    let point_data = vec![(
+        "temperature".to_string(),
+        (DataAttribute::Scalar, vec![1.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+        Checkpoint::write(
+            &file_name,
+            f64::NAN,
+            &[0.0; 3],
+            (&[], &[]),
+            Some(&point_data),
+            None
+        )
+        .unwrap_err()
+        .to_string(),
+        "Time must be a valid float, and not 'NaN'"
+    );
+
+    assert_eq!(
+        Checkpoint::write(
+            &file_name,
+            0.0,
+            &[0.0; 6],
+            (&[], &[]),
+            Some(&point_data),
+            None
+        )
+        .unwrap_err()
+        .to_string(),
+        "Size of point-data 'temperature' must be 2, but is 1"
+    );
+
+    assert!(!file_name.with_extension("h5").exists());
+}
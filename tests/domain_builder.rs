@@ -0,0 +1,225 @@
+use temp_dir::TempDir;
+use xdmf::{
+    DomainBuilder, GridBuilder,
+    xdmf_elements::{
+        attribute::{AttributeType, Center},
+        data_item::DataItem,
+        dimensions::Dimensions,
+        geometry::{Geometry, GeometryType},
+        grid::CollectionType,
+        topology::{Topology, TopologyType},
+    },
+};
+
+#[test]
+fn write_custom_domain() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut domain_builder = DomainBuilder::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_information("solver", "custom");
+
+    // two partitions, each a single vertex
+    for partition in 0..2 {
+        domain_builder
+            .write_shared_values(
+                &format!("coords_{partition}"),
+                &vec![f64::from(partition), 0.0, 0.0].into(),
+                Dimensions(vec![1, 3]),
+            )
+            .unwrap();
+    }
+    domain_builder
+        .write_shared_values("connectivity", &vec![0_u64].into(), Dimensions(vec![1]))
+        .unwrap();
+
+    // a temporal collection of spatial collections of the partitions
+    let mut time_series = GridBuilder::collection("time_series", CollectionType::Temporal);
+    for time in 0_u64..2 {
+        let mut partitions =
+            GridBuilder::collection(format!("partitions_t{time}"), CollectionType::Spatial)
+                .time(time);
+
+        for partition in 0_u32..2 {
+            let pressure = domain_builder
+                .write_values(
+                    &format!("pressure_t{time}_p{partition}"),
+                    &vec![time * 10 + u64::from(partition)].into(),
+                    Dimensions(vec![1]),
+                )
+                .unwrap();
+
+            let grid = GridBuilder::uniform(
+                format!("partition_{partition}"),
+                Geometry {
+                    geometry_type: GeometryType::XYZ,
+                    data_items: vec![
+                        domain_builder
+                            .reference(&format!("coords_{partition}"))
+                            .unwrap(),
+                    ],
+                },
+                Topology {
+                    topology_type: TopologyType::Polyvertex,
+                    number_of_elements: "1".into(),
+                    dimensions: None,
+                    data_item: Some(domain_builder.reference("connectivity").unwrap()),
+                },
+            )
+            .attribute("pressure", AttributeType::Scalar, Center::Node, pressure)
+            .build()
+            .unwrap();
+
+            partitions = partitions.grid(grid);
+        }
+
+        time_series = time_series.grid(partitions.build().unwrap());
+    }
+    domain_builder.add_grid(time_series.build().unwrap());
+    domain_builder.write().unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="partitions_t0" GridType="Collection" CollectionType="Spatial">
+                <Grid Name="partition_0" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_0"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                    </Topology>
+                    <Attribute Name="pressure" AttributeType="Scalar" Center="Node">
+                        <DataItem Dimensions="1" NumberType="UInt" Format="XML" Precision="8">0</DataItem>
+                    </Attribute>
+                </Grid>
+                <Grid Name="partition_1" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_1"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                    </Topology>
+                    <Attribute Name="pressure" AttributeType="Scalar" Center="Node">
+                        <DataItem Dimensions="1" NumberType="UInt" Format="XML" Precision="8">1</DataItem>
+                    </Attribute>
+                </Grid>
+                <Time Value="0"/>
+            </Grid>
+            <Grid Name="partitions_t1" GridType="Collection" CollectionType="Spatial">
+                <Grid Name="partition_0" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_0"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                    </Topology>
+                    <Attribute Name="pressure" AttributeType="Scalar" Center="Node">
+                        <DataItem Dimensions="1" NumberType="UInt" Format="XML" Precision="8">10</DataItem>
+                    </Attribute>
+                </Grid>
+                <Grid Name="partition_1" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_1"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                    </Topology>
+                    <Attribute Name="pressure" AttributeType="Scalar" Center="Node">
+                        <DataItem Dimensions="1" NumberType="UInt" Format="XML" Precision="8">11</DataItem>
+                    </Attribute>
+                </Grid>
+                <Time Value="1"/>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords_0" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="coords_1" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="8">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+    <Information Name="solver" Value="custom"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn custom_domain_invalid_input() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut domain_builder =
+        DomainBuilder::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline).unwrap();
+
+    let values = vec![1.0, 2.0].into();
+
+    assert_eq!(
+        domain_builder
+            .write_values("values", &values, Dimensions(vec![3]))
+            .unwrap_err()
+            .to_string(),
+        "Size of data 'values' must be 3 according to its dimensions, but is 2"
+    );
+
+    domain_builder
+        .write_values("values", &values, Dimensions(vec![2]))
+        .unwrap();
+    assert_eq!(
+        domain_builder
+            .write_shared_values("values", &values, Dimensions(vec![2]))
+            .unwrap_err()
+            .to_string(),
+        "Data 'values' has already been written"
+    );
+
+    assert_eq!(
+        domain_builder.reference("values").unwrap_err().to_string(),
+        "DataItem with name 'values' is not registered"
+    );
+
+    let geometry = Geometry {
+        geometry_type: GeometryType::XYZ,
+        data_items: vec![],
+    };
+    let topology = Topology {
+        topology_type: TopologyType::Polyvertex,
+        number_of_elements: "0".into(),
+        dimensions: None,
+        data_item: None,
+    };
+    let uniform = GridBuilder::uniform("mesh", geometry, topology);
+
+    assert_eq!(
+        uniform
+            .clone()
+            .grid(uniform.build().unwrap())
+            .build()
+            .unwrap_err()
+            .to_string(),
+        "Uniform grid 'mesh' can not contain other grids"
+    );
+
+    assert_eq!(
+        GridBuilder::collection("collection", CollectionType::Spatial)
+            .attribute(
+                "pressure",
+                AttributeType::Scalar,
+                Center::Node,
+                DataItem::default()
+            )
+            .build()
+            .unwrap_err()
+            .to_string(),
+        "Attributes can only be added to uniform grids, but grid 'collection' is a collection"
+    );
+
+    assert_eq!(
+        domain_builder.write().unwrap_err().to_string(),
+        "The domain must contain at least one grid"
+    );
+}
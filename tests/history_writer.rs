@@ -0,0 +1,138 @@
+use temp_dir::TempDir;
+use xdmf::HistoryWriter;
+
+#[test]
+fn write_history() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("history");
+
+    let mut history_writer = HistoryWriter::new(
+        &xdmf_file_path,
+        xdmf::DataStorage::AsciiInline,
+        &[("inlet", [0.0, 0.0, 0.0]), ("outlet", [2.0, 0.5, 0.0])],
+    )
+    .unwrap()
+    .with_csv()
+    .unwrap();
+
+    for (time, pressure) in [(0.0, [1.0, 0.5]), (0.5, [1.5, 0.25])] {
+        history_writer
+            .write_step(&time, &[("pressure", &pressure), ("velocity", &[2.0, 3.0])])
+            .unwrap();
+    }
+
+    history_writer.close().unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 5.0000000000000000e-1</DataItem>
+                </Attribute>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">2.0000000000000000e0 3.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t0.5" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0.5"/>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.5000000000000000e0 2.5000000000000000e-1</DataItem>
+                </Attribute>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">2.0000000000000000e0 3.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 5.0000000000000000e-1 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="2" NumberType="UInt" Format="XML" Precision="4">0 1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+    <Information Name="probes" Value="inlet outlet"/>
+    <Information Name="history_csv" Value="history.csv"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    // the histories are also written to the CSV file
+
+    assert_eq!(
+        std::fs::read_to_string(xdmf_file_path.with_extension("csv")).unwrap(),
+        "time,inlet.pressure,outlet.pressure,inlet.velocity,outlet.velocity
+0,1.0000000000000000e0,5.0000000000000000e-1,2.0000000000000000e0,3.0000000000000000e0
+0.5,1.5000000000000000e0,2.5000000000000000e-1,2.0000000000000000e0,3.0000000000000000e0
+"
+    );
+}
+
+#[test]
+fn write_history_invalid_input() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("history");
+
+    assert_eq!(
+        HistoryWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline, &[])
+            .err()
+            .unwrap()
+            .to_string(),
+        "At least one probe is required"
+    );
+    assert_eq!(
+        HistoryWriter::new(
+            &xdmf_file_path,
+            xdmf::DataStorage::AsciiInline,
+            &[("probe", [0.0; 3]), ("probe", [1.0; 3])]
+        )
+        .err()
+        .unwrap()
+        .to_string(),
+        "Probe name 'probe' is not unique"
+    );
+
+    let mut history_writer = HistoryWriter::new(
+        &xdmf_file_path,
+        xdmf::DataStorage::AsciiInline,
+        &[("probe", [0.0; 3])],
+    )
+    .unwrap();
+
+    history_writer
+        .write_step(&0.0, &[("pressure", &[1.0])])
+        .unwrap();
+
+    assert_eq!(
+        history_writer
+            .write_step(&1.0, &[("force", &[1.0])])
+            .unwrap_err()
+            .to_string(),
+        "Fields of step '1' must be the same as at the first step, expected [\"pressure\"], got [\"force\"]"
+    );
+    assert_eq!(
+        history_writer
+            .write_step(&1.0, &[("pressure", &[1.0, 2.0])])
+            .unwrap_err()
+            .to_string(),
+        "Size of point-data 'pressure' must be 1, but is 2"
+    );
+
+    assert_eq!(
+        history_writer.with_csv().err().unwrap().to_string(),
+        "The CSV file must be enabled before the first step is written"
+    );
+}
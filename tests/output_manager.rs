@@ -0,0 +1,146 @@
+use temp_dir::TempDir;
+use xdmf::{DataAttribute, DataMap, FaceVertexMesh, OutputConfig, OutputManager, SimulationState};
+
+struct State {
+    temperature: Vec<f64>,
+    velocity: Vec<f64>,
+    material: Vec<i64>,
+}
+
+impl SimulationState for State {
+    fn point_data(&self) -> DataMap<'_> {
+        DataMap::from([
+            (
+                "temperature".to_string(),
+                (DataAttribute::Scalar, self.temperature.as_slice().into()),
+            ),
+            (
+                "velocity".to_string(),
+                (DataAttribute::Vector, self.velocity.as_slice().into()),
+            ),
+        ])
+    }
+
+    fn cell_data(&self) -> DataMap<'_> {
+        DataMap::from([(
+            "material".to_string(),
+            (DataAttribute::Scalar, self.material.as_slice().into()),
+        )])
+    }
+}
+
+fn triangle() -> FaceVertexMesh {
+    FaceVertexMesh {
+        vertices: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        faces: vec![vec![0, 1, 2]],
+    }
+}
+
+#[test]
+fn write_output_manager() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let config = OutputConfig {
+        directory: tmp_dir.path().join("output"),
+        basename: "simulation".to_string(),
+        storage: Some(xdmf::DataStorage::AsciiInline),
+        fields: vec!["temperature".to_string(), "material".to_string()],
+        frequency: 2,
+        retention: Some(1),
+    };
+
+    let mut manager = OutputManager::new(&config, &triangle()).unwrap();
+
+    let mut state = State {
+        temperature: vec![20.0; 3],
+        velocity: vec![0.0; 9],
+        material: vec![7],
+    };
+    let mut written = Vec::new();
+    for i in 0..5 {
+        state.temperature = vec![20.0 + f64::from(i); 3];
+        written.push(manager.step(&i, &state).unwrap());
+    }
+
+    manager.close().unwrap();
+
+    // every second step is written, of which only the last one is kept
+    assert_eq!(written, [true, false, true, false, true]);
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t4" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Triangle" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="4"/>
+                <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">2.4000000000000000e1 2.4000000000000000e1 2.4000000000000000e1</DataItem>
+                </Attribute>
+                <Attribute Name="material" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="1" NumberType="Int" Format="XML" Precision="8">7</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="3 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="3" NumberType="UInt" Format="XML" Precision="4">0 1 2</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf =
+        std::fs::read_to_string(tmp_dir.path().join("output/simulation.xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn output_manager_missing_field() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let config = OutputConfig {
+        directory: tmp_dir.path().to_path_buf(),
+        storage: Some(xdmf::DataStorage::AsciiInline),
+        fields: vec!["pressure".to_string()],
+        frequency: 2,
+        ..Default::default()
+    };
+
+    let mut manager = OutputManager::new(&config, &triangle()).unwrap();
+
+    let state = State {
+        temperature: vec![20.0; 3],
+        velocity: vec![0.0; 9],
+        material: vec![7],
+    };
+    assert_eq!(
+        manager.step(&0.0, &state).unwrap_err().to_string(),
+        "Field 'pressure' of the output configuration is not provided by the state"
+    );
+
+    // the failed step does not count, hence the retried step is due again
+    assert_eq!(
+        manager.step(&0.0, &state).unwrap_err().to_string(),
+        "Field 'pressure' of the output configuration is not provided by the state"
+    );
+
+    assert_eq!(
+        OutputManager::new(
+            &OutputConfig {
+                frequency: 0,
+                ..config
+            },
+            &triangle()
+        )
+        .err()
+        .unwrap()
+        .to_string(),
+        "Output frequency must be at least 1"
+    );
+}
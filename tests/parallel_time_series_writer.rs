@@ -0,0 +1,180 @@
+use temp_dir::TempDir;
+use xdmf::{GHOST_TYPE_NAME, ParallelTimeSeriesWriter, TimeSeriesWriter};
+
+#[test]
+fn write_parallel_xdmf() {
+    let num_ranks = 2;
+
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    // in a real application each rank runs in its own process
+    let mut writers = (0..num_ranks)
+        .map(|rank| {
+            let offset = rank as f64;
+            let coords = [offset, 0.0, 0.0, offset + 1.0, 0.0, 0.0, offset, 1.0, 0.0];
+
+            let builder = TimeSeriesWriter::builder()
+                .directory(tmp_dir.path())
+                .basename("test_output")
+                .storage(xdmf::DataStorage::AsciiInline);
+
+            ParallelTimeSeriesWriter::new(builder, rank, num_ranks)
+                .unwrap()
+                .write_mesh(&coords, (&[0, 1, 2], &[xdmf::CellType::Triangle]))
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    for i in 0..2 {
+        for writer in &mut writers {
+            let point_data = vec![(
+                "point_data".to_string(),
+                (
+                    xdmf::DataAttribute::Scalar,
+                    vec![writer.rank() as f64; 3].into(),
+                ),
+            )]
+            .into_iter()
+            .collect();
+
+            writer
+                .write_data(&i.to_string(), Some(&point_data), None)
+                .unwrap();
+        }
+    }
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="partitions-t0" GridType="Collection" CollectionType="Spatial">
+                <xi:include href="test_output_r0.xdmf2" xpointer="xpointer(/Xdmf/Domain/Grid/Grid[@Name='time_series-t0'])"/>
+                <xi:include href="test_output_r1.xdmf2" xpointer="xpointer(/Xdmf/Domain/Grid/Grid[@Name='time_series-t0'])"/>
+                <Time Value="0"/>
+            </Grid>
+            <Grid Name="partitions-t1" GridType="Collection" CollectionType="Spatial">
+                <xi:include href="test_output_r0.xdmf2" xpointer="xpointer(/Xdmf/Domain/Grid/Grid[@Name='time_series-t1'])"/>
+                <xi:include href="test_output_r1.xdmf2" xpointer="xpointer(/Xdmf/Domain/Grid/Grid[@Name='time_series-t1'])"/>
+                <Time Value="1"/>
+            </Grid>
+        </Grid>
+        <xi:include href="test_output_r0.xdmf2" xpointer="xpointer(/Xdmf/Domain/DataItem)"/>
+        <xi:include href="test_output_r1.xdmf2" xpointer="xpointer(/Xdmf/Domain/DataItem)"/>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="num_ranks" Value="2"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    // the files of the ranks use unique names for the mesh data
+    let read_xdmf_r1 =
+        std::fs::read_to_string(tmp_dir.path().join("test_output_r1.xdmf2")).unwrap();
+    assert!(read_xdmf_r1.contains(r#"<DataItem Name="coords_r1""#));
+    assert!(read_xdmf_r1.contains(r#"/Xdmf/Domain/DataItem[@Name="connectivity_r1"]"#));
+}
+
+#[test]
+fn write_parallel_xdmf_ghosts() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0];
+    let cells = [0, 1, 2, 1, 3, 2];
+    let cell_types = [xdmf::CellType::Triangle; 2];
+
+    let builder = TimeSeriesWriter::builder()
+        .directory(tmp_dir.path())
+        .basename("test_output")
+        .storage(xdmf::DataStorage::AsciiInline);
+
+    let mut writer = ParallelTimeSeriesWriter::new(builder, 1, 2)
+        .unwrap()
+        .write_mesh(&coords, (&cells, &cell_types))
+        .unwrap();
+
+    assert_eq!(
+        writer.set_ghost_cells(&[2]).unwrap_err().to_string(),
+        "Ghost cell index 2 is out of bounds for 2 cells"
+    );
+
+    writer.set_ghost_points(&[0, 2]).unwrap();
+    writer.set_ghost_cells(&[0]).unwrap();
+
+    let cell_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    writer.write_data("0", None, Some(&cell_data)).unwrap();
+
+    let ghost_data = vec![(
+        GHOST_TYPE_NAME.to_string(),
+        (xdmf::DataAttribute::Scalar, vec![0_u8; 4].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+        writer
+            .write_data("1", Some(&ghost_data), None)
+            .unwrap_err()
+            .to_string(),
+        "The ghost type of the points is added automatically, hence point-data 'vtkGhostType' must not be given"
+    );
+
+    let read_xdmf_r1 =
+        std::fs::read_to_string(tmp_dir.path().join("test_output_r1.xdmf2")).unwrap();
+
+    // the ghost types are written also if no data of the entities is given
+    for (center, ghost_type) in [("Node", "1 0 1 0"), ("Cell", "1 0")] {
+        assert!(read_xdmf_r1.contains(&format!(
+            r#"<Attribute Name="vtkGhostType" AttributeType="Scalar" Center="{center}">"#
+        )));
+        assert!(read_xdmf_r1.contains(&format!(r#"Precision="1">{ghost_type}</DataItem>"#)));
+    }
+    assert!(read_xdmf_r1.contains(r#"<Attribute Name="pressure""#));
+}
+
+// the options of the builder are applied to the writer of each rank
+#[test]
+fn write_parallel_xdmf_builder_options() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let num_events = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let counter = num_events.clone();
+    let builder = TimeSeriesWriter::builder()
+        .directory(tmp_dir.path())
+        .basename("test_output")
+        .storage(xdmf::DataStorage::AsciiInline)
+        .information("solver", "fluid")
+        .statistics(true)
+        .progress_callback(move |_event| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        });
+
+    let point_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 3.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    ParallelTimeSeriesWriter::new(builder, 1, 2)
+        .unwrap()
+        .write_points(&[0.0; 6])
+        .unwrap()
+        .write_data("0.0", Some(&point_data), None)
+        .unwrap();
+
+    let read_xdmf_r1 =
+        std::fs::read_to_string(tmp_dir.path().join("test_output_r1.xdmf2")).unwrap();
+    assert!(read_xdmf_r1.contains(r#"<Information Name="solver" Value="fluid"/>"#));
+    assert!(read_xdmf_r1.contains(r#"<Information Name="mean" Value="2.0000000000000000e0"/>"#));
+    assert!(num_events.load(std::sync::atomic::Ordering::Relaxed) > 0);
+}
@@ -1,5 +1,5 @@
 use temp_dir::TempDir;
-use xdmf::TimeSeriesWriter;
+use xdmf::{TimeSeriesWriter, xdmf_elements::set::SetType};
 
 #[test]
 fn write_xdmf() {
@@ -80,7 +80,10 @@ fn write_xdmf() {
             ),
             (
                 "point_data_generic-5".to_string(),
-                (xdmf::DataAttribute::Generic(5), point_data_generic.into()),
+                (
+                    xdmf::DataAttribute::Generic(vec![5]),
+                    point_data_generic.into(),
+                ),
             ),
         ]
         .into_iter()
@@ -121,7 +124,7 @@ fn write_xdmf() {
                 <Attribute Name="point_data_tensor" AttributeType="Tensor" Center="Node">
                     <DataItem Dimensions="17 9" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0</DataItem>
                 </Attribute>
-                <Attribute Name="point_data_tensor6" AttributeType="Matrix" Center="Node">
+                <Attribute Name="point_data_tensor6" AttributeType="Tensor6" Center="Node">
                     <DataItem Dimensions="17 6" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0</DataItem>
                 </Attribute>
                 <Attribute Name="point_data_vector" AttributeType="Vector" Center="Node">
@@ -151,7 +154,7 @@ fn write_xdmf() {
                 <Attribute Name="point_data_tensor" AttributeType="Tensor" Center="Node">
                     <DataItem Dimensions="17 9" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0</DataItem>
                 </Attribute>
-                <Attribute Name="point_data_tensor6" AttributeType="Matrix" Center="Node">
+                <Attribute Name="point_data_tensor6" AttributeType="Tensor6" Center="Node">
                     <DataItem Dimensions="17 6" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0</DataItem>
                 </Attribute>
                 <Attribute Name="point_data_vector" AttributeType="Vector" Center="Node">
@@ -181,7 +184,7 @@ fn write_xdmf() {
                 <Attribute Name="point_data_tensor" AttributeType="Tensor" Center="Node">
                     <DataItem Dimensions="17 9" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0</DataItem>
                 </Attribute>
-                <Attribute Name="point_data_tensor6" AttributeType="Matrix" Center="Node">
+                <Attribute Name="point_data_tensor6" AttributeType="Tensor6" Center="Node">
                     <DataItem Dimensions="17 6" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0</DataItem>
                 </Attribute>
                 <Attribute Name="point_data_vector" AttributeType="Vector" Center="Node">
@@ -193,7 +196,7 @@ fn write_xdmf() {
             </Grid>
         </Grid>
         <DataItem Name="coords" Dimensions="17 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 5.0000000000000000e-1 -5.0000000000000000e-1 2.0000000000000001e-1 -5.0000000000000000e-1 5.0000000000000000e-1 2.0000000000000001e-1 1.5000000000000000e0 -5.0000000000000000e-1 2.0000000000000001e-1 2.5000000000000000e0 5.0000000000000000e-1 2.0000000000000001e-1 5.0000000000000000e-1 1.5000000000000000e0 2.0000000000000001e-1 5.0000000000000000e-1 2.5000000000000000e0 2.0000000000000001e-1 1.5000000000000000e0 2.5000000000000000e0 2.0000000000000001e-1 2.5000000000000000e0 1.5000000000000000e0 2.0000000000000001e-1</DataItem>
-        <DataItem Name="connectivity" Dimensions="52" NumberType="UInt" Format="XML" Precision="8">5 0 1 4 3 5 1 2 5 4 5 3 4 7 6 5 4 5 8 7 4 0 1 9 4 3 0 10 4 1 2 11 4 2 5 12 4 6 3 13 4 6 7 14 4 7 8 15 4 5 8 16</DataItem>
+        <DataItem Name="connectivity" Dimensions="52" NumberType="UInt" Format="XML" Precision="4">5 0 1 4 3 5 1 2 5 4 5 3 4 7 6 5 4 5 8 7 4 0 1 9 4 3 0 10 4 1 2 11 4 2 5 12 4 6 3 13 4 6 7 14 4 7 8 15 4 5 8 16</DataItem>
     </Domain>
     <Information Name="data_storage" Value="AsciiInline"/>
     <Information Name="version" Value="0.1.3"/>
@@ -258,7 +261,7 @@ fn write_xdmf_only_mesh() {
             </Topology>
         </Grid>
         <DataItem Name="coords" Dimensions="17 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 5.0000000000000000e-1 -5.0000000000000000e-1 2.0000000000000001e-1 -5.0000000000000000e-1 5.0000000000000000e-1 2.0000000000000001e-1 1.5000000000000000e0 -5.0000000000000000e-1 2.0000000000000001e-1 2.5000000000000000e0 5.0000000000000000e-1 2.0000000000000001e-1 5.0000000000000000e-1 1.5000000000000000e0 2.0000000000000001e-1 5.0000000000000000e-1 2.5000000000000000e0 2.0000000000000001e-1 1.5000000000000000e0 2.5000000000000000e0 2.0000000000000001e-1 2.5000000000000000e0 1.5000000000000000e0 2.0000000000000001e-1</DataItem>
-        <DataItem Name="connectivity" Dimensions="52" NumberType="UInt" Format="XML" Precision="8">5 0 1 4 3 5 1 2 5 4 5 3 4 7 6 5 4 5 8 7 4 0 1 9 4 3 0 10 4 1 2 11 4 2 5 12 4 6 3 13 4 6 7 14 4 7 8 15 4 5 8 16</DataItem>
+        <DataItem Name="connectivity" Dimensions="52" NumberType="UInt" Format="XML" Precision="4">5 0 1 4 3 5 1 2 5 4 5 3 4 7 6 5 4 5 8 7 4 0 1 9 4 3 0 10 4 1 2 11 4 2 5 12 4 6 3 13 4 6 7 14 4 7 8 15 4 5 8 16</DataItem>
     </Domain>
     <Information Name="data_storage" Value="AsciiInline"/>
     <Information Name="version" Value="0.1.3"/>
@@ -307,7 +310,7 @@ fn write_xdmf_only_point_mesh() {
             </Topology>
         </Grid>
         <DataItem Name="coords" Dimensions="17 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 5.0000000000000000e-1 -5.0000000000000000e-1 2.0000000000000001e-1 -5.0000000000000000e-1 5.0000000000000000e-1 2.0000000000000001e-1 1.5000000000000000e0 -5.0000000000000000e-1 2.0000000000000001e-1 2.5000000000000000e0 5.0000000000000000e-1 2.0000000000000001e-1 5.0000000000000000e-1 1.5000000000000000e0 2.0000000000000001e-1 5.0000000000000000e-1 2.5000000000000000e0 2.0000000000000001e-1 1.5000000000000000e0 2.5000000000000000e0 2.0000000000000001e-1 2.5000000000000000e0 1.5000000000000000e0 2.0000000000000001e-1</DataItem>
-        <DataItem Name="connectivity" Dimensions="17" NumberType="UInt" Format="XML" Precision="8">0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16</DataItem>
+        <DataItem Name="connectivity" Dimensions="17" NumberType="UInt" Format="XML" Precision="4">0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16</DataItem>
     </Domain>
     <Information Name="data_storage" Value="AsciiInline"/>
     <Information Name="version" Value="0.1.3"/>
@@ -401,7 +404,7 @@ fn write_xdmf_point_mesh() {
             </Grid>
         </Grid>
         <DataItem Name="coords" Dimensions="17 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 5.0000000000000000e-1 -5.0000000000000000e-1 2.0000000000000001e-1 -5.0000000000000000e-1 5.0000000000000000e-1 2.0000000000000001e-1 1.5000000000000000e0 -5.0000000000000000e-1 2.0000000000000001e-1 2.5000000000000000e0 5.0000000000000000e-1 2.0000000000000001e-1 5.0000000000000000e-1 1.5000000000000000e0 2.0000000000000001e-1 5.0000000000000000e-1 2.5000000000000000e0 2.0000000000000001e-1 1.5000000000000000e0 2.5000000000000000e0 2.0000000000000001e-1 2.5000000000000000e0 1.5000000000000000e0 2.0000000000000001e-1</DataItem>
-        <DataItem Name="connectivity" Dimensions="17" NumberType="UInt" Format="XML" Precision="8">0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16</DataItem>
+        <DataItem Name="connectivity" Dimensions="17" NumberType="UInt" Format="XML" Precision="4">0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16</DataItem>
     </Domain>
     <Information Name="data_storage" Value="AsciiInline"/>
     <Information Name="version" Value="0.1.3"/>
@@ -415,3 +418,5547 @@ fn write_xdmf_point_mesh() {
 
     pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
 }
+
+#[test]
+fn write_xdmf_points() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_points(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0])
+        .unwrap();
+
+    // each point is a cell, hence the data of the particles can be written as point or cell data
+    let point_data = vec![(
+        "velocity".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    let cell_data = vec![(
+        "radius".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![0.1, 0.2].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), Some(&cell_data))
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="radius" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000001e-1 2.0000000000000001e-1</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="2" NumberType="UInt" Format="XML" Precision="4">0 1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_base64_inline() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::Base64Inline)
+        .unwrap()
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            (&[0, 1], &[xdmf::CellType::Edge]),
+        )
+        .unwrap();
+
+    let point_data = vec![(
+        "velocity".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), None)
+        .unwrap();
+
+    // no additional files are written
+    assert_eq!(std::fs::read_dir(tmp_dir.path()).unwrap().count(), 1);
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8" Encoding="Base64">AAAAAAAA8D8AAAAAAAAAQA==</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8" Encoding="Base64">AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA8D8AAAAAAAAAAAAAAAAAAAAA</DataItem>
+        <DataItem Name="connectivity" Dimensions="4" NumberType="UInt" Format="XML" Precision="4" Encoding="Base64">AgAAAAIAAAAAAAAAAQAAAA==</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="Base64Inline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_sets() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            (&[0, 1, 1, 2], &[xdmf::CellType::Edge; 2]),
+        )
+        .unwrap();
+
+    xdmf_writer.write_set("inlet", SetType::Node, &[0]).unwrap();
+    xdmf_writer
+        .write_set("outlet_cells", SetType::Cell, &[1])
+        .unwrap();
+
+    let point_data = vec![(
+        "velocity".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0, 3.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0</DataItem>
+                </Attribute>
+                <Set Name="inlet" SetType="Node">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="set_inlet"]</DataItem>
+                </Set>
+                <Set Name="outlet_cells" SetType="Cell">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="set_outlet_cells"]</DataItem>
+                </Set>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="3 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="8" NumberType="UInt" Format="XML" Precision="4">2 2 0 1 2 2 1 2</DataItem>
+        <DataItem Name="set_inlet" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+        <DataItem Name="set_outlet_cells" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_sets_invalid() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let mut xdmf_writer = TimeSeriesWriter::new(
+        tmp_dir.path().join("test_output"),
+        xdmf::DataStorage::AsciiInline,
+    )
+    .unwrap()
+    .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+    .unwrap();
+
+    xdmf_writer.write_set("inlet", SetType::Node, &[0]).unwrap();
+
+    let errors = [
+        xdmf_writer.write_set("inlet", SetType::Cell, &[0]),
+        xdmf_writer.write_set("in let", SetType::Node, &[0]),
+        xdmf_writer.write_set("outlet", SetType::Node, &[2]),
+        xdmf_writer.write_set("faces", SetType::Face, &[0]),
+    ];
+
+    pretty_assertions::assert_eq!(
+        errors.map(|res| res.unwrap_err().to_string()),
+        [
+            "Set 'inlet' has already been written",
+            "Set name 'in let' is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
+            "Indices of set 'outlet' out of bounds, max index: 2, but number of entities is 2",
+            "Sets of type Face are not supported",
+        ]
+    );
+
+    let point_data = vec![(
+        "velocity".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), None)
+        .unwrap();
+
+    assert_eq!(
+        xdmf_writer
+            .write_set("outlet", SetType::Node, &[1])
+            .unwrap_err()
+            .to_string(),
+        "Sets must be written before the first time step is written"
+    );
+}
+
+#[test]
+fn write_xdmf_set_data() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            (&[0, 1, 1, 2], &[xdmf::CellType::Edge; 2]),
+        )
+        .unwrap();
+
+    xdmf_writer
+        .write_set("wall", SetType::Node, &[0, 2])
+        .unwrap();
+    xdmf_writer
+        .write_set("outlet_cells", SetType::Cell, &[1])
+        .unwrap();
+
+    let point_data = vec![(
+        "velocity".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0, 3.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    let wall_data = vec![(
+        "wall_shear_stress".to_string(),
+        (xdmf::DataAttribute::Vector, vec![0.5; 6].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    let outlet_data = vec![(
+        "flux".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![4_u64].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    // the set data requires the time step to be written
+    assert_eq!(
+        xdmf_writer
+            .write_set_data("0", "wall", &wall_data)
+            .unwrap_err()
+            .to_string(),
+        "Time step '0' has not been written yet, its point or cell data must be written first"
+    );
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), None)
+        .unwrap();
+    xdmf_writer.write_set_data("0", "wall", &wall_data).unwrap();
+    xdmf_writer
+        .write_set_data("0", "outlet_cells", &outlet_data)
+        .unwrap();
+
+    let errors = [
+        xdmf_writer.write_set_data("0", "wall", &wall_data),
+        xdmf_writer.write_set_data("0", "inlet", &wall_data),
+        xdmf_writer.write_set_data("0", "outlet_cells", &wall_data),
+    ];
+
+    pretty_assertions::assert_eq!(
+        errors.map(|res| res.unwrap_err().to_string()),
+        [
+            "Data 'wall_shear_stress' of set 'wall' has already been written for time step '0'",
+            "Set 'inlet' is not part of the mesh of time step '0'",
+            "Size of outlet_cells-data 'wall_shear_stress' must be 3, but is 6",
+        ]
+    );
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0</DataItem>
+                </Attribute>
+                <Set Name="wall" SetType="Node">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="set_wall"]</DataItem>
+                    <Attribute Name="wall_shear_stress" AttributeType="Vector" Center="Node">
+                        <DataItem Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">5.0000000000000000e-1 5.0000000000000000e-1 5.0000000000000000e-1 5.0000000000000000e-1 5.0000000000000000e-1 5.0000000000000000e-1</DataItem>
+                    </Attribute>
+                </Set>
+                <Set Name="outlet_cells" SetType="Cell">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="set_outlet_cells"]</DataItem>
+                    <Attribute Name="flux" AttributeType="Scalar" Center="Cell">
+                        <DataItem Dimensions="1" NumberType="UInt" Format="XML" Precision="8">4</DataItem>
+                    </Attribute>
+                </Set>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="3 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="8" NumberType="UInt" Format="XML" Precision="4">2 2 0 1 2 2 1 2</DataItem>
+        <DataItem Name="set_wall" Dimensions="2" NumberType="UInt" Format="XML" Precision="4">0 2</DataItem>
+        <DataItem Name="set_outlet_cells" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    let report = xdmf::validate::validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert!(report.issues().is_empty());
+}
+
+#[test]
+fn write_xdmf_subsets() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 2.0, 0.0, 0.0],
+            (&[0, 1, 1, 2], &[xdmf::CellType::Edge; 2]),
+        )
+        .unwrap();
+
+    xdmf_writer.write_subset("outlet", &[1]).unwrap();
+
+    let errors = [
+        xdmf_writer.write_subset("outlet", &[0]),
+        xdmf_writer.write_subset("inlet", &[2]),
+        xdmf_writer.write_subset("in let", &[0]),
+    ];
+
+    pretty_assertions::assert_eq!(
+        errors.map(|res| res.unwrap_err().to_string()),
+        [
+            "Subset 'outlet' has already been written, the names of subsets and submeshes must be unique",
+            "Indices of subset 'inlet' out of bounds, max index: 2, but number of cells is 2",
+            "Subset name 'in let' is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes",
+        ]
+    );
+
+    let cell_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    for time in ["0", "1"] {
+        xdmf_writer
+            .write_data(time, None, Some(&cell_data))
+            .unwrap();
+    }
+
+    assert_eq!(
+        xdmf_writer
+            .write_subset("inlet", &[0])
+            .unwrap_err()
+            .to_string(),
+        "Subsets must be written before the first time step is written"
+    );
+    assert_eq!(
+        xdmf_writer
+            .write_mesh_update("2", &[0.0; 3], (&[], &[]))
+            .unwrap_err()
+            .to_string(),
+        "Mesh cannot be updated after subsets were written, their cell indices refer to the initial mesh"
+    );
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t1" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="1"/>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <Grid Name="outlet" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="outlet-time_series-t0" GridType="SubSet" Section="DataItem">
+                <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="subset_outlet"]</DataItem>
+                <Grid Name="outlet-time_series-t0" GridType="Uniform" Reference="XML">/Xdmf/Domain/Grid[@Name="time_series"]/Grid[@Name="time_series-t0"]</Grid>
+                <Time Value="0"/>
+            </Grid>
+            <Grid Name="outlet-time_series-t1" GridType="SubSet" Section="DataItem">
+                <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="subset_outlet"]</DataItem>
+                <Grid Name="outlet-time_series-t1" GridType="Uniform" Reference="XML">/Xdmf/Domain/Grid[@Name="time_series"]/Grid[@Name="time_series-t1"]</Grid>
+                <Time Value="1"/>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="3 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="8" NumberType="UInt" Format="XML" Precision="4">2 2 0 1 2 2 1 2</DataItem>
+        <DataItem Name="subset_outlet" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    let report = xdmf::validate::validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert!(report.issues().is_empty(), "{:?}", report.issues());
+}
+
+#[test]
+fn write_xdmf_submeshes() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    // an edge and two triangles, written as mixed topology
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0],
+            (
+                &[0, 1, 0, 1, 2, 1, 3, 2],
+                &[
+                    xdmf::CellType::Edge,
+                    xdmf::CellType::Triangle,
+                    xdmf::CellType::Triangle,
+                ],
+            ),
+        )
+        .unwrap();
+
+    xdmf_writer.write_submesh("top", &[2]).unwrap();
+
+    let errors = [
+        xdmf_writer.write_submesh("top", &[0]),
+        xdmf_writer.write_submesh("bottom", &[3]),
+        xdmf_writer.write_subset("top", &[0]),
+    ];
+
+    pretty_assertions::assert_eq!(
+        errors.map(|res| res.unwrap_err().to_string()),
+        [
+            "Submesh 'top' has already been written, the names of subsets and submeshes must be unique",
+            "Indices of submesh 'bottom' out of bounds, max index: 3, but number of cells is 3",
+            "Subset 'top' has already been written, the names of subsets and submeshes must be unique",
+        ]
+    );
+
+    let point_data = vec![(
+        "temperature".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0, 3.0, 4.0].into()),
+    )]
+    .into_iter()
+    .collect();
+    let cell_data = vec![
+        (
+            "pressure".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![5.0, 6.0, 7.0].into()),
+        ),
+        (
+            "velocity".to_string(),
+            (
+                xdmf::DataAttribute::Vector,
+                vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0].into(),
+            ),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), Some(&cell_data))
+        .unwrap();
+
+    assert_eq!(
+        xdmf_writer
+            .write_mesh_update("1", &[0.0; 3], (&[], &[]))
+            .unwrap_err()
+            .to_string(),
+        "Mesh cannot be updated after submeshes were written, their cell indices refer to the initial mesh"
+    );
+
+    xdmf_writer.close().unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="3">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="4" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="velocity" AttributeType="Vector" Center="Cell">
+                    <DataItem Dimensions="3 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 2.0000000000000000e0 2.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <Grid Name="top" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="top-time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem ItemType="Coordinates" Dimensions="4">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="submesh_top_connectivity"]</DataItem>
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                    </DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="4" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Cell">
+                    <DataItem ItemType="Coordinates" Dimensions="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="submesh_top_cells"]</DataItem>
+                        <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0</DataItem>
+                    </DataItem>
+                </Attribute>
+                <Attribute Name="velocity" AttributeType="Vector" Center="Cell">
+                    <DataItem ItemType="Coordinates" Dimensions="1 3">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="submesh_top_cells_3"]</DataItem>
+                        <DataItem Dimensions="3 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 2.0000000000000000e0 2.0000000000000000e0</DataItem>
+                    </DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="4 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="12" NumberType="UInt" Format="XML" Precision="4">2 2 0 1 4 0 1 2 4 1 3 2</DataItem>
+        <DataItem Name="submesh_top_connectivity" Dimensions="4" NumberType="UInt" Format="XML" Precision="4">8 9 10 11</DataItem>
+        <DataItem Name="submesh_top_cells" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">2</DataItem>
+        <DataItem Name="submesh_top_cells_3" Dimensions="6" NumberType="UInt" Format="XML" Precision="4">2 0 2 1 2 2</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    let report = xdmf::validate::validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert!(report.issues().is_empty(), "{:?}", report.issues());
+
+    // image data has no connectivity to select from
+    let mut xdmf_writer = TimeSeriesWriter::new(
+        tmp_dir.path().join("test_output_image"),
+        xdmf::DataStorage::AsciiInline,
+    )
+    .unwrap()
+    .write_image_data([0.0; 3], [1.0; 3], [2, 2, 2])
+    .unwrap();
+    assert_eq!(
+        xdmf_writer
+            .write_submesh("corner", &[0])
+            .unwrap_err()
+            .to_string(),
+        "Submeshes are only supported for meshes with a connectivity, e.g. not for image data"
+    );
+}
+
+#[test]
+fn write_xdmf_xml_header() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_xml_header(
+            xdmf::XmlHeader::default()
+                .with_declaration(true)
+                .with_doctype(true),
+        )
+        .write_mesh(&[0.0; 3], (&[], &[]))
+        .unwrap();
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert!(read_xdmf.starts_with(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE Xdmf SYSTEM \"Xdmf.dtd\">\n<Xdmf Version=\"2.0\""
+    ));
+
+    let report = xdmf::validate::validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert!(report.issues().is_empty(), "{:?}", report.issues());
+}
+
+#[test]
+fn write_xdmf_progress_callback() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let events_callback = events.clone();
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::Ascii)
+        .unwrap()
+        .with_progress_callback(move |event: &xdmf::WriteEvent| {
+            events_callback.lock().unwrap().push(event.clone());
+        })
+        .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+        .unwrap();
+
+    let point_data = vec![(
+        "temperature".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0_f32, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+    xdmf_writer
+        .write_data("0.0", Some(&point_data), None)
+        .unwrap();
+    xdmf_writer.close().unwrap();
+
+    let xdmf_size = std::fs::metadata(xdmf_file_path.with_extension("xdmf2"))
+        .unwrap()
+        .len() as usize;
+    let xdmf_name = xdmf_file_path.with_extension("xdmf2").display().to_string();
+
+    let events: Vec<_> = events
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|event| (event.operation, event.name.clone(), event.num_bytes))
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            // coordinates and connectivity (including the type of the cell)
+            (xdmf::WriteOperation::Mesh, "mesh".to_string(), 80),
+            (xdmf::WriteOperation::Xml, xdmf_name.clone(), 985),
+            (xdmf::WriteOperation::Data, "temperature".to_string(), 8),
+            (xdmf::WriteOperation::Xml, xdmf_name.clone(), xdmf_size),
+            // the XDMF file is written again when closing the writer
+            (xdmf::WriteOperation::Xml, xdmf_name, xdmf_size),
+        ]
+    );
+}
+
+#[test]
+fn write_xdmf_integer_fields() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            (&[0, 1, 1, 2], &[xdmf::CellType::Edge; 2]),
+        )
+        .unwrap();
+
+    let cell_data = vec![
+        (
+            "material_id".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![-1_i64, 4].into()),
+        ),
+        (
+            xdmf::GHOST_TYPE_NAME.to_string(),
+            (
+                xdmf::DataAttribute::Scalar,
+                xdmf::Values::ghost_type(&[false, true]),
+            ),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    xdmf_writer.write_data("0", None, Some(&cell_data)).unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="material_id" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="2" NumberType="Int" Format="XML" Precision="8">-1 4</DataItem>
+                </Attribute>
+                <Attribute Name="vtkGhostType" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="2" NumberType="UChar" Format="XML" Precision="1">0 1</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="3 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="8" NumberType="UInt" Format="XML" Precision="4">2 2 0 1 2 2 1 2</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_generic_shape() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_points(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0])
+        .unwrap();
+
+    // two species with three moments per point
+    let point_data = vec![(
+        "moments".to_string(),
+        (
+            xdmf::DataAttribute::Generic(vec![2, 3]),
+            (0..12).map(f64::from).collect::<Vec<_>>().into(),
+        ),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="moments" AttributeType="Matrix" Center="Node">
+                    <DataItem Dimensions="2 2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0 9.0000000000000000e0 1.0000000000000000e1 1.1000000000000000e1</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="2" NumberType="UInt" Format="XML" Precision="4">0 1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_time_scale() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_time_scale(1e-3)
+        .with_time_unit("s")
+        .write_mesh(&[0.0, 0.0, 0.0], (&[], &[]))
+        .unwrap();
+
+    let point_data = vec![(
+        "velocity".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    // the solver time is given in milliseconds
+    for time in [0, 250] {
+        xdmf_writer
+            .write_data(&time, Some(&point_data), None)
+            .unwrap();
+    }
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t250" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0.25"/>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+    <Information Name="time_unit" Value="s"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_time_list() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_time_list()
+        .write_mesh(&[0.0, 0.0, 0.0], (&[], &[]))
+        .unwrap();
+
+    let point_data = vec![(
+        "velocity".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    for time in ["0.0", "0.5", "1.0"] {
+        xdmf_writer
+            .write_data(time, Some(&point_data), None)
+            .unwrap();
+    }
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0.0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t0.5" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t1.0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Time TimeType="List">
+                <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">0.0 0.5 1.0</DataItem>
+            </Time>
+        </Grid>
+        <DataItem Name="coords" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_latest_step_file() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::Ascii)
+        .unwrap()
+        .with_latest_step_file(true)
+        .write_points(&[0.0, 0.0, 0.0])
+        .unwrap();
+
+    let latest_file_path = tmp_dir.path().join("test_output_latest.xdmf2");
+
+    // without time steps there is no latest step
+    xdmf_writer.flush_xml().unwrap();
+    assert!(!latest_file_path.exists());
+
+    for (time, value) in [("0.0", 1.0), ("0.5", 2.0)] {
+        let point_data = vec![(
+            "velocity".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![value].into()),
+        )]
+        .into_iter()
+        .collect();
+
+        xdmf_writer
+            .write_data(time, Some(&point_data), None)
+            .unwrap();
+    }
+
+    // the latest step references the heavy data of the time series
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0.5" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0.5"/>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">
+                        <xi:include href="test_output.txt/data_t_0.5_point_data_velocity.txt" parse="text"/>
+                    </DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">
+            <xi:include href="test_output.txt/points.txt" parse="text"/>
+        </DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">
+            <xi:include href="test_output.txt/cells.txt" parse="text"/>
+        </DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="Ascii"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(&latest_file_path).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    assert!(
+        xdmf::validate::validate_file(&latest_file_path)
+            .unwrap()
+            .is_valid()
+    );
+
+    // the writers of multiple meshes and bodies only write the XDMF file of the whole time series
+    let create_writer = |name: &str| {
+        TimeSeriesWriter::new(tmp_dir.path().join(name), xdmf::DataStorage::AsciiInline)
+            .unwrap()
+            .with_latest_step_file(true)
+    };
+    let error = "A file with the latest time step is only supported when writing a single mesh, not for multiple meshes or bodies";
+
+    assert_eq!(
+        create_writer("multi_mesh")
+            .add_mesh("mesh", &[0.0; 3], (&[], &[]))
+            .err()
+            .unwrap()
+            .to_string(),
+        error
+    );
+    assert_eq!(
+        create_writer("multi_body")
+            .into_multi_body_writer()
+            .err()
+            .unwrap()
+            .to_string(),
+        error
+    );
+}
+
+#[test]
+fn write_xdmf_rollover() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_rollover(2)
+        .write_points(&[0.0, 0.0, 0.0])
+        .unwrap();
+
+    for (time, value) in [
+        ("0.0", 1.0),
+        ("0.5", 2.0),
+        ("1.0", 3.0),
+        ("1.5", 4.0),
+        ("2.0", 5.0),
+    ] {
+        let point_data = vec![(
+            "velocity".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![value].into()),
+        )]
+        .into_iter()
+        .collect();
+
+        xdmf_writer
+            .write_data(time, Some(&point_data), None)
+            .unwrap();
+    }
+
+    // the XDMF file includes the time steps of all files
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <xi:include href="test_output_0000.xdmf2" xpointer="xpointer(/Xdmf/Domain/Grid/Grid)"/>
+            <xi:include href="test_output_0001.xdmf2" xpointer="xpointer(/Xdmf/Domain/Grid/Grid)"/>
+            <xi:include href="test_output_0002.xdmf2" xpointer="xpointer(/Xdmf/Domain/Grid/Grid)"/>
+        </Grid>
+        <DataItem Name="coords" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(tmp_dir.path().join("test_output.xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    // the last file contains the remaining time step
+    let last_file = std::fs::read_to_string(tmp_dir.path().join("test_output_0002.xdmf2")).unwrap();
+    assert_eq!(last_file.matches("<Time ").count(), 1);
+    assert!(last_file.contains(r#"<Time Value="2.0"/>"#));
+    assert!(!tmp_dir.path().join("test_output_0003.xdmf2").exists());
+
+    // the writers of multiple meshes and bodies write all time steps into one file
+    let create_writer = |name: &str| {
+        TimeSeriesWriter::new(tmp_dir.path().join(name), xdmf::DataStorage::AsciiInline)
+            .unwrap()
+            .with_rollover(2)
+    };
+    let error = "Splitting the time steps into multiple files is only supported when writing a single mesh, not for multiple meshes or bodies";
+
+    assert_eq!(
+        create_writer("multi_mesh")
+            .add_mesh("mesh", &[0.0; 3], (&[], &[]))
+            .err()
+            .unwrap()
+            .to_string(),
+        error
+    );
+    assert_eq!(
+        create_writer("multi_body")
+            .into_multi_body_writer()
+            .err()
+            .unwrap()
+            .to_string(),
+        error
+    );
+}
+
+#[test]
+fn write_xdmf_rollover_out_of_order() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_rollover(2)
+        .write_points(&[0.0, 0.0, 0.0])
+        .unwrap();
+
+    for time in ["3.0", "1.0", "2.0", "0.0"] {
+        let point_data = vec![(
+            "velocity".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![1.0].into()),
+        )]
+        .into_iter()
+        .collect();
+
+        xdmf_writer
+            .write_data(time, Some(&point_data), None)
+            .unwrap();
+    }
+
+    // the files are split along the time order, not the order of writing
+    let first_file =
+        std::fs::read_to_string(tmp_dir.path().join("test_output_0000.xdmf2")).unwrap();
+    let second_file =
+        std::fs::read_to_string(tmp_dir.path().join("test_output_0001.xdmf2")).unwrap();
+
+    let time_0 = first_file.find(r#"<Time Value="0.0"/>"#).unwrap();
+    let time_1 = first_file.find(r#"<Time Value="1.0"/>"#).unwrap();
+    assert!(time_0 < time_1);
+    assert_eq!(first_file.matches("<Time ").count(), 2);
+
+    let time_2 = second_file.find(r#"<Time Value="2.0"/>"#).unwrap();
+    let time_3 = second_file.find(r#"<Time Value="3.0"/>"#).unwrap();
+    assert!(time_2 < time_3);
+    assert_eq!(second_file.matches("<Time ").count(), 2);
+}
+
+#[test]
+fn write_xdmf_field_name_prefix() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_field_name_prefix("fluid/")
+        .with_field_information("pressure", "units", "Pa")
+        .write_mesh(&[0.0, 0.0, 0.0], (&[0], &[xdmf::CellType::Vertex]))
+        .unwrap();
+
+    let point_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0].into()),
+    )]
+    .into_iter()
+    .collect();
+    let cell_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    // the fields are identified without the prefix
+    xdmf_writer
+        .write_data("0.0", Some(&point_data), Some(&cell_data))
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0.0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0.0"/>
+                <Attribute Name="fluid/pressure" AttributeType="Scalar" Center="Node">
+                    <Information Name="units" Value="Pa"/>
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="fluid/pressure" AttributeType="Scalar" Center="Cell">
+                    <Information Name="units" Value="Pa"/>
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">2.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_tensor6_as_matrix() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_tensor6_as_matrix()
+        .write_mesh(&[0.0, 0.0, 0.0], (&[], &[]))
+        .unwrap();
+
+    let point_data = vec![(
+        "stress".to_string(),
+        (
+            xdmf::DataAttribute::Tensor6,
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0].into(),
+        ),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0.0", Some(&point_data), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0.0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0.0"/>
+                <Attribute Name="stress" AttributeType="Matrix" Center="Node">
+                    <DataItem Dimensions="1 6" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_mesh_soa() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh_soa(
+            &[0.0, 1.0, 0.0],
+            &[0.0, 0.0, 1.0],
+            &[0.0, 0.0, 0.5],
+            (&[0, 1, 2], &[xdmf::CellType::Triangle]),
+        )
+        .unwrap();
+
+    let point_data = vec![(
+        "velocity".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0, 3.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="X_Y_Z">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_x"]</DataItem>
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_y"]</DataItem>
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_z"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Triangle" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords_x" Dimensions="3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="coords_y" Dimensions="3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0</DataItem>
+        <DataItem Name="coords_z" Dimensions="3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 5.0000000000000000e-1</DataItem>
+        <DataItem Name="connectivity" Dimensions="3" NumberType="UInt" Format="XML" Precision="4">0 1 2</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_polygon_polyhedron() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    // a square pyramid on top of a pentagon
+    let coords = [
+        0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.5, 0.5, 1.0, -0.5, 0.5, 0.0,
+    ];
+    let (polyhedron, face_stream) = xdmf::CellType::polyhedron(&[
+        &[0, 1, 2, 3],
+        &[0, 1, 4],
+        &[1, 2, 4],
+        &[2, 3, 4],
+        &[3, 0, 4],
+    ]);
+    let connectivity: Vec<u64> = [0, 5, 3, 2, 1].into_iter().chain(face_stream).collect();
+
+    TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh(
+            &coords,
+            (&connectivity, &[xdmf::CellType::Polygon(5), polyhedron]),
+        )
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="mesh" GridType="Uniform">
+            <Geometry GeometryType="XYZ">
+                <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+            </Geometry>
+            <Topology TopologyType="Mixed" NumberOfElements="2">
+                <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+            </Topology>
+        </Grid>
+        <DataItem Name="coords" Dimensions="6 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 5.0000000000000000e-1 5.0000000000000000e-1 1.0000000000000000e0 -5.0000000000000000e-1 5.0000000000000000e-1 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="30" NumberType="UInt" Format="XML" Precision="4">3 5 0 5 3 2 1 16 5 4 0 1 2 3 3 0 1 4 3 1 2 4 3 2 3 4 3 3 0 4</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_mesh_from_source() {
+    // in-house mesh type with a tetrahedron and a polyhedron (pyramid), given by its faces
+    struct Mesh {
+        nodes: Vec<[f64; 3]>,
+        tetrahedra: Vec<[u32; 4]>,
+        polyhedra: Vec<Vec<Vec<u32>>>,
+    }
+
+    impl xdmf::MeshSource for Mesh {
+        fn points(&self) -> impl Iterator<Item = [f64; 3]> {
+            self.nodes.iter().copied()
+        }
+
+        fn cells(&self) -> impl Iterator<Item = (xdmf::CellType, impl IntoIterator<Item = u64>)> {
+            let tetrahedra = self.tetrahedra.iter().map(|tetrahedron| {
+                (
+                    xdmf::CellType::Tetrahedron,
+                    tetrahedron.iter().map(|&node| u64::from(node)).collect(),
+                )
+            });
+
+            let polyhedra = self.polyhedra.iter().map(|faces| {
+                let faces: Vec<Vec<u64>> = faces
+                    .iter()
+                    .map(|face| face.iter().map(|&node| u64::from(node)).collect())
+                    .collect();
+                let faces: Vec<&[u64]> = faces.iter().map(Vec::as_slice).collect();
+                xdmf::CellType::polyhedron(&faces)
+            });
+
+            tetrahedra.chain(polyhedra)
+        }
+    }
+
+    let mesh = Mesh {
+        nodes: vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.5, 0.5, 1.0],
+            [0.5, 0.5, -1.0],
+        ],
+        tetrahedra: vec![[0, 1, 3, 5]],
+        polyhedra: vec![vec![
+            vec![0, 1, 2, 3],
+            vec![0, 1, 4],
+            vec![1, 2, 4],
+            vec![2, 3, 4],
+            vec![3, 0, 4],
+        ]],
+    };
+
+    let tmp_dir = TempDir::new().unwrap();
+    let from_source_path = tmp_dir.path().join("from_source");
+    let flattened_path = tmp_dir.path().join("flattened");
+
+    TimeSeriesWriter::new(&from_source_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh_from(&mesh)
+        .unwrap();
+
+    let (points, (connectivity, cell_types)) = xdmf::MeshSource::flatten(&mesh);
+    assert_eq!(cell_types.len(), 2);
+
+    TimeSeriesWriter::new(&flattened_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh(&points, (&connectivity, &cell_types))
+        .unwrap();
+
+    // the mesh is written as if it was flattened manually
+    pretty_assertions::assert_eq!(
+        std::fs::read_to_string(flattened_path.with_extension("xdmf2")).unwrap(),
+        std::fs::read_to_string(from_source_path.with_extension("xdmf2")).unwrap()
+    );
+}
+
+#[test]
+fn write_mesh_soa_mismatched_lengths() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let res = TimeSeriesWriter::new(tmp_dir.path().join("test_output"), xdmf::DataStorage::Ascii)
+        .unwrap()
+        .write_mesh_soa(&[0.0, 1.0], &[0.0, 0.0], &[0.0], (&[], &[]));
+
+    assert_eq!(
+        res.err().unwrap().to_string(),
+        "Coordinate arrays must have the same length, but have 2, 2 and 1"
+    );
+}
+
+#[test]
+fn write_xdmf_image_data() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_image_data([1.0, 2.0, 3.0], [0.5, 0.25, 1.0], [3, 2, 2])
+        .unwrap();
+
+    let point_data = vec![(
+        "velocity".to_string(),
+        (xdmf::DataAttribute::Vector, vec![1.0; 36].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    let cell_data = vec![(
+        "segmentation".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1_u8, 2].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), Some(&cell_data))
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="ORIGIN_DXDYDZ">
+                    <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">3.0000000000000000e0 2.0000000000000000e0 1.0000000000000000e0</DataItem>
+                    <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.5000000000000000e-1 5.0000000000000000e-1</DataItem>
+                </Geometry>
+                <Topology TopologyType="3DCoRectMesh" Dimensions="2 2 3"/>
+                <Time Value="0"/>
+                <Attribute Name="velocity" AttributeType="Vector" Center="Node">
+                    <DataItem Dimensions="2 2 3 3" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="segmentation" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="1 1 2" NumberType="UChar" Format="XML" Precision="1">1 2</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_image_data_invalid() {
+    let tmp_dir = TempDir::new().unwrap();
+    let writer = || {
+        TimeSeriesWriter::new(tmp_dir.path().join("test_output"), xdmf::DataStorage::Ascii).unwrap()
+    };
+
+    assert_eq!(
+        writer()
+            .write_image_data([0.0; 3], [1.0; 3], [2, 1, 2])
+            .err()
+            .unwrap()
+            .to_string(),
+        "Image data requires at least 2 points per direction, but has [2, 1, 2]"
+    );
+
+    assert_eq!(
+        writer()
+            .write_image_data([0.0; 3], [1.0, 0.0, 1.0], [2, 2, 2])
+            .err()
+            .unwrap()
+            .to_string(),
+        "Image data requires a finite origin and a positive spacing, but has [0.0, 0.0, 0.0] and [1.0, 0.0, 1.0]"
+    );
+}
+
+#[test]
+fn write_xdmf_rectilinear_mesh() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_rectilinear_mesh(&[0.0, 0.1, 0.5], &[1.0, 2.0], &[-1.0, 0.0])
+        .unwrap();
+
+    let cell_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer.write_data("0", None, Some(&cell_data)).unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="VXVYVZ">
+                    <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 1.0000000000000001e-1 5.0000000000000000e-1</DataItem>
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0</DataItem>
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">-1.0000000000000000e0 0.0000000000000000e0</DataItem>
+                </Geometry>
+                <Topology TopologyType="3DRectMesh" Dimensions="2 2 3"/>
+                <Time Value="0"/>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="1 1 2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_rectilinear_mesh_invalid() {
+    let tmp_dir = TempDir::new().unwrap();
+    let writer = || {
+        TimeSeriesWriter::new(tmp_dir.path().join("test_output"), xdmf::DataStorage::Ascii).unwrap()
+    };
+
+    assert_eq!(
+        writer()
+            .write_rectilinear_mesh(&[0.0, 1.0], &[0.0], &[0.0, 1.0])
+            .err()
+            .unwrap()
+            .to_string(),
+        "Rectilinear mesh requires at least 2 points per direction, but has [2, 1, 2]"
+    );
+
+    assert_eq!(
+        writer()
+            .write_rectilinear_mesh(&[0.0, 1.0], &[0.0, 1.0], &[1.0, 0.0])
+            .err()
+            .unwrap()
+            .to_string(),
+        "Rectilinear mesh requires finite and strictly increasing coordinates of the axes"
+    );
+}
+
+#[test]
+fn write_xdmf_mesh_update() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let xdmf_writer =
+        TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline).unwrap();
+
+    let mut xdmf_writer = xdmf_writer
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            (&[0, 1, 2], &[xdmf::CellType::Triangle]),
+        )
+        .unwrap();
+
+    let point_data = |values: Vec<f64>| {
+        vec![(
+            "point_data_scalar".to_string(),
+            (xdmf::DataAttribute::Scalar, values.into()),
+        )]
+        .into_iter()
+        .collect()
+    };
+
+    xdmf_writer
+        .write_data("0", Some(&point_data(vec![0.0, 1.0, 2.0])), None)
+        .unwrap();
+
+    xdmf_writer
+        .write_mesh_update(
+            "1",
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0],
+            (&[0, 1, 2, 1, 3, 2], &[xdmf::CellType::Triangle; 2]),
+        )
+        .unwrap();
+
+    // the data has to match the updated mesh
+    assert!(
+        xdmf_writer
+            .write_data("1", Some(&point_data(vec![0.0, 1.0, 2.0])), None)
+            .is_err()
+    );
+
+    xdmf_writer
+        .write_data("1", Some(&point_data(vec![0.0, 1.0, 2.0, 3.0])), None)
+        .unwrap();
+
+    // updating the mesh of an already written time step is not possible
+    assert_eq!(
+        xdmf_writer
+            .write_mesh_update("1", &[0.0; 3], (&[], &[]))
+            .unwrap_err()
+            .to_string(),
+        "Mesh cannot be updated for time step '1', it has already been written"
+    );
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Triangle" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="point_data_scalar" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t1" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_t1"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Triangle" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_t1"]</DataItem>
+                </Topology>
+                <Time Value="1"/>
+                <Attribute Name="point_data_scalar" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="4" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="3 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="3" NumberType="UInt" Format="XML" Precision="4">0 1 2</DataItem>
+        <DataItem Name="coords_t1" Dimensions="4 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity_t1" Dimensions="6" NumberType="UInt" Format="XML" Precision="4">0 1 2 1 3 2</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let xdmf_file = xdmf_file_path.with_extension("xdmf2");
+    let read_xdmf = std::fs::read_to_string(&xdmf_file).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_particles() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_points(&[0.0, 0.0, 0.0])
+        .unwrap();
+
+    // the number of particles changes between the time steps
+    for (time, points) in [
+        ("0", vec![0.0, 0.0, 0.0]),
+        ("1", vec![0.5, 0.0, 0.0, 1.0, 0.0, 0.0]),
+    ] {
+        let point_data = vec![(
+            "diameter".to_string(),
+            (
+                xdmf::DataAttribute::Scalar,
+                vec![0.1; points.len() / 3].into(),
+            ),
+        )]
+        .into_iter()
+        .collect();
+
+        xdmf_writer
+            .write_particles(time, &points, Some(&point_data))
+            .unwrap();
+    }
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_t0"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_t0"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="diameter" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000001e-1</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t1" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_t1"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_t1"]</DataItem>
+                </Topology>
+                <Time Value="1"/>
+                <Attribute Name="diameter" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000001e-1 1.0000000000000001e-1</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+        <DataItem Name="coords_t0" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity_t0" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+        <DataItem Name="coords_t1" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">5.0000000000000000e-1 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity_t1" Dimensions="2" NumberType="UInt" Format="XML" Precision="4">0 1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    // the point data is validated against the particles of the time step
+    let point_data = vec![(
+        "diameter".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![0.1; 2].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    assert!(
+        xdmf_writer
+            .write_particles("2", &[0.0; 3], Some(&point_data))
+            .is_err()
+    );
+}
+
+#[test]
+fn write_xdmf_mesh_tree() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let xdmf_writer =
+        TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline).unwrap();
+
+    let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+
+    let mut xdmf_writer = xdmf_writer
+        .write_mesh_tree(&[
+            (
+                "frame/beam",
+                &coords[..6],
+                (&[0, 1], &[xdmf::CellType::Edge]),
+            ),
+            (
+                "frame/plate",
+                &coords,
+                (&[0, 1, 2], &[xdmf::CellType::Triangle]),
+            ),
+            ("bolt", &coords[..3], (&[], &[])),
+        ])
+        .unwrap();
+
+    let point_data = vec![(
+        "point_data".to_string(),
+        (
+            xdmf::DataAttribute::Scalar,
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0].into(),
+        ),
+    )]
+    .into_iter()
+    .collect();
+
+    let cell_data = vec![(
+        "cell_data".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![7_u64, 8, 9].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), Some(&cell_data))
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Tree">
+                <Grid Name="frame" GridType="Tree">
+                    <Grid Name="beam" GridType="Uniform">
+                        <Geometry GeometryType="XYZ">
+                            <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_part0"]</DataItem>
+                        </Geometry>
+                        <Topology TopologyType="Mixed" NumberOfElements="1">
+                            <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_part0"]</DataItem>
+                        </Topology>
+                        <Attribute Name="point_data" AttributeType="Scalar" Center="Node">
+                            <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0</DataItem>
+                        </Attribute>
+                        <Attribute Name="cell_data" AttributeType="Scalar" Center="Cell">
+                            <DataItem Dimensions="1" NumberType="UInt" Format="XML" Precision="8">7</DataItem>
+                        </Attribute>
+                    </Grid>
+                    <Grid Name="plate" GridType="Uniform">
+                        <Geometry GeometryType="XYZ">
+                            <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_part1"]</DataItem>
+                        </Geometry>
+                        <Topology TopologyType="Triangle" NumberOfElements="1">
+                            <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_part1"]</DataItem>
+                        </Topology>
+                        <Attribute Name="point_data" AttributeType="Scalar" Center="Node">
+                            <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0</DataItem>
+                        </Attribute>
+                        <Attribute Name="cell_data" AttributeType="Scalar" Center="Cell">
+                            <DataItem Dimensions="1" NumberType="UInt" Format="XML" Precision="8">8</DataItem>
+                        </Attribute>
+                    </Grid>
+                </Grid>
+                <Grid Name="bolt" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_part2"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_part2"]</DataItem>
+                    </Topology>
+                    <Attribute Name="point_data" AttributeType="Scalar" Center="Node">
+                        <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">6.0000000000000000e0</DataItem>
+                    </Attribute>
+                    <Attribute Name="cell_data" AttributeType="Scalar" Center="Cell">
+                        <DataItem Dimensions="1" NumberType="UInt" Format="XML" Precision="8">9</DataItem>
+                    </Attribute>
+                </Grid>
+                <Time Value="0"/>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords_part0" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity_part0" Dimensions="4" NumberType="UInt" Format="XML" Precision="4">2 2 0 1</DataItem>
+        <DataItem Name="coords_part1" Dimensions="3 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity_part1" Dimensions="3" NumberType="UInt" Format="XML" Precision="4">0 1 2</DataItem>
+        <DataItem Name="coords_part2" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity_part2" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let xdmf_file = xdmf_file_path.with_extension("xdmf2");
+    let read_xdmf = std::fs::read_to_string(&xdmf_file).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_mesh_partitioned() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0];
+    let connectivity = [0, 1, 2, 0, 1, 1, 3, 2];
+    let cell_types = [
+        xdmf::CellType::Triangle,
+        xdmf::CellType::Edge,
+        xdmf::CellType::Triangle,
+    ];
+
+    let errors = [
+        TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+            .unwrap()
+            .write_mesh_partitioned(&coords, (&connectivity, &cell_types), &[1, 2]),
+        TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+            .unwrap()
+            .write_mesh_partitioned(&coords, (&[], &[]), &[]),
+    ];
+
+    pretty_assertions::assert_eq!(
+        errors.map(|res| res.err().unwrap().to_string()),
+        [
+            "Number of part ids (2) must match the number of cells (3)",
+            "At least one cell is required for a partitioned mesh",
+        ]
+    );
+
+    // the triangles belong to part 7, the edge in between to part 3
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh_partitioned(&coords, (&connectivity, &cell_types), &[7, 3, 7])
+        .unwrap();
+
+    let point_data = vec![(
+        "temperature".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0, 3.0, 4.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    let cell_data = vec![(
+        "material".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![70_u64, 30, 71].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), Some(&cell_data))
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Collection" CollectionType="Spatial">
+                <Grid Name="part_3" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Mixed" NumberOfElements="1">
+                        <DataItem ItemType="HyperSlab" Dimensions="4">
+                            <DataItem Dimensions="3 1" NumberType="UInt" Format="XML" Precision="8">0 1 4</DataItem>
+                            <DataItem Dimensions="12" Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                        </DataItem>
+                    </Topology>
+                    <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                        <DataItem Dimensions="4" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0</DataItem>
+                    </Attribute>
+                    <Attribute Name="material" AttributeType="Scalar" Center="Cell">
+                        <DataItem Dimensions="1" NumberType="UInt" Format="XML" Precision="8">30</DataItem>
+                    </Attribute>
+                </Grid>
+                <Grid Name="part_7" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Mixed" NumberOfElements="2">
+                        <DataItem ItemType="HyperSlab" Dimensions="8">
+                            <DataItem Dimensions="3 1" NumberType="UInt" Format="XML" Precision="8">4 1 8</DataItem>
+                            <DataItem Dimensions="12" Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                        </DataItem>
+                    </Topology>
+                    <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                        <DataItem Dimensions="4" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0</DataItem>
+                    </Attribute>
+                    <Attribute Name="material" AttributeType="Scalar" Center="Cell">
+                        <DataItem Dimensions="2" NumberType="UInt" Format="XML" Precision="8">70 71</DataItem>
+                    </Attribute>
+                </Grid>
+                <Time Value="0"/>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="4 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="12" NumberType="UInt" Format="XML" Precision="4">2 2 0 1 4 0 1 2 4 1 3 2</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    let report = xdmf::validate::validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert!(report.issues().is_empty(), "{:?}", report.issues());
+}
+
+#[test]
+fn write_xdmf_global_data() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let xdmf_writer =
+        TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline).unwrap();
+
+    let mut xdmf_writer = xdmf_writer
+        .write_mesh(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0], (&[], &[]))
+        .unwrap();
+
+    let point_data = vec![(
+        "point_data".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    let global_data = vec![
+        (
+            "kinetic_energy".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![1.5].into()),
+        ),
+        (
+            "num_iterations".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![12_u64].into()),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    // the time step has to be written first
+    assert_eq!(
+        xdmf_writer
+            .write_global_data("0", &global_data)
+            .unwrap_err()
+            .to_string(),
+        "Time step '0' has not been written yet, its point or cell data must be written first"
+    );
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), None)
+        .unwrap();
+    xdmf_writer.write_global_data("0", &global_data).unwrap();
+
+    // global data can only be written once per time step
+    assert_eq!(
+        xdmf_writer
+            .write_global_data("0", &global_data)
+            .unwrap_err()
+            .to_string(),
+        "Global data 'kinetic_energy' has already been written for time step '0'"
+    );
+
+    // global data must consist of a single value
+    let invalid_global_data = vec![(
+        "residual".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+        xdmf_writer
+            .write_global_data("0", &invalid_global_data)
+            .unwrap_err()
+            .to_string(),
+        "Size of global-data 'residual' must be 1, but is 2"
+    );
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="point_data" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="kinetic_energy" AttributeType="Scalar" Center="Grid">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.5000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="num_iterations" AttributeType="Scalar" Center="Grid">
+                    <DataItem Dimensions="1" NumberType="UInt" Format="XML" Precision="8">12</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="2" NumberType="UInt" Format="XML" Precision="4">0 1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let xdmf_file = xdmf_file_path.with_extension("xdmf2");
+    let read_xdmf = std::fs::read_to_string(&xdmf_file).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_edge_and_face_data() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    // a quadrilateral with 4 edges, which are also its faces in 2D
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+            (&[0, 1, 2, 3], &[xdmf::CellType::Quadrilateral]),
+        )
+        .unwrap();
+
+    let data = |name: &str, values: Vec<f64>| -> xdmf::DataMap<'static> {
+        vec![(
+            name.to_string(),
+            (xdmf::DataAttribute::Scalar, values.into()),
+        )]
+        .into_iter()
+        .collect()
+    };
+
+    let edge_data = data("circulation", vec![1.0, 2.0, 3.0, 4.0]);
+
+    assert_eq!(
+        xdmf_writer
+            .write_edge_and_face_data("0.0", Some(&edge_data), None)
+            .unwrap_err()
+            .to_string(),
+        "Time step '0.0' has not been written yet, its point or cell data must be written first"
+    );
+
+    xdmf_writer
+        .write_data("0.0", None, Some(&data("pressure", vec![1.0])))
+        .unwrap();
+
+    assert_eq!(
+        xdmf_writer
+            .write_edge_and_face_data("0.0", Some(&edge_data), None)
+            .unwrap_err()
+            .to_string(),
+        "Number of edges must be declared before writing edge-data"
+    );
+
+    xdmf_writer.set_num_edges(4);
+    xdmf_writer.set_num_faces(4);
+
+    assert_eq!(
+        xdmf_writer
+            .write_edge_and_face_data("0.0", None, Some(&data("flux", vec![1.0])))
+            .unwrap_err()
+            .to_string(),
+        "Size of face-data 'flux' must be 4, but is 1"
+    );
+
+    xdmf_writer
+        .write_edge_and_face_data(
+            "0.0",
+            Some(&edge_data),
+            Some(&data("flux", vec![0.5, -0.5, 0.5, -0.5])),
+        )
+        .unwrap();
+
+    assert_eq!(
+        xdmf_writer
+            .write_edge_and_face_data("0.0", Some(&edge_data), None)
+            .unwrap_err()
+            .to_string(),
+        "Data 'circulation' of edge-data has already been written for time step '0.0'"
+    );
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0.0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Quadrilateral" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0.0"/>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="circulation" AttributeType="Scalar" Center="Edge">
+                    <DataItem Dimensions="4" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="flux" AttributeType="Scalar" Center="Face">
+                    <DataItem Dimensions="4" NumberType="Float" Format="XML" Precision="8">5.0000000000000000e-1 -5.0000000000000000e-1 5.0000000000000000e-1 -5.0000000000000000e-1</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="4 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="4" NumberType="UInt" Format="XML" Precision="4">0 1 2 3</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let xdmf_file = xdmf_file_path.with_extension("xdmf2");
+    let read_xdmf = std::fs::read_to_string(&xdmf_file).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_static_data() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            (&[0, 1], &[xdmf::CellType::Edge]),
+        )
+        .unwrap();
+
+    let material = vec![(
+        "material".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![2_u64].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_static_data(None, Some(&material))
+        .unwrap();
+
+    assert_eq!(
+        xdmf_writer
+            .write_static_data(None, Some(&material))
+            .unwrap_err()
+            .to_string(),
+        "Static cell-data 'material' has already been written"
+    );
+
+    assert_eq!(
+        xdmf_writer
+            .write_data("0", None, Some(&material))
+            .unwrap_err()
+            .to_string(),
+        "Field 'material' of cell-data was written as static data, hence it must not be written for a time step"
+    );
+
+    for i in 0..2 {
+        let point_data = vec![(
+            "temperature".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![i as f64; 2].into()),
+        )]
+        .into_iter()
+        .collect();
+
+        xdmf_writer
+            .write_data(&i.to_string(), Some(&point_data), None)
+            .unwrap();
+    }
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="material" AttributeType="Scalar" Center="Cell">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="static_cell_material"]</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t1" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="1"/>
+                <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 1.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="material" AttributeType="Scalar" Center="Cell">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="static_cell_material"]</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="4" NumberType="UInt" Format="XML" Precision="4">2 2 0 1</DataItem>
+        <DataItem Name="static_cell_material" Dimensions="1" NumberType="UInt" Format="XML" Precision="8">2</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let xdmf_file = xdmf_file_path.with_extension("xdmf2");
+    let read_xdmf = std::fs::read_to_string(&xdmf_file).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_data_streamed() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let xdmf_writer =
+        TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline).unwrap();
+
+    let mut xdmf_writer = xdmf_writer
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            (&[0, 1], &[xdmf::CellType::Edge]),
+        )
+        .unwrap();
+
+    let point_data = vec![(
+        "point_data".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), None)
+        .unwrap();
+
+    // streamed data is added to the existing time step
+    xdmf_writer
+        .write_cell_data_streamed(
+            "0",
+            "cell_data",
+            xdmf::DataAttribute::Scalar,
+            std::iter::once(5.0),
+            1,
+        )
+        .unwrap();
+
+    assert_eq!(
+        xdmf_writer
+            .write_point_data_streamed(
+                "0",
+                "point_data",
+                xdmf::DataAttribute::Scalar,
+                [1.0, 2.0],
+                2,
+            )
+            .unwrap_err()
+            .to_string(),
+        "Data 'point_data' of point-data has already been written for time step '0'"
+    );
+
+    assert_eq!(
+        xdmf_writer
+            .write_point_data_streamed("1", "velocity", xdmf::DataAttribute::Vector, [1.0, 2.0], 2,)
+            .unwrap_err()
+            .to_string(),
+        "Size of point-data 'velocity' must be 6, but is 2"
+    );
+
+    // a new time step is created
+    xdmf_writer
+        .write_point_data_streamed(
+            "1",
+            "velocity",
+            xdmf::DataAttribute::Vector,
+            (0..6).map(f64::from),
+            6,
+        )
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="point_data" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="cell_data" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">5.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t1" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="1"/>
+                <Attribute Name="velocity" AttributeType="Vector" Center="Node">
+                    <DataItem Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="4" NumberType="UInt" Format="XML" Precision="4">2 2 0 1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let xdmf_file = xdmf_file_path.with_extension("xdmf2");
+    let read_xdmf = std::fs::read_to_string(&xdmf_file).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_step_keys() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let xdmf_writer =
+        TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline).unwrap();
+
+    let mut xdmf_writer = xdmf_writer.write_mesh(&[0.0; 3], (&[], &[])).unwrap();
+
+    let point_data = vec![(
+        "point_data".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data(
+            &xdmf::StepLabel("initial".to_string()),
+            Some(&point_data),
+            None,
+        )
+        .unwrap();
+    xdmf_writer
+        .write_data(&0.5, Some(&point_data), None)
+        .unwrap();
+    xdmf_writer
+        .write_data(&2_u64, Some(&point_data), None)
+        .unwrap();
+
+    // the same step cannot be written twice, regardless of the type of the key
+    assert_eq!(
+        xdmf_writer
+            .write_data("0.5", Some(&point_data), None)
+            .unwrap_err()
+            .to_string(),
+        "Time step '0.5' has already been written"
+    );
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-tinitial" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="point_data" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t0.5" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0.5"/>
+                <Attribute Name="point_data" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t2" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="2"/>
+                <Attribute Name="point_data" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let xdmf_file = xdmf_file_path.with_extension("xdmf2");
+    let read_xdmf = std::fs::read_to_string(&xdmf_file).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_time_order() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_time_list()
+        .write_mesh(&[0.0; 3], (&[], &[]))
+        .unwrap();
+
+    let point_data = vec![(
+        "point_data".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    // the time steps are emitted in increasing time, not in the order they are written (nor lexically)
+    for time in [10.0, 9.0, 0.125] {
+        xdmf_writer
+            .write_data(
+                &xdmf::FormattedTime::new(time, xdmf::TimeFormat::Fixed(2)),
+                Some(&point_data),
+                None,
+            )
+            .unwrap();
+    }
+
+    // the labels of the formatted times must be unique
+    assert_eq!(
+        xdmf_writer
+            .write_data(
+                &xdmf::FormattedTime::new(9.001, xdmf::TimeFormat::Fixed(2)),
+                Some(&point_data),
+                None,
+            )
+            .unwrap_err()
+            .to_string(),
+        "Time step '9.00' has already been written"
+    );
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0.12" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Attribute Name="point_data" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t9.00" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Attribute Name="point_data" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t10.00" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Attribute Name="point_data" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Time TimeType="List">
+                <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">0.125 9 10</DataItem>
+            </Time>
+        </Grid>
+        <DataItem Name="coords" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let xdmf_file = xdmf_file_path.with_extension("xdmf2");
+    let read_xdmf = std::fs::read_to_string(&xdmf_file).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_information() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_information("solver", "my_solver 1.2.3")
+        .with_field_information("temperature", "units", "K")
+        .with_field_information("temperature", "description", "fluid temperature")
+        .write_mesh(&[0.0, 0.0, 0.0], (&[], &[]))
+        .unwrap();
+
+    let point_data = vec![
+        (
+            "temperature".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![293.15].into()),
+        ),
+        (
+            "pressure".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![1.0].into()),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                    <Information Name="units" Value="K"/>
+                    <Information Name="description" Value="fluid temperature"/>
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">2.9314999999999998e2</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+    <Information Name="solver" Value="my_solver 1.2.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    // the file can still be read and validated
+    let report = xdmf::validate::validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert!(report.issues().is_empty());
+}
+
+#[test]
+fn write_xdmf_field_descriptor() {
+    use xdmf::dictionary::{FieldDescriptor, Quantity, UnitSystem};
+
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_field_descriptor(&Quantity::Displacement.descriptor(UnitSystem::MmTonneSecond))
+        .with_field_descriptor(&FieldDescriptor::new(
+            "damage",
+            "1",
+            "Damage of the material",
+        ))
+        .write_mesh(&[0.0, 0.0, 0.0], (&[], &[]))
+        .unwrap();
+
+    let point_data = vec![
+        (
+            "displacement".to_string(),
+            (xdmf::DataAttribute::Vector, vec![0.1, 0.0, 0.0].into()),
+        ),
+        (
+            "damage".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![0.5].into()),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="damage" AttributeType="Scalar" Center="Node">
+                    <Information Name="units" Value="1"/>
+                    <Information Name="description" Value="Damage of the material"/>
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">5.0000000000000000e-1</DataItem>
+                </Attribute>
+                <Attribute Name="displacement" AttributeType="Vector" Center="Node">
+                    <Information Name="units" Value="mm"/>
+                    <Information Name="description" Value="Displacement of the structure"/>
+                    <DataItem Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">1.0000000000000001e-1 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_component_names() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_component_names("velocity", &["vx", "vy", "vz"])
+        .with_component_names("pressure", &["p", "p_ref"])
+        .write_mesh(&[0.0, 0.0, 0.0], (&[], &[]))
+        .unwrap();
+
+    let point_data = vec![(
+        "velocity".to_string(),
+        (xdmf::DataAttribute::Vector, vec![1.0, 2.0, 3.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    // the names are written in every time step
+    for time in ["0", "1"] {
+        xdmf_writer
+            .write_data(time, Some(&point_data), None)
+            .unwrap();
+    }
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert_eq!(
+        read_xdmf
+            .matches(r#"<Information Name="component_names" Value="vx vy vz"/>"#)
+            .count(),
+        2
+    );
+
+    // the number of names must match the number of components
+    let point_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+        xdmf_writer
+            .write_data("2", Some(&point_data), None)
+            .unwrap_err()
+            .to_string(),
+        "Field 'pressure' has 1 components, but 2 component names are given"
+    );
+}
+
+#[test]
+fn write_xdmf_averaged_fields() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_cell_average("temperature", "temperature_cell")
+        .with_point_average("material", "material_point")
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 2.0, 0.0, 0.0],
+            (
+                &[0, 1, 2, 1, 3],
+                &[xdmf::CellType::Triangle, xdmf::CellType::Edge],
+            ),
+        )
+        .unwrap();
+
+    let point_data = vec![(
+        "temperature".to_string(),
+        (
+            xdmf::DataAttribute::Scalar,
+            vec![290.0, 300.0, 310.0, 320.0].into(),
+        ),
+    )]
+    .into_iter()
+    .collect();
+
+    let cell_data = vec![(
+        "material".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1_u64, 2].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), Some(&cell_data))
+        .unwrap();
+
+    // the source field is only averaged if it is written
+    xdmf_writer
+        .write_data("1", Some(&point_data), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="material_point" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="4" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 1.5000000000000000e0 1.0000000000000000e0 2.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="4" NumberType="Float" Format="XML" Precision="8">2.9000000000000000e2 3.0000000000000000e2 3.1000000000000000e2 3.2000000000000000e2</DataItem>
+                </Attribute>
+                <Attribute Name="material" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="2" NumberType="UInt" Format="XML" Precision="8">1 2</DataItem>
+                </Attribute>
+                <Attribute Name="temperature_cell" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">3.0000000000000000e2 3.1000000000000000e2</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t1" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="1"/>
+                <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="4" NumberType="Float" Format="XML" Precision="8">2.9000000000000000e2 3.0000000000000000e2 3.1000000000000000e2 3.2000000000000000e2</DataItem>
+                </Attribute>
+                <Attribute Name="temperature_cell" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">3.0000000000000000e2 3.1000000000000000e2</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="4 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="8" NumberType="UInt" Format="XML" Precision="4">4 0 1 2 2 2 1 3</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    let cell_data = vec![(
+        "temperature_cell".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![0.0, 0.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+        xdmf_writer
+            .write_data("2", Some(&point_data), Some(&cell_data))
+            .unwrap_err()
+            .to_string(),
+        "Averaged field 'temperature_cell' conflicts with the cell-data of the same name"
+    );
+}
+
+#[test]
+fn write_xdmf_statistics() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_statistics(true)
+        .with_field_information("temperature", "units", "K")
+        .with_field_precision("temperature", xdmf::FloatPrecision::Single)
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            (&[0, 1], &[xdmf::CellType::Edge]),
+        )
+        .unwrap();
+
+    let point_data = vec![
+        (
+            "temperature".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![290.0, 300.5].into()),
+        ),
+        (
+            "velocity".to_string(),
+            (
+                xdmf::DataAttribute::Vector,
+                vec![1.0, -2.0, 0.0, 3.0, 0.0, 4.0].into(),
+            ),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    let cell_data = vec![(
+        "material".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![7_u64].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), Some(&cell_data))
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                    <Information Name="units" Value="K"/>
+                    <Information Name="min" Value="2.9000000000000000e2"/>
+                    <Information Name="max" Value="3.0050000000000000e2"/>
+                    <Information Name="mean" Value="2.9525000000000000e2"/>
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="4">2.9000000e2 3.0050000e2</DataItem>
+                </Attribute>
+                <Attribute Name="velocity" AttributeType="Vector" Center="Node">
+                    <Information Name="min" Value="-2.0000000000000000e0"/>
+                    <Information Name="max" Value="4.0000000000000000e0"/>
+                    <Information Name="mean" Value="1.0000000000000000e0"/>
+                    <DataItem Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 -2.0000000000000000e0 0.0000000000000000e0 3.0000000000000000e0 0.0000000000000000e0 4.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="material" AttributeType="Scalar" Center="Cell">
+                    <Information Name="min" Value="7.0000000000000000e0"/>
+                    <Information Name="max" Value="7.0000000000000000e0"/>
+                    <Information Name="mean" Value="7.0000000000000000e0"/>
+                    <DataItem Dimensions="1" NumberType="UInt" Format="XML" Precision="8">7</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="4" NumberType="UInt" Format="XML" Precision="4">2 2 0 1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    let report = xdmf::validate::validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert!(report.issues().is_empty());
+}
+
+#[test]
+fn write_xdmf_multiple_bodies() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut body_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::Ascii)
+        .unwrap()
+        .into_multi_body_writer()
+        .unwrap();
+
+    let data = |value: f64| -> xdmf::DataMap<'static> {
+        vec![(
+            "data".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![value].into()),
+        )]
+        .into_iter()
+        .collect()
+    };
+
+    let rod = (&[0, 1][..], &[xdmf::CellType::Edge][..]);
+
+    // the rod moves, the ball only enters the scene in the second time step
+    body_writer
+        .write_body(
+            "rod",
+            "0.0",
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            rod,
+            None,
+            Some(&data(1.0)),
+        )
+        .unwrap();
+    body_writer
+        .write_body(
+            "ball",
+            "1.0",
+            &[3.0, 0.0, 0.0],
+            (&[], &[]),
+            Some(&data(2.0)),
+            None,
+        )
+        .unwrap();
+    body_writer
+        .write_body(
+            "rod",
+            "1.0",
+            &[0.5, 0.0, 0.0, 1.5, 0.0, 0.0],
+            rod,
+            None,
+            Some(&data(3.0)),
+        )
+        .unwrap();
+
+    assert_eq!(
+        body_writer
+            .write_body("rod", "1.0", &[0.0; 6], rod, None, None)
+            .unwrap_err()
+            .to_string(),
+        "Body 'rod' has already been written for time step '1.0'"
+    );
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="bodies" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="bodies-t0.0" GridType="Collection" CollectionType="Spatial">
+                <Grid Name="rod-t0.0" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_rod_t0.0"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Mixed" NumberOfElements="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_rod_t0.0"]</DataItem>
+                    </Topology>
+                    <Attribute Name="data" AttributeType="Scalar" Center="Cell">
+                        <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">
+                            <xi:include href="test_output.txt/data_t_0.0_cell_data_data_rod.txt" parse="text"/>
+                        </DataItem>
+                    </Attribute>
+                </Grid>
+                <Time Value="0.0"/>
+            </Grid>
+            <Grid Name="bodies-t1.0" GridType="Collection" CollectionType="Spatial">
+                <Grid Name="ball-t1.0" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_ball_t1.0"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_ball_t1.0"]</DataItem>
+                    </Topology>
+                    <Attribute Name="data" AttributeType="Scalar" Center="Node">
+                        <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">
+                            <xi:include href="test_output.txt/data_t_1.0_point_data_data_ball.txt" parse="text"/>
+                        </DataItem>
+                    </Attribute>
+                </Grid>
+                <Grid Name="rod-t1.0" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_rod_t1.0"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Mixed" NumberOfElements="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_rod_t1.0"]</DataItem>
+                    </Topology>
+                    <Attribute Name="data" AttributeType="Scalar" Center="Cell">
+                        <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">
+                            <xi:include href="test_output.txt/data_t_1.0_cell_data_data_rod.txt" parse="text"/>
+                        </DataItem>
+                    </Attribute>
+                </Grid>
+                <Time Value="1.0"/>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords_rod_t0.0" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">
+            <xi:include href="test_output.txt/mesh_rod_t0.0_points.txt" parse="text"/>
+        </DataItem>
+        <DataItem Name="connectivity_rod_t0.0" Dimensions="4" NumberType="UInt" Format="XML" Precision="4">
+            <xi:include href="test_output.txt/mesh_rod_t0.0_cells.txt" parse="text"/>
+        </DataItem>
+        <DataItem Name="coords_ball_t1.0" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">
+            <xi:include href="test_output.txt/mesh_ball_t1.0_points.txt" parse="text"/>
+        </DataItem>
+        <DataItem Name="connectivity_ball_t1.0" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">
+            <xi:include href="test_output.txt/mesh_ball_t1.0_cells.txt" parse="text"/>
+        </DataItem>
+        <DataItem Name="coords_rod_t1.0" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">
+            <xi:include href="test_output.txt/mesh_rod_t1.0_points.txt" parse="text"/>
+        </DataItem>
+        <DataItem Name="connectivity_rod_t1.0" Dimensions="4" NumberType="UInt" Format="XML" Precision="4">
+            <xi:include href="test_output.txt/mesh_rod_t1.0_cells.txt" parse="text"/>
+        </DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="Ascii"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_multiple_meshes() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .add_mesh(
+            "fluid",
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            (&[0, 1, 2], &[xdmf::CellType::Triangle]),
+        )
+        .unwrap();
+
+    xdmf_writer
+        .add_mesh(
+            "structure",
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            (&[0, 1], &[xdmf::CellType::Edge]),
+        )
+        .unwrap();
+
+    // the names of the meshes must be unique
+    assert_eq!(
+        xdmf_writer
+            .add_mesh("fluid", &[0.0; 3], (&[], &[]))
+            .unwrap_err()
+            .to_string(),
+        "Mesh 'fluid' has already been added"
+    );
+
+    let data = |values: Vec<f64>| -> xdmf::DataMap<'static> {
+        vec![(
+            "data".to_string(),
+            (xdmf::DataAttribute::Scalar, values.into()),
+        )]
+        .into_iter()
+        .collect()
+    };
+
+    xdmf_writer
+        .write_data("fluid", "0", Some(&data(vec![1.0, 2.0, 3.0])), None)
+        .unwrap();
+    xdmf_writer
+        .write_data("structure", "0", None, Some(&data(vec![4.0])))
+        .unwrap();
+    xdmf_writer
+        .write_data("fluid", "1", Some(&data(vec![5.0, 6.0, 7.0])), None)
+        .unwrap();
+
+    // the data is validated against the mesh it is written to
+    assert_eq!(
+        xdmf_writer
+            .write_data("structure", "1", Some(&data(vec![1.0, 2.0, 3.0])), None)
+            .unwrap_err()
+            .to_string(),
+        "Size of point-data 'data' must be 2, but is 3"
+    );
+
+    assert_eq!(
+        xdmf_writer
+            .write_data("solid", "1", Some(&data(vec![1.0])), None)
+            .unwrap_err()
+            .to_string(),
+        "Mesh 'solid' has not been added"
+    );
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="meshes" GridType="Collection" CollectionType="Spatial">
+            <Grid Name="fluid" GridType="Collection" CollectionType="Temporal">
+                <Grid Name="fluid-t0" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_fluid"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Triangle" NumberOfElements="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_fluid"]</DataItem>
+                    </Topology>
+                    <Time Value="0"/>
+                    <Attribute Name="data" AttributeType="Scalar" Center="Node">
+                        <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0</DataItem>
+                    </Attribute>
+                </Grid>
+                <Grid Name="fluid-t1" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_fluid"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Triangle" NumberOfElements="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_fluid"]</DataItem>
+                    </Topology>
+                    <Time Value="1"/>
+                    <Attribute Name="data" AttributeType="Scalar" Center="Node">
+                        <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">5.0000000000000000e0 6.0000000000000000e0 7.0000000000000000e0</DataItem>
+                    </Attribute>
+                </Grid>
+            </Grid>
+            <Grid Name="structure" GridType="Collection" CollectionType="Temporal">
+                <Grid Name="structure-t0" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_structure"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Mixed" NumberOfElements="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_structure"]</DataItem>
+                    </Topology>
+                    <Time Value="0"/>
+                    <Attribute Name="data" AttributeType="Scalar" Center="Cell">
+                        <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">4.0000000000000000e0</DataItem>
+                    </Attribute>
+                </Grid>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords_fluid" Dimensions="3 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity_fluid" Dimensions="3" NumberType="UInt" Format="XML" Precision="4">0 1 2</DataItem>
+        <DataItem Name="coords_structure" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity_structure" Dimensions="4" NumberType="UInt" Format="XML" Precision="4">2 2 0 1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_multiple_domains() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+    let cells = (&[0, 1][..], &[xdmf::CellType::Edge][..]);
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .add_mesh_to_domain("smoothed", "beam_smoothed", &coords, cells)
+        .unwrap();
+
+    xdmf_writer.add_mesh("beam", &coords, cells).unwrap();
+
+    // the names of the meshes must be unique over all domains
+    assert_eq!(
+        xdmf_writer
+            .add_mesh_to_domain("raw", "beam", &coords, cells)
+            .unwrap_err()
+            .to_string(),
+        "Mesh 'beam' has already been added"
+    );
+
+    assert_eq!(
+        xdmf_writer
+            .add_mesh_to_domain("with space", "beam_raw", &coords, cells)
+            .unwrap_err()
+            .to_string(),
+        "Domain name 'with space' is not valid, must be non-empty and contain only alphanumeric characters, underscores or dashes"
+    );
+
+    let data = |values: Vec<f64>| -> xdmf::DataMap<'static> {
+        vec![(
+            "data".to_string(),
+            (xdmf::DataAttribute::Scalar, values.into()),
+        )]
+        .into_iter()
+        .collect()
+    };
+
+    xdmf_writer
+        .write_data("beam", "0", Some(&data(vec![1.0, 3.0])), None)
+        .unwrap();
+    xdmf_writer
+        .write_data("beam_smoothed", "0", Some(&data(vec![2.0, 2.0])), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="meshes" GridType="Collection" CollectionType="Spatial">
+            <Grid Name="beam" GridType="Collection" CollectionType="Temporal">
+                <Grid Name="beam-t0" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_beam"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Mixed" NumberOfElements="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_beam"]</DataItem>
+                    </Topology>
+                    <Time Value="0"/>
+                    <Attribute Name="data" AttributeType="Scalar" Center="Node">
+                        <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 3.0000000000000000e0</DataItem>
+                    </Attribute>
+                </Grid>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords_beam" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity_beam" Dimensions="4" NumberType="UInt" Format="XML" Precision="4">2 2 0 1</DataItem>
+    </Domain>
+    <Domain Name="smoothed">
+        <Grid Name="meshes" GridType="Collection" CollectionType="Spatial">
+            <Grid Name="beam_smoothed" GridType="Collection" CollectionType="Temporal">
+                <Grid Name="beam_smoothed-t0" GridType="Uniform">
+                    <Geometry GeometryType="XYZ">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_beam_smoothed"]</DataItem>
+                    </Geometry>
+                    <Topology TopologyType="Mixed" NumberOfElements="1">
+                        <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_beam_smoothed"]</DataItem>
+                    </Topology>
+                    <Time Value="0"/>
+                    <Attribute Name="data" AttributeType="Scalar" Center="Node">
+                        <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">2.0000000000000000e0 2.0000000000000000e0</DataItem>
+                    </Attribute>
+                </Grid>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords_beam_smoothed" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity_beam_smoothed" Dimensions="4" NumberType="UInt" Format="XML" Precision="4">2 2 0 1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let xdmf_file = xdmf_file_path.with_extension("xdmf2");
+    let read_xdmf = std::fs::read_to_string(&xdmf_file).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    let report = xdmf::validate::validate_file(&xdmf_file).unwrap();
+    assert!(report.is_valid());
+}
+
+#[test]
+fn write_xdmf_data_overwrite() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::Ascii)
+        .unwrap()
+        .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+        .unwrap();
+
+    let data = |values: Vec<f64>| -> xdmf::DataMap<'static> {
+        vec![(
+            "data".to_string(),
+            (xdmf::DataAttribute::Scalar, values.into()),
+        )]
+        .into_iter()
+        .collect()
+    };
+
+    xdmf_writer
+        .write_data("0", Some(&data(vec![1.0, 2.0])), None)
+        .unwrap();
+    xdmf_writer
+        .write_data("1", Some(&data(vec![3.0, 4.0])), None)
+        .unwrap();
+
+    // existing time steps are only replaced when overwriting
+    assert_eq!(
+        xdmf_writer
+            .write_data("0", Some(&data(vec![5.0, 6.0])), None)
+            .unwrap_err()
+            .to_string(),
+        "Time step '0' has already been written"
+    );
+
+    xdmf_writer
+        .write_data_overwrite("0", Some(&data(vec![7.0, 8.0])), None)
+        .unwrap();
+
+    // a time step that has not been written yet is added
+    xdmf_writer
+        .write_data_overwrite("2", Some(&data(vec![8.0, 9.0])), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="data" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">
+                        <xi:include href="test_output.txt/data_t_0_point_data_data.txt" parse="text"/>
+                    </DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t1" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="1"/>
+                <Attribute Name="data" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">
+                        <xi:include href="test_output.txt/data_t_1_point_data_data.txt" parse="text"/>
+                    </DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t2" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="2"/>
+                <Attribute Name="data" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">
+                        <xi:include href="test_output.txt/data_t_2_point_data_data.txt" parse="text"/>
+                    </DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">
+            <xi:include href="test_output.txt/points.txt" parse="text"/>
+        </DataItem>
+        <DataItem Name="connectivity" Dimensions="4" NumberType="UInt" Format="XML" Precision="4">
+            <xi:include href="test_output.txt/cells.txt" parse="text"/>
+        </DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="Ascii"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    // the heavy data is overwritten as well
+    let read_data = std::fs::read_to_string(
+        tmp_dir
+            .path()
+            .join("test_output.txt/data_t_0_point_data_data.txt"),
+    )
+    .unwrap();
+    assert_eq!(read_data, "7.0000000000000000e0 8.0000000000000000e0\n");
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn write_xdmf_data_parallel() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let point_data = (0..10)
+        .map(|i| {
+            (
+                format!("point_field_{i}"),
+                (
+                    xdmf::DataAttribute::Vector,
+                    vec![f64::from(i) * 0.5; 9].into(),
+                ),
+            )
+        })
+        .collect();
+    let cell_data = vec![
+        (
+            "material".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![1_u64, 2].into()),
+        ),
+        (
+            "stress".to_string(),
+            (xdmf::DataAttribute::Tensor6, vec![0.25_f32; 12].into()),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    // the fields written concurrently must result in the same files as written sequentially
+    for data_storage in [
+        xdmf::DataStorage::Ascii,
+        xdmf::DataStorage::AsciiGz,
+        xdmf::DataStorage::AsciiInline,
+        xdmf::DataStorage::Base64Inline,
+    ] {
+        let write = |folder_name: &str, parallel: bool| {
+            let xdmf_file_path = tmp_dir.path().join(folder_name).join("test_output");
+
+            let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, data_storage)
+                .unwrap()
+                .write_mesh(
+                    &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+                    (
+                        &[0, 1, 0, 1, 2],
+                        &[xdmf::CellType::Edge, xdmf::CellType::Triangle],
+                    ),
+                )
+                .unwrap();
+
+            for step in ["0", "1"] {
+                if parallel {
+                    xdmf_writer
+                        .write_data_parallel(step, Some(&point_data), Some(&cell_data))
+                        .unwrap();
+                } else {
+                    xdmf_writer
+                        .write_data(step, Some(&point_data), Some(&cell_data))
+                        .unwrap();
+                }
+            }
+
+            xdmf_file_path
+        };
+
+        let sequential_path = write("sequential", false);
+        let parallel_path = write("parallel", true);
+
+        pretty_assertions::assert_eq!(
+            std::fs::read_to_string(sequential_path.with_extension("xdmf2")).unwrap(),
+            std::fs::read_to_string(parallel_path.with_extension("xdmf2")).unwrap()
+        );
+
+        let data_dir = sequential_path.with_extension("txt");
+        if data_dir.exists() {
+            for entry in std::fs::read_dir(&data_dir).unwrap() {
+                let file_name = entry.unwrap().file_name();
+                assert_eq!(
+                    std::fs::read(data_dir.join(&file_name)).unwrap(),
+                    std::fs::read(parallel_path.with_extension("txt").join(&file_name)).unwrap()
+                );
+            }
+        }
+
+        std::fs::remove_dir_all(tmp_dir.path().join("sequential")).unwrap();
+        std::fs::remove_dir_all(tmp_dir.path().join("parallel")).unwrap();
+    }
+}
+
+#[test]
+fn write_xdmf_registered_fields() {
+    use xdmf::{DataAttribute, xdmf_elements::attribute::Center};
+
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+        .unwrap();
+
+    assert_eq!(
+        xdmf_writer
+            .register_fields(&[
+                ("pressure", DataAttribute::Scalar, Center::Node),
+                ("pressure", DataAttribute::Scalar, Center::Node),
+            ])
+            .unwrap_err()
+            .to_string(),
+        "Field 'pressure' of point-data is registered multiple times"
+    );
+
+    xdmf_writer
+        .register_fields(&[
+            ("pressure", DataAttribute::Scalar, Center::Node),
+            ("stress", DataAttribute::Tensor6, Center::Cell),
+            ("energy", DataAttribute::Scalar, Center::Grid),
+        ])
+        .unwrap();
+
+    let data = |name: &str, data_attribute: DataAttribute, len: usize| -> xdmf::DataMap<'static> {
+        vec![(name.to_string(), (data_attribute, vec![1.0; len].into()))]
+            .into_iter()
+            .collect()
+    };
+
+    let point_data = data("pressure", DataAttribute::Scalar, 2);
+    let cell_data = data("stress", DataAttribute::Tensor6, 6);
+
+    assert_eq!(
+        xdmf_writer
+            .write_data("0", Some(&point_data), None)
+            .unwrap_err()
+            .to_string(),
+        "Registered field 'stress' of cell-data is missing"
+    );
+
+    assert_eq!(
+        xdmf_writer
+            .write_data(
+                "0",
+                Some(&data("velocity", DataAttribute::Vector, 6)),
+                Some(&cell_data)
+            )
+            .unwrap_err()
+            .to_string(),
+        "Field 'velocity' of point-data is not registered"
+    );
+
+    assert_eq!(
+        xdmf_writer
+            .write_data(
+                "0",
+                Some(&data("pressure", DataAttribute::Generic(vec![1]), 2)),
+                Some(&cell_data)
+            )
+            .unwrap_err()
+            .to_string(),
+        "Field 'pressure' of point-data is registered as Scalar, but is Generic([1])"
+    );
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), Some(&cell_data))
+        .unwrap();
+
+    assert_eq!(
+        xdmf_writer
+            .write_global_data("0", &data("residual", DataAttribute::Scalar, 1))
+            .unwrap_err()
+            .to_string(),
+        "Field 'residual' of global-data is not registered"
+    );
+    xdmf_writer
+        .write_global_data("0", &data("energy", DataAttribute::Scalar, 1))
+        .unwrap();
+
+    assert_eq!(
+        xdmf_writer
+            .write_point_data_streamed("1", "temperature", DataAttribute::Scalar, [0.0; 2], 2)
+            .unwrap_err()
+            .to_string(),
+        "Field 'temperature' of point-data is not registered"
+    );
+
+    // the fields can not be changed after time steps were written
+    assert_eq!(
+        xdmf_writer
+            .register_fields(&[("pressure", DataAttribute::Scalar, Center::Node)])
+            .unwrap_err()
+            .to_string(),
+        "Fields must be registered before the first time step is written"
+    );
+}
+
+#[test]
+fn write_xdmf_derived_fields() {
+    use xdmf::{DataAttribute, xdmf_elements::attribute::Center};
+
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+        .unwrap();
+
+    assert_eq!(
+        xdmf_writer
+            .register_derived_field(
+                "speed",
+                DataAttribute::Scalar,
+                Center::Node,
+                "SQRT($0*$0 + $1*$1 + $2*$2)",
+                &["velocity_x", "velocity_y"],
+            )
+            .unwrap_err()
+            .to_string(),
+        "Function of derived field 'speed' refers to $2, but only 2 source fields are given"
+    );
+
+    assert_eq!(
+        xdmf_writer
+            .register_derived_field(
+                "speed",
+                DataAttribute::Scalar,
+                Center::Grid,
+                "$0",
+                &["velocity_x"]
+            )
+            .unwrap_err()
+            .to_string(),
+        "Derived field 'speed' can not be registered, only fields of point- or cell-data are supported"
+    );
+
+    xdmf_writer
+        .register_derived_field(
+            "speed",
+            DataAttribute::Scalar,
+            Center::Node,
+            "SQRT($0*$0 + $1*$1)",
+            &["velocity_x", "velocity_y"],
+        )
+        .unwrap();
+
+    let point_data: xdmf::DataMap = vec![
+        (
+            "velocity_x".to_string(),
+            (DataAttribute::Scalar, vec![3.0, 0.0].into()),
+        ),
+        (
+            "velocity_y".to_string(),
+            (DataAttribute::Scalar, vec![4.0, 1.0].into()),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0.0", Some(&point_data), None)
+        .unwrap();
+
+    // time steps without all source fields have no derived field
+    let cell_data: xdmf::DataMap = vec![(
+        "stress".to_string(),
+        (DataAttribute::Scalar, vec![1.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("1.0", None, Some(&cell_data))
+        .unwrap();
+
+    let conflicting_data: xdmf::DataMap = vec![(
+        "speed".to_string(),
+        (DataAttribute::Scalar, vec![5.0, 1.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+        xdmf_writer
+            .write_data("2.0", Some(&conflicting_data), None)
+            .unwrap_err()
+            .to_string(),
+        "Data 'speed' of point-data conflicts with the derived field of the same name"
+    );
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0.0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0.0"/>
+                <Attribute Name="velocity_x" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">3.0000000000000000e0 0.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="velocity_y" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">4.0000000000000000e0 1.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="speed" AttributeType="Scalar" Center="Node">
+                    <DataItem ItemType="Function" Function="SQRT($0*$0 + $1*$1)" Dimensions="2">
+                        <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">3.0000000000000000e0 0.0000000000000000e0</DataItem>
+                        <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">4.0000000000000000e0 1.0000000000000000e0</DataItem>
+                    </DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t1.0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="1.0"/>
+                <Attribute Name="stress" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="4" NumberType="UInt" Format="XML" Precision="4">2 2 0 1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_flush_policy() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let data = |value: f64| -> xdmf::DataMap<'static> {
+        vec![(
+            "data".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![value].into()),
+        )]
+        .into_iter()
+        .collect()
+    };
+
+    let read_xdmf = |xdmf_file_path: &std::path::Path| {
+        std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap()
+    };
+
+    // the XDMF file is rewritten after every second time step
+    let xdmf_file_path = tmp_dir.path().join("every_n");
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_flush_policy(xdmf::FlushPolicy::EveryN(2))
+        .write_points(&[0.0; 3])
+        .unwrap();
+
+    xdmf_writer.write_data("0", Some(&data(0.0)), None).unwrap();
+    assert!(!read_xdmf(&xdmf_file_path).contains("time_series-t0"));
+
+    xdmf_writer.write_data("1", Some(&data(1.0)), None).unwrap();
+    assert!(read_xdmf(&xdmf_file_path).contains("time_series-t1"));
+
+    xdmf_writer.write_data("2", Some(&data(2.0)), None).unwrap();
+    assert!(!read_xdmf(&xdmf_file_path).contains("time_series-t2"));
+
+    xdmf_writer.flush_xml().unwrap();
+    assert!(read_xdmf(&xdmf_file_path).contains("time_series-t2"));
+
+    // the XDMF file is only written when finalizing
+    let xdmf_file_path = tmp_dir.path().join("on_finalize");
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_flush_policy(xdmf::FlushPolicy::OnFinalize)
+        .write_points(&[0.0; 3])
+        .unwrap();
+
+    // the mesh is written immediately
+    let mesh_only = read_xdmf(&xdmf_file_path);
+
+    for step in 0..3 {
+        xdmf_writer
+            .write_data(&step, Some(&data(f64::from(step))), None)
+            .unwrap();
+    }
+    assert_eq!(read_xdmf(&xdmf_file_path), mesh_only);
+
+    xdmf_writer.finalize().unwrap();
+
+    let written = read_xdmf(&xdmf_file_path);
+    for step in 0..3 {
+        assert!(written.contains(&format!("time_series-t{step}")));
+    }
+}
+
+#[test]
+fn write_xdmf_retention_policy() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let data = |value: f64| -> xdmf::DataMap<'static> {
+        vec![(
+            "data".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![value].into()),
+        )]
+        .into_iter()
+        .collect()
+    };
+
+    let read_xdmf = |xdmf_file_path: &std::path::Path| {
+        std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap()
+    };
+
+    let data_file = |xdmf_file_path: &std::path::Path, step: i32| {
+        xdmf_file_path
+            .with_extension("txt")
+            .join(format!("data_t_{step}_point_data_data.txt"))
+    };
+
+    // only the last two time steps are kept, the files of the others are deleted
+    let xdmf_file_path = tmp_dir.path().join("keep_last");
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::Ascii)
+        .unwrap()
+        .with_retention_policy(xdmf::RetentionPolicy::KeepLast(2))
+        .write_points(&[0.0; 3])
+        .unwrap();
+
+    for step in 0..5 {
+        xdmf_writer
+            .write_data(&step, Some(&data(f64::from(step))), None)
+            .unwrap();
+    }
+
+    let written = read_xdmf(&xdmf_file_path);
+    for step in 0..5 {
+        assert_eq!(written.contains(&format!("time_series-t{step}")), step >= 3);
+        assert_eq!(data_file(&xdmf_file_path, step).exists(), step >= 3);
+    }
+
+    assert_eq!(
+        xdmf_writer
+            .write_data(&0, Some(&data(0.0)), None)
+            .unwrap_err()
+            .to_string(),
+        "Time step '0' has been pruned by the retention policy, it can not be written again"
+    );
+
+    // every second time step is kept, along with the most recent one
+    let xdmf_file_path = tmp_dir.path().join("keep_every");
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_retention_policy(xdmf::RetentionPolicy::KeepEvery(2))
+        .write_points(&[0.0; 3])
+        .unwrap();
+
+    for step in 0..4 {
+        xdmf_writer
+            .write_data(&step, Some(&data(f64::from(step))), None)
+            .unwrap();
+    }
+
+    let written = read_xdmf(&xdmf_file_path);
+    for step in 0..4 {
+        assert_eq!(written.contains(&format!("time_series-t{step}")), step != 1);
+    }
+
+    // the files of pruned time steps are deleted once the XDMF file does not reference them anymore
+    let xdmf_file_path = tmp_dir.path().join("on_finalize");
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::Ascii)
+        .unwrap()
+        .with_flush_policy(xdmf::FlushPolicy::OnFinalize)
+        .with_retention_policy(xdmf::RetentionPolicy::KeepLast(1))
+        .write_points(&[0.0; 3])
+        .unwrap();
+
+    for step in 0..2 {
+        xdmf_writer
+            .write_data(&step, Some(&data(f64::from(step))), None)
+            .unwrap();
+    }
+    assert!(data_file(&xdmf_file_path, 0).exists());
+
+    xdmf_writer.finalize().unwrap();
+    assert!(!data_file(&xdmf_file_path, 0).exists());
+    assert!(data_file(&xdmf_file_path, 1).exists());
+
+    // the writers of multiple meshes and bodies keep all time steps
+    let create_writer = |name: &str| {
+        TimeSeriesWriter::new(tmp_dir.path().join(name), xdmf::DataStorage::AsciiInline)
+            .unwrap()
+            .with_retention_policy(xdmf::RetentionPolicy::KeepLast(1))
+    };
+    let error = "Retention policies are only supported when writing a single mesh, not for multiple meshes or bodies";
+
+    assert_eq!(
+        create_writer("multi_mesh")
+            .add_mesh("mesh", &[0.0; 3], (&[], &[]))
+            .err()
+            .unwrap()
+            .to_string(),
+        error
+    );
+    assert_eq!(
+        create_writer("multi_mesh_domain")
+            .add_mesh_to_domain("domain", "mesh", &[0.0; 3], (&[], &[]))
+            .err()
+            .unwrap()
+            .to_string(),
+        error
+    );
+    assert_eq!(
+        create_writer("multi_body")
+            .into_multi_body_writer()
+            .err()
+            .unwrap()
+            .to_string(),
+        error
+    );
+}
+
+#[test]
+fn write_xdmf_close_and_drop() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let data: xdmf::DataMap = vec![(
+        "data".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    let read_xdmf = |xdmf_file_path: &std::path::Path| {
+        std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap()
+    };
+
+    let create_writer = |xdmf_file_path: &std::path::Path| {
+        TimeSeriesWriter::new(xdmf_file_path, xdmf::DataStorage::Ascii)
+            .unwrap()
+            .with_flush_policy(xdmf::FlushPolicy::OnFinalize)
+            .write_points(&[0.0; 3])
+            .unwrap()
+    };
+
+    // closing explicitly writes the pending time steps
+    let xdmf_file_path = tmp_dir.path().join("closed");
+    let mut xdmf_writer = create_writer(&xdmf_file_path);
+    xdmf_writer.write_data("0", Some(&data), None).unwrap();
+    xdmf_writer.close().unwrap();
+    assert!(read_xdmf(&xdmf_file_path).contains("time_series-t0"));
+
+    // dropping the writer writes the pending time steps as well
+    let xdmf_file_path = tmp_dir.path().join("dropped");
+    let mut xdmf_writer = create_writer(&xdmf_file_path);
+    xdmf_writer.write_data("0", Some(&data), None).unwrap();
+    drop(xdmf_writer);
+    assert!(read_xdmf(&xdmf_file_path).contains("time_series-t0"));
+
+    // also when the application panics
+    let xdmf_file_path = tmp_dir.path().join("panicked");
+    let mut xdmf_writer = create_writer(&xdmf_file_path);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+        xdmf_writer.write_data("0", Some(&data), None).unwrap();
+        panic!("simulation failed");
+    }));
+    assert!(result.is_err());
+    assert!(read_xdmf(&xdmf_file_path).contains("time_series-t0"));
+}
+
+#[test]
+fn write_xdmf_memory_file_system() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let data = |value: f64| -> xdmf::DataMap<'static> {
+        vec![(
+            "data".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![value].into()),
+        )]
+        .into_iter()
+        .collect()
+    };
+
+    for data_storage in [xdmf::DataStorage::Ascii, xdmf::DataStorage::AsciiGz] {
+        let memory = xdmf::MemoryFileSystem::default();
+        let xdmf_file_path = tmp_dir.path().join("in_memory");
+
+        let mut xdmf_writer =
+            TimeSeriesWriter::new_with_file_system(&xdmf_file_path, data_storage, memory.clone())
+                .unwrap()
+                .with_retention_policy(xdmf::RetentionPolicy::KeepLast(1))
+                .write_points(&[0.0; 3])
+                .unwrap();
+
+        for step in 0..2 {
+            xdmf_writer
+                .write_data(&step, Some(&data(f64::from(step))), None)
+                .unwrap();
+        }
+        xdmf_writer.close().unwrap();
+
+        // nothing is written to disk
+        assert_eq!(std::fs::read_dir(tmp_dir.path()).unwrap().count(), 0);
+
+        let files: Vec<_> = memory
+            .files()
+            .into_keys()
+            .map(|path| {
+                path.strip_prefix(tmp_dir.path())
+                    .unwrap()
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        let extension = if data_storage == xdmf::DataStorage::AsciiGz {
+            ".gz"
+        } else {
+            ""
+        };
+
+        // the files of the pruned time step were removed
+        assert_eq!(
+            files,
+            vec![
+                format!("in_memory.txt/cells.txt{extension}"),
+                format!("in_memory.txt/data_t_1_point_data_data.txt{extension}"),
+                format!("in_memory.txt/points.txt{extension}"),
+                "in_memory.xdmf2".to_string(),
+            ]
+        );
+
+        let xdmf_file =
+            String::from_utf8(memory.file(xdmf_file_path.with_extension("xdmf2")).unwrap())
+                .unwrap();
+        assert!(!xdmf_file.contains("time_series-t0"));
+        assert!(xdmf_file.contains("time_series-t1"));
+    }
+
+    assert_eq!(
+        TimeSeriesWriter::new_with_file_system(
+            tmp_dir.path().join("in_memory"),
+            xdmf::DataStorage::Hdf5SingleFile,
+            xdmf::MemoryFileSystem::default()
+        )
+        .err()
+        .unwrap()
+        .to_string(),
+        "Hdf5SingleFile DataStorage can only write to the local file system"
+    );
+}
+
+#[test]
+fn write_xdmf_memory_file_system_inline() {
+    // as in a browser, where there is no file system
+    let memory = xdmf::MemoryFileSystem::default();
+
+    let mut xdmf_writer = TimeSeriesWriter::new_with_file_system(
+        "dataset",
+        xdmf::DataStorage::Base64Inline,
+        memory.clone(),
+    )
+    .unwrap()
+    .write_mesh(
+        &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+        (&[0, 1], &[xdmf::CellType::Edge]),
+    )
+    .unwrap();
+
+    let point_data = vec![(
+        "data".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+    xdmf_writer
+        .write_data("0.0", Some(&point_data), None)
+        .unwrap();
+    xdmf_writer.close().unwrap();
+
+    // the XDMF file is self-contained
+    let files = memory.files();
+    assert_eq!(
+        files.keys().collect::<Vec<_>>(),
+        [std::path::Path::new("dataset.xdmf2")]
+    );
+    assert!(
+        String::from_utf8_lossy(&files[std::path::Path::new("dataset.xdmf2")]).contains("Base64")
+    );
+}
+
+#[test]
+fn write_xdmf_custom_data_writer() {
+    use std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+    };
+
+    use xdmf::{
+        DataStorage, DataWriter, Values, XdmfResult,
+        xdmf_elements::{
+            attribute::Center,
+            data_item::{DataContent, Format},
+        },
+    };
+
+    // stores the number of values of each object, referenced as HDF5 datasets
+    struct ObjectStoreWriter {
+        objects: Arc<Mutex<BTreeMap<String, usize>>>,
+        label: String,
+    }
+
+    impl ObjectStoreWriter {
+        fn put(&mut self, name: &str, len: usize) -> DataContent {
+            let key = format!("{}/{name}", self.label);
+            self.objects.lock().unwrap().insert(key.clone(), len);
+            DataContent::Raw(format!("store.h5:/{key}"))
+        }
+    }
+
+    impl DataWriter for ObjectStoreWriter {
+        fn format(&self) -> Format {
+            Format::HDF
+        }
+
+        fn data_storage(&self) -> DataStorage {
+            DataStorage::Hdf5SingleFile
+        }
+
+        fn write_mesh(
+            &mut self,
+            points: &[f64],
+            cells: &[u64],
+        ) -> XdmfResult<(DataContent, DataContent)> {
+            self.write_named_mesh("mesh", points, cells)
+        }
+
+        fn write_named_mesh(
+            &mut self,
+            name: &str,
+            points: &[f64],
+            cells: &[u64],
+        ) -> XdmfResult<(DataContent, DataContent)> {
+            Ok((
+                self.put(&format!("{name}_points"), points.len()),
+                self.put(&format!("{name}_cells"), cells.len()),
+            ))
+        }
+
+        fn write_mesh_soa(
+            &mut self,
+            name: Option<&str>,
+            coordinates: [&[f64]; 3],
+            cells: &[u64],
+        ) -> XdmfResult<([DataContent; 3], DataContent)> {
+            let name = name.unwrap_or("mesh");
+            Ok((
+                ["x", "y", "z"]
+                    .map(|axis| self.put(&format!("{name}_{axis}"), coordinates[0].len())),
+                self.put(&format!("{name}_cells"), cells.len()),
+            ))
+        }
+
+        fn write_set(&mut self, name: &str, indices: &[u64]) -> XdmfResult<DataContent> {
+            Ok(self.put(name, indices.len()))
+        }
+
+        fn write_data(
+            &mut self,
+            name: &str,
+            _center: Center,
+            data: &Values,
+        ) -> XdmfResult<DataContent> {
+            Ok(self.put(name, data.len()))
+        }
+
+        fn write_data_streamed(
+            &mut self,
+            name: &str,
+            _center: Center,
+            values: &mut dyn Iterator<Item = f64>,
+            _len: usize,
+        ) -> XdmfResult<DataContent> {
+            Ok(self.put(name, values.count()))
+        }
+
+        fn write_data_initialize(&mut self, time: &str) -> XdmfResult<()> {
+            self.label = time.to_string();
+            Ok(())
+        }
+    }
+
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("custom");
+    let objects = Arc::new(Mutex::new(BTreeMap::new()));
+
+    let mut xdmf_writer = TimeSeriesWriter::new_with_data_writer(
+        &xdmf_file_path,
+        ObjectStoreWriter {
+            objects: objects.clone(),
+            label: "mesh".to_string(),
+        },
+    )
+    .unwrap()
+    .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+    .unwrap();
+
+    let point_data = vec![(
+        "temperature".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+    xdmf_writer
+        .write_data("0.5", Some(&point_data), None)
+        .unwrap();
+    xdmf_writer.close().unwrap();
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    let keys: Vec<_> = objects.lock().unwrap().keys().cloned().collect();
+    for key in &keys {
+        assert!(
+            read_xdmf.contains(&format!(">store.h5:/{key}</DataItem>")),
+            "{key} is not referenced"
+        );
+    }
+    assert!(read_xdmf.contains(r#"<Information Name="data_storage" Value="Hdf5SingleFile"/>"#));
+    assert_eq!(
+        keys,
+        vec!["0.5/temperature", "mesh/mesh_cells", "mesh/mesh_points"]
+    );
+}
+
+#[test]
+fn write_xdmf_cycle() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("cycle");
+
+    let data = |value: f64| -> xdmf::DataMap<'static> {
+        vec![(
+            "data".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![value].into()),
+        )]
+        .into_iter()
+        .collect()
+    };
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_points(&[0.0; 3])
+        .unwrap();
+
+    xdmf_writer
+        .write_data(&xdmf::WithCycle::new(0.5, 10), Some(&data(1.0)), None)
+        .unwrap();
+    xdmf_writer
+        .write_data(&1.0, Some(&data(2.0)), None)
+        .unwrap();
+
+    // the cycle is replaced when overwriting a time step
+    xdmf_writer
+        .write_data_overwrite(&xdmf::WithCycle::new(1.0, 21), Some(&data(3.0)), None)
+        .unwrap();
+    xdmf_writer
+        .write_point_data_streamed(
+            &xdmf::WithCycle::new(1.5, 30),
+            "data",
+            xdmf::DataAttribute::Scalar,
+            [4.0],
+            1,
+        )
+        .unwrap();
+    xdmf_writer.close().unwrap();
+
+    let xdmf = xdmf::xdmf_elements::Xdmf::read_from(std::io::BufReader::new(
+        std::fs::File::open(xdmf_file_path.with_extension("xdmf2")).unwrap(),
+    ))
+    .unwrap();
+
+    let cycles: Vec<_> = xdmf.domains[0].grids[0]
+        .grids
+        .iter()
+        .flatten()
+        .map(|grid| (grid.name.as_str(), grid.cycle()))
+        .collect();
+    assert_eq!(
+        cycles,
+        vec![
+            ("time_series-t0.5", Some(10)),
+            ("time_series-t1", Some(21)),
+            ("time_series-t1.5", Some(30)),
+        ]
+    );
+}
+
+#[test]
+fn write_xdmf_field_output_frequency() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("output_frequency");
+
+    let data = |value: f64| -> xdmf::DataMap<'static> {
+        ["temperature", "stress", "strain"]
+            .into_iter()
+            .map(|name| {
+                (
+                    name.to_string(),
+                    (xdmf::DataAttribute::Scalar, vec![value].into()),
+                )
+            })
+            .collect()
+    };
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_field_output_frequency("stress", 2)
+        .with_field_output_frequency("strain", 3)
+        .write_points(&[0.0; 3])
+        .unwrap();
+
+    for step in 0..5 {
+        xdmf_writer
+            .write_data(&step, Some(&data(f64::from(step))), None)
+            .unwrap();
+    }
+
+    // an overwritten time step keeps the fields that are due
+    xdmf_writer
+        .write_data_overwrite(&1, Some(&data(10.0)), None)
+        .unwrap();
+    xdmf_writer.close().unwrap();
+
+    let xdmf = xdmf::xdmf_elements::Xdmf::read_from(std::io::BufReader::new(
+        std::fs::File::open(xdmf_file_path.with_extension("xdmf2")).unwrap(),
+    ))
+    .unwrap();
+
+    let fields: Vec<Vec<_>> = xdmf.domains[0].grids[0]
+        .grids
+        .iter()
+        .flatten()
+        .map(|grid| {
+            grid.attributes
+                .iter()
+                .flatten()
+                .map(|attribute| attribute.name.as_str())
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        fields,
+        vec![
+            vec!["strain", "stress", "temperature"],
+            vec!["temperature"],
+            vec!["stress", "temperature"],
+            vec!["strain", "temperature"],
+            vec!["stress", "temperature"],
+        ]
+    );
+}
+
+#[test]
+fn write_xdmf_path_policy() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::Ascii)
+        .unwrap()
+        .with_path_policy(xdmf::PathPolicy::Absolute)
+        .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+        .unwrap()
+        .close()
+        .unwrap();
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    // the paths are absolute and use forward slashes on all platforms
+    let txt_files_dir = std::path::absolute(xdmf_file_path.with_extension("txt"))
+        .unwrap()
+        .to_string_lossy()
+        .replace(std::path::MAIN_SEPARATOR, "/");
+    assert!(read_xdmf.contains(&format!(
+        r#"<xi:include href="{txt_files_dir}/points.txt" parse="text"/>"#
+    )));
+    assert!(read_xdmf.contains(&format!(
+        r#"<xi:include href="{txt_files_dir}/cells.txt" parse="text"/>"#
+    )));
+
+    // the XDMF file can be moved without the files of the heavy data
+    let moved_xdmf_file_path = tmp_dir.path().join("moved/test_output.xdmf2");
+    std::fs::create_dir(tmp_dir.path().join("moved")).unwrap();
+    std::fs::rename(
+        xdmf_file_path.with_extension("xdmf2"),
+        &moved_xdmf_file_path,
+    )
+    .unwrap();
+
+    let report = xdmf::validate::validate_file(&moved_xdmf_file_path).unwrap();
+    assert!(report.issues().is_empty());
+}
+
+#[test]
+fn write_xdmf_reference_style() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let data: xdmf::DataMap = vec![(
+        "data".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    let write = |name: &str, data_storage, reference_style| {
+        let xdmf_file_path = tmp_dir.path().join(name);
+
+        let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, data_storage)
+            .unwrap()
+            .with_reference_style(reference_style)
+            .write_mesh(
+                &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+                (&[0, 1], &[xdmf::CellType::Edge]),
+            )
+            .unwrap();
+        for step in ["0", "1"] {
+            xdmf_writer.write_data(step, Some(&data), None).unwrap();
+        }
+        xdmf_writer.close().unwrap();
+
+        let report = xdmf::validate::validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+        assert!(report.issues().is_empty());
+
+        std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap()
+    };
+
+    // the grids contain the paths to the files of the mesh, instead of referencing the DataItems of the domain
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">
+                        <xi:include href="path_per_grid.txt/points.txt" parse="text"/>
+                    </DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem Dimensions="4" NumberType="UInt" Format="XML" Precision="4">
+                        <xi:include href="path_per_grid.txt/cells.txt" parse="text"/>
+                    </DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="data" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">
+                        <xi:include href="path_per_grid.txt/data_t_0_point_data_data.txt" parse="text"/>
+                    </DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t1" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">
+                        <xi:include href="path_per_grid.txt/points.txt" parse="text"/>
+                    </DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="1">
+                    <DataItem Dimensions="4" NumberType="UInt" Format="XML" Precision="4">
+                        <xi:include href="path_per_grid.txt/cells.txt" parse="text"/>
+                    </DataItem>
+                </Topology>
+                <Time Value="1"/>
+                <Attribute Name="data" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">
+                        <xi:include href="path_per_grid.txt/data_t_1_point_data_data.txt" parse="text"/>
+                    </DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+    </Domain>
+    <Information Name="data_storage" Value="Ascii"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+    pretty_assertions::assert_eq!(
+        expected_xdmf,
+        write(
+            "path_per_grid",
+            xdmf::DataStorage::Ascii,
+            xdmf::ReferenceStyle::HdfPathPerGrid
+        )
+    );
+
+    // with inline data storages the values are only repeated in every grid if requested
+    let written = write(
+        "inline_domain_reference",
+        xdmf::DataStorage::AsciiInline,
+        xdmf::ReferenceStyle::HdfPathPerGrid,
+    );
+    assert_eq!(written.matches("Reference=\"XML\"").count(), 4);
+
+    let written = write(
+        "inline_per_grid",
+        xdmf::DataStorage::AsciiInline,
+        xdmf::ReferenceStyle::InlinePerGrid,
+    );
+    assert!(!written.contains("Reference="));
+    assert_eq!(
+        written
+            .matches("1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0")
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn write_xdmf_transform() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    // coordinates in millimeters, written in meters, rotated by 90 degrees around z and moved by 1m in x direction
+    let transform = xdmf::Transform::default()
+        .with_scale(1e-3)
+        .with_matrix([[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]])
+        .with_offset([1.0, 0.0, 0.0]);
+
+    let points = [0.0, 0.0, 0.0, 1000.0, 0.0, 0.0, 0.0, 2000.0, 0.0];
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_transform(transform)
+        .write_mesh(&points, (&[0, 1, 2], &[xdmf::CellType::Triangle]))
+        .unwrap();
+
+    let temperature = |values: Vec<f64>| -> xdmf::DataMap<'static> {
+        vec![(
+            "temperature".to_string(),
+            (xdmf::DataAttribute::Scalar, values.into()),
+        )]
+        .into_iter()
+        .collect()
+    };
+
+    xdmf_writer
+        .write_data("0", Some(&temperature(vec![1.0, 2.0, 3.0])), None)
+        .unwrap();
+
+    // mesh updates are transformed as well
+    xdmf_writer
+        .write_mesh_update("1", &[0.0, 0.0, 500.0], (&[], &[]))
+        .unwrap();
+    xdmf_writer
+        .write_data("1", Some(&temperature(vec![4.0])), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Triangle" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t1" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords_t1"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity_t1"]</DataItem>
+                </Topology>
+                <Time Value="1"/>
+                <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">4.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="3 3" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 -1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="3" NumberType="UInt" Format="XML" Precision="4">0 1 2</DataItem>
+        <DataItem Name="coords_t1" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 0.0000000000000000e0 5.0000000000000000e-1</DataItem>
+        <DataItem Name="connectivity_t1" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    // image data can be scaled and translated
+    let xdmf_file_path = tmp_dir.path().join("test_output_image");
+    TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_transform(
+            xdmf::Transform::default()
+                .with_scale(1e-3)
+                .with_offset([0.0, 0.0, 1.0]),
+        )
+        .write_image_data([0.0, 0.0, 0.0], [500.0, 500.0, 250.0], [2, 2, 2])
+        .unwrap()
+        .close()
+        .unwrap();
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert!(read_xdmf.contains(
+        r#"<DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>"#
+    ));
+    assert!(read_xdmf.contains(
+        r#"<DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">2.5000000000000000e-1 5.0000000000000000e-1 5.0000000000000000e-1</DataItem>"#
+    ));
+
+    // but not rotated
+    assert_eq!(
+        TimeSeriesWriter::new(
+            tmp_dir.path().join("rotated_image"),
+            xdmf::DataStorage::AsciiInline
+        )
+        .unwrap()
+        .with_transform(transform)
+        .write_image_data([0.0, 0.0, 0.0], [1.0, 1.0, 1.0], [2, 2, 2])
+        .err()
+        .unwrap()
+        .to_string(),
+        "Image data can only be scaled by positive factors and translated, but the transformation rotates, mirrors or shears it"
+    );
+
+    // the transformation must be invertible
+    assert_eq!(
+        TimeSeriesWriter::new(
+            tmp_dir.path().join("invalid"),
+            xdmf::DataStorage::AsciiInline
+        )
+        .unwrap()
+        .with_transform(xdmf::Transform::default().with_scale(0.0))
+        .write_mesh(&points, (&[0, 1, 2], &[xdmf::CellType::Triangle]))
+        .err()
+        .unwrap()
+        .to_string(),
+        "Transformation must be invertible, but is Transform { scale: 0.0, matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]], offset: [0.0, 0.0, 0.0] }"
+    );
+}
+
+#[test]
+fn write_xdmf_subdivide_quadratic() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    // a quadratic triangle, with its midside points after its corner points
+    let points = [
+        0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 2.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0,
+    ];
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_subdivide_quadratic(true)
+        .write_mesh(&points, (&[0, 1, 2, 3, 4, 5], &[xdmf::CellType::Triangle6]))
+        .unwrap();
+
+    let point_data = vec![(
+        "temperature".to_string(),
+        (
+            xdmf::DataAttribute::Scalar,
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0].into(),
+        ),
+    )]
+    .into_iter()
+    .collect();
+
+    // the data of the quadratic cell is written for each of its linear cells
+    let cell_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![7.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+        xdmf_writer
+            .write_subset("corner", &[0])
+            .unwrap_err()
+            .to_string(),
+        "Subsets are not supported for meshes with subdivided quadratic cells"
+    );
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), Some(&cell_data))
+        .unwrap();
+
+    xdmf_writer.close().unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Triangle" NumberOfElements="4">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="6" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0 4.0000000000000000e0 5.0000000000000000e0 6.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="4" NumberType="Float" Format="XML" Precision="8">7.0000000000000000e0 7.0000000000000000e0 7.0000000000000000e0 7.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="6 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 2.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="12" NumberType="UInt" Format="XML" Precision="4">0 3 5 3 1 4 5 4 2 3 4 5</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    // the writers of multiple meshes and bodies write the cells as given
+    let create_writer = |name: &str| {
+        TimeSeriesWriter::new(tmp_dir.path().join(name), xdmf::DataStorage::AsciiInline)
+            .unwrap()
+            .with_subdivide_quadratic(true)
+    };
+    let error = "Subdividing quadratic cells is only supported when writing a single mesh, not for multiple meshes or bodies";
+
+    assert_eq!(
+        create_writer("multi_mesh")
+            .add_mesh(
+                "mesh",
+                &points,
+                (&[0, 1, 2, 3, 4, 5], &[xdmf::CellType::Triangle6])
+            )
+            .err()
+            .unwrap()
+            .to_string(),
+        error
+    );
+    assert_eq!(
+        create_writer("multi_body")
+            .into_multi_body_writer()
+            .err()
+            .unwrap()
+            .to_string(),
+        error
+    );
+}
+
+#[test]
+fn write_xdmf_builder() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let mut xdmf_writer = TimeSeriesWriter::builder()
+        .directory(tmp_dir.path().join("results"))
+        .basename("test_output")
+        .storage(xdmf::DataStorage::AsciiInline)
+        .precision(xdmf::FloatPrecision::Single)
+        .information("solver_version", "1.2.3")
+        .field_information("velocity", "units", "m/s")
+        .build()
+        .unwrap()
+        .write_mesh(&[0.0, 0.0, 0.0], (&[], &[]))
+        .unwrap();
+
+    let point_data = vec![
+        (
+            "velocity".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![0.1].into()),
+        ),
+        (
+            "material_id".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![3_u64].into()),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0.0", Some(&point_data), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0.0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0.0"/>
+                <Attribute Name="material_id" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="UInt" Format="XML" Precision="8">3</DataItem>
+                </Attribute>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <Information Name="units" Value="m/s"/>
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="4">1.0000000e-1</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+    <Information Name="solver_version" Value="1.2.3"/>
+</Xdmf>"#;
+
+    let read_xdmf =
+        std::fs::read_to_string(tmp_dir.path().join("results/test_output.xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_field_precision() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let mut xdmf_writer = TimeSeriesWriter::builder()
+        .directory(tmp_dir.path())
+        .basename("test_output")
+        .storage(xdmf::DataStorage::AsciiInline)
+        .precision(xdmf::FloatPrecision::Single)
+        .field_precision("displacement", xdmf::FloatPrecision::Double)
+        .build()
+        .unwrap()
+        .with_field_precision("pressure", xdmf::FloatPrecision::Double)
+        .write_mesh(&[0.0, 0.0, 0.0], (&[], &[]))
+        .unwrap();
+
+    let point_data = vec![
+        (
+            "displacement".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![0.1].into()),
+        ),
+        (
+            "pressure".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![0.1].into()),
+        ),
+        (
+            "velocity".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![0.1].into()),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0.0", Some(&point_data), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0.0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0.0"/>
+                <Attribute Name="displacement" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000001e-1</DataItem>
+                </Attribute>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">1.0000000000000001e-1</DataItem>
+                </Attribute>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="4">1.0000000e-1</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="1 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="1" NumberType="UInt" Format="XML" Precision="4">0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(tmp_dir.path().join("test_output.xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn write_xdmf_nan_policy() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_nan_policy(xdmf::NanPolicy::ReplaceWith(-1.0))
+        .write_mesh(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0], (&[], &[]))
+        .unwrap();
+
+    let point_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![f64::NAN, 2.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), None)
+        .unwrap();
+    xdmf_writer
+        .write_point_data_streamed(
+            "0",
+            "velocity",
+            xdmf::DataAttribute::Scalar,
+            [1.0, f64::INFINITY],
+            2,
+        )
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Polyvertex" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">-1.0000000000000000e0 2.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 -1.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="2 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="2" NumberType="UInt" Format="XML" Precision="4">0 1</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_nan_policy(xdmf::NanPolicy::Error)
+        .write_mesh(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0], (&[], &[]))
+        .unwrap();
+
+    assert_eq!(
+        xdmf_writer
+            .write_data("0", Some(&point_data), None)
+            .unwrap_err()
+            .to_string(),
+        "Data 'pressure' contains 1 NaN or infinite values"
+    );
+    assert_eq!(
+        xdmf_writer
+            .write_point_data_streamed(
+                "0",
+                "velocity",
+                xdmf::DataAttribute::Scalar,
+                [f64::NEG_INFINITY, f64::NAN],
+                2,
+            )
+            .unwrap_err()
+            .to_string(),
+        "Data 'velocity' contains 2 NaN or infinite values"
+    );
+
+    // the rejected field can be written again
+    xdmf_writer
+        .write_point_data_streamed("0", "velocity", xdmf::DataAttribute::Scalar, [1.0, 2.0], 2)
+        .unwrap();
+}
+
+#[test]
+fn write_xdmf_index_precision() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let mut xdmf_writer = TimeSeriesWriter::builder()
+        .directory(tmp_dir.path())
+        .basename("test_output")
+        .storage(xdmf::DataStorage::Base64Inline)
+        .index_precision(xdmf::IndexPrecision::U64)
+        .build()
+        .unwrap()
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            (&[0, 1, 2], &[xdmf::CellType::Triangle]),
+        )
+        .unwrap();
+
+    xdmf_writer
+        .write_set("inlet", SetType::Node, &[0, 1])
+        .unwrap();
+
+    let point_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0, 3.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Triangle" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8" Encoding="Base64">AAAAAAAA8D8AAAAAAAAAQAAAAAAAAAhA</DataItem>
+                </Attribute>
+                <Set Name="inlet" SetType="Node">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="set_inlet"]</DataItem>
+                </Set>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="3 3" NumberType="Float" Format="XML" Precision="8" Encoding="Base64">AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA8D8AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAADwPwAAAAAAAAAA</DataItem>
+        <DataItem Name="connectivity" Dimensions="3" NumberType="UInt" Format="XML" Precision="8" Encoding="Base64">AAAAAAAAAAABAAAAAAAAAAIAAAAAAAAA</DataItem>
+        <DataItem Name="set_inlet" Dimensions="2" NumberType="UInt" Format="XML" Precision="8" Encoding="Base64">AAAAAAAAAAABAAAAAAAAAA==</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="Base64Inline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(tmp_dir.path().join("test_output.xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+}
+
+#[test]
+fn builder_invalid() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let errors = [
+        TimeSeriesWriter::builder().build(),
+        TimeSeriesWriter::builder()
+            .directory(tmp_dir.path())
+            .basename("results/test_output")
+            .build(),
+        TimeSeriesWriter::builder()
+            .directory(tmp_dir.path())
+            .basename("test_output")
+            .hdf5_options(xdmf::Hdf5Options::default().with_compression(10))
+            .build(),
+        TimeSeriesWriter::builder()
+            .directory(tmp_dir.path())
+            .basename("test_output")
+            .hdf5_options(
+                xdmf::Hdf5Options::default()
+                    .with_file_naming(xdmf::Hdf5FileNaming::default().with_prefix("results/")),
+            )
+            .build(),
+        TimeSeriesWriter::builder()
+            .directory(tmp_dir.path())
+            .basename("test_output")
+            .transform(xdmf::Transform::default().with_offset([f64::INFINITY, 0.0, 0.0]))
+            .build(),
+        TimeSeriesWriter::builder()
+            .directory(tmp_dir.path())
+            .basename("test_output")
+            .storage(xdmf::DataStorage::Ascii)
+            .hdf5_group("sweep", "/case_1")
+            .build(),
+        TimeSeriesWriter::new_with_hdf5_group(tmp_dir.path().join("test_output"), "sweep", "/"),
+    ];
+
+    pretty_assertions::assert_eq!(
+        errors.map(|res| res.err().unwrap().to_string()),
+        [
+            "The basename of the files must be given",
+            "Basename 'results/test_output' must be a plain file name, use directory() for its location",
+            "Compression level must be between 0 and 9, but is 10",
+            "Prefix 'results/' of the HDF5 files must not contain path separators",
+            "Transformation must consist of finite values, but is Transform { scale: 1.0, matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]], offset: [inf, 0.0, 0.0] }",
+            "Writing into a group of an HDF5 file requires the Hdf5SingleFile DataStorage, but it is Ascii",
+            "Group of the HDF5 file must not be empty or the root group",
+        ]
+    );
+}
+
+#[test]
+fn written_times_and_fields() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::Ascii)
+        .unwrap()
+        .with_retention_policy(xdmf::RetentionPolicy::KeepLast(2))
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            (&[0, 1, 2], &[xdmf::CellType::Triangle]),
+        )
+        .unwrap();
+
+    assert_eq!(xdmf_writer.num_points(), 3);
+    assert_eq!(xdmf_writer.num_cells(), 1);
+    assert_eq!(
+        xdmf_writer.xdmf_file_name(),
+        xdmf_file_path.with_extension("xdmf2")
+    );
+    assert_eq!(
+        xdmf_writer.heavy_data_path(),
+        Some(xdmf_file_path.with_extension("txt").as_path())
+    );
+    assert!(xdmf_writer.times().is_empty());
+    assert_eq!(xdmf_writer.fields().count(), 0);
+
+    let point_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0, 3.0].into()),
+    )]
+    .into_iter()
+    .collect();
+    let cell_data = vec![(
+        "velocity".to_string(),
+        (xdmf::DataAttribute::Vector, vec![1.0, 2.0, 3.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    for time in ["0.0", "0.5", "1.0"] {
+        xdmf_writer
+            .write_data(time, Some(&point_data), Some(&cell_data))
+            .unwrap();
+    }
+
+    // the first time step is pruned
+    assert_eq!(xdmf_writer.times(), ["0.5", "1.0"]);
+    assert_eq!(
+        xdmf_writer.fields().collect::<Vec<_>>(),
+        vec![
+            (
+                "pressure",
+                xdmf::DataAttribute::Scalar,
+                xdmf::xdmf_elements::attribute::Center::Node
+            ),
+            (
+                "velocity",
+                xdmf::DataAttribute::Vector,
+                xdmf::xdmf_elements::attribute::Center::Cell
+            ),
+        ]
+    );
+
+    xdmf_writer.close().unwrap();
+}
+
+#[test]
+fn write_xdmf_name_collision_policy() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_name_collision_policy(xdmf::NameCollisionPolicy::SuffixCellData("_cell".into()))
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            (&[0, 1, 2], &[xdmf::CellType::Triangle]),
+        )
+        .unwrap();
+
+    let point_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![1.0, 2.0, 3.0].into()),
+    )]
+    .into_iter()
+    .collect();
+    let cell_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![4.0].into()),
+    )]
+    .into_iter()
+    .collect();
+
+    xdmf_writer
+        .write_data("0", Some(&point_data), Some(&cell_data))
+        .unwrap();
+
+    // the collision is also detected when the data is streamed in separate calls
+    xdmf_writer
+        .write_cell_data_streamed("1", "velocity", xdmf::DataAttribute::Scalar, [5.0], 1)
+        .unwrap();
+    xdmf_writer
+        .write_point_data_streamed(
+            "1",
+            "velocity",
+            xdmf::DataAttribute::Scalar,
+            [6.0, 7.0, 8.0],
+            3,
+        )
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Triangle" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 2.0000000000000000e0 3.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="pressure_cell" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">4.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+            <Grid Name="time_series-t1" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Triangle" NumberOfElements="1">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="1"/>
+                <Attribute Name="velocity_cell" AttributeType="Scalar" Center="Cell">
+                    <DataItem Dimensions="1" NumberType="Float" Format="XML" Precision="8">5.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="velocity" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="3" NumberType="Float" Format="XML" Precision="8">6.0000000000000000e0 7.0000000000000000e0 8.0000000000000000e0</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="3 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="3" NumberType="UInt" Format="XML" Precision="4">0 1 2</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .with_name_collision_policy(xdmf::NameCollisionPolicy::Error)
+        .write_mesh(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            (&[0, 1, 2], &[xdmf::CellType::Triangle]),
+        )
+        .unwrap();
+
+    assert_eq!(
+        xdmf_writer
+            .write_data("0", Some(&point_data), Some(&cell_data))
+            .unwrap_err()
+            .to_string(),
+        "Data 'pressure' is written as point- and cell-data of time step '0'"
+    );
+}
+
+#[test]
+fn write_xdmf_mesh_with_quality() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("test_output");
+
+    // a unit square and a stretched triangle
+    let mut xdmf_writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+        .unwrap()
+        .write_mesh_with_quality(
+            &[
+                0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0, 4.0, 0.0, 0.0,
+            ],
+            (
+                &[0, 1, 2, 3, 1, 4, 2],
+                &[xdmf::CellType::Quadrilateral, xdmf::CellType::Triangle],
+            ),
+            &[
+                xdmf::QualityMetric::Volume,
+                xdmf::QualityMetric::AspectRatio,
+            ],
+        )
+        .unwrap();
+
+    let point_data = vec![(
+        "pressure".to_string(),
+        (xdmf::DataAttribute::Scalar, vec![0.0; 5].into()),
+    )]
+    .into_iter()
+    .collect();
+    xdmf_writer
+        .write_data("0", Some(&point_data), None)
+        .unwrap();
+
+    let expected_xdmf = r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="time_series" GridType="Collection" CollectionType="Temporal">
+            <Grid Name="time_series-t0" GridType="Uniform">
+                <Geometry GeometryType="XYZ">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="coords"]</DataItem>
+                </Geometry>
+                <Topology TopologyType="Mixed" NumberOfElements="2">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="connectivity"]</DataItem>
+                </Topology>
+                <Time Value="0"/>
+                <Attribute Name="pressure" AttributeType="Scalar" Center="Node">
+                    <DataItem Dimensions="5" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+                </Attribute>
+                <Attribute Name="aspect_ratio" AttributeType="Scalar" Center="Cell">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="static_cell_aspect_ratio"]</DataItem>
+                </Attribute>
+                <Attribute Name="cell_volume" AttributeType="Scalar" Center="Cell">
+                    <DataItem Reference="XML">/Xdmf/Domain/DataItem[@Name="static_cell_cell_volume"]</DataItem>
+                </Attribute>
+            </Grid>
+        </Grid>
+        <DataItem Name="coords" Dimensions="5 3" NumberType="Float" Format="XML" Precision="8">0.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0 1.0000000000000000e0 0.0000000000000000e0 4.0000000000000000e0 0.0000000000000000e0 0.0000000000000000e0</DataItem>
+        <DataItem Name="connectivity" Dimensions="9" NumberType="UInt" Format="XML" Precision="4">5 0 1 2 3 4 1 4 2</DataItem>
+        <DataItem Name="static_cell_aspect_ratio" Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 3.1622776601683795e0</DataItem>
+        <DataItem Name="static_cell_cell_volume" Dimensions="2" NumberType="Float" Format="XML" Precision="8">1.0000000000000000e0 1.5000000000000000e0</DataItem>
+    </Domain>
+    <Information Name="data_storage" Value="AsciiInline"/>
+    <Information Name="version" Value="0.1.3"/>
+</Xdmf>"#;
+
+    let read_xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    pretty_assertions::assert_eq!(expected_xdmf, read_xdmf);
+
+    let (cell_type, connectivity) =
+        xdmf::CellType::polyhedron(&[&[0, 1, 2], &[0, 1, 3], &[0, 2, 3], &[1, 2, 3]]);
+    assert_eq!(
+        TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::AsciiInline)
+            .unwrap()
+            .write_mesh_with_quality(
+                &[0.0; 12],
+                (&connectivity, &[cell_type]),
+                &[xdmf::QualityMetric::Volume],
+            )
+            .err()
+            .unwrap()
+            .to_string(),
+        "Quality metrics are not supported for cells of type 'Polyhedron(17)'"
+    );
+}
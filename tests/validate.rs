@@ -0,0 +1,828 @@
+use temp_dir::TempDir;
+use xdmf::{
+    ParallelTimeSeriesWriter, TimeSeriesWriter, validate::validate_file,
+    xdmf_elements::set::SetType,
+};
+
+fn data_storages() -> Vec<xdmf::DataStorage> {
+    let mut data_storages = vec![
+        xdmf::DataStorage::Ascii,
+        xdmf::DataStorage::AsciiGz,
+        xdmf::DataStorage::AsciiInline,
+        xdmf::DataStorage::Base64Inline,
+    ];
+
+    if xdmf::is_hdf5_enabled() {
+        data_storages.push(xdmf::DataStorage::Hdf5SingleFile);
+        data_storages.push(xdmf::DataStorage::Hdf5MultipleFiles);
+    }
+
+    data_storages
+}
+
+fn data(values: Vec<f64>) -> xdmf::DataMap<'static> {
+    vec![(
+        "data".to_string(),
+        (xdmf::DataAttribute::Scalar, values.into()),
+    )]
+    .into_iter()
+    .collect()
+}
+
+// the files written by this crate must be valid
+#[test]
+fn validate_written_files() {
+    let tmp_dir = TempDir::new().unwrap();
+    let coords = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+
+    for data_storage in data_storages() {
+        let xdmf_file_path = tmp_dir.path().join(format!("{data_storage:?}"));
+
+        let mut writer = TimeSeriesWriter::new(&xdmf_file_path, data_storage)
+            .unwrap()
+            .write_mesh(
+                &coords,
+                (
+                    &[0, 1, 0, 1, 2],
+                    &[xdmf::CellType::Edge, xdmf::CellType::Triangle],
+                ),
+            )
+            .unwrap();
+
+        for time in ["0.0", "1.0"] {
+            writer
+                .write_data(
+                    time,
+                    Some(&data(vec![1.0, 2.0, 3.0])),
+                    Some(&data(vec![4.0, 5.0])),
+                )
+                .unwrap();
+        }
+
+        let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+    }
+}
+
+#[test]
+fn validate_written_parallel_files() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("parallel");
+
+    for rank in 0..2 {
+        let builder = TimeSeriesWriter::builder()
+            .directory(tmp_dir.path())
+            .basename("parallel")
+            .storage(xdmf::DataStorage::Ascii);
+
+        ParallelTimeSeriesWriter::new(builder, rank, 2)
+            .unwrap()
+            .write_mesh(&[0.0; 3], (&[], &[]))
+            .unwrap()
+            .write_data("0.0", Some(&data(vec![1.0])), None)
+            .unwrap();
+    }
+
+    let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert!(report.issues().is_empty(), "{:?}", report.issues());
+
+    let report = validate_file(tmp_dir.path().join("parallel_r1.xdmf2")).unwrap();
+    assert!(report.issues().is_empty(), "{:?}", report.issues());
+
+    // a missing file of a rank is detected
+    std::fs::remove_file(tmp_dir.path().join("parallel_r1.xdmf2")).unwrap();
+
+    let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert!(!report.is_valid());
+    assert!(
+        report
+            .errors()
+            .all(|issue| issue.message == "Included file 'parallel_r1.xdmf2' does not exist")
+    );
+}
+
+#[test]
+fn validate_written_multiple_meshes() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    for data_storage in data_storages() {
+        let xdmf_file_path = tmp_dir
+            .path()
+            .join(format!("multiple_meshes_{data_storage:?}"));
+
+        let mut writer = TimeSeriesWriter::new(&xdmf_file_path, data_storage)
+            .unwrap()
+            .add_mesh("fluid", &[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+            .unwrap();
+        writer.add_mesh("structure", &[0.0; 3], (&[], &[])).unwrap();
+
+        for time in ["0.0", "1.0"] {
+            writer
+                .write_data("fluid", time, Some(&data(vec![1.0, 2.0])), None)
+                .unwrap();
+            writer
+                .write_data("structure", time, None, Some(&data(vec![3.0])))
+                .unwrap();
+        }
+
+        let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+    }
+}
+
+#[test]
+fn validate_written_multiple_bodies() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    for data_storage in data_storages() {
+        let xdmf_file_path = tmp_dir
+            .path()
+            .join(format!("multiple_bodies_{data_storage:?}"));
+
+        let mut writer = TimeSeriesWriter::new(&xdmf_file_path, data_storage)
+            .unwrap()
+            .into_multi_body_writer()
+            .unwrap();
+
+        for (time, x) in [("0.0", 0.0), ("1.0", 1.0)] {
+            writer
+                .write_body(
+                    "rod",
+                    time,
+                    &[x, 0.0, 0.0, x + 1.0, 0.0, 0.0],
+                    (&[0, 1], &[xdmf::CellType::Edge]),
+                    Some(&data(vec![1.0, 2.0])),
+                    None,
+                )
+                .unwrap();
+        }
+        writer
+            .write_body(
+                "ball",
+                "1.0",
+                &[0.0; 3],
+                (&[], &[]),
+                None,
+                Some(&data(vec![3.0])),
+            )
+            .unwrap();
+        writer.close().unwrap();
+
+        let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+    }
+}
+
+#[test]
+fn validate_modified_files() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("modified");
+
+    TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::Ascii)
+        .unwrap()
+        .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+        .unwrap()
+        .write_data("0.0", Some(&data(vec![1.0, 2.0])), None)
+        .unwrap();
+
+    // e.g. the simulation crashed while writing
+    std::fs::write(
+        tmp_dir.path().join("modified.txt/points.txt"),
+        "0.0 0.0 0.0",
+    )
+    .unwrap();
+    std::fs::remove_file(
+        tmp_dir
+            .path()
+            .join("modified.txt/data_t_0.0_point_data_data.txt"),
+    )
+    .unwrap();
+
+    let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    let issues: Vec<_> = report.issues().iter().map(ToString::to_string).collect();
+
+    pretty_assertions::assert_eq!(
+        issues,
+        vec![
+            "error: /Xdmf/Domain/DataItem[@Name=\"coords\"]: Number of values (3) does not match the Dimensions (6)",
+            "error: /Xdmf/Domain/Grid[@Name=\"time_series\"]/Grid[@Name=\"time_series-t0.0\"]/Attribute[@Name=\"data\"]/DataItem: Included file 'modified.txt/data_t_0.0_point_data_data.txt' does not exist",
+        ]
+    );
+
+    // files that are not XDMF can not be validated
+    validate_file(tmp_dir.path().join("modified.txt/cells.txt")).unwrap_err();
+}
+
+#[test]
+fn validate_written_mesh_soa() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    for data_storage in data_storages() {
+        let xdmf_file_path = tmp_dir.path().join(format!("mesh_soa_{data_storage:?}"));
+
+        TimeSeriesWriter::new(&xdmf_file_path, data_storage)
+            .unwrap()
+            .write_mesh_soa(
+                &[0.0, 1.0, 0.0],
+                &[0.0, 0.0, 1.0],
+                &[0.0; 3],
+                (&[0, 1, 2], &[xdmf::CellType::Triangle]),
+            )
+            .unwrap()
+            .write_data("0.0", Some(&data(vec![1.0, 2.0, 3.0])), None)
+            .unwrap();
+
+        let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+    }
+}
+
+#[test]
+fn validate_written_image_data() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    for data_storage in data_storages() {
+        let xdmf_file_path = tmp_dir.path().join(format!("image_data_{data_storage:?}"));
+
+        TimeSeriesWriter::new(&xdmf_file_path, data_storage)
+            .unwrap()
+            .write_image_data([0.0; 3], [1.0; 3], [3, 2, 2])
+            .unwrap()
+            .write_data(
+                "0.0",
+                Some(&data(vec![1.0; 12])),
+                Some(&data(vec![1.0, 2.0])),
+            )
+            .unwrap();
+
+        let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+    }
+}
+
+#[test]
+fn validate_written_sets() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    for data_storage in data_storages() {
+        let xdmf_file_path = tmp_dir.path().join(format!("sets_{data_storage:?}"));
+
+        let mut writer = TimeSeriesWriter::new(&xdmf_file_path, data_storage)
+            .unwrap()
+            .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+            .unwrap();
+
+        writer.write_set("inlet", SetType::Node, &[0]).unwrap();
+        writer.write_set("wall", SetType::Cell, &[0]).unwrap();
+        writer
+            .write_data("0.0", Some(&data(vec![1.0, 2.0])), None)
+            .unwrap();
+        writer
+            .write_set_data("0.0", "wall", &data(vec![3.0]))
+            .unwrap();
+
+        let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+    }
+}
+
+#[test]
+fn validate_written_absolute_paths() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    for data_storage in data_storages() {
+        let xdmf_file_path = tmp_dir
+            .path()
+            .join(format!("absolute_paths_{data_storage:?}"));
+
+        TimeSeriesWriter::builder()
+            .directory(tmp_dir.path())
+            .basename(format!("absolute_paths_{data_storage:?}"))
+            .storage(data_storage)
+            .path_policy(xdmf::PathPolicy::Absolute)
+            .build()
+            .unwrap()
+            .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+            .unwrap()
+            .write_data("0.0", Some(&data(vec![1.0, 2.0])), None)
+            .unwrap();
+
+        let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+    }
+}
+
+// relative references survive moving the case folder, other moves are fixed by rebasing the references
+#[test]
+fn validate_rebased_files() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    for data_storage in data_storages() {
+        let case_dir = tmp_dir.path().join(format!("case_{data_storage:?}"));
+        std::fs::create_dir(&case_dir).unwrap();
+
+        let mut writer = TimeSeriesWriter::new(case_dir.join("results"), data_storage)
+            .unwrap()
+            .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+            .unwrap();
+        writer
+            .write_data("0.0", Some(&data(vec![1.0, 2.0])), None)
+            .unwrap();
+        let heavy_data_name = writer
+            .heavy_data_path()
+            .map(|path| path.file_name().unwrap().to_owned());
+        drop(writer);
+
+        let renamed_dir = tmp_dir.path().join(format!("renamed_{data_storage:?}"));
+        std::fs::rename(&case_dir, &renamed_dir).unwrap();
+
+        let xdmf_file_path = renamed_dir.join("results.xdmf2");
+        let report = validate_file(&xdmf_file_path).unwrap();
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+
+        // the XDMF file is moved into a subfolder
+        std::fs::create_dir(renamed_dir.join("views")).unwrap();
+        let moved_file_path = renamed_dir.join("views").join("results.xdmf2");
+        std::fs::rename(&xdmf_file_path, &moved_file_path).unwrap();
+        xdmf::rebase_references(&moved_file_path, "", "..").unwrap();
+
+        let report = validate_file(&moved_file_path).unwrap();
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+
+        // the heavy data is renamed
+        if let Some(heavy_data_name) = heavy_data_name {
+            std::fs::rename(
+                renamed_dir.join(&heavy_data_name),
+                renamed_dir.join("heavy_data"),
+            )
+            .unwrap();
+
+            let num_rebased = xdmf::rebase_references(
+                &moved_file_path,
+                std::path::Path::new("..").join(&heavy_data_name),
+                "../heavy_data",
+            )
+            .unwrap();
+            assert!(num_rebased > 0);
+
+            let report = validate_file(&moved_file_path).unwrap();
+            assert!(report.issues().is_empty(), "{:?}", report.issues());
+        }
+    }
+}
+
+#[test]
+fn verify_written_checksums() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    for data_storage in data_storages() {
+        let xdmf_file_path = tmp_dir.path().join(format!("checksums_{data_storage:?}"));
+
+        let cell_data = vec![(
+            "material".to_string(),
+            (xdmf::DataAttribute::Scalar, vec![3_u8].into()),
+        )]
+        .into_iter()
+        .collect();
+
+        let mut writer = TimeSeriesWriter::new(&xdmf_file_path, data_storage)
+            .unwrap()
+            .with_checksums(true)
+            .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+            .unwrap();
+        writer
+            .write_data("0.0", Some(&data(vec![1.0, 0.1])), Some(&cell_data))
+            .unwrap();
+        writer
+            .write_point_data_streamed(
+                "0.0",
+                "streamed",
+                xdmf::DataAttribute::Scalar,
+                [-1.0, 1e-300],
+                2,
+            )
+            .unwrap();
+        drop(writer);
+
+        let xdmf_file_path = xdmf_file_path.with_extension("xdmf2");
+        let xdmf_file = std::fs::read_to_string(&xdmf_file_path).unwrap();
+        assert_eq!(
+            xdmf_file.matches("<Information Name=\"checksum\"").count(),
+            3
+        );
+
+        let report = xdmf::validate::verify_checksums_file(&xdmf_file_path).unwrap();
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+    }
+
+    // modified heavy data is detected
+    let data_file = tmp_dir
+        .path()
+        .join("checksums_Ascii.txt")
+        .join("data_t_0.0_point_data_data.txt");
+    std::fs::write(&data_file, "1.0 0.2").unwrap();
+
+    let report =
+        xdmf::validate::verify_checksums_file(tmp_dir.path().join("checksums_Ascii.xdmf2"))
+            .unwrap();
+    assert_eq!(report.errors().count(), 1, "{:?}", report.issues());
+    assert!(
+        report.issues()[0]
+            .message
+            .contains("does not match the written checksum xxh64:"),
+        "{:?}",
+        report.issues()
+    );
+
+    // the algorithm is named by the prefix of the checksum
+    let xdmf_file_path = tmp_dir.path().join("checksums_AsciiInline.xdmf2");
+    let xdmf_file = std::fs::read_to_string(&xdmf_file_path).unwrap();
+    std::fs::write(&xdmf_file_path, xdmf_file.replace("xxh64:", "crc32:")).unwrap();
+
+    let report = xdmf::validate::verify_checksums_file(&xdmf_file_path).unwrap();
+    assert_eq!(report.errors().count(), 3, "{:?}", report.issues());
+    assert_eq!(
+        report.issues()[0].message,
+        "Checksum algorithm 'crc32' is not supported"
+    );
+}
+
+// time steps whose heavy data is missing after a crash are removed
+#[test]
+fn validate_pruned_files() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    for time_list in [false, true] {
+        let xdmf_file_path = tmp_dir.path().join(format!("pruned_{time_list}"));
+
+        let mut writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::Ascii).unwrap();
+        if time_list {
+            writer = writer.with_time_list();
+        }
+
+        let mut writer = writer
+            .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+            .unwrap();
+        for time in ["0.0", "1.0", "2.0"] {
+            writer
+                .write_data(time, Some(&data(vec![1.0, 2.0])), None)
+                .unwrap();
+        }
+        drop(writer);
+
+        let xdmf_file_path = xdmf_file_path.with_extension("xdmf2");
+        assert!(
+            xdmf::prune_dangling_steps(&xdmf_file_path)
+                .unwrap()
+                .is_empty()
+        );
+
+        std::fs::remove_file(
+            tmp_dir
+                .path()
+                .join(format!("pruned_{time_list}.txt"))
+                .join("data_t_1.0_point_data_data.txt"),
+        )
+        .unwrap();
+        assert!(!validate_file(&xdmf_file_path).unwrap().is_valid());
+
+        assert_eq!(
+            xdmf::prune_dangling_steps(&xdmf_file_path).unwrap(),
+            ["time_series-t1.0"]
+        );
+
+        let report = validate_file(&xdmf_file_path).unwrap();
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+
+        let xdmf_file = std::fs::read_to_string(&xdmf_file_path).unwrap();
+        assert!(!xdmf_file.contains("t_1.0"));
+        if time_list {
+            assert!(xdmf_file.contains(
+                "Dimensions=\"2\" NumberType=\"Float\" Format=\"XML\" Precision=\"8\">0.0 2.0<"
+            ));
+        }
+    }
+}
+
+#[test]
+fn validate_merged_files() {
+    for data_storage in data_storages() {
+        let tmp_dir = TempDir::new().unwrap();
+
+        // each rank writes its own file into a subdirectory, without coordination
+        let rank_files: Vec<_> = (0..3)
+            .map(|rank| {
+                let xdmf_file_path = tmp_dir.path().join(format!("rank_{rank}")).join("case");
+                std::fs::create_dir_all(xdmf_file_path.parent().unwrap()).unwrap();
+
+                let mut writer = TimeSeriesWriter::new(&xdmf_file_path, data_storage)
+                    .unwrap()
+                    .write_mesh(
+                        &[f64::from(rank), 0.0, 0.0, f64::from(rank) + 1.0, 0.0, 0.0],
+                        (&[0, 1], &[xdmf::CellType::Edge]),
+                    )
+                    .unwrap();
+                for time in ["0.0", "1.0"] {
+                    writer
+                        .write_data(time, Some(&data(vec![1.0, 2.0])), None)
+                        .unwrap();
+                }
+
+                xdmf_file_path.with_extension("xdmf2")
+            })
+            .collect();
+
+        let master_file_path = tmp_dir.path().join("master.xdmf2");
+        xdmf::merge::merge_files(&rank_files, &master_file_path).unwrap();
+
+        let report = validate_file(&master_file_path).unwrap();
+        assert!(
+            report.issues().is_empty(),
+            "{data_storage:?}: {:?}",
+            report.issues()
+        );
+
+        let master_file = std::fs::read_to_string(&master_file_path).unwrap();
+        assert_eq!(master_file.matches("CollectionType=\"Spatial\"").count(), 2);
+        assert!(master_file.contains("time_series-t1.0-rank2"));
+    }
+
+    // the files must be next to the master file
+    let tmp_dir = TempDir::new().unwrap();
+    assert_eq!(
+        xdmf::merge::merge_files(&["case.xdmf2"], tmp_dir.path().join("master.xdmf2"))
+            .unwrap_err()
+            .to_string(),
+        format!(
+            "File 'case.xdmf2' must be in the directory of the master file '{}' or in a subdirectory",
+            tmp_dir.path().join("master.xdmf2").display()
+        )
+    );
+}
+
+#[cfg(feature = "hdf5")]
+#[test]
+fn validate_written_atomic_steps() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    let mut writer = TimeSeriesWriter::builder()
+        .directory(tmp_dir.path())
+        .basename("atomic_steps")
+        .storage(xdmf::DataStorage::Hdf5MultipleFiles)
+        .hdf5_options(xdmf::Hdf5Options::default().with_atomic_steps())
+        .build()
+        .unwrap()
+        .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+        .unwrap();
+
+    for time in ["0.0", "1.0"] {
+        writer
+            .write_data(time, Some(&data(vec![1.0, 2.0])), None)
+            .unwrap();
+    }
+    // data added to a written time step replaces its file as well
+    writer
+        .write_data("1.0", None, Some(&data(vec![3.0])))
+        .unwrap();
+    drop(writer);
+
+    let report = validate_file(tmp_dir.path().join("atomic_steps.xdmf2")).unwrap();
+    assert!(report.issues().is_empty(), "{:?}", report.issues());
+
+    // no temporary files are left behind
+    for entry in std::fs::read_dir(tmp_dir.path().join("atomic_steps.h5")).unwrap() {
+        let file_name = entry.unwrap().file_name();
+        assert!(
+            !file_name.to_string_lossy().ends_with(".tmp"),
+            "{file_name:?}"
+        );
+    }
+
+    assert_eq!(
+        TimeSeriesWriter::builder()
+            .directory(tmp_dir.path())
+            .basename("atomic_single_file")
+            .storage(xdmf::DataStorage::Hdf5SingleFile)
+            .hdf5_options(xdmf::Hdf5Options::default().with_atomic_steps())
+            .build()
+            .err()
+            .unwrap()
+            .to_string(),
+        "Atomic steps are only supported by the Hdf5MultipleFiles DataStorage"
+    );
+}
+
+// the cases of a parameter sweep share an HDF5 file, each in its own group
+#[cfg(feature = "hdf5")]
+#[test]
+fn validate_written_hdf5_groups() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    for case in 0..2 {
+        TimeSeriesWriter::builder()
+            .directory(tmp_dir.path())
+            .basename(format!("case_{case}"))
+            .storage(xdmf::DataStorage::Hdf5SingleFile)
+            .hdf5_group("sweep", format!("/case_{case}"))
+            .build()
+            .unwrap()
+            .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+            .unwrap()
+            .write_data("0.0", Some(&data(vec![1.0, f64::from(case)])), None)
+            .unwrap();
+    }
+
+    for case in 0..2 {
+        let xdmf_file_path = tmp_dir.path().join(format!("case_{case}.xdmf2"));
+        let report = validate_file(&xdmf_file_path).unwrap();
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+
+        let xdmf_file = std::fs::read_to_string(xdmf_file_path).unwrap();
+        assert!(xdmf_file.contains(&format!("sweep.h5:/case_{case}/mesh/points")));
+    }
+
+    // the group of an existing case is not overwritten
+    assert_eq!(
+        TimeSeriesWriter::new_with_hdf5_group(
+            tmp_dir.path().join("case_2"),
+            tmp_dir.path().join("sweep"),
+            "case_1",
+        )
+        .err()
+        .unwrap()
+        .to_string(),
+        format!(
+            "Group '/case_1' already exists in HDF5 file '{}'",
+            tmp_dir.path().join("sweep.h5").display()
+        )
+    );
+}
+
+#[cfg(feature = "hdf5")]
+#[test]
+fn validate_written_time_major_layout() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("time_major");
+
+    let mut xdmf_writer = TimeSeriesWriter::builder()
+        .directory(tmp_dir.path())
+        .basename("time_major")
+        .storage(xdmf::DataStorage::Hdf5SingleFile)
+        .hdf5_options(
+            xdmf::Hdf5Options::default()
+                .with_layout(xdmf::Hdf5Layout::TimeMajorDatasets)
+                .with_compression(4),
+        )
+        .build()
+        .unwrap()
+        .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+        .unwrap();
+
+    for (time, value) in [("0.0", 1.0), ("1.0", 2.0), ("2.0", 3.0)] {
+        xdmf_writer
+            .write_data(time, Some(&data(vec![value; 2])), Some(&data(vec![value])))
+            .unwrap();
+    }
+
+    let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert!(report.issues().is_empty(), "{:?}", report.issues());
+
+    // the grid of each time step selects its row of the dataset of the field
+    let xdmf = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+    assert_eq!(xdmf.matches(r#"ItemType="HyperSlab""#).count(), 6);
+    assert!(xdmf.contains(">2 0 1 1 1 2</DataItem>"));
+    assert!(xdmf.contains(">time_major.h5:data/point_data/data</DataItem>"));
+}
+
+#[test]
+fn validate_written_integer_fields() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    for data_storage in data_storages() {
+        let xdmf_file_path = tmp_dir
+            .path()
+            .join(format!("integer_fields_{data_storage:?}"));
+
+        let cell_data = vec![
+            (
+                "material_id".to_string(),
+                (xdmf::DataAttribute::Scalar, vec![-1_i64].into()),
+            ),
+            (
+                xdmf::GHOST_TYPE_NAME.to_string(),
+                (
+                    xdmf::DataAttribute::Scalar,
+                    xdmf::Values::ghost_type(&[true]),
+                ),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        TimeSeriesWriter::new(&xdmf_file_path, data_storage)
+            .unwrap()
+            .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+            .unwrap()
+            .write_data("0.0", None, Some(&cell_data))
+            .unwrap();
+
+        let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+    }
+}
+
+#[test]
+fn validate_written_time_list() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("time_list");
+
+    let mut writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::Ascii)
+        .unwrap()
+        .with_time_list()
+        .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+        .unwrap();
+
+    for time in ["0.0", "1.0"] {
+        writer
+            .write_data(time, Some(&data(vec![1.0, 2.0])), None)
+            .unwrap();
+    }
+
+    let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+    assert!(report.issues().is_empty(), "{:?}", report.issues());
+}
+
+#[test]
+fn validate_written_derived_fields() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    for data_storage in data_storages() {
+        let xdmf_file_path = tmp_dir
+            .path()
+            .join(format!("derived_fields_{data_storage:?}"));
+
+        let mut writer = TimeSeriesWriter::new(&xdmf_file_path, data_storage)
+            .unwrap()
+            .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+            .unwrap();
+
+        writer
+            .register_derived_field(
+                "data_squared",
+                xdmf::DataAttribute::Scalar,
+                xdmf::xdmf_elements::attribute::Center::Node,
+                "$0 * $0",
+                &["data"],
+            )
+            .unwrap();
+
+        writer
+            .write_data("0.0", Some(&data(vec![1.0, 2.0])), None)
+            .unwrap();
+
+        let report = validate_file(xdmf_file_path.with_extension("xdmf2")).unwrap();
+
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+    }
+}
+
+#[test]
+fn validate_written_builder_options() {
+    let tmp_dir = TempDir::new().unwrap();
+
+    for data_storage in data_storages() {
+        let basename = format!("builder_{data_storage:?}");
+
+        TimeSeriesWriter::builder()
+            .directory(tmp_dir.path())
+            .basename(&basename)
+            .storage(data_storage)
+            .precision(xdmf::FloatPrecision::Single)
+            .hdf5_options(
+                xdmf::Hdf5Options::default()
+                    .with_compression(4)
+                    .with_shuffle(),
+            )
+            .time_list(true)
+            .build()
+            .unwrap()
+            .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+            .unwrap()
+            .write_data("0.0", Some(&data(vec![1.0, 2.0])), Some(&data(vec![3.0])))
+            .unwrap();
+
+        let report = validate_file(tmp_dir.path().join(basename).with_extension("xdmf2")).unwrap();
+
+        assert!(report.issues().is_empty(), "{:?}", report.issues());
+    }
+}
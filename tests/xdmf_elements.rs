@@ -1,36 +1,43 @@
+use std::sync::Arc;
+
 use quick_xml::Writer;
 use xdmf::xdmf_elements::{
     Domain, XDMF_TAG, Xdmf,
     attribute::{Attribute, AttributeType, Center},
-    data_item::{DataItem, NumberType},
+    data_item::{DataItem, NumberType, Reference},
     dimensions::Dimensions,
     geometry::{Geometry, GeometryType},
     grid::{CollectionType, Grid, Time},
     topology::{Topology, TopologyType},
 };
 
+fn reference(source: &DataItem) -> xdmf::XdmfResult<DataItem> {
+    Ok(Reference::builder().source(source).build()?.into())
+}
+
 #[test]
 fn basic_grid() {
     let xdmf = Xdmf::new(Domain::new(Grid::new_uniform(
         "Grid_1",
         Geometry {
             geometry_type: GeometryType::XYZ,
-            data_item: DataItem {
+            data_items: vec![DataItem {
                 dimensions: Some(Dimensions(vec![4, 3])),
                 data: "0 0 0 0 1 0 1 1 0 1 0 0.5".into(),
                 number_type: Some(NumberType::Float),
                 ..Default::default()
-            },
+            }],
         },
         Topology {
             topology_type: TopologyType::Triangle,
             number_of_elements: "2".into(),
-            data_item: DataItem {
+            dimensions: None,
+            data_item: Some(DataItem {
                 dimensions: Some(Dimensions(vec![6])),
                 number_type: Some(NumberType::Int),
                 data: "0 1 2 0 2 3".into(),
                 ..Default::default()
-            },
+            }),
         },
     )));
 
@@ -75,44 +82,46 @@ fn hierarchical_tree_grid() {
                         "sub_grid_1",
                         Geometry {
                             geometry_type: GeometryType::XYZ,
-                            data_item: DataItem {
+                            data_items: vec![DataItem {
                                 dimensions: Some(Dimensions(vec![5, 3])),
                                 data: "0 1 0 0 1.5 0 0.5 1.5 0.5 1 1.5 0 1 1 0".into(),
                                 number_type: Some(NumberType::Float),
                                 ..Default::default()
-                            },
+                            }],
                         },
                         Topology {
                             topology_type: TopologyType::Triangle,
                             number_of_elements: "2".into(),
-                            data_item: DataItem {
+                            dimensions: None,
+                            data_item: Some(DataItem {
                                 dimensions: Some(Dimensions(vec![6])),
                                 number_type: Some(NumberType::Int),
                                 data: "0 1 2 2 3 4".into(),
                                 ..Default::default()
-                            },
+                            }),
                         },
                     ),
                     Grid::new_uniform(
                         "sub_grid_2",
                         Geometry {
                             geometry_type: GeometryType::XYZ,
-                            data_item: DataItem {
+                            data_items: vec![DataItem {
                                 dimensions: Some(Dimensions(vec![6, 3])),
                                 data: "1 1.5 0 1 1 0 1 0 0 1.3 1.5 0 1.3 1 0 1.3 0 0".into(),
                                 number_type: Some(NumberType::Float),
                                 ..Default::default()
-                            },
+                            }],
                         },
                         Topology {
                             topology_type: TopologyType::Quadrilateral,
                             number_of_elements: "2".into(),
-                            data_item: DataItem {
+                            dimensions: None,
+                            data_item: Some(DataItem {
                                 dimensions: Some(Dimensions(vec![8])),
                                 number_type: Some(NumberType::Int),
                                 data: "0 1 4 3 1 2 5 4".into(),
                                 ..Default::default()
-                            },
+                            }),
                         },
                     ),
                 ]),
@@ -121,22 +130,23 @@ fn hierarchical_tree_grid() {
                 "Grid_1",
                 Geometry {
                     geometry_type: GeometryType::XYZ,
-                    data_item: DataItem {
+                    data_items: vec![DataItem {
                         dimensions: Some(Dimensions(vec![5, 3])),
                         data: "0 0 0 0 1 0 1 1 0 1 0 0 0.5 1.5 0.5".into(),
                         number_type: Some(NumberType::Float),
                         ..Default::default()
-                    },
+                    }],
                 },
                 Topology {
                     topology_type: TopologyType::Mixed,
                     number_of_elements: "2".into(),
-                    data_item: DataItem {
+                    dimensions: None,
+                    data_item: Some(DataItem {
                         dimensions: Some(Dimensions(vec![9])),
                         number_type: Some(NumberType::Int),
                         data: "5 0 1 2 3 4 1 2 4".into(),
                         ..Default::default()
-                    },
+                    }),
                 },
             ),
         ]),
@@ -200,22 +210,23 @@ fn mixed_grid() {
         "Grid_1",
         Geometry {
             geometry_type: GeometryType::XYZ,
-            data_item: DataItem {
+            data_items: vec![DataItem {
                 dimensions: Some(Dimensions(vec![5, 3])),
                 data: "0 0 0 0 1 0 1 1 0 1 0 0 0.5 1.5 0.5".into(),
                 number_type: Some(NumberType::Float),
                 ..Default::default()
-            },
+            }],
         },
         Topology {
             topology_type: TopologyType::Mixed,
             number_of_elements: "2".into(),
-            data_item: DataItem {
+            dimensions: None,
+            data_item: Some(DataItem {
                 dimensions: Some(Dimensions(vec![9])),
                 number_type: Some(NumberType::Int),
                 data: "5 0 1 2 3 4 1 2 4".into(),
                 ..Default::default()
-            },
+            }),
         },
     )));
 
@@ -258,66 +269,69 @@ fn spatial_collection_grid() {
                 "sub_grid_1",
                 Geometry {
                     geometry_type: GeometryType::XYZ,
-                    data_item: DataItem {
+                    data_items: vec![DataItem {
                         dimensions: Some(Dimensions(vec![5, 3])),
                         data: "0 1 0 0 1.5 0 0.5 1.5 0.5 1 1.5 0 1 1 0".into(),
                         number_type: Some(NumberType::Float),
                         ..Default::default()
-                    },
+                    }],
                 },
                 Topology {
                     topology_type: TopologyType::Triangle,
                     number_of_elements: "2".into(),
-                    data_item: DataItem {
+                    dimensions: None,
+                    data_item: Some(DataItem {
                         dimensions: Some(Dimensions(vec![6])),
                         number_type: Some(NumberType::Int),
                         data: "0 1 2 2 3 4".into(),
                         ..Default::default()
-                    },
+                    }),
                 },
             ),
             Grid::new_uniform(
                 "sub_grid_2",
                 Geometry {
                     geometry_type: GeometryType::XYZ,
-                    data_item: DataItem {
+                    data_items: vec![DataItem {
                         dimensions: Some(Dimensions(vec![6, 3])),
                         data: "1 1.5 0 1 1 0 1 0 0 1.3 1.5 0 1.3 1 0 1.3 0 0".into(),
                         number_type: Some(NumberType::Float),
                         ..Default::default()
-                    },
+                    }],
                 },
                 Topology {
                     topology_type: TopologyType::Quadrilateral,
                     number_of_elements: "2".into(),
-                    data_item: DataItem {
+                    dimensions: None,
+                    data_item: Some(DataItem {
                         dimensions: Some(Dimensions(vec![8])),
                         number_type: Some(NumberType::Int),
                         data: "0 1 4 3 1 2 5 4".into(),
                         ..Default::default()
-                    },
+                    }),
                 },
             ),
             Grid::new_uniform(
                 "Grid_1",
                 Geometry {
                     geometry_type: GeometryType::XYZ,
-                    data_item: DataItem {
+                    data_items: vec![DataItem {
                         dimensions: Some(Dimensions(vec![5, 3])),
                         data: "0 0 0 0 1 0 1 1 0 1 0 0 0.5 1.5 0.5".into(),
                         number_type: Some(NumberType::Float),
                         ..Default::default()
-                    },
+                    }],
                 },
                 Topology {
                     topology_type: TopologyType::Mixed,
                     number_of_elements: "2".into(),
-                    data_item: DataItem {
+                    dimensions: None,
+                    data_item: Some(DataItem {
                         dimensions: Some(Dimensions(vec![9])),
                         number_type: Some(NumberType::Int),
                         data: "5 0 1 2 3 4 1 2 4".into(),
                         ..Default::default()
-                    },
+                    }),
                 },
             ),
         ]),
@@ -393,6 +407,7 @@ fn temporal_collection_grid() {
     ];
 
     let xdmf = Xdmf::new(Domain {
+        name: None,
         grids: vec![Grid::new_collection(
             "temporal_collection_grid",
             CollectionType::Temporal,
@@ -401,131 +416,171 @@ fn temporal_collection_grid() {
                     name: "Grid_t1".into(),
                     geometry: Some(Geometry {
                         geometry_type: GeometryType::XYZ,
-                        data_item: DataItem::new_reference(&data_items[0], "/Xdmf/Domain/DataItem"),
+                        data_items: vec![reference(&data_items[0]).unwrap()],
                     }),
                     topology: Some(Topology {
                         topology_type: TopologyType::Mixed,
                         number_of_elements: "2".into(),
-                        data_item: DataItem::new_reference(&data_items[1], "/Xdmf/Domain/DataItem"),
+                        dimensions: None,
+                        data_item: Some(reference(&data_items[1]).unwrap()),
                     }),
                     grid_type: xdmf::xdmf_elements::grid::GridType::Uniform,
-                    time: Some(Time {
-                        value: "1.0".into(),
-                    }),
-                    attributes: Some(vec![
-                        Attribute {
-                            name: String::from("Pressure"),
-                            attribute_type: AttributeType::Scalar,
-                            center: Center::Node,
-                            data_items: vec![DataItem {
-                                dimensions: Some(Dimensions(vec![5])),
-                                data: "1 2 2 3 9".into(),
-                                number_type: Some(NumberType::Float),
-                                ..Default::default()
-                            }],
-                        },
-                        Attribute {
-                            name: String::from("Temperature"),
-                            attribute_type: AttributeType::Scalar,
-                            center: Center::Cell,
-                            data_items: vec![DataItem {
-                                dimensions: Some(Dimensions(vec![2])),
-                                data: "1 2".into(),
-                                number_type: Some(NumberType::Float),
-                                ..Default::default()
-                            }],
-                        },
-                    ]),
+                    time: Some(Time::new("1.0")),
+                    information: vec![],
+                    attributes: Some(
+                        vec![
+                            Attribute {
+                                name: String::from("Pressure"),
+                                attribute_type: AttributeType::Scalar,
+                                center: Center::Node,
+                                information: vec![],
+                                data_items: vec![DataItem {
+                                    dimensions: Some(Dimensions(vec![5])),
+                                    data: "1 2 2 3 9".into(),
+                                    number_type: Some(NumberType::Float),
+                                    ..Default::default()
+                                }],
+                            },
+                            Attribute {
+                                name: String::from("Temperature"),
+                                attribute_type: AttributeType::Scalar,
+                                center: Center::Cell,
+                                information: vec![],
+                                data_items: vec![DataItem {
+                                    dimensions: Some(Dimensions(vec![2])),
+                                    data: "1 2".into(),
+                                    number_type: Some(NumberType::Float),
+                                    ..Default::default()
+                                }],
+                            },
+                        ]
+                        .into_iter()
+                        .map(Arc::new)
+                        .collect(),
+                    ),
                     collection_type: None,
                     grids: None,
+                    includes: None,
+                    sets: None,
+                    section: None,
+                    reference: None,
+                    data_item: None,
+                    reference_path: None,
                 },
                 Grid {
                     name: "Grid_t2".into(),
                     geometry: Some(Geometry {
                         geometry_type: GeometryType::XYZ,
-                        data_item: DataItem::new_reference(&data_items[0], "/Xdmf/Domain/DataItem"),
+                        data_items: vec![reference(&data_items[0]).unwrap()],
                     }),
                     topology: Some(Topology {
                         topology_type: TopologyType::Mixed,
                         number_of_elements: "2".into(),
-                        data_item: DataItem::new_reference(&data_items[1], "/Xdmf/Domain/DataItem"),
+                        dimensions: None,
+                        data_item: Some(reference(&data_items[1]).unwrap()),
                     }),
                     grid_type: xdmf::xdmf_elements::grid::GridType::Uniform,
-                    time: Some(Time {
-                        value: "2.0".into(),
-                    }),
-                    attributes: Some(vec![
-                        Attribute {
-                            name: String::from("Pressure"),
-                            attribute_type: AttributeType::Scalar,
-                            center: Center::Node,
-                            data_items: vec![DataItem {
-                                dimensions: Some(Dimensions(vec![5])),
-                                data: "1 2 3 4 7".into(),
-                                number_type: Some(NumberType::Float),
-                                ..Default::default()
-                            }],
-                        },
-                        Attribute {
-                            name: String::from("Temperature"),
-                            attribute_type: AttributeType::Scalar,
-                            center: Center::Cell,
-                            data_items: vec![DataItem {
-                                dimensions: Some(Dimensions(vec![2])),
-                                data: "2 3".into(),
-                                number_type: Some(NumberType::Float),
-                                ..Default::default()
-                            }],
-                        },
-                    ]),
+                    time: Some(Time::new("2.0")),
+                    information: vec![],
+                    attributes: Some(
+                        vec![
+                            Attribute {
+                                name: String::from("Pressure"),
+                                attribute_type: AttributeType::Scalar,
+                                center: Center::Node,
+                                information: vec![],
+                                data_items: vec![DataItem {
+                                    dimensions: Some(Dimensions(vec![5])),
+                                    data: "1 2 3 4 7".into(),
+                                    number_type: Some(NumberType::Float),
+                                    ..Default::default()
+                                }],
+                            },
+                            Attribute {
+                                name: String::from("Temperature"),
+                                attribute_type: AttributeType::Scalar,
+                                center: Center::Cell,
+                                information: vec![],
+                                data_items: vec![DataItem {
+                                    dimensions: Some(Dimensions(vec![2])),
+                                    data: "2 3".into(),
+                                    number_type: Some(NumberType::Float),
+                                    ..Default::default()
+                                }],
+                            },
+                        ]
+                        .into_iter()
+                        .map(Arc::new)
+                        .collect(),
+                    ),
                     collection_type: None,
                     grids: None,
+                    includes: None,
+                    sets: None,
+                    section: None,
+                    reference: None,
+                    data_item: None,
+                    reference_path: None,
                 },
                 Grid {
                     name: "Grid_t3".into(),
                     geometry: Some(Geometry {
                         geometry_type: GeometryType::XYZ,
-                        data_item: DataItem::new_reference(&data_items[0], "/Xdmf/Domain/DataItem"),
+                        data_items: vec![reference(&data_items[0]).unwrap()],
                     }),
                     topology: Some(Topology {
                         topology_type: TopologyType::Mixed,
                         number_of_elements: "2".into(),
-                        data_item: DataItem::new_reference(&data_items[1], "/Xdmf/Domain/DataItem"),
+                        dimensions: None,
+                        data_item: Some(reference(&data_items[1]).unwrap()),
                     }),
                     grid_type: xdmf::xdmf_elements::grid::GridType::Uniform,
-                    time: Some(Time {
-                        value: "3.0".into(),
-                    }),
-                    attributes: Some(vec![
-                        Attribute {
-                            name: String::from("Pressure"),
-                            attribute_type: AttributeType::Scalar,
-                            center: Center::Node,
-                            data_items: vec![DataItem {
-                                dimensions: Some(Dimensions(vec![5])),
-                                data: "3 2 2 3 8".into(),
-                                number_type: Some(NumberType::Float),
-                                ..Default::default()
-                            }],
-                        },
-                        Attribute {
-                            name: String::from("Temperature"),
-                            attribute_type: AttributeType::Scalar,
-                            center: Center::Cell,
-                            data_items: vec![DataItem {
-                                dimensions: Some(Dimensions(vec![2])),
-                                data: "3 4".into(),
-                                number_type: Some(NumberType::Float),
-                                ..Default::default()
-                            }],
-                        },
-                    ]),
+                    time: Some(Time::new("3.0")),
+                    information: vec![],
+                    attributes: Some(
+                        vec![
+                            Attribute {
+                                name: String::from("Pressure"),
+                                attribute_type: AttributeType::Scalar,
+                                center: Center::Node,
+                                information: vec![],
+                                data_items: vec![DataItem {
+                                    dimensions: Some(Dimensions(vec![5])),
+                                    data: "3 2 2 3 8".into(),
+                                    number_type: Some(NumberType::Float),
+                                    ..Default::default()
+                                }],
+                            },
+                            Attribute {
+                                name: String::from("Temperature"),
+                                attribute_type: AttributeType::Scalar,
+                                center: Center::Cell,
+                                information: vec![],
+                                data_items: vec![DataItem {
+                                    dimensions: Some(Dimensions(vec![2])),
+                                    data: "3 4".into(),
+                                    number_type: Some(NumberType::Float),
+                                    ..Default::default()
+                                }],
+                            },
+                        ]
+                        .into_iter()
+                        .map(Arc::new)
+                        .collect(),
+                    ),
                     collection_type: None,
                     grids: None,
+                    includes: None,
+                    sets: None,
+                    section: None,
+                    reference: None,
+                    data_item: None,
+                    reference_path: None,
                 },
             ]),
         )],
         data_items,
+        includes: Vec::new(),
     });
 
     // Create an in-memory buffer to serialize to
@@ -601,3 +656,296 @@ fn temporal_collection_grid() {
     // );
     // file_writer.write_serializable(XDMF_TAG, &xdmf).unwrap();
 }
+
+#[test]
+fn write_invalid_references() {
+    let coords = DataItem {
+        name: Some("coords".into()),
+        ..Default::default()
+    };
+
+    let mut grid = Grid::new_uniform(
+        "mesh",
+        Geometry {
+            geometry_type: GeometryType::XYZ,
+            data_items: vec![reference(&coords).unwrap()],
+        },
+        Topology {
+            topology_type: TopologyType::Mixed,
+            number_of_elements: "0".into(),
+            dimensions: None,
+            data_item: None,
+        },
+    );
+
+    let mut xdmf = Xdmf::new(Domain::new(grid.clone()));
+    assert_eq!(
+        xdmf.write_to(&mut Vec::new()).unwrap_err().to_string(),
+        "Reference to DataItem 'coords' can not be resolved within the domain"
+    );
+
+    xdmf.domains[0].data_items = vec![coords.clone(), coords];
+    assert_eq!(
+        xdmf.write_to(&mut Vec::new()).unwrap_err().to_string(),
+        "Name 'coords' of DataItem is not unique within the domain"
+    );
+
+    xdmf.domains[0].data_items.pop();
+    xdmf.write_to(&mut Vec::new()).unwrap();
+
+    // references nested in function items are checked as well
+    grid.geometry.as_mut().unwrap().data_items = vec![DataItem::new_function(
+        "$0",
+        Dimensions(vec![1]),
+        vec![
+            reference(&DataItem {
+                name: Some("other".into()),
+                ..Default::default()
+            })
+            .unwrap(),
+        ],
+    )];
+    xdmf.domains[0].grids = vec![grid];
+    assert_eq!(
+        xdmf.write_to(&mut Vec::new()).unwrap_err().to_string(),
+        "Reference to DataItem 'other' can not be resolved within the domain"
+    );
+}
+
+#[test]
+fn read_written_files_roundtrip() {
+    let tmp_dir = temp_dir::TempDir::new().unwrap();
+
+    let data = |num_entities: usize| -> xdmf::DataMap<'static> {
+        vec![
+            (
+                "temperature".to_string(),
+                (xdmf::DataAttribute::Scalar, vec![1.0; num_entities].into()),
+            ),
+            (
+                "stress".to_string(),
+                (
+                    xdmf::DataAttribute::Tensor6,
+                    vec![0.5; 6 * num_entities].into(),
+                ),
+            ),
+            (
+                "material".to_string(),
+                (
+                    xdmf::DataAttribute::Scalar,
+                    vec![1_i64; num_entities].into(),
+                ),
+            ),
+        ]
+        .into_iter()
+        .collect()
+    };
+
+    for data_storage in [
+        xdmf::DataStorage::Ascii,
+        xdmf::DataStorage::AsciiInline,
+        xdmf::DataStorage::Base64Inline,
+    ] {
+        for time_list in [false, true] {
+            let xdmf_file_path = tmp_dir.path().join(format!("{data_storage:?}_{time_list}"));
+
+            let mut xdmf_writer = xdmf::TimeSeriesWriter::new(&xdmf_file_path, data_storage)
+                .unwrap()
+                .with_information("solver", "test")
+                .with_field_information("temperature", "units", "K");
+            if time_list {
+                xdmf_writer = xdmf_writer.with_time_list();
+            }
+
+            let mut xdmf_writer = xdmf_writer
+                .write_mesh(
+                    &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+                    (&[0, 1, 2], &[xdmf::CellType::Triangle]),
+                )
+                .unwrap();
+            xdmf_writer
+                .write_set("boundary", xdmf::xdmf_elements::set::SetType::Node, &[0, 1])
+                .unwrap();
+            xdmf_writer
+                .register_derived_field(
+                    "temperature_sum",
+                    xdmf::DataAttribute::Scalar,
+                    Center::Node,
+                    "$0 + $1",
+                    &["temperature", "temperature"],
+                )
+                .unwrap();
+
+            xdmf_writer
+                .write_data(
+                    &xdmf::WithCycle::new(0.0, 1),
+                    Some(&data(3)),
+                    Some(&data(1)),
+                )
+                .unwrap();
+            xdmf_writer
+                .write_mesh_update(&1.0, &[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+                .unwrap();
+            xdmf_writer.write_data(&1.0, Some(&data(2)), None).unwrap();
+            xdmf_writer.close().unwrap();
+
+            // reading and writing a file again results in the same file
+            let written = std::fs::read_to_string(xdmf_file_path.with_extension("xdmf2")).unwrap();
+            let mut rewritten = Vec::new();
+            Xdmf::read_from(written.as_bytes())
+                .unwrap()
+                .write_to(&mut rewritten)
+                .unwrap();
+
+            pretty_assertions::assert_eq!(String::from_utf8(rewritten).unwrap(), written);
+        }
+    }
+}
+
+#[test]
+fn read_optional_attributes() {
+    // written by another tool, relying on the defaults of XDMF and using `Type` instead of e.g. `TopologyType`
+    let xdmf_text = r#"<?xml version="1.0" ?>
+<!DOCTYPE Xdmf SYSTEM "Xdmf.dtd" []>
+<Xdmf Version="2.0">
+    <Domain>
+        <Grid Name="mesh">
+            <Topology Type="Triangle" NumberOfElements="1">
+                <DataItem Dimensions="3" NumberType="Int" Format="XML">0 1 2</DataItem>
+            </Topology>
+            <Geometry>
+                <DataItem Dimensions="3 3" Format="HDF">mesh.h5:/coords</DataItem>
+            </Geometry>
+            <Attribute Name="temperature">
+                <Information Name="units"/>
+                <DataItem Dimensions="3" Format="XML">1 2 3</DataItem>
+            </Attribute>
+            <Attribute Name="velocity" Type="Vector" Center="Cell">
+                <DataItem Dimensions="1 3" Format="XML">1 2 3</DataItem>
+            </Attribute>
+        </Grid>
+    </Domain>
+</Xdmf>"#;
+
+    let xdmf = Xdmf::read_from(xdmf_text.as_bytes()).unwrap();
+    assert_eq!(xdmf.version, "2.0");
+    assert_eq!(xdmf.xinclude_url, "http://www.w3.org/2001/XInclude");
+
+    let grid = &xdmf.domains[0].grids[0];
+    assert_eq!(grid.grid_type, xdmf::xdmf_elements::grid::GridType::Uniform);
+    assert_eq!(
+        grid.topology.as_ref().unwrap().topology_type,
+        TopologyType::Triangle
+    );
+    assert_eq!(
+        grid.geometry.as_ref().unwrap().geometry_type,
+        GeometryType::XYZ
+    );
+
+    let attributes = grid.attributes.as_ref().unwrap();
+    assert_eq!(
+        (attributes[0].attribute_type, attributes[0].center),
+        (AttributeType::Scalar, Center::Node)
+    );
+    assert_eq!(attributes[0].information[0].value, "");
+    assert_eq!(
+        (attributes[1].attribute_type, attributes[1].center),
+        (AttributeType::Vector, Center::Cell)
+    );
+
+    // the missing attributes are written explicitly
+    let mut written = Vec::new();
+    xdmf.write_to(&mut written).unwrap();
+    pretty_assertions::assert_eq!(
+        String::from_utf8(written).unwrap(),
+        r#"
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain>
+        <Grid Name="mesh" GridType="Uniform">
+            <Geometry GeometryType="XYZ">
+                <DataItem Dimensions="3 3" Format="HDF">mesh.h5:/coords</DataItem>
+            </Geometry>
+            <Topology TopologyType="Triangle" NumberOfElements="1">
+                <DataItem Dimensions="3" NumberType="Int" Format="XML">0 1 2</DataItem>
+            </Topology>
+            <Attribute Name="temperature" AttributeType="Scalar" Center="Node">
+                <Information Name="units" Value=""/>
+                <DataItem Dimensions="3" Format="XML">1 2 3</DataItem>
+            </Attribute>
+            <Attribute Name="velocity" AttributeType="Vector" Center="Cell">
+                <DataItem Dimensions="1 3" Format="XML">1 2 3</DataItem>
+            </Attribute>
+        </Grid>
+    </Domain>
+</Xdmf>"#
+    );
+}
+
+#[test]
+fn write_xml_header() {
+    let xdmf = Xdmf::default();
+
+    let mut written = Vec::new();
+    xdmf.write_to_with_header(
+        &mut written,
+        &xdmf::XmlHeader::default()
+            .with_declaration(true)
+            .with_doctype(true),
+    )
+    .unwrap();
+    let written = String::from_utf8(written).unwrap();
+    pretty_assertions::assert_eq!(
+        written,
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE Xdmf SYSTEM "Xdmf.dtd">
+<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude">
+    <Domain/>
+</Xdmf>"#
+    );
+
+    // the header is skipped when reading
+    Xdmf::read_from(written.as_bytes()).unwrap();
+
+    let mut written = Vec::new();
+    xdmf.write_to_with_header(
+        &mut written,
+        &xdmf::XmlHeader::default()
+            .with_declaration(true)
+            .with_encoding("ISO-8859-1"),
+    )
+    .unwrap();
+    assert!(
+        String::from_utf8(written)
+            .unwrap()
+            .starts_with("<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\n<Xdmf")
+    );
+}
+
+#[test]
+fn write_xml_indent() {
+    let mut xdmf = Xdmf::default();
+    xdmf.information
+        .push(xdmf::xdmf_elements::Information::new("version", "1"));
+
+    let write = |header: xdmf::XmlHeader| {
+        let mut written = Vec::new();
+        xdmf.write_to_with_header(&mut written, &header).unwrap();
+        String::from_utf8(written).unwrap()
+    };
+
+    let written = write(xdmf::XmlHeader::default().with_indent(b'\t', 1));
+    pretty_assertions::assert_eq!(
+        written,
+        "\n<Xdmf Version=\"2.0\" xmlns:xi=\"http://www.w3.org/2001/XInclude\">\n\t<Domain/>\n\t<Information Name=\"version\" Value=\"1\"/>\n</Xdmf>"
+    );
+
+    let written = write(xdmf::XmlHeader::default().with_compact(true));
+    pretty_assertions::assert_eq!(
+        written,
+        r#"<Xdmf Version="2.0" xmlns:xi="http://www.w3.org/2001/XInclude"><Domain/><Information Name="version" Value="1"/></Xdmf>"#
+    );
+
+    // compact files are read like indented ones
+    let read = Xdmf::read_from(written.as_bytes()).unwrap();
+    assert_eq!(read.information, xdmf.information);
+}
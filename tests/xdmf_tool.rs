@@ -0,0 +1,122 @@
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+
+use temp_dir::TempDir;
+use xdmf::TimeSeriesWriter;
+
+fn data(values: Vec<f64>) -> xdmf::DataMap<'static> {
+    vec![(
+        "data".to_string(),
+        (xdmf::DataAttribute::Scalar, values.into()),
+    )]
+    .into_iter()
+    .collect()
+}
+
+#[test]
+fn xdmf_tool_summary() {
+    let tmp_dir = TempDir::new().unwrap();
+    let xdmf_file_path = tmp_dir.path().join("summary");
+
+    let mut writer = TimeSeriesWriter::new(&xdmf_file_path, xdmf::DataStorage::Ascii)
+        .unwrap()
+        .write_mesh(&[0.0; 6], (&[0, 1], &[xdmf::CellType::Edge]))
+        .unwrap();
+    writer
+        .write_set("inlet", xdmf::xdmf_elements::set::SetType::Node, &[0])
+        .unwrap();
+
+    for (time, cycle) in [("0.0", 0), ("1.0", 25)] {
+        writer
+            .write_data(
+                &xdmf::WithCycle::new(time, cycle),
+                Some(&data(vec![1.0, 2.0])),
+                None,
+            )
+            .unwrap();
+    }
+    writer
+        .write_set_data("0.0", "inlet", &data(vec![3.0]))
+        .unwrap();
+    writer.close().unwrap();
+
+    // e.g. the simulation crashed while writing
+    std::fs::remove_file(
+        tmp_dir
+            .path()
+            .join("summary.txt/data_t_1.0_point_data_data.txt"),
+    )
+    .unwrap();
+
+    let xdmf_file_name = xdmf_file_path.with_extension("xdmf2");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_xdmf-tool"))
+        .arg(&xdmf_file_name)
+        .arg("--validate")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+
+    pretty_assertions::assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!(
+            "File: {}
+Information:
+    data_storage: Ascii
+    version: 0.1.3
+Grids:
+    time_series (Collection Temporal, 2 time steps: 0.0 .. 1.0)
+        time_series-t0.0 (Uniform, time 0.0, cycle 0)
+            Topology: Mixed, 1 elements
+            Geometry: XYZ
+            Attribute 'data' (Scalar, Node): 2
+            Set 'inlet' (Node)
+                Attribute 'data' (Scalar, Node): 1
+        time_series-t1.0 (Uniform, time 1.0, cycle 25)
+            Topology: Mixed, 1 elements
+            Geometry: XYZ
+            Attribute 'data' (Scalar, Node): 2
+            Set 'inlet' (Node)
+Heavy data files:
+    summary.txt/cells.txt
+    summary.txt/data_t_0.0_point_data_data.txt
+    summary.txt/data_t_0.0_point_data_data_set_inlet.txt
+    summary.txt/data_t_1.0_point_data_data.txt (missing)
+    summary.txt/points.txt
+    summary.txt/set_inlet.txt
+Validation:
+    error: /Xdmf/Domain/Grid[@Name=\"time_series\"]/Grid[@Name=\"time_series-t1.0\"]/Attribute[@Name=\"data\"]/DataItem: Included file 'summary.txt/data_t_1.0_point_data_data.txt' does not exist
+    1 errors, 0 warnings
+",
+            xdmf_file_name.display()
+        )
+    );
+}
+
+#[test]
+fn xdmf_tool_invalid_arguments() {
+    let output = Command::new(env!("CARGO_BIN_EXE_xdmf-tool"))
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .starts_with("The XDMF file must be given")
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_xdmf-tool"))
+        .arg("does_not_exist.xdmf2")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .starts_with("Error: ")
+    );
+}